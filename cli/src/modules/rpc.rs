@@ -38,13 +38,17 @@ impl Rpc {
                 tprintln!(ctx, "ok");
             }
             RpcApiOps::GetMetrics => {
-                let result = rpc.get_metrics(true, true, true, true).await?;
+                let result = rpc.get_metrics(true, true, true, true, true, true).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetServerInfo => {
                 let result = rpc.get_server_info_call(GetServerInfoRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetServerCapabilities => {
+                let result = rpc.get_server_capabilities().await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetSyncStatus => {
                 let result = rpc.get_sync_status_call(GetSyncStatusRequest {}).await?;
                 self.println(&ctx, result);
@@ -62,7 +66,7 @@ impl Rpc {
             //     self.println(&ctx, result);
             // }
             RpcApiOps::GetPeerAddresses => {
-                let result = rpc.get_peer_addresses_call(GetPeerAddressesRequest {}).await?;
+                let result = rpc.get_peer_addresses_call(GetPeerAddressesRequest::default()).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetSink => {
@@ -75,9 +79,7 @@ impl Rpc {
             // }
             RpcApiOps::GetMempoolEntries => {
                 // TODO
-                let result = rpc
-                    .get_mempool_entries_call(GetMempoolEntriesRequest { include_orphan_pool: true, filter_transaction_pool: true })
-                    .await?;
+                let result = rpc.get_mempool_entries_call(GetMempoolEntriesRequest::new(true, true)).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetConnectedPeerInfo => {
@@ -103,7 +105,7 @@ impl Rpc {
                 }
                 let hash = argv.remove(0);
                 let hash = RpcHash::from_hex(hash.as_str())?;
-                let result = rpc.get_block_call(GetBlockRequest { hash, include_transactions: true }).await?;
+                let result = rpc.get_block_call(GetBlockRequest::new(hash, true)).await?;
                 self.println(&ctx, result);
             }
             // RpcApiOps::GetSubnetwork => {
@@ -143,7 +145,7 @@ impl Rpc {
                     return Err(Error::custom("Please specify at least one address"));
                 }
                 let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
-                let result = rpc.get_utxos_by_addresses_call(GetUtxosByAddressesRequest { addresses }).await?;
+                let result = rpc.get_utxos_by_addresses_call(GetUtxosByAddressesRequest::new(addresses)).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetBalanceByAddress => {
@@ -170,18 +172,81 @@ impl Rpc {
             }
             RpcApiOps::Ban => {
                 if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
+                    return Err(Error::custom("Please specify peer IP address or network (CIDR notation)"));
                 }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.ban_call(BanRequest { ip }).await?;
+                let (ip, prefix_length) = parse_ip_network(&argv.remove(0))?;
+                let mut request = BanRequest::new(ip);
+                if let Some(prefix_length) = prefix_length {
+                    request = request.with_prefix_length(prefix_length);
+                }
+                if !argv.is_empty() {
+                    let duration = argv
+                        .remove(0)
+                        .parse::<u64>()
+                        .map_err(|_| Error::custom("Could not parse the ban duration (in milliseconds)"))?;
+                    request = request.with_duration(duration);
+                }
+                if !argv.is_empty() {
+                    request = request.with_reason(argv.join(" "));
+                }
+                let result = rpc.ban_call(request).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::Unban => {
                 if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
+                    return Err(Error::custom("Please specify peer IP address or network (CIDR notation)"));
+                }
+                let (ip, prefix_length) = parse_ip_network(&argv.remove(0))?;
+                let mut request = UnbanRequest::new(ip);
+                if let Some(prefix_length) = prefix_length {
+                    request = request.with_prefix_length(prefix_length);
+                }
+                let result = rpc.unban_call(request).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBans => {
+                let result = rpc.get_bans_call(GetBansRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBlockProcessingStatus => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the block processing ticket"));
+                }
+                let ticket =
+                    argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse the block processing ticket"))?;
+                let result = rpc.get_block_processing_status_call(GetBlockProcessingStatusRequest::new(ticket)).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::StartJob => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the job kind (utxoindex-resync or txindex-resync)"));
+                }
+                let kind = argv.remove(0).parse::<RpcJobKind>().map_err(Error::custom)?;
+                let result = rpc.start_job_call(StartJobRequest::new(kind)).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetJobStatus => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the job id"));
+                }
+                let job_id = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse the job id"))?;
+                let result = rpc.get_job_status_call(GetJobStatusRequest::new(job_id)).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetNotificationStats => {
+                let result = rpc.get_notification_stats_call(GetNotificationStatsRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GenerateBlocks => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the pay address"));
                 }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.unban_call(UnbanRequest { ip }).await?;
+                let pay_address = Address::try_from(argv.remove(0).as_str())?;
+                let count = match argv.is_empty() {
+                    true => 1,
+                    false => argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse the block count"))?,
+                };
+                let result = rpc.generate_blocks_call(GenerateBlocksRequest::new(pay_address, count)).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetInfo => {
@@ -257,3 +322,14 @@ impl Rpc {
         Ok(())
     }
 }
+
+/// Parses either a single IP address or an IP network in CIDR notation, such as `192.168.0.0/16`
+fn parse_ip_network(s: &str) -> Result<(RpcIpAddress, Option<u8>)> {
+    match s.split_once('/') {
+        Some((ip, prefix_length)) => {
+            let prefix_length = prefix_length.parse::<u8>().map_err(|_| Error::custom("Could not parse the network prefix length"))?;
+            Ok((ip.parse()?, Some(prefix_length)))
+        }
+        None => Ok((s.parse()?, None)),
+    }
+}
@@ -6,19 +6,20 @@ pub struct Send;
 
 impl Send {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
-        // address, amount, priority fee
+        // address, amount, priority fee, coin selection
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         let account = ctx.wallet().account()?;
 
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(ctx, "usage: send <address> <amount> <priority fee> [sequential|largest-first|branch-and-bound|privacy]");
             return Ok(());
         }
 
         let address = Address::try_from(argv.first().unwrap().as_str())?;
         let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
         let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        let coin_selection = argv.get(3).map(|s| s.parse::<CoinSelection>()).transpose()?.unwrap_or_default();
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
@@ -29,6 +30,7 @@ impl Send {
                 outputs.into(),
                 priority_fee_sompi.into(),
                 None,
+                coin_selection,
                 wallet_secret,
                 payment_secret,
                 &abortable,
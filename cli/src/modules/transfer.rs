@@ -34,6 +34,7 @@ impl Transfer {
                 outputs.into(),
                 priority_fee_sompi.into(),
                 None,
+                CoinSelection::default(),
                 wallet_secret,
                 payment_secret,
                 &abortable,
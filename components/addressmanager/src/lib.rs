@@ -1,4 +1,5 @@
 mod port_mapping_extender;
+mod query;
 mod stores;
 extern crate self as address_manager;
 
@@ -19,14 +20,21 @@ use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
 use kaspa_utils::networking::IpAddress;
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::{
+    banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, DbBannedAddressesStore},
+    banned_network_store::{BannedNetworksStore, DbBannedNetworksStore},
+};
 use thiserror::Error;
 
-pub use stores::NetAddress;
+pub use query::{AddressQuery, IpFamily, IpNetwork, IpNetworkError, RECENT_ADDRESS_PERIOD};
+pub use stores::{banned_address_store::ConnectionBan, NetAddress};
 
 const MAX_ADDRESSES: usize = 4096;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// Duration of a ban, in milliseconds, when none is specified
+pub const DEFAULT_BAN_DURATION: u64 = 24 * 60 * 60 * 1000;
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -53,6 +61,7 @@ pub enum UpnpError {
 
 pub struct AddressManager {
     banned_address_store: DbBannedAddressesStore,
+    banned_network_store: DbBannedNetworksStore,
     address_store: address_store_with_cache::Store,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
@@ -62,6 +71,7 @@ impl AddressManager {
     pub fn new(config: Arc<Config>, db: Arc<DB>, tick_service: Arc<TickService>) -> (Arc<Mutex<Self>>, Option<Extender>) {
         let mut instance = Self {
             banned_address_store: DbBannedAddressesStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
+            banned_network_store: DbBannedNetworksStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
             address_store: address_store_with_cache::new(db),
             local_net_addresses: Vec::new(),
             config,
@@ -258,11 +268,13 @@ impl AddressManager {
         }
 
         if self.address_store.has(address) {
+            self.address_store.mark_seen(address, unix_now());
             return;
         }
 
         // We mark `connection_failed_count` as 0 only after first success
         self.address_store.set(address, 1);
+        self.address_store.mark_seen(address, unix_now());
     }
 
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
@@ -284,6 +296,7 @@ impl AddressManager {
         }
 
         self.address_store.set(address, 0);
+        self.address_store.mark_seen(address, unix_now());
     }
 
     pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
@@ -294,8 +307,10 @@ impl AddressManager {
         self.address_store.iterate_prioritized_random_addresses(exceptions)
     }
 
-    pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+    /// Bans `ip` for `duration` milliseconds, or [`DEFAULT_BAN_DURATION`] if `None`
+    pub fn ban(&mut self, ip: IpAddress, duration: Option<u64>, reason: Option<String>) {
+        let ban = ConnectionBan::new(unix_now(), duration.unwrap_or(DEFAULT_BAN_DURATION), reason);
+        self.banned_address_store.set(ip.into(), ban).unwrap();
         self.address_store.remove_by_ip(ip.into());
     }
 
@@ -303,11 +318,40 @@ impl AddressManager {
         self.banned_address_store.remove(ip.into()).unwrap();
     }
 
+    /// Bans all the addresses of `network`. A single address network is banned as a regular IP ban.
+    pub fn ban_network(&mut self, network: IpNetwork, duration: Option<u64>, reason: Option<String>) {
+        if network.is_single_address() {
+            return self.ban(network.address().into(), duration, reason);
+        }
+        let ban = ConnectionBan::new(unix_now(), duration.unwrap_or(DEFAULT_BAN_DURATION), reason);
+        self.banned_network_store.set(network, ban).unwrap();
+        self.address_store.remove_by_network(&network);
+    }
+
+    pub fn unban_network(&mut self, network: IpNetwork) {
+        if network.is_single_address() {
+            return self.unban(network.address().into());
+        }
+        self.banned_network_store.remove(network).unwrap();
+    }
+
+    /// Returns whether `network` is banned as such, a single address network being banned by an IP ban only
+    pub fn is_network_banned(&mut self, network: IpNetwork) -> bool {
+        if network.is_single_address() {
+            return self.is_ip_banned(network.address().into());
+        }
+        self.get_active_network_bans().iter().any(|(banned, _)| *banned == network)
+    }
+
+    /// Returns whether `ip` is banned, either directly or through the ban of a network it belongs to
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
+        self.is_ip_banned(ip) || self.get_active_network_bans().iter().any(|(network, _)| network.contains(ip.0))
+    }
+
+    fn is_ip_banned(&mut self, ip: IpAddress) -> bool {
         match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
+            Some(ban) => {
+                if ban.is_expired(unix_now()) {
                     self.unban(ip);
                     false
                 } else {
@@ -325,6 +369,51 @@ impl AddressManager {
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
         self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
     }
+
+    /// Returns the requested page of the known addresses matching `query`, along with the total number of matches
+    pub fn query_addresses(&self, query: &AddressQuery) -> (Vec<NetAddress>, usize) {
+        let recent_threshold = unix_now().saturating_sub(RECENT_ADDRESS_PERIOD);
+        let matches = self
+            .address_store
+            .iterate_addresses_with_last_seen()
+            .filter(|(_, last_seen)| !query.recent_only || last_seen.is_some_and(|t| t >= recent_threshold))
+            .filter(|(address, _)| query.matches_ip(address.ip.0))
+            .map(|(address, _)| (address.ip.0, address.port))
+            .collect_vec();
+        let (page, total) = query.paginate(matches);
+        (page.into_iter().map(|(ip, port)| NetAddress::new(ip.into(), port)).collect(), total)
+    }
+
+    /// Returns the requested page of the actively banned addresses matching `query`, along with the total number of matches
+    pub fn query_banned_addresses(&mut self, query: &AddressQuery) -> (Vec<IpAddress>, usize) {
+        let matches = self.get_active_bans().into_iter().map(|(ip, _)| ip.0).filter(|ip| query.matches_ip(*ip)).collect_vec();
+        let (page, total) = query.paginate(matches);
+        (page.into_iter().map(IpAddress::from).collect(), total)
+    }
+
+    /// Returns the bans which did not expire yet, removing the expired ones on the way
+    pub fn get_active_bans(&mut self) -> Vec<(IpAddress, ConnectionBan)> {
+        let now = unix_now();
+        let (expired, active): (Vec<_>, Vec<_>) = self
+            .banned_address_store
+            .iterator()
+            .map(|x| {
+                let (ip, ban) = x.unwrap();
+                (IpAddress::from(ip), ban)
+            })
+            .partition(|(_, ban)| ban.is_expired(now));
+        expired.into_iter().for_each(|(ip, _)| self.unban(ip));
+        active
+    }
+
+    /// Returns the network bans which did not expire yet, removing the expired ones on the way
+    pub fn get_active_network_bans(&mut self) -> Vec<(IpNetwork, ConnectionBan)> {
+        let now = unix_now();
+        let (expired, active): (Vec<_>, Vec<_>) =
+            self.banned_network_store.iterator().map(|x| x.unwrap()).partition(|(_, ban)| ban.is_expired(now));
+        expired.into_iter().for_each(|(network, _)| self.banned_network_store.remove(network).unwrap());
+        active
+    }
 }
 
 mod address_store_with_cache {
@@ -349,12 +438,14 @@ mod address_store_with_cache {
             address_store::{AddressesStore, DbAddressesStore, Entry},
             AddressKey,
         },
-        NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
+        IpNetwork, NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
     };
 
     pub struct Store {
         db_store: DbAddressesStore,
         addresses: HashMap<AddressKey, Entry>,
+        /// Last time each address was advertised or connected to during this session. Not persisted
+        last_seen: HashMap<AddressKey, u64>,
     }
 
     impl Store {
@@ -366,7 +457,7 @@ mod address_store_with_cache {
                 addresses.insert(key, entry);
             }
 
-            Self { db_store, addresses }
+            Self { db_store, addresses, last_seen: HashMap::new() }
         }
 
         pub fn has(&mut self, address: NetAddress) -> bool {
@@ -399,8 +490,16 @@ mod address_store_with_cache {
             self.remove_by_key(address.into())
         }
 
+        pub fn mark_seen(&mut self, address: NetAddress, timestamp: u64) {
+            let key = address.into();
+            if self.addresses.contains_key(&key) {
+                self.last_seen.insert(key, timestamp);
+            }
+        }
+
         fn remove_by_key(&mut self, key: AddressKey) {
             self.addresses.remove(&key);
+            self.last_seen.remove(&key);
             self.db_store.remove(key).unwrap()
         }
 
@@ -408,6 +507,10 @@ mod address_store_with_cache {
             self.addresses.values().map(|entry| entry.address)
         }
 
+        pub fn iterate_addresses_with_last_seen(&self) -> impl Iterator<Item = (NetAddress, Option<u64>)> + '_ {
+            self.addresses.iter().map(|(key, entry)| (entry.address, self.last_seen.get(key).copied()))
+        }
+
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
@@ -455,6 +558,14 @@ mod address_store_with_cache {
                 self.remove_by_key(key);
             }
         }
+
+        pub fn remove_by_network(&mut self, network: &IpNetwork) {
+            for key in
+                self.addresses.iter().filter(|(_, entry)| network.contains(entry.address.ip.0)).map(|(key, _)| *key).collect_vec()
+            {
+                self.remove_by_key(key);
+            }
+        }
     }
 
     pub fn new(db: Arc<DB>) -> Store {
@@ -514,11 +625,16 @@ mod address_store_with_cache {
         use std::str::FromStr;
 
         use super::*;
+        use crate::{
+            stores::banned_address_store::{AddressKey as BannedAddressKey, ConnectionBanTimestamp},
+            AddressQuery, IpFamily, IpNetwork, DEFAULT_BAN_DURATION,
+        };
         use address_manager::AddressManager;
         use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
-        use kaspa_core::task::tick::TickService;
+        use kaspa_core::{task::tick::TickService, time::unix_now};
         use kaspa_database::create_temp_db;
-        use kaspa_database::prelude::ConnBuilder;
+        use kaspa_database::prelude::{CachedDbAccess, ConnBuilder, DirectDbWriter};
+        use kaspa_database::registry::DatabaseStorePrefixes;
         use kaspa_utils::networking::IpAddress;
         use statest::ks::KSTest;
         use statrs::distribution::Uniform;
@@ -621,5 +737,134 @@ mod address_store_with_cache {
             );
             assert!(adjusted_p <= significance)
         }
+
+        #[test]
+        fn test_timed_bans() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+
+            // A ban recorded by a former version, hence without expiry nor reason
+            let legacy_ip = IpAddress::from_str("1.1.1.1").unwrap();
+            let legacy_access: CachedDbAccess<BannedAddressKey, ConnectionBanTimestamp> =
+                CachedDbAccess::new(db.1.clone(), CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into());
+            legacy_access.write(DirectDbWriter::new(&db.1), legacy_ip.0.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1.clone(), Arc::new(TickService::default()));
+            let mut am = am.lock();
+            assert!(legacy_access.iterator().next().is_none(), "legacy bans should be migrated");
+            assert!(am.is_banned(legacy_ip));
+
+            let default_ip = IpAddress::from_str("2.2.2.2").unwrap();
+            let expired_ip = IpAddress::from_str("3.3.3.3").unwrap();
+            let timed_ip = IpAddress::from_str("4.4.4.4").unwrap();
+            am.ban(default_ip, None, None);
+            am.ban(expired_ip, Some(0), None);
+            am.ban(timed_ip, Some(60 * 60 * 1000), Some("misbehaving".to_owned()));
+
+            assert!(am.is_banned(default_ip));
+            assert!(!am.is_banned(expired_ip));
+            assert!(am.is_banned(timed_ip));
+
+            am.ban(expired_ip, Some(0), None);
+            let now = unix_now();
+            let bans = am.get_active_bans();
+            assert_eq!(bans.iter().map(|(ip, _)| ip.0).sorted().collect_vec(), vec![legacy_ip.0, default_ip.0, timed_ip.0]);
+            assert!(!am.get_all_banned_addresses().contains(&expired_ip), "expired bans should be removed");
+
+            let (_, default_ban) = bans.iter().find(|(ip, _)| *ip == default_ip).unwrap();
+            assert_eq!(default_ban.expiry - default_ban.timestamp, DEFAULT_BAN_DURATION);
+            assert!(default_ban.remaining(now) <= DEFAULT_BAN_DURATION);
+            let (_, timed_ban) = bans.iter().find(|(ip, _)| *ip == timed_ip).unwrap();
+            assert_eq!(timed_ban.reason.as_deref(), Some("misbehaving"));
+            assert!(timed_ban.remaining(now) <= 60 * 60 * 1000);
+
+            am.unban(timed_ip);
+            assert!(!am.is_banned(timed_ip));
+        }
+
+        #[test]
+        fn test_network_bans() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let inside = NetAddress::new(IpAddress::from_str("10.1.2.3").unwrap(), 16111);
+            let outside = NetAddress::new(IpAddress::from_str("10.2.0.1").unwrap(), 16111);
+            am.add_address(inside);
+            am.add_address(outside);
+
+            let network = IpNetwork::from_str("10.1.0.0/16").unwrap();
+            am.ban_network(network, None, Some("spamming".to_owned()));
+            assert!(am.is_banned(inside.ip));
+            assert!(am.is_banned(IpAddress::from_str("::ffff:10.1.200.1").unwrap()));
+            assert!(!am.is_banned(outside.ip));
+            assert!(am.is_network_banned(network));
+            assert!(!am.is_network_banned(IpNetwork::from_str("10.0.0.0/8").unwrap()));
+            assert_eq!(am.get_all_addresses(), vec![outside], "addresses of a banned network should be removed");
+            assert!(am.get_active_bans().is_empty(), "a network ban should not register single IP bans");
+            let bans = am.get_active_network_bans();
+            assert_eq!(bans.len(), 1);
+            assert_eq!(bans[0].0, network);
+            assert_eq!(bans[0].1.reason.as_deref(), Some("spamming"));
+
+            // A single address network is a regular IP ban
+            let single = IpNetwork::from_str("10.2.0.1/32").unwrap();
+            am.ban_network(single, None, None);
+            assert!(am.is_banned(outside.ip));
+            assert_eq!(am.get_active_bans().len(), 1);
+            am.unban_network(single);
+            assert!(!am.is_banned(outside.ip));
+
+            am.unban_network(network);
+            assert!(!am.is_banned(inside.ip));
+            assert!(am.get_active_network_bans().is_empty());
+
+            am.ban_network(network, Some(0), None);
+            assert!(!am.is_banned(inside.ip), "expired network bans should not apply");
+            assert!(am.get_active_network_bans().is_empty());
+        }
+
+        #[test]
+        fn test_query_addresses() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let addresses = ["10.0.0.3", "10.0.0.1", "10.0.1.2", "20.0.0.1", "2001:db8::1", "2001:db9::1"]
+                .map(|ip| NetAddress::new(IpAddress::from_str(ip).unwrap(), 16111));
+            addresses.iter().for_each(|address| am.add_address(*address));
+            let ips = |addresses: Vec<NetAddress>| addresses.into_iter().map(|x| x.ip.to_string()).collect_vec();
+
+            // No filter returns all addresses in address order
+            let (page, total) = am.query_addresses(&AddressQuery::default());
+            assert_eq!(total, 6);
+            assert_eq!(ips(page), vec!["10.0.0.1", "10.0.0.3", "10.0.1.2", "20.0.0.1", "2001:db8::1", "2001:db9::1"]);
+
+            // All addresses were just added so they are all recent
+            assert_eq!(am.query_addresses(&AddressQuery { recent_only: true, ..Default::default() }).1, 6);
+
+            let (page, total) = am.query_addresses(&AddressQuery { ip_family: Some(IpFamily::V6), ..Default::default() });
+            assert_eq!(total, 2);
+            assert_eq!(ips(page), vec!["2001:db8::1", "2001:db9::1"]);
+
+            let network = Some(IpNetwork::from_str("10.0.0.0/24").unwrap());
+            let (page, total) = am.query_addresses(&AddressQuery { network, ..Default::default() });
+            assert_eq!(total, 2);
+            assert_eq!(ips(page), vec!["10.0.0.1", "10.0.0.3"]);
+
+            let query = AddressQuery { ip_family: Some(IpFamily::V4), offset: 1, limit: Some(2), ..Default::default() };
+            let (page, total) = am.query_addresses(&query);
+            assert_eq!(total, 4);
+            assert_eq!(ips(page), vec!["10.0.0.3", "10.0.1.2"]);
+
+            am.ban(addresses[0].ip, None, None);
+            am.ban(addresses[4].ip, None, None);
+            let (page, total) = am.query_banned_addresses(&AddressQuery { ip_family: Some(IpFamily::V4), ..Default::default() });
+            assert_eq!(total, 1);
+            assert_eq!(page, vec![addresses[0].ip]);
+            assert_eq!(am.query_addresses(&AddressQuery::default()).1, 4);
+        }
     }
 }
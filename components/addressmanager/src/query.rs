@@ -0,0 +1,193 @@
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Addresses advertised to or successfully connected by this node within this period are considered recent
+pub const RECENT_ADDRESS_PERIOD: u64 = 3 * 60 * 60 * 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IpFamily {
+    V4,
+    V6,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum IpNetworkError {
+    #[error("expected an ip network of the form <address>/<prefix length>, got '{0}'")]
+    InvalidFormat(String),
+
+    #[error("prefix length {0} exceeds the {1} bits of the address")]
+    InvalidPrefixLength(u8, u8),
+}
+
+/// An IP network in CIDR notation, such as `192.168.0.0/16` or `2001:db8::/32`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<Self, IpNetworkError> {
+        let max_len = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max_len {
+            return Err(IpNetworkError::InvalidPrefixLength(prefix_len, max_len));
+        }
+        // Networks of IPv4 addresses mapped to IPv6 are handled as IPv4 networks
+        if let IpAddr::V6(ip) = address {
+            if let (Some(ipv4), true) = (ip.to_ipv4_mapped(), prefix_len >= 96) {
+                return Self::new(IpAddr::V4(ipv4), prefix_len - 96);
+            }
+        }
+        // Clear the host bits so that equal networks compare equal
+        let address = match address {
+            IpAddr::V4(ip) => IpAddr::V4(Ipv4Addr::from(u32::from(ip) & (u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0)))),
+            IpAddr::V6(ip) => {
+                IpAddr::V6(Ipv6Addr::from(u128::from(ip) & (u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0))))
+            }
+        };
+        Ok(Self { address, prefix_len })
+    }
+
+    /// Returns the network address, with all host bits cleared
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns whether the network consists of a single address
+    pub fn is_single_address(&self) -> bool {
+        match self.address {
+            IpAddr::V4(_) => self.prefix_len == 32,
+            IpAddr::V6(_) => self.prefix_len == 128,
+        }
+    }
+
+    /// Returns whether `ip` belongs to the network. IPv4 addresses mapped to IPv6 are matched as IPv4 addresses.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.address, canonical(ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                prefix_eq(u32::from(network) as u128, u32::from(ip) as u128, self.prefix_len as u32 + 96)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => prefix_eq(u128::from(network), u128::from(ip), self.prefix_len as u32),
+            _ => false,
+        }
+    }
+}
+
+impl Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || IpNetworkError::InvalidFormat(s.to_owned());
+        let (address, prefix_len) = s.split_once('/').ok_or_else(invalid)?;
+        let address = IpAddr::from_str(address).map_err(|_| invalid())?;
+        let prefix_len = u8::from_str(prefix_len).map_err(|_| invalid())?;
+        Self::new(address, prefix_len)
+    }
+}
+
+/// Filters and pagination applied when querying the address manager
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AddressQuery {
+    /// Keep only the addresses seen within [`RECENT_ADDRESS_PERIOD`]. Applies to known addresses only.
+    pub recent_only: bool,
+    pub ip_family: Option<IpFamily>,
+    pub network: Option<IpNetwork>,
+    /// Number of matching addresses to skip, in address order
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl AddressQuery {
+    pub(crate) fn matches_ip(&self, ip: IpAddr) -> bool {
+        let ip = canonical(ip);
+        let family_matches = match self.ip_family {
+            Some(IpFamily::V4) => ip.is_ipv4(),
+            Some(IpFamily::V6) => ip.is_ipv6(),
+            None => true,
+        };
+        family_matches && self.network.map_or(true, |network| network.contains(ip))
+    }
+
+    /// Sorts `items` and returns the requested page along with the total number of items
+    pub(crate) fn paginate<T: Ord>(&self, mut items: Vec<T>) -> (Vec<T>, usize) {
+        let total = items.len();
+        items.sort_unstable();
+        let page = items.into_iter().skip(self.offset).take(self.limit.unwrap_or(usize::MAX)).collect();
+        (page, total)
+    }
+}
+
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    }
+}
+
+/// Compares the first `len` bits of two 128 bits values
+fn prefix_eq(a: u128, b: u128, len: u32) -> bool {
+    len == 0 || (a ^ b) >> (128 - len) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_network() {
+        let network = IpNetwork::from_str("192.168.0.0/16").unwrap();
+        assert!(network.contains("192.168.10.1".parse().unwrap()));
+        assert!(network.contains("::ffff:192.168.10.1".parse().unwrap()));
+        assert!(!network.contains("192.169.0.1".parse().unwrap()));
+        assert!(!network.contains("2001:db8::1".parse().unwrap()));
+
+        let network = IpNetwork::from_str("2001:db8::/32").unwrap();
+        assert!(network.contains("2001:db8:1::1".parse().unwrap()));
+        assert!(!network.contains("2001:db9::1".parse().unwrap()));
+        assert!(!network.contains("192.168.10.1".parse().unwrap()));
+
+        let any = IpNetwork::from_str("0.0.0.0/0").unwrap();
+        assert!(any.contains("1.2.3.4".parse().unwrap()));
+        let single = IpNetwork::from_str("1.2.3.4/32").unwrap();
+        assert!(single.contains("1.2.3.4".parse().unwrap()));
+        assert!(!single.contains("1.2.3.5".parse().unwrap()));
+        assert!(single.is_single_address());
+
+        let network = IpNetwork::from_str("5.6.7.8/16").unwrap();
+        assert_eq!(network, IpNetwork::from_str("5.6.0.0/16").unwrap());
+        assert_eq!(network.to_string(), "5.6.0.0/16");
+        assert_eq!(IpNetwork::from_str("2001:db8::1/32").unwrap().to_string(), "2001:db8::/32");
+        assert_eq!(IpNetwork::from_str("1.2.3.4/0").unwrap().to_string(), "0.0.0.0/0");
+        assert_eq!(IpNetwork::from_str("::ffff:10.1.2.3/112").unwrap(), IpNetwork::from_str("10.1.0.0/16").unwrap());
+
+        assert_eq!(IpNetwork::from_str("1.2.3.4/33"), Err(IpNetworkError::InvalidPrefixLength(33, 32)));
+        assert!(matches!(IpNetwork::from_str("1.2.3.4"), Err(IpNetworkError::InvalidFormat(_))));
+        assert!(matches!(IpNetwork::from_str("1.2.3/8"), Err(IpNetworkError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_paginate() {
+        let query = AddressQuery { offset: 2, limit: Some(2), ..Default::default() };
+        assert_eq!(query.paginate(vec![5, 1, 4, 2, 3]), (vec![3, 4], 5));
+        let query = AddressQuery { offset: 4, ..Default::default() };
+        assert_eq!(query.paginate(vec![5, 1, 4, 2, 3]), (vec![5], 5));
+        assert_eq!(query.paginate(Vec::<u8>::new()), (vec![], 0));
+    }
+}
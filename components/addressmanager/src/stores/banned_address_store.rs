@@ -1,3 +1,4 @@
+use crate::DEFAULT_BAN_DURATION;
 use kaspa_database::{
     prelude::{CachePolicy, StoreError, StoreResult},
     prelude::{CachedDbAccess, DirectDbWriter, DB},
@@ -6,19 +7,52 @@ use kaspa_database::{
 use kaspa_utils::mem_size::MemSizeEstimator;
 use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv6Addr};
-use std::{error::Error, fmt::Display, sync::Arc};
+use std::{error::Error, fmt::Display, mem::size_of, sync::Arc};
 
+/// Legacy ban record holding only the time at which the ban was issued
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct ConnectionBanTimestamp(pub u64);
 
 impl MemSizeEstimator for ConnectionBanTimestamp {}
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionBan {
+    /// Unix time in milliseconds at which the ban was issued
+    pub timestamp: u64,
+    /// Unix time in milliseconds at which the ban expires
+    pub expiry: u64,
+    pub reason: Option<String>,
+}
+
+impl ConnectionBan {
+    pub fn new(timestamp: u64, duration: u64, reason: Option<String>) -> Self {
+        Self { timestamp, expiry: timestamp.saturating_add(duration), reason }
+    }
+
+    #[inline]
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expiry
+    }
+
+    /// Returns the remaining ban time in milliseconds
+    #[inline]
+    pub fn remaining(&self, now: u64) -> u64 {
+        self.expiry.saturating_sub(now)
+    }
+}
+
+impl MemSizeEstimator for ConnectionBan {
+    fn estimate_mem_bytes(&self) -> usize {
+        size_of::<Self>() + self.reason.as_ref().map_or(0, |x| x.len())
+    }
+}
+
 pub trait BannedAddressesStoreReader {
-    fn get(&self, address: IpAddr) -> Result<ConnectionBanTimestamp, StoreError>;
+    fn get(&self, address: IpAddr) -> Result<ConnectionBan, StoreError>;
 }
 
 pub trait BannedAddressesStore: BannedAddressesStoreReader {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()>;
+    fn set(&mut self, ip: IpAddr, ban: ConnectionBan) -> StoreResult<()>;
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
 }
 
@@ -26,7 +60,7 @@ const IPV6_LEN: usize = 16;
 const ADDRESS_KEY_SIZE: usize = IPV6_LEN;
 
 #[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
-struct AddressKey([u8; ADDRESS_KEY_SIZE]);
+pub(crate) struct AddressKey([u8; ADDRESS_KEY_SIZE]);
 
 impl AsRef<[u8]> for AddressKey {
     fn as_ref(&self) -> &[u8] {
@@ -66,41 +100,69 @@ impl From<AddressKey> for IpAddr {
     }
 }
 
+fn parse_key<T>(iter_result: Result<(Box<[u8]>, T), Box<dyn Error>>) -> Result<(IpAddr, T), Box<dyn Error>> {
+    match iter_result {
+        Ok((key_bytes, data)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
+            Ok(address_key_slice) => {
+                let addr_key = AddressKey(address_key_slice);
+                let address: IpAddr = addr_key.into();
+                Ok((address, data))
+            }
+            Err(e) => Err(e.into()),
+        },
+        Err(e) => Err(e),
+    }
+}
+
 #[derive(Clone)]
 pub struct DbBannedAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<AddressKey, ConnectionBanTimestamp>,
+    access: CachedDbAccess<AddressKey, ConnectionBan>,
 }
 
 impl DbBannedAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) }
-    }
-
-    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanTimestamp), Box<dyn Error>>> + '_ {
-        self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_ban_timestamp)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
-                Ok(address_key_slice) => {
-                    let addr_key = AddressKey(address_key_slice);
-                    let address: IpAddr = addr_key.into();
-                    Ok((address, connection_ban_timestamp))
-                }
-                Err(e) => Err(e.into()),
-            },
-            Err(e) => Err(e),
-        })
+        let store = Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TimedBannedAddresses.into()),
+        };
+        store.migrate_legacy_bans().unwrap();
+        store
+    }
+
+    /// Moves the bans recorded by former versions, which carry no expiry, to the timed bans store
+    /// with the default ban duration
+    fn migrate_legacy_bans(&self) -> StoreResult<()> {
+        let legacy_access: CachedDbAccess<AddressKey, ConnectionBanTimestamp> =
+            CachedDbAccess::new(self.db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into());
+        let legacy_bans = legacy_access.iterator().filter_map(|x| parse_key(x).ok()).collect::<Vec<_>>();
+        if legacy_bans.is_empty() {
+            return Ok(());
+        }
+        for (ip, timestamp) in legacy_bans {
+            self.access.write(
+                DirectDbWriter::new(&self.db),
+                ip.into(),
+                ConnectionBan::new(timestamp.0, DEFAULT_BAN_DURATION, None),
+            )?;
+        }
+        legacy_access.delete_all(DirectDbWriter::new(&self.db))
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBan), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(parse_key)
     }
 }
 
 impl BannedAddressesStoreReader for DbBannedAddressesStore {
-    fn get(&self, ip: IpAddr) -> Result<ConnectionBanTimestamp, StoreError> {
+    fn get(&self, ip: IpAddr) -> Result<ConnectionBan, StoreError> {
         self.access.read(ip.into())
     }
 }
 
 impl BannedAddressesStore for DbBannedAddressesStore {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+    fn set(&mut self, ip: IpAddr, ban: ConnectionBan) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), ip.into(), ban)
     }
 
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
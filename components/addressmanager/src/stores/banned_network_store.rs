@@ -0,0 +1,103 @@
+use super::banned_address_store::ConnectionBan;
+use crate::IpNetwork;
+use kaspa_database::{
+    prelude::{CachePolicy, StoreError, StoreResult},
+    prelude::{CachedDbAccess, DirectDbWriter, DB},
+    registry::DatabaseStorePrefixes,
+};
+use std::net::{IpAddr, Ipv6Addr};
+use std::{error::Error, fmt::Display, sync::Arc};
+
+pub trait BannedNetworksStoreReader {
+    fn get(&self, network: IpNetwork) -> Result<ConnectionBan, StoreError>;
+}
+
+pub trait BannedNetworksStore: BannedNetworksStoreReader {
+    fn set(&mut self, network: IpNetwork, ban: ConnectionBan) -> StoreResult<()>;
+    fn remove(&mut self, network: IpNetwork) -> StoreResult<()>;
+}
+
+const IPV6_LEN: usize = 16;
+const NETWORK_KEY_SIZE: usize = IPV6_LEN + 1;
+
+/// The network address, mapped to IPv6, followed by the prefix length of the network
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct NetworkKey([u8; NETWORK_KEY_SIZE]);
+
+impl AsRef<[u8]> for NetworkKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for NetworkKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match IpNetwork::try_from(*self) {
+            Ok(network) => write!(f, "{network}"),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+impl From<IpNetwork> for NetworkKey {
+    fn from(network: IpNetwork) -> Self {
+        let mut bytes = [0u8; NETWORK_KEY_SIZE];
+        bytes[..IPV6_LEN].copy_from_slice(&match network.address() {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped().octets(),
+            IpAddr::V6(ip) => ip.octets(),
+        });
+        bytes[IPV6_LEN] = network.prefix_len();
+        Self(bytes)
+    }
+}
+
+impl TryFrom<NetworkKey> for IpNetwork {
+    type Error = crate::IpNetworkError;
+
+    fn try_from(k: NetworkKey) -> Result<Self, Self::Error> {
+        let ipv6 = Ipv6Addr::from(<[u8; IPV6_LEN]>::try_from(&k.0[..IPV6_LEN]).unwrap());
+        let address = match ipv6.to_ipv4_mapped() {
+            Some(ipv4) => IpAddr::V4(ipv4),
+            None => IpAddr::V6(ipv6),
+        };
+        IpNetwork::new(address, k.0[IPV6_LEN])
+    }
+}
+
+fn parse_key(iter_result: Result<(Box<[u8]>, ConnectionBan), Box<dyn Error>>) -> Result<(IpNetwork, ConnectionBan), Box<dyn Error>> {
+    let (key_bytes, ban) = iter_result?;
+    let key = NetworkKey(<[u8; NETWORK_KEY_SIZE]>::try_from(&key_bytes[..])?);
+    Ok((key.try_into()?, ban))
+}
+
+#[derive(Clone)]
+pub struct DbBannedNetworksStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<NetworkKey, ConnectionBan>,
+}
+
+impl DbBannedNetworksStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedNetworks.into()) }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpNetwork, ConnectionBan), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(parse_key)
+    }
+}
+
+impl BannedNetworksStoreReader for DbBannedNetworksStore {
+    fn get(&self, network: IpNetwork) -> Result<ConnectionBan, StoreError> {
+        self.access.read(network.into())
+    }
+}
+
+impl BannedNetworksStore for DbBannedNetworksStore {
+    fn set(&mut self, network: IpNetwork, ban: ConnectionBan) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), network.into(), ban)
+    }
+
+    fn remove(&mut self, network: IpNetwork) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), network.into())
+    }
+}
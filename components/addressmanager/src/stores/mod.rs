@@ -4,6 +4,7 @@ pub use kaspa_utils::networking::NetAddress;
 
 pub(super) mod address_store;
 pub(super) mod banned_address_store;
+pub(super) mod banned_network_store;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct AddressKey(Ipv6Addr, u16);
@@ -9,7 +9,7 @@ use std::{
 use duration_string::DurationString;
 use futures_util::future::join_all;
 use itertools::Itertools;
-use kaspa_addressmanager::{AddressManager, NetAddress};
+use kaspa_addressmanager::{AddressManager, IpNetwork, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
 use kaspa_utils::triggers::SingleTrigger;
@@ -279,10 +279,11 @@ impl ConnectionManager {
         }
     }
 
-    /// Bans the given IP and disconnects from all the peers with that IP.
+    /// Bans the given IP for `duration` milliseconds (or the address manager default if `None`)
+    /// and disconnects from all the peers with that IP.
     ///
     /// _GO-KASPAD: BanByIP_
-    pub async fn ban(&self, ip: IpAddr) {
+    pub async fn ban(&self, ip: IpAddr, duration: Option<u64>, reason: Option<String>) {
         if self.ip_has_permanent_connection(ip).await {
             return;
         }
@@ -291,7 +292,19 @@ impl ConnectionManager {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
+        self.address_manager.lock().ban(ip.into(), duration, reason);
+    }
+
+    /// Bans all the addresses of `network` and terminates the connections to the matching peers, except those
+    /// having a permanent connection request.
+    pub async fn ban_network(&self, network: IpNetwork, duration: Option<u64>, reason: Option<String>) {
+        for peer in self.p2p_adaptor.active_peers() {
+            let address = peer.net_address();
+            if network.contains(address.ip()) && !self.ip_has_permanent_connection(address.ip()).await {
+                self.p2p_adaptor.terminate(peer.key()).await;
+            }
+        }
+        self.address_manager.lock().ban_network(network, duration, reason);
     }
 
     /// Returns whether the given address is banned.
@@ -304,6 +317,16 @@ impl ConnectionManager {
         self.connection_requests.lock().await.contains_key(address)
     }
 
+    /// Returns an IP of `network` having some permanent request, if any.
+    pub async fn network_permanent_connection(&self, network: &IpNetwork) -> Option<IpAddr> {
+        self.connection_requests
+            .lock()
+            .await
+            .iter()
+            .find(|(address, request)| request.is_permanent && network.contains(address.ip()))
+            .map(|(address, _)| address.ip())
+    }
+
     /// Returns whether the given IP has some permanent request.
     pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
         self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
@@ -5,9 +5,11 @@ use parking_lot::RwLock;
 use std::{collections::VecDeque, ops::Deref, sync::Arc, thread::JoinHandle};
 
 mod batch;
+mod refresher;
 mod session;
 
 pub use batch::BlockProcessingBatch;
+pub use refresher::ConsensusRefresher;
 pub use session::{
     spawn_blocking, ConsensusInstance, ConsensusProxy, ConsensusSessionBlocking, SessionLock, SessionReadGuard, SessionWriteGuard,
 };
@@ -142,6 +144,17 @@ impl ConsensusManager {
         StagingConsensus::new(self.clone(), ConsensusInner::new(consensus, ctl))
     }
 
+    /// Replaces the current consensus with a fresh instance of the active consensus. Used by read-only secondary
+    /// instances to observe the progress of the primary node owning the databases
+    pub fn refresh_active_consensus(&self) {
+        let (consensus, ctl) = self.factory.new_active_consensus();
+        let handles = ctl.start();
+        let mut g = self.inner.write();
+        let prev = std::mem::replace(&mut g.current, ConsensusInner::new(consensus, ctl));
+        g.handles.extend(handles);
+        prev.ctl.stop();
+    }
+
     pub fn register_consensus_reset_handler(&self, handler: Arc<dyn ConsensusResetHandler>) {
         self.inner.write().consensus_reset_handlers.push(handler);
     }
@@ -235,3 +248,79 @@ impl Deref for StagingConsensus {
         &self.staging.consensus
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    /// A consensus snapshot identified by its virtual DAA score
+    struct SnapshotConsensus {
+        id: u64,
+        started: AtomicBool,
+        stopped: AtomicBool,
+    }
+
+    impl ConsensusApi for SnapshotConsensus {
+        fn get_virtual_daa_score(&self) -> u64 {
+            self.id
+        }
+    }
+
+    impl ConsensusCtl for SnapshotConsensus {
+        fn start(&self) -> Vec<JoinHandle<()>> {
+            self.started.store(true, Ordering::SeqCst);
+            vec![]
+        }
+
+        fn stop(&self) {
+            self.stopped.store(true, Ordering::SeqCst);
+        }
+
+        fn make_active(&self) {}
+    }
+
+    #[derive(Default)]
+    struct SnapshotFactory {
+        next_id: AtomicU64,
+        snapshots: parking_lot::Mutex<Vec<Arc<SnapshotConsensus>>>,
+    }
+
+    impl ConsensusFactory for SnapshotFactory {
+        fn new_active_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+            let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+            let consensus = Arc::new(SnapshotConsensus { id, started: AtomicBool::new(false), stopped: AtomicBool::new(false) });
+            self.snapshots.lock().push(consensus.clone());
+            (ConsensusInstance::new(SessionLock::new(), consensus.clone()), consensus)
+        }
+
+        fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+            unimplemented!()
+        }
+
+        fn close(&self) {}
+
+        fn delete_inactive_consensus_entries(&self) {}
+
+        fn delete_staging_entry(&self) {}
+    }
+
+    #[test]
+    fn test_refresh_active_consensus() {
+        let factory = Arc::new(SnapshotFactory::default());
+        let manager = ConsensusManager::new(factory.clone());
+        let previous = manager.consensus();
+        assert_eq!(previous.unguarded_session_blocking().get_virtual_daa_score(), 0);
+
+        // The refreshed consensus is started and replaces the previous one, which is stopped
+        manager.refresh_active_consensus();
+        assert_eq!(manager.consensus().unguarded_session_blocking().get_virtual_daa_score(), 1);
+        let snapshots = factory.snapshots.lock();
+        assert!(snapshots[0].stopped.load(Ordering::SeqCst));
+        assert!(snapshots[1].started.load(Ordering::SeqCst));
+        assert!(!snapshots[1].stopped.load(Ordering::SeqCst));
+
+        // Sessions acquired before the refresh keep reading the previous snapshot
+        assert_eq!(previous.unguarded_session_blocking().get_virtual_daa_score(), 0);
+    }
+}
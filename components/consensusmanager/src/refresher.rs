@@ -0,0 +1,59 @@
+use crate::{spawn_blocking, ConsensusManager};
+use kaspa_core::{
+    debug,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace,
+};
+use std::{sync::Arc, time::Duration};
+
+const SERVICE_NAME: &str = "consensus-refresher";
+
+/// Periodically refreshes the active consensus of a read-only secondary instance, so every refresh
+/// exposes a new consistent snapshot of the databases of the primary node
+pub struct ConsensusRefresher {
+    consensus_manager: Arc<ConsensusManager>,
+    tick_service: Arc<TickService>,
+    refresh_interval: Duration,
+}
+
+impl ConsensusRefresher {
+    pub fn new(consensus_manager: Arc<ConsensusManager>, tick_service: Arc<TickService>, refresh_interval: Duration) -> Self {
+        Self { consensus_manager, tick_service, refresh_interval }
+    }
+
+    async fn worker(self: &Arc<Self>) {
+        while let TickReason::Wakeup = self.tick_service.tick(self.refresh_interval).await {
+            let consensus_manager = self.consensus_manager.clone();
+            spawn_blocking(move || consensus_manager.refresh_active_consensus()).await.unwrap();
+            debug!("[{SERVICE_NAME}] refreshed the active consensus snapshot");
+        }
+        trace!("{SERVICE_NAME} worker exiting");
+    }
+}
+
+impl AsyncService for ConsensusRefresher {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
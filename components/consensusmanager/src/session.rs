@@ -4,7 +4,9 @@
 
 use kaspa_consensus_core::{
     acceptance_data::AcceptanceData,
-    api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus},
+    api::{
+        BlockColor, BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DagStats, DynConsensus, VirtualParentsSelection,
+    },
     block::Block,
     blockstatus::BlockStatus,
     daa_score_timestamp::DaaScoreTimestamp,
@@ -267,6 +269,37 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_virtual_chain_from_block(hash)).await
     }
 
+    /// Samples `count` selected chain blocks, with replacement, in the blue score range, weighted by the work they add to the chain.
+    /// See [`ConsensusApi::sample_chain_blocks_by_work`].
+    pub async fn async_sample_chain_blocks_by_work(
+        &self,
+        low_blue_score: u64,
+        high_blue_score: u64,
+        count: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        self.clone().spawn_blocking(move |c| c.sample_chain_blocks_by_work(low_blue_score, high_blue_score, count)).await
+    }
+
+    /// See [`ConsensusApi::get_chain_block_by_blue_score`].
+    pub async fn async_get_chain_block_by_blue_score(&self, blue_score: u64) -> ConsensusResult<Hash> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_by_blue_score(blue_score)).await
+    }
+
+    /// See [`ConsensusApi::get_chain_block_by_daa_score`].
+    pub async fn async_get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_by_daa_score(daa_score)).await
+    }
+
+    /// See [`ConsensusApi::get_chain_block_by_timestamp`].
+    pub async fn async_get_chain_block_by_timestamp(&self, timestamp: u64) -> ConsensusResult<Hash> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_by_timestamp(timestamp)).await
+    }
+
+    /// See [`ConsensusApi::get_chain_headers`].
+    pub async fn async_get_chain_headers(&self, start: Hash, limit: usize, is_ascending: bool) -> ConsensusResult<Vec<Arc<Header>>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_headers(start, limit, is_ascending)).await
+    }
+
     pub async fn async_get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -384,6 +417,15 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.is_chain_block(hash)).await
     }
 
+    /// See [`ConsensusApi::get_current_block_color`].
+    pub async fn async_get_current_block_color(&self, hash: Hash) -> ConsensusResult<Option<BlockColor>> {
+        self.clone().spawn_blocking(move |c| c.get_current_block_color(hash)).await
+    }
+
+    pub async fn async_resolve_finality_conflict(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        self.clone().spawn_blocking(move |c| c.resolve_finality_conflict(finality_block_hash)).await
+    }
+
     pub async fn async_get_pruning_point_utxos(
         &self,
         expected_pruning_point: Hash,
@@ -416,8 +458,17 @@ impl ConsensusSessionOwned {
         &self,
         start_hash: Option<Hash>,
         window_size: usize,
+        min_window_size: usize,
     ) -> ConsensusResult<u64> {
-        self.clone().spawn_blocking(move |c| c.estimate_network_hashes_per_second(start_hash, window_size)).await
+        self.clone().spawn_blocking(move |c| c.estimate_network_hashes_per_second(start_hash, window_size, min_window_size)).await
+    }
+
+    pub async fn async_get_dag_stats(&self, window_size: usize) -> DagStats {
+        self.clone().spawn_blocking(move |c| c.get_dag_stats(window_size)).await
+    }
+
+    pub async fn async_get_virtual_parents_selection(&self) -> VirtualParentsSelection {
+        self.clone().spawn_blocking(|c| c.get_virtual_parents_selection()).await
     }
 
     pub async fn async_validate_pruning_points(&self) -> ConsensusResult<()> {
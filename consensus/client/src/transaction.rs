@@ -46,6 +46,7 @@ export interface ITransactionVerboseData {
     mass : bigint;
     blockHash : HexString;
     blockTime : bigint;
+    confirmations : bigint;
 }
 "#;
 
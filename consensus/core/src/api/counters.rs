@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 #[derive(Default)]
@@ -25,9 +26,21 @@ impl ProcessingCounters {
             mass_counts: self.mass_counts.load(Ordering::Relaxed),
         }
     }
+
+    /// Adds the counts of a previously taken snapshot, typically persisted by a former run of the node
+    pub fn restore(&self, snapshot: &ProcessingCountersSnapshot) {
+        self.blocks_submitted.fetch_add(snapshot.blocks_submitted, Ordering::Relaxed);
+        self.header_counts.fetch_add(snapshot.header_counts, Ordering::Relaxed);
+        self.dep_counts.fetch_add(snapshot.dep_counts, Ordering::Relaxed);
+        self.mergeset_counts.fetch_add(snapshot.mergeset_counts, Ordering::Relaxed);
+        self.body_counts.fetch_add(snapshot.body_counts, Ordering::Relaxed);
+        self.txs_counts.fetch_add(snapshot.txs_counts, Ordering::Relaxed);
+        self.chain_block_counts.fetch_add(snapshot.chain_block_counts, Ordering::Relaxed);
+        self.mass_counts.fetch_add(snapshot.mass_counts, Ordering::Relaxed);
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProcessingCountersSnapshot {
     pub blocks_submitted: u64,
     pub header_counts: u64,
@@ -55,3 +68,29 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processing_counters_restore() {
+        let counters = ProcessingCounters::default();
+        counters.blocks_submitted.store(2, Ordering::Relaxed);
+        counters.mass_counts.store(100, Ordering::Relaxed);
+        let persisted = ProcessingCountersSnapshot {
+            blocks_submitted: 10,
+            header_counts: 11,
+            dep_counts: 12,
+            mergeset_counts: 13,
+            body_counts: 14,
+            txs_counts: 15,
+            chain_block_counts: 16,
+            mass_counts: 17,
+        };
+
+        // Restored counts add up with the ones accumulated so far
+        counters.restore(&persisted);
+        assert_eq!(counters.snapshot(), ProcessingCountersSnapshot { blocks_submitted: 12, mass_counts: 117, ..persisted });
+    }
+}
@@ -23,9 +23,19 @@ use crate::{
 };
 use kaspa_hashes::Hash;
 
-pub use self::stats::{BlockCount, ConsensusStats};
+pub use self::selection::{ExcludedParentCandidate, ParentExclusionReason, VirtualParentsSelection};
+pub use self::stats::{BlockCount, ConsensusStats, DagStats};
+
+/// The GHOSTDAG coloring of a block from the perspective of the virtual
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockColor {
+    pub is_blue: bool,
+    /// The selected chain block having the block in its mergeset
+    pub merging_block: Hash,
+}
 
 pub mod counters;
+pub mod selection;
 pub mod stats;
 
 pub type BlockValidationFuture = BoxFuture<'static, BlockProcessResult<BlockStatus>>;
@@ -151,6 +161,41 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Samples `count` selected chain blocks, with replacement, having a blue score in `[low_blue_score, high_blue_score]`,
+    /// each block being drawn with a probability proportional to the work it adds to the chain. The range must lie above
+    /// the pruning point.
+    ///
+    /// Returns the sampled hashes in ascending blue score order.
+    fn sample_chain_blocks_by_work(&self, low_blue_score: u64, high_blue_score: u64, count: usize) -> ConsensusResult<Vec<Hash>> {
+        unimplemented!()
+    }
+
+    /// Returns the lowest selected chain block having a blue score greater than or equal to `blue_score`. The blue score
+    /// must lie between those of the pruning point and of the sink.
+    fn get_chain_block_by_blue_score(&self, blue_score: u64) -> ConsensusResult<Hash> {
+        unimplemented!()
+    }
+
+    /// Returns the lowest selected chain block having a DAA score greater than or equal to `daa_score`. The DAA score
+    /// must lie between those of the pruning point and of the sink.
+    fn get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        unimplemented!()
+    }
+
+    /// Returns the selected chain block with the timestamp nearest to `timestamp`, in milliseconds, favoring the lowest
+    /// block on ties. Timestamps above the one of the sink resolve to the sink while timestamps below the one of the
+    /// pruning point are rejected.
+    fn get_chain_block_by_timestamp(&self, timestamp: u64) -> ConsensusResult<Hash> {
+        unimplemented!()
+    }
+
+    /// Returns the headers of up to `limit` selected chain blocks starting at `start`, included, and going towards
+    /// the sink if `is_ascending` or towards the pruning point otherwise. `start` must be a selected chain block
+    /// above or at the pruning point.
+    fn get_chain_headers(&self, start: Hash, limit: usize, is_ascending: bool) -> ConsensusResult<Vec<Arc<Header>>> {
+        unimplemented!()
+    }
+
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
         unimplemented!()
     }
@@ -163,6 +208,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the outcome of the last virtual parent selection, including the excluded candidates
+    fn get_virtual_parents_selection(&self) -> VirtualParentsSelection {
+        unimplemented!()
+    }
+
     fn get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -291,6 +341,19 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns whether `hash` is currently blue or red from the perspective of the virtual, along with the selected chain
+    /// block merging it. Returns `None` if `hash` was not merged by the selected chain yet, i.e. is not in the past of the sink.
+    fn get_current_block_color(&self, hash: Hash) -> ConsensusResult<Option<BlockColor>> {
+        unimplemented!()
+    }
+
+    /// Resolves a finality conflict by switching the virtual selected chain to the chain of `finality_block_hash`,
+    /// disqualifying the conflicting blocks of the current selected chain. Meant to be directed by the node operator.
+    /// Fails unless `finality_block_hash` is on the chain of a detected finality conflict.
+    fn resolve_finality_conflict(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        unimplemented!()
+    }
+
     fn get_pruning_point_utxos(
         &self,
         expected_pruning_point: Hash,
@@ -320,7 +383,21 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
-    fn estimate_network_hashes_per_second(&self, start_hash: Option<Hash>, window_size: usize) -> ConsensusResult<u64> {
+    /// Estimates the network hashrate over a window of `window_size` blocks ending at `start_hash` (or the virtual if `None`),
+    /// failing if the window holds less than `min_window_size` blocks. A `start_hash` window includes the block itself, so
+    /// anchoring at the sink matches the virtual estimate and any past chain block can serve as a historical sample point
+    fn estimate_network_hashes_per_second(
+        &self,
+        start_hash: Option<Hash>,
+        window_size: usize,
+        min_window_size: usize,
+    ) -> ConsensusResult<u64> {
+        unimplemented!()
+    }
+
+    /// Returns structural DAG stats over the mergesets of the last `window_size` selected chain blocks
+    /// (bounded by the pruning point)
+    fn get_dag_stats(&self, window_size: usize) -> DagStats {
         unimplemented!()
     }
 
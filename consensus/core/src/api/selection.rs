@@ -0,0 +1,40 @@
+use kaspa_hashes::Hash;
+
+/// Reason for a virtual parent candidate not being selected as a virtual parent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParentExclusionReason {
+    /// The candidate was left out of the random sample taken when candidates exceed the max number considered
+    NotSampled,
+    /// The max number of block parents was already reached
+    MaxBlockParents,
+    /// Merging the candidate would make the virtual mergeset exceed the mergeset size limit
+    MergesetSizeLimit,
+    /// Merging the candidate would make virtual merge a red block violating the bounded merge depth rule
+    BoundedMergeDepth,
+}
+
+/// A virtual parent candidate excluded from the virtual parents
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExcludedParentCandidate {
+    pub hash: Hash,
+    pub reason: ParentExclusionReason,
+}
+
+impl ExcludedParentCandidate {
+    pub fn new(hash: Hash, reason: ParentExclusionReason) -> Self {
+        Self { hash, reason }
+    }
+}
+
+/// Outcome of the last virtual parent selection
+#[derive(Clone, Debug, Default)]
+pub struct VirtualParentsSelection {
+    /// The virtual selected parent, aka the sink
+    pub selected_parent: Hash,
+
+    /// The virtual parents, selected parent first
+    pub parents: Vec<Hash>,
+
+    /// The candidates which were considered and excluded, in order of exclusion
+    pub excluded: Vec<ExcludedParentCandidate>,
+}
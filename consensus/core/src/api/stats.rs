@@ -33,3 +33,22 @@ pub struct ConsensusStats {
     /// Virtual-related stats
     pub virtual_stats: VirtualStateStats,
 }
+
+/// Structural DAG stats aggregated over the mergesets of a window of recent selected chain blocks
+#[derive(Clone, Debug, Default)]
+pub struct DagStats {
+    /// Overall number of current DAG tips
+    pub num_tips: u64,
+
+    /// Number of selected chain blocks in the window
+    pub chain_block_count: u64,
+
+    /// Number of blocks merged by the chain blocks of the window
+    pub merged_block_count: u64,
+
+    /// Number of merged blocks which are red
+    pub red_block_count: u64,
+
+    /// Overall number of direct parents of the merged blocks
+    pub parent_count: u64,
+}
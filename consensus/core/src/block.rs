@@ -4,8 +4,10 @@ use crate::{
     tx::{Transaction, TransactionId},
     BlueWorkType,
 };
+use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_hashes::Hash;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr, sync::Arc};
 
 /// A mutable block structure where header and transactions within can still be mutated.
 #[derive(Debug, Clone)]
@@ -82,6 +84,49 @@ pub trait TemplateTransactionSelector {
 
     /// Determine whether this was an overall successful selection episode
     fn is_successful(&self) -> bool;
+
+    /// Orders the final set of selected transactions before the block template gets built.
+    /// The transactions must remain sorted by subnetwork id. Keeps the selection order by default.
+    fn order_transactions(&self, _txs: &mut [Transaction]) {}
+}
+
+/// Ordering of the transactions of a block template
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TemplateTxOrdering {
+    /// The order in which the transactions were selected, which depends on the mempool internal state
+    #[default]
+    Selection,
+    /// Subnetwork id, then parents before children, then decreasing feerate, then transaction id,
+    /// so a same set of transactions always yields a same template
+    Deterministic,
+}
+
+impl TemplateTxOrdering {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateTxOrdering::Selection => "selection",
+            TemplateTxOrdering::Deterministic => "deterministic",
+        }
+    }
+}
+
+impl Display for TemplateTxOrdering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TemplateTxOrdering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "selection" => Ok(TemplateTxOrdering::Selection),
+            "deterministic" => Ok(TemplateTxOrdering::Deterministic),
+            _ => Err(format!("unknown template transaction ordering {s}, expected one of selection, deterministic")),
+        }
+    }
 }
 
 /// Block template build mode
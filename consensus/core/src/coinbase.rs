@@ -1,4 +1,4 @@
-use crate::tx::{ScriptPublicKey, Transaction};
+use crate::tx::{ScriptPublicKey, ScriptVec, Transaction};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -13,6 +13,18 @@ impl<T: AsRef<[u8]>> MinerData<T> {
     }
 }
 
+/// Returns the script public key of the miner recorded in the payload of a coinbase transaction, if well-formed.
+///
+/// The payload starts with the blue score (u64) and the subsidy (u64), followed by the script public key
+/// version (u16), length (u8) and script, all little-endian. The size limits enforced by consensus are not
+/// checked, the payload being expected to come from a valid block.
+pub fn coinbase_payload_script_public_key(payload: &[u8]) -> Option<ScriptPublicKey> {
+    let version = u16::from_le_bytes(payload.get(16..18)?.try_into().ok()?);
+    let len = *payload.get(18)? as usize;
+    let script = payload.get(19..19 + len)?;
+    Some(ScriptPublicKey::new(version, ScriptVec::from_slice(script)))
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct CoinbaseData<T: AsRef<[u8]> = Vec<u8>> {
     pub blue_score: u64,
@@ -146,12 +146,16 @@ pub mod perf {
         //
         // Thread-pools
         //
-        /// Defaults to 0 which indicates using system default
-        /// which is typically the number of logical CPU cores
-        pub block_processors_num_threads: usize,
+        /// Number of threads of the header processor pool. Defaults to 0 which indicates
+        /// half the number of logical CPU cores, see [`PerfParams::header_processor_threads`]
+        pub header_processor_num_threads: usize,
 
-        /// Defaults to 0 which indicates using system default
-        /// which is typically the number of logical CPU cores
+        /// Number of threads of the body processor pool. Defaults to 0 which indicates
+        /// the number of logical CPU cores
+        pub body_processor_num_threads: usize,
+
+        /// Number of threads of the virtual processor pool, used for parallel transaction
+        /// verification. Defaults to 0 which indicates the number of logical CPU cores
         pub virtual_processor_num_threads: usize,
     }
 
@@ -160,7 +164,8 @@ pub mod perf {
         block_data_cache_size: BASELINE_BLOCK_DATA_CACHE_SIZE,
         utxo_set_cache_size: BASELINE_UTXOSET_CACHE_SIZE,
         block_window_cache_size: BASELINE_BLOCK_WINDOW_CACHE_SIZE,
-        block_processors_num_threads: 0,
+        header_processor_num_threads: 0,
+        body_processor_num_threads: 0,
         virtual_processor_num_threads: 0,
     };
 
@@ -169,12 +174,37 @@ pub mod perf {
             // Allow caching up to 10x over the baseline
             self.block_data_cache_size *= consensus_params.bps().clamp(1, 10) as usize;
         }
+
+        /// Header validation is much lighter than body and UTXO validation so by default the header pool gets half
+        /// of the cores, leaving the others for the RPC and p2p services while the node syncs headers
+        pub fn header_processor_threads(&self) -> usize {
+            Self::num_threads_or(self.header_processor_num_threads, |cores| (cores / 2).max(1))
+        }
+
+        pub fn body_processor_threads(&self) -> usize {
+            Self::num_threads_or(self.body_processor_num_threads, |cores| cores)
+        }
+
+        pub fn virtual_processor_threads(&self) -> usize {
+            Self::num_threads_or(self.virtual_processor_num_threads, |cores| cores)
+        }
+
+        /// Returns `num_threads` if set or else the default derived from the number of logical CPU cores
+        fn num_threads_or(num_threads: usize, default: impl FnOnce(usize) -> usize) -> usize {
+            match num_threads {
+                0 => default(std::thread::available_parallelism().map_or(1, |cores| cores.get())),
+                num_threads => num_threads,
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::consensus::{MAX_DIFFICULTY_TARGET, MAX_DIFFICULTY_TARGET_AS_F64};
+    use super::{
+        consensus::{MAX_DIFFICULTY_TARGET, MAX_DIFFICULTY_TARGET_AS_F64},
+        perf::PERF_PARAMS,
+    };
     use kaspa_math::Uint256;
 
     #[test]
@@ -182,4 +212,18 @@ mod tests {
         assert_eq!(MAX_DIFFICULTY_TARGET, Uint256::from_u64(1).wrapping_shl(255) - 1.into());
         assert_eq!(MAX_DIFFICULTY_TARGET_AS_F64, MAX_DIFFICULTY_TARGET.as_f64());
     }
+
+    #[test]
+    fn test_perf_params_num_threads() {
+        let cores = std::thread::available_parallelism().unwrap().get();
+        let mut perf = PERF_PARAMS;
+        assert_eq!(perf.header_processor_threads(), (cores / 2).max(1));
+        assert_eq!(perf.body_processor_threads(), cores);
+        assert_eq!(perf.virtual_processor_threads(), cores);
+
+        perf.header_processor_num_threads = 3;
+        perf.body_processor_num_threads = 5;
+        perf.virtual_processor_num_threads = 7;
+        assert_eq!((perf.header_processor_threads(), perf.body_processor_threads(), perf.virtual_processor_threads()), (3, 5, 7));
+    }
 }
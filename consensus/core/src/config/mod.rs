@@ -3,6 +3,8 @@ pub mod constants;
 pub mod genesis;
 pub mod params;
 
+use crate::block::TemplateTxOrdering;
+use kaspa_core::time::{DynClock, SystemClock};
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
 
 #[cfg(feature = "devnet-prealloc")]
@@ -12,6 +14,9 @@ use std::sync::Arc;
 
 use std::ops::Deref;
 
+/// Default lowest number of blocks a window must hold for the network hashrate to be estimated over it
+pub const DEFAULT_MIN_HASHRATE_ESTIMATION_WINDOW_SIZE: usize = 1000;
+
 use {
     constants::perf::{PerfParams, PERF_PARAMS},
     params::Params,
@@ -35,6 +40,16 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Blue score depth below the sink beyond which block bodies are moved to the cold store, when the node
+    /// is configured with one
+    pub cold_store_depth: u64,
+
+    /// zstd compression level of the block transactions at rest, if compressed
+    pub block_transactions_compression_level: Option<i32>,
+
+    /// zstd compression level of the acceptance data at rest, if compressed
+    pub acceptance_data_compression_level: Option<i32>,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -42,9 +57,15 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the transaction index
+    pub txindex: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
+    /// Run as a read-only secondary instance over the databases of a primary node
+    pub is_secondary: bool,
+
     /// Allow the node to accept blocks from RPC while not synced
     /// (required when initiating a new network from genesis)
     pub enable_unsynced_mining: bool,
@@ -52,6 +73,10 @@ pub struct Config {
     /// Allow mainnet mining. Until a stable Beta version we keep this option off by default
     pub enable_mainnet_mining: bool,
 
+    /// Allow RPC clients to delegate UTXO selection and unsigned transaction construction
+    /// to the node. Off by default since it links the caller's addresses on the node side
+    pub enable_transaction_builder_rpc: bool,
+
     pub user_agent_comments: Vec<String>,
 
     /// If undefined, sets it to 0.0.0.0
@@ -61,6 +86,13 @@ pub struct Config {
 
     pub block_template_cache_lifetime: Option<u64>,
 
+    /// Window, in milliseconds, during which consecutive UtxosChanged notifications are merged before being
+    /// sent to the RPC clients. Disabled if undefined
+    pub utxos_changed_coalescing_window: Option<u64>,
+
+    /// Ordering of the transactions of the block templates
+    pub template_tx_ordering: TemplateTxOrdering,
+
     #[cfg(feature = "devnet-prealloc")]
     pub initial_utxo_set: Arc<UtxoCollection>,
 
@@ -68,6 +100,18 @@ pub struct Config {
 
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
+
+    /// Lowest number of blocks a window must hold for the network hashrate to be estimated over it.
+    /// RPC clients may require more but not less
+    pub min_hashrate_estimation_window_size: usize,
+
+    /// Keep the consensus databases entirely in memory, never touching the filesystem.
+    /// Meant for tests and simulations, the whole state is lost on shutdown
+    pub ephemeral: bool,
+
+    /// The source of wall-clock time used for timestamp validation and by the p2p flows.
+    /// The system clock unless a virtual clock is injected by a simulation or a test
+    pub clock: DynClock,
 }
 
 impl Config {
@@ -76,25 +120,40 @@ impl Config {
     }
 
     pub fn with_perf(params: Params, perf: PerfParams) -> Self {
+        let cold_store_depth = params.finality_depth;
+        // Young networks would otherwise not be able to estimate their hashrate for a long while
+        let min_hashrate_estimation_window_size =
+            if params.net.is_mainnet() { DEFAULT_MIN_HASHRATE_ESTIMATION_WINDOW_SIZE } else { params.min_difficulty_window_len };
         Self {
             params,
             perf,
             process_genesis: true,
             is_archival: false,
+            cold_store_depth,
+            block_transactions_compression_level: None,
+            acceptance_data_compression_level: None,
             enable_sanity_checks: false,
             utxoindex: false,
+            txindex: false,
             unsafe_rpc: false,
+            is_secondary: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
+            enable_transaction_builder_rpc: false,
             user_agent_comments: Default::default(),
             externalip: None,
             p2p_listen_address: ContextualNetAddress::unspecified(),
             block_template_cache_lifetime: None,
+            utxos_changed_coalescing_window: None,
+            template_tx_ordering: Default::default(),
 
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
             ram_scale: 1.0,
+            min_hashrate_estimation_window_size,
+            ephemeral: false,
+            clock: SystemClock::new_dyn(),
         }
     }
 
@@ -172,7 +231,33 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn set_ephemeral(mut self) -> Self {
+        self.config.ephemeral = true;
+        self
+    }
+
+    pub fn set_clock(mut self, clock: DynClock) -> Self {
+        self.config.clock = clock;
+        self
+    }
+
     pub fn build(self) -> Config {
         self.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::params::{DEVNET_PARAMS, MAINNET_PARAMS, TESTNET_PARAMS};
+
+    #[test]
+    fn test_min_hashrate_estimation_window_size() {
+        assert_eq!(Config::new(MAINNET_PARAMS).min_hashrate_estimation_window_size, DEFAULT_MIN_HASHRATE_ESTIMATION_WINDOW_SIZE);
+        // Other networks only require the shortest difficulty window
+        for params in [TESTNET_PARAMS, DEVNET_PARAMS] {
+            let min_difficulty_window_len = params.min_difficulty_window_len;
+            assert_eq!(Config::new(params).min_hashrate_estimation_window_size, min_difficulty_window_len);
+        }
+    }
+}
@@ -9,9 +9,11 @@ use crate::{
     BlockLevel, KType,
 };
 use kaspa_addresses::Prefix;
+use kaspa_hashes::Hash;
 use kaspa_math::Uint256;
 use std::{
     cmp::min,
+    fmt::{Display, Formatter},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -83,6 +85,9 @@ pub struct Params {
     /// DAA score from which storage mass calculation and transaction mass field are activated as a consensus rule
     pub storage_mass_activation_daa_score: u64,
 
+    /// DAA score after which transactions of version [`TX_VERSION_2`](crate::constants::TX_VERSION_2) are consensus-valid
+    pub tx_version_2_activation_daa_score: u64,
+
     /// DAA score after which the pre-deflationary period switches to the deflationary period
     pub deflationary_phase_daa_score: u64,
 
@@ -257,11 +262,56 @@ impl Params {
         self.net.default_rpc_port()
     }
 
+    /// Derives the default network bindings implied by these params
+    pub fn network_bindings(&self) -> NetworkBindings {
+        NetworkBindings {
+            network_id: self.net,
+            p2p_port: self.default_p2p_port(),
+            rpc_port: self.default_rpc_port(),
+            borsh_rpc_port: self.net.default_borsh_rpc_port(),
+            json_rpc_port: self.net.default_json_rpc_port(),
+            http_rpc_port: self.net.default_http_rpc_port(),
+            prefix: self.prefix(),
+            genesis_hash: self.genesis.hash,
+        }
+    }
+
     pub fn finality_duration(&self) -> u64 {
         self.target_time_per_block * self.finality_depth
     }
 }
 
+/// The network bindings derived from a [`Params`] instance: the default ports, the address prefix
+/// and the genesis hash identifying the network a node runs on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NetworkBindings {
+    pub network_id: NetworkId,
+    pub p2p_port: u16,
+    pub rpc_port: u16,
+    pub borsh_rpc_port: u16,
+    pub json_rpc_port: u16,
+    pub http_rpc_port: u16,
+    pub prefix: Prefix,
+    pub genesis_hash: Hash,
+}
+
+impl Display for NetworkBindings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "network {}, P2P port {}, gRPC port {}, wRPC Borsh port {}, wRPC JSON port {}, HTTP port {}, address prefix {}, genesis {}",
+            self.network_id,
+            self.p2p_port,
+            self.rpc_port,
+            self.borsh_rpc_port,
+            self.json_rpc_port,
+            self.http_rpc_port,
+            self.prefix,
+            self.genesis_hash
+        )
+    }
+}
+
 impl From<NetworkType> for Params {
     fn from(value: NetworkType) -> Self {
         match value {
@@ -349,6 +399,7 @@ pub const MAINNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    tx_version_2_activation_daa_score: u64::MAX,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -408,6 +459,7 @@ pub const TESTNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    tx_version_2_activation_daa_score: u64::MAX,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -474,6 +526,7 @@ pub const TESTNET11_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: 0,
+    tx_version_2_activation_daa_score: u64::MAX,
 
     skip_proof_of_work: false,
     max_block_level: 250,
@@ -526,6 +579,7 @@ pub const SIMNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: 0,
+    tx_version_2_activation_daa_score: u64::MAX,
 
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
@@ -572,6 +626,7 @@ pub const DEVNET_PARAMS: Params = Params {
 
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation_daa_score: u64::MAX,
+    tx_version_2_activation_daa_score: u64::MAX,
 
     // deflationary_phase_daa_score is the DAA score after which the pre-deflationary period
     // switches to the deflationary period. This number is calculated as follows:
@@ -586,3 +641,24 @@ pub const DEVNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: 1000,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_bindings_are_distinct() {
+        let bindings = NetworkType::iter().map(|nt| Params::from(nt).network_bindings()).collect::<Vec<_>>();
+        for (i, a) in bindings.iter().enumerate() {
+            for b in bindings.iter().skip(i + 1) {
+                assert_ne!(a.p2p_port, b.p2p_port, "{} and {} share a P2P port", a.network_id, b.network_id);
+                assert_ne!(a.rpc_port, b.rpc_port, "{} and {} share a gRPC port", a.network_id, b.network_id);
+                assert_ne!(a.borsh_rpc_port, b.borsh_rpc_port, "{} and {} share a wRPC Borsh port", a.network_id, b.network_id);
+                assert_ne!(a.json_rpc_port, b.json_rpc_port, "{} and {} share a wRPC JSON port", a.network_id, b.network_id);
+                assert_ne!(a.http_rpc_port, b.http_rpc_port, "{} and {} share an HTTP port", a.network_id, b.network_id);
+                assert_ne!(a.prefix, b.prefix, "{} and {} share an address prefix", a.network_id, b.network_id);
+                assert_ne!(a.genesis_hash, b.genesis_hash, "{} and {} share a genesis", a.network_id, b.network_id);
+            }
+        }
+    }
+}
@@ -4,6 +4,10 @@ pub const BLOCK_VERSION: u16 = 1;
 /// TX_VERSION is the current latest supported transaction version.
 pub const TX_VERSION: u16 = 0;
 
+/// TX_VERSION_2 is the next transaction version, only accepted by consensus once activated, see
+/// [`Params::tx_version_2_activation_daa_score`](crate::config::params::Params::tx_version_2_activation_daa_score).
+pub const TX_VERSION_2: u16 = 2;
+
 pub const LOCK_TIME_THRESHOLD: u64 = 500_000_000_000;
 
 /// MAX_SCRIPT_PUBLIC_KEY_VERSION is the current latest supported public key script version.
@@ -17,6 +17,18 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --secondary-datadir cannot be used with --{0}")]
+    SecondaryInstanceConflict(&'static str),
+
+    #[error("Configuration: --grpc-tls-cert and --grpc-tls-key must be used together")]
+    MissingGrpcTlsCertOrKey,
+
+    #[error("Configuration: --grpc-tls-client-ca requires --grpc-tls-cert and --grpc-tls-key")]
+    GrpcClientCaWithoutTls,
+
+    #[error("Configuration: --simnet-coinbase-maturity can only be used with --simnet")]
+    CoinbaseMaturityOverrideOnNonSimnet,
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -32,6 +32,30 @@ pub enum ConsensusError {
     #[error("difficulty error: {0}")]
     DifficultyError(#[from] DifficultyError),
 
+    #[error("invalid blue score range [{0}, {1}]")]
+    InvalidBlueScoreRange(u64, u64),
+
+    #[error("blue score {0} is not above the blue score {1} of the pruning point")]
+    BlueScoreNotAbovePruningPoint(u64, u64),
+
+    #[error("{0} score {1} is out of the selected chain range [{2}, {3}]")]
+    ScoreOutOfSelectedChainRange(&'static str, u64, u64, u64),
+
+    #[error("timestamp {0} is below the timestamp {1} of the pruning point")]
+    TimestampBelowPruningPoint(u64, u64),
+
+    #[error("block {0} is not a selected chain block above the pruning point")]
+    NotSelectedChainBlock(Hash),
+
+    #[error("block {0} is not in the future of the pruning point")]
+    BlockNotInPruningPointFuture(Hash),
+
+    #[error("the pruning point is not on the selected chain of block {0}")]
+    PruningPointNotInChain(Hash),
+
+    #[error("block {0} is not on the chain of a detected finality conflict, hence there is no finality conflict to resolve")]
+    NoFinalityConflict(Hash),
+
     #[error("{0}")]
     General(&'static str),
 }
@@ -20,6 +20,9 @@ pub enum TxRuleError {
     #[error("transaction version {0} is unknown")]
     UnknownTxVersion(u16),
 
+    #[error("transaction version {0} is only valid after DAA score {1}")]
+    TxVersionNotActivated(u16, u64),
+
     #[error("transaction has {0} inputs where the max allowed is {1}")]
     TooManyInputs(usize, usize),
 
@@ -84,6 +84,17 @@ pub struct ChainPath {
     pub removed: Vec<Hash>,
 }
 
+impl ChainPath {
+    /// Truncates the added chain blocks to `max_added`, returning the last block kept if any block was truncated
+    pub fn truncate_added(&mut self, max_added: usize) -> Option<Hash> {
+        if self.added.len() <= max_added {
+            return None;
+        }
+        self.added.truncate(max_added);
+        self.added.last().copied()
+    }
+}
+
 /// `hashes::Hash` writes 4 u64s so we just use the last one as the hash here
 #[derive(Default, Clone, Copy)]
 pub struct BlockHasher(u64);
@@ -123,7 +134,7 @@ pub type BlockLevel = u8;
 
 #[cfg(test)]
 mod tests {
-    use super::BlockHasher;
+    use super::{BlockHasher, ChainPath};
     use kaspa_hashes::Hash;
     use std::hash::{Hash as _, Hasher as _};
     #[test]
@@ -133,4 +144,23 @@ mod tests {
         hash.hash(&mut hasher);
         assert_eq!(hasher.finish(), 4);
     }
+
+    #[test]
+    fn test_chain_path_truncate_added() {
+        let hashes = (1..=5).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let path = || ChainPath { added: hashes.clone(), removed: vec![Hash::from_u64_word(9)] };
+
+        // The last block kept is where the next batch starts from
+        let mut truncated = path();
+        assert_eq!(truncated.truncate_added(2), Some(hashes[1]));
+        assert_eq!(truncated.added, hashes[..2]);
+        assert_eq!(truncated.removed, path().removed);
+
+        // A path fitting in the batch is left intact
+        for max_added in [5, 6] {
+            let mut intact = path();
+            assert_eq!(intact.truncate_added(max_added), None);
+            assert_eq!(intact.added, hashes);
+        }
+    }
 }
@@ -1,9 +1,164 @@
 use crate::{
     subnets::SUBNETWORK_ID_SIZE,
-    tx::{Transaction, TransactionInput, TransactionOutput},
+    tx::{Transaction, TransactionInput, TransactionOutput, VerifiableTransaction},
 };
 use kaspa_hashes::HASH_SIZE;
 
+/// The components of the compute mass of a transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeMassComponents {
+    /// Mass of the estimated serialized size of the transaction
+    pub size_mass: u64,
+    /// Mass of the script public keys of the transaction outputs
+    pub script_public_key_mass: u64,
+    /// Mass of the signature operations of the transaction inputs
+    pub sig_op_mass: u64,
+}
+
+impl ComputeMassComponents {
+    pub fn total(&self) -> u64 {
+        self.size_mass + self.script_public_key_mass + self.sig_op_mass
+    }
+}
+
+/// Calculates the compute mass components of a non-coinbase transaction given the mass parameters
+pub fn calc_tx_compute_mass_components(
+    tx: &Transaction,
+    mass_per_tx_byte: u64,
+    mass_per_script_pub_key_byte: u64,
+    mass_per_sig_op: u64,
+) -> ComputeMassComponents {
+    let size = transaction_estimated_serialized_size(tx);
+    let total_script_public_key_size: u64 =
+        tx.outputs.iter().map(|output| 2 /* script public key version (u16) */ + output.script_public_key.script().len() as u64).sum();
+    let total_sigops: u64 = tx.inputs.iter().map(|input| input.sig_op_count as u64).sum();
+
+    ComputeMassComponents {
+        size_mass: size * mass_per_tx_byte,
+        script_public_key_mass: total_script_public_key_size * mass_per_script_pub_key_byte,
+        sig_op_mass: total_sigops * mass_per_sig_op,
+    }
+}
+
+/// Temp enum for the transition phases of KIP9
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Kip9Version {
+    /// Initial KIP9 mass calculation, w/o the relaxed formula and summing storage mass and compute mass
+    Alpha,
+
+    /// Currently proposed KIP9 mass calculation, with the relaxed formula (for the cases `|O| = 1 OR |O| <= |I| <= 2`),
+    /// and using a maximum operator over storage and compute mass
+    Beta,
+}
+
+// TODO (aspect) - review and potentially merge this with the new MassCalculator currently located in the wallet core
+#[derive(Clone)]
+pub struct MassCalculator {
+    mass_per_tx_byte: u64,
+    mass_per_script_pub_key_byte: u64,
+    mass_per_sig_op: u64,
+    storage_mass_parameter: u64,
+}
+
+impl MassCalculator {
+    pub fn new(mass_per_tx_byte: u64, mass_per_script_pub_key_byte: u64, mass_per_sig_op: u64, storage_mass_parameter: u64) -> Self {
+        Self { mass_per_tx_byte, mass_per_script_pub_key_byte, mass_per_sig_op, storage_mass_parameter }
+    }
+
+    /// Calculates the compute mass of this transaction. This does not include the storage mass calculation below which
+    /// requires full UTXO context
+    pub fn calc_tx_compute_mass(&self, tx: &Transaction) -> u64 {
+        if tx.is_coinbase() {
+            return 0;
+        }
+
+        calc_tx_compute_mass_components(tx, self.mass_per_tx_byte, self.mass_per_script_pub_key_byte, self.mass_per_sig_op).total()
+    }
+
+    /// Calculates the storage mass for this populated transaction.
+    /// Assumptions which must be verified before this call:
+    ///     1. All output values are non-zero
+    ///     2. At least one input (unless coinbase)
+    ///
+    /// Otherwise this function should never fail.
+    pub fn calc_tx_storage_mass(&self, tx: &impl VerifiableTransaction, version: Kip9Version) -> Option<u64> {
+        if tx.is_coinbase() {
+            return Some(0);
+        }
+        /* The code below computes the following formula:
+
+                max( 0 , C·( |O|/H(O) - |I|/A(I) ) )
+
+        where C is the mass storage parameter, O is the set of output values, I is the set of
+        input values, H(S) := |S|/sum_{s in S} 1 / s is the harmonic mean over the set S and
+        A(S) := sum_{s in S} / |S| is the arithmetic mean.
+
+        See KIP-0009 for more details
+        */
+
+        // Since we are doing integer division, we perform the multiplication with C over the inner
+        // fractions, otherwise we'll get a sum of zeros or ones.
+        //
+        // If sum of fractions overflowed (nearly impossible, requires 10^7 outputs for C = 10^12),
+        // we return `None` indicating mass is incomputable
+        //
+        // Note: in theory this can be tighten by subtracting input mass in the process (possibly avoiding the overflow),
+        // however the overflow case is so unpractical with current mass limits so we avoid the hassle
+        let harmonic_outs = tx
+            .tx()
+            .outputs
+            .iter()
+            .map(|out| self.storage_mass_parameter / out.value)
+            .try_fold(0u64, |total, current| total.checked_add(current))?; // C·|O|/H(O)
+
+        let outs_len = tx.tx().outputs.len() as u64;
+        let ins_len = tx.tx().inputs.len() as u64;
+
+        /*
+          KIP-0009 relaxed formula for the cases |O| = 1 OR |O| <= |I| <= 2:
+              max( 0 , C·( |O|/H(O) - |I|/H(I) ) )
+
+           Note: in the case |I| = 1 both formulas are equal, yet the following code (harmonic_ins) is a bit more efficient.
+                 Hence, we transform the condition to |O| = 1 OR |I| = 1 OR |O| = |I| = 2 which is equivalent (and faster).
+        */
+        if version == Kip9Version::Beta && (outs_len == 1 || ins_len == 1 || (outs_len == 2 && ins_len == 2)) {
+            let harmonic_ins = tx
+                .populated_inputs()
+                .map(|(_, entry)| self.storage_mass_parameter / entry.amount)
+                .fold(0u64, |total, current| total.saturating_add(current)); // C·|I|/H(I)
+            return Some(harmonic_outs.saturating_sub(harmonic_ins)); // max( 0 , C·( |O|/H(O) - |I|/H(I) ) );
+        }
+
+        // Total supply is bounded, so a sum of existing UTXO entries cannot overflow (nor can it be zero)
+        let sum_ins = tx.populated_inputs().map(|(_, entry)| entry.amount).sum::<u64>(); // |I|·A(I)
+        let mean_ins = sum_ins / ins_len;
+
+        // Inner fraction must be with C and over the mean value, in order to maximize precision.
+        // We can saturate the overall expression at u64::MAX since we lower-bound the subtraction below by zero anyway
+        let arithmetic_ins = ins_len.saturating_mul(self.storage_mass_parameter / mean_ins); // C·|I|/A(I)
+
+        Some(harmonic_outs.saturating_sub(arithmetic_ins)) // max( 0 , C·( |O|/H(O) - |I|/A(I) ) )
+    }
+
+    /// Calculates the overall mass of this transaction, combining both compute and storage masses.
+    /// The combination strategy depends on the version passed.
+    pub fn calc_tx_overall_mass(
+        &self,
+        tx: &impl VerifiableTransaction,
+        cached_compute_mass: Option<u64>,
+        version: Kip9Version,
+    ) -> Option<u64> {
+        match version {
+            Kip9Version::Alpha => self
+                .calc_tx_storage_mass(tx, version)
+                .and_then(|mass| mass.checked_add(cached_compute_mass.unwrap_or_else(|| self.calc_tx_compute_mass(tx.tx())))),
+            Kip9Version::Beta => self
+                .calc_tx_storage_mass(tx, version)
+                .map(|mass| mass.max(cached_compute_mass.unwrap_or_else(|| self.calc_tx_compute_mass(tx.tx())))),
+        }
+    }
+}
+
 // transaction_estimated_serialized_size is the estimated size of a transaction in some
 // serialization. This has to be deterministic, but not necessarily accurate, since
 // it's only used as the size component in the transaction and block mass limit
@@ -55,3 +210,38 @@ pub fn transaction_output_estimated_serialized_size(output: &TransactionOutput)
     size += output.script_public_key.script().len() as u64;
     size
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        subnets::{SubnetworkId, SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionOutpoint},
+    };
+    use kaspa_hashes::Hash;
+
+    fn transaction(subnetwork_id: SubnetworkId) -> Transaction {
+        let inputs = (0..2)
+            .map(|i| TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(i), 0), vec![0; 66], 0, (i + 1) as u8))
+            .collect();
+        let outputs = (0..2).map(|i| TransactionOutput::new(i + 1, ScriptPublicKey::from_vec(0, vec![0; 34]))).collect();
+        Transaction::new(0, inputs, outputs, 0, subnetwork_id, 0, vec![0; 4])
+    }
+
+    #[test]
+    fn test_compute_mass_components() {
+        let tx = transaction(SUBNETWORK_ID_NATIVE);
+        let components = calc_tx_compute_mass_components(&tx, 1, 10, 1000);
+
+        // version, inputs, outputs, lock time, subnetwork id, gas, payload hash and payload
+        let size = 10 + 2 * (36 + 8 + 66 + 8) + 8 + 2 * (8 + 2 + 8 + 34) + 8 + 20 + 8 + 32 + 8 + 4;
+        assert_eq!(transaction_estimated_serialized_size(&tx), size);
+        assert_eq!(components, ComputeMassComponents { size_mass: size, script_public_key_mass: 2 * 36 * 10, sig_op_mass: 3 * 1000 });
+        assert_eq!(components.total(), size + 720 + 3000);
+
+        // The calculator matches the components while ignoring coinbase transactions
+        let mass_calculator = MassCalculator::new(1, 10, 1000, 0);
+        assert_eq!(mass_calculator.calc_tx_compute_mass(&tx), components.total());
+        assert_eq!(mass_calculator.calc_tx_compute_mass(&transaction(SUBNETWORK_ID_COINBASE)), 0);
+    }
+}
@@ -0,0 +1,75 @@
+#![no_main]
+
+use kaspa_consensus::{
+    model::stores::block_window_cache::WindowOrigin,
+    processes::difficulty::{FullDifficultyManager, SampledDifficultyManager},
+    test_helpers::{build_difficulty_window, reference_difficulty_bits},
+};
+use kaspa_consensus_core::config::params::MIN_DIFFICULTY_WINDOW_LEN;
+use kaspa_math::Uint256;
+use libfuzzer_sys::fuzz_target;
+
+const GENESIS_BITS: u32 = 0x1e7fffff;
+const TARGET_TIME_PER_BLOCK: u64 = 1000;
+const WINDOW_SIZE: usize = 263;
+
+fn consume<const N: usize>(data: &mut &[u8]) -> Option<[u8; N]> {
+    if data.len() < N {
+        None
+    } else {
+        let ret = &data[..N];
+        *data = &(*data)[N..];
+        Some(ret.try_into().unwrap())
+    }
+}
+
+/// Consumes 12 bytes per block, keeping only blocks with a target within the max target
+fn generate_blocks(data: &mut &[u8], max_target: Uint256) -> Vec<(u64, u32)> {
+    let mut blocks = Vec::with_capacity(WINDOW_SIZE);
+    while blocks.len() < WINDOW_SIZE {
+        let (Some(timestamp), Some(bits)) = (consume(data), consume(data)) else { break };
+        let (timestamp, bits) = (u64::from_le_bytes(timestamp), u32::from_le_bytes(bits));
+        // Header validation rejects bits encoding a target above the max target
+        if bits >> 24 <= 0x20 && Uint256::from_compact_target_bits(bits) <= max_target {
+            blocks.push((timestamp, bits));
+        }
+    }
+    blocks
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut data = data;
+    let Some([sample_rate]) = consume::<1>(&mut data) else { return };
+    let sample_rate = (sample_rate as u64 % 16) + 1;
+    let max_target = Uint256::from_compact_target_bits(GENESIS_BITS);
+    let blocks = generate_blocks(&mut data, max_target);
+    let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Full);
+
+    let full = FullDifficultyManager::new(
+        store.clone(),
+        GENESIS_BITS,
+        max_target,
+        WINDOW_SIZE,
+        MIN_DIFFICULTY_WINDOW_LEN,
+        TARGET_TIME_PER_BLOCK * sample_rate,
+    );
+    let sampled = SampledDifficultyManager::new(
+        store,
+        GENESIS_BITS,
+        max_target,
+        WINDOW_SIZE,
+        MIN_DIFFICULTY_WINDOW_LEN,
+        sample_rate,
+        TARGET_TIME_PER_BLOCK,
+    );
+
+    let full_bits = full.calculate_difficulty_bits(&window);
+    let sampled_bits = sampled.calculate_difficulty_bits(&window);
+    assert!(Uint256::from_compact_target_bits(full_bits) <= max_target, "bits {full_bits:#x} exceed the max target");
+    assert_eq!(full_bits, sampled_bits, "sample rate: {sample_rate}, blocks: {blocks:?}");
+    assert_eq!(
+        full_bits,
+        reference_difficulty_bits(&blocks, GENESIS_BITS, MIN_DIFFICULTY_WINDOW_LEN, TARGET_TIME_PER_BLOCK * sample_rate, max_target),
+        "sample rate: {sample_rate}, blocks: {blocks:?}"
+    );
+});
@@ -3,17 +3,18 @@ use super::utxo_set_override::{set_genesis_utxo_commitment_from_config, set_init
 use super::{ctl::Ctl, Consensus};
 use crate::{model::stores::U64Key, pipeline::ProcessingCounters};
 use itertools::Itertools;
-use kaspa_consensus_core::config::Config;
+use kaspa_consensus_core::{config::Config, network::NetworkId};
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensusmanager::{ConsensusFactory, ConsensusInstance, DynConsensusCtl, SessionLock};
 use kaspa_core::{debug, time::unix_now, warn};
 use kaspa_database::{
+    metrics::StorageMetricsProvider,
     prelude::{
         BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError, StoreResult, StoreResultExtensions, DB,
     },
     registry::DatabaseStorePrefixes,
 };
-
+use kaspa_hashes::Hash;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
@@ -60,6 +61,11 @@ pub struct MultiConsensusMetadata {
 }
 
 const LATEST_DB_VERSION: u32 = 3;
+
+/// Property keys recording the network the databases were created for
+const NETWORK_ID_PROP_KEY: &[u8] = b"network-id";
+const GENESIS_HASH_PROP_KEY: &[u8] = b"genesis-hash";
+
 impl Default for MultiConsensusMetadata {
     fn default() -> Self {
         Self {
@@ -109,6 +115,15 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    /// The entry of the active consensus, if one exists. None otherwise
+    pub fn current_consensus_entry(&self) -> StoreResult<Option<ConsensusEntry>> {
+        let metadata = self.metadata.read()?;
+        match metadata.current_consensus_key {
+            Some(key) => Ok(Some(self.entries.read(key.into())?)),
+            None => Ok(None),
+        }
+    }
+
     /// The entry type signifies whether the returned entry is an existing/new consensus
     pub fn active_consensus_entry(&mut self) -> StoreResult<ConsensusEntryType> {
         let mut metadata = self.metadata.read()?;
@@ -219,6 +234,34 @@ impl MultiConsensusManagementStore {
         }
     }
 
+    /// The network id and genesis hash the databases were created for, if recorded. None otherwise
+    pub fn network_identity(&self) -> StoreResult<Option<(NetworkId, Hash)>> {
+        let metadata = match self.metadata.read() {
+            Ok(data) => data,
+            Err(StoreError::KeyNotFound(_)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let (Some(network_id), Some(genesis_hash)) =
+            (metadata.props.get(NETWORK_ID_PROP_KEY), metadata.props.get(GENESIS_HASH_PROP_KEY))
+        else {
+            return Ok(None);
+        };
+        let parse_error =
+            |prop: &str| StoreError::DataInconsistency(format!("invalid {prop} property in the multi-consensus metadata"));
+        let network_id = std::str::from_utf8(network_id).ok().and_then(|x| x.parse().ok()).ok_or_else(|| parse_error("network id"))?;
+        let genesis_hash =
+            std::str::from_utf8(genesis_hash).ok().and_then(|x| x.parse().ok()).ok_or_else(|| parse_error("genesis hash"))?;
+        Ok(Some((network_id, genesis_hash)))
+    }
+
+    /// Records the network id and genesis hash the databases are used for
+    pub fn set_network_identity(&mut self, network_id: NetworkId, genesis_hash: Hash) -> StoreResult<()> {
+        let mut metadata = self.metadata.read()?;
+        metadata.props.insert(NETWORK_ID_PROP_KEY.to_vec(), network_id.to_string().into_bytes());
+        metadata.props.insert(GENESIS_HASH_PROP_KEY.to_vec(), genesis_hash.to_string().into_bytes());
+        self.metadata.write(DirectDbWriter::new(&self.db), &metadata)
+    }
+
     pub fn should_upgrade(&self) -> StoreResult<bool> {
         match self.metadata.read() {
             Ok(data) => Ok(data.version != LATEST_DB_VERSION),
@@ -229,14 +272,21 @@ impl MultiConsensusManagementStore {
 }
 
 pub struct Factory {
+    management_db: Arc<DB>,
     management_store: Arc<RwLock<MultiConsensusManagementStore>>,
     config: Config,
     db_root_dir: PathBuf,
+    cold_db_root_dir: Option<PathBuf>,
+    /// Root dir of the secondary instances of the consensus DBs, if this node is a read-only secondary instance
+    /// of a primary node owning the DBs
+    secondary_db_root_dir: Option<PathBuf>,
     db_parallelism: usize,
     notification_root: Arc<ConsensusNotificationRoot>,
     counters: Arc<ProcessingCounters>,
     tx_script_cache_counters: Arc<TxScriptCacheCounters>,
     fd_budget: i32,
+    /// Reports the storage statistics of the consensus DBs, if set
+    storage_metrics: Option<Arc<StorageMetricsProvider>>,
 }
 
 impl Factory {
@@ -244,6 +294,8 @@ impl Factory {
         management_db: Arc<DB>,
         config: &Config,
         db_root_dir: PathBuf,
+        cold_db_root_dir: Option<PathBuf>,
+        secondary_db_root_dir: Option<PathBuf>,
         db_parallelism: usize,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
@@ -255,26 +307,113 @@ impl Factory {
         #[cfg(feature = "devnet-prealloc")]
         set_genesis_utxo_commitment_from_config(&mut config);
         config.process_genesis = false;
-        let management_store = Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db)));
-        management_store.write().set_is_archival_node(config.is_archival);
+        let management_store = Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db.clone())));
+        if secondary_db_root_dir.is_none() {
+            management_store.write().set_is_archival_node(config.is_archival);
+        }
         let factory = Self {
+            management_db,
             management_store,
             config,
             db_root_dir,
+            cold_db_root_dir,
+            secondary_db_root_dir,
             db_parallelism,
             notification_root,
             counters,
             tx_script_cache_counters,
             fd_budget,
+            storage_metrics: None,
         };
         factory.delete_inactive_consensus_entries();
         factory
     }
+
+    /// Registers every consensus DB opened by this factory to `storage_metrics`
+    pub fn with_storage_metrics(self, storage_metrics: Arc<StorageMetricsProvider>) -> Self {
+        Self { storage_metrics: Some(storage_metrics), ..self }
+    }
+
+    fn is_secondary(&self) -> bool {
+        self.secondary_db_root_dir.is_some()
+    }
+
+    /// Files limit of a single consensus DB. Active and staging consensuses should have equal budgets,
+    /// further split between the hot and cold DBs of each when a cold DB is used
+    fn files_limit(&self) -> i32 {
+        match self.cold_db_root_dir {
+            Some(_) => self.fd_budget / 4,
+            None => self.fd_budget / 2,
+        }
+    }
+
+    /// Opens the DB at `dir` named `name`, as a secondary instance if this node is a secondary instance
+    fn open_db(&self, dir: PathBuf, name: &str) -> Arc<DB> {
+        let builder = kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(dir)
+            .with_parallelism(self.db_parallelism)
+            .with_files_limit(self.files_limit())
+            .with_in_memory(self.config.ephemeral);
+        let db = match &self.secondary_db_root_dir {
+            Some(root) => builder.with_secondary_path(root.join(name)).build().unwrap(),
+            None => builder.build().unwrap(),
+        };
+        if let Some(storage_metrics) = self.storage_metrics.as_ref() {
+            storage_metrics.register(name, &db);
+        }
+        db
+    }
+
+    /// Opens the cold DB of the consensus entry, if a cold DB root dir is configured
+    fn open_cold_db(&self, entry: &ConsensusEntry) -> Option<Arc<DB>> {
+        self.cold_db_root_dir
+            .as_ref()
+            .map(|root| self.open_db(root.join(&entry.directory_name), &format!("cold-{}", entry.directory_name)))
+    }
+
+    /// Creates a consensus instance over a snapshot of the active consensus of the primary node, taken when opening
+    /// its DBs as secondary instances
+    fn new_secondary_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+        // Catch up with the primary metadata so a switch of the active consensus by the primary (following IBD) is observed
+        self.management_db.try_catch_up_with_primary().unwrap();
+        let management_store = MultiConsensusManagementStore::new(self.management_db.clone());
+        let entry = management_store.current_consensus_entry().unwrap().expect("the primary node has no active consensus");
+        *self.management_store.write() = management_store;
+
+        // The hot DB is opened first, so a block body moved to the cold DB in between is found in either snapshot
+        let db = self.open_db(self.db_root_dir.join(entry.directory_name.clone()), &entry.directory_name);
+        let cold_db = self.open_cold_db(&entry);
+
+        let session_lock = SessionLock::new();
+        let consensus = Arc::new(Consensus::new(
+            db.clone(),
+            cold_db,
+            Arc::new(self.config.clone()),
+            session_lock.clone(),
+            self.notification_root.clone(),
+            self.counters.clone(),
+            self.tx_script_cache_counters.clone(),
+            entry.creation_timestamp,
+        ));
+
+        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+    }
+
+    /// Deletes the cold DB dir of the consensus entry, if any
+    fn delete_cold_dir(&self, entry: &ConsensusEntry) -> std::io::Result<()> {
+        match &self.cold_db_root_dir {
+            Some(root) if root.join(&entry.directory_name).exists() => fs::remove_dir_all(root.join(&entry.directory_name)),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl ConsensusFactory for Factory {
     fn new_active_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
         assert!(!self.notification_root.is_closed());
+        if self.is_secondary() {
+            return self.new_secondary_consensus();
+        }
 
         let mut config = self.config.clone();
         let mut is_new_consensus = false;
@@ -291,17 +430,13 @@ impl ConsensusFactory for Factory {
             }
         };
 
-        let dir = self.db_root_dir.join(entry.directory_name.clone());
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let db = self.open_db(self.db_root_dir.join(entry.directory_name.clone()), &entry.directory_name);
+        let cold_db = self.open_cold_db(&entry);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
+            cold_db,
             Arc::new(config),
             session_lock.clone(),
             self.notification_root.clone(),
@@ -323,19 +458,16 @@ impl ConsensusFactory for Factory {
 
     fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
         assert!(!self.notification_root.is_closed());
+        assert!(!self.is_secondary(), "a secondary instance cannot create a staging consensus");
 
         let entry = self.management_store.write().new_staging_consensus_entry().unwrap();
-        let dir = self.db_root_dir.join(entry.directory_name);
-        let db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(dir)
-            .with_parallelism(self.db_parallelism)
-            .with_files_limit(self.fd_budget / 2) // active and staging consensuses should have equal budgets
-            .build()
-            .unwrap();
+        let db = self.open_db(self.db_root_dir.join(entry.directory_name.clone()), &entry.directory_name);
+        let cold_db = self.open_cold_db(&entry);
 
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
+            cold_db,
             Arc::new(self.config.to_builder().skip_adding_genesis().build()),
             session_lock.clone(),
             self.notification_root.clone(),
@@ -353,6 +485,11 @@ impl ConsensusFactory for Factory {
     }
 
     fn delete_inactive_consensus_entries(&self) {
+        // Entries are owned by the primary node
+        if self.is_secondary() {
+            return;
+        }
+
         // Staging entry is deleted also by archival nodes since it represents non-final data
         self.delete_staging_entry();
 
@@ -366,16 +503,13 @@ impl ConsensusFactory for Factory {
             .filter_map(|entry_result| {
                 let entry = entry_result.unwrap();
                 let dir = self.db_root_dir.join(entry.directory_name.clone());
-                if dir.exists() {
-                    match fs::remove_dir_all(dir) {
-                        Ok(_) => Some(entry),
-                        Err(e) => {
-                            warn!("Error deleting consensus entry {}: {}", entry.key, e);
-                            None
-                        }
+                let result = if dir.exists() { fs::remove_dir_all(dir) } else { Ok(()) };
+                match result.and_then(|_| self.delete_cold_dir(&entry)) {
+                    Ok(_) => Some(entry),
+                    Err(e) => {
+                        warn!("Error deleting consensus entry {}: {}", entry.key, e);
+                        None
                     }
-                } else {
-                    Some(entry)
                 }
             })
             .collect_vec();
@@ -386,10 +520,13 @@ impl ConsensusFactory for Factory {
     }
 
     fn delete_staging_entry(&self) {
+        if self.is_secondary() {
+            return;
+        }
         let mut write_guard = self.management_store.write();
         if let Some(entry) = write_guard.staging_consensus_entry() {
             let dir = self.db_root_dir.join(entry.directory_name.clone());
-            match fs::remove_dir_all(dir) {
+            match fs::remove_dir_all(dir).and_then(|_| self.delete_cold_dir(&entry)) {
                 Ok(_) => {
                     write_guard.delete_entry(entry).unwrap();
                 }
@@ -36,11 +36,14 @@ use crate::{
         virtual_processor::{errors::PruningImportResult, VirtualStateProcessor},
         ProcessingCounters,
     },
-    processes::window::{WindowManager, WindowType},
+    processes::{
+        ghostdag::ordering::SortableBlock,
+        window::{WindowManager, WindowType},
+    },
 };
 use kaspa_consensus_core::{
     acceptance_data::AcceptanceData,
-    api::{stats::BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats},
+    api::{stats::BlockCount, BlockColor, BlockValidationFutures, ConsensusApi, ConsensusStats, DagStats, VirtualParentsSelection},
     block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId},
     blockhash::BlockHashExtensions,
     blockstatus::BlockStatus,
@@ -89,7 +92,7 @@ use self::{services::ConsensusServices, storage::ConsensusStorage};
 
 use crate::model::stores::selected_chain::SelectedChainStoreReader;
 
-use std::cmp;
+use std::cmp::{self, Reverse};
 
 pub struct Consensus {
     // DB
@@ -140,6 +143,7 @@ impl Deref for Consensus {
 impl Consensus {
     pub fn new(
         db: Arc<DB>,
+        cold_db: Option<Arc<DB>>,
         config: Arc<Config>,
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
@@ -155,7 +159,7 @@ impl Consensus {
         // Storage layer
         //
 
-        let storage = ConsensusStorage::new(db.clone(), config.clone());
+        let storage = ConsensusStorage::new(db.clone(), cold_db, config.clone());
 
         //
         // Services and managers
@@ -190,11 +194,19 @@ impl Consensus {
         // Thread-pools
         //
 
-        // Pool for header and body processors
-        let block_processors_pool = Arc::new(
+        // Header and body processors have pools of their own, so that operators can size them separately and
+        // trade the responsiveness of the other services against the sync speed
+        let header_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(perf_params.header_processor_threads())
+                .thread_name(|i| format!("header-pool-{i}"))
+                .build()
+                .unwrap(),
+        );
+        let body_pool = Arc::new(
             rayon::ThreadPoolBuilder::new()
-                .num_threads(perf_params.block_processors_num_threads)
-                .thread_name(|i| format!("block-pool-{i}"))
+                .num_threads(perf_params.body_processor_threads())
+                .thread_name(|i| format!("body-pool-{i}"))
                 .build()
                 .unwrap(),
         );
@@ -203,7 +215,7 @@ impl Consensus {
         // See for instance https://github.com/rayon-rs/rayon/issues/690
         let virtual_pool = Arc::new(
             rayon::ThreadPoolBuilder::new()
-                .num_threads(perf_params.virtual_processor_num_threads)
+                .num_threads(perf_params.virtual_processor_threads())
                 .thread_name(|i| format!("virtual-pool-{i}"))
                 .build()
                 .unwrap(),
@@ -216,19 +228,20 @@ impl Consensus {
         let header_processor = Arc::new(HeaderProcessor::new(
             receiver,
             body_sender,
-            block_processors_pool.clone(),
+            header_pool,
             params,
             db.clone(),
             &storage,
             &services,
             pruning_lock.clone(),
             counters.clone(),
+            config.clock.clone(),
         ));
 
         let body_processor = Arc::new(BlockBodyProcessor::new(
             body_receiver,
             virtual_sender,
-            block_processors_pool,
+            body_pool,
             db.clone(),
             storage.statuses_store.clone(),
             storage.ghostdag_primary_store.clone(),
@@ -260,6 +273,7 @@ impl Consensus {
             pruning_lock.clone(),
             notification_root.clone(),
             counters.clone(),
+            config.clock.clone(),
         ));
 
         let pruning_processor = Arc::new(PruningProcessor::new(
@@ -378,13 +392,28 @@ impl Consensus {
         }
     }
 
-    fn estimate_network_hashes_per_second_impl(&self, ghostdag_data: &GhostdagData, window_size: usize) -> ConsensusResult<u64> {
-        let window = match self.services.window_manager.block_window(ghostdag_data, WindowType::VaryingWindow(window_size)) {
+    /// Estimates the hashrate over the window of `ghostdag_data`. If `anchor` is provided, `ghostdag_data` is expected to be
+    /// its GHOSTDAG data, and the anchor block joins the window just like the sink does for the virtual window
+    fn estimate_network_hashes_per_second_impl(
+        &self,
+        ghostdag_data: &GhostdagData,
+        anchor: Option<Hash>,
+        window_size: usize,
+        min_window_size: usize,
+    ) -> ConsensusResult<u64> {
+        let mut window = match self.services.window_manager.block_window(ghostdag_data, WindowType::VaryingWindow(window_size)) {
             Ok(w) => w,
             Err(RuleError::InsufficientDaaWindowSize(s)) => return Err(DifficultyError::InsufficientWindowData(s).into()),
             Err(e) => panic!("unexpected error: {e}"),
         };
-        Ok(self.services.window_manager.estimate_network_hashes_per_second(window)?)
+        if let Some(anchor) = anchor {
+            let window = Arc::make_mut(&mut window);
+            window.push(Reverse(SortableBlock::new(anchor, ghostdag_data.blue_work)));
+            if window.len() > window_size {
+                window.pop();
+            }
+        }
+        Ok(self.services.window_manager.estimate_network_hashes_per_second(window, min_window_size)?)
     }
 
     fn pruning_point_compact_headers(&self) -> Vec<(Hash, CompactHeaderData)> {
@@ -544,6 +573,123 @@ impl ConsensusApi for Consensus {
         Ok(self.services.dag_traversal_manager.calculate_chain_path(hash, self.get_sink()))
     }
 
+    fn sample_chain_blocks_by_work(&self, low_blue_score: u64, high_blue_score: u64, count: usize) -> ConsensusResult<Vec<Hash>> {
+        if low_blue_score > high_blue_score {
+            return Err(ConsensusError::InvalidBlueScoreRange(low_blue_score, high_blue_score));
+        }
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        // The selected parents of the sampled blocks must not be pruned
+        let pruning_point_blue_score = self.ghostdag_primary_store.get_blue_score(self.pruning_point()).unwrap();
+        if low_blue_score <= pruning_point_blue_score {
+            return Err(ConsensusError::BlueScoreNotAbovePruningPoint(low_blue_score, pruning_point_blue_score));
+        }
+        let high =
+            self.services.dag_traversal_manager.highest_chain_block_below_or_equal_to_blue_score(self.get_sink(), high_blue_score);
+        Ok(self.services.dag_traversal_manager.sample_chain_blocks_by_work(high, low_blue_score, count, &mut rand::thread_rng()))
+    }
+
+    fn get_chain_block_by_blue_score(&self, blue_score: u64) -> ConsensusResult<Hash> {
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        let pruning_point = self.pruning_point();
+        let sink = self.get_sink();
+        let low = self.ghostdag_primary_store.get_blue_score(pruning_point).unwrap();
+        let high = self.ghostdag_primary_store.get_blue_score(sink).unwrap();
+        if blue_score < low || blue_score > high {
+            return Err(ConsensusError::ScoreOutOfSelectedChainRange("blue", blue_score, low, high));
+        }
+        if blue_score == low {
+            // Avoids reading the ghostdag data of the pruned selected parent of the pruning point
+            return Ok(pruning_point);
+        }
+        Ok(self.services.dag_traversal_manager.lowest_chain_block_above_or_equal_to_blue_score(sink, blue_score))
+    }
+
+    fn get_chain_block_by_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        let sc_read = self.storage.selected_chain_store.read();
+        let high_index = sc_read.get_tip().unwrap().0;
+        // See `get_chain_block_samples` regarding the pruning point being missing from the selected chain store
+        let low_index = sc_read.get_by_hash(self.pruning_point()).unwrap_option().unwrap_or(high_index);
+        let daa_score_at = |index| self.storage.headers_store.get_daa_score(sc_read.get_by_index(index).unwrap()).unwrap();
+        let (low, high) = (daa_score_at(low_index), daa_score_at(high_index));
+        if daa_score < low || daa_score > high {
+            return Err(ConsensusError::ScoreOutOfSelectedChainRange("DAA", daa_score, low, high));
+        }
+        // DAA scores are non-decreasing along the selected chain, hence the chain index can be binary searched
+        let (mut low_index, mut high_index) = (low_index, high_index);
+        while low_index < high_index {
+            let mid_index = low_index + (high_index - low_index) / 2;
+            if daa_score_at(mid_index) < daa_score {
+                low_index = mid_index + 1;
+            } else {
+                high_index = mid_index;
+            }
+        }
+        Ok(sc_read.get_by_index(low_index).unwrap())
+    }
+
+    fn get_chain_block_by_timestamp(&self, timestamp: u64) -> ConsensusResult<Hash> {
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        let sc_read = self.storage.selected_chain_store.read();
+        let (high_index, sink) = sc_read.get_tip().unwrap();
+        // See `get_chain_block_samples` regarding the pruning point being missing from the selected chain store
+        let low_index = sc_read.get_by_hash(self.pruning_point()).unwrap_option().unwrap_or(high_index);
+        let timestamp_at = |index| self.storage.headers_store.get_timestamp(sc_read.get_by_index(index).unwrap()).unwrap();
+        let low_timestamp = timestamp_at(low_index);
+        if timestamp < low_timestamp {
+            return Err(ConsensusError::TimestampBelowPruningPoint(timestamp, low_timestamp));
+        }
+        if timestamp >= timestamp_at(high_index) {
+            return Ok(sink);
+        }
+
+        // Chain block timestamps are not monotonic, a block timestamp being only required to be above the past median time
+        // of the block. Yet, by this rule, a chain block timestamp cannot be lower than the timestamps of the chain blocks
+        // preceding it by more than a past median time window. The selected chain index is thus binary searched as if
+        // timestamps were monotonic and the result is refined by scanning a window-sized neighborhood.
+        let (mut low, mut high) = (low_index, high_index);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if timestamp_at(mid) < timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let sink_daa_score = self.storage.headers_store.get_daa_score(sink).unwrap();
+        let window =
+            self.config.past_median_time_window_size(sink_daa_score) as u64 * self.config.past_median_time_sample_rate(sink_daa_score);
+        let nearest = (low.saturating_sub(window).max(low_index)..=(low + window).min(high_index))
+            .min_by_key(|&index| (timestamp_at(index).abs_diff(timestamp), index))
+            .unwrap();
+        Ok(sc_read.get_by_index(nearest).unwrap())
+    }
+
+    fn get_chain_headers(&self, start: Hash, limit: usize, is_ascending: bool) -> ConsensusResult<Vec<Arc<Header>>> {
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        let sc_read = self.storage.selected_chain_store.read();
+        let high_index = sc_read.get_tip().unwrap().0;
+        // See `get_chain_block_samples` regarding the pruning point being missing from the selected chain store
+        let low_index = sc_read.get_by_hash(self.pruning_point()).unwrap_option().unwrap_or(high_index);
+        let start_index = match sc_read.get_by_hash(start).unwrap_option() {
+            Some(index) if index >= low_index => index,
+            _ => return Err(ConsensusError::NotSelectedChainBlock(start)),
+        };
+        let indices = match is_ascending {
+            true => (start_index..=high_index).take(limit).collect_vec(),
+            false => (low_index..=start_index).rev().take(limit).collect_vec(),
+        };
+        Ok(indices
+            .into_iter()
+            .map(|index| self.storage.headers_store.get_header(sc_read.get_by_index(index).unwrap()).unwrap())
+            .collect())
+    }
+
     /// Returns a Vec of header samples since genesis
     /// ordered by ascending daa_score, first entry is genesis
     fn get_chain_block_samples(&self) -> Vec<DaaScoreTimestamp> {
@@ -619,6 +765,20 @@ impl ConsensusApi for Consensus {
         self.lkg_virtual_state.load().parents.len()
     }
 
+    fn get_virtual_parents_selection(&self) -> VirtualParentsSelection {
+        let virtual_state = self.lkg_virtual_state.load();
+        let selection = self.virtual_processor.virtual_parents_selection();
+        if selection.parents == virtual_state.parents {
+            return selection;
+        }
+        // The virtual state was set with no parent selection taking place since startup (or a pruning point import)
+        VirtualParentsSelection {
+            selected_parent: virtual_state.ghostdag_data.selected_parent,
+            parents: virtual_state.parents.clone(),
+            excluded: vec![],
+        }
+    }
+
     fn get_virtual_utxos(
         &self,
         from_outpoint: Option<TransactionOutpoint>,
@@ -848,6 +1008,44 @@ impl ConsensusApi for Consensus {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
 
+    fn resolve_finality_conflict(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        self.virtual_processor.resolve_finality_conflict(finality_block_hash)
+    }
+
+    fn get_current_block_color(&self, hash: Hash) -> ConsensusResult<Option<BlockColor>> {
+        // We need consistency between the pruning point and the selected chain reads
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_block_exists(hash)?;
+        let pruning_point = self.pruning_point();
+        if !self.services.reachability_service.is_dag_ancestor_of(pruning_point, hash) {
+            return Err(ConsensusError::BlockNotInPruningPointFuture(hash));
+        }
+        let sc_read = self.storage.selected_chain_store.read();
+        let (high_index, sink) = sc_read.get_tip().unwrap();
+        if hash == sink || !self.services.reachability_service.is_dag_ancestor_of(hash, sink) {
+            return Ok(None);
+        }
+
+        // The merging block is the lowest selected chain block having `hash` in its past. The chain blocks having `hash`
+        // in their past form a suffix of the selected chain, so the selected chain index is binary searched.
+        // See `get_chain_block_samples` regarding the pruning point being missing from the selected chain store
+        let (mut low, mut high) = (sc_read.get_by_hash(pruning_point).unwrap_option().unwrap_or(high_index), high_index);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let chain_block = sc_read.get_by_index(mid).unwrap();
+            if chain_block != hash && self.services.reachability_service.is_dag_ancestor_of(hash, chain_block) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        let merging_block = sc_read.get_by_index(low).unwrap();
+        let ghostdag_data = self.ghostdag_primary_store.get_data(merging_block).unwrap();
+        let is_blue = ghostdag_data.mergeset_blues.contains(&hash);
+        debug_assert!(is_blue || ghostdag_data.mergeset_reds.contains(&hash), "{hash} is expected to be merged by {merging_block}");
+        Ok(Some(BlockColor { is_blue, merging_block }))
+    }
+
     fn get_missing_block_body_hashes(&self, high: Hash) -> ConsensusResult<Vec<Hash>> {
         let _guard = self.pruning_lock.blocking_read();
         self.validate_block_exists(high)?;
@@ -898,7 +1096,12 @@ impl ConsensusApi for Consensus {
         Ok(self.services.sync_manager.create_block_locator_from_pruning_point(high, pruning_point, Some(limit))?)
     }
 
-    fn estimate_network_hashes_per_second(&self, start_hash: Option<Hash>, window_size: usize) -> ConsensusResult<u64> {
+    fn estimate_network_hashes_per_second(
+        &self,
+        start_hash: Option<Hash>,
+        window_size: usize,
+        min_window_size: usize,
+    ) -> ConsensusResult<u64> {
         let _guard = self.pruning_lock.blocking_read();
         match start_hash {
             Some(hash) => {
@@ -908,13 +1111,33 @@ impl ConsensusApi for Consensus {
                 if !self.headers_store.has(ghostdag_data.selected_parent).unwrap() {
                     return Err(ConsensusError::DifficultyError(DifficultyError::InsufficientWindowData(0)));
                 }
-                self.estimate_network_hashes_per_second_impl(&ghostdag_data, window_size)
+                self.estimate_network_hashes_per_second_impl(&ghostdag_data, Some(hash), window_size, min_window_size)
             }
             None => {
                 let virtual_state = self.lkg_virtual_state.load();
-                self.estimate_network_hashes_per_second_impl(&virtual_state.ghostdag_data, window_size)
+                self.estimate_network_hashes_per_second_impl(&virtual_state.ghostdag_data, None, window_size, min_window_size)
+            }
+        }
+    }
+
+    fn get_dag_stats(&self, window_size: usize) -> DagStats {
+        let _guard = self.pruning_lock.blocking_read();
+        let pruning_point = self.pruning_point();
+        let mut stats = DagStats { num_tips: self.get_tips_len() as u64, ..Default::default() };
+        let mut current = self.get_sink();
+        while stats.chain_block_count < window_size as u64 && current != pruning_point && !current.is_origin() {
+            let ghostdag_data = self.ghostdag_primary_store.get_data(current).unwrap();
+            for merged in ghostdag_data.unordered_mergeset() {
+                if let Some(parents) = self.services.relations_service.get_parents(merged).unwrap_option() {
+                    stats.parent_count += parents.len() as u64;
+                }
             }
+            stats.chain_block_count += 1;
+            stats.merged_block_count += ghostdag_data.mergeset_size() as u64;
+            stats.red_block_count += ghostdag_data.mergeset_reds.len() as u64;
+            current = ghostdag_data.selected_parent;
         }
+        stats
     }
 
     fn are_pruning_points_violating_finality(&self, pp_list: PruningPointsList) -> bool {
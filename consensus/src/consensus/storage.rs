@@ -28,7 +28,7 @@ use crate::{
 use super::cache_policy_builder::CachePolicyBuilder as PolicyBuilder;
 use itertools::Itertools;
 use kaspa_consensus_core::{blockstatus::BlockStatus, BlockHashSet};
-use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_database::{prelude::CompressionConfig, registry::DatabaseStorePrefixes};
 use kaspa_hashes::Hash;
 use parking_lot::RwLock;
 use std::{mem::size_of, ops::DerefMut, sync::Arc};
@@ -74,7 +74,8 @@ pub struct ConsensusStorage {
 }
 
 impl ConsensusStorage {
-    pub fn new(db: Arc<DB>, config: Arc<Config>) -> Arc<Self> {
+    /// Builds the consensus stores over `db`. If provided, `cold_db` is used as the cold tier of block bodies
+    pub fn new(db: Arc<DB>, cold_db: Option<Arc<DB>>, config: Arc<Config>) -> Arc<Self> {
         let scale_factor = config.ram_scale;
         let scaled = |s| (s as f64 * scale_factor) as usize;
 
@@ -217,10 +218,17 @@ impl ConsensusStorage {
         let pruning_utxoset_stores = Arc::new(RwLock::new(PruningUtxosetStores::new(db.clone(), utxo_set_builder.build())));
 
         // Txs
-        let block_transactions_store = Arc::new(DbBlockTransactionsStore::new(db.clone(), transactions_builder.build()));
+        let block_transactions_store = Arc::new(
+            DbBlockTransactionsStore::new(db.clone(), transactions_builder.build())
+                .with_compression(config.block_transactions_compression_level.map(CompressionConfig::new))
+                .with_cold_db(cold_db),
+        );
         let utxo_diffs_store = Arc::new(DbUtxoDiffsStore::new(db.clone(), utxo_diffs_builder.build()));
         let utxo_multisets_store = Arc::new(DbUtxoMultisetsStore::new(db.clone(), block_data_builder.build()));
-        let acceptance_data_store = Arc::new(DbAcceptanceDataStore::new(db.clone(), acceptance_data_builder.build()));
+        let acceptance_data_store = Arc::new(
+            DbAcceptanceDataStore::new(db.clone(), acceptance_data_builder.build())
+                .with_compression(config.acceptance_data_compression_level.map(CompressionConfig::new)),
+        );
 
         // Tips
         let headers_selected_tip_store = Arc::new(RwLock::new(DbHeadersSelectedTipStore::new(db.clone())));
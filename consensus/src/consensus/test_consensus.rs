@@ -13,8 +13,8 @@ use kaspa_hashes::Hash;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use parking_lot::RwLock;
 
-use kaspa_database::create_temp_db;
 use kaspa_database::prelude::ConnBuilder;
+use kaspa_database::{create_ephemeral_db, create_temp_db};
 use std::future::Future;
 use std::{sync::Arc, thread::JoinHandle};
 
@@ -39,6 +39,14 @@ use crate::{
 use super::services::{DbDagTraversalManager, DbGhostdagManager, DbWindowManager};
 use super::Consensus;
 
+fn create_test_db(config: &Config) -> (DbLifetime, Arc<DB>) {
+    let conn_builder = ConnBuilder::default().with_files_limit(10);
+    match config.ephemeral {
+        true => create_ephemeral_db!(conn_builder),
+        false => create_temp_db!(conn_builder),
+    }
+}
+
 pub struct TestConsensus {
     params: Params,
     consensus: Arc<Consensus>,
@@ -54,6 +62,7 @@ impl TestConsensus {
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -66,14 +75,16 @@ impl TestConsensus {
         Self { params: config.params.clone(), consensus, block_builder, db_lifetime: Default::default() }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and the provided `notification_sender`
+    /// Creates a test consensus instance based on `config` with a temp DB (or an in-memory DB if `config.ephemeral`)
+    /// and the provided `notification_sender`
     pub fn with_notifier(config: &Config, notification_sender: Sender<Notification>, context: SubscriptionContext) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_test_db(config);
         let notification_root = Arc::new(ConsensusNotificationRoot::with_context(notification_sender, context));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -86,15 +97,17 @@ impl TestConsensus {
         Self { consensus, block_builder, params: config.params.clone(), db_lifetime }
     }
 
-    /// Creates a test consensus instance based on `config` with a temp DB and no notifier
+    /// Creates a test consensus instance based on `config` with a temp DB (or an in-memory DB if `config.ephemeral`)
+    /// and no notifier
     pub fn new(config: &Config) -> Self {
-        let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (db_lifetime, db) = create_test_db(config);
         let (dummy_notification_sender, _) = async_channel::unbounded();
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             Arc::new(config.clone()),
             Default::default(),
             notification_root,
@@ -3,6 +3,7 @@ use kaspa_consensus_core::acceptance_data::AcceptedTxEntry;
 use kaspa_consensus_core::acceptance_data::MergesetBlockAcceptanceData;
 use kaspa_consensus_core::BlockHasher;
 use kaspa_database::prelude::CachePolicy;
+use kaspa_database::prelude::CompressionConfig;
 use kaspa_database::prelude::StoreError;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
@@ -49,8 +50,13 @@ impl DbAcceptanceDataStore {
         Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AcceptanceData.into()) }
     }
 
+    /// Enables reading compressed acceptance data, and compressing the acceptance data being written if `compression` is set
+    pub fn with_compression(self, compression: Option<CompressionConfig>) -> Self {
+        Self { access: self.access.with_compression(compression), ..self }
+    }
+
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self { db: Arc::clone(&self.db), access: self.access.clone_with_new_cache(cache_policy) }
     }
 
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, acceptance_data: Arc<AcceptanceData>) -> Result<(), StoreError> {
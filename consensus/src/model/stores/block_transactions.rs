@@ -1,6 +1,7 @@
 use kaspa_consensus_core::tx::{TransactionInput, TransactionOutput};
 use kaspa_consensus_core::{tx::Transaction, BlockHasher};
 use kaspa_database::prelude::CachePolicy;
+use kaspa_database::prelude::CompressionConfig;
 use kaspa_database::prelude::StoreError;
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
@@ -42,48 +43,135 @@ impl MemSizeEstimator for BlockBody {
     }
 }
 
+/// The secondary ("cold") tier of the block transactions store, meant to live on a different volume
+#[derive(Clone)]
+struct ColdBlockTransactions {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, BlockBody, BlockHasher>,
+}
+
+impl ColdBlockTransactions {
+    fn new(db: Arc<DB>, compression: Option<CompressionConfig>) -> Self {
+        // Historical bodies are rarely read so they are not cached
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::BlockTransactions.into())
+                .with_compression(compression),
+        }
+    }
+}
+
 /// A DB + cache implementation of `BlockTransactionsStore` trait, with concurrency support.
+///
+/// When a cold DB is provided, the bodies of historical blocks can be moved to it with [`Self::move_to_cold`].
+/// Reads fall through to the cold DB for bodies missing from the primary DB, so the tiering is transparent to
+/// the store users.
+///
+/// Compressed bodies are supported once [`Self::with_compression`] is called, which also sets whether the bodies
+/// being written are compressed.
 #[derive(Clone)]
 pub struct DbBlockTransactionsStore {
     db: Arc<DB>,
     access: CachedDbAccess<Hash, BlockBody, BlockHasher>,
+    cold: Option<ColdBlockTransactions>,
+    compression: Option<CompressionConfig>,
 }
 
 impl DbBlockTransactionsStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BlockTransactions.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BlockTransactions.into()),
+            cold: None,
+            compression: None,
+        }
+    }
+
+    /// Enables reading compressed bodies, and compressing the bodies being written if `compression` is set.
+    /// Note this has to be called before setting the cold DB
+    pub fn with_compression(self, compression: Option<CompressionConfig>) -> Self {
+        Self { access: self.access.with_compression(compression), compression, ..self }
+    }
+
+    pub fn with_cold_db(mut self, cold_db: Option<Arc<DB>>) -> Self {
+        self.cold = cold_db.map(|db| ColdBlockTransactions::new(db, self.compression));
+        self
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
-        Self::new(Arc::clone(&self.db), cache_policy)
+        Self { access: self.access.clone_with_new_cache(cache_policy), ..self.clone() }
+    }
+
+    pub fn has_cold_db(&self) -> bool {
+        self.cold.is_some()
     }
 
     pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
-        self.access.has(hash)
+        if self.access.has(hash)? {
+            return Ok(true);
+        }
+        match self.cold {
+            Some(ref cold) => cold.access.has(hash),
+            None => Ok(false),
+        }
     }
 
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, transactions: Arc<Vec<Transaction>>) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access.write(BatchDbWriter::new(batch), hash, BlockBody(transactions))?;
         Ok(())
     }
 
+    /// Deletes the body from the primary DB as part of `batch`. Note that a body held by the cold DB is deleted
+    /// immediately, regardless of `batch` being later written.
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
+        if let Some(ref cold) = self.cold {
+            cold.access.delete(DirectDbWriter::new(&cold.db), hash)?;
+        }
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Moves the body of block `hash` from the primary DB to the cold DB. Returns whether the body was moved,
+    /// that is, whether the cold DB is set and the body was held by the primary DB.
+    ///
+    /// The body is written to the cold DB before being deleted from the primary DB, so it remains readable
+    /// throughout the operation.
+    pub fn move_to_cold(&self, hash: Hash) -> Result<bool, StoreError> {
+        let Some(ref cold) = self.cold else {
+            return Ok(false);
+        };
+        let body = match self.access.read(hash) {
+            Ok(body) => body,
+            Err(StoreError::KeyNotFound(_)) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        if !cold.access.has(hash)? {
+            cold.access.write(DirectDbWriter::new(&cold.db), hash, body)?;
+        }
+        self.access.delete(DirectDbWriter::new(&self.db), hash)?;
+        Ok(true)
+    }
+
+    /// Returns whether the body of block `hash` is held by the primary DB
+    pub fn is_hot(&self, hash: Hash) -> Result<bool, StoreError> {
+        self.access.has(hash)
+    }
 }
 
 impl BlockTransactionsStoreReader for DbBlockTransactionsStore {
     fn get(&self, hash: Hash) -> Result<Arc<Vec<Transaction>>, StoreError> {
-        Ok(self.access.read(hash)?.0)
+        match (self.access.read(hash), self.cold.as_ref()) {
+            (Err(StoreError::KeyNotFound(_)), Some(cold)) => Ok(cold.access.read(hash)?.0),
+            (result, _) => Ok(result?.0),
+        }
     }
 }
 
 impl BlockTransactionsStore for DbBlockTransactionsStore {
     fn insert(&self, hash: Hash, transactions: Arc<Vec<Transaction>>) -> Result<(), StoreError> {
-        if self.access.has(hash)? {
+        if self.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access.write(DirectDbWriter::new(&self.db), hash, BlockBody(transactions))?;
@@ -91,6 +179,39 @@ impl BlockTransactionsStore for DbBlockTransactionsStore {
     }
 
     fn delete(&self, hash: Hash) -> Result<(), StoreError> {
+        if let Some(ref cold) = self.cold {
+            cold.access.delete(DirectDbWriter::new(&cold.db), hash)?;
+        }
         self.access.delete(DirectDbWriter::new(&self.db), hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_move_to_cold() {
+        let (_hot_lifetime, hot_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (_cold_lifetime, cold_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbBlockTransactionsStore::new(hot_db, CachePolicy::Count(10)).with_cold_db(Some(cold_db));
+        let transactions = Arc::new(vec![Transaction::new(0, vec![], vec![], 0, Default::default(), 0, vec![])]);
+        store.insert(1.into(), transactions.clone()).unwrap();
+
+        assert!(store.is_hot(1.into()).unwrap());
+        assert!(store.move_to_cold(1.into()).unwrap());
+        assert!(!store.is_hot(1.into()).unwrap());
+        // Moving again or moving an unknown body is a no-op
+        assert!(!store.move_to_cold(1.into()).unwrap());
+        assert!(!store.move_to_cold(2.into()).unwrap());
+
+        // The moved body remains readable through the store
+        assert!(store.has(1.into()).unwrap());
+        assert_eq!(store.get(1.into()).unwrap(), transactions);
+        assert!(matches!(store.insert(1.into(), transactions), Err(StoreError::HashAlreadyExists(_))));
+
+        store.delete(1.into()).unwrap();
+        assert!(!store.has(1.into()).unwrap());
+    }
+}
@@ -37,6 +37,7 @@ use kaspa_consensus_core::{
     BlockHashSet, BlockLevel,
 };
 use kaspa_consensusmanager::SessionLock;
+use kaspa_core::time::DynClock;
 use kaspa_database::prelude::{StoreResultEmptyTuple, StoreResultExtensions};
 use kaspa_hashes::Hash;
 use kaspa_utils::vec::VecExtensions;
@@ -119,6 +120,7 @@ pub struct HeaderProcessor {
     pub(super) mergeset_size_limit: u64,
     pub(super) skip_proof_of_work: bool,
     pub(super) max_block_level: BlockLevel,
+    pub(super) clock: DynClock,
 
     // DB
     db: Arc<DB>,
@@ -167,6 +169,7 @@ impl HeaderProcessor {
         services: &Arc<ConsensusServices>,
         pruning_lock: SessionLock,
         counters: Arc<ProcessingCounters>,
+        clock: DynClock,
     ) -> Self {
         Self {
             receiver,
@@ -206,6 +209,7 @@ impl HeaderProcessor {
             mergeset_size_limit: params.mergeset_size_limit,
             skip_proof_of_work: params.skip_proof_of_work,
             max_block_level: params.max_block_level,
+            clock,
         }
     }
 
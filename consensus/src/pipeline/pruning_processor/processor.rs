@@ -14,7 +14,7 @@ use crate::{
             pruning::{PruningStore, PruningStoreReader},
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
             relations::StagingRelationsStore,
-            selected_chain::SelectedChainStore,
+            selected_chain::{SelectedChainStore, SelectedChainStoreReader},
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
             utxo_diffs::UtxoDiffsStoreReader,
@@ -39,7 +39,7 @@ use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_utils::iter::IterExtensions;
-use parking_lot::RwLockUpgradableReadGuard;
+use parking_lot::{Mutex, RwLockUpgradableReadGuard};
 use rocksdb::WriteBatch;
 use std::{
     collections::VecDeque,
@@ -81,6 +81,9 @@ pub struct PruningProcessor {
 
     // Signals
     is_consensus_exiting: Arc<AtomicBool>,
+
+    // Selected chain index of the first chain block whose mergeset bodies were not moved to the cold DB yet
+    cold_store_cursor: Mutex<Option<u64>>,
 }
 
 impl Deref for PruningProcessor {
@@ -112,6 +115,7 @@ impl PruningProcessor {
             pruning_lock,
             config,
             is_consensus_exiting,
+            cold_store_cursor: Mutex::new(None),
         }
     }
 
@@ -124,9 +128,11 @@ impl PruningProcessor {
         // in order to make sure the node is already connected and receiving blocks before we start background recovery operations
         self.recover_pruning_workflows_if_needed();
         self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+        self.move_historical_bodies_to_cold_store(sink_ghostdag_data);
 
         while let Ok(PruningProcessingMessage::Process { sink_ghostdag_data }) = self.receiver.recv() {
             self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+            self.move_historical_bodies_to_cold_store(sink_ghostdag_data);
         }
     }
 
@@ -205,6 +211,68 @@ impl PruningProcessor {
         }
     }
 
+    /// Moves the block bodies merged by chain blocks deeper than `cold_store_depth` below the sink to the cold DB.
+    /// Bodies are moved in selected chain order, so the moved bodies always form a prefix of the selected chain
+    fn move_historical_bodies_to_cold_store(&self, sink_ghostdag_data: CompactGhostdagData) {
+        if !self.block_transactions_store.has_cold_db() {
+            return;
+        }
+
+        let mut cursor = self.cold_store_cursor.lock();
+        let (low, tip_index) = {
+            let selected_chain_read = self.selected_chain_store.read();
+            // Non-archival nodes prune the selected chain below the pruning point
+            let low = match self.config.is_archival {
+                true => 0,
+                false => selected_chain_read.get_by_hash(self.pruning_point_store.read().pruning_point().unwrap()).unwrap(),
+            };
+            (low, selected_chain_read.get_tip().unwrap().0)
+        };
+        let mut index = match *cursor {
+            Some(index) => index.max(low),
+            None => self.first_hot_chain_index(low, tip_index),
+        };
+
+        let mut moved = 0;
+        while index <= tip_index {
+            if self.is_consensus_exiting.load(Ordering::Relaxed) {
+                break;
+            }
+            let Some(chain_block) = self.selected_chain_store.read().get_by_index(index).unwrap_option() else {
+                break;
+            };
+            let ghostdag = self.ghostdag_primary_store.get_data(chain_block).unwrap();
+            if ghostdag.blue_score + self.config.cold_store_depth >= sink_ghostdag_data.blue_score {
+                break;
+            }
+            for hash in ghostdag.unordered_mergeset_without_selected_parent().chain(std::iter::once(chain_block)) {
+                moved += self.block_transactions_store.move_to_cold(hash).unwrap() as usize;
+            }
+            index += 1;
+        }
+        *cursor = Some(index);
+
+        if moved > 0 {
+            debug!("Moved {} block bodies to the cold store", moved);
+        }
+    }
+
+    /// Finds the first chain block in `[low, high]` whose body is still held by the primary DB (or `high + 1` if none)
+    fn first_hot_chain_index(&self, mut low: u64, high: u64) -> u64 {
+        let selected_chain_read = self.selected_chain_store.read();
+        let mut high = high + 1;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let chain_block = selected_chain_read.get_by_index(mid).unwrap();
+            if self.block_transactions_store.is_hot(chain_block).unwrap() {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        low
+    }
+
     fn advance_pruning_utxoset(&self, utxoset_position: Hash, new_pruning_point: Hash) -> bool {
         let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
         for chain_block in self.reachability_service.forward_chain_iterator(utxoset_position, new_pruning_point, true).skip(1) {
@@ -48,10 +48,12 @@ use crate::{
 };
 use kaspa_consensus_core::{
     acceptance_data::AcceptanceData,
+    api::{ExcludedParentCandidate, ParentExclusionReason, VirtualParentsSelection},
     block::{BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockstatus::BlockStatus::{StatusDisqualifiedFromChain, StatusUTXOValid},
     coinbase::MinerData,
     config::genesis::GenesisBlock,
+    errors::consensus::{ConsensusError, ConsensusResult},
     header::Header,
     merkle::calc_hash_merkle_root_with_options,
     pruning::PruningPointsList,
@@ -64,17 +66,18 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensus_notify::{
     notification::{
-        NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification, UtxosChangedNotification,
-        VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+        FinalityConflictNotification, FinalityConflictResolvedNotification, NewBlockTemplateNotification, Notification,
+        SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification,
+        VirtualDaaScoreChangedNotification,
     },
     root::ConsensusNotificationRoot,
 };
 use kaspa_consensusmanager::SessionLock;
-use kaspa_core::{debug, info, time::unix_now, trace, warn};
+use kaspa_core::{debug, info, time::DynClock, trace, warn};
 use kaspa_database::prelude::{StoreError, StoreResultEmptyTuple, StoreResultExtensions};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
-use kaspa_notify::{events::EventType, notifier::Notify};
+use kaspa_notify::{events::EventType, notifier::Notify, scope::Scope};
 
 use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use itertools::Itertools;
@@ -89,6 +92,7 @@ use rocksdb::WriteBatch;
 use std::{
     cmp::min,
     collections::{BinaryHeap, HashMap, VecDeque},
+    iter::once,
     ops::Deref,
     sync::{atomic::Ordering, Arc},
 };
@@ -112,6 +116,7 @@ pub struct VirtualStateProcessor {
     pub(super) max_block_parents: u8,
     pub(super) mergeset_size_limit: u64,
     pub(super) pruning_depth: u64,
+    clock: DynClock,
 
     // Stores
     pub(super) statuses_store: Arc<RwLock<DbStatusesStore>>,
@@ -136,6 +141,13 @@ pub struct VirtualStateProcessor {
     /// for a possible virtual state write to complete but can rather settle with the last known state
     pub lkg_virtual_state: LkgVirtualState,
 
+    /// The outcome of the last virtual parent selection
+    virtual_parents_selection: RwLock<VirtualParentsSelection>,
+
+    /// The tips of the chains which violate finality while having more blue work than the selected chain,
+    /// pending a resolution directed by the node operator
+    finality_conflicts: RwLock<BlockHashSet>,
+
     // Managers and services
     pub(super) ghostdag_manager: DbGhostdagManager,
     pub(super) reachability_service: MTReachabilityService<DbReachabilityStore>,
@@ -175,6 +187,7 @@ impl VirtualStateProcessor {
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
+        clock: DynClock,
     ) -> Self {
         Self {
             receiver,
@@ -186,6 +199,7 @@ impl VirtualStateProcessor {
             max_block_parents: params.max_block_parents,
             mergeset_size_limit: params.mergeset_size_limit,
             pruning_depth: params.pruning_depth,
+            clock,
 
             db,
             statuses_store: storage.statuses_store.clone(),
@@ -204,6 +218,8 @@ impl VirtualStateProcessor {
             virtual_stores: storage.virtual_stores.clone(),
             pruning_utxoset_stores: storage.pruning_utxoset_stores.clone(),
             lkg_virtual_state: storage.lkg_virtual_state.clone(),
+            virtual_parents_selection: Default::default(),
+            finality_conflicts: Default::default(),
 
             ghostdag_manager: services.ghostdag_primary_manager.clone(),
             reachability_service: services.reachability_service.clone(),
@@ -257,8 +273,19 @@ impl VirtualStateProcessor {
     fn resolve_virtual(self: &Arc<Self>) {
         let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
         let virtual_read = self.virtual_stores.upgradable_read();
+        self.resolve_virtual_impl(virtual_read, pruning_point, None);
+    }
+
+    /// Resolves virtual, using `finality_point_override` (if provided) in place of the finality point of the previous virtual
+    fn resolve_virtual_impl(
+        self: &Arc<Self>,
+        virtual_read: RwLockUpgradableReadGuard<'_, VirtualStores>,
+        pruning_point: Hash,
+        finality_point_override: Option<Hash>,
+    ) {
         let prev_state = virtual_read.state.get().unwrap();
-        let finality_point = self.virtual_finality_point(&prev_state.ghostdag_data, pruning_point);
+        let finality_point =
+            finality_point_override.unwrap_or_else(|| self.virtual_finality_point(&prev_state.ghostdag_data, pruning_point));
 
         // PRUNE SAFETY: in order to avoid locking the prune lock throughout virtual resolving we make sure
         // to only process blocks in the future of the finality point (F) which are never pruned (since finality depth << pruning depth).
@@ -268,8 +295,9 @@ impl VirtualStateProcessor {
         //      2. Such tips cannot be merged by virtual since they are violating the merge depth
         //         bound (merge depth <= finality depth).
         // (both claims are true by induction for any block in their past as well)
+        let prev_sink = prev_state.ghostdag_data.selected_parent;
         let prune_guard = self.pruning_lock.blocking_read();
-        let tips = self
+        let (tips, violating_tips): (Vec<_>, Vec<_>) = self
             .body_tips_store
             .read()
             .get()
@@ -277,15 +305,24 @@ impl VirtualStateProcessor {
             .read()
             .iter()
             .copied()
-            .filter(|&h| self.reachability_service.is_dag_ancestor_of(finality_point, h))
-            .collect_vec();
+            .partition(|&h| self.reachability_service.is_dag_ancestor_of(finality_point, h));
+        // Tips outside the future of F having more blue work than the selected chain form a finality conflict.
+        // `finality_point == pruning_point` indicates we are at IBD start hence no conflict is recorded
+        if finality_point_override.is_none() && finality_point != pruning_point {
+            let prev_sink_blue_work = self.ghostdag_primary_store.get_blue_work(prev_sink).unwrap();
+            for tip in violating_tips {
+                if self.ghostdag_primary_store.get_blue_work(tip).unwrap() > prev_sink_blue_work {
+                    self.record_finality_conflict(tip);
+                }
+            }
+        }
         drop(prune_guard);
-        let prev_sink = prev_state.ghostdag_data.selected_parent;
         let mut accumulated_diff = prev_state.utxo_diff.clone().to_reversed();
 
         let (new_sink, virtual_parent_candidates) =
             self.sink_search_algorithm(&virtual_read, &mut accumulated_diff, prev_sink, tips, finality_point, pruning_point);
-        let (virtual_parents, virtual_ghostdag_data) = self.pick_virtual_parents(new_sink, virtual_parent_candidates, pruning_point);
+        let (virtual_parents, virtual_ghostdag_data, excluded) =
+            self.pick_virtual_parents(new_sink, virtual_parent_candidates, pruning_point);
         assert_eq!(virtual_ghostdag_data.selected_parent, new_sink);
 
         let sink_multiset = self.utxo_multisets_store.get(new_sink).unwrap();
@@ -300,6 +337,8 @@ impl VirtualStateProcessor {
                 &chain_path,
             )
             .expect("all possible rule errors are unexpected here");
+        *self.virtual_parents_selection.write() =
+            VirtualParentsSelection { selected_parent: new_sink, parents: new_virtual_state.parents.clone(), excluded };
 
         // Update the pruning processor about the virtual state change
         let sink_ghostdag_data = self.ghostdag_primary_store.get_compact_data(new_sink).unwrap();
@@ -323,10 +362,18 @@ impl VirtualStateProcessor {
         self.notification_root
             .notify(Notification::VirtualDaaScoreChanged(VirtualDaaScoreChangedNotification::new(new_virtual_state.daa_score)))
             .expect("expecting an open unbounded channel");
-        if self.notification_root.has_subscription(EventType::VirtualChainChanged) {
-            // check for subscriptions before the heavy lifting
-            let added_chain_blocks_acceptance_data =
-                chain_path.added.iter().copied().map(|added| self.acceptance_data_store.get(added).unwrap()).collect_vec();
+        // check for subscriptions before the heavy lifting, acceptance data being only needed by listeners
+        // which asked for accepted transaction ids and by the firehose, which gets every notification in full
+        let include_acceptance_data = match self.notification_root.subscription_scope(EventType::VirtualChainChanged) {
+            _ if self.notification_root.has_firehose() => Some(true),
+            Some(Scope::VirtualChainChanged(scope)) => Some(scope.include_accepted_transaction_ids),
+            _ => None,
+        };
+        if let Some(include_acceptance_data) = include_acceptance_data {
+            let added_chain_blocks_acceptance_data = match include_acceptance_data {
+                true => chain_path.added.iter().copied().map(|added| self.acceptance_data_store.get(added).unwrap()).collect_vec(),
+                false => vec![],
+            };
             self.notification_root
                 .notify(Notification::VirtualChainChanged(VirtualChainChangedNotification::new(
                     chain_path.added.into(),
@@ -337,6 +384,101 @@ impl VirtualStateProcessor {
         }
     }
 
+    /// Records `violating_block` as the tip of a chain conflicting with finality, notifying about the conflict
+    /// unless the chain of `violating_block` was already known to be in conflict
+    fn record_finality_conflict(&self, violating_block: Hash) {
+        let mut finality_conflicts = self.finality_conflicts.write();
+        if finality_conflicts.contains(&violating_block) {
+            return;
+        }
+        // A conflicting chain extending a recorded one replaces it
+        let len = finality_conflicts.len();
+        finality_conflicts.retain(|&recorded| !self.reachability_service.is_chain_ancestor_of(recorded, violating_block));
+        let is_new_conflict = finality_conflicts.len() == len;
+        finality_conflicts.insert(violating_block);
+        drop(finality_conflicts);
+        if is_new_conflict {
+            warn!(
+                "Finality conflict detected: block {} has more blue work than the selected chain but violates finality",
+                violating_block
+            );
+            self.notification_root
+                .notify(Notification::FinalityConflict(FinalityConflictNotification::new(violating_block)))
+                .expect("expecting an open unbounded channel");
+        }
+    }
+
+    /// Resolves a finality conflict in favor of the chain of `preferred_block`, as directed by the node operator.
+    /// Fails unless `preferred_block` is on the chain of a recorded finality conflict.
+    ///
+    /// The UTXO state of the preferred chain is validated first. Then, the current selected chain blocks which are not on
+    /// the preferred chain are disqualified from chain, and virtual is resolved with `preferred_block` standing for the
+    /// finality point, so that the new sink is the best valid block having `preferred_block` on its chain.
+    pub fn resolve_finality_conflict(self: &Arc<Self>, preferred_block: Hash) -> ConsensusResult<()> {
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        // Holding the upgradable read guard all along prevents concurrent virtual resolving, which would otherwise
+        // observe a disqualified selected chain
+        let virtual_read = self.virtual_stores.upgradable_read();
+
+        match self.statuses_store.read().get(preferred_block).unwrap_option() {
+            None => return Err(ConsensusError::BlockNotFound(preferred_block)),
+            Some(status) if !status.has_block_body() => return Err(ConsensusError::BlockNotFound(preferred_block)),
+            Some(StatusDisqualifiedFromChain) => return Err(ConsensusError::InvalidBlock(preferred_block)),
+            Some(_) => {}
+        }
+        if !self.reachability_service.is_chain_ancestor_of(pruning_point, preferred_block) {
+            return Err(ConsensusError::PruningPointNotInChain(preferred_block));
+        }
+        let prev_state = virtual_read.state.get().unwrap();
+        let prev_sink = prev_state.ghostdag_data.selected_parent;
+        // Any block off the selected chain is not necessarily involved in a conflict, the block must be on the
+        // chain of a recorded conflicting tip
+        if self.reachability_service.is_chain_ancestor_of(preferred_block, prev_sink)
+            || !self
+                .finality_conflicts
+                .read()
+                .iter()
+                .any(|&violating_block| self.reachability_service.is_chain_ancestor_of(preferred_block, violating_block))
+        {
+            return Err(ConsensusError::NoFinalityConflict(preferred_block));
+        }
+
+        // Make sure the preferred chain is UTXO valid before altering the current chain
+        let mut diff = prev_state.utxo_diff.clone().to_reversed();
+        if self.calculate_utxo_state_relatively(&virtual_read, &mut diff, prev_sink, preferred_block) != preferred_block {
+            return Err(ConsensusError::InvalidBlock(preferred_block));
+        }
+
+        let mut statuses_write = self.statuses_store.write();
+        let mut disqualified_count = 0;
+        for current in self.reachability_service.default_backward_chain_iterator(prev_sink) {
+            if self.reachability_service.is_chain_ancestor_of(current, preferred_block) {
+                break;
+            }
+            statuses_write.set(current, StatusDisqualifiedFromChain).unwrap();
+            disqualified_count += 1;
+        }
+        drop(statuses_write);
+        warn!(
+            "Resolving a finality conflict in favor of the chain of block {}, disqualifying {} blocks of the previous selected chain",
+            preferred_block, disqualified_count
+        );
+
+        self.resolve_virtual_impl(virtual_read, pruning_point, Some(preferred_block));
+        self.finality_conflicts
+            .write()
+            .retain(|&violating_block| !self.reachability_service.is_chain_ancestor_of(preferred_block, violating_block));
+        self.notification_root
+            .notify(Notification::FinalityConflictResolved(FinalityConflictResolvedNotification::new(preferred_block)))
+            .expect("expecting an open unbounded channel");
+        Ok(())
+    }
+
+    /// Returns the outcome of the last virtual parent selection
+    pub fn virtual_parents_selection(&self) -> VirtualParentsSelection {
+        self.virtual_parents_selection.read().clone()
+    }
+
     pub(crate) fn virtual_finality_point(&self, virtual_ghostdag_data: &GhostdagData, pruning_point: Hash) -> Hash {
         let finality_point = self.depth_manager.calc_finality_point(virtual_ghostdag_data, pruning_point);
         if self.reachability_service.is_chain_ancestor_of(pruning_point, finality_point) {
@@ -389,17 +531,18 @@ impl VirtualStateProcessor {
                 continue;
             }
 
+            if self.statuses_store.read().get(current).unwrap() == StatusDisqualifiedFromChain {
+                // Current block is already known to be disqualified. Note that it might still have UTXO data
+                // if it was disqualified by a finality conflict resolution
+                continue;
+            }
+
             match self.utxo_diffs_store.get(current) {
                 Ok(mergeset_diff) => {
                     diff.with_diff_in_place(mergeset_diff.deref()).unwrap();
                     diff_point = current;
                 }
                 Err(StoreError::KeyNotFound(_)) => {
-                    if self.statuses_store.read().get(current).unwrap() == StatusDisqualifiedFromChain {
-                        // Current block is already known to be disqualified
-                        continue;
-                    }
-
                     let header = self.headers_store.get_header(current).unwrap();
                     let mergeset_data = self.ghostdag_primary_store.get_data(current).unwrap();
                     let pov_daa_score = header.daa_score;
@@ -595,6 +738,7 @@ impl VirtualStateProcessor {
             } else if finality_point != pruning_point {
                 // `finality_point == pruning_point` indicates we are at IBD start hence no warning required
                 warn!("Finality Violation Detected. Block {} violates finality and is ignored from Virtual chain.", candidate);
+                self.record_finality_conflict(candidate);
             }
             // PRUNE SAFETY: see comment within [`resolve_virtual`]
             let prune_guard = self.pruning_lock.blocking_read();
@@ -610,6 +754,7 @@ impl VirtualStateProcessor {
     }
 
     /// Picks the virtual parents according to virtual parent selection pruning constrains.
+    /// Returns the virtual parents, the virtual GHOSTDAG data and the candidates which were excluded.
     /// Assumes:
     ///     1. `selected_parent` is a UTXO-valid block
     ///     2. `candidates` are an antichain ordered in descending blue work order
@@ -619,9 +764,7 @@ impl VirtualStateProcessor {
         selected_parent: Hash,
         mut candidates: VecDeque<Hash>,
         pruning_point: Hash,
-    ) -> (Vec<Hash>, GhostdagData) {
-        // TODO: tests
-
+    ) -> (Vec<Hash>, GhostdagData, Vec<ExcludedParentCandidate>) {
         // Mergeset increasing might traverse DAG areas which are below the finality point and which theoretically
         // can borderline with pruned data, hence we acquire the prune lock to ensure data consistency. Note that
         // the final selected mergeset can never be pruned (this is the essence of the prunality proof), however
@@ -630,6 +773,7 @@ impl VirtualStateProcessor {
         let _prune_guard = self.pruning_lock.blocking_read();
         let max_block_parents = self.max_block_parents as usize;
         let max_candidates = self.max_virtual_parent_candidates();
+        let mut excluded = Vec::new();
 
         // Prioritize half the blocks with highest blue work and pick the rest randomly to ensure diversity between nodes
         if candidates.len() > max_candidates {
@@ -646,7 +790,8 @@ impl VirtualStateProcessor {
             }
 
             // Truncate the unchosen elements
-            candidates.truncate(max_candidates);
+            let not_sampled = candidates.drain(max_candidates..);
+            excluded.extend(not_sampled.map(|h| ExcludedParentCandidate::new(h, ParentExclusionReason::NotSampled)));
         } else if candidates.len() > max_block_parents / 2 {
             // Fallback to a simpler algo in this case
             candidates.make_contiguous()[max_block_parents / 2..].shuffle(&mut rand::thread_rng());
@@ -659,6 +804,12 @@ impl VirtualStateProcessor {
         // Try adding parents as long as mergeset size and number of parents limits are not reached
         while let Some(candidate) = candidates.pop_front() {
             if mergeset_size >= self.mergeset_size_limit || virtual_parents.len() >= max_block_parents {
+                let reason = match virtual_parents.len() >= max_block_parents {
+                    true => ParentExclusionReason::MaxBlockParents,
+                    false => ParentExclusionReason::MergesetSizeLimit,
+                };
+                let remaining = once(candidate).chain(candidates.drain(..));
+                excluded.extend(remaining.map(|h| ExcludedParentCandidate::new(h, reason)));
                 break;
             }
             match self.mergeset_increase(&virtual_parents, candidate, self.mergeset_size_limit - mergeset_size) {
@@ -667,19 +818,28 @@ impl VirtualStateProcessor {
                     virtual_parents.push(candidate);
                 }
                 MergesetIncreaseResult::Rejected { new_candidate } => {
+                    excluded.push(ExcludedParentCandidate::new(candidate, ParentExclusionReason::MergesetSizeLimit));
                     // If we already have a candidate in the past of new candidate then skip.
                     if self.reachability_service.is_any_dag_ancestor(&mut candidates.iter().copied(), new_candidate) {
                         continue; // TODO: not sure this test is needed if candidates invariant as antichain is kept
                     }
                     // Remove all candidates which are in the future of the new candidate
-                    candidates.retain(|&h| !self.reachability_service.is_dag_ancestor_of(new_candidate, h));
+                    candidates.retain(|&h| {
+                        let in_future = self.reachability_service.is_dag_ancestor_of(new_candidate, h);
+                        if in_future {
+                            excluded.push(ExcludedParentCandidate::new(h, ParentExclusionReason::MergesetSizeLimit));
+                        }
+                        !in_future
+                    });
                     candidates.push_back(new_candidate);
                 }
             }
         }
         assert!(mergeset_size <= self.mergeset_size_limit);
         assert!(virtual_parents.len() <= max_block_parents);
-        self.remove_bounded_merge_breaking_parents(virtual_parents, pruning_point)
+        let (virtual_parents, ghostdag_data) =
+            self.remove_bounded_merge_breaking_parents(virtual_parents, pruning_point, &mut excluded);
+        (virtual_parents, ghostdag_data, excluded)
     }
 
     fn mergeset_increase(&self, selected_parents: &[Hash], candidate: Hash, budget: u64) -> MergesetIncreaseResult {
@@ -717,6 +877,7 @@ impl VirtualStateProcessor {
         &self,
         mut virtual_parents: Vec<Hash>,
         current_pruning_point: Hash,
+        excluded: &mut Vec<ExcludedParentCandidate>,
     ) -> (Vec<Hash>, GhostdagData) {
         let mut ghostdag_data = self.ghostdag_manager.ghostdag(&virtual_parents);
         let merge_depth_root = self.depth_manager.calc_merge_depth_root(&ghostdag_data, current_pruning_point);
@@ -743,7 +904,13 @@ impl VirtualStateProcessor {
 
         if !bad_reds.is_empty() {
             // Remove all parents which lead to merging a bad red
-            virtual_parents.retain(|&h| !self.reachability_service.is_any_dag_ancestor(&mut bad_reds.iter().copied(), h));
+            virtual_parents.retain(|&h| {
+                let merges_bad_red = self.reachability_service.is_any_dag_ancestor(&mut bad_reds.iter().copied(), h);
+                if merges_bad_red {
+                    excluded.push(ExcludedParentCandidate::new(h, ParentExclusionReason::BoundedMergeDepth));
+                }
+                !merges_bad_red
+            });
             // Recompute ghostdag data since parents changed
             ghostdag_data = self.ghostdag_manager.ghostdag(&virtual_parents);
         }
@@ -904,6 +1071,7 @@ impl VirtualStateProcessor {
         drop(virtual_read);
 
         // Build the template
+        tx_selector.order_transactions(&mut txs);
         self.build_block_template_from_virtual_state(virtual_state, miner_data, txs)
     }
 
@@ -967,7 +1135,7 @@ impl VirtualStateProcessor {
             hash_merkle_root,
             accepted_id_merkle_root,
             utxo_commitment,
-            u64::max(min_block_time, unix_now()),
+            u64::max(min_block_time, self.clock.unix_now()),
             virtual_state.bits,
             0,
             virtual_state.daa_score,
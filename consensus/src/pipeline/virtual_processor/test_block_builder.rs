@@ -48,7 +48,7 @@ impl TestBlockBuilder {
         // Search for the sink block from the PoV of this virtual
         let (pov_sink, virtual_parent_candidates) =
             self.sink_search_algorithm(&virtual_read, &mut accumulated_diff, sink, parents, finality_point, pruning_point);
-        let (pov_virtual_parents, pov_virtual_ghostdag_data) =
+        let (pov_virtual_parents, pov_virtual_ghostdag_data, _) =
             self.pick_virtual_parents(pov_sink, virtual_parent_candidates, pruning_point);
         let pov_sink_multiset = self.utxo_multisets_store.get(pov_sink).unwrap();
         let pov_virtual_state = self.calculate_virtual_state(
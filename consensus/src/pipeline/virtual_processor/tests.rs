@@ -1,11 +1,15 @@
-use crate::{consensus::test_consensus::TestConsensus, model::services::reachability::ReachabilityService};
+use crate::{
+    consensus::test_consensus::TestConsensus,
+    model::{services::reachability::ReachabilityService, stores::ghostdag::GhostdagStoreReader},
+};
 use kaspa_consensus_core::{
-    api::ConsensusApi,
+    api::{ConsensusApi, ParentExclusionReason},
     block::{Block, BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockhash,
     blockstatus::BlockStatus,
     coinbase::MinerData,
     config::{params::MAINNET_PARAMS, ConfigBuilder},
+    errors::consensus::ConsensusError,
     tx::{ScriptPublicKey, ScriptVec, Transaction},
     BlockHashSet,
 };
@@ -296,6 +300,162 @@ async fn double_search_disqualified_test() {
     ctx.assert_tips_num(1);
 }
 
+#[tokio::test]
+async fn resolve_finality_conflict_test() {
+    kaspa_core::log::try_init_logger("info");
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.max_block_parents = 4;
+            p.mergeset_size_limit = 10;
+            p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+            p.merge_depth = 10;
+            p.finality_depth = 20;
+        })
+        .build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+    let genesis = config.genesis.hash;
+
+    // Mine 2 valid blocks over genesis, only one of them remaining on the selected chain
+    ctx.build_block_template_row(0..2).validate_and_insert_row().await.assert_valid_utxo_tip();
+    let original_sink = ctx.consensus.get_sink();
+    let mut virtual_parents = ctx.consensus.get_virtual_parents();
+    assert!(virtual_parents.remove(&original_sink));
+    let merged = virtual_parents.into_iter().next().unwrap();
+
+    // Mine a valid chain deeper than the finality depth, merging both
+    for _ in 0..30 {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    let sink = ctx.consensus.get_sink();
+    assert!(ctx.consensus.reachability_service().is_chain_ancestor_of(original_sink, sink));
+    assert!(!ctx.consensus.reachability_service().is_chain_ancestor_of(merged, sink));
+
+    // Without a conflict, neither blocks of the selected chain nor blocks off it can be preferred
+    for block in [original_sink, merged, genesis] {
+        assert!(matches!(ctx.consensus.resolve_finality_conflict(block), Err(ConsensusError::NoFinalityConflict(_))));
+    }
+    assert!(matches!(ctx.consensus.resolve_finality_conflict(blockhash::NONE), Err(ConsensusError::BlockNotFound(_))));
+
+    // Mine a longer valid chain over genesis, reorging deeper than the finality point
+    let mut conflicting_chain = vec![];
+    let mut parent = genesis;
+    for _ in 0..40 {
+        let mut b = ctx.consensus.build_utxo_valid_block_with_parents(blockhash::NONE, vec![parent], ctx.miner_data.clone(), vec![]);
+        b.header.finalize();
+        parent = b.header.hash;
+        conflicting_chain.push(parent);
+        ctx.validate_and_insert_block(b.to_immutable()).await;
+    }
+    let conflicting_tip = parent;
+    assert!(
+        ctx.consensus.ghostdag_store().get_blue_work(conflicting_tip).unwrap()
+            > ctx.consensus.ghostdag_store().get_blue_work(sink).unwrap()
+    );
+
+    // Finality keeps the selected chain despite the conflicting chain having more blue work
+    assert_eq!(ctx.consensus.get_sink(), sink);
+    assert!(matches!(ctx.consensus.resolve_finality_conflict(merged), Err(ConsensusError::NoFinalityConflict(_))));
+
+    // The operator switches to the conflicting chain, disqualifying the previous selected chain down to genesis
+    ctx.consensus.resolve_finality_conflict(conflicting_chain[5]).unwrap();
+    assert_eq!(ctx.consensus.get_sink(), conflicting_tip);
+    assert_eq!(ctx.consensus.get_block_status(original_sink), Some(BlockStatus::StatusDisqualifiedFromChain));
+    assert_eq!(ctx.consensus.get_block_status(sink), Some(BlockStatus::StatusDisqualifiedFromChain));
+
+    // The conflict is resolved
+    assert!(matches!(ctx.consensus.resolve_finality_conflict(conflicting_chain[5]), Err(ConsensusError::NoFinalityConflict(_))));
+
+    // Mining goes on over the preferred chain
+    for _ in 0..5 {
+        ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_valid_utxo_tip();
+    }
+    assert!(ctx.consensus.reachability_service().is_chain_ancestor_of(conflicting_tip, ctx.consensus.get_sink()));
+    assert!(!ctx.consensus.reachability_service().is_dag_ancestor_of(sink, ctx.consensus.get_sink()));
+}
+
+#[tokio::test]
+async fn virtual_parents_exclusion_test() {
+    // Counts the candidates excluded for `reason` and asserts that the virtual parents and the excluded candidates partition the tips
+    fn count_excluded(ctx: &TestContext, reason: ParentExclusionReason) -> usize {
+        let selection = ctx.consensus.get_virtual_parents_selection();
+        assert_eq!(selection.parents[0], selection.selected_parent);
+        assert_eq!(BlockHashSet::from_iter(selection.parents.iter().copied()), ctx.consensus.get_virtual_parents());
+        let excluded = BlockHashSet::from_iter(selection.excluded.iter().map(|candidate| candidate.hash));
+        assert_eq!(excluded.len(), selection.excluded.len());
+        assert!(selection.parents.iter().all(|parent| !excluded.contains(parent)));
+        assert_eq!(excluded.len() + selection.parents.len(), ctx.current_tips.len());
+        assert!(excluded.is_subset(&ctx.current_tips));
+        selection.excluded.iter().filter(|candidate| candidate.reason == reason).count()
+    }
+
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.max_block_parents = 4;
+            p.mergeset_size_limit = 10;
+        })
+        .build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+
+    // Out of a 32-wide antichain, the 31 candidates besides the selected parent are sampled down to 3 x 4 = 12
+    // candidates, of which only 3 fit in the max block parents along with the selected parent
+    ctx.build_block_template_row(0..32).validate_and_insert_row().await.assert_tips();
+    assert_eq!(count_excluded(&ctx, ParentExclusionReason::NotSampled), 31 - 12);
+    assert_eq!(count_excluded(&ctx, ParentExclusionReason::MaxBlockParents), 12 - 3);
+    assert_eq!(ctx.consensus.get_virtual_parents_selection().excluded.len(), 32 - 4);
+
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.max_block_parents = 10;
+            p.mergeset_size_limit = 5;
+        })
+        .build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+
+    // Out of an 8-wide antichain, each parent adds a single block to the mergeset of virtual so the
+    // mergeset size limit is reached with 5 parents while the max block parents is not
+    ctx.build_block_template_row(0..8).validate_and_insert_row().await.assert_tips();
+    assert_eq!(ctx.consensus.get_virtual_parents_selection().parents.len(), 5);
+    assert_eq!(count_excluded(&ctx, ParentExclusionReason::MergesetSizeLimit), 3);
+    assert_eq!(ctx.consensus.get_virtual_parents_selection().excluded.len(), 3);
+}
+
+#[tokio::test]
+async fn dag_stats_test() {
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.max_block_parents = 4;
+            p.mergeset_size_limit = 10;
+        })
+        .build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+
+    // A 3-wide antichain over genesis, merged by a single block, which is then extended by a single chain block
+    ctx.build_block_template_row(0..3).validate_and_insert_row().await.assert_tips();
+    ctx.build_block_template_row(0..1).assert_row_parents().validate_and_insert_row().await.assert_tips();
+    ctx.build_block_template_row(0..1).validate_and_insert_row().await.assert_tips();
+
+    // The last chain block merges its selected parent only, which itself merges the 3 blocks of the
+    // antichain, each having genesis as single parent
+    let stats = ctx.consensus.get_dag_stats(2);
+    assert_eq!(stats.num_tips, 1);
+    assert_eq!(stats.chain_block_count, 2);
+    assert_eq!(stats.merged_block_count, 1 + 3);
+    assert_eq!(stats.red_block_count, 0);
+    assert_eq!(stats.parent_count, 3 + 3);
+
+    // The window is bounded by the pruning point, which is genesis here
+    let stats = ctx.consensus.get_dag_stats(100);
+    assert_eq!(stats.chain_block_count, 3);
+    assert_eq!(stats.merged_block_count, 1 + 3 + 1);
+
+    let stats = ctx.consensus.get_dag_stats(0);
+    assert_eq!((stats.num_tips, stats.chain_block_count, stats.merged_block_count), (1, 0, 0));
+}
+
 fn new_miner_data() -> MinerData {
     let secp = secp256k1::Secp256k1::new();
     let mut rng = rand::thread_rng();
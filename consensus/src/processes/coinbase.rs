@@ -441,6 +441,8 @@ mod tests {
             },
         };
         assert_eq!(expected_data, deserialized_data);
+        assert_eq!(coinbase_payload_script_public_key(&payload), Some(expected_data.miner_data.script_public_key));
+        assert_eq!(coinbase_payload_script_public_key(&payload[..20]), None);
     }
 
     #[test]
@@ -39,12 +39,12 @@ trait DifficultyManagerExtension {
             .collect()
     }
 
-    fn internal_estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
-        // TODO: perhaps move this const
-        const MIN_WINDOW_SIZE: usize = 1000;
+    fn internal_estimate_network_hashes_per_second(&self, window: &BlockWindowHeap, min_window_size: usize) -> DifficultyResult<u64> {
+        // A window of less than 2 blocks has no timestamp range
+        let min_window_size = min_window_size.max(2);
         let window_size = window.len();
-        if window_size < MIN_WINDOW_SIZE {
-            return Err(DifficultyError::UnderMinWindowSizeAllowed(window_size, MIN_WINDOW_SIZE));
+        if window_size < min_window_size {
+            return Err(DifficultyError::UnderMinWindowSizeAllowed(window_size, min_window_size));
         }
         let difficulty_blocks = self.get_difficulty_blocks(window);
         let (min_ts, max_ts) = difficulty_blocks.iter().map(|x| x.timestamp).minmax().into_option().unwrap();
@@ -153,8 +153,8 @@ impl<T: HeaderStoreReader> FullDifficultyManager<T> {
         Uint256::try_from(new_target.min(self.max_difficulty_target)).expect("max target < Uint256::MAX").compact_target_bits()
     }
 
-    pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
-        self.internal_estimate_network_hashes_per_second(window)
+    pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap, min_window_size: usize) -> DifficultyResult<u64> {
+        self.internal_estimate_network_hashes_per_second(window, min_window_size)
     }
 }
 
@@ -259,8 +259,8 @@ impl<T: HeaderStoreReader> SampledDifficultyManager<T> {
         Uint256::try_from(new_target.min(self.max_difficulty_target)).expect("max target < Uint256::MAX").compact_target_bits()
     }
 
-    pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap) -> DifficultyResult<u64> {
-        self.internal_estimate_network_hashes_per_second(window)
+    pub fn estimate_network_hashes_per_second(&self, window: &BlockWindowHeap, min_window_size: usize) -> DifficultyResult<u64> {
+        self.internal_estimate_network_hashes_per_second(window, min_window_size)
     }
 }
 
@@ -307,3 +307,298 @@ impl Ord for DifficultyBlock {
         self.timestamp.cmp(&other.timestamp).then_with(|| self.sortable_block.cmp(&other.sortable_block))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::stores::block_window_cache::WindowOrigin,
+        test_helpers::{build_difficulty_window, reference_difficulty_bits, HeaderStoreMock},
+    };
+    use flate2::read::GzDecoder;
+    use kaspa_consensus_core::config::params::{DEVNET_PARAMS, LEGACY_DIFFICULTY_WINDOW_SIZE};
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+    use std::io::Read;
+
+    const GENESIS_BITS: u32 = 0x1e7fffff;
+    const BITS: u32 = 0x1b0fffff;
+    const TARGET_TIME_PER_BLOCK: u64 = 1000;
+    const WINDOW_SIZE: usize = 263;
+    const SAMPLE_RATE: u64 = 4;
+
+    fn full_manager(
+        store: Arc<HeaderStoreMock>,
+        max_target: Uint256,
+        target_time_per_block: u64,
+    ) -> FullDifficultyManager<HeaderStoreMock> {
+        FullDifficultyManager::new(store, GENESIS_BITS, max_target, WINDOW_SIZE, MIN_DIFFICULTY_WINDOW_LEN, target_time_per_block)
+    }
+
+    fn sampled_manager(
+        store: Arc<HeaderStoreMock>,
+        max_target: Uint256,
+        sample_rate: u64,
+    ) -> SampledDifficultyManager<HeaderStoreMock> {
+        SampledDifficultyManager::new(
+            store,
+            GENESIS_BITS,
+            max_target,
+            WINDOW_SIZE,
+            MIN_DIFFICULTY_WINDOW_LEN,
+            sample_rate,
+            TARGET_TIME_PER_BLOCK,
+        )
+    }
+
+    fn max_target() -> Uint256 {
+        Uint256::from_compact_target_bits(GENESIS_BITS)
+    }
+
+    /// Blocks evenly spaced by `spacing` milliseconds, all carrying `bits`
+    fn even_blocks(len: usize, spacing: u64, bits: u32) -> Vec<(u64, u32)> {
+        (0..len as u64).map(|i| (1_000_000 + i * spacing, bits)).collect()
+    }
+
+    /// Random window with distinct timestamps and bits spread over a few orders of magnitude
+    fn random_blocks(rng: &mut SmallRng, len: usize) -> Vec<(u64, u32)> {
+        let mut timestamp = rng.gen_range(1_000_000..2_000_000);
+        (0..len)
+            .map(|_| {
+                timestamp += rng.gen_range(1..10 * TARGET_TIME_PER_BLOCK);
+                (timestamp, (rng.gen_range(0x19..=0x1bu32) << 24) | rng.gen_range(0x8000..=0x7fffffu32))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_short_windows_keep_genesis_bits() {
+        for len in 0..MIN_DIFFICULTY_WINDOW_LEN {
+            let (store, window) = build_difficulty_window(&even_blocks(len, 1, BITS), WindowOrigin::Full);
+            assert_eq!(full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), GENESIS_BITS);
+            let (store, window) = build_difficulty_window(&even_blocks(len, 1, BITS), WindowOrigin::Sampled);
+            assert_eq!(sampled_manager(store, max_target(), SAMPLE_RATE).calculate_difficulty_bits(&window), GENESIS_BITS);
+        }
+        // The very first window reaching the min length is already used for adjusting the difficulty
+        let (store, window) =
+            build_difficulty_window(&even_blocks(MIN_DIFFICULTY_WINDOW_LEN, TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        assert_eq!(full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), BITS);
+    }
+
+    #[test]
+    fn test_steady_rate_keeps_difficulty() {
+        for len in [MIN_DIFFICULTY_WINDOW_LEN, 100, WINDOW_SIZE] {
+            let (store, window) = build_difficulty_window(&even_blocks(len, TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+            assert_eq!(full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), BITS);
+            let (store, window) =
+                build_difficulty_window(&even_blocks(len, TARGET_TIME_PER_BLOCK * SAMPLE_RATE, BITS), WindowOrigin::Sampled);
+            assert_eq!(sampled_manager(store, max_target(), SAMPLE_RATE).calculate_difficulty_bits(&window), BITS);
+        }
+    }
+
+    #[test]
+    fn test_difficulty_follows_block_rate() {
+        let target = Uint256::from_compact_target_bits(BITS);
+
+        // Blocks twice slower than expected double the target
+        let (store, window) = build_difficulty_window(&even_blocks(WINDOW_SIZE, 2 * TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        let bits = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window);
+        assert_eq!(Uint256::from_compact_target_bits(bits), target << 1);
+        assert_eq!(bits, 0x1b1ffffe);
+
+        // Blocks twice faster than expected halve the target, the compact encoding truncating the result
+        let (store, window) = build_difficulty_window(&even_blocks(WINDOW_SIZE, TARGET_TIME_PER_BLOCK / 2, BITS), WindowOrigin::Full);
+        let bits = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window);
+        assert_eq!(bits, 0x1b07ffff);
+
+        // Same for a sampled window
+        let (store, window) =
+            build_difficulty_window(&even_blocks(WINDOW_SIZE, 2 * TARGET_TIME_PER_BLOCK * SAMPLE_RATE, BITS), WindowOrigin::Sampled);
+        let bits = sampled_manager(store, max_target(), SAMPLE_RATE).calculate_difficulty_bits(&window);
+        assert_eq!(bits, 0x1b1ffffe);
+    }
+
+    #[test]
+    fn test_earliest_block_is_excluded_from_average() {
+        // The earliest block carries an outlier target which must not weigh in the average
+        let mut blocks = even_blocks(WINDOW_SIZE, TARGET_TIME_PER_BLOCK, BITS);
+        blocks[0].1 = GENESIS_BITS;
+        // Shuffle the blue work order so that the earliest block is not the lowest one in the window
+        blocks.rotate_left(WINDOW_SIZE / 2);
+        let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Full);
+        assert_eq!(full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), BITS);
+    }
+
+    #[test]
+    fn test_clamp_at_min_difficulty() {
+        // Very slow blocks mined at the max target cannot push the target any higher
+        let (store, window) =
+            build_difficulty_window(&even_blocks(WINDOW_SIZE, 1000 * TARGET_TIME_PER_BLOCK, GENESIS_BITS), WindowOrigin::Full);
+        assert_eq!(full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), GENESIS_BITS);
+        let (store, window) =
+            build_difficulty_window(&even_blocks(WINDOW_SIZE, 1000 * TARGET_TIME_PER_BLOCK, GENESIS_BITS), WindowOrigin::Sampled);
+        assert_eq!(sampled_manager(store, max_target(), SAMPLE_RATE).calculate_difficulty_bits(&window), GENESIS_BITS);
+
+        // A max target lower than the window targets caps the result as well
+        let low_max_target = Uint256::from_compact_target_bits(BITS);
+        let (store, window) = build_difficulty_window(&even_blocks(WINDOW_SIZE, 2 * TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        assert_eq!(full_manager(store, low_max_target, TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window), BITS);
+    }
+
+    #[test]
+    fn test_clamp_at_max_difficulty() {
+        // All timestamps being equal, the measured duration is floored at 1 millisecond
+        let blocks = even_blocks(WINDOW_SIZE, 0, BITS);
+        let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Full);
+        let bits = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window);
+        let expected = Uint256::from_compact_target_bits(BITS) / (TARGET_TIME_PER_BLOCK * (WINDOW_SIZE as u64 - 1));
+        assert_eq!(bits, expected.compact_target_bits());
+        assert_eq!(
+            bits,
+            reference_difficulty_bits(&blocks, GENESIS_BITS, MIN_DIFFICULTY_WINDOW_LEN, TARGET_TIME_PER_BLOCK, max_target())
+        );
+
+        // The lowest possible target cannot go any lower than zero
+        let (store, window) = build_difficulty_window(&even_blocks(WINDOW_SIZE, 1, 0x03000001), WindowOrigin::Full);
+        let bits = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window);
+        assert_eq!(Uint256::from_compact_target_bits(bits), Uint256::ZERO);
+    }
+
+    #[test]
+    fn test_sampled_matches_full_with_scaled_target_time() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        for sample_rate in [1, 2, SAMPLE_RATE, 10] {
+            for _ in 0..20 {
+                let len = rng.gen_range(MIN_DIFFICULTY_WINDOW_LEN..=WINDOW_SIZE);
+                let blocks = random_blocks(&mut rng, len);
+                let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Sampled);
+                let sampled = sampled_manager(store.clone(), max_target(), sample_rate).calculate_difficulty_bits(&window);
+                let full = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK * sample_rate).calculate_difficulty_bits(&window);
+                assert_eq!(sampled, full, "sample rate {sample_rate}, window length {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_reference_implementation() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let len = rng.gen_range(0..=WINDOW_SIZE);
+            let blocks = random_blocks(&mut rng, len);
+            let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Full);
+            assert_eq!(
+                full_manager(store.clone(), max_target(), TARGET_TIME_PER_BLOCK).calculate_difficulty_bits(&window),
+                reference_difficulty_bits(&blocks, GENESIS_BITS, MIN_DIFFICULTY_WINDOW_LEN, TARGET_TIME_PER_BLOCK, max_target()),
+                "full window of length {len}"
+            );
+            assert_eq!(
+                sampled_manager(store, max_target(), SAMPLE_RATE).calculate_difficulty_bits(&window),
+                reference_difficulty_bits(
+                    &blocks,
+                    GENESIS_BITS,
+                    MIN_DIFFICULTY_WINDOW_LEN,
+                    TARGET_TIME_PER_BLOCK * SAMPLE_RATE,
+                    max_target()
+                ),
+                "sampled window of length {len}"
+            );
+        }
+    }
+
+    /// Genesis bits of the devnet DAG mined by the golang node the recorded vectors come from
+    const GOREF_GENESIS_BITS: u32 = 0x207fffff;
+
+    /// Returns the difficulty windows recorded from a DAG mined by the golang node, each along with the hash of its
+    /// block and the bits the golang node set in the header of that block
+    fn goref_vectors() -> Vec<(String, u32, Vec<(u64, u32)>)> {
+        let mut data = String::new();
+        GzDecoder::new(&include_bytes!("../../testdata/difficulty/goref-notx-5000-blocks.txt.gz")[..])
+            .read_to_string(&mut data)
+            .unwrap();
+        let mut vectors: Vec<(String, u32, Vec<(u64, u32)>)> = Vec::new();
+        for line in data.lines().filter(|line| !line.starts_with('#')) {
+            match line.split(' ').collect_vec()[..] {
+                ["block", hash, bits] => vectors.push((hash.to_string(), u32::from_str_radix(bits, 16).unwrap(), vec![])),
+                [timestamp, bits] => {
+                    vectors.last_mut().unwrap().2.push((timestamp.parse().unwrap(), u32::from_str_radix(bits, 16).unwrap()))
+                }
+                _ => panic!("malformed vector line: {line}"),
+            }
+        }
+        vectors
+    }
+
+    #[test]
+    fn test_matches_golang_vectors() {
+        let vectors = goref_vectors();
+        assert_eq!(vectors.len(), 4);
+        for (hash, expected_bits, blocks) in vectors {
+            assert_eq!(blocks.len(), LEGACY_DIFFICULTY_WINDOW_SIZE, "block {hash}");
+            let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Full);
+            let manager = FullDifficultyManager::new(
+                store,
+                GOREF_GENESIS_BITS,
+                DEVNET_PARAMS.max_difficulty_target,
+                LEGACY_DIFFICULTY_WINDOW_SIZE,
+                LEGACY_DIFFICULTY_WINDOW_SIZE,
+                DEVNET_PARAMS.target_time_per_block,
+            );
+            assert_eq!(manager.calculate_difficulty_bits(&window), expected_bits, "block {hash}");
+
+            // A sampled window with a sample rate of 1 is the full window
+            let (store, window) = build_difficulty_window(&blocks, WindowOrigin::Sampled);
+            let manager = SampledDifficultyManager::new(
+                store,
+                GOREF_GENESIS_BITS,
+                DEVNET_PARAMS.max_difficulty_target,
+                LEGACY_DIFFICULTY_WINDOW_SIZE,
+                LEGACY_DIFFICULTY_WINDOW_SIZE,
+                1,
+                DEVNET_PARAMS.target_time_per_block,
+            );
+            assert_eq!(manager.calculate_difficulty_bits(&window), expected_bits, "block {hash}");
+
+            // The vectors also ground the reference implementation the randomized tests and the fuzz target compare to
+            let reference = reference_difficulty_bits(
+                &blocks,
+                GOREF_GENESIS_BITS,
+                LEGACY_DIFFICULTY_WINDOW_SIZE,
+                DEVNET_PARAMS.target_time_per_block,
+                DEVNET_PARAMS.max_difficulty_target,
+            );
+            assert_eq!(reference, expected_bits, "block {hash}");
+        }
+    }
+
+    #[test]
+    fn test_estimate_network_hashes_per_second() {
+        const MIN_WINDOW_SIZE: usize = 1000;
+        let (store, window) = build_difficulty_window(&even_blocks(999, TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        let manager = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK);
+        assert!(matches!(
+            manager.estimate_network_hashes_per_second(&window, MIN_WINDOW_SIZE),
+            Err(DifficultyError::UnderMinWindowSizeAllowed(999, MIN_WINDOW_SIZE))
+        ));
+        // A lower min window size allows estimating over short windows, such as the ones of young networks
+        assert_eq!(manager.estimate_network_hashes_per_second(&window, 10).unwrap(), 1);
+
+        let (store, window) = build_difficulty_window(&even_blocks(1000, 0, BITS), WindowOrigin::Full);
+        let manager = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK);
+        assert!(matches!(
+            manager.estimate_network_hashes_per_second(&window, MIN_WINDOW_SIZE),
+            Err(DifficultyError::EmptyTimestampRange)
+        ));
+
+        // Blue work spans 1..=1001 over 1000 seconds
+        let (store, window) = build_difficulty_window(&even_blocks(1001, TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        let manager = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK);
+        assert_eq!(manager.estimate_network_hashes_per_second(&window, MIN_WINDOW_SIZE).unwrap(), 1);
+
+        // Windows of less than 2 blocks are always rejected
+        let (store, window) = build_difficulty_window(&even_blocks(1, TARGET_TIME_PER_BLOCK, BITS), WindowOrigin::Full);
+        let manager = full_manager(store, max_target(), TARGET_TIME_PER_BLOCK);
+        assert!(matches!(
+            manager.estimate_network_hashes_per_second(&window, 0),
+            Err(DifficultyError::UnderMinWindowSizeAllowed(1, 2))
+        ));
+    }
+}
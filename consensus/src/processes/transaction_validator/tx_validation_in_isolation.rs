@@ -1,4 +1,4 @@
-use crate::constants::{MAX_SOMPI, TX_VERSION};
+use crate::constants::{MAX_SOMPI, TX_VERSION, TX_VERSION_2};
 use kaspa_consensus_core::tx::Transaction;
 use std::collections::HashSet;
 
@@ -17,7 +17,18 @@ impl TransactionValidator {
         check_duplicate_transaction_inputs(tx)?;
         check_gas(tx)?;
         check_transaction_payload(tx)?;
-        check_transaction_version(tx)
+        self.check_transaction_version(tx)
+    }
+
+    /// Checks the version of the transaction is known. Whether it is activated at a given DAA score is checked
+    /// in context, see [`TransactionValidator::utxo_free_tx_validation`].
+    fn check_transaction_version(&self, tx: &Transaction) -> TxResult<()> {
+        match tx.version {
+            TX_VERSION => Ok(()),
+            // Until an activation is scheduled, version 2 is as unknown as any other version
+            TX_VERSION_2 if self.tx_version_2_activation_daa_score != u64::MAX => Ok(()),
+            version => Err(TxRuleError::UnknownTxVersion(version)),
+        }
     }
 
     fn check_transaction_inputs_in_isolation(&self, tx: &Transaction) -> TxResult<()> {
@@ -114,13 +125,6 @@ fn check_transaction_payload(tx: &Transaction) -> TxResult<()> {
     Ok(())
 }
 
-fn check_transaction_version(tx: &Transaction) -> TxResult<()> {
-    if tx.version != TX_VERSION {
-        return Err(TxRuleError::UnknownTxVersion(tx.version));
-    }
-    Ok(())
-}
-
 fn check_transaction_output_value_ranges(tx: &Transaction) -> TxResult<()> {
     let mut total: u64 = 0;
     for (i, output) in tx.outputs.iter().enumerate() {
@@ -155,7 +159,7 @@ mod tests {
     use kaspa_core::assert_match;
 
     use crate::{
-        constants::TX_VERSION,
+        constants::{TX_VERSION, TX_VERSION_2},
         params::MAINNET_PARAMS,
         processes::transaction_validator::{errors::TxRuleError, TransactionValidator},
     };
@@ -293,8 +297,18 @@ mod tests {
         tx.payload = vec![0];
         assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::NonCoinbaseTxHasPayload));
 
-        let mut tx = valid_tx;
+        let mut tx = valid_tx.clone();
         tx.version = TX_VERSION + 1;
         assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::UnknownTxVersion(_)));
+
+        // Version 2 is unknown until its activation is scheduled and only valid in the context of a later DAA score
+        let mut tx = valid_tx;
+        tx.version = TX_VERSION_2;
+        assert_match!(tv.validate_tx_in_isolation(&tx), Err(TxRuleError::UnknownTxVersion(TX_VERSION_2)));
+        let mut tv = tv;
+        tv.tx_version_2_activation_daa_score = 100;
+        assert_match!(tv.validate_tx_in_isolation(&tx), Ok(()));
+        assert_match!(tv.utxo_free_tx_validation(&tx, 100, 0), Err(TxRuleError::TxVersionNotActivated(TX_VERSION_2, 100)));
+        assert_match!(tv.utxo_free_tx_validation(&tx, 101, 0), Ok(()));
     }
 }
@@ -1,6 +1,6 @@
 use kaspa_consensus_core::tx::Transaction;
 
-use crate::constants::LOCK_TIME_THRESHOLD;
+use crate::constants::{LOCK_TIME_THRESHOLD, TX_VERSION_2};
 
 use super::{
     errors::{TxResult, TxRuleError},
@@ -9,9 +9,17 @@ use super::{
 
 impl TransactionValidator {
     pub fn utxo_free_tx_validation(&self, tx: &Transaction, ctx_daa_score: u64, ctx_block_time: u64) -> TxResult<()> {
+        self.check_tx_version_is_activated(tx, ctx_daa_score)?;
         self.check_tx_is_finalized(tx, ctx_daa_score, ctx_block_time)
     }
 
+    fn check_tx_version_is_activated(&self, tx: &Transaction, ctx_daa_score: u64) -> TxResult<()> {
+        if tx.version == TX_VERSION_2 && ctx_daa_score <= self.tx_version_2_activation_daa_score {
+            return Err(TxRuleError::TxVersionNotActivated(tx.version, self.tx_version_2_activation_daa_score));
+        }
+        Ok(())
+    }
+
     fn check_tx_is_finalized(&self, tx: &Transaction, ctx_daa_score: u64, ctx_block_time: u64) -> TxResult<()> {
         // Lock time of zero means the transaction is finalized.
         if tx.lock_time == 0 {
@@ -12,6 +12,10 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::trace;
 use kaspa_hashes::Hash;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
 
 #[derive(Clone)]
 pub struct DagTraversalManager<T: GhostdagStoreReader, U: ReachabilityStoreReader, V: RelationsStoreReader> {
@@ -144,4 +148,47 @@ impl<T: GhostdagStoreReader, U: ReachabilityStoreReader, V: RelationsStoreReader
 
         current
     }
+
+    pub fn highest_chain_block_below_or_equal_to_blue_score(&self, high: Hash, blue_score: u64) -> Hash {
+        let mut current = high;
+        let mut current_gd = self.ghostdag_store.get_compact_data(high).unwrap();
+
+        while current_gd.blue_score > blue_score && current != self.genesis_hash {
+            assert!(!current.is_origin(), "there's no such known block");
+            current = current_gd.selected_parent;
+            current_gd = self.ghostdag_store.get_compact_data(current).unwrap();
+        }
+
+        current
+    }
+
+    /// Samples `count` blocks, with replacement, among the selected chain blocks of `high` having a blue score of at least
+    /// `low_blue_score`. Each chain block is drawn with a probability proportional to the work it adds to its selected
+    /// parent, i.e., the work of its blue mergeset. The selected parents of the chain blocks in range must have known
+    /// ghostdag data.
+    ///
+    /// The sampled hashes are returned in ascending blue score order.
+    pub fn sample_chain_blocks_by_work(&self, high: Hash, low_blue_score: u64, count: usize, rng: &mut impl Rng) -> Vec<Hash> {
+        let mut chain = Vec::new();
+        let mut weights = Vec::new();
+        let mut current = high;
+        let mut current_gd = self.ghostdag_store.get_compact_data(high).unwrap();
+        while current_gd.blue_score >= low_blue_score && current != self.genesis_hash {
+            assert!(!current.is_origin(), "there's no such known block");
+            let selected_parent_gd = self.ghostdag_store.get_compact_data(current_gd.selected_parent).unwrap();
+            chain.push(current);
+            weights.push((current_gd.blue_work - selected_parent_gd.blue_work).as_f64());
+            current = current_gd.selected_parent;
+            current_gd = selected_parent_gd;
+        }
+
+        let Ok(distribution) = WeightedIndex::new(&weights) else {
+            // The range is empty or holds no work
+            return vec![];
+        };
+        // The chain was collected downwards, so descending indices yield ascending blue scores
+        let mut indices = (0..count).map(|_| distribution.sample(rng)).collect_vec();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        indices.into_iter().map(|i| chain[i]).collect()
+    }
 }
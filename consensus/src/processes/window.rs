@@ -50,7 +50,7 @@ pub trait WindowManager {
     fn block_daa_window(&self, ghostdag_data: &GhostdagData) -> Result<DaaWindow, RuleError>;
     fn calculate_difficulty_bits(&self, ghostdag_data: &GhostdagData, daa_window: &DaaWindow) -> u32;
     fn calc_past_median_time(&self, ghostdag_data: &GhostdagData) -> Result<(u64, Arc<BlockWindowHeap>), RuleError>;
-    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>) -> DifficultyResult<u64>;
+    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>, min_window_size: usize) -> DifficultyResult<u64>;
     fn window_size(&self, ghostdag_data: &GhostdagData, window_type: WindowType) -> usize;
     fn sample_rate(&self, ghostdag_data: &GhostdagData, window_type: WindowType) -> u64;
 }
@@ -215,8 +215,8 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader> Wi
         Ok((past_median_time, window))
     }
 
-    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>) -> DifficultyResult<u64> {
-        self.difficulty_manager.estimate_network_hashes_per_second(&window)
+    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>, min_window_size: usize) -> DifficultyResult<u64> {
+        self.difficulty_manager.estimate_network_hashes_per_second(&window, min_window_size)
     }
 
     fn window_size(&self, _ghostdag_data: &GhostdagData, window_type: WindowType) -> usize {
@@ -493,8 +493,8 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader, W:
         Ok((past_median_time, window))
     }
 
-    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>) -> DifficultyResult<u64> {
-        self.difficulty_manager.estimate_network_hashes_per_second(&window)
+    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>, min_window_size: usize) -> DifficultyResult<u64> {
+        self.difficulty_manager.estimate_network_hashes_per_second(&window, min_window_size)
     }
 
     fn window_size(&self, _ghostdag_data: &GhostdagData, window_type: WindowType) -> usize {
@@ -625,8 +625,8 @@ impl<T: GhostdagStoreReader, U: BlockWindowCacheReader, V: HeaderStoreReader, W:
         }
     }
 
-    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>) -> DifficultyResult<u64> {
-        self.sampled_window_manager.estimate_network_hashes_per_second(window)
+    fn estimate_network_hashes_per_second(&self, window: Arc<BlockWindowHeap>, min_window_size: usize) -> DifficultyResult<u64> {
+        self.sampled_window_manager.estimate_network_hashes_per_second(window, min_window_size)
     }
 
     fn window_size(&self, ghostdag_data: &GhostdagData, window_type: WindowType) -> usize {
@@ -1,12 +1,23 @@
+use crate::{
+    model::stores::{
+        block_window_cache::{BlockWindowHeap, WindowOrigin},
+        headers::{CompactHeaderData, HeaderStoreReader, HeaderWithBlockLevel},
+    },
+    processes::ghostdag::ordering::SortableBlock,
+};
 use kaspa_consensus_core::{
     block::Block,
     header::Header,
     subnets::SubnetworkId,
     tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
     utxo::utxo_collection::UtxoCollection,
+    BlockHashMap, HashMapCustomHasher,
 };
+use kaspa_database::prelude::StoreError;
 use kaspa_hashes::{Hash, HASH_SIZE};
+use kaspa_math::{Uint256, Uint320};
 use rand::{rngs::SmallRng, seq::SliceRandom, Rng};
+use std::{cmp::Reverse, sync::Arc};
 
 pub fn header_from_precomputed_hash(hash: Hash, parents: Vec<Hash>) -> Header {
     Header::from_precomputed_hash(hash, parents)
@@ -157,4 +168,84 @@ pub fn generate_random_transaction_outpoint(rng: &mut SmallRng) -> TransactionOu
     TransactionOutpoint::new(generate_random_hash(rng), rng.gen())
 }
 
+/// A header store only holding the compact header data read by the difficulty managers
+#[derive(Default)]
+pub struct HeaderStoreMock {
+    pub map: BlockHashMap<CompactHeaderData>,
+}
+
+#[allow(unused_variables)]
+impl HeaderStoreReader for HeaderStoreMock {
+    fn get_daa_score(&self, hash: Hash) -> Result<u64, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_blue_score(&self, hash: Hash) -> Result<u64, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_timestamp(&self, hash: Hash) -> Result<u64, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_bits(&self, hash: Hash) -> Result<u32, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_header(&self, hash: Hash) -> Result<Arc<Header>, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_header_with_block_level(&self, hash: Hash) -> Result<HeaderWithBlockLevel, StoreError> {
+        unimplemented!()
+    }
+
+    fn get_compact_header_data(&self, hash: Hash) -> Result<CompactHeaderData, StoreError> {
+        Ok(*self.map.get(&hash).unwrap())
+    }
+}
+
+/// Builds a difficulty window out of `(timestamp, bits)` pairs, the blue work of each block being its position in `blocks`
+pub fn build_difficulty_window(blocks: &[(u64, u32)], origin: WindowOrigin) -> (Arc<HeaderStoreMock>, BlockWindowHeap) {
+    let mut map = BlockHashMap::new();
+    let mut window = BlockWindowHeap::with_capacity(origin, blocks.len());
+    for (i, &(timestamp, bits)) in blocks.iter().enumerate() {
+        let hash: Hash = (i as u64 + 1).into();
+        map.insert(hash, CompactHeaderData { daa_score: i as u64, timestamp, bits, blue_score: i as u64 });
+        window.push(Reverse(SortableBlock { hash, blue_work: (i as u64 + 1).into() }));
+    }
+    (Arc::new(HeaderStoreMock { map }), window)
+}
+
+/// Plain restatement of the difficulty adjustment of the golang implementation: the average target of the window
+/// without its earliest block, scaled by the measured duration of the window over its expected duration and capped
+/// by the max target. Windows shorter than `min_window_len` keep the genesis bits.
+///
+/// Among blocks sharing the earliest timestamp, the one of lowest blue work, hence lowest position in `blocks`, is
+/// considered the earliest.
+pub fn reference_difficulty_bits(
+    blocks: &[(u64, u32)],
+    genesis_bits: u32,
+    min_window_len: usize,
+    expected_time_per_block: u64,
+    max_target: Uint256,
+) -> u32 {
+    if blocks.len() < min_window_len {
+        return genesis_bits;
+    }
+    let min_index = (0..blocks.len()).min_by_key(|&i| blocks[i].0).unwrap();
+    let min_ts = blocks[min_index].0;
+    let max_ts = blocks.iter().map(|x| x.0).max().unwrap();
+    let targets: Vec<Uint320> = blocks
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != min_index)
+        .map(|(_, &(_, bits))| Uint320::from(Uint256::from_compact_target_bits(bits)))
+        .collect();
+    let len = targets.len() as u64;
+    let average_target = targets.into_iter().sum::<Uint320>() / len;
+    let new_target = average_target * (max_ts - min_ts).max(1) / expected_time_per_block / len;
+    Uint256::try_from(new_target.min(max_target.into())).unwrap().compact_target_bits()
+}
+
 //TODO: create `assert_eq_<kaspa-sturct>!()` helper macros in `consensus::test_helpers`
@@ -33,6 +33,10 @@ impl AppenderSpec {
     }
 
     pub fn roller(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str) -> Self {
+        Self::roller_with_pattern(name, level, log_dir, file_name, LOG_LINE_PATTERN)
+    }
+
+    pub fn roller_with_pattern(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str, pattern: &str) -> Self {
         let appender = {
             let trigger = Box::new(SizeTrigger::new(LOG_FILE_MAX_SIZE));
 
@@ -47,7 +51,7 @@ impl AppenderSpec {
 
             let compound_policy = Box::new(CompoundPolicy::new(trigger, roller));
             let file_appender = RollingFileAppender::builder()
-                .encoder(Box::new(PatternEncoder::new(LOG_LINE_PATTERN)))
+                .encoder(Box::new(PatternEncoder::new(pattern)))
                 .build(file_path, compound_policy)
                 .unwrap();
 
@@ -2,6 +2,7 @@ pub const DEFAULT_LOGGER_ENV: &str = "RUST_LOG";
 
 pub const LOG_FILE_NAME: &str = "rusty-kaspa.log";
 pub const ERR_LOG_FILE_NAME: &str = "rusty-kaspa_err.log";
+pub const ACCESS_LOG_FILE_NAME: &str = "rusty-kaspa_access.log";
 
 pub const LOG_ARCHIVE_SUFFIX: &str = ".{}.gz";
 
@@ -13,3 +14,5 @@ pub const LOG_FILE_MAX_SIZE: u64 = 100_000_000;
 pub const LOG_LINE_PATTERN_COLORED: &str = "{d(%Y-%m-%d %H:%M:%S%.3f%:z)} [{h({({l}):5.5})}] {m}{n}";
 /// File log line pattern, with offset from the local time to UTC (UTC being +00:00)
 pub const LOG_LINE_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S%.3f%:z)} [{({l}):5.5}] {m}{n}";
+/// Access log line pattern, the records being self-describing JSON objects
+pub const ACCESS_LOG_LINE_PATTERN: &str = "{m}{n}";
@@ -17,19 +17,34 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Target of the access log records, written as JSON lines to a file of their own when the access log is enabled
+/// and discarded otherwise
+pub const ACCESS_LOG_TARGET: &str = "kaspa_access";
+
 pub fn set_log_level(level: LevelFilter) {
     workflow_log::set_log_level(level);
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn init_logger(log_dir: Option<&str>, filters: &str) {
+    init_logger_with_access_log(log_dir, filters, false)
+}
+
+/// Inits the global logger, writing the [`ACCESS_LOG_TARGET`] records to a rolling access log file of `log_dir`
+/// if `access_log` is set
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_logger_with_access_log(log_dir: Option<&str>, filters: &str, access_log: bool) {
     use crate::log::appender::AppenderSpec;
-    use log4rs::{config::Root, Config};
+    use log4rs::{
+        config::{Logger, Root},
+        Config,
+    };
     use std::iter::once;
 
     const CONSOLE_APPENDER: &str = "stdout";
     const LOG_FILE_APPENDER: &str = "log_file";
     const ERR_LOG_FILE_APPENDER: &str = "err_log_file";
+    const ACCESS_LOG_FILE_APPENDER: &str = "access_log_file";
 
     let level = LevelFilter::Info;
     let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
@@ -38,11 +53,24 @@ pub fn init_logger(log_dir: Option<&str>, filters: &str) {
     let mut file_appender = log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME));
     let mut err_file_appender =
         log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME));
-    let appenders = once(&mut stdout_appender).chain(&mut file_appender).chain(&mut err_file_appender).map(|x| x.appender());
+    let mut access_file_appender = log_dir
+        .filter(|_| access_log)
+        .map(|x| AppenderSpec::roller_with_pattern(ACCESS_LOG_FILE_APPENDER, None, x, ACCESS_LOG_FILE_NAME, ACCESS_LOG_LINE_PATTERN));
+    // The access records never reach the other appenders
+    let access_logger = match access_file_appender {
+        Some(_) => Logger::builder().appender(ACCESS_LOG_FILE_APPENDER).additive(false).build(ACCESS_LOG_TARGET, LevelFilter::Info),
+        None => Logger::builder().additive(false).build(ACCESS_LOG_TARGET, LevelFilter::Off),
+    };
+    let appenders = once(&mut stdout_appender)
+        .chain(&mut file_appender)
+        .chain(&mut err_file_appender)
+        .chain(&mut access_file_appender)
+        .map(|x| x.appender());
 
     let config = Config::builder()
         .appenders(appenders)
         .loggers(loggers.items())
+        .logger(access_logger)
         .build(
             Root::builder()
                 .appenders(once(&stdout_appender).chain(&file_appender).chain(&err_file_appender).map(|x| x.name))
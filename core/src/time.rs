@@ -1,4 +1,11 @@
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// Returns the number of milliseconds since UNIX EPOCH
 #[inline]
@@ -6,6 +13,62 @@ pub fn unix_now() -> u64 {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
 }
 
+/// A source of wall-clock time. Components reading the time through a clock rather than
+/// through [`unix_now`] can be driven by a [`VirtualClock`] in simulations and tests
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the number of milliseconds since UNIX EPOCH
+    fn unix_now(&self) -> u64;
+}
+
+pub type DynClock = Arc<dyn Clock>;
+
+/// The clock of the operating system
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn unix_now(&self) -> u64 {
+        unix_now()
+    }
+}
+
+impl SystemClock {
+    pub fn new_dyn() -> DynClock {
+        Arc::new(Self)
+    }
+}
+
+/// A clock standing still until explicitly set or advanced, allowing the deterministic
+/// reproduction of timestamp dependent behaviors
+#[derive(Debug, Default)]
+pub struct VirtualClock {
+    now: AtomicU64,
+}
+
+impl VirtualClock {
+    /// Creates a clock set at `now`, in milliseconds since UNIX EPOCH
+    pub fn new(now: u64) -> Self {
+        Self { now: AtomicU64::new(now) }
+    }
+
+    pub fn set(&self, now: u64) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward by `millis` milliseconds and returns the new time
+    pub fn advance(&self, millis: u64) -> u64 {
+        self.now.fetch_add(millis, Ordering::SeqCst) + millis
+    }
+}
+
+impl Clock for VirtualClock {
+    #[inline]
+    fn unix_now(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
 /// Stopwatch which reports on drop if the timed operation passed the threshold `TR` in milliseconds
 pub struct Stopwatch<const TR: u64 = 1000> {
     name: &'static str,
@@ -135,6 +135,14 @@ fn get_sig_op_count_by_opcodes<T: VerifiableTransaction>(opcodes: &[Result<Box<d
     num_sigs
 }
 
+/// Returns whether the passed public key script is a data carrier, that is an OP_RETURN followed by data pushes only.
+///
+/// Such a script is provably unspendable and its only purpose is to embed data into a transaction.
+pub fn is_data_carrier<T: VerifiableTransaction>(script: &[u8]) -> bool {
+    let mut opcodes = parse_script::<T>(script);
+    matches!(opcodes.next(), Some(Ok(op)) if op.value() == OpReturn) && opcodes.all(|op| op.is_ok_and(|op| op.is_push_opcode()))
+}
+
 /// Returns whether the passed public key script is unspendable, or guaranteed to fail at execution.
 ///
 /// This allows inputs to be pruned instantly when entering the UTXO set.
@@ -1,4 +1,9 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{
+    cache::CachePolicy,
+    compression::{CompressionConfig, Compressor},
+    db::DB,
+    errors::{check_write_fault, StoreError},
+};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use kaspa_utils::mem_size::MemSizeEstimator;
@@ -20,6 +25,9 @@ where
 
     // DB bucket/path
     prefix: Vec<u8>,
+
+    // Compression of the values at rest
+    compressor: Option<Arc<Compressor>>,
 }
 
 impl<TKey, TData, S> CachedDbAccess<TKey, TData, S>
@@ -29,7 +37,40 @@ where
     S: BuildHasher + Default,
 {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy, prefix: Vec<u8>) -> Self {
-        Self { db, cache: Cache::new(cache_policy), prefix }
+        Self { db, cache: Cache::new(cache_policy), prefix, compressor: None }
+    }
+
+    /// Clones the access with a new, empty cache. The compressor, if any, is shared with the clone
+    pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
+        Self { db: self.db.clone(), cache: Cache::new(cache_policy), prefix: self.prefix.clone(), compressor: self.compressor.clone() }
+    }
+
+    /// Enables reading compressed values, and compressing the values being written if `config` is set
+    pub fn with_compression(self, config: Option<CompressionConfig>) -> Self {
+        let compressor =
+            Compressor::new(self.db.clone(), &self.prefix, config).expect("compression dictionary is expected to be readable");
+        Self { compressor: Some(Arc::new(compressor)), ..self }
+    }
+
+    fn serialize(&self, data: &TData) -> Result<Vec<u8>, StoreError>
+    where
+        TData: Serialize,
+    {
+        let bin_data = bincode::serialize(data)?;
+        match self.compressor {
+            Some(ref compressor) => compressor.encode(bin_data),
+            None => Ok(bin_data),
+        }
+    }
+
+    fn deserialize(&self, bin_data: &[u8]) -> Result<TData, StoreError>
+    where
+        TData: DeserializeOwned,
+    {
+        match self.compressor {
+            Some(ref compressor) => Ok(bincode::deserialize(&compressor.decode(bin_data)?)?),
+            None => Ok(bincode::deserialize(bin_data)?),
+        }
     }
 
     pub fn read_from_cache(&self, key: TKey) -> Option<TData>
@@ -56,7 +97,7 @@ where
         } else {
             let db_key = DbKey::new(&self.prefix, key.clone());
             if let Some(slice) = self.db.get_pinned(&db_key)? {
-                let data: TData = bincode::deserialize(&slice)?;
+                let data = self.deserialize(&slice)?;
                 self.cache.insert(key, data.clone());
                 Ok(data)
             } else {
@@ -75,7 +116,7 @@ where
         read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
         self.db.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts).map(move |iter_result| {
             match iter_result {
-                Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
+                Ok((key, data_bytes)) => match self.deserialize(&data_bytes) {
                     Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
                     Err(e) => Err(e.into()),
                 },
@@ -89,7 +130,8 @@ where
         TKey: Clone + AsRef<[u8]>,
         TData: Serialize,
     {
-        let bin_data = bincode::serialize(&data)?;
+        check_write_fault()?;
+        let bin_data = self.serialize(&data)?;
         self.cache.insert(key.clone(), data);
         writer.put(DbKey::new(&self.prefix, key), bin_data)?;
         Ok(())
@@ -104,10 +146,11 @@ where
         TKey: Clone + AsRef<[u8]>,
         TData: Serialize,
     {
+        check_write_fault()?;
         let iter_clone = iter.clone();
         self.cache.insert_many(iter);
         for (key, data) in iter_clone {
-            let bin_data = bincode::serialize(&data)?;
+            let bin_data = self.serialize(&data)?;
             writer.put(DbKey::new(&self.prefix, key.clone()), bin_data)?;
         }
         Ok(())
@@ -123,8 +166,9 @@ where
         TKey: Clone + AsRef<[u8]>,
         TData: Serialize,
     {
+        check_write_fault()?;
         for (key, data) in iter {
-            let bin_data = bincode::serialize(&data)?;
+            let bin_data = self.serialize(&data)?;
             writer.put(DbKey::new(&self.prefix, key), bin_data)?;
         }
         // We must clear the cache in order to avoid invalidated entries
@@ -136,6 +180,7 @@ where
     where
         TKey: Clone + AsRef<[u8]>,
     {
+        check_write_fault()?;
         self.cache.remove(&key);
         writer.delete(DbKey::new(&self.prefix, key))?;
         Ok(())
@@ -145,6 +190,7 @@ where
     where
         TKey: Clone + AsRef<[u8]>,
     {
+        check_write_fault()?;
         let key_iter_clone = key_iter.clone();
         self.cache.remove_many(key_iter);
         for key in key_iter_clone {
@@ -158,6 +204,7 @@ where
     where
         TKey: Clone + AsRef<[u8]>,
     {
+        check_write_fault()?;
         self.cache.remove_all();
         let db_key = DbKey::prefix_only(&self.prefix);
         let (from, to) = rocksdb::PrefixRange(db_key.as_ref()).into_bounds();
@@ -202,7 +249,7 @@ where
         }
 
         db_iterator.take(limit).map(move |item| match item {
-            Ok((key_bytes, value_bytes)) => match bincode::deserialize::<TData>(value_bytes.as_ref()) {
+            Ok((key_bytes, value_bytes)) => match self.deserialize(value_bytes.as_ref()) {
                 Ok(value) => Ok((key_bytes[db_key.prefix_len()..].into(), value)),
                 Err(err) => Err(err.into()),
             },
@@ -2,7 +2,23 @@ use indexmap::IndexMap;
 use kaspa_utils::mem_size::{MemMode, MemSizeEstimator};
 use parking_lot::RwLock;
 use rand::Rng;
-use std::{collections::hash_map::RandomState, hash::BuildHasher, sync::Arc};
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Hits and misses of the reads of all the caches, reported by the storage metrics
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the numbers of cache hits and misses since the process started
+pub(crate) fn read_counters() -> (u64, u64) {
+    (CACHE_HITS.load(Ordering::Relaxed), CACHE_MISSES.load(Ordering::Relaxed))
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum CachePolicy {
@@ -146,7 +162,15 @@ where
     }
 
     pub fn get(&self, key: &TKey) -> Option<TData> {
-        self.inner.read().map.get(key).cloned()
+        let data = self.inner.read().map.get(key).cloned();
+        // Empty caches are a configuration choice rather than a cache performance issue
+        if self.policy.max_size > 0 {
+            match data {
+                Some(_) => CACHE_HITS.fetch_add(1, Ordering::Relaxed),
+                None => CACHE_MISSES.fetch_add(1, Ordering::Relaxed),
+            };
+        }
+        data
     }
 
     pub fn contains_key(&self, key: &TKey) -> bool {
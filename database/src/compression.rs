@@ -0,0 +1,308 @@
+//! Optional zstd compression of store values at rest.
+//!
+//! Values are compressed with a dictionary trained on the first values written to the store, which considerably
+//! improves the compression ratio of small values sharing a common structure (such as block transactions). The
+//! dictionary is trained by a background thread, so the write path is never blocked by the training, and is
+//! persisted in the DB and loaded on start-up, so values compressed with it remain readable.
+//!
+//! Compressed values are identified by the zstd frame magic number they start with. A value written uncompressed
+//! never starts with it (see [`Compressor::encode`]), so compression can be enabled (or disabled) on an existing DB,
+//! as long as the values written before the store supported compression do not start with it either. This holds for
+//! the sequences (such as block transactions and acceptance data), which bincode prefixes with their `u64` length:
+//! starting with the magic number would take a sequence of at least `0xFD2F_B528` items.
+
+use crate::{db::DB, errors::StoreError, key::DbKey, registry::DatabaseStorePrefixes};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    borrow::Cow,
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+/// The zstd frame magic number, in little-endian order
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Sizes of the values written by the compressing stores, before and after compression, reported by the storage metrics
+static UNCOMPRESSED_BYTES: AtomicU64 = AtomicU64::new(0);
+static COMPRESSED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the total sizes of the values compressed since the process started, before and after compression
+pub(crate) fn read_counters() -> (u64, u64) {
+    (UNCOMPRESSED_BYTES.load(Ordering::Relaxed), COMPRESSED_BYTES.load(Ordering::Relaxed))
+}
+
+/// Compression settings of a store
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// The zstd compression level
+    pub level: i32,
+    /// Max size in bytes of the dictionary trained for the store. Zero disables dictionary training
+    pub dictionary_size: usize,
+    /// Number of values sampled for training the dictionary
+    pub training_samples: usize,
+    /// Max number of leading bytes of a value kept as a training sample, bounding the memory held by the samples
+    /// and the duration of the training
+    pub max_sample_size: usize,
+}
+
+impl CompressionConfig {
+    pub fn new(level: i32) -> Self {
+        Self { level, ..Default::default() }
+    }
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self { level: 3, dictionary_size: 64 * 1024, training_samples: 1024, max_sample_size: 8 * 1024 }
+    }
+}
+
+struct Dictionaries {
+    encoder: EncoderDictionary<'static>,
+    decoder: DecoderDictionary<'static>,
+}
+
+impl Dictionaries {
+    fn new(dictionary: &[u8], level: i32) -> Self {
+        Self { encoder: EncoderDictionary::copy(dictionary, level), decoder: DecoderDictionary::copy(dictionary) }
+    }
+}
+
+#[derive(Default)]
+struct Training {
+    samples: Vec<Vec<u8>>,
+    /// The thread training the dictionary, once enough samples were collected
+    trainer: Option<JoinHandle<()>>,
+}
+
+/// Compresses and decompresses the values of a single store
+pub struct Compressor {
+    db: Arc<DB>,
+    /// Compression settings, if values being written are compressed
+    config: Option<CompressionConfig>,
+    /// The DB key the dictionary of the store is persisted at
+    dictionary_key: DbKey,
+    dictionaries: Arc<RwLock<Option<Dictionaries>>>,
+    training: Mutex<Training>,
+}
+
+impl Compressor {
+    /// Creates the compressor of the store with prefix `prefix`, loading its dictionary if it was already trained.
+    /// Values are compressed only if `config` is set, while values compressed previously remain readable regardless
+    pub fn new(db: Arc<DB>, prefix: &[u8], config: Option<CompressionConfig>) -> Result<Self, StoreError> {
+        let dictionary_key = DbKey::new(DatabaseStorePrefixes::CompressionDictionaries.as_ref(), prefix);
+        let level = config.map_or(CompressionConfig::default().level, |config| config.level);
+        let dictionaries = db.get_pinned(&dictionary_key)?.map(|dictionary| Dictionaries::new(&dictionary, level));
+        Ok(Self { db, config, dictionary_key, dictionaries: Arc::new(RwLock::new(dictionaries)), training: Default::default() })
+    }
+
+    pub fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, StoreError> {
+        let Some(config) = self.config else {
+            // A value starting with the magic number would be mistaken for a compressed value, so it gets compressed anyway
+            if data.starts_with(&ZSTD_MAGIC) {
+                return Ok(zstd::bulk::compress(&data, CompressionConfig::default().level)?);
+            }
+            return Ok(data);
+        };
+        let compressed = match *self.dictionaries.read() {
+            Some(ref dictionaries) => zstd::bulk::Compressor::with_prepared_dictionary(&dictionaries.encoder)?.compress(&data)?,
+            None => {
+                if config.dictionary_size > 0 {
+                    self.sample(config, &data);
+                }
+                zstd::bulk::compress(&data, config.level)?
+            }
+        };
+        UNCOMPRESSED_BYTES.fetch_add(data.len() as u64, Ordering::Relaxed);
+        COMPRESSED_BYTES.fetch_add(compressed.len() as u64, Ordering::Relaxed);
+        Ok(compressed)
+    }
+
+    pub fn decode<'a>(&self, data: &'a [u8]) -> Result<Cow<'a, [u8]>, StoreError> {
+        if !data.starts_with(&ZSTD_MAGIC) {
+            return Ok(data.into());
+        }
+        let decompressed = match zstd::zstd_safe::get_dict_id_from_frame(data) {
+            None => zstd::stream::decode_all(data)?,
+            Some(_) => {
+                let dictionaries = self.dictionaries.read();
+                let dictionaries = dictionaries.as_ref().ok_or_else(|| {
+                    StoreError::CompressionError(io::Error::new(io::ErrorKind::NotFound, "missing compression dictionary"))
+                })?;
+                let mut decoder = zstd::stream::read::Decoder::with_prepared_dictionary(data, &dictionaries.decoder)?;
+                let mut decompressed = Vec::new();
+                io::Read::read_to_end(&mut decoder, &mut decompressed)?;
+                decompressed
+            }
+        };
+        Ok(decompressed.into())
+    }
+
+    /// Collects the leading bytes of `data` as a training sample, and starts training the dictionary in the
+    /// background once enough samples were collected
+    fn sample(&self, config: CompressionConfig, data: &[u8]) {
+        let mut training = self.training.lock();
+        if training.trainer.is_some() {
+            return;
+        }
+        training.samples.push(data[..data.len().min(config.max_sample_size)].to_vec());
+        if training.samples.len() < config.training_samples {
+            return;
+        }
+        let samples = std::mem::take(&mut training.samples);
+        let (db, dictionary_key, dictionaries) = (self.db.clone(), self.dictionary_key.clone(), self.dictionaries.clone());
+        // Should the thread fail to spawn, the samples get collected again for another attempt
+        training.trainer = thread::Builder::new()
+            .name("compression-dictionary-trainer".to_string())
+            .spawn(move || train_dictionary(db, dictionary_key, dictionaries, samples, config))
+            .ok();
+    }
+}
+
+/// Trains a dictionary out of `samples` and persists it, before making it available for compressing values so
+/// that no value compressed with it gets written before it. Values keep being compressed without a dictionary
+/// if the training fails.
+fn train_dictionary(
+    db: Arc<DB>,
+    dictionary_key: DbKey,
+    dictionaries: Arc<RwLock<Option<Dictionaries>>>,
+    samples: Vec<Vec<u8>>,
+    config: CompressionConfig,
+) {
+    let Ok(dictionary) = zstd::dict::from_samples(&samples, config.dictionary_size) else {
+        return;
+    };
+    drop(samples);
+    if db.put(&dictionary_key, &dictionary).is_ok() {
+        *dictionaries.write() = Some(Dictionaries::new(&dictionary, config.level));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_temp_db, prelude::ConnBuilder};
+
+    /// A value made of a common structure with some random content, as for block transactions
+    fn value(i: u64) -> Vec<u8> {
+        let mut value = Vec::new();
+        for j in 0..8u64 {
+            value.extend_from_slice(&[0u8, 0, 0, 0, 1, 0, 0, 0]);
+            for k in 0..4u64 {
+                value.extend_from_slice(&(i * 32 + j * 4 + k).wrapping_mul(0x9E37_79B9_7F4A_7C15).to_le_bytes());
+            }
+            value.extend_from_slice(&[0x20, 0xAA, 0xBB, 0xCC, 0xDD, 0xAC, 0, 0, 0, 0, 0, 0]);
+            value.extend_from_slice(&(i * 1000 + j).to_le_bytes());
+        }
+        value
+    }
+
+    /// Waits for the background training of the dictionary, if started
+    fn wait_for_training(compressor: &Compressor) {
+        let trainer = compressor.training.lock().trainer.take();
+        if let Some(trainer) = trainer {
+            trainer.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_compression() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = CompressionConfig { level: 3, dictionary_size: 1024, training_samples: 512, max_sample_size: 1024 };
+        let compressor = Compressor::new(db.clone(), &[2], Some(config)).unwrap();
+
+        // Uncompressed values written before compression was enabled remain readable
+        assert_eq!(compressor.decode(&value(0)).unwrap().as_ref(), value(0).as_slice());
+
+        let mut encoded = (0..512).map(|i| compressor.encode(value(i)).unwrap()).collect::<Vec<_>>();
+        wait_for_training(&compressor);
+        assert!(compressor.dictionaries.read().is_some());
+        encoded.extend((512..1024).map(|i| compressor.encode(value(i)).unwrap()));
+        for (i, encoded) in encoded.iter().enumerate() {
+            assert_eq!(compressor.decode(encoded).unwrap().as_ref(), value(i as u64).as_slice());
+        }
+
+        // Values compressed with the trained dictionary are smaller than the ones compressed before training
+        let raw_size = (0..512).map(|i| value(i).len()).sum::<usize>();
+        let plain_size = encoded[..512].iter().map(|x| x.len()).sum::<usize>();
+        let trained_size = encoded[512..].iter().map(|x| x.len()).sum::<usize>();
+        // Values lacking a dictionary are barely compressible, so they only grow by the frame overhead
+        assert!(plain_size <= raw_size + 512 * 16);
+        assert!(trained_size < plain_size);
+
+        // The dictionary is loaded on restart, and values stay readable when compression gets disabled
+        let compressor = Compressor::new(db, &[2], None).unwrap();
+        assert_eq!(compressor.decode(&encoded[600]).unwrap().as_ref(), value(600).as_slice());
+        assert_eq!(compressor.encode(value(200)).unwrap(), value(200));
+    }
+
+    #[test]
+    fn test_dictionary_training() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = CompressionConfig { level: 3, dictionary_size: 1024, training_samples: 256, max_sample_size: 64 };
+        let compressor = Compressor::new(db.clone(), &[3], Some(config)).unwrap();
+        let dictionary_key = DbKey::new(DatabaseStorePrefixes::CompressionDictionaries.as_ref(), [3u8]);
+
+        // Only the leading bytes of the values are kept as samples
+        let encoded = (0..255).map(|i| compressor.encode(value(i)).unwrap()).collect::<Vec<_>>();
+        {
+            let training = compressor.training.lock();
+            assert_eq!(training.samples.len(), 255);
+            assert!(training.samples.iter().enumerate().all(|(i, sample)| sample.as_slice() == &value(i as u64)[..64]));
+            assert!(training.trainer.is_none());
+        }
+
+        // The last sample starts the training in the background, the value itself being compressed without dictionary
+        let last = compressor.encode(value(255)).unwrap();
+        assert_eq!(zstd::zstd_safe::get_dict_id_from_frame(&last), None);
+        assert!(compressor.training.lock().samples.is_empty());
+        wait_for_training(&compressor);
+
+        // The trained dictionary is persisted and used for the values written next
+        assert!(db.get_pinned(&dictionary_key).unwrap().is_some());
+        let trained = compressor.encode(value(256)).unwrap();
+        assert!(zstd::zstd_safe::get_dict_id_from_frame(&trained).is_some());
+        assert_eq!(compressor.decode(&trained).unwrap().as_ref(), value(256).as_slice());
+        for (i, encoded) in encoded.iter().enumerate() {
+            assert_eq!(compressor.decode(encoded).unwrap().as_ref(), value(i as u64).as_slice());
+        }
+    }
+
+    #[test]
+    fn test_values_starting_with_magic() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let compressor = Compressor::new(db, &[4], None).unwrap();
+
+        // A raw value which would be mistaken for a compressed one is compressed even though compression is disabled
+        let value = [ZSTD_MAGIC.as_slice(), &[1, 2, 3, 4]].concat();
+        let encoded = compressor.encode(value.clone()).unwrap();
+        assert_ne!(encoded, value);
+        assert_eq!(compressor.decode(&encoded).unwrap().as_ref(), value.as_slice());
+
+        // Other values are written as they are
+        assert_eq!(compressor.encode(vec![1, 2, 3, 4]).unwrap(), vec![1, 2, 3, 4]);
+
+        // Sequences start with their length, so only a sequence of at least 0xFD2F_B528 items could start with the magic number
+        let sequence = bincode::serialize(&vec![u64::MAX; 3]).unwrap();
+        assert_eq!(sequence[..8], 3u64.to_le_bytes());
+        assert_eq!(u32::from_le_bytes(ZSTD_MAGIC), 0xFD2F_B528);
+    }
+
+    #[test]
+    fn test_compression_counters() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let compressor = Compressor::new(db, &[5], Some(CompressionConfig { dictionary_size: 0, ..Default::default() })).unwrap();
+        let (uncompressed_before, compressed_before) = read_counters();
+        let encoded = compressor.encode(vec![7; 4096]).unwrap();
+        let (uncompressed_after, compressed_after) = read_counters();
+        // Other tests may compress values concurrently
+        assert!(uncompressed_after - uncompressed_before >= 4096);
+        assert!(compressed_after - compressed_before >= encoded.len() as u64);
+        assert!(encoded.len() < 4096);
+    }
+}
@@ -13,6 +13,8 @@ pub struct ConnBuilder<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> {
     files_limit: FDLimit,
     mem_budget: usize,
     stats_period: StatsPeriod,
+    secondary_path: Option<PathBuf>,
+    in_memory: bool,
 }
 
 impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
@@ -24,6 +26,8 @@ impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
             mem_budget: 64 * 1024 * 1024,
             stats_period: Unspecified,
             files_limit: Unspecified,
+            secondary_path: None,
+            in_memory: false,
         }
     }
 }
@@ -37,6 +41,8 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            secondary_path: self.secondary_path,
+            in_memory: self.in_memory,
         }
     }
     pub fn with_create_if_missing(self, create_if_missing: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
@@ -48,6 +54,19 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
     pub fn with_mem_budget(self, mem_budget: impl Into<usize>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
         ConnBuilder { mem_budget: mem_budget.into(), ..self }
     }
+    /// Opens the DB at the DB path as a read-only secondary instance of the primary instance owning it,
+    /// `secondary_path` being where the secondary instance keeps its own info logs.
+    ///
+    /// A secondary instance only observes writes of the primary made before opening or before its last
+    /// call to `try_catch_up_with_primary`, and fails any write.
+    pub fn with_secondary_path(self, secondary_path: PathBuf) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { secondary_path: Some(secondary_path), ..self }
+    }
+    /// Keeps the DB entirely in memory, the DB path only naming it. Nothing is written to the filesystem
+    /// and the DB content is lost once it is dropped.
+    pub fn with_in_memory(self, in_memory: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { in_memory, ..self }
+    }
     pub fn with_files_limit(self, files_limit: impl Into<i32>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, i32> {
         ConnBuilder {
             db_path: self.db_path,
@@ -56,6 +75,8 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            secondary_path: self.secondary_path,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -69,6 +90,8 @@ impl<Path, FDLimit> ConnBuilder<Path, false, Unspecified, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
+            secondary_path: self.secondary_path,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -82,6 +105,8 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: Unspecified,
+            secondary_path: self.secondary_path,
+            in_memory: self.in_memory,
         }
     }
     pub fn with_stats_period(self, stats_period: impl Into<u32>) -> ConnBuilder<Path, true, u32, FDLimit> {
@@ -92,6 +117,8 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
             stats_period: stats_period.into(),
+            secondary_path: self.secondary_path,
+            in_memory: self.in_memory,
         }
     }
 }
@@ -105,16 +132,38 @@ macro_rules! default_opts {
 
         opts.optimize_level_style_compaction($self.mem_budget);
         let guard = kaspa_utils::fd_budget::acquire_guard($self.files_limit)?;
-        opts.set_max_open_files($self.files_limit);
+        match $self.secondary_path {
+            // A secondary instance has to keep all files open, so it keeps reading the files deleted by
+            // compactions of the primary until it catches up
+            Some(_) => opts.set_max_open_files(-1),
+            None => opts.set_max_open_files($self.files_limit),
+        }
         opts.create_if_missing($self.create_if_missing);
+        if $self.in_memory {
+            opts.set_env(&rocksdb::Env::mem_env().expect("the in-memory env is expected to be created"));
+        }
         Ok((opts, guard))
     }};
 }
 
+fn open(
+    opts: &rocksdb::Options,
+    db_path: &std::path::Path,
+    secondary_path: Option<&std::path::Path>,
+) -> DBWithThreadMode<MultiThreaded> {
+    match secondary_path {
+        Some(secondary_path) => {
+            <DBWithThreadMode<MultiThreaded>>::open_as_secondary(opts, db_path.to_str().unwrap(), secondary_path.to_str().unwrap())
+                .unwrap()
+        }
+        None => <DBWithThreadMode<MultiThreaded>>::open(opts, db_path.to_str().unwrap()).unwrap(),
+    }
+}
+
 impl ConnBuilder<PathBuf, false, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (opts, guard) = default_opts!(self)?;
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open(&opts, &self.db_path, self.secondary_path.as_deref()), guard));
         Ok(db)
     }
 }
@@ -123,7 +172,7 @@ impl ConnBuilder<PathBuf, true, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (mut opts, guard) = default_opts!(self)?;
         opts.enable_statistics();
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open(&opts, &self.db_path, self.secondary_path.as_deref()), guard));
         Ok(db)
     }
 }
@@ -134,7 +183,21 @@ impl ConnBuilder<PathBuf, true, u32, i32> {
         opts.enable_statistics();
         opts.set_report_bg_io_stats(true);
         opts.set_stats_dump_period_sec(self.stats_period);
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open(&opts, &self.db_path, self.secondary_path.as_deref()), guard));
         Ok(db)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_db() {
+        let db_path = std::env::temp_dir().join(format!("kaspa-in-memory-test-{}", std::process::id()));
+        let db = ConnBuilder::default().with_db_path(db_path.clone()).with_files_limit(10).with_in_memory(true).build().unwrap();
+        db.put(b"key", b"value").unwrap();
+        assert_eq!(db.get(b"key").unwrap().as_deref(), Some(&b"value"[..]));
+        assert!(!db_path.exists(), "an in-memory DB should not touch the filesystem");
+    }
+}
@@ -23,10 +23,27 @@ pub enum StoreError {
 
     #[error("bincode error {0}")]
     DeserializationError(#[from] Box<bincode::ErrorKind>),
+
+    #[error("compression error {0}")]
+    CompressionError(#[from] std::io::Error),
+
+    #[cfg(feature = "fault-injection")]
+    #[error("injected fault {0}")]
+    InjectedFault(&'static str),
 }
 
 pub type StoreResult<T> = std::result::Result<T, StoreError>;
 
+/// Fails with an injected error if the store write fault point fires
+#[inline(always)]
+pub(crate) fn check_write_fault() -> StoreResult<()> {
+    #[cfg(feature = "fault-injection")]
+    if kaspa_utils::fault::STORE_WRITE.should_fire() {
+        return Err(StoreError::InjectedFault(kaspa_utils::fault::STORE_WRITE.name()));
+    }
+    Ok(())
+}
+
 pub trait StoreResultExtensions<T> {
     /// Unwrap or assert that the error is key not fund in which case `None` is returned
     fn unwrap_option(self) -> Option<T>;
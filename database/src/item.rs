@@ -1,6 +1,6 @@
 use crate::{
     db::DB,
-    errors::StoreError,
+    errors::{check_write_fault, StoreError},
     prelude::{DbSetAccess, ReadLock},
 };
 
@@ -46,6 +46,7 @@ impl<T> CachedDbItem<T> {
     where
         T: Clone + Serialize,
     {
+        check_write_fault()?;
         *self.cached_item.write() = Some(item.clone());
         let bin_data = bincode::serialize(item)?;
         writer.put(&self.key, bin_data)?;
@@ -54,6 +55,7 @@ impl<T> CachedDbItem<T> {
 
     pub fn remove(&mut self, mut writer: impl DbWriter) -> Result<(), StoreError>
 where {
+        check_write_fault()?;
         *self.cached_item.write() = None;
         writer.delete(&self.key)?;
         Ok(())
@@ -64,6 +66,7 @@ where {
         T: Clone + Serialize + DeserializeOwned,
         F: Fn(T) -> T,
     {
+        check_write_fault()?;
         let mut guard = self.cached_item.write();
         let mut item = if let Some(item) = guard.take() {
             item
@@ -137,6 +140,7 @@ where
     where
         T: Clone + Serialize,
     {
+        check_write_fault()?;
         let set = self.read_locked_set()?;
         {
             let mut set_write = set.write();
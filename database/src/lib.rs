@@ -1,11 +1,13 @@
 mod access;
 mod cache;
+mod compression;
 mod db;
 mod errors;
 mod item;
 mod key;
 mod writer;
 
+pub mod metrics;
 pub mod registry;
 mod set_access;
 pub mod utils;
@@ -15,6 +17,7 @@ pub mod prelude {
 
     pub use super::access::CachedDbAccess;
     pub use super::cache::{Cache, CachePolicy};
+    pub use super::compression::{CompressionConfig, Compressor};
     pub use super::item::{CachedDbItem, CachedDbSetItem};
     pub use super::key::DbKey;
     pub use super::set_access::{CachedDbSetAccess, DbSetAccess, ReadLock};
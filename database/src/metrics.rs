@@ -0,0 +1,168 @@
+use crate::{cache, compression, db::DB};
+use parking_lot::Mutex;
+use rocksdb::properties;
+use std::{
+    ffi::CStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+};
+
+/// Storage statistics of a column family of a RocksDB instance
+#[derive(Clone, Debug, Default)]
+pub struct ColumnFamilyMetrics {
+    pub name: String,
+    /// Total size of the SST files of all versions
+    pub sst_files_size: u64,
+    /// Estimated size of the live data
+    pub live_data_size: u64,
+    /// Size of the active and unflushed immutable memtables
+    pub mem_tables_size: u64,
+    /// Estimated number of keys
+    pub estimated_keys: u64,
+}
+
+/// Storage and compaction statistics of a RocksDB instance
+#[derive(Clone, Debug, Default)]
+pub struct DatabaseMetrics {
+    pub name: String,
+    pub column_families: Vec<ColumnFamilyMetrics>,
+    pub running_compactions: u64,
+    /// Whether at least one compaction is pending
+    pub compaction_pending: bool,
+    /// Estimated number of bytes compactions need to rewrite to bring all levels below their target size
+    pub pending_compaction_bytes: u64,
+    pub block_cache_usage: u64,
+    pub block_cache_capacity: u64,
+}
+
+/// A snapshot of the storage statistics collected by a [`StorageMetricsProvider`]
+#[derive(Clone, Debug, Default)]
+pub struct StorageMetrics {
+    /// Disk usage of the data directories, in bytes
+    pub data_dir_size: u64,
+    /// Number of reads served by the in-memory caches of the stores since the node started
+    pub cache_hits: u64,
+    /// Number of reads missing the in-memory caches of the stores since the node started
+    pub cache_misses: u64,
+    /// Size of the values written compressed since the node started, before compression
+    pub compression_input_bytes: u64,
+    /// Size of the values written compressed since the node started, after compression
+    pub compression_output_bytes: u64,
+    pub databases: Vec<DatabaseMetrics>,
+}
+
+impl StorageMetrics {
+    /// The ratio of store reads served by the in-memory caches
+    pub fn cache_hit_rate(&self) -> f64 {
+        match self.cache_hits + self.cache_misses {
+            0 => 0.0,
+            reads => self.cache_hits as f64 / reads as f64,
+        }
+    }
+
+    /// The size of the values written compressed relative to their uncompressed size, 1 if none was written
+    pub fn compression_ratio(&self) -> f64 {
+        match self.compression_input_bytes {
+            0 => 1.0,
+            input => self.compression_output_bytes as f64 / input as f64,
+        }
+    }
+}
+
+/// Collects storage statistics of the registered DBs and of the data directories they live in.
+///
+/// DBs are held weakly, so a DB dropped after its registration (like the consensus DB replaced
+/// by a staging consensus) is no longer reported.
+pub struct StorageMetricsProvider {
+    data_dirs: Vec<PathBuf>,
+    dbs: Mutex<Vec<(String, Weak<DB>)>>,
+}
+
+impl StorageMetricsProvider {
+    pub fn new(data_dirs: Vec<PathBuf>) -> Self {
+        Self { data_dirs, dbs: Default::default() }
+    }
+
+    /// Registers `db` to be reported under `name`
+    pub fn register(&self, name: impl Into<String>, db: &Arc<DB>) {
+        let mut dbs = self.dbs.lock();
+        dbs.retain(|(_, db)| db.strong_count() > 0);
+        dbs.push((name.into(), Arc::downgrade(db)));
+    }
+
+    pub fn metrics(&self) -> StorageMetrics {
+        let dbs = self.dbs.lock().iter().filter_map(|(name, db)| db.upgrade().map(|db| (name.clone(), db))).collect::<Vec<_>>();
+        let (cache_hits, cache_misses) = cache::read_counters();
+        let (compression_input_bytes, compression_output_bytes) = compression::read_counters();
+        StorageMetrics {
+            data_dir_size: self.data_dirs.iter().map(|dir| dir_size(dir)).sum(),
+            cache_hits,
+            cache_misses,
+            compression_input_bytes,
+            compression_output_bytes,
+            databases: dbs.iter().map(|(name, db)| database_metrics(name, db)).collect(),
+        }
+    }
+}
+
+fn database_metrics(name: &str, db: &DB) -> DatabaseMetrics {
+    let property = |property: &CStr| db.property_int_value(property).ok().flatten().unwrap_or_default();
+    // Stores are all kept in the default column family, distinguished by key prefixes
+    let default_column_family = ColumnFamilyMetrics {
+        name: rocksdb::DEFAULT_COLUMN_FAMILY_NAME.to_string(),
+        sst_files_size: property(properties::TOTAL_SST_FILES_SIZE),
+        live_data_size: property(properties::ESTIMATE_LIVE_DATA_SIZE),
+        mem_tables_size: property(properties::SIZE_ALL_MEM_TABLES),
+        estimated_keys: property(properties::ESTIMATE_NUM_KEYS),
+    };
+    DatabaseMetrics {
+        name: name.to_string(),
+        column_families: vec![default_column_family],
+        running_compactions: property(properties::NUM_RUNNING_COMPACTIONS),
+        compaction_pending: property(properties::COMPACTION_PENDING) > 0,
+        pending_compaction_bytes: property(properties::ESTIMATE_PENDING_COMPACTION_BYTES),
+        block_cache_usage: property(properties::BLOCK_CACHE_USAGE),
+        block_cache_capacity: property(properties::BLOCK_CACHE_CAPACITY),
+    }
+}
+
+/// Returns the total size of the files under `dir`, ignoring the entries which cannot be read
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_storage_metrics() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        db.put(b"key", vec![0u8; 1024]).unwrap();
+        db.flush().unwrap();
+
+        let provider = StorageMetricsProvider::new(vec![db.path().to_path_buf()]);
+        provider.register("test", &db);
+        let metrics = provider.metrics();
+        assert!(metrics.data_dir_size > 0);
+        assert_eq!(metrics.databases.len(), 1);
+        assert_eq!(metrics.databases[0].name, "test");
+        assert!(metrics.databases[0].column_families[0].sst_files_size > 0);
+
+        // Dropped DBs are no longer reported
+        drop(db);
+        assert!(provider.metrics().databases.is_empty());
+    }
+}
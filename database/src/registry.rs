@@ -36,6 +36,7 @@ pub enum DatabaseStorePrefixes {
     UtxoMultisets = 26,
     VirtualUtxoset = 27,
     VirtualState = 28,
+    CompressionDictionaries = 29,
 
     // ---- Decomposed reachability stores ----
     ReachabilityTreeChildren = 30,
@@ -48,11 +49,19 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    TimedBannedAddresses = 130,
+    BannedNetworks = 131,
+    NodeCounters = 132,
+    ChainQualitySamples = 133,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexEntries = 195,
+    TxIndexAcceptedTransactions = 196,
+    TxIndexSink = 197,
+    BurnedSupply = 198,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -1,4 +1,8 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{
+    cache::CachePolicy,
+    db::DB,
+    errors::{check_write_fault, StoreError},
+};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use parking_lot::{RwLock, RwLockReadGuard};
@@ -130,6 +134,7 @@ where
     }
 
     pub fn write(&self, mut writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError> {
+        check_write_fault()?;
         writer.put(self.get_db_key(&key, &data)?, [])?;
         Ok(())
     }
@@ -140,6 +145,7 @@ where
     }
 
     pub fn delete_bucket(&self, mut writer: impl DbWriter, key: TKey) -> Result<(), StoreError> {
+        check_write_fault()?;
         let db_key = DbKey::new_with_bucket(&self.prefix, &key, []);
         let (from, to) = rocksdb::PrefixRange(db_key.as_ref()).into_bounds();
         writer.delete_range(from.unwrap(), to.unwrap())?;
@@ -147,6 +153,7 @@ where
     }
 
     pub fn delete(&self, mut writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError> {
+        check_write_fault()?;
         writer.delete(self.get_db_key(&key, &data)?)?;
         Ok(())
     }
@@ -61,6 +61,16 @@ macro_rules! create_temp_db {
     }};
 }
 
+/// Creates a DB kept entirely in memory, which never touches the filesystem.
+/// Callers must keep the `DbLifetime` guard for as long as they wish the DB instance to exist.
+#[macro_export]
+macro_rules! create_ephemeral_db {
+    ($conn_builder: expr) => {{
+        let db = $conn_builder.with_db_path(std::path::PathBuf::from("kaspa-ephemeral")).with_in_memory(true).build().unwrap();
+        ($crate::utils::DbLifetime::without_destroy(std::sync::Arc::downgrade(&db)), db)
+    }};
+}
+
 /// Creates a DB within the provided directory path.
 /// Callers must keep the `TempDbLifetime` guard for as long as they wish the DB instance to exist.
 #[macro_export]
@@ -0,0 +1,141 @@
+//! Progress tracking and throttling of the full rebuilds of an index
+
+use kaspa_core::time::unix_now;
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Scale of the completion ratio stored as an integer
+const COMPLETION_SCALE: u64 = 1_000_000;
+
+/// Progress of an index resync, updated by the index and observable without locking it
+#[derive(Debug, Default)]
+pub struct ResyncProgress {
+    running: AtomicBool,
+    /// Unix time in milliseconds at which the running or last resync started
+    started_at: AtomicU64,
+    /// Number of items (utxos, chain blocks) committed so far
+    processed: AtomicU64,
+    /// Estimated completion ratio scaled by [`COMPLETION_SCALE`]
+    completion: AtomicU64,
+}
+
+impl ResyncProgress {
+    pub fn start(&self) {
+        self.processed.store(0, Ordering::Relaxed);
+        self.completion.store(0, Ordering::Relaxed);
+        self.started_at.store(unix_now(), Ordering::Relaxed);
+        self.running.store(true, Ordering::Release);
+    }
+
+    /// Records `count` more committed items and the estimated completion `ratio`, in the `[0, 1]` range
+    pub fn advance(&self, count: u64, ratio: f64) {
+        self.processed.fetch_add(count, Ordering::Relaxed);
+        self.completion.store((ratio.clamp(0.0, 1.0) * COMPLETION_SCALE as f64) as u64, Ordering::Relaxed);
+    }
+
+    pub fn finish(&self) {
+        self.running.store(false, Ordering::Release);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+
+    /// Returns the progress of the running resync, if any
+    pub fn snapshot(&self) -> Option<ResyncProgressSnapshot> {
+        self.is_running().then(|| ResyncProgressSnapshot {
+            started_at: self.started_at.load(Ordering::Relaxed),
+            processed: self.processed.load(Ordering::Relaxed),
+            completion: self.completion.load(Ordering::Relaxed) as f64 / COMPLETION_SCALE as f64,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResyncProgressSnapshot {
+    /// Unix time in milliseconds at which the resync started
+    pub started_at: u64,
+    /// Number of items committed so far
+    pub processed: u64,
+    /// Estimated completion ratio, in the `[0, 1]` range
+    pub completion: f64,
+}
+
+impl ResyncProgressSnapshot {
+    pub fn percent(&self) -> f64 {
+        self.completion * 100.0
+    }
+
+    /// Estimates the remaining duration of the resync at unix time `now` in milliseconds by extrapolating its
+    /// rate so far, `None` until some progress is made
+    pub fn eta(&self, now: u64) -> Option<Duration> {
+        if self.completion <= 0.0 {
+            return None;
+        }
+        let elapsed = now.saturating_sub(self.started_at) as f64;
+        Some(Duration::from_millis((elapsed * (1.0 - self.completion) / self.completion) as u64))
+    }
+}
+
+/// Bounds the rate at which a resync commits items so the database I/O remains available to the RPC
+/// and to consensus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResyncThrottle {
+    /// Maximum number of items committed per second, unbounded if `None`
+    max_rate: Option<u64>,
+}
+
+impl ResyncThrottle {
+    /// Creates a throttle committing at most `max_rate` items per second, 0 meaning unbounded
+    pub fn new(max_rate: u64) -> Self {
+        Self { max_rate: (max_rate > 0).then_some(max_rate) }
+    }
+
+    /// Returns the pause keeping the rate of `processed` items committed in `elapsed` below the maximum, if any
+    pub fn delay(&self, processed: u64, elapsed: Duration) -> Option<Duration> {
+        let target = Duration::from_secs_f64(processed as f64 / self.max_rate? as f64);
+        target.checked_sub(elapsed).filter(|delay| !delay.is_zero())
+    }
+
+    /// Blocks the current thread until the rate of `processed` items committed since `start` gets below the maximum
+    pub fn wait(&self, processed: u64, start: Instant) {
+        if let Some(delay) = self.delay(processed, start.elapsed()) {
+            thread::sleep(delay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resync_progress() {
+        let progress = ResyncProgress::default();
+        assert_eq!(progress.snapshot(), None);
+
+        progress.start();
+        progress.advance(100, 0.25);
+        progress.advance(100, 0.5);
+        let snapshot = progress.snapshot().unwrap();
+        assert_eq!(snapshot.processed, 200);
+        assert_eq!(snapshot.percent(), 50.0);
+        assert_eq!(snapshot.eta(snapshot.started_at + 10_000), Some(Duration::from_secs(10)));
+
+        progress.finish();
+        assert_eq!(progress.snapshot(), None);
+    }
+
+    #[test]
+    fn test_resync_throttle() {
+        assert_eq!(ResyncThrottle::default().delay(1_000_000, Duration::ZERO), None);
+
+        let throttle = ResyncThrottle::new(1000);
+        assert_eq!(throttle.delay(500, Duration::from_millis(200)), Some(Duration::from_millis(300)));
+        assert_eq!(throttle.delay(500, Duration::from_millis(500)), None);
+        assert_eq!(throttle.delay(500, Duration::from_secs(1)), None);
+    }
+}
@@ -1,4 +1,5 @@
 use kaspa_notify::events::EventType;
+use kaspa_txindex::errors::TxIndexError;
 use kaspa_utxoindex::errors::UtxoIndexError;
 use thiserror::Error;
 
@@ -7,6 +8,9 @@ pub enum IndexError {
     #[error("{0}")]
     UtxoIndexError(#[from] UtxoIndexError),
 
+    #[error("{0}")]
+    TxIndexError(#[from] TxIndexError),
+
     #[error("event type {0:?} is not supported")]
     NotSupported(EventType),
 }
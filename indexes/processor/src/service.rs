@@ -11,9 +11,10 @@ use kaspa_notify::{
     connection::ChannelType,
     events::{EventSwitches, EventType},
     listener::ListenerLifespan,
-    scope::{PruningPointUtxoSetOverrideScope, UtxosChangedScope},
+    scope::{PruningPointUtxoSetOverrideScope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
 use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::sync::Arc;
@@ -22,6 +23,7 @@ const INDEX_SERVICE: &str = IDENT;
 
 pub struct IndexService {
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
     notifier: Arc<IndexNotifier>,
     shutdown: SingleTrigger,
 }
@@ -31,6 +33,7 @@ impl IndexService {
         consensus_notifier: &Arc<ConsensusNotifier>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to consensus notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard);
@@ -45,18 +48,26 @@ impl IndexService {
         // Prepare the index-processor notifier
         // No subscriber is defined here because the subscription are manually created during the construction and never changed after that.
         let events: EventSwitches = [EventType::UtxosChanged, EventType::PruningPointUtxoSetOverride].as_ref().into();
-        let collector = Arc::new(Processor::new(utxoindex.clone(), consensus_notify_channel.receiver()));
+        let collector = Arc::new(Processor::new(utxoindex.clone(), txindex.clone(), consensus_notify_channel.receiver()));
         let notifier = Arc::new(IndexNotifier::new(INDEX_SERVICE, events, vec![collector], vec![], subscription_context, 1, policies));
 
         // Manually subscribe to index-processor related event types
-        consensus_notifier
-            .try_start_notify(consensus_notify_listener_id, UtxosChangedScope::default().into())
-            .expect("the subscription always succeeds");
-        consensus_notifier
-            .try_start_notify(consensus_notify_listener_id, PruningPointUtxoSetOverrideScope::default().into())
-            .expect("the subscription always succeeds");
-
-        Self { utxoindex, notifier, shutdown: SingleTrigger::default() }
+        if utxoindex.is_some() {
+            consensus_notifier
+                .try_start_notify(consensus_notify_listener_id, UtxosChangedScope::default().into())
+                .expect("the subscription always succeeds");
+            consensus_notifier
+                .try_start_notify(consensus_notify_listener_id, PruningPointUtxoSetOverrideScope::default().into())
+                .expect("the subscription always succeeds");
+        }
+        if txindex.is_some() {
+            // The txindex needs the acceptance data of added chain blocks
+            consensus_notifier
+                .try_start_notify(consensus_notify_listener_id, VirtualChainChangedScope::new(true).into())
+                .expect("the subscription always succeeds");
+        }
+
+        Self { utxoindex, txindex, notifier, shutdown: SingleTrigger::default() }
     }
 
     pub fn notifier(&self) -> Arc<IndexNotifier> {
@@ -66,6 +77,10 @@ impl IndexService {
     pub fn utxoindex(&self) -> Option<UtxoIndexProxy> {
         self.utxoindex.clone()
     }
+
+    pub fn txindex(&self) -> Option<TxIndexProxy> {
+        self.txindex.clone()
+    }
 }
 
 impl AsyncService for IndexService {
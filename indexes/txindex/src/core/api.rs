@@ -0,0 +1,87 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use kaspa_index_core::resync::{ResyncProgress, ResyncProgressSnapshot};
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{errors::TxIndexResult, model::TxIndexEntry};
+
+///Txindex API targeted at retrieval calls.
+pub trait TxIndexApi: Send + Sync + Debug {
+    /// Retrieve the location of a transaction accepted by the selected chain from the txindex db.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+
+    /// Retrieve the stored sink of the txindex.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_txindex_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the txindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> TxIndexResult<bool>;
+
+    /// Update the txindex with the given virtual chain changes.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()>;
+
+    /// Resync the txindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> TxIndexResult<()>;
+
+    /// Returns the progress of the resyncs, which can be observed without locking the txindex
+    fn resync_progress(&self) -> Arc<ResyncProgress>;
+}
+
+/// Async proxy for the transaction index
+#[derive(Debug, Clone)]
+pub struct TxIndexProxy {
+    inner: Arc<RwLock<dyn TxIndexApi>>,
+    resync_progress: Arc<ResyncProgress>,
+}
+
+impl TxIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn TxIndexApi>>) -> Self {
+        let resync_progress = inner.read().resync_progress();
+        Self { inner, resync_progress }
+    }
+
+    /// Returns the progress of the running resync, if any
+    pub fn resync_progress(&self) -> Option<ResyncProgressSnapshot> {
+        self.resync_progress.snapshot()
+    }
+
+    pub async fn get_transaction_entry(self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        spawn_blocking(move || self.inner.read().get_transaction_entry(transaction_id)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn resync(self) -> TxIndexResult<()> {
+        spawn_blocking(move || self.inner.write().resync()).await.unwrap()
+    }
+}
@@ -0,0 +1,25 @@
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// Location in the DAG of a transaction accepted by the selected chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    /// The block containing the transaction
+    pub containing_block_hash: Hash,
+    /// The chain block accepting the transaction, or `None` if the chain block which accepted it was
+    /// since removed from the selected chain by a reorg
+    pub accepting_block_hash: Option<Hash>,
+}
+
+impl TxIndexEntry {
+    pub fn new(containing_block_hash: Hash, accepting_block_hash: Option<Hash>) -> Self {
+        Self { containing_block_hash, accepting_block_hash }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.accepting_block_hash.is_some()
+    }
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
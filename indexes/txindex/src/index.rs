@@ -0,0 +1,222 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::TxIndexEntry,
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use kaspa_index_core::resync::{ResyncProgress, ResyncThrottle};
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
+
+/// Number of chain blocks whose acceptance data is fetched from consensus and committed at once while resyncing
+const RESYNC_CHUNK_SIZE: usize = 1024;
+
+/// Minimum interval between two logs of the resync progress
+const RESYNC_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// TxIndex indexes the transactions accepted by the selected chain by [`TransactionId`], recording the block
+/// containing each transaction and the chain block accepting it.
+///
+/// The index follows the selected chain from the pruning point at the time it was first synced, so transactions
+/// accepted below that point are not indexed.
+///
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+    resync_progress: Arc<ResyncProgress>,
+    resync_throttle: ResyncThrottle,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        Self::with_resync_throttle(consensus_manager, db, ResyncThrottle::default())
+    }
+
+    /// Creates a new [`TxIndex`] within a [`RwLock`], bounding the rate at which its resyncs commit chain blocks
+    pub fn with_resync_throttle(
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        resync_throttle: ResyncThrottle,
+    ) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Store::new(db),
+            resync_progress: Default::default(),
+            resync_throttle,
+        };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    /// Retrieve the location of a transaction from the txindex db.
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving transaction {1}", IDENT, transaction_id);
+
+        self.store.get_entry(transaction_id)
+    }
+
+    /// Retrieve the stored sink of the txindex.
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means txindex sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(TxIndexError::StoreAccessError(err)),
+        }
+    }
+
+    /// Updates the [TxIndex] via the virtual chain changes supplied, un-accepting the transactions
+    /// of removed chain blocks and indexing the ones accepted by added chain blocks.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        let sink = match added_chain_block_hashes.last() {
+            Some(sink) => *sink,
+            None => self.store.get_sink()?,
+        };
+        self.store.apply_chain_changes(
+            &removed_chain_block_hashes,
+            &added_chain_block_hashes,
+            &added_chain_blocks_acceptance_data,
+            sink,
+        )?;
+        Ok(())
+    }
+
+    /// Syncs the txindex with the selected chain of the consensus database.
+    ///
+    /// The txindex catches up from its stored sink if it is still known by consensus, otherwise it is
+    /// deleted and reinstated from the pruning point.
+    ///
+    /// **Note:** resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        self.resync_progress.start();
+        let result = self.catch_up();
+        self.resync_progress.finish();
+        result
+    }
+
+    fn resync_progress(&self) -> Arc<ResyncProgress> {
+        self.resync_progress.clone()
+    }
+}
+
+impl TxIndex {
+    /// Catches the txindex up with the selected chain chunk by chunk, rebuilding it from the pruning point if needed
+    fn catch_up(&mut self) -> TxIndexResult<()> {
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let chain_path = match self.store.get_sink() {
+            Ok(txindex_sink) => session.get_virtual_chain_from_block(txindex_sink).ok(),
+            Err(StoreError::KeyNotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let chain_path = match chain_path {
+            Some(chain_path) => chain_path,
+            None => {
+                self.store.delete_all()?;
+                session.get_virtual_chain_from_block(session.pruning_point())?
+            }
+        };
+
+        let mut removed = chain_path.removed.as_slice();
+        if chain_path.added.is_empty() {
+            self.store.apply_chain_changes(removed, &[], &[], session.get_sink())?;
+        }
+        let start = Instant::now();
+        let mut last_log = start;
+        let mut processed = 0u64;
+        let total = chain_path.added.len() as u64;
+        // Every chunk commits its last chain block as sink, so an interrupted resync resumes from there
+        for added in chain_path.added.chunks(RESYNC_CHUNK_SIZE) {
+            trace!("[{0}] resyncing with a chunk of {1} chain blocks from consensus db", IDENT, added.len());
+            let acceptance_data = session.get_blocks_acceptance_data(added)?;
+            self.store.apply_chain_changes(removed, added, &acceptance_data, *added.last().unwrap())?;
+            removed = &[];
+
+            processed += added.len() as u64;
+            self.resync_progress.advance(added.len() as u64, processed as f64 / total as f64);
+            if last_log.elapsed() >= RESYNC_LOG_INTERVAL {
+                info!("Resyncing the txindex: {:.1}% ({}/{} chain blocks)", processed as f64 * 100.0 / total as f64, processed, total);
+                last_log = Instant::now();
+            }
+            self.resync_throttle.wait(processed, start);
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
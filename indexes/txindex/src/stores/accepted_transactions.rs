@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+/// Reader API for `TxIndexAcceptedTransactionsStore`.
+pub trait TxIndexAcceptedTransactionsStoreReader {
+    /// Returns the ids of the transactions accepted by the chain block `hash`, if the block was indexed
+    fn get(&self, hash: Hash) -> StoreResult<Option<Arc<Vec<TransactionId>>>>;
+}
+
+pub trait TxIndexAcceptedTransactionsStore: TxIndexAcceptedTransactionsStoreReader {
+    fn insert_batch(&mut self, batch: &mut WriteBatch, hash: Hash, transaction_ids: Arc<Vec<TransactionId>>) -> StoreResult<()>;
+    fn delete_batch(&mut self, batch: &mut WriteBatch, hash: Hash) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAcceptedTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAcceptedTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<TransactionId>>>,
+}
+
+impl DbTxIndexAcceptedTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptedTransactions.into()),
+        }
+    }
+}
+
+impl TxIndexAcceptedTransactionsStoreReader for DbTxIndexAcceptedTransactionsStore {
+    fn get(&self, hash: Hash) -> StoreResult<Option<Arc<Vec<TransactionId>>>> {
+        match self.access.read(hash) {
+            Ok(transaction_ids) => Ok(Some(transaction_ids)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexAcceptedTransactionsStore for DbTxIndexAcceptedTransactionsStore {
+    fn insert_batch(&mut self, batch: &mut WriteBatch, hash: Hash, transaction_ids: Arc<Vec<TransactionId>>) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), hash, transaction_ids)
+    }
+
+    fn delete_batch(&mut self, batch: &mut WriteBatch, hash: Hash) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), hash)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
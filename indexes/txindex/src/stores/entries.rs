@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+
+use crate::model::TxIndexEntry;
+
+/// Reader API for `TxIndexEntriesStore`.
+pub trait TxIndexEntriesStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+}
+
+pub trait TxIndexEntriesStore: TxIndexEntriesStoreReader {
+    fn insert_batch(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, entry: TxIndexEntry) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexEntriesStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexEntriesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry>,
+}
+
+impl DbTxIndexEntriesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexEntries.into()) }
+    }
+}
+
+impl TxIndexEntriesStoreReader for DbTxIndexEntriesStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        match self.access.read(transaction_id) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexEntriesStore for DbTxIndexEntriesStore {
+    fn insert_batch(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, entry: TxIndexEntry) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), transaction_id, entry)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,4 @@
+mod accepted_transactions;
+mod entries;
+mod sink;
+pub mod store_manager;
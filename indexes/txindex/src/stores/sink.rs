@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachedDbItem, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+/// Reader API for `TxIndexSinkStore`.
+pub trait TxIndexSinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait TxIndexSinkStore: TxIndexSinkStoreReader {
+    fn set_batch(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexSinkStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexSinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbTxIndexSinkStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::TxIndexSink.into()) }
+    }
+}
+
+impl TxIndexSinkStoreReader for DbTxIndexSinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl TxIndexSinkStore for DbTxIndexSinkStore {
+    fn set_batch(&mut self, batch: &mut WriteBatch, sink: Hash) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), &sink)
+    }
+
+    fn remove(&mut self) -> StoreResult<()> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_core::trace;
+use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::TxIndexEntry,
+    stores::{
+        accepted_transactions::{
+            DbTxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStoreReader,
+        },
+        entries::{DbTxIndexEntriesStore, TxIndexEntriesStore, TxIndexEntriesStoreReader},
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+    },
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    entries_store: DbTxIndexEntriesStore,
+    accepted_transactions_store: DbTxIndexAcceptedTransactionsStore,
+    sink_store: DbTxIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            entries_store: DbTxIndexEntriesStore::new(db.clone(), CachePolicy::Empty),
+            accepted_transactions_store: DbTxIndexAcceptedTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            sink_store: DbTxIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        self.entries_store.get(transaction_id)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically un-accepts the transactions accepted by the `removed` chain blocks, indexes the transactions
+    /// accepted by the `added` chain blocks according to their acceptance data and sets the new index sink
+    pub fn apply_chain_changes(
+        &mut self,
+        removed: &[Hash],
+        added: &[Hash],
+        added_acceptance_data: &[Arc<AcceptanceData>],
+        sink: Hash,
+    ) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+
+        for &block in removed {
+            // Blocks accepted before the index was synced have no record
+            let Some(transaction_ids) = self.accepted_transactions_store.get(block)? else {
+                continue;
+            };
+            for &transaction_id in transaction_ids.iter() {
+                if let Some(entry) = self.entries_store.get(transaction_id)?.filter(|x| x.accepting_block_hash == Some(block)) {
+                    self.entries_store.insert_batch(
+                        &mut batch,
+                        transaction_id,
+                        TxIndexEntry::new(entry.containing_block_hash, None),
+                    )?;
+                }
+            }
+            self.accepted_transactions_store.delete_batch(&mut batch, block)?;
+        }
+
+        for (&block, acceptance_data) in added.iter().zip(added_acceptance_data.iter()) {
+            let mut transaction_ids = Vec::new();
+            for mergeset_block_data in acceptance_data.iter() {
+                for accepted in mergeset_block_data.accepted_transactions.iter() {
+                    let entry = TxIndexEntry::new(mergeset_block_data.block_hash, Some(block));
+                    self.entries_store.insert_batch(&mut batch, accepted.transaction_id, entry)?;
+                    transaction_ids.push(accepted.transaction_id);
+                }
+            }
+            self.accepted_transactions_store.insert_batch(&mut batch, block, Arc::new(transaction_ids))?;
+        }
+
+        self.sink_store.set_batch(&mut batch, sink)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Resets the txindex database
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        // TODO: explore possibility of deleting and replacing whole db, currently there is an issue because of file lock and db being in an arc.
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        self.sink_store.remove()?;
+        self.accepted_transactions_store.delete_all()?;
+        self.entries_store.delete_all()?;
+
+        trace!("[{0}] cleared txindex database", IDENT);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::acceptance_data::{AcceptedTxEntry, MergesetBlockAcceptanceData};
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn acceptance_data(mergeset: &[(u64, &[u64])]) -> Arc<AcceptanceData> {
+        Arc::new(
+            mergeset
+                .iter()
+                .map(|(block, transactions)| MergesetBlockAcceptanceData {
+                    block_hash: (*block).into(),
+                    accepted_transactions: transactions
+                        .iter()
+                        .enumerate()
+                        .map(|(i, id)| AcceptedTxEntry { transaction_id: (*id).into(), index_within_block: i as u32 })
+                        .collect(),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_chain_changes() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+
+        // Chain blocks 1 and 2, block 2 merging block 3
+        store
+            .apply_chain_changes(
+                &[],
+                &[1.into(), 2.into()],
+                &[acceptance_data(&[(1, &[11])]), acceptance_data(&[(2, &[21]), (3, &[31, 32])])],
+                2.into(),
+            )
+            .unwrap();
+        assert_eq!(store.get_sink().unwrap(), 2.into());
+        assert_eq!(store.get_entry(11.into()).unwrap(), Some(TxIndexEntry::new(1.into(), Some(1.into()))));
+        assert_eq!(store.get_entry(32.into()).unwrap(), Some(TxIndexEntry::new(3.into(), Some(2.into()))));
+        assert_eq!(store.get_entry(41.into()).unwrap(), None);
+
+        // A reorg replaces block 2 with block 4, which merges block 3 but not block 2
+        store.apply_chain_changes(&[2.into()], &[4.into()], &[acceptance_data(&[(4, &[41]), (3, &[31])])], 4.into()).unwrap();
+        assert_eq!(store.get_sink().unwrap(), 4.into());
+        assert_eq!(store.get_entry(11.into()).unwrap(), Some(TxIndexEntry::new(1.into(), Some(1.into()))));
+        assert_eq!(store.get_entry(21.into()).unwrap(), Some(TxIndexEntry::new(2.into(), None)));
+        assert_eq!(store.get_entry(31.into()).unwrap(), Some(TxIndexEntry::new(3.into(), Some(4.into()))));
+        assert_eq!(store.get_entry(32.into()).unwrap(), Some(TxIndexEntry::new(3.into(), None)));
+        assert_eq!(store.get_entry(41.into()).unwrap(), Some(TxIndexEntry::new(4.into(), Some(4.into()))));
+
+        store.delete_all().unwrap();
+        assert!(store.get_sink().is_err());
+        assert_eq!(store.get_entry(11.into()).unwrap(), None);
+    }
+}
@@ -1,18 +1,21 @@
 use kaspa_consensus_core::{
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     utxo::utxo_diff::UtxoDiff,
     BlockHashSet,
 };
 use kaspa_consensusmanager::spawn_blocking;
 use kaspa_database::prelude::StoreResult;
 use kaspa_hashes::Hash;
-use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
+use kaspa_index_core::{
+    indexed_utxos::BalanceByScriptPublicKey,
+    resync::{ResyncProgress, ResyncProgressSnapshot},
+};
 use parking_lot::RwLock;
 use std::{collections::HashSet, fmt::Debug, sync::Arc};
 
 use crate::{
     errors::UtxoIndexResult,
-    model::{UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{UtxoChanges, UtxoSetByScriptPublicKey, UtxoSetByScriptPublicKeyPage},
 };
 
 ///Utxoindex API targeted at retrieval calls.
@@ -22,11 +25,27 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
     /// Note: Use a read lock when accessing this method
     fn get_circulating_supply(&self) -> StoreResult<u64>;
 
+    /// Retrieve the part of the circulating supply held by provably unspendable utxos from the utxoindex db.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_burned_supply(&self) -> StoreResult<u64>;
+
     /// Retrieve utxos by script public keys supply from the utxoindex db.
     ///
     /// Note: Use a read lock when accessing this method
     fn get_utxos_by_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<UtxoSetByScriptPublicKey>;
 
+    /// Retrieve a page of at most `limit` utxos of the ordered script public keys, starting right after
+    /// the outpoint `start_after` of the first script public key if provided.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_utxos_page_by_script_public_keys(
+        &self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage>;
+
     fn get_balance_by_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey>;
 
     // This can have a big memory footprint, so it should be used only for tests.
@@ -53,27 +72,52 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
     ///
     /// Note: Use a write lock when accessing this method
     fn resync(&mut self) -> UtxoIndexResult<()>;
+
+    /// Returns the progress of the resyncs, which can be observed without locking the utxoindex
+    fn resync_progress(&self) -> Arc<ResyncProgress>;
 }
 
 /// Async proxy for the UTXO index
 #[derive(Debug, Clone)]
 pub struct UtxoIndexProxy {
     inner: Arc<RwLock<dyn UtxoIndexApi>>,
+    resync_progress: Arc<ResyncProgress>,
 }
 
 impl UtxoIndexProxy {
     pub fn new(inner: Arc<RwLock<dyn UtxoIndexApi>>) -> Self {
-        Self { inner }
+        let resync_progress = inner.read().resync_progress();
+        Self { inner, resync_progress }
+    }
+
+    /// Returns the progress of the running resync, if any
+    pub fn resync_progress(&self) -> Option<ResyncProgressSnapshot> {
+        self.resync_progress.snapshot()
     }
 
     pub async fn get_circulating_supply(self) -> StoreResult<u64> {
         spawn_blocking(move || self.inner.read().get_circulating_supply()).await.unwrap()
     }
 
+    pub async fn get_burned_supply(self) -> StoreResult<u64> {
+        spawn_blocking(move || self.inner.read().get_burned_supply()).await.unwrap()
+    }
+
     pub async fn get_utxos_by_script_public_keys(self, script_public_keys: ScriptPublicKeys) -> StoreResult<UtxoSetByScriptPublicKey> {
         spawn_blocking(move || self.inner.read().get_utxos_by_script_public_keys(script_public_keys)).await.unwrap()
     }
 
+    pub async fn get_utxos_page_by_script_public_keys(
+        self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage> {
+        spawn_blocking(move || self.inner.read().get_utxos_page_by_script_public_keys(script_public_keys, start_after, limit))
+            .await
+            .unwrap()
+    }
+
     pub async fn get_balance_by_script_public_keys(
         self,
         script_public_keys: ScriptPublicKeys,
@@ -81,7 +125,15 @@ impl UtxoIndexProxy {
         spawn_blocking(move || self.inner.read().get_balance_by_script_public_keys(script_public_keys)).await.unwrap()
     }
 
+    pub async fn get_utxo_index_tips(self) -> StoreResult<Arc<BlockHashSet>> {
+        spawn_blocking(move || self.inner.read().get_utxo_index_tips()).await.unwrap()
+    }
+
     pub async fn update(self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         spawn_blocking(move || self.inner.write().update(utxo_diff, tips)).await.unwrap()
     }
+
+    pub async fn resync(self) -> UtxoIndexResult<()> {
+        spawn_blocking(move || self.inner.write().resync()).await.unwrap()
+    }
 }
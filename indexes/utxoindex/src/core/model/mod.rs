@@ -1,3 +1,4 @@
+mod page;
 mod supply;
 
-pub use {kaspa_index_core::indexed_utxos::*, supply::*};
+pub use {kaspa_index_core::indexed_utxos::*, page::*, supply::*};
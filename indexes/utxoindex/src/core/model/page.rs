@@ -0,0 +1,11 @@
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionOutpoint};
+use kaspa_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
+
+/// A page of the UTXO set of an ordered list of script public keys
+#[derive(Debug, Default, Clone)]
+pub struct UtxoSetByScriptPublicKeyPage {
+    pub entries: UtxoSetByScriptPublicKey,
+
+    /// Last entry of the page if the page is full, the next page starting right after it
+    pub last: Option<(ScriptPublicKey, TransactionOutpoint)>,
+}
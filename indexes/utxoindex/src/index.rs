@@ -1,39 +1,67 @@
 use crate::{
     api::UtxoIndexApi,
     errors::{UtxoIndexError, UtxoIndexResult},
-    model::{CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey, UtxoSetByScriptPublicKeyPage},
     stores::store_manager::Store,
     update_container::UtxoIndexChanges,
     IDENT,
 };
-use kaspa_consensus_core::{tx::ScriptPublicKeys, utxo::utxo_diff::UtxoDiff, BlockHashSet};
+use kaspa_consensus_core::{
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet,
+};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
 use kaspa_core::{info, trace};
-use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_database::prelude::{StoreError, StoreResult, StoreResultExtensions, DB};
 use kaspa_hashes::Hash;
-use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
+use kaspa_index_core::{
+    indexed_utxos::BalanceByScriptPublicKey,
+    resync::{ResyncProgress, ResyncThrottle},
+};
 use kaspa_utils::arc::ArcExtensions;
 use parking_lot::RwLock;
 use std::{
     fmt::Debug,
     sync::{Arc, Weak},
+    time::{Duration, Instant},
 };
 
 const RESYNC_CHUNK_SIZE: usize = 2048; //Increased from 1k (used in go-kaspad), for quicker resets, while still having a low memory footprint.
 
+/// Minimum interval between two logs of the resync progress
+const RESYNC_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
 /// UtxoIndex indexes [`CompactUtxoEntryCollections`] by [`ScriptPublicKey`], commits them to its owns store, and emits changes.
 /// Note: The UtxoIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
 /// please follow guidelines found in the comments under `utxoindex::core::api::UtxoIndexApi` for proper thread safety.
 pub struct UtxoIndex {
     consensus_manager: Arc<ConsensusManager>,
     store: Store,
+    resync_progress: Arc<ResyncProgress>,
+    resync_throttle: ResyncThrottle,
 }
 
 impl UtxoIndex {
     /// Creates a new [`UtxoIndex`] within a [`RwLock`]
     pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> UtxoIndexResult<Arc<RwLock<Self>>> {
-        let mut utxoindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
-        if !utxoindex.is_synced()? {
+        Self::with_resync_throttle(consensus_manager, db, ResyncThrottle::default())
+    }
+
+    /// Creates a new [`UtxoIndex`] within a [`RwLock`], bounding the rate at which its resyncs commit utxos
+    pub fn with_resync_throttle(
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        resync_throttle: ResyncThrottle,
+    ) -> UtxoIndexResult<Arc<RwLock<Self>>> {
+        let mut utxoindex = Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Store::new(db),
+            resync_progress: Default::default(),
+            resync_throttle,
+        };
+        // A database written before the burned supply was tracked lacks it and must be rebuilt
+        if !utxoindex.is_synced()? || utxoindex.store.get_burned_supply().unwrap_option().is_none() {
             utxoindex.resync()?;
         }
         let utxoindex = Arc::new(RwLock::new(utxoindex));
@@ -50,6 +78,13 @@ impl UtxoIndexApi for UtxoIndex {
         self.store.get_circulating_supply()
     }
 
+    /// Retrieve burned supply from the utxoindex db.
+    fn get_burned_supply(&self) -> StoreResult<u64> {
+        trace!("[{0}] retrieving burned supply", IDENT);
+
+        self.store.get_burned_supply()
+    }
+
     /// Retrieve utxos by script public keys from the utxoindex db.
     fn get_utxos_by_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<UtxoSetByScriptPublicKey> {
         trace!("[{0}] retrieving utxos from {1} script public keys", IDENT, script_public_keys.len());
@@ -57,6 +92,18 @@ impl UtxoIndexApi for UtxoIndex {
         self.store.get_utxos_by_script_public_key(script_public_keys)
     }
 
+    /// Retrieve a page of utxos by script public keys from the utxoindex db.
+    fn get_utxos_page_by_script_public_keys(
+        &self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage> {
+        trace!("[{0}] retrieving a page of at most {1} utxos from {2} script public keys", IDENT, limit, script_public_keys.len());
+
+        self.store.get_utxos_page_by_script_public_key(script_public_keys, start_after, limit)
+    }
+
     /// Retrieve utxos by script public keys from the utxoindex db.
     fn get_balance_by_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey> {
         trace!("[{0}] retrieving utxos from {1} script public keys", IDENT, script_public_keys.len());
@@ -72,7 +119,7 @@ impl UtxoIndexApi for UtxoIndex {
     }
 
     /// Updates the [UtxoIndex] via the virtual state supplied:
-    /// 1) Saves updated utxo differences, virtual parent hashes and circulating supply to the database.
+    /// 1) Saves updated utxo differences, virtual parent hashes and circulating and burned supplies to the database.
     /// 2) returns an event about utxoindex changes.
     fn update(&mut self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         trace!("[{0}] updating...", IDENT);
@@ -94,6 +141,9 @@ impl UtxoIndexApi for UtxoIndex {
                 self.store.update_circulating_supply(utxoindex_changes.supply_change as CirculatingSupply, false)?;
         }
 
+        // Commit burned supply change to db.
+        self.store.update_burned_supply(utxoindex_changes.burned_supply_change, false)?;
+
         // Commit new consensus virtual tips.
         self.store.set_tips(utxoindex_changes.tips, false)?; //we expect new tips with every virtual!
 
@@ -136,12 +186,36 @@ impl UtxoIndexApi for UtxoIndex {
     fn resync(&mut self) -> UtxoIndexResult<()> {
         info!("Resyncing the utxoindex...");
 
+        self.resync_progress.start();
+        let result = self.rebuild();
+        self.resync_progress.finish();
+        result
+    }
+
+    fn resync_progress(&self) -> Arc<ResyncProgress> {
+        self.resync_progress.clone()
+    }
+
+    // This can have a big memory footprint, so it should be used only for tests.
+    fn get_all_outpoints(&self) -> StoreResult<std::collections::HashSet<kaspa_consensus_core::tx::TransactionOutpoint>> {
+        self.store.get_all_outpoints()
+    }
+}
+
+impl UtxoIndex {
+    /// Deletes the utxoindex database and rebuilds it chunk by chunk from the consensus virtual utxo set
+    fn rebuild(&mut self) -> UtxoIndexResult<()> {
         self.store.delete_all()?;
         let consensus = self.consensus_manager.consensus();
         let session = futures::executor::block_on(consensus.session_blocking());
 
         let consensus_tips = session.get_virtual_parents();
         let mut circulating_supply: CirculatingSupply = 0;
+        let mut burned_supply: CirculatingSupply = 0;
+
+        let start = Instant::now();
+        let mut last_log = start;
+        let mut processed = 0u64;
 
         //Initial batch is without specified seek and none-skipping.
         let mut virtual_utxo_batch = session.get_virtual_utxos(None, RESYNC_CHUNK_SIZE, false);
@@ -158,9 +232,19 @@ impl UtxoIndexApi for UtxoIndex {
             utxoindex_changes.add_utxos_from_vector(virtual_utxo_batch);
 
             circulating_supply += utxoindex_changes.supply_change as CirculatingSupply;
+            burned_supply += utxoindex_changes.burned_supply_change as CirculatingSupply;
 
             self.store.update_utxo_state(&utxoindex_changes.utxo_changes.added, &utxoindex_changes.utxo_changes.removed, true)?;
 
+            processed += current_chunk_size as u64;
+            self.resync_progress.advance(current_chunk_size as u64, key_space_ratio(next_outpoint_from.as_ref().unwrap()));
+            if last_log.elapsed() >= RESYNC_LOG_INTERVAL {
+                let snapshot = self.resync_progress.snapshot().unwrap();
+                info!("Resyncing the utxoindex: {:.1}% ({} utxos)", snapshot.percent(), processed);
+                last_log = Instant::now();
+            }
+            self.resync_throttle.wait(processed, start);
+
             if current_chunk_size < RESYNC_CHUNK_SIZE {
                 break;
             };
@@ -175,16 +259,24 @@ impl UtxoIndexApi for UtxoIndex {
         trace!("[{0}] committing circulating supply {1} from consensus db", IDENT, circulating_supply);
         self.store.insert_circulating_supply(circulating_supply, true)?;
 
+        trace!("[{0}] committing burned supply {1} from consensus db", IDENT, burned_supply);
+        self.store.insert_burned_supply(burned_supply, true)?;
+
         trace!("[{0}] committing consensus tips {consensus_tips:?} from consensus db", IDENT);
         self.store.set_tips(consensus_tips, true)?;
 
+        info!("Resynced the utxoindex with {} utxos in {:.1}s", processed, start.elapsed().as_secs_f64());
         Ok(())
     }
+}
 
-    // This can have a big memory footprint, so it should be used only for tests.
-    fn get_all_outpoints(&self) -> StoreResult<std::collections::HashSet<kaspa_consensus_core::tx::TransactionOutpoint>> {
-        self.store.get_all_outpoints()
-    }
+/// Returns the fraction of the outpoint key space preceding `outpoint`.
+///
+/// The virtual utxo set is iterated in outpoint key order, whose leading transaction id is uniformly
+/// distributed, so this fraction estimates the completion of the iteration.
+fn key_space_ratio(outpoint: &TransactionOutpoint) -> f64 {
+    let prefix = u64::from_be_bytes(outpoint.transaction_id.as_bytes()[..8].try_into().unwrap());
+    prefix as f64 / u64::MAX as f64
 }
 
 impl Debug for UtxoIndex {
@@ -225,12 +317,14 @@ mod tests {
     };
     use kaspa_consensus_core::{
         api::ConsensusApi,
+        tx::PopulatedTransaction,
         utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
     };
     use kaspa_consensusmanager::ConsensusManager;
     use kaspa_core::info;
     use kaspa_database::create_temp_db;
     use kaspa_database::prelude::ConnBuilder;
+    use kaspa_txscript::is_unspendable;
     use std::{collections::HashSet, sync::Arc, time::Instant};
 
     /// TODO: use proper Simnet when implemented.
@@ -279,9 +373,13 @@ mod tests {
         let consensus_utxos = tc.get_virtual_utxos(None, usize::MAX, false); // `usize::MAX` to ensure to get all.
         let mut i = 0;
         let mut consensus_supply: CirculatingSupply = 0;
+        let mut consensus_burned_supply: CirculatingSupply = 0;
         let consensus_utxo_set_size = consensus_utxos.len();
         for (tx_outpoint, utxo_entry) in consensus_utxos.into_iter() {
             consensus_supply += utxo_entry.amount;
+            if is_unspendable::<PopulatedTransaction>(utxo_entry.script_public_key.script()) {
+                consensus_burned_supply += utxo_entry.amount;
+            }
             let indexed_utxos = utxoindex
                 .read()
                 .get_utxos_by_script_public_keys(HashSet::from_iter(vec![utxo_entry.script_public_key.clone()]))
@@ -298,6 +396,7 @@ mod tests {
 
         assert_eq!(i, consensus_utxo_set_size);
         assert_eq!(utxoindex.read().get_circulating_supply().expect("expected circulating supply"), consensus_supply);
+        assert_eq!(utxoindex.read().get_burned_supply().expect("expected burned supply"), consensus_burned_supply);
         assert_eq!(*utxoindex.read().get_utxo_index_tips().expect("expected circulating supply"), tc.get_virtual_parents());
 
         // Test update: Change and signal new virtual state.
@@ -377,6 +476,38 @@ mod tests {
         assert_eq!(i, consensus_utxo_set_size);
         assert_eq!(*utxoindex.read().get_utxo_index_tips().expect("expected circulating supply"), tc.get_virtual_parents());
 
+        // Test paging through the utxos of all script public keys.
+        let mut seen = HashSet::new();
+        let script_public_keys = tc
+            .get_virtual_utxos(None, usize::MAX, false)
+            .into_iter()
+            .map(|(_, utxo_entry)| utxo_entry.script_public_key)
+            .filter(|script_public_key| seen.insert(script_public_key.clone()))
+            .collect::<Vec<_>>();
+        let page_size = 7;
+        let (mut start, mut start_after) = (0, None);
+        let mut paged_outpoints = HashSet::new();
+        loop {
+            let page = utxoindex
+                .read()
+                .get_utxos_page_by_script_public_keys(script_public_keys[start..].to_vec(), start_after, page_size)
+                .expect("expected a page of utxos");
+            let page_len = page.entries.values().map(|collection| collection.len()).sum::<usize>();
+            assert!(page_len <= page_size);
+            for (tx_outpoint, _) in page.entries.into_values().flatten() {
+                assert!(paged_outpoints.insert(tx_outpoint), "expected each utxo to be returned once");
+            }
+            match page.last {
+                Some((script_public_key, tx_outpoint)) => {
+                    assert_eq!(page_len, page_size);
+                    start += script_public_keys[start..].iter().position(|x| *x == script_public_key).unwrap();
+                    start_after = Some(tx_outpoint);
+                }
+                None => break,
+            }
+        }
+        assert_eq!(paged_outpoints, utxoindex.read().get_all_outpoints().expect("expected all outpoints"));
+
         // Deconstruct
         drop(utxoindex);
         drop(tc);
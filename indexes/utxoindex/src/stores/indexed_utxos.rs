@@ -1,4 +1,4 @@
-use crate::core::model::{CompactUtxoCollection, CompactUtxoEntry, UtxoSetByScriptPublicKey};
+use crate::core::model::{CompactUtxoCollection, CompactUtxoEntry, UtxoSetByScriptPublicKey, UtxoSetByScriptPublicKeyPage};
 
 use kaspa_consensus_core::tx::{
     ScriptPublicKey, ScriptPublicKeyVersion, ScriptPublicKeys, ScriptVec, TransactionIndexType, TransactionOutpoint,
@@ -125,6 +125,17 @@ impl AsRef<[u8]> for UtxoEntryFullAccessKey {
 pub trait UtxoSetByScriptPublicKeyStoreReader {
     /// Get [UtxoSetByScriptPublicKey] set by queried [ScriptPublicKeys],
     fn get_utxos_from_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<UtxoSetByScriptPublicKey>;
+    /// Get a page of at most `limit` entries of the [UtxoSetByScriptPublicKey] set of the ordered `script_public_keys`,
+    /// starting right after the outpoint `start_after` of the first script public key if provided.
+    ///
+    /// The page holds fewer than `limit` entries only if it is the last one, in which case its `last` is `None`.
+    /// Note that a `limit` of zero yields an empty page reading as the last one, so callers should avoid it.
+    fn get_utxos_page_from_script_public_keys(
+        &self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage>;
     fn get_balance_from_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey>;
     fn get_all_outpoints(&self) -> StoreResult<HashSet<TransactionOutpoint>>; // This can have a big memory footprint, so it should be used only for tests.
 }
@@ -177,6 +188,55 @@ impl UtxoSetByScriptPublicKeyStoreReader for DbUtxoSetByScriptPublicKeyStore {
         Ok(utxos_by_script_public_keys)
     }
 
+    fn get_utxos_page_from_script_public_keys(
+        &self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage> {
+        let script_count = script_public_keys.len();
+        let mut remaining = limit;
+        let mut page = UtxoSetByScriptPublicKeyPage::default();
+        for (i, script_public_key) in script_public_keys.into_iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            let script_public_key_bucket = ScriptPublicKeyBucket::from(&script_public_key);
+            // The cursor only applies to the first script public key. Since the outpoint it refers to may have been
+            // spent in the meantime, the iteration starts at the outpoint position and skips it only if present.
+            let start_after = start_after.filter(|_| i == 0);
+            let seek_from = start_after.map(|outpoint| {
+                UtxoEntryFullAccessKey::new(script_public_key_bucket.clone(), TransactionOutpointKey::from(&outpoint))
+            });
+            let entries = self
+                .access
+                .seek_iterator(Some(script_public_key_bucket.as_ref()), seek_from, usize::MAX, false)
+                .map(|res| {
+                    let (key, entry) = res.unwrap();
+                    (
+                        TransactionOutpoint::from(TransactionOutpointKey(
+                            <[u8; TRANSACTION_OUTPOINT_KEY_SIZE]>::try_from(&key[..]).unwrap(),
+                        )),
+                        entry,
+                    )
+                })
+                .filter(|(outpoint, _)| Some(outpoint) != start_after.as_ref())
+                .take(remaining)
+                .collect::<Vec<_>>();
+            remaining -= entries.len();
+            if remaining == 0 {
+                page.last = entries.last().map(|(outpoint, _)| (script_public_key.clone(), *outpoint));
+            }
+            page.entries.insert(script_public_key, CompactUtxoCollection::from_iter(entries));
+        }
+        debug!(
+            "IDXPRC, Executed a paged query for the utxo set of {} script public keys yielding {} entries",
+            script_count,
+            limit - remaining
+        );
+        Ok(page)
+    }
+
     fn get_balance_from_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey> {
         let script_count = script_public_keys.len();
         let mut entries_count: usize = 0;
@@ -257,3 +317,112 @@ impl UtxoSetByScriptPublicKeyStore for DbUtxoSetByScriptPublicKeyStore {
         self.access.delete_all(DirectDbWriter::new(&self.db))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn script_public_key(i: u8) -> ScriptPublicKey {
+        ScriptPublicKey::new(0, ScriptVec::from_slice(&[i; 34]))
+    }
+
+    /// Outpoints are ordered by their transaction id bytes, so `outpoint(1) < outpoint(2) < ...` within a bucket
+    fn outpoint(i: u8) -> TransactionOutpoint {
+        TransactionOutpoint::new(Hash::from_bytes([i; 32]), 0)
+    }
+
+    /// Returns the outpoints of the page in store order, along with its last entry
+    fn page(
+        store: &DbUtxoSetByScriptPublicKeyStore,
+        script_public_keys: &[ScriptPublicKey],
+        start_after: Option<u8>,
+        limit: usize,
+    ) -> (Vec<TransactionOutpoint>, Option<(ScriptPublicKey, TransactionOutpoint)>) {
+        let page =
+            store.get_utxos_page_from_script_public_keys(script_public_keys.to_vec(), start_after.map(outpoint), limit).unwrap();
+        let mut outpoints = vec![];
+        for script_public_key in script_public_keys {
+            let mut bucket = page.entries.get(script_public_key).map(|collection| collection.keys().copied().collect::<Vec<_>>());
+            bucket.iter_mut().for_each(|bucket| bucket.sort_by_key(|outpoint| outpoint.transaction_id));
+            outpoints.extend(bucket.into_iter().flatten());
+        }
+        (outpoints, page.last)
+    }
+
+    /// Creates a store holding outpoints 1 to 3 for the first script public key, and 4 and 5 for the second one
+    fn store() -> (kaspa_database::utils::DbLifetime, DbUtxoSetByScriptPublicKeyStore, Vec<ScriptPublicKey>) {
+        let (lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = DbUtxoSetByScriptPublicKeyStore::new(db, CachePolicy::Empty);
+        let script_public_keys = vec![script_public_key(1), script_public_key(2)];
+        let entry = CompactUtxoEntry::new(100, 0, false);
+        let utxos = UtxoSetByScriptPublicKey::from_iter([
+            (script_public_keys[0].clone(), CompactUtxoCollection::from_iter((1..=3).map(|i| (outpoint(i), entry)))),
+            (script_public_keys[1].clone(), CompactUtxoCollection::from_iter((4..=5).map(|i| (outpoint(i), entry)))),
+        ]);
+        store.add_utxo_entries(&utxos).unwrap();
+        (lifetime, store, script_public_keys)
+    }
+
+    #[test]
+    fn test_utxos_page_cursor_boundary() {
+        let (_lifetime, mut store, spks) = store();
+
+        // A full page ending on the last outpoint of a script public key resumes with the next script public key
+        let (outpoints, last) = page(&store, &spks, None, 3);
+        assert_eq!(outpoints, (1..=3).map(outpoint).collect::<Vec<_>>());
+        assert_eq!(last, Some((spks[0].clone(), outpoint(3))));
+        let (outpoints, last) = page(&store, &spks, Some(3), 3);
+        assert_eq!(outpoints, vec![outpoint(4), outpoint(5)]);
+        assert_eq!(last, None);
+
+        // A page crossing the boundary between script public keys
+        let (outpoints, last) = page(&store, &spks, Some(2), 2);
+        assert_eq!(outpoints, vec![outpoint(3), outpoint(4)]);
+        assert_eq!(last, Some((spks[1].clone(), outpoint(4))));
+
+        // The cursor only applies to the first script public key
+        let (outpoints, last) = page(&store, &spks[1..], Some(4), 2);
+        assert_eq!(outpoints, vec![outpoint(5)]);
+        assert_eq!(last, None);
+
+        // A cursor outpoint spent in the meantime still resumes right after its position
+        let spent = UtxoSetByScriptPublicKey::from_iter([(
+            spks[0].clone(),
+            CompactUtxoCollection::from_iter([(outpoint(2), CompactUtxoEntry::new(100, 0, false))]),
+        )]);
+        store.remove_utxo_entries(&spent).unwrap();
+        let (outpoints, last) = page(&store, &spks, Some(2), 2);
+        assert_eq!(outpoints, vec![outpoint(3), outpoint(4)]);
+        assert_eq!(last, Some((spks[1].clone(), outpoint(4))));
+    }
+
+    #[test]
+    fn test_utxos_last_page() {
+        let (_lifetime, store, spks) = store();
+
+        // A page exactly fitting the remaining entries is full, so the next page is the empty last one
+        let (outpoints, last) = page(&store, &spks, None, 5);
+        assert_eq!(outpoints, (1..=5).map(outpoint).collect::<Vec<_>>());
+        assert_eq!(last, Some((spks[1].clone(), outpoint(5))));
+        let (outpoints, last) = page(&store, &spks[1..], Some(5), 5);
+        assert!(outpoints.is_empty());
+        assert_eq!(last, None);
+
+        // A page larger than the remaining entries is the last one
+        let (outpoints, last) = page(&store, &spks, None, 6);
+        assert_eq!(outpoints, (1..=5).map(outpoint).collect::<Vec<_>>());
+        assert_eq!(last, None);
+
+        // Paging through all entries one at a time
+        let (mut start, mut start_after, mut paged) = (0, None, vec![]);
+        loop {
+            let (outpoints, last) = page(&store, &spks[start..], start_after, 1);
+            paged.extend(outpoints);
+            let Some((script_public_key, outpoint)) = last else { break };
+            start += spks[start..].iter().position(|x| *x == script_public_key).unwrap();
+            start_after = Some(outpoint.transaction_id.as_bytes()[0]);
+        }
+        assert_eq!(paged, (1..=5).map(outpoint).collect::<Vec<_>>());
+    }
+}
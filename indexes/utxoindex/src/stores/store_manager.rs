@@ -1,7 +1,7 @@
 use std::{collections::HashSet, sync::Arc};
 
 use kaspa_consensus_core::{
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     BlockHashSet,
 };
 use kaspa_core::trace;
@@ -9,10 +9,13 @@ use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
 
 use crate::{
-    model::UtxoSetByScriptPublicKey,
+    model::{CirculatingSupplyDiff, UtxoSetByScriptPublicKey, UtxoSetByScriptPublicKeyPage},
     stores::{
         indexed_utxos::{DbUtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStoreReader},
-        supply::{CirculatingSupplyStore, CirculatingSupplyStoreReader, DbCirculatingSupplyStore},
+        supply::{
+            BurnedSupplyStore, BurnedSupplyStoreReader, CirculatingSupplyStore, CirculatingSupplyStoreReader, DbBurnedSupplyStore,
+            DbCirculatingSupplyStore,
+        },
         tips::{DbUtxoIndexTipsStore, UtxoIndexTipsStore, UtxoIndexTipsStoreReader},
     },
     IDENT,
@@ -22,6 +25,7 @@ use crate::{
 pub struct Store {
     utxoindex_tips_store: DbUtxoIndexTipsStore,
     circulating_supply_store: DbCirculatingSupplyStore,
+    burned_supply_store: DbBurnedSupplyStore,
     utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore,
 }
 
@@ -30,6 +34,7 @@ impl Store {
         Self {
             utxoindex_tips_store: DbUtxoIndexTipsStore::new(db.clone()),
             circulating_supply_store: DbCirculatingSupplyStore::new(db.clone()),
+            burned_supply_store: DbBurnedSupplyStore::new(db.clone()),
             utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore::new(db, CachePolicy::Empty),
         }
     }
@@ -38,6 +43,15 @@ impl Store {
         self.utxos_by_script_public_key_store.get_utxos_from_script_public_keys(script_public_keys)
     }
 
+    pub fn get_utxos_page_by_script_public_key(
+        &self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        start_after: Option<TransactionOutpoint>,
+        limit: usize,
+    ) -> StoreResult<UtxoSetByScriptPublicKeyPage> {
+        self.utxos_by_script_public_key_store.get_utxos_page_from_script_public_keys(script_public_keys, start_after, limit)
+    }
+
     pub fn get_balance_by_script_public_key(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey> {
         self.utxos_by_script_public_key_store.get_balance_from_script_public_keys(script_public_keys)
     }
@@ -91,6 +105,26 @@ impl Store {
         res
     }
 
+    pub fn get_burned_supply(&self) -> StoreResult<u64> {
+        self.burned_supply_store.get()
+    }
+
+    pub fn update_burned_supply(&mut self, burned_supply_diff: CirculatingSupplyDiff, try_reset_on_err: bool) -> StoreResult<u64> {
+        let res = self.burned_supply_store.update_burned_supply(burned_supply_diff);
+        if try_reset_on_err && res.is_err() {
+            self.delete_all()?;
+        }
+        res
+    }
+
+    pub fn insert_burned_supply(&mut self, burned_supply: u64, try_reset_on_err: bool) -> StoreResult<()> {
+        let res = self.burned_supply_store.insert(burned_supply);
+        if try_reset_on_err && res.is_err() {
+            self.delete_all()?;
+        }
+        res
+    }
+
     pub fn get_tips(&self) -> StoreResult<Arc<BlockHashSet>> {
         self.utxoindex_tips_store.get()
     }
@@ -111,6 +145,7 @@ impl Store {
         // Clear all
         self.utxoindex_tips_store.remove()?;
         self.circulating_supply_store.remove()?;
+        self.burned_supply_store.remove()?;
         self.utxos_by_script_public_key_store.delete_all()?;
 
         trace!("[{0}] clearing utxoindex database - success!", IDENT);
@@ -5,7 +5,7 @@ use kaspa_database::{
     registry::DatabaseStorePrefixes,
 };
 
-use crate::model::CirculatingSupply;
+use crate::model::{CirculatingSupply, CirculatingSupplyDiff};
 
 /// Reader API for `UtxoIndexTipsStore`.
 pub trait CirculatingSupplyStoreReader {
@@ -58,3 +58,54 @@ impl CirculatingSupplyStore for DbCirculatingSupplyStore {
         self.access.remove(DirectDbWriter::new(&self.db))
     }
 }
+
+/// Reader API for `BurnedSupplyStore`.
+pub trait BurnedSupplyStoreReader {
+    fn get(&self) -> StoreResult<u64>;
+}
+
+/// Tracks the supply held by provably unspendable UTXOs, which is part of the circulating supply
+pub trait BurnedSupplyStore: BurnedSupplyStoreReader {
+    fn update_burned_supply(&mut self, to_add: CirculatingSupplyDiff) -> StoreResult<u64>;
+    fn insert(&mut self, burned_supply: u64) -> StoreResult<()>;
+    fn remove(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `BurnedSupplyStore` trait
+#[derive(Clone)]
+pub struct DbBurnedSupplyStore {
+    db: Arc<DB>,
+    access: CachedDbItem<u64>,
+}
+
+impl DbBurnedSupplyStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::BurnedSupply.into()) }
+    }
+}
+
+impl BurnedSupplyStoreReader for DbBurnedSupplyStore {
+    fn get(&self) -> StoreResult<u64> {
+        self.access.read()
+    }
+}
+
+impl BurnedSupplyStore for DbBurnedSupplyStore {
+    fn update_burned_supply(&mut self, to_add: CirculatingSupplyDiff) -> StoreResult<u64> {
+        if to_add == 0 {
+            return self.get();
+        }
+
+        // Unspendable UTXOs can only leave the UTXO set by a reorg, so the diff is applied as is, unlike the
+        // circulating supply one.
+        self.access.update(DirectDbWriter::new(&self.db), move |burned_supply| burned_supply.saturating_add_signed(to_add))
+    }
+
+    fn insert(&mut self, burned_supply: u64) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), &burned_supply)
+    }
+
+    fn remove(&mut self) -> StoreResult<()> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -1,9 +1,10 @@
 use kaspa_consensus_core::{
-    tx::{TransactionOutpoint, UtxoEntry},
+    tx::{PopulatedTransaction, TransactionOutpoint, UtxoEntry},
     utxo::utxo_diff::UtxoDiff,
     BlockHashSet, HashMapCustomHasher,
 };
 use kaspa_hashes::Hash;
+use kaspa_txscript::is_unspendable;
 use kaspa_utils::hashmap::NestedHashMapExtensions;
 
 use crate::model::{CirculatingSupplyDiff, CompactUtxoEntry, UtxoChanges, UtxoSetByScriptPublicKey};
@@ -12,6 +13,8 @@ use crate::model::{CirculatingSupplyDiff, CompactUtxoEntry, UtxoChanges, UtxoSet
 pub struct UtxoIndexChanges {
     pub utxo_changes: UtxoChanges,
     pub supply_change: CirculatingSupplyDiff,
+    /// Change of the supply held by provably unspendable UTXOs
+    pub burned_supply_change: CirculatingSupplyDiff,
     pub tips: BlockHashSet,
 }
 
@@ -21,6 +24,7 @@ impl UtxoIndexChanges {
         Self {
             utxo_changes: UtxoChanges::new(UtxoSetByScriptPublicKey::new(), UtxoSetByScriptPublicKey::new()),
             supply_change: 0,
+            burned_supply_change: 0,
             tips: BlockHashSet::new(),
         }
     }
@@ -34,6 +38,9 @@ impl UtxoIndexChanges {
                 continue;
             }; // We try and remove from `utxo_diff.remove`, if we do, discard utxo.
             self.supply_change += utxo_entry.amount as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
+            if is_burned(&utxo_entry) {
+                self.burned_supply_change += utxo_entry.amount as CirculatingSupplyDiff;
+            }
 
             self.utxo_changes.added.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -44,6 +51,9 @@ impl UtxoIndexChanges {
 
         for (transaction_outpoint, utxo_entry) in to_remove.into_iter() {
             self.supply_change -= utxo_entry.amount as CirculatingSupplyDiff; // TODO: Using `virtual_state.mergeset_rewards` might be a better way to extract this.
+            if is_burned(&utxo_entry) {
+                self.burned_supply_change -= utxo_entry.amount as CirculatingSupplyDiff;
+            }
 
             self.utxo_changes.removed.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -59,6 +69,9 @@ impl UtxoIndexChanges {
     pub fn add_utxos_from_vector(&mut self, utxo_vector: Vec<(TransactionOutpoint, UtxoEntry)>) {
         for (transaction_outpoint, utxo_entry) in utxo_vector.into_iter() {
             self.supply_change += utxo_entry.amount as CirculatingSupplyDiff;
+            if is_burned(&utxo_entry) {
+                self.burned_supply_change += utxo_entry.amount as CirculatingSupplyDiff;
+            }
 
             self.utxo_changes.added.insert_into_nested(
                 utxo_entry.script_public_key,
@@ -72,3 +85,55 @@ impl UtxoIndexChanges {
         self.tips = BlockHashSet::from_iter(tips);
     }
 }
+
+/// Returns whether the UTXO is provably unspendable, its amount being burned
+fn is_burned(utxo_entry: &UtxoEntry) -> bool {
+    is_unspendable::<PopulatedTransaction>(utxo_entry.script_public_key.script())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        tx::{ScriptPublicKey, ScriptVec},
+        utxo::utxo_collection::UtxoCollection,
+    };
+    use kaspa_txscript::opcodes::codes::{OpData1, OpReturn, OpTrue};
+
+    fn entry(amount: u64, script: &[u8]) -> UtxoEntry {
+        UtxoEntry::new(amount, ScriptPublicKey::new(0, ScriptVec::from_slice(script)), 0, false)
+    }
+
+    fn outpoint(i: u64) -> TransactionOutpoint {
+        TransactionOutpoint::new(Hash::from_u64_word(i), 0)
+    }
+
+    #[test]
+    fn test_burned_supply_change() {
+        let (spendable, burned, data_carrier) = (vec![OpTrue], vec![OpReturn], vec![OpReturn, OpData1, 0x01]);
+        let mut changes = UtxoIndexChanges::new();
+        changes.add_utxos_from_vector(vec![(outpoint(1), entry(100, &spendable)), (outpoint(2), entry(20, &burned))]);
+        assert_eq!((changes.supply_change, changes.burned_supply_change), (120, 20));
+
+        // Burned outputs being added count towards the burned supply, and the ones being removed are subtracted from it
+        let mut changes = UtxoIndexChanges::new();
+        changes.update_utxo_diff(UtxoDiff::new(
+            UtxoCollection::from_iter([
+                (outpoint(3), entry(7, &burned)),
+                (outpoint(4), entry(5, &data_carrier)),
+                (outpoint(5), entry(1000, &spendable)),
+            ]),
+            UtxoCollection::from_iter([(outpoint(2), entry(20, &burned)), (outpoint(1), entry(100, &spendable))]),
+        ));
+        assert_eq!(changes.supply_change, 7 + 5 + 1000 - 20 - 100);
+        assert_eq!(changes.burned_supply_change, 7 + 5 - 20);
+
+        // An output both added and removed by the diff leaves the supplies unchanged
+        let mut changes = UtxoIndexChanges::new();
+        changes.update_utxo_diff(UtxoDiff::new(
+            UtxoCollection::from_iter([(outpoint(6), entry(9, &burned))]),
+            UtxoCollection::from_iter([(outpoint(6), entry(9, &burned))]),
+        ));
+        assert_eq!((changes.supply_change, changes.burned_supply_change), (0, 0));
+    }
+}
@@ -0,0 +1,47 @@
+use std::{
+    env,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() {
+    // The git commit the node is built from, unless provided by the build environment
+    let git_hash = env::var("KASPAD_GIT_HASH").ok().or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|hash| hash.trim().to_string())
+    });
+    if let Some(git_hash) = git_hash.filter(|hash| !hash.is_empty()) {
+        println!("cargo:rustc-env=KASPAD_GIT_HASH={git_hash}");
+    }
+
+    // The build date, honoring `SOURCE_DATE_EPOCH` for reproducible builds
+    let timestamp = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+    println!("cargo:rustc-env=KASPAD_BUILD_DATE={}", utc_date(timestamp));
+
+    println!("cargo:rerun-if-env-changed=KASPAD_GIT_HASH");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+}
+
+/// Formats a unix timestamp as a `YYYY-MM-DD` UTC date (see http://howardhinnant.github.io/date_algorithms.html)
+fn utc_date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
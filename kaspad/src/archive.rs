@@ -0,0 +1,260 @@
+//! Block archive files: a portable sequence of full blocks, written by `kaspad export --format=archive` and
+//! ingested by `kaspad import-blocks`.
+//!
+//! An archive starts with a header made of a magic, a format version and the network id of the blocks,
+//! followed by the blocks in topological order, so every block is preceded by those of its parents which
+//! are part of the archive. Each block is stored as a little endian `u32` length followed by the borsh
+//! serialization of the block as a [`RpcBlock`].
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::{block::Block, network::NetworkId};
+use kaspa_rpc_core::RpcBlock;
+use std::{
+    io::{ErrorKind, Read, Write},
+    str::FromStr,
+};
+use thiserror::Error;
+
+const MAGIC: &[u8; 8] = b"KASBLKAR";
+const VERSION: u16 = 1;
+
+/// Upper bound of the serialized size of a single block, protecting the reader against corrupted lengths
+const MAX_BLOCK_SIZE: u32 = 1 << 30;
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("not a block archive")]
+    InvalidMagic,
+
+    #[error("unsupported block archive version {0}, expected {VERSION}")]
+    UnsupportedVersion(u16),
+
+    #[error("invalid network id '{0}' in the block archive header")]
+    InvalidNetwork(String),
+
+    #[error("block archive record of {0} bytes exceeds the maximum block size")]
+    RecordTooLarge(u32),
+
+    #[error("invalid block at record {0}: {1}")]
+    InvalidBlock(u64, String),
+
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub type ArchiveResult<T> = std::result::Result<T, ArchiveError>;
+
+/// Writes blocks to a block archive
+pub struct BlockArchiveWriter<W: Write> {
+    writer: W,
+    count: u64,
+}
+
+impl<W: Write> BlockArchiveWriter<W> {
+    /// Writes the archive header and returns a writer ready to append blocks of `network`
+    pub fn new(mut writer: W, network: NetworkId) -> ArchiveResult<Self> {
+        let network = network.to_string();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(network.len() as u16).to_le_bytes())?;
+        writer.write_all(network.as_bytes())?;
+        Ok(Self { writer, count: 0 })
+    }
+
+    /// Appends a block. Callers are responsible for appending blocks in topological order.
+    pub fn append(&mut self, block: &Block) -> ArchiveResult<()> {
+        let bytes = RpcBlock::from(block).try_to_vec()?;
+        self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&bytes)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Number of blocks appended so far
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Flushes the archive and returns the inner writer
+    pub fn finish(mut self) -> ArchiveResult<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the blocks of a block archive, in archive order
+pub struct BlockArchiveReader<R: Read> {
+    reader: R,
+    network: NetworkId,
+    count: u64,
+}
+
+impl<R: Read> BlockArchiveReader<R> {
+    /// Reads and validates the archive header
+    pub fn new(mut reader: R) -> ArchiveResult<Self> {
+        let mut magic = [0u8; MAGIC.len()];
+        reader.read_exact(&mut magic).map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => ArchiveError::InvalidMagic,
+            _ => err.into(),
+        })?;
+        if &magic != MAGIC {
+            return Err(ArchiveError::InvalidMagic);
+        }
+        let version = u16::from_le_bytes(read_array(&mut reader)?);
+        if version != VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+        let mut network = vec![0u8; u16::from_le_bytes(read_array(&mut reader)?) as usize];
+        reader.read_exact(&mut network)?;
+        let network = String::from_utf8_lossy(&network).to_string();
+        let network = NetworkId::from_str(&network).map_err(|_| ArchiveError::InvalidNetwork(network))?;
+        Ok(Self { reader, network, count: 0 })
+    }
+
+    /// Network of the archived blocks
+    pub fn network(&self) -> NetworkId {
+        self.network
+    }
+
+    fn read_block(&mut self) -> ArchiveResult<Option<Block>> {
+        let mut len = [0u8; 4];
+        // A clean end of file is only expected at a record boundary, a truncated record being an error
+        let read = loop {
+            match self.reader.read(&mut len) {
+                Ok(read) => break read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        };
+        if read == 0 {
+            return Ok(None);
+        }
+        self.reader.read_exact(&mut len[read..])?;
+        let len = u32::from_le_bytes(len);
+        if len > MAX_BLOCK_SIZE {
+            return Err(ArchiveError::RecordTooLarge(len));
+        }
+        let mut bytes = vec![0u8; len as usize];
+        self.reader.read_exact(&mut bytes)?;
+        let block = RpcBlock::try_from_slice(&bytes).map_err(|err| ArchiveError::InvalidBlock(self.count, err.to_string()))?;
+        let block = Block::try_from(&block).map_err(|err| ArchiveError::InvalidBlock(self.count, err.to_string()))?;
+        self.count += 1;
+        Ok(Some(block))
+    }
+}
+
+impl<R: Read> Iterator for BlockArchiveReader<R> {
+    type Item = ArchiveResult<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_block().transpose()
+    }
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        header::Header,
+        network::NetworkType,
+        subnets::SUBNETWORK_ID_COINBASE,
+        tx::{ScriptPublicKey, Transaction, TransactionOutput},
+    };
+    use kaspa_hashes::Hash;
+
+    fn block(hash: u64, parents: Vec<u64>) -> Block {
+        let header = Header::from_precomputed_hash(hash.into(), parents.into_iter().map(Hash::from).collect());
+        let coinbase = Transaction::new(
+            0,
+            vec![],
+            vec![TransactionOutput::new(hash, ScriptPublicKey::from_vec(0, vec![0x51]))],
+            0,
+            SUBNETWORK_ID_COINBASE,
+            0,
+            hash.to_le_bytes().to_vec(),
+        );
+        Block::new(header, vec![coinbase])
+    }
+
+    fn archive(network: NetworkId, blocks: &[Block]) -> Vec<u8> {
+        let mut writer = BlockArchiveWriter::new(Vec::new(), network).unwrap();
+        for block in blocks {
+            writer.append(block).unwrap();
+        }
+        assert_eq!(writer.count(), blocks.len() as u64);
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let network = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let blocks = vec![block(1, vec![]), block(2, vec![1]), block(3, vec![1]), block(4, vec![2, 3])];
+        let bytes = archive(network, &blocks);
+
+        let reader = BlockArchiveReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.network(), network);
+        let read = reader.collect::<ArchiveResult<Vec<_>>>().unwrap();
+        assert_eq!(read.len(), blocks.len());
+        for (read, block) in read.iter().zip(blocks.iter()) {
+            assert_eq!(read.hash(), block.hash());
+            assert_eq!(read.header.direct_parents(), block.header.direct_parents());
+            assert_eq!(read.transactions.len(), 1);
+            assert_eq!(read.transactions[0].id(), block.transactions[0].id());
+        }
+
+        // An archive without blocks only holds the header
+        let bytes = archive(NetworkId::new(NetworkType::Mainnet), &[]);
+        let mut reader = BlockArchiveReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.network(), NetworkId::new(NetworkType::Mainnet));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_archive_invalid_header() {
+        let bytes = archive(NetworkId::new(NetworkType::Mainnet), &[block(1, vec![])]);
+
+        assert!(matches!(BlockArchiveReader::new(&bytes[..4]), Err(ArchiveError::InvalidMagic)));
+        let mut corrupted = bytes.clone();
+        corrupted[0] ^= 0xff;
+        assert!(matches!(BlockArchiveReader::new(corrupted.as_slice()), Err(ArchiveError::InvalidMagic)));
+
+        let mut corrupted = bytes.clone();
+        corrupted[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert!(matches!(BlockArchiveReader::new(corrupted.as_slice()), Err(ArchiveError::UnsupportedVersion(v)) if v == VERSION + 1));
+
+        let mut corrupted = bytes.clone();
+        corrupted[MAGIC.len() + 4] = b'x';
+        assert!(matches!(BlockArchiveReader::new(corrupted.as_slice()), Err(ArchiveError::InvalidNetwork(_))));
+    }
+
+    #[test]
+    fn test_archive_invalid_records() {
+        let network = NetworkId::new(NetworkType::Mainnet);
+        let header_len = archive(network, &[]).len();
+        let bytes = archive(network, &[block(1, vec![]), block(2, vec![1])]);
+
+        // A truncated record is an error rather than the end of the archive
+        for truncated_len in [bytes.len() - 1, header_len + 2] {
+            let mut reader = BlockArchiveReader::new(&bytes[..truncated_len]).unwrap();
+            let results = reader.by_ref().collect::<Vec<_>>();
+            assert!(matches!(results.last(), Some(Err(ArchiveError::IoError(err))) if err.kind() == ErrorKind::UnexpectedEof));
+        }
+
+        let mut corrupted = bytes[..header_len].to_vec();
+        corrupted.extend_from_slice(&(MAX_BLOCK_SIZE + 1).to_le_bytes());
+        let mut reader = BlockArchiveReader::new(corrupted.as_slice()).unwrap();
+        assert!(matches!(reader.next(), Some(Err(ArchiveError::RecordTooLarge(len))) if len == MAX_BLOCK_SIZE + 1));
+
+        let mut corrupted = bytes[..header_len].to_vec();
+        corrupted.extend_from_slice(&3u32.to_le_bytes());
+        corrupted.extend_from_slice(&[1, 2, 3]);
+        let mut reader = BlockArchiveReader::new(corrupted.as_slice()).unwrap();
+        assert!(matches!(reader.next(), Some(Err(ArchiveError::InvalidBlock(0, _)))));
+    }
+}
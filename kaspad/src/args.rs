@@ -1,15 +1,28 @@
+use crate::{
+    export::{self, ExportArgs},
+    import::{self, ImportArgs},
+};
 use clap::{arg, Arg, ArgAction, Command};
 use kaspa_consensus_core::{
-    config::Config,
+    block::TemplateTxOrdering,
+    config::{params::MIN_DIFFICULTY_WINDOW_LEN, Config, DEFAULT_MIN_HASHRATE_ESTIMATION_WINDOW_SIZE},
     network::{NetworkId, NetworkType},
 };
 use kaspa_core::kaspad_env::version;
+use kaspa_grpc_server::{
+    compression::GrpcCompression,
+    listen::GrpcListenAddress,
+    outgoing::{OutgoingOverflowPolicy, DEFAULT_OUTGOING_ROUTE_CAPACITY},
+    transport::{DEFAULT_HTTP2_KEEPALIVE_TIMEOUT, DEFAULT_TCP_KEEPALIVE},
+};
+use kaspa_mining::mempool::config::{DustPolicy, DEFAULT_DUST_RELAY_TRANSACTION_FEE};
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_rpc_service::access::RpcInterface;
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
-use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use serde_with::{serde_as, DisplayFromStr, OneOrMany};
+use std::{ffi::OsString, fs, net::IpAddr};
 use toml::from_str;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -30,12 +43,17 @@ pub struct Args {
     pub logdir: Option<String>,
     #[serde(rename = "nologfiles")]
     pub no_log_files: bool,
-    #[serde_as(as = "Option<DisplayFromStr>")]
-    pub rpclisten: Option<ContextualNetAddress>,
+    #[serde(rename = "accesslog")]
+    pub access_log: bool,
+    #[serde_as(as = "OneOrMany<DisplayFromStr>")]
+    pub rpclisten: Vec<GrpcListenAddress>,
+    pub rpclisten_unix: Option<String>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub rpclisten_borsh: Option<WrpcNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub rpclisten_json: Option<WrpcNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub rpclisten_http: Option<ContextualNetAddress>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
     pub wrpc_verbose: bool,
@@ -53,6 +71,9 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub utxoindex_resync_rate: u64,
+    pub txindex_resync_rate: u64,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -60,22 +81,69 @@ pub struct Args {
     pub inbound_limit: usize,
     #[serde(rename = "rpcmaxclients")]
     pub rpc_max_clients: usize,
+    pub rpc_max_clients_per_ip: usize,
+    pub rpc_trusted_ips: Vec<IpAddr>,
+    #[serde(rename = "rpctenants")]
+    pub rpc_tenants: Option<String>,
+    pub grpc_tls_cert: Option<String>,
+    pub grpc_tls_key: Option<String>,
+    pub grpc_tls_client_ca: Option<String>,
+    pub grpc_rate_limit: Option<u32>,
+    pub grpc_rate_limit_burst: Option<u32>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub grpc_compression: Vec<GrpcCompression>,
+    pub grpc_tcp_keepalive: u64,
+    pub grpc_http2_keepalive_interval: u64,
+    pub grpc_http2_keepalive_timeout: u64,
+    pub grpc_max_concurrent_streams: u32,
+    pub grpc_outgoing_capacity: usize,
+    #[serde_as(as = "DisplayFromStr")]
+    pub grpc_outgoing_overflow: OutgoingOverflowPolicy,
+    pub grpc_request_timeout: u64,
+    pub grpc_method_timeout: Vec<String>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub rpc_public: Vec<RpcInterface>,
+    pub rpc_public_ops: Option<String>,
+    pub rpc_public_deny_ops: Option<String>,
     pub max_tracked_addresses: usize,
+    pub max_listener_addresses: usize,
+    pub utxos_changed_window: Option<u64>,
     pub enable_unsynced_mining: bool,
     pub enable_mainnet_mining: bool,
+    pub enable_transaction_builder_rpc: bool,
     pub testnet: bool,
     #[serde(rename = "netsuffix")]
     pub testnet_suffix: u32,
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    pub cold_datadir: Option<String>,
+    pub cold_store_depth: Option<u64>,
+    pub compress_block_transactions: Option<i32>,
+    pub compress_acceptance_data: Option<i32>,
+    pub secondary_datadir: Option<String>,
+    /// Set when running the `export` subcommand instead of the node
+    #[serde(skip)]
+    pub export: Option<ExportArgs>,
+    /// Set when running the `import-blocks` subcommand instead of the node
+    #[serde(skip)]
+    pub import: Option<ImportArgs>,
+    /// Set when the build information is requested instead of running the node
+    #[serde(skip)]
+    pub build_info: bool,
     pub sanity: bool,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub externalip: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
+    pub persist_counters: bool,
+    pub chain_quality: bool,
     pub perf_metrics_interval_sec: u64,
     pub block_template_cache_lifetime: Option<u64>,
+    pub template_tx_ordering: TemplateTxOrdering,
+    pub dust_relay_fee: u64,
+    pub min_output_value: u64,
+    pub datacarrier: bool,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -90,6 +158,11 @@ pub struct Args {
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    pub header_threads: Option<usize>,
+    pub body_threads: Option<usize>,
+    pub virtual_threads: Option<usize>,
+    pub min_hashrate_window_size: Option<usize>,
+    pub simnet_coinbase_maturity: Option<u64>,
 }
 
 impl Default for Args {
@@ -97,26 +170,63 @@ impl Default for Args {
         Self {
             appdir: None,
             no_log_files: false,
+            access_log: false,
             rpclisten_borsh: None,
             rpclisten_json: None,
+            rpclisten_http: None,
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            utxoindex_resync_rate: 0,
+            txindex_resync_rate: 0,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
             rpc_max_clients: 128,
+            rpc_max_clients_per_ip: 0,
+            rpc_trusted_ips: vec![],
+            rpc_tenants: None,
+            grpc_tls_cert: None,
+            grpc_tls_key: None,
+            grpc_tls_client_ca: None,
+            grpc_rate_limit: None,
+            grpc_rate_limit_burst: None,
+            grpc_compression: vec![],
+            grpc_tcp_keepalive: DEFAULT_TCP_KEEPALIVE.as_secs(),
+            grpc_http2_keepalive_interval: 0,
+            grpc_http2_keepalive_timeout: DEFAULT_HTTP2_KEEPALIVE_TIMEOUT.as_secs(),
+            grpc_max_concurrent_streams: 0,
+            grpc_outgoing_capacity: DEFAULT_OUTGOING_ROUTE_CAPACITY,
+            grpc_outgoing_overflow: Default::default(),
+            grpc_request_timeout: 0,
+            grpc_method_timeout: vec![],
+            rpc_public: vec![],
+            rpc_public_ops: None,
+            rpc_public_deny_ops: None,
             max_tracked_addresses: 0,
+            max_listener_addresses: 0,
+            utxos_changed_window: None,
             enable_unsynced_mining: false,
             enable_mainnet_mining: true,
+            enable_transaction_builder_rpc: false,
             testnet: false,
             testnet_suffix: 10,
             devnet: false,
             simnet: false,
             archival: false,
+            cold_datadir: None,
+            cold_store_depth: None,
+            compress_block_transactions: None,
+            compress_acceptance_data: None,
+            secondary_datadir: None,
+            export: None,
+            build_info: false,
+            import: None,
             sanity: false,
             logdir: None,
-            rpclisten: None,
+            rpclisten: vec![],
+            rpclisten_unix: None,
             wrpc_verbose: false,
             log_level: "INFO".into(),
             connect_peers: vec![],
@@ -125,9 +235,15 @@ impl Default for Args {
             user_agent_comments: vec![],
             yes: false,
             perf_metrics: false,
+            persist_counters: false,
+            chain_quality: false,
             perf_metrics_interval_sec: 10,
             externalip: None,
             block_template_cache_lifetime: None,
+            template_tx_ordering: Default::default(),
+            dust_relay_fee: DEFAULT_DUST_RELAY_TRANSACTION_FEE,
+            min_output_value: 0,
+            datacarrier: false,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -140,6 +256,11 @@ impl Default for Args {
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            header_threads: None,
+            body_threads: None,
+            virtual_threads: None,
+            min_hashrate_window_size: None,
+            simnet_coinbase_maturity: None,
         }
     }
 }
@@ -147,18 +268,45 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
         config.disable_upnp = self.disable_upnp;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
+        config.enable_transaction_builder_rpc = self.enable_transaction_builder_rpc;
         config.is_archival = self.archival;
+        if let Some(cold_store_depth) = self.cold_store_depth {
+            config.cold_store_depth = cold_store_depth;
+        }
+        config.block_transactions_compression_level = self.compress_block_transactions;
+        config.acceptance_data_compression_level = self.compress_acceptance_data;
+        config.is_secondary = self.secondary_datadir.is_some();
+        // A secondary instance does not take part in the p2p network
+        config.disable_upnp = self.disable_upnp || config.is_secondary;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
         config.block_template_cache_lifetime = self.block_template_cache_lifetime;
+        config.utxos_changed_coalescing_window = self.utxos_changed_window.filter(|&window| window > 0);
+        config.template_tx_ordering = self.template_tx_ordering;
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        if let Some(header_threads) = self.header_threads {
+            config.perf.header_processor_num_threads = header_threads;
+        }
+        if let Some(body_threads) = self.body_threads {
+            config.perf.body_processor_num_threads = body_threads;
+        }
+        if let Some(virtual_threads) = self.virtual_threads {
+            config.perf.virtual_processor_num_threads = virtual_threads;
+        }
+        if let Some(min_hashrate_window_size) = self.min_hashrate_window_size {
+            config.min_hashrate_estimation_window_size = min_hashrate_window_size;
+        }
+        if let Some(coinbase_maturity) = self.simnet_coinbase_maturity {
+            config.params.coinbase_maturity = coinbase_maturity;
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -180,6 +328,14 @@ impl Args {
             .collect()
     }
 
+    pub fn dust_policy(&self) -> DustPolicy {
+        DustPolicy {
+            dust_relay_fee: self.dust_relay_fee,
+            minimum_output_value: self.min_output_value,
+            accept_data_carrier: self.datacarrier,
+        }
+    }
+
     pub fn network(&self) -> NetworkId {
         match (self.testnet, self.devnet, self.simnet) {
             (false, false, false) => NetworkId::new(NetworkType::Mainnet),
@@ -198,10 +354,12 @@ pub fn cli() -> Command {
     let cmd = Command::new("kaspad")
         .about(format!("{} (rusty-kaspa) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
         .version(env!("CARGO_PKG_VERSION"))
+        .arg(arg!(--"build-info" "Print the version, git hash, build date, cargo features, protocol versions and RPC capabilities of this build as JSON, then exit."))
         .arg(arg!(-C --configfile <CONFIG_FILE> "Path of config file."))
         .arg(arg!(-b --appdir <DATA_DIR> "Directory to store data."))
         .arg(arg!(--logdir <LOG_DIR> "Directory to log output."))
         .arg(arg!(--nologfiles "Disable logging to files."))
+        .arg(arg!(--accesslog "Record every gRPC request as a JSON line in a rotated access log file of the log directory."))
         .arg(
             Arg::new("async_threads")
                 .short('t')
@@ -223,11 +381,18 @@ pub fn cli() -> Command {
         .arg(
             Arg::new("rpclisten")
                 .long("rpclisten")
-                .value_name("IP[:PORT]")
-                .num_args(0..=1)
+                .value_name("IP[:PORT][/PROFILE]")
+                .action(ArgAction::Append)
                 .require_equals(true)
-                .value_parser(clap::value_parser!(ContextualNetAddress))
-                .help("Interface:port to listen for gRPC connections (default port: 16110, testnet: 16210)."),
+                .value_parser(clap::value_parser!(GrpcListenAddress))
+                .help("Interface:port to listen for gRPC connections (default port: 16110, testnet: 16210). Can be repeated to listen on several addresses, each optionally suffixed by the access profile of its connections, private or public, as in 0.0.0.0:16110/public (default: the one of the grpc interface, see --rpc-public)."),
+        )
+        .arg(
+            Arg::new("rpclisten-unix")
+                .long("rpclisten-unix")
+                .value_name("PATH")
+                .require_equals(true)
+                .help("Path of a unix domain socket to additionally listen on for gRPC connections, sparing co-located clients the TCP overhead (unix platforms only). Its connections get the access profile of the grpc interface."),
         )
         .arg(
             Arg::new("rpclisten-borsh")
@@ -250,6 +415,16 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(WrpcNetAddress))
                 .help("Interface:port to listen for wRPC JSON connections (default port: 18110, testnet: 18210)."),
         )
+        .arg(
+            Arg::new("rpclisten-http")
+                .long("rpclisten-http")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("127.0.0.1")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to serve the read-only HTTP gateway on, described by its /openapi.json endpoint (default: 127.0.0.1, default port: 19110, testnet: 19210)."),
+        )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
         .arg(
             Arg::new("connect-peers")
@@ -301,8 +476,170 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Max number of RPC clients for standard connections (default: 128)."),
         )
+        .arg(
+            Arg::new("rpc-max-clients-per-ip")
+                .long("rpc-max-clients-per-ip")
+                .value_name("rpc-max-clients-per-ip")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max number of gRPC clients connected from a single IP address, 0 for no limit (default: 0)."),
+        )
+        .arg(
+            Arg::new("rpc-trusted-ip")
+                .long("rpc-trusted-ip")
+                .value_name("IP")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(IpAddr))
+                .help("IP address exempted from the per IP gRPC client limit. Can be repeated."),
+        )
+        .arg(
+            Arg::new("rpctenants")
+                .long("rpctenants")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Path of a TOML file mapping gRPC API keys to tenants with their quotas (requests per second, subscriptions, addresses)."),
+        )
+        .arg(
+            Arg::new("grpc-tls-cert")
+                .long("grpc-tls-cert")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("grpc-tls-key")
+                .help("Path of the PEM certificate (chain) served by the gRPC server, enabling TLS. Requires --grpc-tls-key."),
+        )
+        .arg(
+            Arg::new("grpc-tls-key")
+                .long("grpc-tls-key")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("grpc-tls-cert")
+                .help("Path of the PEM private key of the gRPC server certificate."),
+        )
+        .arg(
+            Arg::new("grpc-tls-client-ca")
+                .long("grpc-tls-client-ca")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("grpc-tls-cert")
+                .help("Path of a PEM CA certificate. When set, gRPC clients must present a certificate issued by this CA (mutual TLS)."),
+        )
+        .arg(
+            Arg::new("grpc-rate-limit")
+                .long("grpc-rate-limit")
+                .value_name("RATE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .help("Maximum gRPC request rate per remote address, in requests per second. Heavy methods (e.g. GetBlocks) count as several requests."),
+        )
+        .arg(
+            Arg::new("grpc-rate-limit-burst")
+                .long("grpc-rate-limit-burst")
+                .value_name("REQUESTS")
+                .require_equals(true)
+                .requires("grpc-rate-limit")
+                .value_parser(clap::value_parser!(u32))
+                .help("Maximum gRPC request burst per remote address (default: the rate limit)."),
+        )
+        .arg(
+            Arg::new("grpc-compression")
+                .long("grpc-compression")
+                .value_name("ALGORITHM")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(GrpcCompression))
+                .help("Compression algorithm enabled on the gRPC server (gzip or none). Can be repeated; each client gets the first enabled algorithm it accepts (default: gzip)."),
+        )
+        .arg(
+            Arg::new("grpc-request-timeout")
+                .long("grpc-request-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Deadline of the gRPC requests, after which they are cancelled, 0 for none (default: 0)."),
+        )
+        .arg(
+            Arg::new("grpc-method-timeout")
+                .long("grpc-method-timeout")
+                .value_name("METHOD=SECONDS")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help("Deadline of the requests of a specific gRPC method, as in GetVirtualChainFromBlock=30. Can be repeated."),
+        )
+        .arg(
+            Arg::new("grpc-tcp-keepalive")
+                .long("grpc-tcp-keepalive")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Interval of the TCP keepalive probes of the gRPC connections, 0 to disable (default: {}).", defaults.grpc_tcp_keepalive)),
+        )
+        .arg(
+            Arg::new("grpc-http2-keepalive-interval")
+                .long("grpc-http2-keepalive-interval")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Interval of the HTTP/2 keepalive pings sent to the gRPC clients, 0 to disable (default: 0)."),
+        )
+        .arg(
+            Arg::new("grpc-http2-keepalive-timeout")
+                .long("grpc-http2-keepalive-timeout")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(format!("Time a gRPC client has to acknowledge an HTTP/2 keepalive ping before getting disconnected (default: {}).", defaults.grpc_http2_keepalive_timeout)),
+        )
+        .arg(
+            Arg::new("grpc-max-concurrent-streams")
+                .long("grpc-max-concurrent-streams")
+                .value_name("STREAMS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Maximum number of concurrent HTTP/2 streams of a gRPC connection, 0 for no limit (default: 0)."),
+        )
+        .arg(
+            Arg::new("grpc-outgoing-capacity")
+                .long("grpc-outgoing-capacity")
+                .value_name("MESSAGES")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize).range(1..))
+                .help(format!("Number of responses and notifications queued for a gRPC client reading them too slowly before the overflow policy applies (default: {}).", defaults.grpc_outgoing_capacity)),
+        )
+        .arg(
+            Arg::new("grpc-outgoing-overflow")
+                .long("grpc-outgoing-overflow")
+                .value_name("POLICY")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(OutgoingOverflowPolicy))
+                .help("What to do when the outgoing queue of a gRPC client is full: disconnect the client, or drop-oldest to drop its oldest queued notification, disconnecting it only if the queue holds responses only (default: disconnect)."),
+        )
+        .arg(
+            Arg::new("rpc-public")
+                .long("rpc-public")
+                .value_name("INTERFACE")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(RpcInterface))
+                .help("Restrict the RPC methods served on an interface (grpc, wrpc-borsh or wrpc-json) to the public ones. Can be repeated."),
+        )
+        .arg(
+            Arg::new("rpc-public-ops")
+                .long("rpc-public-ops")
+                .value_name("OPS")
+                .require_equals(true)
+                .help("Comma-separated list of the RPC methods served on public interfaces (default: all but the denied ones)."),
+        )
+        .arg(
+            Arg::new("rpc-public-deny-ops")
+                .long("rpc-public-deny-ops")
+                .value_name("OPS")
+                .require_equals(true)
+                .help("Comma-separated list of the RPC methods denied on public interfaces (default: Shutdown,AddPeer,Ban,Unban,ResolveFinalityConflict,SetMempoolPolicy,StartJob,GenerateBlocks)."),
+        )
         .arg(arg!(--"reset-db" "Reset database before starting node. It's needed when switching between subnetworks."))
         .arg(arg!(--"enable-unsynced-mining" "Allow the node to accept blocks from RPC while not synced (this flag is mainly used for testing)"))
+        .arg(arg!(--"enable-transaction-builder-rpc" "Allow RPC clients to have the node select UTXOs and build unsigned transactions for their addresses (requires --utxoindex)"))
         .arg(
             Arg::new("enable-mainnet-mining")
                 .long("enable-mainnet-mining")
@@ -310,7 +647,49 @@ pub fn cli() -> Command {
                 .hide(true)
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
+        .arg(
+            Arg::new("template-tx-ordering")
+                .long("template-tx-ordering")
+                .value_name("ORDERING")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(TemplateTxOrdering))
+                .help("Ordering of the block template transactions: selection, or deterministic for reproducible templates (by subnetwork, dependencies, decreasing feerate and id) (default: selection)."),
+        )
+        .arg(
+            Arg::new("dust-relay-fee")
+                .long("dust-relay-fee")
+                .value_name("SOMPI_PER_KG")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Fee rate, in sompi per kg of mass, defining outputs costing more to spend than a third of their value as dust (default: {DEFAULT_DUST_RELAY_TRANSACTION_FEE}).")),
+        )
+        .arg(
+            Arg::new("min-output-value")
+                .long("min-output-value")
+                .value_name("SOMPI")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Value below which any spendable transaction output is considered dust, whatever the dust relay fee (default: 0)."),
+        )
+        .arg(arg!(--datacarrier "Relay and mine transactions having zero-value provably unspendable data outputs (OP_RETURN followed by up to 80 bytes of pushed data)"))
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(
+            Arg::new("utxoindex-resync-rate")
+                .long("utxoindex-resync-rate")
+                .value_name("UTXOS_PER_SEC")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Max number of UTXOs committed per second while rebuilding the UTXO index, keeping the node responsive (default: 0 - unlimited)."),
+        )
+        .arg(
+            Arg::new("txindex-resync-rate")
+                .long("txindex-resync-rate")
+                .value_name("BLOCKS_PER_SEC")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Max number of chain blocks committed per second while resyncing the transaction index, keeping the node responsive (default: 0 - unlimited)."),
+        )
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -320,6 +699,21 @@ pub fn cli() -> Command {
 Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0 memory footprint as long as unused but to sub-optimal footprint if used.", 
 0, Tracker::MAX_ADDRESS_UPPER_BOUND, Tracker::DEFAULT_MAX_ADDRESSES)),
         )
+        .arg(
+            Arg::new("max-listener-addresses")
+                .long("max-listener-addresses")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Max number of addresses a single RPC client can subscribe to UTXO changed events for (default: 0 - unlimited)."),
+        )
+        .arg(
+            Arg::new("utxos-changed-window")
+                .long("utxos-changed-window")
+                .value_name("MILLISECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Window during which consecutive UTXO changed notifications are merged into one before being sent to the RPC clients, e.g. 100 (default: 0 - disabled)."),
+        )
         .arg(arg!(--testnet "Use the test network"))
         .arg(
             Arg::new("netsuffix")
@@ -331,9 +725,62 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         )
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
+        .arg(
+            Arg::new("simnet-coinbase-maturity")
+                .long("simnet-coinbase-maturity")
+                .value_name("BLOCKS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Overrides the coinbase maturity of the simulation test network, so tests can spend mined rewards after a few blocks (allowed only on simnet)."),
+        )
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(
+            Arg::new("cold-datadir")
+                .long("cold-datadir")
+                .value_name("DIR")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Directory of a secondary (cold) data store, typically on a cheaper volume, where block bodies deeper than --cold-store-depth are moved to."),
+        )
+        .arg(
+            Arg::new("cold-store-depth")
+                .long("cold-store-depth")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Blue score depth below the sink beyond which block bodies are moved to the cold data store (default: finality depth)."),
+        )
+        .arg(
+            Arg::new("compress-block-transactions")
+                .long("compress-block-transactions")
+                .value_name("LEVEL")
+                .require_equals(true)
+                .num_args(0..=1)
+                .default_missing_value("3")
+                .value_parser(clap::value_parser!(i32).range(1..=22))
+                .help("Compress the block transactions at rest with zstd and a dictionary trained on the first stored blocks, at the given level (default: 3)."),
+        )
+        .arg(
+            Arg::new("compress-acceptance-data")
+                .long("compress-acceptance-data")
+                .value_name("LEVEL")
+                .require_equals(true)
+                .num_args(0..=1)
+                .default_missing_value("3")
+                .value_parser(clap::value_parser!(i32).range(1..=22))
+                .help("Compress the block acceptance data at rest with zstd and a dictionary trained on the first stored blocks, at the given level (default: 3)."),
+        )
+        .arg(
+            Arg::new("secondary-datadir")
+                .long("secondary-datadir")
+                .value_name("DIR")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Run as a read-only secondary instance serving RPC over the databases of the primary node owning --appdir, keeping the secondary DB state in DIR."),
+        )
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
+        .subcommand(export::cli())
+        .subcommand(import::cli())
         .arg(
             Arg::new("user_agent_comments")
                 .long("uacomment")
@@ -351,6 +798,8 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Add a socket address(ip:port) to the list of local addresses we claim to listen on to peers"),
         )
         .arg(arg!(--"perf-metrics" "Enable performance metrics: cpu, memory, disk io usage"))
+        .arg(arg!(--"persist-counters" "Persist the cumulative processing and mempool counters across restarts, so metrics count since the datadir creation instead of the node start"))
+        .arg(arg!(--"chain-quality" "Sample the red rate, the inferred block propagation delay and the miner concentration of the selected chain every minute, keeping a day of samples queryable with the GetChainQuality RPC"))
         .arg(
             Arg::new("perf-metrics-interval-sec")
                 .long("perf-metrics-interval-sec")
@@ -369,6 +818,34 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("header-threads")
+                .long("header-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize).range(1..))
+                .help("Number of threads validating block headers (default: half the logical CPU cores). Lower values leave more room to the RPC service while syncing."),
+        )
+        .arg(
+            Arg::new("body-threads")
+                .long("body-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize).range(1..))
+                .help("Number of threads validating block bodies (default: the logical CPU cores)."),
+        )
+        .arg(
+            Arg::new("virtual-threads")
+                .long("virtual-threads")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize).range(1..))
+                .help("Number of threads resolving the virtual state and verifying its transactions (default: the logical CPU cores)."),
+        )
+        .arg(
+            Arg::new("min-hashrate-window-size")
+                .long("min-hashrate-window-size")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Lowest number of blocks a window must hold for EstimateNetworkHashesPerSecond to estimate over it (default: {} on mainnet, {} otherwise).", DEFAULT_MIN_HASHRATE_ESTIMATION_WINDOW_SIZE, MIN_DIFFICULTY_WINDOW_LEN)),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -413,9 +890,12 @@ impl Args {
             appdir: m.get_one::<String>("appdir").cloned().or(defaults.appdir),
             logdir: m.get_one::<String>("logdir").cloned().or(defaults.logdir),
             no_log_files: arg_match_unwrap_or::<bool>(&m, "nologfiles", defaults.no_log_files),
-            rpclisten: m.get_one::<ContextualNetAddress>("rpclisten").cloned().or(defaults.rpclisten),
+            access_log: arg_match_unwrap_or::<bool>(&m, "accesslog", defaults.access_log),
+            rpclisten: arg_match_many_unwrap_or::<GrpcListenAddress>(&m, "rpclisten", defaults.rpclisten),
+            rpclisten_unix: m.get_one::<String>("rpclisten-unix").cloned().or(defaults.rpclisten_unix),
             rpclisten_borsh: m.get_one::<WrpcNetAddress>("rpclisten-borsh").cloned().or(defaults.rpclisten_borsh),
             rpclisten_json: m.get_one::<WrpcNetAddress>("rpclisten-json").cloned().or(defaults.rpclisten_json),
+            rpclisten_http: m.get_one::<ContextualNetAddress>("rpclisten-http").cloned().or(defaults.rpclisten_http),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
@@ -426,28 +906,96 @@ impl Args {
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
+            rpc_max_clients_per_ip: arg_match_unwrap_or::<usize>(&m, "rpc-max-clients-per-ip", defaults.rpc_max_clients_per_ip),
+            rpc_trusted_ips: arg_match_many_unwrap_or::<IpAddr>(&m, "rpc-trusted-ip", defaults.rpc_trusted_ips),
+            rpc_tenants: m.get_one::<String>("rpctenants").cloned().or(defaults.rpc_tenants),
+            grpc_tls_cert: m.get_one::<String>("grpc-tls-cert").cloned().or(defaults.grpc_tls_cert),
+            grpc_tls_key: m.get_one::<String>("grpc-tls-key").cloned().or(defaults.grpc_tls_key),
+            grpc_tls_client_ca: m.get_one::<String>("grpc-tls-client-ca").cloned().or(defaults.grpc_tls_client_ca),
+            grpc_rate_limit: m.get_one::<u32>("grpc-rate-limit").cloned().or(defaults.grpc_rate_limit),
+            grpc_rate_limit_burst: m.get_one::<u32>("grpc-rate-limit-burst").cloned().or(defaults.grpc_rate_limit_burst),
+            grpc_compression: arg_match_many_unwrap_or::<GrpcCompression>(&m, "grpc-compression", defaults.grpc_compression),
+            grpc_request_timeout: arg_match_unwrap_or::<u64>(&m, "grpc-request-timeout", defaults.grpc_request_timeout),
+            grpc_method_timeout: arg_match_many_unwrap_or::<String>(&m, "grpc-method-timeout", defaults.grpc_method_timeout),
+            grpc_tcp_keepalive: arg_match_unwrap_or::<u64>(&m, "grpc-tcp-keepalive", defaults.grpc_tcp_keepalive),
+            grpc_http2_keepalive_interval: arg_match_unwrap_or::<u64>(
+                &m,
+                "grpc-http2-keepalive-interval",
+                defaults.grpc_http2_keepalive_interval,
+            ),
+            grpc_http2_keepalive_timeout: arg_match_unwrap_or::<u64>(
+                &m,
+                "grpc-http2-keepalive-timeout",
+                defaults.grpc_http2_keepalive_timeout,
+            ),
+            grpc_max_concurrent_streams: arg_match_unwrap_or::<u32>(
+                &m,
+                "grpc-max-concurrent-streams",
+                defaults.grpc_max_concurrent_streams,
+            ),
+            grpc_outgoing_capacity: arg_match_unwrap_or::<usize>(&m, "grpc-outgoing-capacity", defaults.grpc_outgoing_capacity),
+            grpc_outgoing_overflow: arg_match_unwrap_or::<OutgoingOverflowPolicy>(
+                &m,
+                "grpc-outgoing-overflow",
+                defaults.grpc_outgoing_overflow,
+            ),
+            rpc_public: arg_match_many_unwrap_or::<RpcInterface>(&m, "rpc-public", defaults.rpc_public),
+            rpc_public_ops: m.get_one::<String>("rpc-public-ops").cloned().or(defaults.rpc_public_ops),
+            rpc_public_deny_ops: m.get_one::<String>("rpc-public-deny-ops").cloned().or(defaults.rpc_public_deny_ops),
             max_tracked_addresses: arg_match_unwrap_or::<usize>(&m, "max-tracked-addresses", defaults.max_tracked_addresses),
+            max_listener_addresses: arg_match_unwrap_or::<usize>(&m, "max-listener-addresses", defaults.max_listener_addresses),
+            utxos_changed_window: m.get_one::<u64>("utxos-changed-window").cloned().or(defaults.utxos_changed_window),
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
+            enable_transaction_builder_rpc: arg_match_unwrap_or::<bool>(
+                &m,
+                "enable-transaction-builder-rpc",
+                defaults.enable_transaction_builder_rpc,
+            ),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            utxoindex_resync_rate: arg_match_unwrap_or::<u64>(&m, "utxoindex-resync-rate", defaults.utxoindex_resync_rate),
+            txindex_resync_rate: arg_match_unwrap_or::<u64>(&m, "txindex-resync-rate", defaults.txindex_resync_rate),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
+            simnet_coinbase_maturity: m.get_one::<u64>("simnet-coinbase-maturity").cloned().or(defaults.simnet_coinbase_maturity),
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            cold_datadir: m.get_one::<String>("cold-datadir").cloned().or(defaults.cold_datadir),
+            cold_store_depth: m.get_one::<u64>("cold-store-depth").cloned().or(defaults.cold_store_depth),
+            compress_block_transactions: m
+                .get_one::<i32>("compress-block-transactions")
+                .cloned()
+                .or(defaults.compress_block_transactions),
+            compress_acceptance_data: m.get_one::<i32>("compress-acceptance-data").cloned().or(defaults.compress_acceptance_data),
+            secondary_datadir: m.get_one::<String>("secondary-datadir").cloned().or(defaults.secondary_datadir),
+            export: ExportArgs::parse(&m),
+            build_info: m.get_flag("build-info"),
+            import: ImportArgs::parse(&m),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
+            persist_counters: arg_match_unwrap_or::<bool>(&m, "persist-counters", defaults.persist_counters),
+            chain_quality: arg_match_unwrap_or::<bool>(&m, "chain-quality", defaults.chain_quality),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            template_tx_ordering: arg_match_unwrap_or::<TemplateTxOrdering>(&m, "template-tx-ordering", defaults.template_tx_ordering),
+            dust_relay_fee: arg_match_unwrap_or::<u64>(&m, "dust-relay-fee", defaults.dust_relay_fee),
+            min_output_value: arg_match_unwrap_or::<u64>(&m, "min-output-value", defaults.min_output_value),
+            datacarrier: arg_match_unwrap_or::<bool>(&m, "datacarrier", defaults.datacarrier),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            header_threads: m.get_one::<usize>("header-threads").cloned().or(defaults.header_threads),
+            body_threads: m.get_one::<usize>("body-threads").cloned().or(defaults.body_threads),
+            virtual_threads: m.get_one::<usize>("virtual-threads").cloned().or(defaults.virtual_threads),
+            min_hashrate_window_size: m.get_one::<usize>("min-hashrate-window-size").cloned().or(defaults.min_hashrate_window_size),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -549,6 +1097,7 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 5)
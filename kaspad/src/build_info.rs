@@ -0,0 +1,21 @@
+use kaspa_core::kaspad_env::version;
+use kaspa_p2p_flows::flow_context::SUPPORTED_PROTOCOL_VERSIONS;
+use kaspa_rpc_core::{
+    api::ops::{RpcApiOps, RPC_API_VERSION},
+    RpcBuildInfo,
+};
+use kaspa_rpc_service::access::op_name;
+
+/// Returns the description of this node build, as reported by `--build-info` and `GetInfo`
+pub fn build_info() -> RpcBuildInfo {
+    let features = [("heap", cfg!(feature = "heap")), ("devnet-prealloc", cfg!(feature = "devnet-prealloc"))];
+    RpcBuildInfo {
+        version: version().to_string(),
+        git_hash: option_env!("KASPAD_GIT_HASH").map(str::to_string),
+        build_date: option_env!("KASPAD_BUILD_DATE").map(str::to_string),
+        features: features.into_iter().filter(|(_, enabled)| *enabled).map(|(name, _)| name.to_string()).collect(),
+        protocol_versions: SUPPORTED_PROTOCOL_VERSIONS.to_vec(),
+        rpc_api_version: RPC_API_VERSION,
+        capabilities: RpcApiOps::list().iter().map(op_name).collect(),
+    }
+}
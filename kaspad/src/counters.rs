@@ -0,0 +1,174 @@
+use kaspa_consensus_core::api::counters::{ProcessingCounters, ProcessingCountersSnapshot};
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    time::unix_now,
+    trace, warn,
+};
+use kaspa_database::{
+    prelude::{CachedDbItem, DirectDbWriter, StoreResult, StoreResultExtensions, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_mining::{MempoolCountersSnapshot, MiningCounters};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+
+const SERVICE: &str = "counters-persistence";
+
+/// Interval between two saves of the counters, limiting the loss in case of an unclean shutdown
+const PERSIST_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Cumulative node counters as persisted in the meta database
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PersistedCounters {
+    /// Unix time in milliseconds at which the counters started accumulating
+    pub since: u64,
+    pub processing: ProcessingCountersSnapshot,
+    /// Mempool counters, whose elapsed time is the total uptime of the node since `since`
+    pub mining: MempoolCountersSnapshot,
+}
+
+#[derive(Clone)]
+pub struct DbCountersStore {
+    db: Arc<DB>,
+    access: CachedDbItem<PersistedCounters>,
+}
+
+impl DbCountersStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::NodeCounters.into()) }
+    }
+
+    pub fn get(&self) -> StoreResult<PersistedCounters> {
+        self.access.read()
+    }
+
+    pub fn set(&mut self, counters: &PersistedCounters) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), counters)
+    }
+}
+
+/// Restores the processing and mining counters persisted by former runs of the node and saves them
+/// periodically and on shutdown, so the counts reported by `GetMetrics` cover the datadir lifetime
+pub struct CountersPersistence {
+    store: Mutex<DbCountersStore>,
+    processing_counters: Arc<ProcessingCounters>,
+    mining_counters: Arc<MiningCounters>,
+    since: u64,
+    /// Uptime accumulated by former runs of the node
+    previous_uptime: Duration,
+    tick_service: Arc<TickService>,
+}
+
+impl CountersPersistence {
+    /// Creates the service and restores the persisted counters, if any.
+    /// Must be called before the counters start being updated by the node.
+    pub fn new(
+        db: Arc<DB>,
+        processing_counters: Arc<ProcessingCounters>,
+        mining_counters: Arc<MiningCounters>,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        let store = DbCountersStore::new(db);
+        let (since, previous_uptime) = match store.get().unwrap_option() {
+            Some(persisted) => {
+                processing_counters.restore(&persisted.processing);
+                mining_counters.restore(&persisted.mining);
+                info!("Restored the node counters accumulated since unix time {} ms", persisted.since);
+                (persisted.since, persisted.mining.elapsed_time)
+            }
+            None => (unix_now(), Duration::ZERO),
+        };
+        Self { store: Mutex::new(store), processing_counters, mining_counters, since, previous_uptime, tick_service }
+    }
+
+    fn persist(&self) {
+        let mut mining = self.mining_counters.snapshot();
+        mining.elapsed_time += self.previous_uptime;
+        let counters = PersistedCounters { since: self.since, processing: self.processing_counters.snapshot(), mining };
+        if let Err(err) = self.store.lock().set(&counters) {
+            warn!("Failed to persist the node counters: {}", err);
+        }
+    }
+
+    async fn worker(&self) {
+        loop {
+            let reason = self.tick_service.tick(PERSIST_INTERVAL).await;
+            self.persist();
+            if let TickReason::Shutdown = reason {
+                break;
+            }
+        }
+        trace!("{} exiting", SERVICE);
+    }
+}
+
+impl AsyncService for CountersPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use std::sync::atomic::Ordering;
+
+    fn persistence(db: &Arc<DB>) -> CountersPersistence {
+        CountersPersistence::new(db.clone(), Default::default(), Default::default(), Arc::new(TickService::new()))
+    }
+
+    #[test]
+    fn test_counters_persistence() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+
+        // A fresh datadir starts accumulating from now
+        let before = unix_now();
+        let first_run = persistence(&db);
+        assert!(first_run.since >= before);
+        assert_eq!(first_run.previous_uptime, Duration::ZERO);
+        first_run.processing_counters.blocks_submitted.store(5, Ordering::Relaxed);
+        first_run.mining_counters.tx_accepted_counts.store(7, Ordering::Relaxed);
+        first_run.persist();
+        let persisted = DbCountersStore::new(db.clone()).get().unwrap();
+        assert_eq!(persisted.since, first_run.since);
+
+        // The next run restores the counts, the start time and the uptime of the former run
+        let second_run = persistence(&db);
+        assert_eq!(second_run.since, first_run.since);
+        assert_eq!(second_run.previous_uptime, persisted.mining.elapsed_time);
+        assert_eq!(second_run.processing_counters.snapshot().blocks_submitted, 5);
+        assert_eq!(second_run.mining_counters.snapshot().tx_accepted_counts, 7);
+
+        // Counts and uptime keep accumulating over runs
+        second_run.processing_counters.blocks_submitted.fetch_add(1, Ordering::Relaxed);
+        second_run.persist();
+        let persisted_again = DbCountersStore::new(db.clone()).get().unwrap();
+        assert_eq!(persisted_again.processing.blocks_submitted, 6);
+        assert_eq!(persisted_again.mining.tx_accepted_counts, 7);
+        assert!(persisted_again.mining.elapsed_time >= persisted.mining.elapsed_time);
+    }
+}
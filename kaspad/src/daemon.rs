@@ -1,17 +1,40 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
 use kaspa_consensus_core::{
     config::ConfigBuilder,
     errors::config::{ConfigError, ConfigResult},
+    network::NetworkId,
 };
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
-use kaspa_core::{core::Core, info, trace};
+use kaspa_core::{core::Core, error, info, trace};
 use kaspa_core::{kaspad_env::version, task::tick::TickService};
-use kaspa_database::prelude::CachePolicy;
-use kaspa_grpc_server::service::GrpcService;
+use kaspa_database::{metrics::StorageMetricsProvider, prelude::CachePolicy};
+use kaspa_grpc_server::{
+    compression::GrpcCompressionConfig,
+    deadline::RequestDeadlines,
+    listen::GrpcBinding,
+    manager::IpConnectionLimit,
+    outgoing::OutgoingRouteConfig,
+    rate_limit::RateLimitConfig,
+    service::GrpcService,
+    tenant::{Tenants, TenantsConfig},
+    tls::GrpcTlsConfig,
+    transport::GrpcTransportConfig,
+};
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_rpc_service::{
+    access::{op_name, parse_ops, RpcAccessPolicies, RpcAccessPolicy, NODE_CONTROL_OPS},
+    chain_quality::ChainQualityMonitor,
+    health::HealthMonitor,
+    service::RpcCoreService,
+};
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
@@ -21,8 +44,9 @@ use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline:
 use kaspa_consensus::{
     consensus::factory::MultiConsensusManagementStore, model::stores::headers::DbHeadersStore, pipeline::monitor::ConsensusMonitor,
 };
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{ConsensusManager, ConsensusRefresher};
 use kaspa_core::task::runtime::AsyncRuntime;
+use kaspa_index_core::resync::ResyncThrottle;
 use kaspa_index_processor::service::IndexService;
 use kaspa_mining::{
     manager::{MiningManager, MiningManagerProxy},
@@ -31,7 +55,9 @@ use kaspa_mining::{
 };
 use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
+use kaspa_http_server::service::{HttpService, Options as HttpServerOptions};
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use kaspa_txindex::{api::TxIndexProxy, TxIndex};
 use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -44,16 +70,18 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-use crate::args::Args;
+use crate::{args::Args, build_info::build_info, counters::CountersPersistence};
 
-const DEFAULT_DATA_DIR: &str = "datadir";
-const CONSENSUS_DB: &str = "consensus";
+pub(crate) const DEFAULT_DATA_DIR: &str = "datadir";
+pub(crate) const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
-const META_DB: &str = "meta";
-const META_DB_FILE_LIMIT: i32 = 5;
+const TXINDEX_DB: &str = "txindex";
+pub(crate) const META_DB: &str = "meta";
+pub(crate) const META_DB_FILE_LIMIT: i32 = 5;
+const SECONDARY_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
 const DEFAULT_LOG_DIR: &str = "logs";
 
-fn get_home_dir() -> PathBuf {
+pub(crate) fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
     return dirs::data_local_dir().unwrap();
     #[cfg(not(target_os = "windows"))]
@@ -92,9 +120,31 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.ram_scale > 10.0 {
         return Err(ConfigError::RamScaleTooHigh);
     }
+    if args.simnet_coinbase_maturity.is_some() && !args.simnet {
+        return Err(ConfigError::CoinbaseMaturityOverrideOnNonSimnet);
+    }
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.secondary_datadir.is_some() {
+        // A secondary instance cannot write to the databases of the primary node
+        if args.utxoindex {
+            return Err(ConfigError::SecondaryInstanceConflict("utxoindex"));
+        }
+        if args.txindex {
+            return Err(ConfigError::SecondaryInstanceConflict("txindex"));
+        }
+        if args.reset_db {
+            return Err(ConfigError::SecondaryInstanceConflict("reset-db"));
+        }
+    }
+    // Config files bypass the requirements declared on the command line arguments
+    if args.grpc_tls_cert.is_some() != args.grpc_tls_key.is_some() {
+        return Err(ConfigError::MissingGrpcTlsCertOrKey);
+    }
+    if args.grpc_tls_client_ca.is_some() && args.grpc_tls_cert.is_none() {
+        return Err(ConfigError::GrpcClientCaWithoutTls);
+    }
     Ok(())
 }
 
@@ -144,6 +194,71 @@ pub fn get_app_dir_from_args(args: &Args) -> PathBuf {
     }
 }
 
+/// Get the directory holding the data of `network` under `root_dir`. Every network gets
+/// its own subdirectory named after it (such as `mainnet`, `testnet-10` or `simnet`).
+pub fn get_network_dir(root_dir: &Path, network: NetworkId) -> PathBuf {
+    root_dir.join(network.to_string())
+}
+
+/// Moves the directory of `network` under `root_dir` from the legacy `kaspa-<network>` layout
+/// to its per-network location. Returns the legacy directory if it was moved.
+fn migrate_legacy_network_dir(root_dir: &Path, network: NetworkId) -> io::Result<Option<PathBuf>> {
+    let legacy_dir = root_dir.join(network.to_prefixed());
+    if !legacy_dir.is_dir() {
+        return Ok(None);
+    }
+    let network_dir = get_network_dir(root_dir, network);
+    if network_dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "both the legacy directory {} and the directory {} exist, remove or rename one of them",
+                legacy_dir.display(),
+                network_dir.display()
+            ),
+        ));
+    }
+    fs::rename(&legacy_dir, &network_dir)?;
+    Ok(Some(legacy_dir))
+}
+
+/// Get the directory holding the data of `network` under `root_dir` without migrating anything, falling back
+/// to the legacy `kaspa-<network>` directory if it is the only one existing. Meant for reading the directories
+/// of a node which may be running.
+pub fn resolve_network_dir(root_dir: &Path, network: NetworkId) -> PathBuf {
+    let network_dir = get_network_dir(root_dir, network);
+    let legacy_dir = root_dir.join(network.to_prefixed());
+    if !network_dir.exists() && legacy_dir.is_dir() {
+        legacy_dir
+    } else {
+        network_dir
+    }
+}
+
+/// Moves the application and cold directories of the network configured by `args` from the legacy
+/// `kaspa-<network>` layout to the per-network layout. Returns the pairs of legacy and new directories
+/// that were moved.
+///
+/// A read-only secondary instance migrates nothing, its application and cold directories being the live
+/// ones of the primary node.
+pub fn migrate_legacy_layout(args: &Args) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    if args.secondary_datadir.is_some() {
+        return Ok(vec![]);
+    }
+    let cold_dir = args.cold_datadir.as_ref().map(|dir| PathBuf::from(dir.replace('~', get_home_dir().as_path().to_str().unwrap())));
+    migrate_legacy_dirs([get_app_dir_from_args(args)].into_iter().chain(cold_dir), args.network())
+}
+
+fn migrate_legacy_dirs(root_dirs: impl IntoIterator<Item = PathBuf>, network: NetworkId) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut moved = vec![];
+    for root_dir in root_dirs {
+        if let Some(legacy_dir) = migrate_legacy_network_dir(&root_dir, network)? {
+            moved.push((legacy_dir, get_network_dir(&root_dir, network)));
+        }
+    }
+    Ok(moved)
+}
+
 /// Get the log directory from the supplied [`Args`].
 pub fn get_log_dir(args: &Args) -> Option<String> {
     let network = args.network();
@@ -151,23 +266,38 @@ pub fn get_log_dir(args: &Args) -> Option<String> {
 
     // Logs directory is usually under the application directory, unless otherwise specified
     let log_dir = args.logdir.clone().unwrap_or_default().replace('~', get_home_dir().as_path().to_str().unwrap());
-    let log_dir = if log_dir.is_empty() { app_dir.join(network.to_prefixed()).join(DEFAULT_LOG_DIR) } else { PathBuf::from(log_dir) };
+    let log_dir = if log_dir.is_empty() { get_network_dir(&app_dir, network).join(DEFAULT_LOG_DIR) } else { PathBuf::from(log_dir) };
     let log_dir = if args.no_log_files { None } else { log_dir.to_str().map(String::from) };
     log_dir
 }
 
 impl Runtime {
-    pub fn from_args(args: &Args) -> Self {
+    pub fn from_args(args: &Args) -> io::Result<Self> {
+        // Move the directories of a legacy layout before the logger creates the logs directory
+        let migrated_dirs = migrate_legacy_layout(args);
+
         let log_dir = get_log_dir(args);
 
         // Initialize the logger
-        kaspa_core::log::init_logger(log_dir.as_deref(), &args.log_level);
+        kaspa_core::log::init_logger_with_access_log(log_dir.as_deref(), &args.log_level, args.access_log);
+
+        match migrated_dirs {
+            Ok(migrated_dirs) => {
+                for (legacy_dir, network_dir) in migrated_dirs {
+                    info!("Moved the legacy directory {} to {}", legacy_dir.display(), network_dir.display());
+                }
+            }
+            Err(err) => {
+                error!("Failed to migrate the legacy directory layout: {}", err);
+                return Err(err);
+            }
+        }
 
         // Configure the panic behavior
         // As we log the panic, we want to set it up after the logger
         kaspa_core::panic::configure_panic();
 
-        Self { log_dir: log_dir.map(|log_dir| log_dir.to_owned()) }
+        Ok(Self { log_dir: log_dir.map(|log_dir| log_dir.to_owned()) })
     }
 }
 
@@ -177,21 +307,23 @@ impl Runtime {
 /// call [`create_core_with_runtime`].
 ///
 /// Usage semantics:
-/// `let (core, rpc_core_service) = create_core(args);`
+/// `let (core, rpc_core_service) = create_core(args)?;`
 ///
 /// The instance of the [`RpcCoreService`] needs to be released
 /// (dropped) before the `Core` is shut down.
 ///
-pub fn create_core(args: Args, fd_total_budget: i32) -> (Arc<Core>, Arc<RpcCoreService>) {
-    let rt = Runtime::from_args(&args);
-    create_core_with_runtime(&rt, &args, fd_total_budget)
+/// Fails if the directories of the node cannot be prepared, the error being logged.
+///
+pub fn create_core(args: Args, fd_total_budget: i32) -> io::Result<(Arc<Core>, Arc<RpcCoreService>)> {
+    let rt = Runtime::from_args(&args)?;
+    Ok(create_core_with_runtime(&rt, &args, fd_total_budget))
 }
 
 /// Create [`Core`] instance with supplied [`Args`] and [`Runtime`].
 ///
 /// Usage semantics:
 /// ```ignore
-/// let Runtime = Runtime::from_args(&args); // or create your own
+/// let Runtime = Runtime::from_args(&args)?; // or create your own
 /// let (core, rpc_core_service) = create_core(&runtime, &args);
 /// ```
 ///
@@ -208,6 +340,13 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -224,7 +363,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     // TODO: Validate `config` forms a valid set of properties
 
     let app_dir = get_app_dir_from_args(args);
-    let db_dir = app_dir.join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    let db_dir = get_network_dir(&app_dir, network).join(DEFAULT_DATA_DIR);
 
     // Print package name and version
     info!("{} v{}", env!("CARGO_PKG_NAME"), version());
@@ -243,7 +382,25 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
+    let cold_consensus_db_dir = args.cold_datadir.as_ref().map(|dir| {
+        get_network_dir(&PathBuf::from(dir.replace('~', get_home_dir().as_path().to_str().unwrap())), network).join(CONSENSUS_DB)
+    });
+
+    // Root of the secondary instances of the databases, if running as a read-only secondary instance of a primary node
+    let secondary_db_dir = args.secondary_datadir.as_ref().map(|dir| {
+        get_network_dir(&PathBuf::from(dir.replace('~', get_home_dir().as_path().to_str().unwrap())), network).join(DEFAULT_DATA_DIR)
+    });
+    let is_secondary = secondary_db_dir.is_some();
+    if let Some(dir) = secondary_db_dir.as_ref() {
+        if !meta_db_dir.exists() {
+            println!("No primary node databases were found in {} to run a secondary instance against", db_dir.display());
+            exit(1);
+        }
+        info!("Secondary data directory {}", dir.display());
+        fs::create_dir_all(dir.as_path()).unwrap();
+    }
 
     let mut is_db_reset_needed = args.reset_db;
 
@@ -254,24 +411,44 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         get_user_approval_or_exit(msg, args.yes);
         info!("Deleting databases");
         fs::remove_dir_all(&db_dir).unwrap();
+        if let Some(dir) = cold_consensus_db_dir.as_ref().filter(|dir| dir.exists()) {
+            fs::remove_dir_all(dir).unwrap();
+        }
     }
 
     fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
     fs::create_dir_all(meta_db_dir.as_path()).unwrap();
+    if let Some(dir) = cold_consensus_db_dir.as_ref() {
+        info!("Cold consensus data directory {}", dir.display());
+        fs::create_dir_all(dir.as_path()).unwrap();
+    }
     if args.utxoindex {
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
-    let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
-        .with_db_path(meta_db_dir.clone())
-        .with_files_limit(META_DB_FILE_LIMIT)
-        .build()
-        .unwrap();
+    let mut meta_db = match secondary_db_dir.as_ref() {
+        Some(dir) => kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(meta_db_dir.clone())
+            .with_files_limit(META_DB_FILE_LIMIT)
+            .with_secondary_path(dir.join(META_DB))
+            .build()
+            .unwrap(),
+        None => kaspa_database::prelude::ConnBuilder::default()
+            .with_db_path(meta_db_dir.clone())
+            .with_files_limit(META_DB_FILE_LIMIT)
+            .build()
+            .unwrap(),
+    };
 
     // Reset Condition: Need to reset DB if we can't find genesis in current DB
-    if !is_db_reset_needed && (args.testnet || args.devnet || args.simnet) {
+    // (the databases of a secondary instance are owned and reset by the primary node)
+    if !is_secondary && !is_db_reset_needed && (args.testnet || args.devnet || args.simnet) {
         // Non-mainnet can be restarted, and when it does we need to reset the DB.
         // This will check if the current Genesis can be found the active consensus
         // DB (if one exists), and if not then ask to reset the DB.
@@ -304,7 +481,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
 
     // Reset Condition: Need to reset if we're upgrading from kaspad DB version
     // TEMP: upgrade from Alpha version or any version before this one
-    if !is_db_reset_needed
+    if !is_secondary
+        && !is_db_reset_needed
         && (meta_db.get_pinned(b"multi-consensus-metadata-key").is_ok_and(|r| r.is_some())
             || MultiConsensusManagementStore::new(meta_db.clone()).should_upgrade().unwrap())
     {
@@ -325,14 +503,23 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
 
         // Delete
         fs::remove_dir_all(db_dir.clone()).unwrap();
+        if let Some(dir) = cold_consensus_db_dir.as_ref() {
+            fs::remove_dir_all(dir).unwrap();
+        }
 
         // Recreate the empty folders
         fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
+        if let Some(dir) = cold_consensus_db_dir.as_ref() {
+            fs::create_dir_all(dir.as_path()).unwrap();
+        }
         fs::create_dir_all(meta_db_dir.as_path()).unwrap();
 
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -342,10 +529,37 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
             .unwrap();
     }
 
-    if !args.archival && MultiConsensusManagementStore::new(meta_db.clone()).is_archival_node().unwrap() {
+    if !is_secondary && !args.archival && MultiConsensusManagementStore::new(meta_db.clone()).is_archival_node().unwrap() {
         get_user_approval_or_exit("--archival is set to false although the node was previously archival. Proceeding may delete archived data. Do you confirm? (y/n)", args.yes);
     }
 
+    // Refuse to reuse a data directory created for another network
+    let network_bindings = config.network_bindings();
+    let mut management_store = MultiConsensusManagementStore::new(meta_db.clone());
+    match management_store.network_identity().unwrap() {
+        Some((network_id, genesis_hash))
+            if network_id != network_bindings.network_id || genesis_hash != network_bindings.genesis_hash =>
+        {
+            println!(
+                "The data directory {} was previously used with network {} (genesis {}) and cannot be used with network {} (genesis {}). \
+                Use a distinct --appdir or pass --reset-db to delete the existing databases.",
+                db_dir.display(),
+                network_id,
+                genesis_hash,
+                network_bindings.network_id,
+                network_bindings.genesis_hash
+            );
+            exit(1);
+        }
+        Some(_) => {}
+        None if !is_secondary => {
+            management_store.set_network_identity(network_bindings.network_id, network_bindings.genesis_hash).unwrap()
+        }
+        None => {}
+    }
+    drop(management_store);
+    info!("Network bindings: {}", network_bindings);
+
     let connect_peers = args.connect_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect::<Vec<_>>();
     let add_peers = args.add_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect();
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
@@ -353,7 +567,22 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
     let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
 
-    let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
+    let mut grpc_bindings = match args.rpclisten.is_empty() {
+        true => vec![GrpcBinding::new(ContextualNetAddress::loopback().normalize(config.default_rpc_port()), None)],
+        false => args.rpclisten.iter().map(|listen| listen.normalize(config.default_rpc_port())).collect::<Vec<_>>(),
+    };
+    if let Some(ref path) = args.rpclisten_unix {
+        if !cfg!(unix) {
+            println!("--rpclisten-unix is only supported on unix platforms");
+            exit(1);
+        }
+        grpc_bindings.push(GrpcBinding::unix(path.into(), None));
+    }
+    info!(
+        "Effective listen addresses: P2P {}, gRPC {}",
+        p2p_server_addr,
+        grpc_bindings.iter().map(|binding| binding.to_string()).collect::<Vec<_>>().join(", ")
+    );
 
     let core = Arc::new(Core::new());
 
@@ -362,7 +591,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let tick_service = Arc::new(TickService::new());
     let (notification_send, notification_recv) = unbounded();
     let max_tracked_addresses = if args.utxoindex && args.max_tracked_addresses > 0 { Some(args.max_tracked_addresses) } else { None };
-    let subscription_context = SubscriptionContext::with_options(max_tracked_addresses);
+    let max_listener_addresses = if args.max_listener_addresses > 0 { Some(args.max_listener_addresses) } else { None };
+    let subscription_context = SubscriptionContext::with_options(max_tracked_addresses, max_listener_addresses);
     let notification_root = Arc::new(ConsensusNotificationRoot::with_context(notification_send, subscription_context.clone()));
     let processing_counters = Arc::new(ProcessingCounters::default());
     let mining_counters = Arc::new(MiningCounters::default());
@@ -371,21 +601,36 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let tx_script_cache_counters = Arc::new(TxScriptCacheCounters::default());
     let p2p_tower_counters = Arc::new(TowerConnectionCounters::default());
     let grpc_tower_counters = Arc::new(TowerConnectionCounters::default());
+    let counters_persistence = (args.persist_counters && !is_secondary).then(|| {
+        Arc::new(CountersPersistence::new(meta_db.clone(), processing_counters.clone(), mining_counters.clone(), tick_service.clone()))
+    });
+
+    // Storage statistics of the node databases, reported by the GetMetrics RPC
+    let storage_metrics =
+        Arc::new(StorageMetricsProvider::new([Some(db_dir.clone()), cold_consensus_db_dir.clone()].into_iter().flatten().collect()));
+    storage_metrics.register(META_DB, &meta_db);
 
     // Use `num_cpus` background threads for the consensus database as recommended by rocksdb
     let consensus_db_parallelism = num_cpus::get();
-    let consensus_factory = Arc::new(ConsensusFactory::new(
-        meta_db.clone(),
-        &config,
-        consensus_db_dir,
-        consensus_db_parallelism,
-        notification_root.clone(),
-        processing_counters.clone(),
-        tx_script_cache_counters.clone(),
-        fd_remaining,
-    ));
+    let consensus_factory = Arc::new(
+        ConsensusFactory::new(
+            meta_db.clone(),
+            &config,
+            consensus_db_dir,
+            cold_consensus_db_dir,
+            secondary_db_dir.as_ref().map(|dir| dir.join(CONSENSUS_DB)),
+            consensus_db_parallelism,
+            notification_root.clone(),
+            processing_counters.clone(),
+            tx_script_cache_counters.clone(),
+            fd_remaining,
+        )
+        .with_storage_metrics(storage_metrics.clone()),
+    );
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
+    let consensus_refresher = is_secondary
+        .then(|| Arc::new(ConsensusRefresher::new(consensus_manager.clone(), tick_service.clone(), SECONDARY_REFRESH_INTERVAL)));
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
         .with_fetch_interval(Duration::from_secs(args.perf_metrics_interval_sec))
@@ -403,19 +648,39 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     };
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            storage_metrics.register(UTXOINDEX_DB, &utxoindex_db);
+            let resync_throttle = ResyncThrottle::new(args.utxoindex_resync_rate);
+            UtxoIndexProxy::new(UtxoIndex::with_resync_throttle(consensus_manager.clone(), utxoindex_db, resync_throttle).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            storage_metrics.register(TXINDEX_DB, &txindex_db);
+            let resync_throttle = ResyncThrottle::new(args.txindex_resync_rate);
+            TxIndexProxy::new(TxIndex::with_resync_throttle(consensus_manager.clone(), txindex_db, resync_throttle).unwrap())
+        });
+        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex));
         Some(index_service)
     } else {
         None
     };
+    let health_monitor = Arc::new(
+        HealthMonitor::new(consensus_manager.clone(), config.clone(), tick_service.clone())
+            .with_indexes(index_service.as_ref().and_then(|x| x.utxoindex()), index_service.as_ref().and_then(|x| x.txindex())),
+    );
+    let chain_quality_monitor = (args.chain_quality && !is_secondary)
+        .then(|| Arc::new(ChainQualityMonitor::new(consensus_manager.clone(), meta_db.clone(), tick_service.clone())));
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
@@ -425,6 +690,8 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         false,
         config.max_block_mass,
         config.ram_scale,
+        config.template_tx_ordering,
+        args.dust_policy(),
         config.block_template_cache_lifetime,
         mining_counters,
     )));
@@ -449,32 +716,100 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         p2p_tower_counters.clone(),
     ));
 
-    let rpc_core_service = Arc::new(RpcCoreService::new(
-        consensus_manager.clone(),
-        notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
-        mining_manager,
-        flow_context,
-        subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
-        config.clone(),
-        core.clone(),
-        processing_counters,
-        wrpc_borsh_counters.clone(),
-        wrpc_json_counters.clone(),
-        perf_monitor.clone(),
-        p2p_tower_counters.clone(),
-        grpc_tower_counters.clone(),
-    ));
+    let rpc_access_policies = {
+        let parse = |ops: &String| {
+            parse_ops(ops).unwrap_or_else(|err| {
+                println!("invalid list of RPC methods {ops}: {err}");
+                exit(1);
+            })
+        };
+        let allowed = args.rpc_public_ops.as_ref().map(parse);
+        let denied = args.rpc_public_deny_ops.as_ref().map(parse).unwrap_or_else(|| NODE_CONTROL_OPS.iter().map(op_name).collect());
+        RpcAccessPolicies::new(args.rpc_public.iter().copied(), RpcAccessPolicy::new(allowed, denied))
+    };
+    for interface in args.rpc_public.iter() {
+        info!("RPC interface {interface} only serves public methods");
+    }
+
+    let rpc_core_service = Arc::new(
+        RpcCoreService::new(
+            consensus_manager.clone(),
+            notify_service.notifier(),
+            index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
+            mining_manager,
+            flow_context,
+            subscription_context,
+            index_service.as_ref().and_then(|x| x.utxoindex()),
+            index_service.as_ref().and_then(|x| x.txindex()),
+            config.clone(),
+            core.clone(),
+            processing_counters,
+            wrpc_borsh_counters.clone(),
+            wrpc_json_counters.clone(),
+            perf_monitor.clone(),
+            p2p_tower_counters.clone(),
+            grpc_tower_counters.clone(),
+            health_monitor.clone(),
+            rpc_access_policies,
+            storage_metrics,
+            build_info(),
+        )
+        .with_chain_quality_monitor(chain_quality_monitor.clone()),
+    );
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
+        let grpc_tenants = args.rpc_tenants.as_ref().map(|path| {
+            let config = fs::read_to_string(path).and_then(|config| {
+                toml::from_str::<TenantsConfig>(&config).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+            });
+            match config {
+                Ok(config) => Arc::new(Tenants::new(config)),
+                Err(err) => {
+                    println!("failed loading the RPC tenants file {path}: {err}");
+                    exit(1);
+                }
+            }
+        });
+        let grpc_tls = args.grpc_tls_cert.as_ref().zip(args.grpc_tls_key.as_ref()).map(|(cert, key)| {
+            match GrpcTlsConfig::load(cert, key, args.grpc_tls_client_ca.as_ref()) {
+                Ok(tls) => tls,
+                Err(err) => {
+                    println!("failed loading the gRPC TLS files: {err}");
+                    exit(1);
+                }
+            }
+        });
+        let grpc_compression = match args.grpc_compression.is_empty() {
+            true => Default::default(),
+            false => GrpcCompressionConfig::new(&args.grpc_compression).unwrap_or_else(|err| {
+                println!("{err}");
+                exit(1);
+            }),
+        };
+        let grpc_deadlines = RequestDeadlines::parse(args.grpc_request_timeout, &args.grpc_method_timeout).unwrap_or_else(|err| {
+            println!("{err}");
+            exit(1);
+        });
         Some(Arc::new(GrpcService::new(
-            grpc_server_addr,
+            grpc_bindings,
             config,
             rpc_core_service.clone(),
             args.rpc_max_clients,
+            IpConnectionLimit::new(args.rpc_max_clients_per_ip, args.rpc_trusted_ips.iter().copied()),
             grpc_service_broadcasters,
             grpc_tower_counters,
+            grpc_tenants,
+            grpc_tls,
+            args.grpc_rate_limit.map(|rate| RateLimitConfig::new(rate, args.grpc_rate_limit_burst)),
+            grpc_deadlines,
+            grpc_compression,
+            GrpcTransportConfig::from_secs(
+                args.grpc_tcp_keepalive,
+                args.grpc_http2_keepalive_interval,
+                args.grpc_http2_keepalive_timeout,
+                args.grpc_max_concurrent_streams,
+            ),
+            OutgoingRouteConfig::new(args.grpc_outgoing_capacity, args.grpc_outgoing_overflow),
         )))
     } else {
         None
@@ -494,10 +829,21 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     if let Some(grpc_service) = grpc_service {
         async_runtime.register(grpc_service)
     }
-    async_runtime.register(p2p_service);
+    match consensus_refresher {
+        // A secondary instance serves RPC only, over snapshots of the primary databases refreshed periodically
+        Some(consensus_refresher) => async_runtime.register(consensus_refresher),
+        None => async_runtime.register(p2p_service),
+    }
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    async_runtime.register(health_monitor);
+    if let Some(chain_quality_monitor) = chain_quality_monitor {
+        async_runtime.register(chain_quality_monitor);
+    }
     async_runtime.register(perf_monitor);
+    if let Some(counters_persistence) = counters_persistence {
+        async_runtime.register(counters_persistence);
+    }
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
     [
@@ -522,9 +868,109 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     })
     .for_each(|server| async_runtime.register(server));
 
+    // Register the HTTP gateway if enabled by command line arguments
+    if let Some(listen_address) = args.rpclisten_http {
+        let http_service = HttpService::new(
+            rpc_core_service.clone(),
+            HttpServerOptions { listen_address: listen_address.normalize(network.network_type.default_http_rpc_port()).to_string() },
+        );
+        async_runtime.register(Arc::new(http_service));
+    }
+
     // Consensus must start first in order to init genesis in stores
     core.bind(consensus_manager);
     core.bind(async_runtime);
 
     (core, rpc_core_service)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(app_dir: &Path) -> Args {
+        Args { appdir: Some(app_dir.to_str().unwrap().to_owned()), ..Default::default() }
+    }
+
+    #[test]
+    fn test_migrate_legacy_layout() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let cold_dir = tempfile::tempdir().unwrap();
+        let args = Args { cold_datadir: Some(cold_dir.path().to_str().unwrap().to_owned()), ..args(app_dir.path()) };
+        let network = args.network();
+        for root_dir in [app_dir.path(), cold_dir.path()] {
+            fs::create_dir_all(root_dir.join(network.to_prefixed()).join(DEFAULT_DATA_DIR)).unwrap();
+        }
+
+        let moved = migrate_legacy_layout(&args).unwrap();
+        assert_eq!(moved.len(), 2);
+        for root_dir in [app_dir.path(), cold_dir.path()] {
+            assert!(!root_dir.join(network.to_prefixed()).exists());
+            assert!(get_network_dir(root_dir, network).join(DEFAULT_DATA_DIR).is_dir());
+        }
+
+        // Once migrated, nothing is left to move
+        assert!(migrate_legacy_layout(&args).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_legacy_layout_conflict() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let args = args(app_dir.path());
+        let network = args.network();
+        let legacy_dir = app_dir.path().join(network.to_prefixed());
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::create_dir_all(get_network_dir(app_dir.path(), network)).unwrap();
+
+        let err = migrate_legacy_layout(&args).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(legacy_dir.is_dir());
+    }
+
+    #[test]
+    fn test_migrate_legacy_layout_secondary() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let secondary_dir = tempfile::tempdir().unwrap();
+        let args = Args { secondary_datadir: Some(secondary_dir.path().to_str().unwrap().to_owned()), ..args(app_dir.path()) };
+        let network = args.network();
+        for root_dir in [app_dir.path(), secondary_dir.path()] {
+            fs::create_dir_all(root_dir.join(network.to_prefixed())).unwrap();
+        }
+
+        // The directories of the primary node are live and the secondary one is not the node's own appdir
+        assert!(migrate_legacy_layout(&args).unwrap().is_empty());
+        for root_dir in [app_dir.path(), secondary_dir.path()] {
+            assert!(root_dir.join(network.to_prefixed()).is_dir());
+            assert!(!get_network_dir(root_dir, network).exists());
+        }
+        // Readers still find the legacy directory of the primary node
+        assert_eq!(resolve_network_dir(app_dir.path(), network), app_dir.path().join(network.to_prefixed()));
+    }
+
+    #[test]
+    fn test_validate_grpc_tls_args() {
+        let path = || Some("server.pem".to_owned());
+        assert!(validate_args(&Args { grpc_tls_cert: path(), grpc_tls_key: path(), ..Default::default() }).is_ok());
+        assert!(validate_args(&Args {
+            grpc_tls_cert: path(),
+            grpc_tls_key: path(),
+            grpc_tls_client_ca: path(),
+            ..Default::default()
+        })
+        .is_ok());
+
+        // A certificate is useless without its key and vice versa
+        assert!(matches!(
+            validate_args(&Args { grpc_tls_cert: path(), ..Default::default() }),
+            Err(ConfigError::MissingGrpcTlsCertOrKey)
+        ));
+        assert!(matches!(
+            validate_args(&Args { grpc_tls_key: path(), ..Default::default() }),
+            Err(ConfigError::MissingGrpcTlsCertOrKey)
+        ));
+        assert!(matches!(
+            validate_args(&Args { grpc_tls_client_ca: path(), ..Default::default() }),
+            Err(ConfigError::GrpcClientCaWithoutTls)
+        ));
+    }
+}
@@ -0,0 +1,727 @@
+//! The `kaspad export` subcommand: walks the selected chain of the node databases and writes blocks, transactions
+//! or acceptance records to CSV or Parquet files for offline analytics, or full blocks to a block archive which
+//! can later be ingested by `kaspad import-blocks`.
+//!
+//! The databases are opened as a read-only secondary instance, so an export can run against the data
+//! directory of a live node without interfering with it.
+
+use crate::{
+    archive::{ArchiveError, BlockArchiveWriter},
+    args::Args,
+    daemon::{get_app_dir_from_args, get_home_dir, resolve_network_dir, CONSENSUS_DB, DEFAULT_DATA_DIR, META_DB, META_DB_FILE_LIMIT},
+};
+use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use async_channel::unbounded;
+use clap::{Arg, ArgMatches, Command};
+use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
+use kaspa_consensus_core::{api::ConsensusApi, config::ConfigBuilder, errors::consensus::ConsensusError, network::NetworkId};
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensusmanager::ConsensusFactory as _;
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_txscript::caches::TxScriptCacheCounters;
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Number of rows buffered before being written to the output
+const BATCH_SIZE: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    /// Full blocks of the DAG, in topological order, see [`crate::archive`]
+    Archive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportRecords {
+    /// One row per selected chain block
+    Blocks,
+    /// One row per transaction of a selected chain block
+    Transactions,
+    /// One row per transaction accepted by a selected chain block
+    Acceptance,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportArgs {
+    pub format: ExportFormat,
+    pub records: ExportRecords,
+    pub output: String,
+    pub from_blue_score: u64,
+    /// Defaults to the blue score of the sink
+    pub to_blue_score: Option<u64>,
+    /// Names of the exported columns, in output order. Defaults to all the columns of the record type
+    pub columns: Option<Vec<String>>,
+}
+
+impl ExportArgs {
+    fn from_matches(m: &ArgMatches) -> Self {
+        Self {
+            format: match m.get_one::<String>("format").map(String::as_str) {
+                Some("parquet") => ExportFormat::Parquet,
+                Some("archive") => ExportFormat::Archive,
+                _ => ExportFormat::Csv,
+            },
+            records: match m.get_one::<String>("records").map(String::as_str) {
+                Some("transactions") => ExportRecords::Transactions,
+                Some("acceptance") => ExportRecords::Acceptance,
+                _ => ExportRecords::Blocks,
+            },
+            output: m.get_one::<String>("output").cloned().unwrap(),
+            from_blue_score: m.get_one::<u64>("from-blue-score").cloned().unwrap_or_default(),
+            to_blue_score: m.get_one::<u64>("to-blue-score").cloned(),
+            columns: m.get_many::<String>("columns").map(|columns| columns.cloned().collect()),
+        }
+    }
+
+    /// Parses the export args if the `export` subcommand was used
+    pub fn parse(m: &ArgMatches) -> Option<Self> {
+        m.subcommand_matches(SUBCOMMAND).map(Self::from_matches)
+    }
+}
+
+pub const SUBCOMMAND: &str = "export";
+
+pub fn cli() -> Command {
+    Command::new(SUBCOMMAND)
+        .about("Export selected chain data to CSV or Parquet files for analytics, or blocks to a block archive, reading the node databases as a read-only secondary instance")
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .require_equals(true)
+                .value_parser(["csv", "parquet", "archive"])
+                .default_value("csv")
+                .help("Output file format. The archive format writes the full blocks merged by the selected chain blocks of the range, to be imported with `kaspad import-blocks`."),
+        )
+        .arg(
+            Arg::new("records")
+                .long("records")
+                .require_equals(true)
+                .value_parser(["blocks", "transactions", "acceptance"])
+                .default_value("blocks")
+                .help("Exported records: selected chain blocks, their transactions or the transactions they accept."),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FILE")
+                .require_equals(true)
+                .required(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Path of the output file."),
+        )
+        .arg(
+            Arg::new("from-blue-score")
+                .long("from-blue-score")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Lowest blue score of the exported chain blocks (default: the lowest available)."),
+        )
+        .arg(
+            Arg::new("to-blue-score")
+                .long("to-blue-score")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Highest blue score of the exported chain blocks (default: the blue score of the sink)."),
+        )
+        .arg(
+            Arg::new("columns")
+                .long("columns")
+                .require_equals(true)
+                .value_delimiter(',')
+                .value_parser(clap::value_parser!(String))
+                .help("Comma separated names of the exported columns, in output order (default: all the columns of the records)."),
+        )
+}
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("unknown column '{0}' for the exported records, available columns: {1}")]
+    UnknownColumn(String, String),
+
+    #[error("invalid blue score range [{0}, {1}]")]
+    InvalidRange(u64, u64),
+
+    #[error("no node databases were found in {0}")]
+    MissingDatabases(String),
+
+    #[error("the archive format only supports the export of all the columns of blocks records")]
+    UnsupportedArchiveRecords,
+
+    #[error("consensus error: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("arrow error: {0}")]
+    ArrowError(#[from] ArrowError),
+
+    #[error("parquet error: {0}")]
+    ParquetError(#[from] ParquetError),
+
+    #[error("block archive error: {0}")]
+    ArchiveError(#[from] ArchiveError),
+}
+
+pub type ExportResult<T> = std::result::Result<T, ExportError>;
+
+#[derive(Debug, Clone, Copy)]
+enum ColumnKind {
+    U64,
+    Bool,
+    Str,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Column {
+    name: &'static str,
+    kind: ColumnKind,
+}
+
+const fn column(name: &'static str, kind: ColumnKind) -> Column {
+    Column { name, kind }
+}
+
+const BLOCK_COLUMNS: &[Column] = &[
+    column("hash", ColumnKind::Str),
+    column("version", ColumnKind::U64),
+    column("timestamp", ColumnKind::U64),
+    column("blue_score", ColumnKind::U64),
+    column("daa_score", ColumnKind::U64),
+    column("blue_work", ColumnKind::Str),
+    column("bits", ColumnKind::U64),
+    column("nonce", ColumnKind::U64),
+    column("selected_parent_hash", ColumnKind::Str),
+    column("pruning_point", ColumnKind::Str),
+    column("transaction_count", ColumnKind::U64),
+];
+
+const TRANSACTION_COLUMNS: &[Column] = &[
+    column("block_hash", ColumnKind::Str),
+    column("block_blue_score", ColumnKind::U64),
+    column("block_timestamp", ColumnKind::U64),
+    column("transaction_id", ColumnKind::Str),
+    column("index_within_block", ColumnKind::U64),
+    column("version", ColumnKind::U64),
+    column("input_count", ColumnKind::U64),
+    column("output_count", ColumnKind::U64),
+    column("output_value", ColumnKind::U64),
+    column("lock_time", ColumnKind::U64),
+    column("subnetwork_id", ColumnKind::Str),
+    column("gas", ColumnKind::U64),
+    column("payload_size", ColumnKind::U64),
+    column("mass", ColumnKind::U64),
+    column("is_coinbase", ColumnKind::Bool),
+];
+
+const ACCEPTANCE_COLUMNS: &[Column] = &[
+    column("accepting_block_hash", ColumnKind::Str),
+    column("accepting_block_blue_score", ColumnKind::U64),
+    column("merged_block_hash", ColumnKind::Str),
+    column("transaction_id", ColumnKind::Str),
+    column("index_within_block", ColumnKind::U64),
+];
+
+impl ExportRecords {
+    fn columns(self) -> &'static [Column] {
+        match self {
+            ExportRecords::Blocks => BLOCK_COLUMNS,
+            ExportRecords::Transactions => TRANSACTION_COLUMNS,
+            ExportRecords::Acceptance => ACCEPTANCE_COLUMNS,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    U64(u64),
+    Bool(bool),
+    Str(String),
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::U64(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<Hash> for Value {
+    fn from(value: Hash) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+type Row = Vec<Value>;
+
+/// The exported columns, as indices into the full columns of the record type
+struct ExportSchema {
+    columns: Vec<Column>,
+    indices: Vec<usize>,
+}
+
+impl ExportSchema {
+    fn new(records: ExportRecords, names: Option<&[String]>) -> ExportResult<Self> {
+        let all = records.columns();
+        let indices = match names {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    all.iter().position(|c| c.name == name.as_str()).ok_or_else(|| {
+                        ExportError::UnknownColumn(name.clone(), all.iter().map(|c| c.name).collect::<Vec<_>>().join(", "))
+                    })
+                })
+                .collect::<ExportResult<Vec<_>>>()?,
+            None => (0..all.len()).collect(),
+        };
+        Ok(Self { columns: indices.iter().map(|&i| all[i]).collect(), indices })
+    }
+
+    fn project(&self, row: Row) -> Row {
+        self.indices.iter().map(|&i| row[i].clone()).collect()
+    }
+}
+
+trait RecordWriter {
+    fn write(&mut self, rows: &[Row]) -> ExportResult<()>;
+    fn finish(self: Box<Self>) -> ExportResult<()>;
+}
+
+struct CsvWriter {
+    writer: BufWriter<File>,
+}
+
+impl CsvWriter {
+    fn new(file: File, columns: &[Column]) -> ExportResult<Self> {
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", columns.iter().map(|c| c.name).collect::<Vec<_>>().join(","))?;
+        Ok(Self { writer })
+    }
+}
+
+impl RecordWriter for CsvWriter {
+    fn write(&mut self, rows: &[Row]) -> ExportResult<()> {
+        for row in rows {
+            // All string values are hex encoded or identifiers, so they never require quoting
+            let line = row
+                .iter()
+                .map(|value| match value {
+                    Value::U64(v) => v.to_string(),
+                    Value::Bool(v) => v.to_string(),
+                    Value::Str(v) => v.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(self.writer, "{line}")?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> ExportResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+struct ParquetWriter {
+    schema: SchemaRef,
+    columns: Vec<Column>,
+    writer: ArrowWriter<File>,
+}
+
+impl ParquetWriter {
+    fn new(file: File, columns: &[Column]) -> ExportResult<Self> {
+        let fields = columns
+            .iter()
+            .map(|c| {
+                let data_type = match c.kind {
+                    ColumnKind::U64 => DataType::UInt64,
+                    ColumnKind::Bool => DataType::Boolean,
+                    ColumnKind::Str => DataType::Utf8,
+                };
+                Field::new(c.name, data_type, false)
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new(fields));
+        let writer = ArrowWriter::try_new(file, schema.clone(), None)?;
+        Ok(Self { schema, columns: columns.to_vec(), writer })
+    }
+}
+
+impl RecordWriter for ParquetWriter {
+    fn write(&mut self, rows: &[Row]) -> ExportResult<()> {
+        let arrays = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| -> ArrayRef {
+                match c.kind {
+                    ColumnKind::U64 => Arc::new(UInt64Array::from_iter_values(rows.iter().map(|row| match row[i] {
+                        Value::U64(v) => v,
+                        _ => unreachable!("column {} is of type u64", c.name),
+                    }))),
+                    ColumnKind::Bool => Arc::new(BooleanArray::from_iter(rows.iter().map(|row| match row[i] {
+                        Value::Bool(v) => Some(v),
+                        _ => unreachable!("column {} is of type bool", c.name),
+                    }))),
+                    ColumnKind::Str => Arc::new(StringArray::from_iter_values(rows.iter().map(|row| match &row[i] {
+                        Value::Str(v) => v.as_str(),
+                        _ => unreachable!("column {} is of type string", c.name),
+                    }))),
+                }
+            })
+            .collect::<Vec<_>>();
+        self.writer.write(&RecordBatch::try_new(self.schema.clone(), arrays)?)?;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> ExportResult<()> {
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Collects the selected chain blocks with a blue score in `[from, to]`, in ascending blue score order
+fn collect_chain(consensus: &dyn ConsensusApi, from: u64, to: u64) -> ExportResult<Vec<Hash>> {
+    let source = consensus.get_source();
+    let mut chain = Vec::new();
+    let mut current = consensus.get_sink();
+    loop {
+        let ghostdag_data = consensus.get_ghostdag_data(current)?;
+        if ghostdag_data.blue_score < from {
+            break;
+        }
+        if ghostdag_data.blue_score <= to {
+            chain.push(current);
+        }
+        if current == source {
+            break;
+        }
+        current = ghostdag_data.selected_parent;
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Writes the blocks merged by the chain blocks to a block archive, in topological order, the first chain block included
+fn write_archive(consensus: &dyn ConsensusApi, chain: &[Hash], file: File, network: NetworkId) -> ExportResult<u64> {
+    let mut writer = BlockArchiveWriter::new(BufWriter::new(file), network)?;
+    if let (Some(&low), Some(&high)) = (chain.first(), chain.last()) {
+        let (hashes, _) = consensus.get_hashes_between(low, high, usize::MAX)?;
+        for hash in std::iter::once(low).chain(hashes) {
+            writer.append(&consensus.get_block(hash)?)?;
+        }
+    }
+    let count = writer.count();
+    writer.finish()?;
+    Ok(count)
+}
+
+/// Returns the full rows of the records of the chain block
+fn chain_block_rows(consensus: &dyn ConsensusApi, records: ExportRecords, hash: Hash) -> ExportResult<Vec<Row>> {
+    let rows = match records {
+        ExportRecords::Blocks => {
+            let block = consensus.get_block_even_if_header_only(hash)?;
+            let header = &block.header;
+            let selected_parent = consensus.get_ghostdag_data(hash)?.selected_parent;
+            vec![vec![
+                header.hash.into(),
+                (header.version as u64).into(),
+                header.timestamp.into(),
+                header.blue_score.into(),
+                header.daa_score.into(),
+                Value::Str(header.blue_work.to_string()),
+                (header.bits as u64).into(),
+                header.nonce.into(),
+                selected_parent.into(),
+                header.pruning_point.into(),
+                (block.transactions.len() as u64).into(),
+            ]]
+        }
+        ExportRecords::Transactions => {
+            let block = consensus.get_block(hash)?;
+            block
+                .transactions
+                .iter()
+                .enumerate()
+                .map(|(i, tx)| {
+                    vec![
+                        hash.into(),
+                        block.header.blue_score.into(),
+                        block.header.timestamp.into(),
+                        tx.id().into(),
+                        (i as u64).into(),
+                        (tx.version as u64).into(),
+                        (tx.inputs.len() as u64).into(),
+                        (tx.outputs.len() as u64).into(),
+                        tx.outputs.iter().map(|output| output.value).sum::<u64>().into(),
+                        tx.lock_time.into(),
+                        Value::Str(tx.subnetwork_id.to_string()),
+                        tx.gas.into(),
+                        (tx.payload.len() as u64).into(),
+                        tx.mass().into(),
+                        tx.is_coinbase().into(),
+                    ]
+                })
+                .collect()
+        }
+        ExportRecords::Acceptance => {
+            let blue_score = consensus.get_header(hash)?.blue_score;
+            let acceptance_data = consensus.get_block_acceptance_data(hash)?;
+            acceptance_data
+                .iter()
+                .flat_map(|mergeset_block| {
+                    mergeset_block.accepted_transactions.iter().map(move |entry| {
+                        vec![
+                            hash.into(),
+                            blue_score.into(),
+                            mergeset_block.block_hash.into(),
+                            entry.transaction_id.into(),
+                            (entry.index_within_block as u64).into(),
+                        ]
+                    })
+                })
+                .collect()
+        }
+    };
+    Ok(rows)
+}
+
+/// Runs the export described by `export_args` over the databases of the node configured by `args`
+pub fn run(args: &Args, export_args: &ExportArgs, fd_total_budget: i32) -> ExportResult<()> {
+    let network = args.network();
+    let config = ConfigBuilder::new(network.into())
+        .adjust_perf_params_to_consensus_params()
+        .apply_args(|config| args.apply_to_config(config))
+        .build();
+
+    // The node may be running, so its directories are read as they are, in the legacy layout or not
+    let db_dir = resolve_network_dir(&get_app_dir_from_args(args), network).join(DEFAULT_DATA_DIR);
+    let meta_db_dir = db_dir.join(META_DB);
+    if !meta_db_dir.exists() {
+        return Err(ExportError::MissingDatabases(db_dir.display().to_string()));
+    }
+    let cold_consensus_db_dir = args.cold_datadir.as_ref().map(|dir| {
+        resolve_network_dir(&PathBuf::from(dir.replace('~', get_home_dir().as_path().to_str().unwrap())), network).join(CONSENSUS_DB)
+    });
+
+    // The secondary instances only keep their info logs, so a temporary directory is sufficient
+    let secondary_dir = tempfile::tempdir()?;
+    let meta_db = kaspa_database::prelude::ConnBuilder::default()
+        .with_db_path(meta_db_dir)
+        .with_files_limit(META_DB_FILE_LIMIT)
+        .with_secondary_path(secondary_dir.path().join(META_DB))
+        .build()
+        .unwrap();
+
+    let (notification_send, _notification_recv) = unbounded();
+    let consensus_factory = ConsensusFactory::new(
+        meta_db,
+        &config,
+        db_dir.join(CONSENSUS_DB),
+        cold_consensus_db_dir,
+        Some(secondary_dir.path().join(CONSENSUS_DB)),
+        num_cpus::get(),
+        Arc::new(ConsensusNotificationRoot::new(notification_send)),
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        fd_total_budget - META_DB_FILE_LIMIT,
+    );
+    // The consensus processors are not started since the snapshot is only read
+    let (consensus_instance, _ctl) = consensus_factory.new_active_consensus();
+    let session = consensus_instance.unguarded_session_blocking();
+    let consensus: &dyn ConsensusApi = &*session;
+
+    let from = export_args.from_blue_score;
+    let to = export_args.to_blue_score.unwrap_or_else(|| consensus.get_header(consensus.get_sink()).unwrap().blue_score);
+    if from > to {
+        return Err(ExportError::InvalidRange(from, to));
+    }
+    if export_args.format == ExportFormat::Archive {
+        if export_args.records != ExportRecords::Blocks || export_args.columns.is_some() {
+            return Err(ExportError::UnsupportedArchiveRecords);
+        }
+        let chain = collect_chain(consensus, from, to)?;
+        info!("Archiving the blocks merged by {} selected chain blocks in blue score range [{}, {}]", chain.len(), from, to);
+        let count = write_archive(consensus, &chain, File::create(&export_args.output)?, network)?;
+        info!("Archived {} blocks to {}", count, export_args.output);
+        return Ok(());
+    }
+
+    let schema = ExportSchema::new(export_args.records, export_args.columns.as_deref())?;
+    let chain = collect_chain(consensus, from, to)?;
+    info!(
+        "Exporting {:?} records of {} selected chain blocks in blue score range [{}, {}]",
+        export_args.records,
+        chain.len(),
+        from,
+        to
+    );
+
+    let file = File::create(&export_args.output)?;
+    let mut writer: Box<dyn RecordWriter> = match export_args.format {
+        ExportFormat::Csv => Box::new(CsvWriter::new(file, &schema.columns)?),
+        ExportFormat::Parquet => Box::new(ParquetWriter::new(file, &schema.columns)?),
+        ExportFormat::Archive => unreachable!("archives are written above"),
+    };
+    let mut rows = Vec::with_capacity(BATCH_SIZE);
+    let mut row_count = 0;
+    for hash in chain {
+        for row in chain_block_rows(consensus, export_args.records, hash)? {
+            rows.push(schema.project(row));
+        }
+        if rows.len() >= BATCH_SIZE {
+            row_count += rows.len();
+            writer.write(&rows)?;
+            rows.clear();
+        }
+    }
+    if !rows.is_empty() {
+        row_count += rows.len();
+        writer.write(&rows)?;
+    }
+    writer.finish()?;
+
+    info!("Exported {} rows to {}", row_count, export_args.output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> ExportArgs {
+        let m = Command::new("kaspad").subcommand(cli()).try_get_matches_from(args).unwrap();
+        ExportArgs::parse(&m).unwrap()
+    }
+
+    fn block_row(blue_score: u64) -> Row {
+        vec![
+            Hash::from_u64_word(blue_score).into(),
+            1u64.into(),
+            (1000 + blue_score).into(),
+            blue_score.into(),
+            (2 * blue_score).into(),
+            Value::Str("1f".to_owned()),
+            486722099u64.into(),
+            7u64.into(),
+            Hash::from_u64_word(blue_score - 1).into(),
+            Hash::from_u64_word(0).into(),
+            3u64.into(),
+        ]
+    }
+
+    #[test]
+    fn test_export_args() {
+        let args = parse_args(&["kaspad", "export", "--output=out.csv"]);
+        assert_eq!(args.format, ExportFormat::Csv);
+        assert_eq!(args.records, ExportRecords::Blocks);
+        assert_eq!(args.output, "out.csv");
+        assert_eq!(args.from_blue_score, 0);
+        assert_eq!(args.to_blue_score, None);
+        assert!(args.columns.is_none());
+
+        let args = parse_args(&[
+            "kaspad",
+            "export",
+            "--format=parquet",
+            "--records=transactions",
+            "-o=out.parquet",
+            "--from-blue-score=10",
+            "--to-blue-score=20",
+            "--columns=transaction_id,mass",
+        ]);
+        assert_eq!(args.format, ExportFormat::Parquet);
+        assert_eq!(args.records, ExportRecords::Transactions);
+        assert_eq!((args.from_blue_score, args.to_blue_score), (10, Some(20)));
+        assert_eq!(args.columns, Some(vec!["transaction_id".to_owned(), "mass".to_owned()]));
+
+        // The output is required and the formats are restricted
+        assert!(Command::new("kaspad").subcommand(cli()).try_get_matches_from(["kaspad", "export"]).is_err());
+        assert!(Command::new("kaspad")
+            .subcommand(cli())
+            .try_get_matches_from(["kaspad", "export", "-o=out", "--format=json"])
+            .is_err());
+        // Without the subcommand, there is nothing to export
+        assert!(ExportArgs::parse(&Command::new("kaspad").subcommand(cli()).get_matches_from(["kaspad"])).is_none());
+    }
+
+    #[test]
+    fn test_export_schema() {
+        for records in [ExportRecords::Blocks, ExportRecords::Transactions, ExportRecords::Acceptance] {
+            let schema = ExportSchema::new(records, None).unwrap();
+            assert_eq!(schema.columns.len(), records.columns().len());
+        }
+
+        let names = ["daa_score", "hash"].map(str::to_owned);
+        let schema = ExportSchema::new(ExportRecords::Blocks, Some(&names)).unwrap();
+        assert_eq!(schema.columns.iter().map(|c| c.name).collect::<Vec<_>>(), ["daa_score", "hash"]);
+        let row = schema.project(block_row(5));
+        assert!(matches!(row[0], Value::U64(10)));
+        assert!(matches!(&row[1], Value::Str(hash) if *hash == Hash::from_u64_word(5).to_string()));
+
+        // Columns of other record types are unknown
+        let names = ["is_coinbase".to_owned()];
+        match ExportSchema::new(ExportRecords::Blocks, Some(&names)) {
+            Err(ExportError::UnknownColumn(name, available)) => {
+                assert_eq!(name, "is_coinbase");
+                assert!(available.starts_with("hash, version, timestamp"));
+            }
+            _ => panic!("expected an unknown column error"),
+        }
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.csv");
+        let names = ["hash", "blue_score", "transaction_count"].map(str::to_owned);
+        let schema = ExportSchema::new(ExportRecords::Blocks, Some(&names)).unwrap();
+
+        let mut writer: Box<dyn RecordWriter> = Box::new(CsvWriter::new(File::create(&path).unwrap(), &schema.columns).unwrap());
+        writer.write(&[schema.project(block_row(1)), schema.project(block_row(2))]).unwrap();
+        writer.write(&[]).unwrap();
+        writer.finish().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "hash,blue_score,transaction_count".to_owned(),
+                format!("{},1,3", Hash::from_u64_word(1)),
+                format!("{},2,3", Hash::from_u64_word(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parquet_writer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocks.parquet");
+        let schema = ExportSchema::new(ExportRecords::Blocks, None).unwrap();
+
+        let mut writer: Box<dyn RecordWriter> = Box::new(ParquetWriter::new(File::create(&path).unwrap(), &schema.columns).unwrap());
+        writer.write(&(1..=3).map(|blue_score| schema.project(block_row(blue_score))).collect::<Vec<_>>()).unwrap();
+        writer.finish().unwrap();
+
+        let content = std::fs::read(&path).unwrap();
+        assert!(content.starts_with(b"PAR1") && content.ends_with(b"PAR1"));
+    }
+}
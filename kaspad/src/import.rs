@@ -0,0 +1,211 @@
+//! The `kaspad import-blocks` subcommand: ingests a block archive written by `kaspad export --format=archive`
+//! into the node databases through the full consensus validation pipeline, bypassing P2P. This allows bootstrapping
+//! air-gapped nodes and replaying the exact same blocks for repeatable benchmarks.
+//!
+//! The node must not be running during the import. The archive must either start from genesis, which requires
+//! an archive exported by an archival node, or from blocks whose parents are already known by the node.
+
+use crate::{
+    archive::{ArchiveError, BlockArchiveReader},
+    args::Args,
+    daemon::{
+        get_app_dir_from_args, get_home_dir, get_network_dir, migrate_legacy_layout, CONSENSUS_DB, DEFAULT_DATA_DIR, META_DB,
+        META_DB_FILE_LIMIT,
+    },
+};
+use async_channel::unbounded;
+use clap::{Arg, ArgMatches, Command};
+use futures_util::future::join_all;
+use kaspa_consensus::{
+    consensus::factory::{Factory as ConsensusFactory, MultiConsensusManagementStore},
+    pipeline::ProcessingCounters,
+};
+use kaspa_consensus_core::{
+    api::{BlockValidationFuture, ConsensusApi},
+    config::ConfigBuilder,
+    errors::block::RuleError,
+    network::NetworkId,
+};
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensusmanager::ConsensusFactory as _;
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_txscript::caches::TxScriptCacheCounters;
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
+use thiserror::Error;
+
+/// Number of blocks submitted to the consensus while the validation of the previous chunk is awaited
+const CHUNK_SIZE: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct ImportArgs {
+    /// Path of the block archive
+    pub file: String,
+}
+
+impl ImportArgs {
+    fn from_matches(m: &ArgMatches) -> Self {
+        Self { file: m.get_one::<String>("file").cloned().unwrap() }
+    }
+
+    /// Parses the import args if the `import-blocks` subcommand was used
+    pub fn parse(m: &ArgMatches) -> Option<Self> {
+        m.subcommand_matches(SUBCOMMAND).map(Self::from_matches)
+    }
+}
+
+pub const SUBCOMMAND: &str = "import-blocks";
+
+pub fn cli() -> Command {
+    Command::new(SUBCOMMAND)
+        .about("Import the blocks of a block archive into the node databases through the full validation pipeline, bypassing P2P (the node must not be running)")
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .required(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Path of the block archive, as written by `kaspad export --format=archive`."),
+        )
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("the block archive holds blocks of {0} while the node is configured for {1}")]
+    NetworkMismatch(NetworkId, NetworkId),
+
+    #[error("the node databases in {0} are from a different version, run the node once to upgrade them")]
+    IncompatibleDatabases(String),
+
+    #[error("block {0} was rejected: {1}")]
+    RejectedBlock(Hash, RuleError),
+
+    #[error("block archive error: {0}")]
+    ArchiveError(#[from] ArchiveError),
+
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub type ImportResult<T> = std::result::Result<T, ImportError>;
+
+#[derive(Default)]
+struct ImportStats {
+    blocks: u64,
+    transactions: u64,
+    skipped: u64,
+}
+
+/// Runs the import described by `import_args` into the databases of the node configured by `args`
+pub fn run(args: &Args, import_args: &ImportArgs, fd_total_budget: i32) -> ImportResult<()> {
+    let reader = BlockArchiveReader::new(BufReader::new(File::open(&import_args.file)?))?;
+    let network = args.network();
+    if reader.network() != network {
+        return Err(ImportError::NetworkMismatch(reader.network(), network));
+    }
+    let config = ConfigBuilder::new(network.into())
+        .adjust_perf_params_to_consensus_params()
+        .apply_args(|config| args.apply_to_config(config))
+        .build();
+
+    for (legacy_dir, network_dir) in migrate_legacy_layout(args)? {
+        info!("Moved the legacy directory {} to {}", legacy_dir.display(), network_dir.display());
+    }
+    let db_dir = get_network_dir(&get_app_dir_from_args(args), network).join(DEFAULT_DATA_DIR);
+    let consensus_db_dir = db_dir.join(CONSENSUS_DB);
+    let meta_db_dir = db_dir.join(META_DB);
+    let cold_consensus_db_dir = args.cold_datadir.as_ref().map(|dir| {
+        get_network_dir(&PathBuf::from(dir.replace('~', get_home_dir().as_path().to_str().unwrap())), network).join(CONSENSUS_DB)
+    });
+    fs::create_dir_all(consensus_db_dir.as_path())?;
+    fs::create_dir_all(meta_db_dir.as_path())?;
+    if let Some(dir) = cold_consensus_db_dir.as_ref() {
+        fs::create_dir_all(dir.as_path())?;
+    }
+
+    let meta_db = kaspa_database::prelude::ConnBuilder::default()
+        .with_db_path(meta_db_dir)
+        .with_files_limit(META_DB_FILE_LIMIT)
+        .build()
+        .unwrap();
+    if MultiConsensusManagementStore::new(meta_db.clone()).should_upgrade().unwrap() {
+        return Err(ImportError::IncompatibleDatabases(db_dir.display().to_string()));
+    }
+
+    let (notification_send, _notification_recv) = unbounded();
+    let consensus_factory = ConsensusFactory::new(
+        meta_db,
+        &config,
+        consensus_db_dir,
+        cold_consensus_db_dir,
+        None,
+        num_cpus::get(),
+        Arc::new(ConsensusNotificationRoot::new(notification_send)),
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        fd_total_budget - META_DB_FILE_LIMIT,
+    );
+    let (consensus_instance, ctl) = consensus_factory.new_active_consensus();
+    let handles = ctl.start();
+    let session = consensus_instance.unguarded_session_blocking();
+
+    info!("Importing blocks from {}", import_args.file);
+    let start = Instant::now();
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let result = runtime.block_on(import_blocks(&*session, reader));
+
+    drop(session);
+    ctl.stop();
+    handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+    let stats = result?;
+    let elapsed = start.elapsed();
+    info!(
+        "Imported {} blocks with {} transactions overall in {:?} ({} already known blocks skipped), block processing rate: {:.2} (b/s), transaction processing rate: {:.2} (t/s)",
+        stats.blocks,
+        stats.transactions,
+        elapsed,
+        stats.skipped,
+        stats.blocks as f64 / elapsed.as_secs_f64(),
+        stats.transactions as f64 / elapsed.as_secs_f64(),
+    );
+    Ok(())
+}
+
+/// Submits the archived blocks by chunks, awaiting the validation of a chunk while the next one is being submitted
+async fn import_blocks(consensus: &dyn ConsensusApi, reader: BlockArchiveReader<BufReader<File>>) -> ImportResult<ImportStats> {
+    let mut stats = ImportStats::default();
+    let mut pending: Vec<(Hash, BlockValidationFuture)> = Vec::with_capacity(CHUNK_SIZE);
+    let mut submitted = Vec::with_capacity(CHUNK_SIZE);
+    for block in reader {
+        let block = block?;
+        let hash = block.hash();
+        if consensus.get_block_status(hash).is_some_and(|status| status.has_block_body()) {
+            stats.skipped += 1;
+            continue;
+        }
+        stats.blocks += 1;
+        stats.transactions += block.transactions.len() as u64;
+        submitted.push((hash, consensus.validate_and_insert_block(block).virtual_state_task));
+        if submitted.len() == CHUNK_SIZE {
+            await_chunk(std::mem::replace(&mut pending, std::mem::take(&mut submitted))).await?;
+            info!("Imported {} blocks", stats.blocks - pending.len() as u64);
+        }
+    }
+    await_chunk(pending).await?;
+    await_chunk(submitted).await?;
+    Ok(stats)
+}
+
+async fn await_chunk(chunk: Vec<(Hash, BlockValidationFuture)>) -> ImportResult<()> {
+    let (hashes, futures): (Vec<_>, Vec<_>) = chunk.into_iter().unzip();
+    for (hash, result) in hashes.into_iter().zip(join_all(futures).await) {
+        result.map_err(|err| ImportError::RejectedBlock(hash, err))?;
+    }
+    Ok(())
+}
@@ -1,2 +1,7 @@
+pub mod archive;
 pub mod args;
+pub mod build_info;
+pub mod counters;
 pub mod daemon;
+pub mod export;
+pub mod import;
@@ -9,7 +9,9 @@ use kaspa_core::{info, signals::Signals};
 use kaspa_utils::fd_budget;
 use kaspad_lib::{
     args::parse_args,
+    build_info::build_info,
     daemon::{create_core, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    export, import,
 };
 
 #[cfg(feature = "heap")]
@@ -24,6 +26,11 @@ pub fn main() {
 
     let args = parse_args();
 
+    if args.build_info {
+        println!("{}", serde_json::to_string_pretty(&build_info()).unwrap());
+        return;
+    }
+
     match fd_budget::try_set_fd_limit(DESIRED_DAEMON_SOFT_FD_LIMIT) {
         Ok(limit) => {
             if limit < MINIMUM_DAEMON_SOFT_FD_LIMIT {
@@ -39,8 +46,29 @@ pub fn main() {
         }
     }
 
+    if let Some(export_args) = args.export.as_ref() {
+        kaspa_core::log::init_logger(None, &args.log_level);
+        if let Err(err) = export::run(&args, export_args, fd_budget::limit()) {
+            println!("Export failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(import_args) = args.import.as_ref() {
+        kaspa_core::log::init_logger(None, &args.log_level);
+        if let Err(err) = import::run(&args, import_args, fd_budget::limit()) {
+            println!("Import failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let fd_total_budget = fd_budget::limit() - args.rpc_max_clients as i32 - args.inbound_limit as i32 - args.outbound_target as i32;
-    let (core, _) = create_core(args, fd_total_budget);
+    let Ok((core, _)) = create_core(args, fd_total_budget) else {
+        // The error got logged already
+        std::process::exit(1);
+    };
 
     // Bind the keyboard signal to the core
     Arc::new(Signals::new(&core)).init();
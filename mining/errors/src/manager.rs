@@ -10,6 +10,10 @@ pub enum MiningManagerError {
     /// A mempool rule error
     #[error(transparent)]
     MempoolError(#[from] RuleError),
+
+    /// A mempool policy parameter out of its allowed range
+    #[error("mempool policy {0} {1} is out of the allowed range [{2}, {3}]")]
+    MempoolPolicyOutOfRange(&'static str, u64, u64, u64),
 }
 
 pub type MiningManagerResult<T> = std::result::Result<T, MiningManagerError>;
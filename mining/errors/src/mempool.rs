@@ -27,6 +27,15 @@ pub enum RuleError {
     #[error("output {0} already spent by transaction {1} in the memory pool")]
     RejectDoubleSpendInMempool(TransactionOutpoint, TransactionId),
 
+    #[error("replacement transaction {0} has a feerate of {2:.3} which is not sufficiently higher than the feerate {3:.3} of the replaced transaction {1}")]
+    RejectReplacementFeerateTooLow(TransactionId, TransactionId, f64, f64),
+
+    #[error("replacement transaction {0} has {1} fees which is under the {2} fees of the replaced transactions and their redeemers")]
+    RejectReplacementFeeTooLow(TransactionId, u64, u64),
+
+    #[error("replacement transaction {0} spends an output of the replaced transaction {1} or of one of its redeemers")]
+    RejectReplacementSpendingReplaced(TransactionId, TransactionId),
+
     /// New behavior: a transaction is rejected if the mempool is full
     #[error("number of high-priority transactions in mempool ({0}) has reached the maximum allowed ({1})")]
     RejectMempoolIsFull(usize, u64),
@@ -2,23 +2,20 @@ use super::{errors::BuilderResult, policy::Policy};
 use crate::{block_template::selector::TransactionsSelector, model::candidate_tx::CandidateTransaction};
 use kaspa_consensus_core::{
     api::ConsensusApi,
-    block::{BlockTemplate, TemplateBuildMode},
+    block::{BlockTemplate, TemplateBuildMode, TemplateTxOrdering},
     coinbase::MinerData,
     merkle::calc_hash_merkle_root,
     tx::COINBASE_TRANSACTION_INDEX,
 };
-use kaspa_core::{
-    debug,
-    time::{unix_now, Stopwatch},
-};
+use kaspa_core::{debug, time::Stopwatch};
 
 pub(crate) struct BlockTemplateBuilder {
     policy: Policy,
 }
 
 impl BlockTemplateBuilder {
-    pub(crate) fn new(max_block_mass: u64) -> Self {
-        let policy = Policy::new(max_block_mass);
+    pub(crate) fn new(max_block_mass: u64, tx_ordering: TemplateTxOrdering) -> Self {
+        let policy = Policy::new(max_block_mass, tx_ordering);
         Self { policy }
     }
 
@@ -98,11 +95,12 @@ impl BlockTemplateBuilder {
         Ok(consensus.build_block_template(miner_data.clone(), selector, build_mode)?)
     }
 
-    /// modify_block_template clones an existing block template, modifies it to the requested coinbase data and updates the timestamp
+    /// modify_block_template clones an existing block template, modifies it to the requested coinbase data and updates the timestamp to `now`
     pub(crate) fn modify_block_template(
         consensus: &dyn ConsensusApi,
         new_miner_data: &MinerData,
         block_template_to_modify: &BlockTemplate,
+        now: u64,
     ) -> BuilderResult<BlockTemplate> {
         let mut block_template = block_template_to_modify.clone();
 
@@ -116,7 +114,7 @@ impl BlockTemplateBuilder {
         }
         // Update the hash merkle root according to the modified transactions
         block_template.block.header.hash_merkle_root = calc_hash_merkle_root(block_template.block.transactions.iter());
-        let new_timestamp = unix_now();
+        let new_timestamp = now;
         if new_timestamp > block_template.block.header.timestamp {
             // Only if new time stamp is later than current, update the header. Otherwise,
             // we keep the previous time as built by internal consensus median time logic
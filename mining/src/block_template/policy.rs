@@ -1,3 +1,5 @@
+use kaspa_consensus_core::block::TemplateTxOrdering;
+
 /// Policy houses the policy (configuration parameters) which is used to control
 /// the generation of block templates. See the documentation for
 /// NewBlockTemplate for more details on each of these parameters are used.
@@ -5,10 +7,13 @@
 pub(crate) struct Policy {
     /// max_block_mass is the maximum block mass to be used when generating a block template.
     pub(crate) max_block_mass: u64,
+
+    /// tx_ordering is the ordering of the transactions of the block template.
+    pub(crate) tx_ordering: TemplateTxOrdering,
 }
 
 impl Policy {
-    pub(crate) fn new(max_block_mass: u64) -> Self {
-        Self { max_block_mass }
+    pub(crate) fn new(max_block_mass: u64, tx_ordering: TemplateTxOrdering) -> Self {
+        Self { max_block_mass, tx_ordering }
     }
 }
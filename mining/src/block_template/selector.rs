@@ -9,7 +9,7 @@ use super::{
     policy::Policy,
 };
 use kaspa_consensus_core::{
-    block::TemplateTransactionSelector,
+    block::{TemplateTransactionSelector, TemplateTxOrdering},
     subnets::SubnetworkId,
     tx::{Transaction, TransactionId},
 };
@@ -258,6 +258,56 @@ impl TemplateTransactionSelector for TransactionsSelector {
             || (self.total_mass as f64) > self.policy.max_block_mass as f64 * SUFFICIENT_MASS_THRESHOLD
             || (self.overall_rejections as f64) < self.transactions.len() as f64 * LOW_REJECTION_FRACTION
     }
+
+    fn order_transactions(&self, txs: &mut [Transaction]) {
+        match self.policy.tx_ordering {
+            TemplateTxOrdering::Selection => {}
+            TemplateTxOrdering::Deterministic => {
+                let fees = self.transactions.iter().map(|x| (x.tx.id(), (x.calculated_fee, x.calculated_mass))).collect();
+                sort_deterministically(txs, &fees);
+            }
+        }
+    }
+}
+
+/// Sorts `txs` by subnetwork id, then parents before children, then decreasing feerate, then transaction id.
+///
+/// `fees` maps the transaction ids to their fee and mass, a missing transaction being ranked as having no fee.
+pub(crate) fn sort_deterministically(txs: &mut [Transaction], fees: &HashMap<TransactionId, (u64, u64)>) {
+    // The depth of a transaction is the length of its longest chain of ancestors among `txs`
+    let indexes = txs.iter().enumerate().map(|(i, tx)| (tx.id(), i)).collect::<HashMap<_, _>>();
+    let mut depths = vec![0usize; txs.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, tx) in txs.iter().enumerate() {
+            let depth = tx
+                .inputs
+                .iter()
+                .filter_map(|input| indexes.get(&input.previous_outpoint.transaction_id))
+                .map(|&parent| depths[parent] + 1)
+                .max()
+                .unwrap_or_default();
+            // A depth beyond the transaction count can only come from a cycle, which a valid set cannot hold
+            if depth > depths[i] && depth <= txs.len() {
+                depths[i] = depth;
+                changed = true;
+            }
+        }
+    }
+    let depths = indexes.into_iter().map(|(id, i)| (id, depths[i])).collect::<HashMap<_, _>>();
+
+    txs.sort_by(|a, b| {
+        let (a_id, b_id) = (a.id(), b.id());
+        let (a_fee, a_mass) = fees.get(&a_id).copied().unwrap_or_default();
+        let (b_fee, b_mass) = fees.get(&b_id).copied().unwrap_or_default();
+        a.subnetwork_id
+            .cmp(&b.subnetwork_id)
+            .then(depths[&a_id].cmp(&depths[&b_id]))
+            // Compare the feerates by cross multiplication so no precision gets lost
+            .then((b_fee as u128 * a_mass as u128).cmp(&(a_fee as u128 * b_mass as u128)))
+            .then(a_id.cmp(&b_id))
+    });
 }
 
 #[cfg(test)]
@@ -281,7 +331,7 @@ mod tests {
 
         // Create a vector of transactions differing by output value so they have unique ids
         let transactions = (0..TX_INITIAL_COUNT).map(|i| create_transaction(SOMPI_PER_KASPA * (i + 1) as u64)).collect_vec();
-        let policy = Policy::new(100_000);
+        let policy = Policy::new(100_000, TemplateTxOrdering::Selection);
         let mut selector = TransactionsSelector::new(policy, transactions);
         let (mut kept, mut rejected) = (HashSet::new(), HashSet::new());
         let mut reject_count = 32;
@@ -307,6 +357,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deterministic_ordering() {
+        // Independent transactions paying increasing fees, and a child of the lowest paying one with the highest fee
+        let mut candidates = (0..5)
+            .map(|i| {
+                let mut candidate = create_transaction(SOMPI_PER_KASPA * (i + 1));
+                candidate.calculated_fee = (i + 1) * 1_000;
+                candidate
+            })
+            .collect_vec();
+        let mut child = create_transaction(SOMPI_PER_KASPA);
+        let mut child_tx = child.tx.as_ref().clone();
+        child_tx.inputs[0].previous_outpoint = TransactionOutpoint::new(candidates[0].tx.id(), 0);
+        child_tx.finalize();
+        child.tx = Arc::new(child_tx);
+        child.calculated_fee = 100_000;
+        candidates.push(child);
+
+        let fees = candidates.iter().map(|x| (x.tx.id(), (x.calculated_fee, x.calculated_mass))).collect();
+        let expected_ids = [4, 3, 2, 1, 0, 5].map(|i| candidates[i].tx.id());
+
+        // Any input order yields the same ordering, the child coming after its parent despite its higher feerate
+        for permutation in [vec![0, 1, 2, 3, 4, 5], vec![5, 4, 3, 2, 1, 0], vec![2, 5, 0, 4, 1, 3]] {
+            let mut txs = permutation.iter().map(|&i| candidates[i].tx.as_ref().clone()).collect_vec();
+            sort_deterministically(&mut txs, &fees);
+            assert_eq!(txs.iter().map(|tx| tx.id()).collect_vec(), expected_ids);
+        }
+
+        // The selection ordering leaves the transactions untouched
+        let policy = Policy::new(100_000, TemplateTxOrdering::Selection);
+        let selector = TransactionsSelector::new(policy, candidates.clone());
+        let mut txs = candidates.iter().map(|x| x.tx.as_ref().clone()).collect_vec();
+        selector.order_transactions(&mut txs);
+        assert_eq!(txs.iter().map(|tx| tx.id()).collect_vec(), candidates.iter().map(|x| x.tx.id()).collect_vec());
+    }
+
     fn create_transaction(value: u64) -> CandidateTransaction {
         let previous_outpoint = TransactionOutpoint::new(TransactionId::default(), 0);
         let (script_public_key, redeem_script) = op_true_script();
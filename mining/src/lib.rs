@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     sync::atomic::{AtomicU64, Ordering},
     time::{Duration, Instant},
@@ -77,6 +78,21 @@ impl MiningCounters {
         }
     }
 
+    /// Adds the counts and samples of a previously taken snapshot, typically persisted by a former run of the node.
+    /// The elapsed time of the snapshot is ignored since it is measured from the creation of these counters.
+    pub fn restore(&self, snapshot: &MempoolCountersSnapshot) {
+        self.high_priority_tx_counts.fetch_add(snapshot.high_priority_tx_counts, Ordering::Relaxed);
+        self.low_priority_tx_counts.fetch_add(snapshot.low_priority_tx_counts, Ordering::Relaxed);
+        self.block_tx_counts.fetch_add(snapshot.block_tx_counts, Ordering::Relaxed);
+        self.tx_accepted_counts.fetch_add(snapshot.tx_accepted_counts, Ordering::Relaxed);
+        self.input_counts.fetch_add(snapshot.input_counts, Ordering::Relaxed);
+        self.output_counts.fetch_add(snapshot.output_counts, Ordering::Relaxed);
+        self.ready_txs_sample.fetch_add(snapshot.ready_txs_sample, Ordering::Relaxed);
+        self.txs_sample.fetch_add(snapshot.txs_sample, Ordering::Relaxed);
+        self.orphans_sample.fetch_add(snapshot.orphans_sample, Ordering::Relaxed);
+        self.accepted_sample.fetch_add(snapshot.accepted_sample, Ordering::Relaxed);
+    }
+
     pub fn increase_tx_counts(&self, value: u64, priority: Priority) {
         match priority {
             Priority::Low => {
@@ -89,7 +105,7 @@ impl MiningCounters {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MempoolCountersSnapshot {
     pub elapsed_time: Duration,
     pub high_priority_tx_counts: u64,
@@ -177,3 +193,36 @@ impl core::ops::Sub for &P2pTxCountSample {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mining_counters_restore() {
+        let counters = MiningCounters::default();
+        counters.increase_tx_counts(3, Priority::High);
+        let persisted = MempoolCountersSnapshot {
+            elapsed_time: Duration::from_secs(3600),
+            high_priority_tx_counts: 10,
+            low_priority_tx_counts: 11,
+            block_tx_counts: 12,
+            tx_accepted_counts: 13,
+            input_counts: 14,
+            output_counts: 15,
+            ready_txs_sample: 16,
+            txs_sample: 17,
+            orphans_sample: 18,
+            accepted_sample: 19,
+        };
+
+        counters.restore(&persisted);
+        let snapshot = counters.snapshot();
+        // The elapsed time keeps being measured from the creation of the counters
+        assert!(snapshot.elapsed_time < persisted.elapsed_time);
+        assert_eq!(
+            snapshot,
+            MempoolCountersSnapshot { elapsed_time: snapshot.elapsed_time, high_priority_tx_counts: 13, ..persisted }
+        );
+    }
+}
@@ -3,26 +3,31 @@ use crate::{
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
     mempool::{
-        config::Config,
+        config::{Config, DustPolicy, MempoolPolicy},
         model::tx::{MempoolTransaction, TxRemovalReason},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
         Mempool,
     },
     model::{
         candidate_tx::CandidateTransaction,
+        evicted_txs::{EvictedTransactions, ReevaluationTrigger},
+        fee_estimate::FeeEstimate,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        template_exclusions::{TemplateExclusions, TemplateExclusionsUpdate},
         topological_sort::IntoIterTopologically,
-        tx_query::TransactionQuery,
+        tx_query::{TransactionFilter, TransactionQuery},
+        TransactionIdSet,
     },
     MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
 };
+use async_channel::Receiver;
 use itertools::Itertools;
 use kaspa_consensus_core::{
     api::ConsensusApi,
-    block::{BlockTemplate, TemplateBuildMode},
+    block::{BlockTemplate, TemplateBuildMode, TemplateTxOrdering},
     coinbase::MinerData,
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
@@ -30,8 +35,13 @@ use kaspa_consensus_core::{
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
-use parking_lot::RwLock;
-use std::sync::Arc;
+use kaspa_utils::channel::Channel;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 pub struct MiningManager {
@@ -39,6 +49,9 @@ pub struct MiningManager {
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
     counters: Arc<MiningCounters>,
+    eviction_channel: Channel<EvictedTransactions>,
+    /// Transactions excluded from the block templates which were already reported in the log
+    reported_template_exclusions: Mutex<TransactionIdSet>,
 }
 
 impl MiningManager {
@@ -58,19 +71,35 @@ impl MiningManager {
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        template_tx_ordering: TemplateTxOrdering,
+        dust_policy: DustPolicy,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .with_template_tx_ordering(template_tx_ordering)
+            .with_dust_policy(dust_policy);
         Self::with_config(config, cache_lifetime, counters)
     }
 
     pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
-        let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        let block_template_cache = BlockTemplateCache::new(cache_lifetime, config.clock.clone());
+        Self {
+            config,
+            block_template_cache,
+            mempool,
+            counters,
+            eviction_channel: Channel::default(),
+            reported_template_exclusions: Default::default(),
+        }
+    }
+
+    /// Returns a receiver of the transactions evicted by [`MiningManager::reevaluate_transactions`]
+    pub fn eviction_receiver(&self) -> Receiver<EvictedTransactions> {
+        self.eviction_channel.receiver()
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -86,7 +115,8 @@ impl MiningManager {
             }
             // Miner data is new -- make the minimum changes required
             // Note the call returns a modified clone of the cached block template
-            let block_template = BlockTemplateBuilder::modify_block_template(consensus, miner_data, &immutable_template)?;
+            let block_template =
+                BlockTemplateBuilder::modify_block_template(consensus, miner_data, &immutable_template, self.config.clock.unix_now())?;
 
             // No point in updating cache since we have no reason to believe this coinbase will be used more
             // than the previous one, and we want to maintain the original template caching time
@@ -104,7 +134,8 @@ impl MiningManager {
             attempts += 1;
 
             let transactions = self.block_candidate_transactions();
-            let block_template_builder = BlockTemplateBuilder::new(self.config.maximum_mass_per_block);
+            let block_template_builder =
+                BlockTemplateBuilder::new(self.config.maximum_mass_per_block, self.config.template_tx_ordering);
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
             } else {
@@ -194,7 +225,18 @@ impl MiningManager {
     }
 
     pub(crate) fn block_candidate_transactions(&self) -> Vec<CandidateTransaction> {
-        self.mempool.read().block_candidate_transactions()
+        let (candidates, excluded) = self.mempool.read().block_candidate_transactions();
+        // Audit the exclusions, reporting every excluded transaction once while it remains a candidate
+        let mut reported = self.reported_template_exclusions.lock();
+        let mut still_excluded = TransactionIdSet::with_capacity(excluded.len());
+        for (transaction_id, reason) in excluded {
+            if !reported.contains(&transaction_id) {
+                info!("Transaction {} excluded from the block templates: {}", transaction_id, reason);
+            }
+            still_excluded.insert(transaction_id);
+        }
+        *reported = still_excluded;
+        candidates
     }
 
     /// Clears the block template cache, forcing the next call to get_block_template to build a new block template.
@@ -205,13 +247,16 @@ impl MiningManager {
 
     #[cfg(test)]
     pub(crate) fn block_template_builder(&self) -> BlockTemplateBuilder {
-        BlockTemplateBuilder::new(self.config.maximum_mass_per_block)
+        BlockTemplateBuilder::new(self.config.maximum_mass_per_block, self.config.template_tx_ordering)
     }
 
     /// validate_and_insert_transaction validates the given transaction, and
     /// adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// With [`RbfPolicy::Allowed`], the transaction may replace the mempool transactions it double spends, along with
+    /// their redeemers, if it pays a sufficiently higher feerate.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub fn validate_and_insert_transaction(
         &self,
@@ -219,8 +264,9 @@ impl MiningManager {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan)
+        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan, rbf_policy)
     }
 
     /// Exposed only for tests. Ordinary users should call `validate_and_insert_transaction` instead
@@ -230,15 +276,16 @@ impl MiningManager {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
         // read lock on mempool
-        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction)?;
+        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
         // no lock on mempool
         let validation_result = validate_mempool_transaction(consensus, &mut transaction);
         // write lock on mempool
         let mut mempool = self.mempool.write();
         if let Some(accepted_transaction) =
-            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan)?
+            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy)?
         {
             let unorphaned_transactions = mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
             drop(mempool);
@@ -297,6 +344,7 @@ impl MiningManager {
                         transaction,
                         priority,
                         Orphan::Forbidden,
+                        RbfPolicy::Forbidden,
                     ) {
                         Ok(Some(accepted_transaction)) => {
                             accepted_transactions.push(accepted_transaction.clone());
@@ -344,7 +392,7 @@ impl MiningManager {
             let mempool = self.mempool.read();
             let txs = chunk.filter_map(|tx| {
                 let transaction_id = tx.id();
-                match mempool.pre_validate_and_populate_transaction(consensus, tx) {
+                match mempool.pre_validate_and_populate_transaction(consensus, tx, RbfPolicy::Forbidden) {
                     Ok(tx) => Some(tx),
                     Err(RuleError::RejectAlreadyAccepted(transaction_id)) => {
                         debug!("Ignoring already accepted transaction {}", transaction_id);
@@ -386,7 +434,14 @@ impl MiningManager {
             let mut mempool = self.mempool.write();
             let txs = chunk.flat_map(|(transaction, validation_result)| {
                 let transaction_id = transaction.id();
-                match mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan) {
+                match mempool.post_validate_and_insert_transaction(
+                    consensus,
+                    validation_result,
+                    transaction,
+                    priority,
+                    orphan,
+                    RbfPolicy::Forbidden,
+                ) {
                     Ok(Some(accepted_transaction)) => {
                         insert_results.push(Ok(accepted_transaction.clone()));
                         self.counters.increase_tx_counts(1, priority);
@@ -411,6 +466,33 @@ impl MiningManager {
         insert_results
     }
 
+    /// Validates a list of possibly chained transactions and adds them, one by one and in topological order, to the set
+    /// of known transactions that have not yet been added to any block. Unlike the batch path, a transaction redeeming
+    /// an output of another transaction of the list is validated after its parent got inserted, so it is not orphaned.
+    ///
+    /// Returns a result per provided transaction, in the order of the provided transactions. Each result holds the
+    /// accepted transaction followed by the transactions it unorphaned, if any. The returned transactions are clones
+    /// of objects owned by the mempool.
+    pub fn validate_and_insert_chained_transactions(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+        orphan: Orphan,
+    ) -> Vec<MiningManagerResult<Vec<Arc<Transaction>>>> {
+        // Map every transaction id to its positions in the provided list, duplicates included
+        let mut positions: HashMap<TransactionId, VecDeque<usize>> = HashMap::with_capacity(transactions.len());
+        transactions.iter().enumerate().for_each(|(i, tx)| positions.entry(tx.id()).or_default().push_back(i));
+
+        let mut results = (0..transactions.len()).map(|_| None).collect_vec();
+        for transaction in transactions.topological_into_iter() {
+            let position = positions.get_mut(&transaction.id()).and_then(|x| x.pop_front()).unwrap();
+            results[position] =
+                Some(self.validate_and_insert_transaction(consensus, transaction, priority, orphan, RbfPolicy::Forbidden));
+        }
+        results.into_iter().map(|x| x.expect("every transaction is yielded by the topological iterator")).collect()
+    }
+
     fn next_transaction_chunk_upper_bound(&self, transactions: &[MutableTransaction], lower_bound: usize) -> Option<usize> {
         if lower_bound >= transactions.len() {
             return None;
@@ -441,12 +523,16 @@ impl MiningManager {
         self.mempool.read().has_transaction(transaction_id, query)
     }
 
-    pub fn get_all_transactions(&self, query: TransactionQuery) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
+    pub fn get_all_transactions(
+        &self,
+        query: TransactionQuery,
+        filter: &TransactionFilter,
+    ) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
         const TRANSACTION_CHUNK_SIZE: usize = 1000;
         // read lock on mempool by transaction chunks
         let transactions = if query.include_transaction_pool() {
-            let transaction_ids = self.mempool.read().get_all_transaction_ids(TransactionQuery::TransactionsOnly).0;
-            let mut transactions = Vec::with_capacity(self.mempool.read().transaction_count(TransactionQuery::TransactionsOnly));
+            let transaction_ids = self.mempool.read().get_all_transaction_ids(TransactionQuery::TransactionsOnly, filter).0;
+            let mut transactions = Vec::with_capacity(transaction_ids.len());
             for chunks in transaction_ids.chunks(TRANSACTION_CHUNK_SIZE) {
                 let mempool = self.mempool.read();
                 transactions.extend(chunks.iter().filter_map(|x| mempool.get_transaction(x, TransactionQuery::TransactionsOnly)));
@@ -457,7 +543,7 @@ impl MiningManager {
         };
         // read lock on mempool
         let orphans = if query.include_orphan_pool() {
-            self.mempool.read().get_all_transactions(TransactionQuery::OrphansOnly).1
+            self.mempool.read().get_all_transactions(TransactionQuery::OrphansOnly, filter).1
         } else {
             vec![]
         };
@@ -481,6 +567,61 @@ impl MiningManager {
         self.mempool.read().transaction_count(query)
     }
 
+    /// Returns the feerates a transaction should pay depending on the urgency of its inclusion
+    /// given the current mempool congestion
+    pub fn get_fee_estimate(&self) -> FeeEstimate {
+        self.mempool.read().fee_estimate()
+    }
+
+    /// Returns the ordering of the transactions of the block templates
+    pub fn template_tx_ordering(&self) -> TemplateTxOrdering {
+        self.config.template_tx_ordering
+    }
+
+    /// Returns the range of the transaction versions relayed and mined by the node
+    pub fn standard_transaction_versions(&self) -> RangeInclusive<u16> {
+        self.config.standard_transaction_versions()
+    }
+
+    /// Returns the mempool policy currently in force
+    pub fn mempool_policy(&self) -> MempoolPolicy {
+        self.mempool.read().config().policy()
+    }
+
+    /// Applies `policy` to the mempool, every parameter having to lie within the bounds set by the node config.
+    /// Returns the policy in force afterwards.
+    pub fn set_mempool_policy(&self, policy: MempoolPolicy) -> MiningManagerResult<MempoolPolicy> {
+        let mut mempool = self.mempool.write();
+        let config = mempool.config().with_policy(&policy, &self.config)?;
+        info!("Mempool policy updated: {:?}", policy);
+        mempool.set_config(Arc::new(config));
+        Ok(mempool.config().policy())
+    }
+
+    /// Returns the transactions and script public keys excluded from the block templates
+    pub fn template_exclusions(&self) -> TemplateExclusions {
+        self.mempool.read().template_exclusions().clone()
+    }
+
+    /// Applies `update` to the exclusions of the block templates, dropping the cached template so that the
+    /// next one complies. Returns the exclusions in force afterwards.
+    pub fn update_template_exclusions(&self, update: TemplateExclusionsUpdate) -> TemplateExclusions {
+        if update.is_empty() {
+            return self.template_exclusions();
+        }
+        let mut mempool = self.mempool.write();
+        info!(
+            "Block template exclusions updated: added transactions {:?}, script public keys {:?}; removed transactions {:?}, script public keys {:?}",
+            update.add_transaction_ids,
+            update.add_script_public_keys.iter().map(|spk| spk.script_as_hex()).collect_vec(),
+            update.remove_transaction_ids,
+            update.remove_script_public_keys.iter().map(|spk| spk.script_as_hex()).collect_vec(),
+        );
+        mempool.template_exclusions_mut().update(update);
+        self.block_template_cache.clear();
+        mempool.template_exclusions().clone()
+    }
+
     pub fn handle_new_block_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -512,6 +653,9 @@ impl MiningManager {
         // accepted transaction cache
         self.mempool.write().expire_accepted_transactions(consensus);
 
+        // high-priority submitters
+        self.mempool.write().prune_high_priority_submitters();
+
         // mempool
         let expired_low_priority_transactions = self.mempool.write().collect_expired_low_priority_transactions(consensus);
         for chunk in &expired_low_priority_transactions.iter().chunks(24) {
@@ -529,6 +673,17 @@ impl MiningManager {
         }
     }
 
+    /// Records `transaction_ids` as high-priority transactions submitted by the RPC connection `submitter` and enforces
+    /// the per-submitter quotas, evicting the oldest transactions of the submitter, along with their redeemers, on overflow.
+    ///
+    /// Returns the ids of all the evicted transactions.
+    pub fn track_high_priority_submission(&self, submitter: &str, transaction_ids: &[TransactionId]) -> Vec<TransactionId> {
+        self.mempool.write().track_high_priority_submission(submitter, transaction_ids).unwrap_or_else(|err| {
+            warn!("Failed to enforce the quota of RPC submitter {}: {}", submitter, err);
+            vec![]
+        })
+    }
+
     pub fn revalidate_high_priority_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -717,6 +872,116 @@ impl MiningManager {
         }
     }
 
+    /// Re-evaluates all the transactions of the transaction pool against the current consensus rules and
+    /// virtual UTXO set, evicting the ones that became invalid along with their redeemers.
+    ///
+    /// This is meant to be called when the validity of mempool transactions may have changed without any
+    /// block spending their inputs, like after a pruning point move or the activation of a new mass rule.
+    /// Otherwise such transactions would linger in the mempool until failing a block template build.
+    ///
+    /// Returns the ids of the evicted transactions.
+    pub fn reevaluate_transactions(&self, consensus: &dyn ConsensusApi, trigger: ReevaluationTrigger) -> Vec<TransactionId> {
+        const TRANSACTION_CHUNK_SIZE: usize = 1000;
+
+        // read lock on mempool
+        let (transactions, _) = self.mempool.read().get_all_transactions(TransactionQuery::TransactionsOnly, &Default::default());
+        if transactions.is_empty() {
+            debug!("<> Re-evaluating the mempool after {} found no transactions", trigger);
+            return vec![];
+        }
+        debug!("<> Re-evaluating {} mempool transactions after {}...", transactions.len(), trigger);
+
+        // Transactions are processed in topological order so that the redeemers of an invalid transaction
+        // get removed along with it before being evaluated.
+        let sorted_transactions = transactions.topological_into_iter();
+
+        // read lock on mempool by transaction chunks
+        // The compute mass and the standardness in isolation are re-checked since both may depend on the rules
+        // in effect. Transactions failing these checks are directly marked for eviction.
+        let mut transactions = Vec::with_capacity(sorted_transactions.len());
+        let mut rejected = Vec::new();
+        for chunk in &sorted_transactions.chunks(TRANSACTION_CHUNK_SIZE) {
+            let mempool = self.mempool.read();
+            for mut transaction in chunk {
+                if !mempool.has_transaction(&transaction.id(), TransactionQuery::TransactionsOnly) {
+                    continue;
+                }
+                transaction.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
+                if !self.config.accept_non_standard {
+                    if let Err(err) = mempool.check_transaction_standard_in_isolation(&transaction) {
+                        rejected.push((transaction.id(), RuleError::from(err)));
+                        continue;
+                    }
+                }
+                transaction.clear_entries();
+                mempool.populate_mempool_entries(&mut transaction);
+                transactions.push(transaction);
+            }
+        }
+
+        // no lock on mempool
+        // We process the transactions by chunks of max block mass to prevent locking the virtual processor for too long.
+        let mut lower_bound: usize = 0;
+        let mut validation_results = Vec::with_capacity(transactions.len());
+        while let Some(upper_bound) = self.next_transaction_chunk_upper_bound(&transactions, lower_bound) {
+            assert!(lower_bound < upper_bound, "the chunk is never empty");
+            validation_results
+                .extend(validate_mempool_transactions_in_parallel(consensus, &mut transactions[lower_bound..upper_bound]));
+            lower_bound = upper_bound;
+        }
+        assert_eq!(transactions.len(), validation_results.len(), "every transaction should have a matching validation result");
+
+        // write lock on mempool
+        let mut evicted_ids = Vec::new();
+        let mut removals = rejected.into_iter().map(|(id, err)| (id, Err(err))).collect::<Vec<_>>();
+        removals.extend(
+            transactions
+                .into_iter()
+                .zip(validation_results)
+                .map(|(transaction, result)| (transaction.id(), result.map(|_| transaction))),
+        );
+        for chunk in &removals.into_iter().chunks(TRANSACTION_CHUNK_SIZE) {
+            let mut mempool = self.mempool.write();
+            for (transaction_id, result) in chunk {
+                let result = result.and_then(|transaction| match self.config.accept_non_standard {
+                    true => Ok(transaction),
+                    false => mempool.check_transaction_standard_in_context(&transaction).map(|_| transaction).map_err(RuleError::from),
+                });
+                match result {
+                    Ok(transaction) => {
+                        mempool.update_revalidated_transaction(transaction);
+                    }
+                    // A missing outpoint may only be the sign of a concurrent block acceptance not yet handled by the
+                    // mempool, so the transaction is left for the regular block processing to handle.
+                    Err(RuleError::RejectMissingOutpoint) => {}
+                    Err(err) => {
+                        let extra_info = format!(" error: {}", err);
+                        match mempool.remove_transaction_collecting_ids(
+                            &transaction_id,
+                            true,
+                            TxRemovalReason::Reevaluation,
+                            extra_info.as_str(),
+                        ) {
+                            Ok(ids) => evicted_ids.extend(ids),
+                            Err(err) => warn!("Failed to remove transaction {} from mempool: {}", transaction_id, err),
+                        }
+                    }
+                }
+            }
+        }
+
+        if evicted_ids.is_empty() {
+            info!("Re-evaluated the mempool after {}, all transactions are still valid", trigger);
+        } else {
+            info!("Re-evaluated the mempool after {} and evicted {} invalid transactions", trigger, evicted_ids.len());
+            // The channel holds a receiver of its own, so only send when somebody else is listening
+            if self.eviction_channel.receiver_count() > 1 {
+                let _ = self.eviction_channel.try_send(EvictedTransactions::new(evicted_ids.clone(), trigger));
+            }
+        }
+        evicted_ids
+    }
+
     /// is_transaction_output_dust returns whether or not the passed transaction output
     /// amount is considered dust or not based on the configured minimum transaction
     /// relay fee.
@@ -728,6 +993,12 @@ impl MiningManager {
         self.mempool.read().is_transaction_output_dust(transaction_output)
     }
 
+    /// Returns the minimum fee required for a transaction with the passed mass to be
+    /// accepted into the mempool and relayed.
+    pub fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+        self.mempool.read().minimum_required_transaction_relay_fee(mass)
+    }
+
     pub fn has_accepted_transaction(&self, transaction_id: &TransactionId) -> bool {
         self.mempool.read().has_accepted_transaction(transaction_id)
     }
@@ -756,6 +1027,10 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_block_template(c, &miner_data)).await
     }
 
+    pub fn template_tx_ordering(&self) -> TemplateTxOrdering {
+        self.inner.template_tx_ordering()
+    }
+
     /// Validates a transaction and adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
@@ -766,8 +1041,12 @@ impl MiningManagerProxy {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan)).await
+        consensus
+            .clone()
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan, rbf_policy))
+            .await
     }
 
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
@@ -788,6 +1067,24 @@ impl MiningManagerProxy {
             .await
     }
 
+    /// Validates a list of possibly chained transactions and adds them, one by one and in topological order, to the set
+    /// of known transactions that have not yet been added to any block.
+    ///
+    /// Returns a result per provided transaction, in the order of the provided transactions. The returned transactions
+    /// are clones of objects owned by the mempool.
+    pub async fn validate_and_insert_chained_transactions(
+        self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+        orphan: Orphan,
+    ) -> Vec<MiningManagerResult<Vec<Arc<Transaction>>>> {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| self.inner.validate_and_insert_chained_transactions(c, transactions, priority, orphan))
+            .await
+    }
+
     pub async fn handle_new_block_transactions(
         self,
         consensus: &ConsensusProxy,
@@ -804,6 +1101,12 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.expire_low_priority_transactions(c)).await;
     }
 
+    /// Records high-priority transactions submitted by an RPC connection and enforces its quota.
+    /// See [`MiningManager::track_high_priority_submission`].
+    pub async fn track_high_priority_submission(self, submitter: String, transaction_ids: Vec<TransactionId>) -> Vec<TransactionId> {
+        spawn_blocking(move || self.inner.track_high_priority_submission(&submitter, &transaction_ids)).await.unwrap()
+    }
+
     pub async fn revalidate_high_priority_transactions(
         self,
         consensus: &ConsensusProxy,
@@ -812,6 +1115,16 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.revalidate_high_priority_transactions(c, transaction_ids_sender)).await;
     }
 
+    /// Re-evaluates all the mempool transactions, evicting the ones that became invalid.
+    /// See [`MiningManager::reevaluate_transactions`].
+    pub async fn reevaluate_transactions(self, consensus: &ConsensusProxy, trigger: ReevaluationTrigger) -> Vec<TransactionId> {
+        consensus.clone().spawn_blocking(move |c| self.inner.reevaluate_transactions(c, trigger)).await
+    }
+
+    pub fn eviction_receiver(&self) -> Receiver<EvictedTransactions> {
+        self.inner.eviction_receiver()
+    }
+
     /// Try to return a mempool transaction by its id.
     ///
     /// Note: the transaction is an orphan if tx.is_fully_populated() returns false.
@@ -828,8 +1141,32 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.transaction_count(query)).await.unwrap()
     }
 
-    pub async fn get_all_transactions(self, query: TransactionQuery) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
-        spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
+    pub async fn get_fee_estimate(self) -> FeeEstimate {
+        spawn_blocking(move || self.inner.get_fee_estimate()).await.unwrap()
+    }
+
+    pub async fn mempool_policy(self) -> MempoolPolicy {
+        spawn_blocking(move || self.inner.mempool_policy()).await.unwrap()
+    }
+
+    pub async fn set_mempool_policy(self, policy: MempoolPolicy) -> MiningManagerResult<MempoolPolicy> {
+        spawn_blocking(move || self.inner.set_mempool_policy(policy)).await.unwrap()
+    }
+
+    pub async fn template_exclusions(self) -> TemplateExclusions {
+        spawn_blocking(move || self.inner.template_exclusions()).await.unwrap()
+    }
+
+    pub async fn update_template_exclusions(self, update: TemplateExclusionsUpdate) -> TemplateExclusions {
+        spawn_blocking(move || self.inner.update_template_exclusions(update)).await.unwrap()
+    }
+
+    pub async fn get_all_transactions(
+        self,
+        query: TransactionQuery,
+        filter: TransactionFilter,
+    ) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
+        spawn_blocking(move || self.inner.get_all_transactions(query, &filter)).await.unwrap()
     }
 
     /// get_transactions_by_addresses returns the sending and receiving transactions for
@@ -868,6 +1205,18 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    pub fn is_transaction_output_dust(&self, transaction_output: &TransactionOutput) -> bool {
+        self.inner.is_transaction_output_dust(transaction_output)
+    }
+
+    pub fn standard_transaction_versions(&self) -> RangeInclusive<u16> {
+        self.inner.standard_transaction_versions()
+    }
+
+    pub fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+        self.inner.minimum_required_transaction_relay_fee(mass)
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
@@ -5,11 +5,20 @@ mod tests {
         errors::{MiningManagerError, MiningManagerResult},
         manager::MiningManager,
         mempool::{
-            config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+            config::{
+                Config, MempoolPolicy, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE, DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS,
+                DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS, MAXIMUM_POLICY_EXPIRE_INTERVAL_SECONDS,
+                MINIMUM_POLICY_EXPIRE_INTERVAL_SECONDS,
+            },
             errors::RuleError,
-            tx::{Orphan, Priority},
+            tx::{Orphan, Priority, RbfPolicy},
+        },
+        model::{
+            candidate_tx::CandidateTransaction,
+            evicted_txs::ReevaluationTrigger,
+            template_exclusions::{TemplateExclusionReason, TemplateExclusionsUpdate},
+            tx_query::{TransactionFilter, TransactionQuery},
         },
-        model::{candidate_tx::CandidateTransaction, tx_query::TransactionQuery},
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -27,12 +36,13 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use kaspa_core::time::{unix_now, VirtualClock};
     use kaspa_hashes::Hash;
     use kaspa_txscript::{
         pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, op_true_script},
     };
-    use std::sync::Arc;
+    use std::{collections::HashSet, sync::Arc};
     use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel};
 
     const TARGET_TIME_PER_BLOCK: u64 = 1_000;
@@ -52,13 +62,14 @@ mod tests {
                 transaction.clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "inserting a valid transaction failed");
         }
 
         // The UtxoEntry was filled manually for those transactions, so the transactions won't be considered orphans.
         // Therefore, all the transactions expected to be contained in the mempool.
-        let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
+        let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly, &Default::default());
         assert_eq!(
             transactions_to_insert.len(),
             transactions_from_pool.len(),
@@ -100,9 +111,10 @@ mod tests {
             transaction_not_an_orphan.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "inserting the child transaction {} into the mempool failed", transaction_not_an_orphan.id());
-        let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
+        let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly, &Default::default());
         assert!(
             contained_by(transaction_not_an_orphan.id(), &transactions_from_pool),
             "missing transaction {} in the mempool",
@@ -110,6 +122,56 @@ mod tests {
         );
     }
 
+    /// test_get_all_transactions_with_filter verifies that mempool queries only return the transactions matching the filter.
+    #[test]
+    fn test_get_all_transactions_with_filter() {
+        const TX_COUNT: u32 = 10;
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        for i in 0..TX_COUNT {
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                create_transaction_with_utxo_entry(i, 0),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction failed");
+        }
+        // The child is unknown to the mempool so the grand child is an orphan
+        let child = create_child_and_parent_txs_and_add_parent_to_consensus(&consensus);
+        let orphan = create_transaction(&child, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan,
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting an orphan transaction failed");
+
+        let count = |filter: TransactionFilter| {
+            let (transactions, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &filter);
+            (transactions.len(), orphans.len())
+        };
+        assert_eq!(count(TransactionFilter::default()), (TX_COUNT as usize, 1));
+        assert_eq!(count(TransactionFilter { min_fee_rate: Some(0.0), ..Default::default() }), (TX_COUNT as usize, 0));
+        assert_eq!(count(TransactionFilter { min_fee_rate: Some(f64::MAX), ..Default::default() }), (0, 0));
+        assert_eq!(count(TransactionFilter { min_added_at_daa_score: Some(1), ..Default::default() }), (0, 0));
+
+        let (script_public_key, _) = op_true_script();
+        let other_script_public_key = ScriptPublicKey::new(0, scriptvec![]);
+        assert_eq!(
+            count(TransactionFilter { script_public_keys: [script_public_key].into_iter().collect(), ..Default::default() }),
+            (TX_COUNT as usize, 1)
+        );
+        assert_eq!(
+            count(TransactionFilter { script_public_keys: [other_script_public_key].into_iter().collect(), ..Default::default() }),
+            (0, 0)
+        );
+    }
+
     /// test_simulated_error_in_consensus verifies that a predefined result is actually
     /// returned by the consensus mock as expected when the mempool tries to validate and
     /// insert a transaction.
@@ -132,6 +194,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         ));
 
         assert_eq!(
@@ -158,6 +221,7 @@ mod tests {
             transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "mempool should have accepted a valid transaction but did not");
 
@@ -167,6 +231,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double submit of the same transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDuplicate(transaction_id))) = result {
@@ -185,6 +250,119 @@ mod tests {
         }
     }
 
+    // test_replace_by_fee verifies that a transaction double-spending another transaction already in the mempool
+    // replaces it only when replacement is allowed and it pays a sufficiently higher feerate.
+    #[test]
+    fn test_replace_by_fee() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let transaction = create_child_and_parent_txs_and_add_parent_to_consensus(&consensus);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the mempool should accept a valid transaction when it is able to populate its UTXO entries");
+
+        // A replacement paying only slightly more is rejected
+        let mut low_fee_replacement = transaction.clone();
+        low_fee_replacement.outputs[0].value -= 1;
+        low_fee_replacement.finalize();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            low_fee_replacement.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectReplacementFeerateTooLow(_, replaced_id, _, _))) if replaced_id == transaction.id()),
+            "the mempool should refuse a replacement with an insufficient feerate but returns {:?}",
+            result
+        );
+
+        // A replacement paying a sufficiently higher feerate is rejected if replacement is forbidden
+        let mut replacement = transaction.clone();
+        replacement.outputs[0].value -= 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        replacement.finalize();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, _)))),
+            "the mempool should refuse a double spend when replacement is forbidden but returns {:?}",
+            result
+        );
+
+        // and accepted, replacing the double spent transaction, if replacement is allowed
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(result.is_ok(), "the mempool should accept a replacement paying a higher feerate but returns {:?}", result);
+        assert!(
+            mining_manager.get_transaction(&transaction.id(), TransactionQuery::All).is_none(),
+            "the replaced transaction should be removed from the mempool"
+        );
+        assert!(
+            mining_manager.get_transaction(&replacement.id(), TransactionQuery::All).is_some(),
+            "the replacement transaction should be in the mempool"
+        );
+    }
+
+    // test_replace_by_fee_in_full_mempool verifies that a replacement entering a full mempool takes the slot
+    // of the transaction it replaces, without evicting any other transaction to make room.
+    #[test]
+    fn test_replace_by_fee_in_full_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        // Limit the mempool to 2 transactions
+        config.maximum_transaction_count = 2;
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let (parent_txs, _) = create_arrays_of_parent_and_children_transactions(&consensus, 2);
+        for transaction in parent_txs.iter() {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept a valid transaction but returns {:?}", result);
+        }
+
+        let mut replacement = parent_txs[0].clone();
+        replacement.outputs[0].value -= 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        replacement.finalize();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert!(result.is_ok(), "the mempool should accept a replacement paying a higher feerate but returns {:?}", result);
+        assert!(mining_manager.get_transaction(&parent_txs[0].id(), TransactionQuery::All).is_none());
+        assert!(
+            mining_manager.get_transaction(&parent_txs[1].id(), TransactionQuery::All).is_some(),
+            "the transaction not double spent by the replacement should remain in the mempool"
+        );
+        assert!(mining_manager.get_transaction(&replacement.id(), TransactionQuery::All).is_some());
+    }
+
     // test_double_spend_in_mempool verifies that an attempt to insert a transaction double-spending
     // another transaction already in the mempool will result in raising an appropriate error.
     #[test]
@@ -200,8 +378,13 @@ mod tests {
             transaction.id()
         );
 
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), transaction.clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the mempool should accept a valid transaction when it is able to populate its UTXO entries");
 
         let mut double_spending_transaction = transaction.clone();
@@ -217,6 +400,7 @@ mod tests {
             double_spending_transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double spend transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, transaction_id))) = result {
@@ -250,6 +434,7 @@ mod tests {
                 transaction.tx.as_ref().clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "the insertion of a new valid transaction in the mempool failed");
         }
@@ -308,6 +493,7 @@ mod tests {
             transaction_in_the_mempool.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok());
 
@@ -340,11 +526,16 @@ mod tests {
         assert_eq!(parent_txs.len(), TX_PAIRS_COUNT);
         assert_eq!(child_txs.len(), TX_PAIRS_COUNT);
         for orphan in child_txs.iter() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), orphan.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                orphan.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid orphan transaction {}", orphan.id());
         }
-        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
         assert!(populated_txs.is_empty(), "the mempool should have no populated transaction since only orphans were submitted");
         for orphan in orphans.iter() {
             assert!(
@@ -381,7 +572,7 @@ mod tests {
             mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(added_parent_txs.iter()));
         assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
         let unorphaned_txs = result.unwrap();
-        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
         assert_eq!(
             unorphaned_txs.len(), child_txs.len() - SKIPPED_TXS,
             "the mempool is expected to have unorphaned all but one child transactions after all but one parent transactions were accepted by the consensus: expected: {}, got: {}",
@@ -467,7 +658,7 @@ mod tests {
         assert!(result.is_ok(), "mining manager should handle new block transactions successfully but returns {result:?}");
 
         let unorphaned_txs = result.unwrap();
-        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
         assert_eq!(
             0,
             unorphaned_txs.len(),
@@ -485,11 +676,16 @@ mod tests {
         );
 
         // Add the remaining parent transaction into the mempool
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_txs[0].clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_txs[0].clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion of the remaining parent transaction in the mempool failed");
         let unorphaned_txs = result.unwrap();
-        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
         assert_eq!(
             unorphaned_txs.len(), SKIPPED_TXS + 1,
             "the mempool is expected to have unorphaned the remaining child transaction after the matching parent transaction was inserted into the mempool: expected: {}, got: {}",
@@ -519,6 +715,46 @@ mod tests {
         assert_eq!(0, orphans.len(), "the orphan pool is expected to be empty: {}, got: {}", 0, orphans.len());
     }
 
+    /// test_validate_and_insert_chained_transactions verifies that children submitted along with their parents, in any order,
+    /// are inserted in the mempool and not orphaned, and that a result is returned per transaction in submission order.
+    #[test]
+    fn test_validate_and_insert_chained_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        const TX_PAIRS_COUNT: usize = 5;
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+
+        // Submit the children first, followed by a true orphan and then by the parents
+        let invalid_tx =
+            create_transaction(&create_transaction_without_input(vec![SOMPI_PER_KASPA]), DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let transactions = child_txs.iter().chain(std::iter::once(&invalid_tx)).chain(parent_txs.iter()).cloned().collect::<Vec<_>>();
+        let results = mining_manager.validate_and_insert_chained_transactions(
+            consensus.as_ref(),
+            transactions.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+        );
+        assert_eq!(transactions.len(), results.len(), "a result should be returned per transaction");
+        for (transaction, result) in transactions.iter().zip(results.iter()) {
+            if transaction.id() == invalid_tx.id() {
+                assert!(result.is_err(), "the orphan transaction should be rejected");
+            } else {
+                let accepted =
+                    result.as_ref().unwrap_or_else(|err| panic!("transaction {} should be accepted: {err}", transaction.id()));
+                assert_eq!(transaction.id(), accepted[0].id(), "the result should match the transaction at the same position");
+            }
+        }
+
+        let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
+        assert!(orphans.is_empty(), "no child transaction should be orphaned");
+        assert_eq!(parent_txs.len() + child_txs.len(), populated_txs.len());
+        for transaction in parent_txs.iter().chain(child_txs.iter()) {
+            assert!(contained_by(transaction.id(), &populated_txs), "transaction {} should exist in the mempool", transaction.id());
+        }
+    }
+
     /// test_high_priority_transactions verifies that inserting a high priority orphan transaction when the orphan pool is full
     /// evicts a low-priority transaction, if available, or fails if the pool is already filled with high priority transactions.
     #[test]
@@ -592,8 +828,13 @@ mod tests {
 
         // Try submit children while rejecting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Forbidden);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_err(), "mempool should reject an orphan transaction with {:?} when asked to do so", test.priority);
             if let Err(MiningManagerError::MempoolError(RuleError::RejectDisallowedOrphan(transaction_id))) = result {
                 assert_eq!(
@@ -613,8 +854,13 @@ mod tests {
 
         // Try submit children while accepting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert_eq!(
                 test.should_enter_orphan_pool,
                 result.is_ok(),
@@ -642,8 +888,13 @@ mod tests {
 
         // Submit all the parents
         for (i, (tx, test)) in parent_txs.iter().zip(tests.iter()).enumerate() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "mempool should accept a valid transaction with {:?} when asked to do so", test.priority,);
             let unorphaned_txs = result.as_ref().unwrap();
             assert_eq!(
@@ -682,8 +933,13 @@ mod tests {
 
         // Add to mempool a transaction that spends child_tx_2 (as high priority)
         let spending_tx = create_transaction(&child_tx_2, 1_000);
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), spending_tx.clone(), Priority::High, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            spending_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion in the mempool of the spending transaction failed");
 
         // Revalidate, to make sure spending_tx is still valid
@@ -720,11 +976,243 @@ mod tests {
         );
 
         // And the mempool should be empty too
-        let (populated_txs, orphan_txs) = mining_manager.get_all_transactions(TransactionQuery::All);
+        let (populated_txs, orphan_txs) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
         assert!(populated_txs.is_empty(), "mempool should be empty");
         assert!(orphan_txs.is_empty(), "orphan pool should be empty");
     }
 
+    /// test_reevaluate_transactions verifies that a re-evaluation of the mempool evicts the transactions no longer
+    /// accepted by the consensus along with their redeemers, reports them through the eviction channel and keeps
+    /// the still valid transactions.
+    #[test]
+    fn test_reevaluate_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let eviction_receiver = mining_manager.eviction_receiver();
+
+        // Add to the mempool a chain of two transactions and an independent transaction
+        let (parent_tx, child_tx) = create_parent_and_children_transactions(&consensus, vec![3000 * SOMPI_PER_KASPA]);
+        let (other_tx, _) = create_parent_and_children_transactions(&consensus, vec![2000 * SOMPI_PER_KASPA]);
+        for transaction in [parent_tx.clone(), child_tx.clone(), other_tx.clone()] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction,
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the insertion of a valid transaction in the mempool failed");
+        }
+
+        // With no rule change, the re-evaluation keeps all transactions
+        let evicted = mining_manager.reevaluate_transactions(consensus.as_ref(), ReevaluationTrigger::PruningPointMove);
+        assert!(evicted.is_empty(), "no transaction should be evicted, got {evicted:?}");
+        assert!(eviction_receiver.try_recv().is_err(), "no eviction should be reported");
+
+        // Simulate a rule activation making the parent transaction invalid
+        consensus.set_status(parent_tx.id(), Err(TxRuleError::MassIncomputable));
+        let mut evicted = mining_manager.reevaluate_transactions(consensus.as_ref(), ReevaluationTrigger::ParameterActivation);
+        evicted.sort();
+        let mut expected = vec![parent_tx.id(), child_tx.id()];
+        expected.sort();
+        assert_eq!(expected, evicted, "the invalid transaction and its redeemer should be evicted");
+
+        let event = eviction_receiver.try_recv().expect("the eviction should be reported");
+        assert_eq!(ReevaluationTrigger::ParameterActivation, event.trigger);
+        assert_eq!(2, event.transaction_ids.len());
+
+        let (populated_txs, orphan_txs) = mining_manager.get_all_transactions(TransactionQuery::All, &Default::default());
+        assert_eq!(1, populated_txs.len(), "only the independent transaction should remain in the mempool");
+        assert_eq!(other_tx.id(), populated_txs[0].id());
+        assert!(orphan_txs.is_empty(), "orphan pool should be empty");
+    }
+
+    // test_high_priority_submitter_quota verifies that the oldest high-priority transactions of a submitter
+    // exceeding its quota get evicted along with their redeemers, leaving other submitters untouched.
+    #[test]
+    fn test_high_priority_submitter_quota() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        // Limit every submitter to 2 high-priority transactions
+        config.maximum_high_priority_transaction_count_per_submitter = 2;
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 3);
+        let submissions = [
+            ("grpc/a", parent_txs[0].clone(), vec![]),
+            ("grpc/a", child_txs[0].clone(), vec![]),
+            ("grpc/b", parent_txs[1].clone(), vec![]),
+            ("grpc/a", parent_txs[2].clone(), vec![parent_txs[0].id(), child_txs[0].id()]),
+        ];
+        for (submitter, transaction, mut expected_evictions) in submissions {
+            let transaction_id = transaction.id();
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction,
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", transaction_id);
+            let mut evicted = mining_manager.track_high_priority_submission(submitter, &[transaction_id]);
+            evicted.sort();
+            expected_evictions.sort();
+            assert_eq!(
+                expected_evictions, evicted,
+                "unexpected evictions after the submission of {} by {}",
+                transaction_id, submitter
+            );
+        }
+
+        let (populated_txs, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly, &Default::default());
+        let mut remaining = populated_txs.iter().map(|x| x.id()).collect::<Vec<_>>();
+        remaining.sort();
+        let mut expected = vec![parent_txs[1].id(), parent_txs[2].id()];
+        expected.sort();
+        assert_eq!(expected, remaining, "only the transactions within the quotas should remain in the mempool");
+    }
+
+    // test_mempool_policy verifies that the mempool policy gets updated at runtime within its bounds only.
+    #[test]
+    fn test_mempool_policy() {
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let initial = mining_manager.mempool_policy();
+        assert_eq!(initial.transaction_expire_interval_seconds, DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS);
+        assert_eq!(initial.orphan_expire_interval_seconds, DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS);
+
+        let policy = MempoolPolicy {
+            transaction_expire_interval_seconds: 20,
+            orphan_expire_interval_seconds: 15,
+            maximum_transaction_count: initial.maximum_transaction_count / 10,
+            maximum_orphan_transaction_count: 0,
+        };
+        assert_eq!(Ok(policy), mining_manager.set_mempool_policy(policy).map_err(|err| err.to_string()));
+        assert_eq!(policy, mining_manager.mempool_policy());
+
+        let out_of_range_policies = [
+            MempoolPolicy { transaction_expire_interval_seconds: MINIMUM_POLICY_EXPIRE_INTERVAL_SECONDS - 1, ..policy },
+            MempoolPolicy { orphan_expire_interval_seconds: MAXIMUM_POLICY_EXPIRE_INTERVAL_SECONDS + 1, ..policy },
+            MempoolPolicy { maximum_transaction_count: 0, ..policy },
+            // Counts cannot exceed the ones the node started with
+            MempoolPolicy { maximum_transaction_count: initial.maximum_transaction_count + 1, ..policy },
+            MempoolPolicy { maximum_orphan_transaction_count: initial.maximum_orphan_transaction_count + 1, ..policy },
+        ];
+        for out_of_range_policy in out_of_range_policies {
+            let result = mining_manager.set_mempool_policy(out_of_range_policy);
+            assert!(
+                matches!(result, Err(MiningManagerError::MempoolPolicyOutOfRange(..))),
+                "policy {:?} should be rejected",
+                out_of_range_policy
+            );
+            assert_eq!(policy, mining_manager.mempool_policy(), "a rejected policy should leave the policy in force unchanged");
+        }
+
+        assert_eq!(Ok(initial), mining_manager.set_mempool_policy(initial).map_err(|err| err.to_string()));
+    }
+
+    // test_template_exclusions verifies that excluded transactions stay in the mempool but are left out of the
+    // block candidates and templates, and are candidates again once their exclusion is lifted.
+    #[test]
+    fn test_template_exclusions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let transactions = (0..3).map(|i| create_transaction_with_utxo_entry(i, 0)).collect::<Vec<_>>();
+        for transaction in transactions.iter() {
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction failed");
+        }
+        let candidate_ids = || mining_manager.block_candidate_transactions().iter().map(|x| x.tx.id()).collect::<HashSet<_>>();
+        assert_eq!(candidate_ids().len(), 3);
+
+        // Exclusion by transaction id
+        let exclusions = mining_manager.update_template_exclusions(TemplateExclusionsUpdate {
+            add_transaction_ids: vec![transactions[0].id()],
+            ..Default::default()
+        });
+        assert_eq!(exclusions.transaction_ids().len(), 1);
+        assert!(!candidate_ids().contains(&transactions[0].id()));
+        assert_eq!(candidate_ids().len(), 2);
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(
+            template.block.transactions.len(),
+            3,
+            "the template should contain the coinbase and the 2 non-excluded transactions"
+        );
+        assert!(!contained_by(transactions[0].id(), &template.block.transactions));
+        assert!(mining_manager.has_transaction(&transactions[0].id(), TransactionQuery::TransactionsOnly));
+
+        // Exclusion by script public key, all the transactions spending from and paying to the same one
+        let (script_public_key, _) = op_true_script();
+        assert_eq!(exclusions.exclusion_reason(&transactions[1]), None);
+        let exclusions = mining_manager.update_template_exclusions(TemplateExclusionsUpdate {
+            add_script_public_keys: vec![script_public_key.clone()],
+            ..Default::default()
+        });
+        assert_eq!(exclusions.exclusion_reason(&transactions[0]), Some(TemplateExclusionReason::TransactionId));
+        assert_eq!(
+            exclusions.exclusion_reason(&transactions[1]),
+            Some(TemplateExclusionReason::SpendsFrom(script_public_key.clone()))
+        );
+        assert!(candidate_ids().is_empty());
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(template.block.transactions.len(), 1, "the updated exclusions should apply to the next template at once");
+
+        // Lifting the exclusions
+        let exclusions = mining_manager.update_template_exclusions(TemplateExclusionsUpdate {
+            remove_transaction_ids: vec![transactions[0].id()],
+            remove_script_public_keys: vec![script_public_key],
+            ..Default::default()
+        });
+        assert!(exclusions.is_empty());
+        assert_eq!(candidate_ids().len(), 3);
+    }
+
+    // test_block_template_cache_lifetime verifies, with a virtual clock, that a cached block template is served
+    // until its lifetime ends and is then rebuilt with the transactions entered in the mempool meanwhile.
+    #[test]
+    fn test_block_template_cache_lifetime() {
+        const CACHE_LIFETIME: u64 = 1_000;
+        let consensus = Arc::new(ConsensusMock::new());
+        let clock = Arc::new(VirtualClock::new(1_000_000));
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS).with_clock(clock.clone());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::with_config(config, Some(CACHE_LIFETIME), counters);
+        let miner_data = get_miner_data(Prefix::Testnet);
+
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(template.block.transactions.len(), 1, "the first template should only contain the coinbase");
+
+        let transaction = create_transaction_with_utxo_entry(0, 0);
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting a valid transaction failed");
+
+        clock.advance(CACHE_LIFETIME);
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(template.block.transactions.len(), 1, "the cached template should be served until its lifetime ends");
+
+        clock.advance(1);
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(template.block.transactions.len(), 2, "an expired template should be rebuilt");
+        assert_eq!(template.block.transactions[1].id(), transaction.id());
+    }
+
     // test_modify_block_template verifies that modifying a block template changes coinbase data correctly.
     #[test]
     fn test_modify_block_template() {
@@ -737,11 +1225,21 @@ mod tests {
         let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
 
         for (parent_tx, child_tx) in parent_txs.iter().zip(child_txs.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                parent_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid parent transaction {}", parent_tx.id());
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), child_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                child_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid child transaction {}", parent_tx.id());
         }
 
@@ -853,7 +1351,7 @@ mod tests {
         let expected_template = result.unwrap();
 
         // Modify to miner_data_1
-        let result = BlockTemplateBuilder::modify_block_template(consensus, &miner_data_1, &expected_template);
+        let result = BlockTemplateBuilder::modify_block_template(consensus, &miner_data_1, &expected_template, unix_now());
         assert!(result.is_ok(), "modify block template failed for miner data 1");
         let mut modified_template = result.unwrap();
         // Make sure timestamps are equal before comparing the hash
@@ -872,7 +1370,7 @@ mod tests {
         assert_ne!(expected_block.hash(), modified_block.hash(), "built and modified blocks should have different hashes");
 
         // And modify back to miner_data_2
-        let result = BlockTemplateBuilder::modify_block_template(consensus, &miner_data_2, &modified_template);
+        let result = BlockTemplateBuilder::modify_block_template(consensus, &miner_data_2, &modified_template, unix_now());
         assert!(result.is_ok(), "modify block template failed for miner data 2");
         let mut modified_template_2 = result.unwrap();
         // Make sure timestamps are equal before comparing the hash
@@ -1,4 +1,5 @@
 use crate::mempool::{
+    config::{MAXIMUM_DATA_CARRIER_SCRIPT_SIZE, MAXIMUM_STANDARD_TRANSACTION_MASS},
     errors::{NonStandardError, NonStandardResult},
     Mempool,
 };
@@ -7,7 +8,7 @@ use kaspa_consensus_core::{
     mass,
     tx::{MutableTransaction, PopulatedTransaction, TransactionOutput},
 };
-use kaspa_txscript::{get_sig_op_count, is_unspendable, script_class::ScriptClass};
+use kaspa_txscript::{get_sig_op_count, is_data_carrier, is_unspendable, script_class::ScriptClass};
 
 /// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
 /// that are considered standard in a pay-to-script-hash script.
@@ -32,19 +33,15 @@ const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
 /// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
 const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
 
-/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
-/// are considered standard and will therefore be relayed and considered for mining.
-const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
-
 impl Mempool {
     pub(crate) fn check_transaction_standard_in_isolation(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
         let transaction_id = transaction.id();
 
         // The transaction must be a currently supported version.
         //
-        // This check is currently mirrored in consensus.
-        // However, in a later version of Kaspa the consensus-valid transaction version range might diverge from the
-        // standard transaction version range, and thus the validation should happen in both levels.
+        // The consensus-valid transaction versions diverge from the standard ones as soon as a version 2 activation
+        // is scheduled by the network params: consensus then accepts version 2 while the standard range only
+        // includes it when configured so. The validation thus happens in both levels.
         if transaction.tx.version > self.config.maximum_standard_transaction_version
             || transaction.tx.version < self.config.minimum_standard_transaction_version
         {
@@ -91,6 +88,11 @@ impl Mempool {
                 return Err(NonStandardError::RejectScriptPublicKeyVersion(transaction_id, i));
             }
 
+            // A zero-value data output is standard when the data carrier policy is enabled
+            if self.is_exempted_data_carrier_output(output) {
+                continue;
+            }
+
             if ScriptClass::from_script(&output.script_public_key) == ScriptClass::NonStandard {
                 return Err(NonStandardError::RejectOutputScriptClass(transaction_id, i));
             }
@@ -104,15 +106,24 @@ impl Mempool {
     }
 
     /// is_transaction_output_dust returns whether or not the passed transaction output
-    /// amount is considered dust or not based on the configured minimum transaction
-    /// relay fee.
+    /// amount is considered dust or not based on the configured dust policy.
     ///
-    /// Dust is defined in terms of the minimum transaction relay fee. In particular,
-    /// if the cost to the network to spend coins is more than 1/3 of the minimum
-    /// transaction relay fee, it is considered dust.
+    /// Dust is defined in terms of the dust relay fee. In particular, if the cost
+    /// to the network to spend coins is more than 1/3 of the dust relay fee, it is
+    /// considered dust.
+    ///
+    /// Dust is further defined as any value below the hard floor of the dust policy,
+    /// except for zero-value data outputs when the data carrier policy is enabled.
     ///
     /// It is exposed by [MiningManager] for use by transaction generators and wallets.
     pub(crate) fn is_transaction_output_dust(&self, transaction_output: &TransactionOutput) -> bool {
+        let dust_policy = &self.config.dust_policy;
+
+        // Data outputs carry no value and are exempted if the policy allows them.
+        if self.is_exempted_data_carrier_output(transaction_output) {
+            return false;
+        }
+
         // Unspendable outputs are considered dust.
         if is_unspendable::<PopulatedTransaction>(transaction_output.script_public_key.script()) {
             return true;
@@ -137,15 +148,18 @@ impl Mempool {
         // that figure is used.
         let total_serialized_size = mass::transaction_output_estimated_serialized_size(transaction_output) + 148;
 
+        // Values below the hard floor are dust whatever the fee rate.
+        if transaction_output.value < dust_policy.minimum_output_value {
+            return true;
+        }
+
         // The output is considered dust if the cost to the network to spend the
-        // coins is more than 1/3 of the minimum free transaction relay fee.
-        // mp.config.MinimumRelayTransactionFee is in sompi/KB, so multiply
-        // by 1000 to convert to bytes.
+        // coins is more than 1/3 of the dust relay fee. The dust relay fee is in
+        // sompi/kg, so multiply by 1000 to convert to grams.
         //
         // Using the typical values for a pay-to-pubkey transaction from
-        // the breakdown above and the default minimum free transaction relay
-        // fee of 1000, this equates to values less than 546 sompi being
-        // considered dust.
+        // the breakdown above and the default dust relay fee of 1000, this
+        // equates to values less than 600 sompi being considered dust.
         //
         // The following is equivalent to (value/total_serialized_size) * (1/3) * 1000
         // without needing to do floating point math.
@@ -153,14 +167,23 @@ impl Mempool {
         // Since the multiplication may overflow a u64, 2 separate calculation paths
         // are considered to avoid overflowing.
         match transaction_output.value.checked_mul(1000) {
-            Some(value_1000) => value_1000 / (3 * total_serialized_size) < self.config.minimum_relay_transaction_fee,
+            Some(value_1000) => value_1000 / (3 * total_serialized_size) < dust_policy.dust_relay_fee,
             None => {
-                (transaction_output.value as u128 * 1000 / (3 * total_serialized_size as u128))
-                    < self.config.minimum_relay_transaction_fee as u128
+                (transaction_output.value as u128 * 1000 / (3 * total_serialized_size as u128)) < dust_policy.dust_relay_fee as u128
             }
         }
     }
 
+    /// Returns whether the passed transaction output is a zero-value data carrier of a standard size
+    /// while the data carrier policy is enabled.
+    fn is_exempted_data_carrier_output(&self, transaction_output: &TransactionOutput) -> bool {
+        self.config.dust_policy.accept_data_carrier
+            && transaction_output.value == 0
+            && transaction_output.script_public_key.version() == MAX_SCRIPT_PUBLIC_KEY_VERSION
+            && transaction_output.script_public_key.script().len() <= MAXIMUM_DATA_CARRIER_SCRIPT_SIZE
+            && is_data_carrier::<PopulatedTransaction>(transaction_output.script_public_key.script())
+    }
+
     /// check_transaction_standard_in_context performs a series of checks on a transaction's
     /// inputs to ensure they are "standard". A standard transaction input within the
     /// context of this function is one whose referenced public key script is of a
@@ -208,7 +231,7 @@ impl Mempool {
 
     /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
     /// for a transaction with the passed mass to be accepted into the mempool and relayed.
-    fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+    pub(crate) fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
         // Calculate the minimum fee for a transaction to be allowed into the
         // mempool and relayed by scaling the base fee. MinimumRelayTransactionFee is in
         // sompi/kg so multiply by mass (which is in grams) and divide by 1000 to get
@@ -231,13 +254,13 @@ impl Mempool {
 mod tests {
     use super::*;
     use crate::{
-        mempool::config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+        mempool::config::{Config, DustPolicy, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
         MiningCounters,
     };
     use kaspa_addresses::{Address, Prefix, Version};
     use kaspa_consensus_core::{
         config::params::Params,
-        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA, TX_VERSION},
+        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA, TX_VERSION, TX_VERSION_2},
         network::NetworkType,
         subnets::SUBNETWORK_ID_NATIVE,
         tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
@@ -317,42 +340,42 @@ mod tests {
         struct Test {
             name: &'static str,
             tx_out: TransactionOutput,
-            minimum_relay_transaction_fee: u64,
+            dust_relay_fee: u64,
             is_dust: bool,
         }
 
         let tests = vec![
-            // Any value is allowed with a zero relay fee.
+            // Any value is allowed with a zero dust relay fee.
             Test {
                 name: "zero value with zero relay fee",
                 tx_out: TransactionOutput::new(0, script_public_key.clone()),
-                minimum_relay_transaction_fee: 0,
+                dust_relay_fee: 0,
                 is_dust: false,
             },
-            // Zero value is dust with any relay fee"
+            // Zero value is dust with any dust relay fee
             Test {
                 name: "zero value with very small tx fee",
                 tx_out: TransactionOutput::new(0, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1,
+                dust_relay_fee: 1,
                 is_dust: true,
             },
             Test {
                 name: "36 byte public key script with value 605",
                 tx_out: TransactionOutput::new(605, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_fee: 1000,
                 is_dust: true,
             },
             Test {
                 name: "36 byte public key script with value 606",
                 tx_out: TransactionOutput::new(606, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_fee: 1000,
                 is_dust: false,
             },
             // Maximum allowed value is never dust.
             Test {
                 name: "max sompi amount is never dust",
                 tx_out: TransactionOutput::new(MAX_SOMPI, script_public_key.clone()),
-                minimum_relay_transaction_fee: 1000,
+                dust_relay_fee: 1000,
                 is_dust: false,
             },
             // Maximum uint64 value causes NO overflow.
@@ -360,14 +383,14 @@ mod tests {
             Test {
                 name: "maximum uint64 value",
                 tx_out: TransactionOutput::new(u64::MAX, script_public_key),
-                minimum_relay_transaction_fee: u64::MAX,
+                dust_relay_fee: u64::MAX,
                 is_dust: false,
             },
             // Unspendable script_public_key due to an invalid public key script.
             Test {
                 name: "unspendable script_public_key",
                 tx_out: TransactionOutput::new(5000, invalid_script_public_key),
-                minimum_relay_transaction_fee: 0,
+                dust_relay_fee: 0,
                 is_dust: true,
             },
         ];
@@ -375,7 +398,7 @@ mod tests {
             for net in NetworkType::iter() {
                 let params: Params = net.into();
                 let mut config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
-                config.minimum_relay_transaction_fee = test.minimum_relay_transaction_fee;
+                config.dust_policy.dust_relay_fee = test.dust_relay_fee;
                 let counters = Arc::new(MiningCounters::default());
                 let mempool = Mempool::new(Arc::new(config), counters);
 
@@ -389,6 +412,120 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dust_policy() {
+        let address = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let pay_to_pubkey = kaspa_txscript::pay_to_address_script(&address);
+        let data_carrier = |data_len: usize| {
+            let mut builder = ScriptBuilder::new();
+            builder.add_op(OpReturn).unwrap().add_data(&vec![7u8; data_len]).unwrap();
+            ScriptPublicKey::new(MAX_SCRIPT_PUBLIC_KEY_VERSION, builder.script().into())
+        };
+        let not_push_only = ScriptPublicKey::new(MAX_SCRIPT_PUBLIC_KEY_VERSION, smallvec![OpReturn, OpTrue, OpReturn]);
+
+        struct Test {
+            name: &'static str,
+            tx_out: TransactionOutput,
+            dust_policy: DustPolicy,
+            is_dust: bool,
+        }
+
+        let default = DustPolicy::default();
+        let floor = DustPolicy { minimum_output_value: 1000, ..Default::default() };
+        let carrier = DustPolicy { accept_data_carrier: true, ..Default::default() };
+        let tests = vec![
+            Test {
+                name: "p2pk with value 599",
+                tx_out: TransactionOutput::new(599, pay_to_pubkey.clone()),
+                dust_policy: default,
+                is_dust: true,
+            },
+            Test {
+                name: "p2pk with value 600",
+                tx_out: TransactionOutput::new(600, pay_to_pubkey.clone()),
+                dust_policy: default,
+                is_dust: false,
+            },
+            Test {
+                name: "p2pk with value 600 and a doubled dust relay fee",
+                tx_out: TransactionOutput::new(600, pay_to_pubkey.clone()),
+                dust_policy: DustPolicy { dust_relay_fee: 2000, ..Default::default() },
+                is_dust: true,
+            },
+            Test {
+                name: "p2pk below the hard floor",
+                tx_out: TransactionOutput::new(999, pay_to_pubkey.clone()),
+                dust_policy: floor,
+                is_dust: true,
+            },
+            Test {
+                name: "p2pk at the hard floor",
+                tx_out: TransactionOutput::new(1000, pay_to_pubkey.clone()),
+                dust_policy: floor,
+                is_dust: false,
+            },
+            Test {
+                name: "hard floor with a zero dust relay fee",
+                tx_out: TransactionOutput::new(999, pay_to_pubkey.clone()),
+                dust_policy: DustPolicy { dust_relay_fee: 0, minimum_output_value: 1000, accept_data_carrier: false },
+                is_dust: true,
+            },
+            Test {
+                name: "data carrier without policy",
+                tx_out: TransactionOutput::new(0, data_carrier(80)),
+                dust_policy: default,
+                is_dust: true,
+            },
+            Test {
+                name: "data carrier with policy",
+                tx_out: TransactionOutput::new(0, data_carrier(80)),
+                dust_policy: carrier,
+                is_dust: false,
+            },
+            Test {
+                name: "data carrier with policy and a hard floor",
+                tx_out: TransactionOutput::new(0, data_carrier(80)),
+                dust_policy: DustPolicy { minimum_output_value: 1000, accept_data_carrier: true, ..Default::default() },
+                is_dust: false,
+            },
+            Test {
+                name: "oversized data carrier",
+                tx_out: TransactionOutput::new(0, data_carrier(81)),
+                dust_policy: carrier,
+                is_dust: true,
+            },
+            Test {
+                name: "data carrier with value",
+                tx_out: TransactionOutput::new(1, data_carrier(80)),
+                dust_policy: carrier,
+                is_dust: true,
+            },
+            Test { name: "non push-only data", tx_out: TransactionOutput::new(0, not_push_only), dust_policy: carrier, is_dust: true },
+        ];
+
+        let params: Params = NetworkType::Mainnet.into();
+        for test in tests {
+            let config =
+                Config::build_default(params.target_time_per_block, false, params.max_block_mass).with_dust_policy(test.dust_policy);
+            let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+            assert_eq!(mempool.is_transaction_output_dust(&test.tx_out), test.is_dust, "test '{}' failed", test.name);
+
+            // Data outputs being exempted from dust must also be standard
+            let tx = Transaction::new(
+                TX_VERSION,
+                vec![TransactionInput::new(TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 1), vec![0u8; 65], 0, 1)],
+                vec![test.tx_out.clone()],
+                0,
+                SUBNETWORK_ID_NATIVE,
+                0,
+                vec![],
+            );
+            let mut mtx = MutableTransaction::from_tx(tx);
+            mtx.calculated_compute_mass = Some(1000);
+            assert_eq!(mempool.check_transaction_standard_in_isolation(&mtx).is_ok(), !test.is_dust, "test '{}' failed", test.name);
+        }
+    }
+
     #[test]
     fn test_check_transaction_standard_in_isolation() {
         // Create some dummy, but otherwise standard, data for transactions.
@@ -579,4 +716,42 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_standard_transaction_versions() {
+        let addr = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let input = TransactionInput::new(
+            TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 1),
+            vec![0u8; 65],
+            MAX_TX_IN_SEQUENCE_NUM,
+            1,
+        );
+        let output = TransactionOutput::new(SOMPI_PER_KASPA, kaspa_txscript::pay_to_address_script(&addr));
+        let new_mtx = |version| {
+            let tx = Transaction::new(version, vec![input.clone()], vec![output.clone()], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+            let mut mtx = MutableTransaction::from_tx(tx);
+            mtx.calculated_compute_mass = Some(1000);
+            mtx
+        };
+
+        let params: Params = NetworkType::Mainnet.into();
+        let config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
+
+        // Version 2 is not standard by default, even if consensus may accept it
+        assert_eq!(config.standard_transaction_versions(), TX_VERSION..=TX_VERSION);
+        let mempool = Mempool::new(Arc::new(config.clone()), Arc::new(MiningCounters::default()));
+        assert!(mempool.check_transaction_standard_in_isolation(&new_mtx(TX_VERSION)).is_ok());
+        assert!(matches!(
+            mempool.check_transaction_standard_in_isolation(&new_mtx(TX_VERSION_2)),
+            Err(NonStandardError::RejectVersion(_, TX_VERSION_2, TX_VERSION, TX_VERSION))
+        ));
+
+        // Widening the range makes version 2 standard while keeping versions outside of it rejected
+        let config = config.with_standard_transaction_versions(TX_VERSION..=TX_VERSION_2);
+        assert_eq!(config.standard_transaction_versions(), TX_VERSION..=TX_VERSION_2);
+        let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+        assert!(mempool.check_transaction_standard_in_isolation(&new_mtx(TX_VERSION)).is_ok());
+        assert!(mempool.check_transaction_standard_in_isolation(&new_mtx(TX_VERSION_2)).is_ok());
+        assert!(mempool.check_transaction_standard_in_isolation(&new_mtx(TX_VERSION_2 + 1)).is_err());
+    }
 }
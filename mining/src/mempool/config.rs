@@ -1,4 +1,7 @@
-use kaspa_consensus_core::constants::TX_VERSION;
+use crate::errors::{MiningManagerError, MiningManagerResult};
+use kaspa_consensus_core::{block::TemplateTxOrdering, constants::TX_VERSION};
+use kaspa_core::time::{DynClock, SystemClock};
+use std::ops::RangeInclusive;
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: u64 = 1_000_000;
 pub(crate) const DEFAULT_MAXIMUM_READY_TRANSACTION_COUNT: u64 = 50_000;
@@ -13,17 +16,40 @@ pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 
+/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
+/// are considered standard and will therefore be relayed and considered for mining.
+pub const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
+
 // TODO: when rusty-kaspa nodes run most of the network, consider increasing this value
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 50;
 
+/// High-priority transactions never expire so every RPC connection submitting them is limited to a quota,
+/// expressed both in transaction count and in total mass. On overflow, the oldest transactions of the
+/// connection are evicted.
+pub(crate) const DEFAULT_MAXIMUM_HIGH_PRIORITY_TRANSACTION_COUNT_PER_SUBMITTER: u64 = 1_000;
+pub(crate) const DEFAULT_MAXIMUM_HIGH_PRIORITY_TRANSACTION_MASS_PER_SUBMITTER: u64 = 20_000_000;
+
+/// Bounds of the expire intervals adjustable at runtime through a [`MempoolPolicy`]
+pub const MINIMUM_POLICY_EXPIRE_INTERVAL_SECONDS: u64 = 10;
+pub const MAXIMUM_POLICY_EXPIRE_INTERVAL_SECONDS: u64 = 24 * 60 * 60;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 
+/// DEFAULT_DUST_RELAY_TRANSACTION_FEE specifies the fee rate, in sompi per 1kg of transaction mass, used to decide
+/// whether spending a transaction output costs the network too much with regard to its value.
+pub const DEFAULT_DUST_RELAY_TRANSACTION_FEE: u64 = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+
+/// MAXIMUM_DATA_CARRIER_SCRIPT_SIZE is the maximum size of the public key script of a standard data carrier output,
+/// allowing for 80 bytes of data.
+pub const MAXIMUM_DATA_CARRIER_SCRIPT_SIZE: usize = 83;
+
 /// Standard transaction version range might be different from what consensus accepts, therefore
 /// we define separate values in mempool.
-/// However, currently there's exactly one transaction version, so mempool accepts the same version
-/// as consensus.
+/// Consensus accepts [`TX_VERSION_2`](kaspa_consensus_core::constants::TX_VERSION_2) once activated by the
+/// network params, whereas the mempool keeps relaying [`TX_VERSION`] only until its standard range gets
+/// explicitly widened with [`Config::with_standard_transaction_versions`].
 pub(crate) const DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 
@@ -42,11 +68,20 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_high_priority_transaction_count_per_submitter: u64,
+    pub maximum_high_priority_transaction_mass_per_submitter: u64,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
+    pub target_time_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
+    /// The policy deciding which transaction outputs are dust
+    pub dust_policy: DustPolicy,
+    /// The ordering of the transactions of the block templates
+    pub template_tx_ordering: TemplateTxOrdering,
+    /// The source of wall-clock time driving the expire scans and the block template cache
+    pub clock: DynClock,
 }
 
 impl Config {
@@ -65,8 +100,11 @@ impl Config {
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_high_priority_transaction_count_per_submitter: u64,
+        maximum_high_priority_transaction_mass_per_submitter: u64,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
+        target_time_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
@@ -85,17 +123,23 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_high_priority_transaction_count_per_submitter,
+            maximum_high_priority_transaction_mass_per_submitter,
             accept_non_standard,
             maximum_mass_per_block,
+            target_time_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
+            dust_policy: Default::default(),
+            template_tx_ordering: Default::default(),
+            clock: SystemClock::new_dyn(),
         }
     }
 
     /// Build a default config.
     /// The arguments should be obtained from the current consensus [`kaspa_consensus_core::config::params::Params`] instance.
-    pub const fn build_default(target_milliseconds_per_block: u64, relay_non_std_transactions: bool, max_block_mass: u64) -> Self {
+    pub fn build_default(target_milliseconds_per_block: u64, relay_non_std_transactions: bool, max_block_mass: u64) -> Self {
         Self {
             maximum_transaction_count: DEFAULT_MAXIMUM_TRANSACTION_COUNT,
             maximum_ready_transaction_count: DEFAULT_MAXIMUM_READY_TRANSACTION_COUNT,
@@ -113,11 +157,17 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_high_priority_transaction_count_per_submitter: DEFAULT_MAXIMUM_HIGH_PRIORITY_TRANSACTION_COUNT_PER_SUBMITTER,
+            maximum_high_priority_transaction_mass_per_submitter: DEFAULT_MAXIMUM_HIGH_PRIORITY_TRANSACTION_MASS_PER_SUBMITTER,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
+            target_time_per_block: target_milliseconds_per_block,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
+            dust_policy: Default::default(),
+            template_tx_ordering: Default::default(),
+            clock: SystemClock::new_dyn(),
         }
     }
 
@@ -125,4 +175,96 @@ impl Config {
         self.maximum_transaction_count = (self.maximum_transaction_count as f64 * ram_scale.min(1.0)) as u64; // Allow only scaling down
         self
     }
+
+    pub fn with_clock(mut self, clock: DynClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_standard_transaction_versions(mut self, versions: RangeInclusive<u16>) -> Self {
+        self.minimum_standard_transaction_version = *versions.start();
+        self.maximum_standard_transaction_version = *versions.end();
+        self
+    }
+
+    /// Returns the range of the transaction versions considered standard
+    pub fn standard_transaction_versions(&self) -> RangeInclusive<u16> {
+        self.minimum_standard_transaction_version..=self.maximum_standard_transaction_version
+    }
+
+    pub fn with_dust_policy(mut self, dust_policy: DustPolicy) -> Self {
+        self.dust_policy = dust_policy;
+        self
+    }
+
+    pub fn with_template_tx_ordering(mut self, template_tx_ordering: TemplateTxOrdering) -> Self {
+        self.template_tx_ordering = template_tx_ordering;
+        self
+    }
+
+    /// Returns the runtime adjustable parameters of this config
+    pub fn policy(&self) -> MempoolPolicy {
+        MempoolPolicy {
+            transaction_expire_interval_seconds: self.transaction_expire_interval_daa_score * self.target_time_per_block / 1000,
+            orphan_expire_interval_seconds: self.orphan_expire_interval_daa_score * self.target_time_per_block / 1000,
+            maximum_transaction_count: self.maximum_transaction_count,
+            maximum_orphan_transaction_count: self.maximum_orphan_transaction_count,
+        }
+    }
+
+    /// Returns a copy of this config with `policy` applied.
+    ///
+    /// `bounds` is the config the node started with. The maximum counts cannot be raised above its own, which were
+    /// sized according to the available memory.
+    pub(crate) fn with_policy(&self, policy: &MempoolPolicy, bounds: &Config) -> MiningManagerResult<Self> {
+        let check = |name: &'static str, value: u64, min: u64, max: u64| match (min..=max).contains(&value) {
+            true => Ok(()),
+            false => Err(MiningManagerError::MempoolPolicyOutOfRange(name, value, min, max)),
+        };
+        let (min_interval, max_interval) = (MINIMUM_POLICY_EXPIRE_INTERVAL_SECONDS, MAXIMUM_POLICY_EXPIRE_INTERVAL_SECONDS);
+        check("transaction expire interval", policy.transaction_expire_interval_seconds, min_interval, max_interval)?;
+        check("orphan expire interval", policy.orphan_expire_interval_seconds, min_interval, max_interval)?;
+        check("maximum transaction count", policy.maximum_transaction_count, 1, bounds.maximum_transaction_count)?;
+        check(
+            "maximum orphan transaction count",
+            policy.maximum_orphan_transaction_count,
+            0,
+            bounds.maximum_orphan_transaction_count,
+        )?;
+
+        let mut config = self.clone();
+        config.transaction_expire_interval_daa_score = policy.transaction_expire_interval_seconds * 1000 / self.target_time_per_block;
+        config.orphan_expire_interval_daa_score = policy.orphan_expire_interval_seconds * 1000 / self.target_time_per_block;
+        config.maximum_transaction_count = policy.maximum_transaction_count;
+        config.maximum_orphan_transaction_count = policy.maximum_orphan_transaction_count;
+        Ok(config)
+    }
+}
+
+/// Mempool parameters adjustable while the node is running, letting operators react to spam waves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MempoolPolicy {
+    /// Time after which a low-priority transaction not included in a block expires
+    pub transaction_expire_interval_seconds: u64,
+    /// Time after which an orphan transaction expires
+    pub orphan_expire_interval_seconds: u64,
+    pub maximum_transaction_count: u64,
+    pub maximum_orphan_transaction_count: u64,
+}
+
+/// Mempool policy deciding which transaction outputs are too small to be relayed and mined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DustPolicy {
+    /// Fee rate, in sompi per 1kg of mass, above which an output costs the network more to spend than it is worth
+    pub dust_relay_fee: u64,
+    /// Hard floor below which the value of any spendable output is dust, whatever the fee rate
+    pub minimum_output_value: u64,
+    /// Exempts provably unspendable zero-value data outputs from being dust and makes them standard
+    pub accept_data_carrier: bool,
+}
+
+impl Default for DustPolicy {
+    fn default() -> Self {
+        Self { dust_relay_fee: DEFAULT_DUST_RELAY_TRANSACTION_FEE, minimum_output_value: 0, accept_data_carrier: false }
+    }
 }
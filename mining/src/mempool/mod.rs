@@ -1,19 +1,25 @@
 use crate::{
     model::{
         candidate_tx::CandidateTransaction,
+        fee_estimate::FeeEstimate,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
-        tx_query::TransactionQuery,
+        template_exclusions::{TemplateExclusionReason, TemplateExclusions},
+        tx_query::{TransactionFilter, TransactionQuery},
     },
     MiningCounters,
 };
 
 use self::{
     config::Config,
-    model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    errors::RuleResult,
+    model::{
+        accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, submitters::HighPrioritySubmitters,
+        transactions_pool::TransactionsPool, tx::TxRemovalReason,
+    },
     tx::Priority,
 };
 use kaspa_consensus_core::tx::{MutableTransaction, TransactionId};
-use kaspa_core::time::Stopwatch;
+use kaspa_core::{debug, time::Stopwatch};
 use std::sync::Arc;
 
 pub(crate) mod check_transaction_standard;
@@ -40,12 +46,18 @@ pub(crate) mod validate_and_insert_transaction;
 ///   They are owned by the node, they never expire in the mempool and the node
 ///   rebroadcasts them once in a while.
 /// - Transactions received through P2P have **low-priority**. They expire after
-///   60 seconds and are removed if not inserted in a block for mining.
+///   60 seconds by default, an interval adjustable at runtime through the mempool
+///   policy, and are removed if not inserted in a block for mining.
+/// - High-priority transactions are tracked by submitting RPC connection. Every
+///   connection is limited to a quota, beyond which its oldest transactions are
+///   evicted.
 pub(crate) struct Mempool {
     config: Arc<Config>,
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
+    high_priority_submitters: HighPrioritySubmitters,
+    template_exclusions: TemplateExclusions,
     counters: Arc<MiningCounters>,
 }
 
@@ -54,7 +66,31 @@ impl Mempool {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        let high_priority_submitters = HighPrioritySubmitters::new(config.clone());
+        Self {
+            config,
+            transaction_pool,
+            orphan_pool,
+            accepted_transactions,
+            high_priority_submitters,
+            template_exclusions: Default::default(),
+            counters,
+        }
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replaces the config of the mempool and of all its pools.
+    ///
+    /// Maximum counts are enforced on the next insertions and expire intervals on the next expire scans.
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.transaction_pool.set_config(config.clone());
+        self.orphan_pool.set_config(config.clone());
+        self.accepted_transactions.set_config(config.clone());
+        self.high_priority_submitters.set_config(config.clone());
+        self.config = config;
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -73,15 +109,24 @@ impl Mempool {
             || (query.include_orphan_pool() && self.orphan_pool.has(transaction_id))
     }
 
-    pub(crate) fn get_all_transactions(&self, query: TransactionQuery) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
-        let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transactions() } else { vec![] };
-        let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transactions() } else { vec![] };
+    pub(crate) fn get_all_transactions(
+        &self,
+        query: TransactionQuery,
+        filter: &TransactionFilter,
+    ) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
+        let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transactions(filter) } else { vec![] };
+        let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transactions(filter) } else { vec![] };
         (transactions, orphans)
     }
 
-    pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
-        let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
-        let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
+    pub(crate) fn get_all_transaction_ids(
+        &self,
+        query: TransactionQuery,
+        filter: &TransactionFilter,
+    ) -> (Vec<TransactionId>, Vec<TransactionId>) {
+        let transactions =
+            if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids(filter) } else { vec![] };
+        let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids(filter) } else { vec![] };
         (transactions, orphans)
     }
 
@@ -111,9 +156,41 @@ impl Mempool {
         count
     }
 
-    pub(crate) fn block_candidate_transactions(&self) -> Vec<CandidateTransaction> {
+    /// Returns the transactions ready for being inserted in a block template along with the ready transactions
+    /// excluded from the templates and the reason of their exclusion.
+    ///
+    /// Ready transactions have no parent in the mempool so excluding one of them does not invalidate the others.
+    pub(crate) fn block_candidate_transactions(&self) -> (Vec<CandidateTransaction>, Vec<(TransactionId, TemplateExclusionReason)>) {
         let _sw = Stopwatch::<10>::with_threshold("block_candidate_transactions op");
-        self.transaction_pool.all_ready_transactions()
+        let mut candidates = self.transaction_pool.all_ready_transactions();
+        let mut excluded = vec![];
+        if !self.template_exclusions.is_empty() {
+            candidates.retain(|candidate| {
+                let transaction_id = candidate.tx.id();
+                let mtx = &self.transaction_pool.get(&transaction_id).unwrap().mtx;
+                match self.template_exclusions.exclusion_reason(mtx) {
+                    Some(reason) => {
+                        excluded.push((transaction_id, reason));
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+        (candidates, excluded)
+    }
+
+    pub(crate) fn template_exclusions(&self) -> &TemplateExclusions {
+        &self.template_exclusions
+    }
+
+    pub(crate) fn template_exclusions_mut(&mut self) -> &mut TemplateExclusions {
+        &mut self.template_exclusions
+    }
+
+    pub(crate) fn fee_estimate(&self) -> FeeEstimate {
+        let _sw = Stopwatch::<15>::with_threshold("fee_estimate op");
+        FeeEstimate::new(self.transaction_pool.all_feerates_and_masses(), &self.config)
     }
 
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
@@ -121,6 +198,56 @@ impl Mempool {
         self.transaction_pool.all_transaction_ids_with_priority(priority)
     }
 
+    /// Records the high-priority transactions submitted by `submitter` and enforces the submitter quota, evicting
+    /// its oldest transactions, along with their redeemers, on overflow.
+    ///
+    /// Returns the ids of all the evicted transactions.
+    pub(crate) fn track_high_priority_submission(
+        &mut self,
+        submitter: &str,
+        transaction_ids: &[TransactionId],
+    ) -> RuleResult<Vec<TransactionId>> {
+        // Forget the transactions of the submitter that left the mempool since its last submission
+        let (transaction_pool, orphan_pool) = (&self.transaction_pool, &self.orphan_pool);
+        self.high_priority_submitters.retain_submitter(submitter, |x| transaction_pool.has(x) || orphan_pool.has(x));
+
+        for transaction_id in transaction_ids.iter() {
+            let transaction = self.transaction_pool.get(transaction_id).or_else(|| self.orphan_pool.get(transaction_id));
+            if let Some(transaction) = transaction.filter(|x| x.priority == Priority::High) {
+                let mass = transaction.mtx.calculated_compute_mass.unwrap_or_else(|| transaction.mtx.tx.mass());
+                self.high_priority_submitters.add(submitter, *transaction_id, mass);
+            }
+        }
+
+        let mut evicted_transactions = vec![];
+        while let Some(transaction_id) = self.high_priority_submitters.oldest_exceeding(submitter) {
+            let removed =
+                self.remove_transaction_collecting_ids(&transaction_id, true, TxRemovalReason::SubmitterQuotaExceeded, "")?;
+            self.high_priority_submitters.remove(&transaction_id);
+            removed.iter().for_each(|x| {
+                self.high_priority_submitters.remove(x);
+            });
+            evicted_transactions.extend(removed);
+        }
+        if !evicted_transactions.is_empty() {
+            let (count, mass) = self.high_priority_submitters.usage(submitter);
+            debug!(
+                "Evicted {} transactions of RPC submitter {} exceeding its quota, now using {} transactions and {} mass",
+                evicted_transactions.len(),
+                submitter,
+                count,
+                mass
+            );
+        }
+        Ok(evicted_transactions)
+    }
+
+    /// Forgets the tracked high-priority transactions that left the mempool
+    pub(crate) fn prune_high_priority_submitters(&mut self) {
+        let (transaction_pool, orphan_pool) = (&self.transaction_pool, &self.orphan_pool);
+        self.high_priority_submitters.retain(|x| transaction_pool.has(x) || orphan_pool.has(x));
+    }
+
     pub(crate) fn update_revalidated_transaction(&mut self, transaction: MutableTransaction) -> bool {
         if let Some(tx) = self.transaction_pool.get_mut(&transaction.id()) {
             tx.mtx = transaction;
@@ -158,4 +285,14 @@ pub mod tx {
         Forbidden,
         Allowed,
     }
+
+    /// Replace by fee (RBF) policy
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RbfPolicy {
+        /// A transaction double spending a mempool transaction is rejected
+        Forbidden,
+        /// A transaction double spending mempool transactions replaces them, along with their redeemers,
+        /// if it pays a sufficiently higher feerate
+        Allowed,
+    }
 }
@@ -1,6 +1,6 @@
 use crate::mempool::config::Config;
 use kaspa_consensus_core::tx::TransactionId;
-use kaspa_core::{debug, time::unix_now};
+use kaspa_core::debug;
 use std::{collections::HashMap, sync::Arc};
 
 pub(crate) struct AcceptedTransactions {
@@ -18,7 +18,12 @@ pub(crate) struct AcceptedTransactions {
 
 impl AcceptedTransactions {
     pub(crate) fn new(config: Arc<Config>) -> Self {
-        Self { config, transactions: Default::default(), last_expire_scan_daa_score: 0, last_expire_scan_time: unix_now() }
+        let last_expire_scan_time = config.clock.unix_now();
+        Self { config, transactions: Default::default(), last_expire_scan_daa_score: 0, last_expire_scan_time }
+    }
+
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
     }
 
     pub(crate) fn add(&mut self, transaction_id: TransactionId, daa_score: u64) -> bool {
@@ -42,7 +47,7 @@ impl AcceptedTransactions {
     }
 
     pub(crate) fn expire(&mut self, virtual_daa_score: u64) {
-        let now = unix_now();
+        let now = self.config.clock.unix_now();
         if virtual_daa_score < self.last_expire_scan_daa_score + self.config.accepted_transaction_expire_scan_interval_daa_score
             || now < self.last_expire_scan_time + self.config.accepted_transaction_expire_scan_interval_milliseconds
         {
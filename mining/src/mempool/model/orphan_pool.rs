@@ -51,6 +51,10 @@ impl OrphanPool {
         }
     }
 
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
     pub(crate) fn outpoint_orphan(&self, outpoint: &TransactionOutpoint) -> Option<&MempoolTransaction> {
         self.outpoint_owner_id.get(outpoint).and_then(|id| self.all_orphans.get(id))
     }
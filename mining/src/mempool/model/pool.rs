@@ -6,6 +6,7 @@ use crate::{
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_index::TopologicalIndex,
+        tx_query::TransactionFilter,
         TransactionIdSet,
     },
 };
@@ -94,14 +95,14 @@ pub(crate) trait Pool {
         descendants
     }
 
-    /// Returns a vector with clones of all the transactions in the pool.
-    fn get_all_transactions(&self) -> Vec<MutableTransaction> {
-        self.all().values().map(|x| x.mtx.clone()).collect()
+    /// Returns a vector with clones of all the transactions in the pool matching `filter`.
+    fn get_all_transactions(&self, filter: &TransactionFilter) -> Vec<MutableTransaction> {
+        self.all().values().filter(|x| x.matches(filter)).map(|x| x.mtx.clone()).collect()
     }
 
-    /// Returns a vector with ids of all the transactions in the pool.
-    fn get_all_transaction_ids(&self) -> Vec<TransactionId> {
-        self.all().keys().cloned().collect()
+    /// Returns a vector with ids of all the transactions in the pool matching `filter`.
+    fn get_all_transaction_ids(&self, filter: &TransactionFilter) -> Vec<TransactionId> {
+        self.all().iter().filter(|(_, x)| x.matches(filter)).map(|(id, _)| *id).collect()
     }
 
     /// Fills owner transactions for a set of script public keys.
@@ -0,0 +1,121 @@
+use crate::mempool::config::Config;
+use kaspa_consensus_core::tx::TransactionId;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// High-priority transactions submitted by a single RPC connection, in submission order
+#[derive(Default)]
+struct SubmitterTransactions {
+    /// Transaction ids with their mass, the oldest first
+    transactions: VecDeque<(TransactionId, u64)>,
+    /// Total mass of `transactions`
+    mass: u64,
+}
+
+impl SubmitterTransactions {
+    fn push(&mut self, transaction_id: TransactionId, mass: u64) {
+        self.transactions.push_back((transaction_id, mass));
+        self.mass += mass;
+    }
+
+    fn retain(&mut self, mut f: impl FnMut(&TransactionId) -> bool) {
+        let mut mass = 0;
+        self.transactions.retain(|(transaction_id, transaction_mass)| {
+            let keep = f(transaction_id);
+            if keep {
+                mass += transaction_mass;
+            }
+            keep
+        });
+        self.mass = mass;
+    }
+}
+
+/// Tracks which RPC connection submitted each high-priority transaction and enforces the per-connection
+/// quotas of the mempool config.
+///
+/// Transactions leaving the mempool are not reported to the tracker, so it has to be pruned by the caller
+/// with the set of transactions still in the mempool.
+pub(crate) struct HighPrioritySubmitters {
+    config: Arc<Config>,
+    submitters: HashMap<String, SubmitterTransactions>,
+    owners: HashMap<TransactionId, String>,
+}
+
+impl HighPrioritySubmitters {
+    pub(crate) fn new(config: Arc<Config>) -> Self {
+        Self { config, submitters: Default::default(), owners: Default::default() }
+    }
+
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
+    /// Records a transaction submitted by `submitter`. A transaction already tracked keeps its original submitter.
+    pub(crate) fn add(&mut self, submitter: &str, transaction_id: TransactionId, mass: u64) -> bool {
+        if self.owners.contains_key(&transaction_id) {
+            return false;
+        }
+        self.owners.insert(transaction_id, submitter.to_string());
+        self.submitters.entry(submitter.to_string()).or_default().push(transaction_id, mass);
+        true
+    }
+
+    pub(crate) fn remove(&mut self, transaction_id: &TransactionId) -> bool {
+        let Some(submitter) = self.owners.remove(transaction_id) else {
+            return false;
+        };
+        if let Some(transactions) = self.submitters.get_mut(&submitter) {
+            transactions.retain(|x| x != transaction_id);
+            if transactions.transactions.is_empty() {
+                self.submitters.remove(&submitter);
+            }
+        }
+        true
+    }
+
+    /// Keeps only the transactions of `submitter` satisfying `f`
+    pub(crate) fn retain_submitter(&mut self, submitter: &str, mut f: impl FnMut(&TransactionId) -> bool) {
+        if let Some(transactions) = self.submitters.get_mut(submitter) {
+            let owners = &mut self.owners;
+            transactions.retain(|transaction_id| {
+                let keep = f(transaction_id);
+                if !keep {
+                    owners.remove(transaction_id);
+                }
+                keep
+            });
+            if transactions.transactions.is_empty() {
+                self.submitters.remove(submitter);
+            }
+        }
+    }
+
+    /// Keeps only the transactions satisfying `f`, dropping the submitters left with no transaction
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&TransactionId) -> bool) {
+        self.owners.retain(|transaction_id, _| f(transaction_id));
+        let owners = &self.owners;
+        self.submitters.retain(|_, transactions| {
+            transactions.retain(|transaction_id| owners.contains_key(transaction_id));
+            !transactions.transactions.is_empty()
+        });
+    }
+
+    /// Returns the oldest transaction of `submitter` if the submitter exceeds its quota
+    pub(crate) fn oldest_exceeding(&self, submitter: &str) -> Option<TransactionId> {
+        let transactions = self.submitters.get(submitter)?;
+        let exceeding = transactions.transactions.len() as u64 > self.config.maximum_high_priority_transaction_count_per_submitter
+            || transactions.mass > self.config.maximum_high_priority_transaction_mass_per_submitter;
+        match exceeding {
+            true => transactions.transactions.front().map(|(transaction_id, _)| *transaction_id),
+            false => None,
+        }
+    }
+
+    /// Returns the number of transactions and the total mass tracked for `submitter`
+    pub(crate) fn usage(&self, submitter: &str) -> (usize, u64) {
+        self.submitters.get(submitter).map_or((0, 0), |x| (x.transactions.len(), x.mass))
+    }
+}
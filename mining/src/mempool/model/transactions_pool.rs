@@ -10,13 +10,13 @@ use crate::{
         },
         tx::Priority,
     },
-    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex},
+    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex, TransactionIdSet},
 };
 use kaspa_consensus_core::{
     tx::TransactionId,
     tx::{MutableTransaction, TransactionOutpoint},
 };
-use kaspa_core::{time::unix_now, trace, warn};
+use kaspa_core::{trace, warn};
 use std::{
     collections::{hash_map::Keys, hash_set::Iter, HashSet},
     sync::Arc,
@@ -67,17 +67,21 @@ pub(crate) struct TransactionsPool {
 impl TransactionsPool {
     pub(crate) fn new(config: Arc<Config>) -> Self {
         Self {
+            last_expire_scan_time: config.clock.unix_now(),
             config,
             all_transactions: MempoolTransactionCollection::default(),
             parent_transactions: TransactionsEdges::default(),
             chained_transactions: TransactionsEdges::default(),
             ready_transactions: Default::default(),
             last_expire_scan_daa_score: 0,
-            last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
         }
     }
 
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
     /// Add a mutable transaction to the pool
     pub(crate) fn add_transaction(
         &mut self,
@@ -157,6 +161,11 @@ impl TransactionsPool {
         Ok(removed_tx)
     }
 
+    /// Returns the feerate and mass of all the transactions in the pool
+    pub(crate) fn all_feerates_and_masses(&self) -> Vec<(f64, u64)> {
+        self.all_transactions.values().map(|tx| (tx.fee_rate(), tx.mtx.tx.mass())).collect()
+    }
+
     pub(crate) fn ready_transaction_count(&self) -> usize {
         self.ready_transactions.len()
     }
@@ -192,12 +201,15 @@ impl TransactionsPool {
         &self,
         free_slots: usize,
         transaction: &MutableTransaction,
+        evicted: &TransactionIdSet,
     ) -> RuleResult<Vec<TransactionId>> {
         assert!(free_slots > 0);
         // Returns a vector of transactions to be removed that the caller has to remove actually.
         // The caller is golang validateAndInsertTransaction equivalent.
         // This behavior differs from golang impl.
-        let trim_size = self.len() + free_slots - usize::min(self.len() + free_slots, self.config.maximum_transaction_count as usize);
+        // The `evicted` transactions are about to be removed by the caller, so they count as free slots already.
+        let len = self.len() - evicted.len();
+        let trim_size = len + free_slots - usize::min(len + free_slots, self.config.maximum_transaction_count as usize);
         let mut transactions_to_remove = Vec::with_capacity(trim_size);
         if trim_size > 0 {
             // TODO: consider introducing an index on all_transactions low-priority items instead.
@@ -205,10 +217,12 @@ impl TransactionsPool {
             // Sorting this vector here may be sub-optimal compared with maintaining a sorted
             // index of all_transactions low-priority items if the proportion of low-priority txs
             // in all_transactions is important.
-            let low_priority_txs = self
-                .all_transactions
-                .values()
-                .filter(|x| x.priority == Priority::Low && self.transaction_is_unchained(&x.id()) && !x.is_parent_of(transaction));
+            let low_priority_txs = self.all_transactions.values().filter(|x| {
+                x.priority == Priority::Low
+                    && self.transaction_is_unchained(&x.id())
+                    && !x.is_parent_of(transaction)
+                    && !evicted.contains(&x.id())
+            });
 
             if trim_size == 1 {
                 // This is the most likely case. Here we just search the minimum, thus avoiding the need to sort altogether.
@@ -227,7 +241,7 @@ impl TransactionsPool {
         }
 
         // An error is returned if the mempool is filled with high priority and other unremovable transactions.
-        let tx_count = self.len() + free_slots - transactions_to_remove.len();
+        let tx_count = len + free_slots - transactions_to_remove.len();
         if tx_count as u64 > self.config.maximum_transaction_count {
             let err = RuleError::RejectMempoolIsFull(tx_count - free_slots, self.config.maximum_transaction_count);
             warn!("{}", err.to_string());
@@ -250,7 +264,7 @@ impl TransactionsPool {
     }
 
     pub(crate) fn collect_expired_low_priority_transactions(&mut self, virtual_daa_score: u64) -> Vec<TransactionId> {
-        let now = unix_now();
+        let now = self.config.clock.unix_now();
         if virtual_daa_score < self.last_expire_scan_daa_score + self.config.transaction_expire_scan_interval_daa_score
             || now < self.last_expire_scan_time + self.config.transaction_expire_scan_interval_milliseconds
         {
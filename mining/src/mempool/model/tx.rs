@@ -1,4 +1,4 @@
-use crate::mempool::tx::Priority;
+use crate::{mempool::tx::Priority, model::tx_query::TransactionFilter};
 use kaspa_consensus_core::{tx::MutableTransaction, tx::TransactionId};
 use std::{
     cmp::Ordering,
@@ -27,6 +27,23 @@ impl MempoolTransaction {
         self.mtx.calculated_fee.unwrap() as f64 / contextual_mass as f64
     }
 
+    /// Returns whether this transaction meets all the criteria of `filter`
+    pub(crate) fn matches(&self, filter: &TransactionFilter) -> bool {
+        if let Some(min_fee_rate) = filter.min_fee_rate {
+            // Orphans have no calculated fee
+            let mass = self.mtx.tx.mass();
+            match self.mtx.calculated_fee {
+                Some(fee) if mass > 0 && fee as f64 / mass as f64 >= min_fee_rate => {}
+                _ => return false,
+            }
+        }
+        if filter.min_added_at_daa_score.is_some_and(|daa_score| self.added_at_daa_score < daa_score) {
+            return false;
+        }
+        filter.script_public_keys.is_empty()
+            || self.mtx.tx.outputs.iter().any(|output| filter.script_public_keys.contains(&output.script_public_key))
+    }
+
     pub(crate) fn is_parent_of(&self, transaction: &MutableTransaction) -> bool {
         let parent_id = self.id();
         transaction.tx.inputs.iter().any(|x| x.previous_outpoint.transaction_id == parent_id)
@@ -61,8 +78,11 @@ pub(crate) enum TxRemovalReason {
     Unorphaned,
     Expired,
     DoubleSpend,
+    ReplacedByFee,
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
+    Reevaluation,
+    SubmitterQuotaExceeded,
 }
 
 impl TxRemovalReason {
@@ -74,8 +94,11 @@ impl TxRemovalReason {
             TxRemovalReason::Unorphaned => "unorphaned",
             TxRemovalReason::Expired => "expired",
             TxRemovalReason::DoubleSpend => "double spend",
+            TxRemovalReason::ReplacedByFee => "replaced by fee",
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
+            TxRemovalReason::Reevaluation => "invalid after re-evaluation",
+            TxRemovalReason::SubmitterQuotaExceeded => "submitter quota exceeded",
         }
     }
 
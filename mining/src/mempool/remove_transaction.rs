@@ -15,12 +15,27 @@ impl Mempool {
         reason: TxRemovalReason,
         extra_info: &str,
     ) -> RuleResult<()> {
+        self.remove_transaction_collecting_ids(transaction_id, remove_redeemers, reason, extra_info).map(|_| ())
+    }
+
+    /// Removes a transaction like [`Mempool::remove_transaction`] does and returns the ids of all
+    /// the transactions actually removed, including redeemers and orphans.
+    pub(crate) fn remove_transaction_collecting_ids(
+        &mut self,
+        transaction_id: &TransactionId,
+        remove_redeemers: bool,
+        reason: TxRemovalReason,
+        extra_info: &str,
+    ) -> RuleResult<Vec<TransactionId>> {
         if self.orphan_pool.has(transaction_id) {
-            return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
+            return self
+                .orphan_pool
+                .remove_orphan(transaction_id, true, reason, extra_info)
+                .map(|removed| removed.iter().map(|x| x.id()).collect());
         }
 
         if !self.transaction_pool.has(transaction_id) {
-            return Ok(());
+            return Ok(vec![]);
         }
 
         let mut removed_transactions = vec![*transaction_id];
@@ -65,6 +80,6 @@ impl Mempool {
             },
         }
 
-        Ok(())
+        Ok(removed_transactions)
     }
 }
@@ -1,11 +1,14 @@
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        tx::{Orphan, Priority, RbfPolicy},
+        Mempool,
     },
-    tx::{Orphan, Priority},
-    Mempool,
+    model::TransactionIdSet,
 };
 use kaspa_consensus_core::{
     api::ConsensusApi,
@@ -20,12 +23,16 @@ impl Mempool {
         &self,
         consensus: &dyn ConsensusApi,
         mut transaction: MutableTransaction,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<MutableTransaction> {
         self.validate_transaction_unacceptance(&transaction)?;
         // Populate mass in the beginning, it will be used in multiple places throughout the validation and insertion.
         transaction.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
         self.validate_transaction_in_isolation(&transaction)?;
-        self.transaction_pool.check_double_spends(&transaction)?;
+        // A replacement is checked once its fee is known, in post validation
+        if rbf_policy == RbfPolicy::Forbidden {
+            self.transaction_pool.check_double_spends(&transaction)?;
+        }
         self.populate_mempool_entries(&mut transaction);
         Ok(transaction)
     }
@@ -37,6 +44,7 @@ impl Mempool {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<Option<Arc<Transaction>>> {
         let transaction_id = transaction.id();
 
@@ -52,7 +60,9 @@ impl Mempool {
         self.validate_transaction_unacceptance(&transaction)?;
 
         // Re-check double spends since validate_and_insert_transaction is no longer atomic
-        self.transaction_pool.check_double_spends(&transaction)?;
+        if rbf_policy == RbfPolicy::Forbidden {
+            self.transaction_pool.check_double_spends(&transaction)?;
+        }
 
         match validation_result {
             Ok(_) => {}
@@ -60,6 +70,10 @@ impl Mempool {
                 if orphan == Orphan::Forbidden {
                     return Err(RuleError::RejectDisallowedOrphan(transaction_id));
                 }
+                // An orphan cannot replace mempool transactions since its fee is unknown
+                if rbf_policy == RbfPolicy::Allowed {
+                    self.transaction_pool.check_double_spends(&transaction)?;
+                }
                 self.orphan_pool.try_add_orphan(consensus.get_virtual_daa_score(), transaction, priority)?;
                 return Ok(None);
             }
@@ -70,8 +84,21 @@ impl Mempool {
 
         self.validate_transaction_in_context(&transaction)?;
 
-        // Before adding the transaction, check if there is room in the pool
-        self.transaction_pool.limit_transaction_count(1, &transaction)?.iter().try_for_each(|x| {
+        // Check the replacement and the room in the pool before removing anything, so the mempool is left untouched
+        // if the transaction gets rejected
+        let (replaced_ids, evicted_ids) = match rbf_policy {
+            RbfPolicy::Allowed => self.validate_replacement(&transaction)?,
+            RbfPolicy::Forbidden => (vec![], TransactionIdSet::new()),
+        };
+        let making_room_ids = self.transaction_pool.limit_transaction_count(1, &transaction, &evicted_ids)?;
+
+        // Evict the double spent mempool transactions along with their redeemers
+        replaced_ids.iter().try_for_each(|x| {
+            self.remove_transaction(x, true, TxRemovalReason::ReplacedByFee, format!(" by {}", transaction_id).as_str())
+        })?;
+
+        // Make room in the pool for the transaction
+        making_room_ids.iter().try_for_each(|x| {
             self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str())
         })?;
 
@@ -102,6 +129,50 @@ impl Mempool {
         Ok(())
     }
 
+    /// Validates that the transaction pays a sufficiently higher feerate than every mempool transaction it
+    /// double spends, and more fees than all of them and their redeemers together.
+    ///
+    /// Returns the ids of the double spent mempool transactions and the ids of all the transactions their
+    /// replacement evicts, redeemers included.
+    fn validate_replacement(&self, transaction: &MutableTransaction) -> RuleResult<(Vec<TransactionId>, TransactionIdSet)> {
+        let transaction_id = transaction.id();
+        let mut replaced_ids = transaction
+            .tx
+            .inputs
+            .iter()
+            .filter_map(|input| self.transaction_pool.get_outpoint_owner_id(&input.previous_outpoint).copied())
+            .filter(|id| *id != transaction_id)
+            .collect::<Vec<_>>();
+        replaced_ids.sort();
+        replaced_ids.dedup();
+        if replaced_ids.is_empty() {
+            return Ok((replaced_ids, TransactionIdSet::new()));
+        }
+
+        // The feerate has to be higher by at least the minimum relay feerate, expressed in sompi per gram
+        let fee = transaction.calculated_fee.unwrap();
+        let feerate = fee as f64 / transaction.tx.mass() as f64;
+        let feerate_increment = self.config.minimum_relay_transaction_fee as f64 / 1000.0;
+        let mut evicted_ids = TransactionIdSet::new();
+        for replaced_id in replaced_ids.iter() {
+            let replaced = self.transaction_pool.get(replaced_id).unwrap();
+            if feerate < replaced.fee_rate() + feerate_increment {
+                return Err(RuleError::RejectReplacementFeerateTooLow(transaction_id, *replaced_id, feerate, replaced.fee_rate()));
+            }
+            evicted_ids.insert(*replaced_id);
+            evicted_ids.extend(self.transaction_pool.get_redeemer_ids_in_pool(replaced_id));
+        }
+        // A replacement cannot spend the outputs of the transactions it evicts
+        if let Some(input) = transaction.tx.inputs.iter().find(|input| evicted_ids.contains(&input.previous_outpoint.transaction_id)) {
+            return Err(RuleError::RejectReplacementSpendingReplaced(transaction_id, input.previous_outpoint.transaction_id));
+        }
+        let evicted_fees = evicted_ids.iter().map(|id| self.transaction_pool.get(id).unwrap().mtx.calculated_fee.unwrap()).sum();
+        if fee <= evicted_fees {
+            return Err(RuleError::RejectReplacementFeeTooLow(transaction_id, fee, evicted_fees));
+        }
+        Ok((replaced_ids, evicted_ids))
+    }
+
     fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // TEMP: apply parts of go-kaspad mempool dust prevention patch
         let has_coinbase_input = transaction.entries.iter().any(|e| e.as_ref().unwrap().is_coinbase);
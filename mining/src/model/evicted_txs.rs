@@ -0,0 +1,39 @@
+use kaspa_consensus_core::tx::TransactionId;
+use std::fmt::{Display, Formatter};
+
+/// Event causing a re-evaluation of all the transactions of the mempool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReevaluationTrigger {
+    /// The pruning point moved
+    PruningPointMove,
+    /// A consensus parameter (like a mass rule) got activated at a fork score
+    ParameterActivation,
+}
+
+impl ReevaluationTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReevaluationTrigger::PruningPointMove => "pruning point move",
+            ReevaluationTrigger::ParameterActivation => "parameter activation",
+        }
+    }
+}
+
+impl Display for ReevaluationTrigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Transactions evicted from the mempool because they became invalid during a re-evaluation
+#[derive(Clone, Debug)]
+pub struct EvictedTransactions {
+    pub transaction_ids: Vec<TransactionId>,
+    pub trigger: ReevaluationTrigger,
+}
+
+impl EvictedTransactions {
+    pub fn new(transaction_ids: Vec<TransactionId>, trigger: ReevaluationTrigger) -> Self {
+        Self { transaction_ids, trigger }
+    }
+}
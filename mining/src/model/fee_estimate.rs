@@ -0,0 +1,81 @@
+use crate::mempool::config::Config;
+
+/// Time horizon of the normal bucket, in seconds
+const NORMAL_HORIZON_SECONDS: u64 = 60;
+
+/// Time horizon of the low bucket, in seconds
+const LOW_HORIZON_SECONDS: u64 = 3600;
+
+/// A feerate, in sompi per gram, along with the expected time for a transaction paying it to get into a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+/// Feerates a transaction should pay depending on the urgency of its inclusion, given the current mempool congestion
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeEstimate {
+    /// Feerate for getting into the next block
+    pub priority_bucket: FeerateBucket,
+    /// Feerate for getting into a block within about a minute
+    pub normal_bucket: FeerateBucket,
+    /// Feerate for getting into a block within about an hour
+    pub low_bucket: FeerateBucket,
+}
+
+impl FeeEstimate {
+    /// Builds an estimate from the feerate and mass of the mempool transactions, assuming block templates
+    /// select transactions by decreasing feerate
+    pub(crate) fn new(mut transactions: Vec<(f64, u64)>, config: &Config) -> Self {
+        transactions.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let minimum_feerate = config.minimum_relay_transaction_fee as f64 / 1000.0;
+        let block_mass = config.maximum_mass_per_block.max(1);
+        let block_seconds = config.target_time_per_block as f64 / 1000.0;
+        let blocks_within = |seconds: u64| (seconds * 1000 / config.target_time_per_block.max(1)).max(1);
+
+        let bucket = |blocks: u64| {
+            // The feerate of the first transaction not fitting in the given number of blocks has to be matched
+            let mut mass = 0u64;
+            let feerate = transactions
+                .iter()
+                .find(|(_, tx_mass)| {
+                    mass = mass.saturating_add(*tx_mass);
+                    mass > blocks.saturating_mul(block_mass)
+                })
+                .map_or(minimum_feerate, |(feerate, _)| feerate.max(minimum_feerate));
+            // Transactions paying a higher feerate get in first
+            let mass_ahead =
+                transactions.iter().take_while(|(tx_feerate, _)| *tx_feerate > feerate).map(|(_, mass)| *mass).sum::<u64>();
+            FeerateBucket { feerate, estimated_seconds: (mass_ahead / block_mass + 1) as f64 * block_seconds }
+        };
+
+        Self {
+            priority_bucket: bucket(1),
+            normal_bucket: bucket(blocks_within(NORMAL_HORIZON_SECONDS)),
+            low_bucket: bucket(blocks_within(LOW_HORIZON_SECONDS)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_estimate() {
+        let config = Config::build_default(1000, false, 1000);
+
+        // An empty mempool gets every transaction paying the minimum feerate into the next block
+        let estimate = FeeEstimate::new(vec![], &config);
+        let minimum = FeerateBucket { feerate: 1.0, estimated_seconds: 1.0 };
+        assert_eq!(estimate, FeeEstimate { priority_bucket: minimum, normal_bucket: minimum, low_bucket: minimum });
+
+        // 10 blocks worth of transactions paying 5 sompi/gram and 100 blocks worth paying 2 sompi/gram
+        let transactions = (0..10).map(|_| (5.0, 1000)).chain((0..100).map(|_| (2.0, 1000))).collect::<Vec<_>>();
+        let estimate = FeeEstimate::new(transactions, &config);
+        assert_eq!(estimate.priority_bucket, FeerateBucket { feerate: 5.0, estimated_seconds: 1.0 });
+        assert_eq!(estimate.normal_bucket, FeerateBucket { feerate: 2.0, estimated_seconds: 11.0 });
+        assert_eq!(estimate.low_bucket, FeerateBucket { feerate: 1.0, estimated_seconds: 111.0 });
+    }
+}
@@ -2,7 +2,10 @@ use kaspa_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub(crate) mod candidate_tx;
+pub mod evicted_txs;
+pub mod fee_estimate;
 pub mod owner_txs;
+pub mod template_exclusions;
 pub mod topological_index;
 pub mod topological_sort;
 pub mod tx_query;
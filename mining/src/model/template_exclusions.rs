@@ -0,0 +1,100 @@
+use super::{owner_txs::ScriptPublicKeySet, TransactionIdSet};
+use kaspa_consensus_core::tx::{MutableTransaction, ScriptPublicKey, TransactionId};
+use std::fmt::{Display, Formatter};
+
+/// Transactions the node refuses to include in the block templates it builds, either by id or because they spend
+/// from or pay to an excluded script public key.
+///
+/// Excluded transactions are still accepted and relayed by the mempool, the exclusion only applying to local mining.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateExclusions {
+    transaction_ids: TransactionIdSet,
+    script_public_keys: ScriptPublicKeySet,
+}
+
+impl TemplateExclusions {
+    pub fn transaction_ids(&self) -> &TransactionIdSet {
+        &self.transaction_ids
+    }
+
+    pub fn script_public_keys(&self) -> &ScriptPublicKeySet {
+        &self.script_public_keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transaction_ids.is_empty() && self.script_public_keys.is_empty()
+    }
+
+    /// Applies `update`, removals being applied after additions
+    pub fn update(&mut self, update: TemplateExclusionsUpdate) {
+        self.transaction_ids.extend(update.add_transaction_ids);
+        self.script_public_keys.extend(update.add_script_public_keys);
+        update.remove_transaction_ids.iter().for_each(|id| {
+            self.transaction_ids.remove(id);
+        });
+        update.remove_script_public_keys.iter().for_each(|spk| {
+            self.script_public_keys.remove(spk);
+        });
+    }
+
+    /// Returns the reason why `transaction` is excluded from the block templates, if it is.
+    ///
+    /// The UTXO entries of the transaction are expected to be populated.
+    pub fn exclusion_reason(&self, transaction: &MutableTransaction) -> Option<TemplateExclusionReason> {
+        if self.is_empty() {
+            return None;
+        }
+        if self.transaction_ids.contains(&transaction.id()) {
+            return Some(TemplateExclusionReason::TransactionId);
+        }
+        if let Some(entry) =
+            transaction.entries.iter().flatten().find(|entry| self.script_public_keys.contains(&entry.script_public_key))
+        {
+            return Some(TemplateExclusionReason::SpendsFrom(entry.script_public_key.clone()));
+        }
+        if let Some(output) = transaction.tx.outputs.iter().find(|output| self.script_public_keys.contains(&output.script_public_key))
+        {
+            return Some(TemplateExclusionReason::PaysTo(output.script_public_key.clone()));
+        }
+        None
+    }
+}
+
+/// Changes to apply to the [`TemplateExclusions`]
+#[derive(Clone, Debug, Default)]
+pub struct TemplateExclusionsUpdate {
+    pub add_transaction_ids: Vec<TransactionId>,
+    pub remove_transaction_ids: Vec<TransactionId>,
+    pub add_script_public_keys: Vec<ScriptPublicKey>,
+    pub remove_script_public_keys: Vec<ScriptPublicKey>,
+}
+
+impl TemplateExclusionsUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.add_transaction_ids.is_empty()
+            && self.remove_transaction_ids.is_empty()
+            && self.add_script_public_keys.is_empty()
+            && self.remove_script_public_keys.is_empty()
+    }
+}
+
+/// Reason why a transaction is excluded from the block templates
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateExclusionReason {
+    /// The transaction id is excluded
+    TransactionId,
+    /// The transaction spends a UTXO locked by an excluded script public key
+    SpendsFrom(ScriptPublicKey),
+    /// The transaction has an output locked by an excluded script public key
+    PaysTo(ScriptPublicKey),
+}
+
+impl Display for TemplateExclusionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateExclusionReason::TransactionId => write!(f, "excluded transaction id"),
+            TemplateExclusionReason::SpendsFrom(spk) => write!(f, "spends from excluded script public key {}", spk.script_as_hex()),
+            TemplateExclusionReason::PaysTo(spk) => write!(f, "pays to excluded script public key {}", spk.script_as_hex()),
+        }
+    }
+}
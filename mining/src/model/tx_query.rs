@@ -1,3 +1,5 @@
+use super::owner_txs::ScriptPublicKeySet;
+
 /// Indicates whether the mempool query result should include transactions/orphans or both
 pub enum TransactionQuery {
     /// Include only non-orphan transactions from the ordinary mempool tx pool
@@ -17,3 +19,25 @@ impl TransactionQuery {
         matches!(self, TransactionQuery::OrphansOnly | TransactionQuery::All)
     }
 }
+
+/// Criteria restricting the transactions returned by a mempool query
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilter {
+    /// Include only transactions with a fee rate (in sompi per gram of mass) at least equal to this value.
+    ///
+    /// Orphan transactions have no calculated fee so they never match this criterion.
+    pub min_fee_rate: Option<f64>,
+
+    /// Include only transactions added to the mempool at or after this DAA score
+    pub min_added_at_daa_score: Option<u64>,
+
+    /// Include only transactions having an output spendable by one of these script public keys.
+    /// An empty set disables the criterion.
+    pub script_public_keys: ScriptPublicKeySet,
+}
+
+impl TransactionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_fee_rate.is_none() && self.min_added_at_daa_score.is_none() && self.script_public_keys.is_empty()
+    }
+}
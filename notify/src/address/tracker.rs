@@ -598,6 +598,10 @@ pub struct TrackerReadGuard<'a> {
 }
 
 impl<'a> TrackerReadGuard<'a> {
+    pub fn get(&self, spk: &ScriptPublicKey) -> Option<(Index, RefCount)> {
+        self.guard.get(spk)
+    }
+
     pub fn get_index(&'a self, index: Index) -> Option<&'a ScriptPublicKey> {
         self.guard.get_index(index)
     }
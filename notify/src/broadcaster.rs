@@ -5,12 +5,16 @@ use crate::{
     events::{EventArray, EventType},
     listener::ListenerId,
     notification::Notification,
+    stats::NotificationStats,
     subscription::{context::SubscriptionContext, BroadcastingSingle, DynSubscription},
 };
 use async_channel::{Receiver, Sender};
 use core::fmt::Debug;
 use derive_more::Deref;
-use futures::{future::FutureExt, select_biased};
+use futures::{
+    future::{BoxFuture, Fuse, FutureExt},
+    select_biased,
+};
 use indexmap::IndexMap;
 use kaspa_core::{debug, trace};
 use std::{
@@ -20,6 +24,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use workflow_core::channel::Channel;
 
@@ -109,6 +114,9 @@ where
     ctl: Channel<Ctl<C>>,
     incoming: Receiver<N>,
     shutdown: Channel<()>,
+    stats: Arc<NotificationStats>,
+    /// Window during which consecutive UtxosChanged notifications are merged before being broadcast, if any
+    utxos_changed_coalescing_window: Option<Duration>,
     /// Sync channel, for handling of messages in predictable sequence; exclusively intended for tests.
     _sync: Option<Sender<()>>,
 }
@@ -123,6 +131,7 @@ where
         index: usize,
         context: SubscriptionContext,
         incoming: Receiver<N>,
+        stats: Arc<NotificationStats>,
         _sync: Option<Sender<()>>,
     ) -> Self {
         Self {
@@ -134,9 +143,18 @@ where
             incoming,
             _sync,
             shutdown: Channel::oneshot(),
+            stats,
+            utxos_changed_coalescing_window: None,
         }
     }
 
+    /// Merges the UtxosChanged notifications received within `window` of each other before broadcasting them.
+    ///
+    /// Must be set before the broadcaster is started.
+    pub fn set_utxos_changed_coalescing_window(&mut self, window: Option<Duration>) {
+        self.utxos_changed_coalescing_window = window;
+    }
+
     pub fn start(self: &Arc<Self>) {
         self.clone().spawn_notification_broadcasting_task();
     }
@@ -151,8 +169,9 @@ where
         workflow_core::task::spawn(async move {
             // Broadcasting plan by event type
             let mut plan = EventArray::<Plan<C>>::default();
-            // Create a store for closed connections to be removed from the plan
-            let mut purge: Vec<ListenerId> = Vec::new();
+            // UtxosChanged notification held back for coalescing and the timer of its broadcast
+            let mut pending: Option<N> = None;
+            let mut flush: Fuse<BoxFuture<'static, ()>> = Fuse::terminated();
             loop {
                 select_biased! {
                     ctl = self.ctl.recv().fuse() => {
@@ -175,37 +194,37 @@ where
 
                     notification = self.incoming.recv().fuse() => {
                         if let Ok(notification) = notification {
-                            // Broadcast the notification...
-                            let event = notification.event_type();
-                            for (subscription, encoding_set) in plan[event].iter() {
-                                // ... by subscription scope
-                                if let Some(applied_notification) = notification.apply_subscription(&**subscription, &context) {
-                                    for (encoding, connection_set) in encoding_set.iter() {
-                                        // ... by message encoding
-                                        let message = C::into_message(&applied_notification, encoding);
-                                        for (id, connection) in connection_set.iter() {
-                                            // ... to listeners connections
-                                            match connection.send(message.clone()).await {
-                                                Ok(_) => {
-                                                    trace!("[{}] sent notification {notification} to listener {id}", self);
-                                                },
-                                                Err(_) => {
-                                                    if connection.is_closed() {
-                                                        trace!("[{}] could not send a notification to listener {id} because its connection is closed - removing it", self);
-                                                        purge.push(*id);
-                                                    }
-                                                }
-                                            }
+                            match self.utxos_changed_coalescing_window {
+                                Some(window) if notification.event_type() == EventType::UtxosChanged => {
+                                    // Hold the notification back for the window, merging into it the ones following meanwhile
+                                    if !pending.as_mut().is_some_and(|pending| pending.coalesce(&notification)) {
+                                        if let Some(pending) = pending.take() {
+                                            self.broadcast(&mut plan, pending).await;
                                         }
+                                        pending = Some(notification);
+                                        flush = workflow_core::task::sleep(window).boxed().fuse();
+                                    }
+                                }
+                                _ => {
+                                    // Preserve the ordering of the notifications by flushing the held back one first
+                                    if let Some(pending) = pending.take() {
+                                        self.broadcast(&mut plan, pending).await;
                                     }
+                                    self.broadcast(&mut plan, notification).await;
                                 }
                             }
-                            // Remove closed connections
-                            purge.drain(..).for_each(|id| { plan[event].remove(&id); });
-
                         } else {
+                            if let Some(pending) = pending.take() {
+                                self.broadcast(&mut plan, pending).await;
+                            }
                             break;
                         }
+                    },
+
+                    _ = flush => {
+                        if let Some(pending) = pending.take() {
+                            self.broadcast(&mut plan, pending).await;
+                        }
                     }
                 }
 
@@ -221,6 +240,57 @@ where
         });
     }
 
+    async fn broadcast(&self, plan: &mut EventArray<Plan<C>>, notification: N) {
+        // Create a store for closed connections to be removed from the plan
+        let mut purge: Vec<ListenerId> = Vec::new();
+        // Broadcast the notification...
+        let event = notification.event_type();
+        // The subscriptions are all applied at once, so a UtxosChanged notification resolves its addresses once
+        let applied_notifications = notification.apply_subscriptions(plan[event].keys(), &self.context);
+        for (encoding_set, applied_notification) in plan[event].values().zip(applied_notifications) {
+            // ... by subscription scope
+            if let Some(applied_notification) = applied_notification {
+                for (encoding, connection_set) in encoding_set.iter() {
+                    // ... by message encoding
+                    let mut message = Some(C::into_message(&applied_notification, encoding));
+                    let last = connection_set.len().saturating_sub(1);
+                    for (index, (id, connection)) in connection_set.iter().enumerate() {
+                        // ... to listeners connections
+                        #[cfg(feature = "fault-injection")]
+                        if kaspa_utils::fault::NOTIFICATION_DROP.should_fire() {
+                            trace!("[{}] dropped notification {notification} to listener {id} (injected fault)", self);
+                            self.stats.dropped(event);
+                            continue;
+                        }
+                        // The last connection gets the message itself, sparing it a clone
+                        let message = match index == last {
+                            true => message.take(),
+                            false => message.clone(),
+                        }
+                        .expect("the message is only taken by the last connection");
+                        match connection.send(message).await {
+                            Ok(_) => {
+                                trace!("[{}] sent notification {notification} to listener {id}", self);
+                                self.stats.delivered(event);
+                            }
+                            Err(_) => {
+                                self.stats.dropped(event);
+                                if connection.is_closed() {
+                                    trace!("[{}] could not send a notification to listener {id} because its connection is closed - removing it", self);
+                                    purge.push(*id);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // Remove closed connections
+        purge.drain(..).for_each(|id| {
+            plan[event].remove(&id);
+        });
+    }
+
     pub fn register(&self, subscription: DynSubscription, id: ListenerId, connection: C) -> Result<()> {
         assert!(subscription.active());
         self.ctl.try_send(Ctl::Register(subscription, id, connection))?;
@@ -258,13 +328,16 @@ where
 mod tests {
     use super::*;
     use crate::{
+        address::test_helpers::get_3_addresses,
         connection::{ChannelConnection, ChannelType},
         listener::Listener,
         notification::test_helpers::*,
         notifier::test_helpers::{
             overall_test_steps, utxos_changed_test_steps, virtual_chain_changed_test_steps, Step, TestConnection, SYNC_MAX_DELAY,
         },
+        scope::{Scope, UtxosChangedScope},
         subscription::context::SubscriptionContext,
+        subscription::{Command, Mutation},
     };
     use async_channel::{unbounded, Sender};
     use tokio::time::timeout;
@@ -290,8 +363,14 @@ mod tests {
             let subscription_context = SubscriptionContext::new();
             let (sync_sender, sync_receiver) = unbounded();
             let (notification_sender, notification_receiver) = unbounded();
-            let broadcaster =
-                Arc::new(TestBroadcaster::new(IDENT, 0, subscription_context.clone(), notification_receiver, Some(sync_sender)));
+            let broadcaster = Arc::new(TestBroadcaster::new(
+                IDENT,
+                0,
+                subscription_context.clone(),
+                notification_receiver,
+                Default::default(),
+                Some(sync_sender),
+            ));
             let mut listeners = Vec::with_capacity(listener_count);
             let mut notification_receivers = Vec::with_capacity(listener_count);
             for i in 0..listener_count {
@@ -455,4 +534,50 @@ mod tests {
         let mut test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(0));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_utxos_changed_coalescing() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        const WINDOW: Duration = Duration::from_millis(100);
+        let addresses = get_3_addresses(true);
+        let subscription_context = SubscriptionContext::new();
+        let (notification_sender, notification_receiver) = unbounded();
+        let mut broadcaster =
+            TestBroadcaster::new("test", 0, subscription_context.clone(), notification_receiver, Default::default(), None);
+        broadcaster.set_utxos_changed_coalescing_window(Some(WINDOW));
+        let broadcaster = Arc::new(broadcaster);
+        broadcaster.start();
+
+        let (sender, receiver) = unbounded();
+        let mut listener = Listener::new(0, TestConnection::new("test", sender, ChannelType::Closable));
+        let mutation = Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::new(addresses.clone())));
+        listener.mutate(mutation, Default::default(), &subscription_context).unwrap();
+        broadcaster.register(listener.subscriptions[EventType::UtxosChanged].clone(), 0, listener.connection()).unwrap();
+
+        let n = |data: u64, indexes: &[usize]| {
+            TestNotification::UtxosChanged(UtxosChangedNotification {
+                data,
+                addresses: Arc::new(indexes.iter().map(|idx| addresses[*idx].clone()).collect()),
+            })
+        };
+
+        // Notifications following each other within the window are merged into a single one...
+        notification_sender.send(n(1, &[0])).await.unwrap();
+        notification_sender.send(n(2, &[1, 2])).await.unwrap();
+        assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), n(2, &[0, 1, 2]));
+        assert!(receiver.is_empty());
+
+        // ... while a notification of another event type flushes the pending one first, preserving the ordering
+        notification_sender.send(n(3, &[2])).await.unwrap();
+        notification_sender.send(TestNotification::BlockAdded(BlockAddedNotification { data: 4 })).await.unwrap();
+        notification_sender.send(n(5, &[0])).await.unwrap();
+        assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), n(3, &[2]));
+        assert_eq!(timeout(SYNC_MAX_DELAY, receiver.recv()).await.unwrap().unwrap(), n(5, &[0]));
+
+        // Closing the incoming channel flushes the pending notification
+        notification_sender.send(n(6, &[1])).await.unwrap();
+        notification_sender.close();
+        assert!(broadcaster.join().await.is_ok(), "broadcaster failed to stop");
+        assert_eq!(receiver.try_recv().unwrap(), n(6, &[1]));
+    }
 }
@@ -26,6 +26,11 @@ pub enum Error {
     #[error("Invalid event type: {0}")]
     InvalidEventType(String),
 
+    #[error(
+        "the UtxosChanged subscription would cover {0} addresses, exceeding the maximum of {1} per listener; consider subscribing to all addresses instead"
+    )]
+    MaxListenerAddressesExceeded(usize, usize),
+
     #[error(transparent)]
     AddressError(#[from] crate::address::error::Error),
 }
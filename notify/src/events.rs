@@ -1,5 +1,6 @@
 use super::scope::Scope;
 use crate::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
@@ -39,7 +40,7 @@ event_type_enum! {
     ///
     /// Note: This enum is central to the notification system. For supporting a new notification type, it is advised to
     /// start by adding a new variant here.
-    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
     #[serde(rename_all = "kebab-case")]
     pub enum EventType {
         BlockAdded = 0,
@@ -51,10 +52,14 @@ event_type_enum! {
         VirtualDaaScoreChanged,
         PruningPointUtxoSetOverride,
         NewBlockTemplate,
+        HealthAlert,
+        MempoolTransactionsEvicted,
+        JobCompleted,
+        IndexResyncProgress,
     }
 }
 
-pub const EVENT_COUNT: usize = 9;
+pub const EVENT_COUNT: usize = 13;
 
 impl FromStr for EventType {
     type Err = Error;
@@ -70,6 +75,10 @@ impl FromStr for EventType {
             "virtual-daa-score-changed" => Ok(EventType::VirtualDaaScoreChanged),
             "pruning-point-utxo-set-override" => Ok(EventType::PruningPointUtxoSetOverride),
             "new-block-template" => Ok(EventType::NewBlockTemplate),
+            "health-alert" => Ok(EventType::HealthAlert),
+            "mempool-transactions-evicted" => Ok(EventType::MempoolTransactionsEvicted),
+            "job-completed" => Ok(EventType::JobCompleted),
+            "index-resync-progress" => Ok(EventType::IndexResyncProgress),
             _ => Err(Error::InvalidEventType(s.to_string())),
         }
     }
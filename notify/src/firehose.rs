@@ -0,0 +1,83 @@
+//! Unfiltered stream of every notification entering a [`Root`](crate::root::Root)
+//!
+//! A firehose bypasses the subscriptions and the fan-out of the notifiers, relaying the notifications
+//! as produced over a dedicated bounded channel. It is meant for co-located consumers, like indexers,
+//! needing every event at the lowest overhead and owning their own backpressure domain.
+
+use crate::error::{Error, Result};
+use async_channel::{bounded, Receiver, Sender, TrySendError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Behavior of a firehose when its channel is full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FirehoseOverflow {
+    /// Blocks the producer until the consumer catches up, so no notification is ever lost
+    #[default]
+    Block,
+    /// Drops the notification, keeping the producer unaffected by a slow consumer
+    Drop,
+}
+
+#[derive(Debug)]
+pub struct Firehose<N> {
+    sender: Sender<N>,
+    overflow: FirehoseOverflow,
+    /// Number of notifications dropped on overflow
+    dropped: AtomicU64,
+}
+
+impl<N> Firehose<N> {
+    /// Creates a firehose holding up to `capacity` pending notifications and the receiver of its channel
+    pub fn new(capacity: usize, overflow: FirehoseOverflow) -> (Self, Receiver<N>) {
+        let (sender, receiver) = bounded(capacity);
+        (Self { sender, overflow, dropped: Default::default() }, receiver)
+    }
+
+    pub fn send(&self, notification: N) -> Result<()> {
+        match self.overflow {
+            FirehoseOverflow::Block => self.sender.send_blocking(notification).map_err(|_| Error::ChannelSendError),
+            FirehoseOverflow::Drop => match self.sender.try_send(notification) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+                Err(TrySendError::Closed(_)) => Err(Error::ChannelSendError),
+            },
+        }
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn close(&self) -> bool {
+        self.sender.close()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_firehose_overflow() {
+        let (firehose, receiver) = Firehose::new(2, FirehoseOverflow::Drop);
+        (0..5).for_each(|i| firehose.send(i).unwrap());
+        assert_eq!(firehose.dropped(), 3);
+        assert_eq!(receiver.try_recv(), Ok(0));
+        assert_eq!(receiver.try_recv(), Ok(1));
+        assert!(receiver.try_recv().is_err());
+
+        let (firehose, receiver) = Firehose::new(1, FirehoseOverflow::Block);
+        let consumer = std::thread::spawn(move || (0..3).map(|_| receiver.recv_blocking().unwrap()).collect::<Vec<_>>());
+        (0..3).for_each(|i| firehose.send(i).unwrap());
+        assert_eq!(consumer.join().unwrap(), vec![0, 1, 2]);
+        assert_eq!(firehose.dropped(), 0);
+        assert!(firehose.send(3).is_err());
+    }
+}
@@ -7,10 +7,12 @@ pub mod connection;
 pub mod converter;
 pub mod error;
 pub mod events;
+pub mod firehose;
 pub mod listener;
 pub mod notification;
 pub mod notifier;
 pub mod root;
 pub mod scope;
+pub mod stats;
 pub mod subscriber;
 pub mod subscription;
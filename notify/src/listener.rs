@@ -1,13 +1,19 @@
-use std::fmt::Debug;
+use std::{collections::HashSet, fmt::Debug};
 extern crate derive_more;
+use kaspa_addresses::Address;
 use kaspa_core::debug;
 
 use crate::{
-    error::Result,
+    error::{Error, Result},
+    events::EventType,
+    scope::{Scope, UtxosChangedScope},
     subscription::{
-        context::SubscriptionContext, DynSubscription, MutateSingle, MutationOutcome, MutationPolicies, UtxosChangedMutationPolicy,
+        context::SubscriptionContext,
+        single::{SubscriptionSummary, UtxosChangedState, UtxosChangedSubscription},
+        Command, DynSubscription, MutateSingle, MutationOutcome, MutationPolicies, UtxosChangedMutationPolicy,
     },
 };
+use itertools::Itertools;
 
 use super::{
     connection::Connection,
@@ -31,7 +37,7 @@ where
 {
     connection: C,
     pub(crate) subscriptions: EventArray<DynSubscription>,
-    pub(crate) _lifespan: ListenerLifespan,
+    pub(crate) lifespan: ListenerLifespan,
 }
 
 impl<C> Listener<C>
@@ -39,7 +45,7 @@ where
     C: Connection,
 {
     pub fn new(id: ListenerId, connection: C) -> Self {
-        Self { connection, subscriptions: ArrayBuilder::single(id, None), _lifespan: ListenerLifespan::Dynamic }
+        Self { connection, subscriptions: ArrayBuilder::single(id, None), lifespan: ListenerLifespan::Dynamic }
     }
 
     pub fn new_static(id: ListenerId, connection: C, context: &SubscriptionContext, policies: MutationPolicies) -> Self {
@@ -55,7 +61,7 @@ where
             UtxosChangedMutationPolicy::Wildcard => None,
         };
         let subscriptions = ArrayBuilder::single(id, capacity);
-        Self { connection, subscriptions, _lifespan: ListenerLifespan::Static(policies) }
+        Self { connection, subscriptions, lifespan: ListenerLifespan::Static(policies) }
     }
 
     pub fn connection(&self) -> C {
@@ -69,10 +75,83 @@ where
         policies: MutationPolicies,
         context: &SubscriptionContext,
     ) -> Result<MutationOutcome> {
+        if let ListenerLifespan::Dynamic = self.lifespan {
+            self.check_address_limit(&mutation, context)?;
+        }
         let event_type = mutation.event_type();
         self.subscriptions[event_type].mutate(mutation, policies, context)
     }
 
+    /// Checks that a mutation does not grow the UtxosChanged address set above the maximum allowed per listener
+    fn check_address_limit(&self, mutation: &Mutation, context: &SubscriptionContext) -> Result<()> {
+        let (Some(max_addresses), Command::Start, Scope::UtxosChanged(scope)) =
+            (context.max_listener_addresses, mutation.command, &mutation.scope)
+        else {
+            return Ok(());
+        };
+        let subscription = &self.subscriptions[EventType::UtxosChanged];
+        let data = subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap().data();
+        if data.to_all() {
+            return Ok(());
+        }
+        let added = scope.addresses.iter().unique().filter(|address| !data.contains_address(address, context)).count();
+        match data.len() + added {
+            count if count > max_addresses => Err(Error::MaxListenerAddressesExceeded(count, max_addresses)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Replaces the UtxosChanged address set with `addresses` by applying only the difference with the current set,
+    /// first starting the added addresses and then stopping the removed ones, so the subscription is never torn down.
+    ///
+    /// Returns `None` without applying anything if the subscription is not on a selected address set or if `addresses`
+    /// is empty, in which case a start command yields the same result.
+    pub fn replace_utxos_changed_addresses(
+        &mut self,
+        addresses: &[Address],
+        policies: MutationPolicies,
+        context: &SubscriptionContext,
+    ) -> Result<Option<Vec<MutationOutcome>>> {
+        let addresses: HashSet<&Address> = addresses.iter().collect();
+        let (added, removed) = {
+            let subscription = &self.subscriptions[EventType::UtxosChanged];
+            let data = subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap().data();
+            let Some(prefix) = addresses.iter().next().map(|address| address.prefix) else {
+                return Ok(None);
+            };
+            if data.state() != UtxosChangedState::Selected {
+                return Ok(None);
+            }
+            if let (ListenerLifespan::Dynamic, Some(max_addresses)) = (self.lifespan, context.max_listener_addresses) {
+                if addresses.len() > max_addresses {
+                    return Err(Error::MaxListenerAddressesExceeded(addresses.len(), max_addresses));
+                }
+            }
+            let added = addresses
+                .iter()
+                .filter(|address| !data.contains_address(address, context))
+                .map(|&address| address.clone())
+                .collect_vec();
+            let removed = data.to_addresses(prefix, context).into_iter().filter(|address| !addresses.contains(&address)).collect_vec();
+            (added, removed)
+        };
+        // Added addresses go first so the address set never gets empty in between, which would
+        // make the subscription inactive and propagate a full stop/start upstream
+        let mut outcomes = Vec::with_capacity(2);
+        for (command, addresses) in [(Command::Start, added), (Command::Stop, removed)] {
+            if !addresses.is_empty() {
+                let mutation = Mutation::new(command, UtxosChangedScope::new(addresses).into());
+                outcomes.push(self.subscriptions[EventType::UtxosChanged].mutate(mutation, policies, context)?);
+            }
+        }
+        Ok(Some(outcomes))
+    }
+
+    /// Returns a summary of the active subscriptions
+    pub fn active_subscriptions(&self) -> Vec<SubscriptionSummary> {
+        self.subscriptions.iter().filter(|subscription| subscription.active()).map(SubscriptionSummary::from).collect()
+    }
+
     pub fn close(&self) {
         if !self.is_closed() {
             self.connection.close();
@@ -4,7 +4,7 @@ use super::{
     events::EventType,
     subscription::{
         single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
-        Single,
+        DynSubscription, Single,
     },
 };
 use std::fmt::{Debug, Display};
@@ -22,6 +22,18 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription, context: &SubscriptionContext)
         -> Option<Self>;
 
+    /// Applies a batch of UtxosChanged subscriptions, returning the notification filtered by each one of them in order.
+    ///
+    /// Implementors can override this to match all the subscriptions in a single pass over the notification,
+    /// see [`UtxosChangedBatch`](crate::subscription::batch::UtxosChangedBatch).
+    fn apply_utxos_changed_subscriptions(
+        &self,
+        subscriptions: &[&UtxosChangedSubscription],
+        context: &SubscriptionContext,
+    ) -> Vec<Option<Self>> {
+        subscriptions.iter().map(|subscription| self.apply_utxos_changed_subscription(subscription, context)).collect()
+    }
+
     fn apply_subscription(&self, subscription: &dyn Single, context: &SubscriptionContext) -> Option<Self> {
         match subscription.event_type() {
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
@@ -34,7 +46,32 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
         }
     }
 
+    /// Applies the subscriptions of a broadcasting plan, all of the event type of the notification, returning
+    /// the notification filtered by each one of them in order
+    fn apply_subscriptions<'a>(
+        &self,
+        subscriptions: impl Iterator<Item = &'a DynSubscription>,
+        context: &SubscriptionContext,
+    ) -> Vec<Option<Self>> {
+        match self.event_type() {
+            EventType::UtxosChanged => {
+                let subscriptions = subscriptions
+                    .map(|subscription| subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap())
+                    .collect::<Vec<_>>();
+                self.apply_utxos_changed_subscriptions(&subscriptions, context)
+            }
+            _ => subscriptions.map(|subscription| self.apply_subscription(&**subscription, context)).collect(),
+        }
+    }
+
     fn event_type(&self) -> EventType;
+
+    /// Merges `next`, a later notification of the same event type, into `self` so that both can be broadcast as one.
+    ///
+    /// Returns `false`, leaving `self` unchanged, if the event type does not support coalescing.
+    fn coalesce(&mut self, _next: &Self) -> bool {
+        false
+    }
 }
 
 #[macro_export]
@@ -181,6 +218,17 @@ pub mod test_helpers {
         fn event_type(&self) -> EventType {
             self.into()
         }
+
+        fn coalesce(&mut self, next: &Self) -> bool {
+            match (self, next) {
+                (TestNotification::UtxosChanged(payload), TestNotification::UtxosChanged(next)) => {
+                    payload.data = next.data;
+                    Arc::make_mut(&mut payload.addresses).extend(next.addresses.iter().cloned());
+                    true
+                }
+                _ => false,
+            }
+        }
     }
 
     /// A trait to help tests match notification received and expected thanks to some predefined data
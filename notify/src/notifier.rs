@@ -1,7 +1,9 @@
 use crate::{
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
-    subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
+    subscription::{
+        context::SubscriptionContext, single::SubscriptionSummary, MutationOutcome, MutationPolicies, UtxosChangedMutationPolicy,
+    },
 };
 
 use super::{
@@ -12,7 +14,8 @@ use super::{
     events::{EventArray, EventSwitches, EventType},
     listener::{Listener, ListenerId},
     notification::Notification,
-    scope::Scope,
+    scope::{Scope, UtxosChangedScope},
+    stats::NotificationStats,
     subscriber::{Subscriber, SubscriptionManager},
     subscription::{array::ArrayBuilder, Command, CompoundedSubscription, Mutation},
 };
@@ -21,6 +24,7 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use futures::future::join_all;
 use itertools::Itertools;
+use kaspa_addresses::Address;
 use kaspa_core::{debug, trace};
 use parking_lot::Mutex;
 use std::{
@@ -29,6 +33,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use workflow_core::channel::Channel;
 
@@ -155,6 +160,18 @@ where
         }
     }
 
+    /// Merges the consecutive UtxosChanged notifications received within `window` of each other into a single one
+    /// before broadcasting it, lowering the notification rate of listeners tracking busy addresses.
+    ///
+    /// Must be called before the notifier is shared or started.
+    pub fn with_utxos_changed_coalescing(mut self, window: Duration) -> Self {
+        let inner = Arc::get_mut(&mut self.inner).expect("the notifier is not shared yet");
+        inner.broadcasters.iter_mut().for_each(|broadcaster| {
+            Arc::get_mut(broadcaster).expect("the broadcaster is not started yet").set_utxos_changed_coalescing_window(Some(window));
+        });
+        self
+    }
+
     pub fn subscription_context(&self) -> &SubscriptionContext {
         &self.inner.subscription_context
     }
@@ -194,6 +211,16 @@ where
         self.inner.unregister_listener(id)
     }
 
+    /// Returns a summary of the active subscriptions of listener `id`, empty if the listener is unknown
+    pub fn listener_subscriptions(&self, id: ListenerId) -> Vec<SubscriptionSummary> {
+        self.inner.listeners.lock().get(&id).map(|listener| listener.active_subscriptions()).unwrap_or_default()
+    }
+
+    /// Returns the notification delivery statistics of this notifier
+    pub fn stats(&self) -> Arc<NotificationStats> {
+        self.inner.stats.clone()
+    }
+
     pub async fn join(&self) -> Result<()> {
         self.inner.clone().join().await
     }
@@ -249,6 +276,9 @@ where
     /// Channel used to send the notifications to the broadcasters
     notification_channel: Channel<N>,
 
+    /// Notification delivery statistics
+    stats: Arc<NotificationStats>,
+
     /// Array of notification broadcasters
     broadcasters: Vec<Arc<Broadcaster<N, C>>>,
 
@@ -291,6 +321,7 @@ where
     ) -> Self {
         assert!(broadcasters > 0, "a notifier requires a minimum of one broadcaster");
         let notification_channel = Channel::unbounded();
+        let stats = Arc::new(NotificationStats::default());
         let broadcasters = (0..broadcasters)
             .map(|idx| {
                 Arc::new(Broadcaster::new(
@@ -298,6 +329,7 @@ where
                     idx,
                     subscription_context.clone(),
                     notification_channel.receiver.clone(),
+                    stats.clone(),
                     _sync.clone(),
                 ))
             })
@@ -319,6 +351,7 @@ where
             subscriptions: Mutex::new(ArrayBuilder::compounded(utxos_changed_capacity)),
             started: Arc::new(AtomicBool::new(false)),
             notification_channel,
+            stats,
             broadcasters,
             collectors,
             subscribers,
@@ -390,7 +423,14 @@ where
         if self.enabled_events[event] {
             let mut listeners = self.listeners.lock();
             if let Some(listener) = listeners.get_mut(&id) {
-                self.execute_subscribe_command_impl(id, listener, scope, command)?;
+                match (command, scope) {
+                    (Command::Start, Scope::UtxosChanged(UtxosChangedScope { addresses, replace: true })) => {
+                        self.replace_utxos_changed_addresses(id, listener, addresses)?;
+                    }
+                    (command, scope) => {
+                        self.execute_subscribe_command_impl(id, listener, scope, command)?;
+                    }
+                }
             } else {
                 trace!("[Notifier {}] {command} notifying listener {id} about {scope} error: listener id not found", self.name);
             }
@@ -419,35 +459,74 @@ where
                 self.name,
                 outcome.mutations.len(),
             );
-            // Update broadcasters
-            match (listener.subscriptions[event].active(), outcome.mutated) {
-                (true, Some(subscription)) => {
-                    self.broadcasters
-                        .iter()
-                        .try_for_each(|broadcaster| broadcaster.register(subscription.clone(), id, listener.connection()))?;
-                }
-                (true, None) => {
-                    sync_feedback = true;
-                }
-                (false, _) => {
-                    self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.unregister(event, id))?;
-                }
-            }
-            self.apply_mutations(event, outcome.mutations, &self.subscription_context)?;
+            sync_feedback = self.process_outcome(id, listener, event, outcome)?;
         } else {
             trace!("[Notifier {}] {command} notifying listener {id} about {scope_trace} is ignored (no mutation)", self.name);
             sync_feedback = true;
         }
         if sync_feedback {
-            // In case we have a sync channel, report that the command was processed.
-            // This is for test only.
-            if let Some(ref sync) = self._sync {
-                let _ = sync.try_send(());
-            }
+            self.send_sync_feedback();
         }
         Ok(())
     }
 
+    /// Updates the broadcasters and the compounded subscriptions according to the outcome of a listener mutation.
+    ///
+    /// Returns true if the outcome does not involve any broadcaster change.
+    fn process_outcome(&self, id: ListenerId, listener: &Listener<C>, event: EventType, outcome: MutationOutcome) -> Result<bool> {
+        let mut sync_feedback = false;
+        // Update broadcasters
+        match (listener.subscriptions[event].active(), outcome.mutated) {
+            (true, Some(subscription)) => {
+                self.broadcasters
+                    .iter()
+                    .try_for_each(|broadcaster| broadcaster.register(subscription.clone(), id, listener.connection()))?;
+            }
+            (true, None) => {
+                sync_feedback = true;
+            }
+            (false, _) => {
+                self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.unregister(event, id))?;
+            }
+        }
+        self.apply_mutations(event, outcome.mutations, &self.subscription_context)?;
+        Ok(sync_feedback)
+    }
+
+    fn send_sync_feedback(&self) {
+        // In case we have a sync channel, report that the command was processed.
+        // This is for test only.
+        if let Some(ref sync) = self._sync {
+            let _ = sync.try_send(());
+        }
+    }
+
+    /// Replaces the UtxosChanged address set of listener `id` with `addresses`, only applying and propagating upwards
+    /// the addresses actually added or removed.
+    ///
+    /// Unlike a stop followed by a start command, this keeps the lock hold times and the compounded subscription churn
+    /// low when a client re-submits a large and mostly unchanged address set. If the listener is not subscribed to a
+    /// selected address set yet, this falls back to a start command.
+    fn replace_utxos_changed_addresses(&self, id: ListenerId, listener: &mut Listener<C>, addresses: Vec<Address>) -> Result<()> {
+        let event = EventType::UtxosChanged;
+        match listener.replace_utxos_changed_addresses(&addresses, self.policies, &self.subscription_context)? {
+            Some(outcomes) => {
+                debug!(
+                    "[Notifier {}] replacing the addresses of listener {id} with {} addresses involves {} mutations",
+                    self.name,
+                    addresses.len(),
+                    outcomes.iter().map(|outcome| outcome.mutations.len()).sum::<usize>()
+                );
+                for outcome in outcomes {
+                    self.process_outcome(id, listener, event, outcome)?;
+                }
+                self.send_sync_feedback();
+                Ok(())
+            }
+            None => self.execute_subscribe_command_impl(id, listener, UtxosChangedScope::new(addresses).into(), Command::Start),
+        }
+    }
+
     fn apply_mutations(&self, event: EventType, mutations: Vec<Mutation>, context: &SubscriptionContext) -> Result<()> {
         let mut subscriptions = self.subscriptions.lock();
         // Compound mutations
@@ -469,8 +548,10 @@ where
     }
 
     fn notify(&self, notification: N) -> Result<()> {
-        if self.enabled_events[notification.event_type()] {
+        let event_type = notification.event_type();
+        if self.enabled_events[event_type] {
             self.notification_channel.try_send(notification)?;
+            self.stats.emitted(event_type);
         }
         Ok(())
     }
@@ -823,11 +904,13 @@ pub mod test_helpers {
 mod tests {
     use super::{test_helpers::*, *};
     use crate::{
+        address::test_helpers::get_3_addresses,
         collector::CollectorFrom,
         connection::ChannelType,
         converter::ConverterFrom,
         events::EVENT_TYPE_ARRAY,
         notification::test_helpers::*,
+        scope::{BlockAddedScope, UtxosChangedScope, VirtualChainChangedScope},
         subscriber::test_helpers::{SubscriptionManagerMock, SubscriptionMessage},
     };
     use async_channel::{unbounded, Receiver, Sender};
@@ -1004,4 +1087,123 @@ mod tests {
         let test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(SUBSCRIPTION_MANAGER_ID));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_listener_subscriptions() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        let test = Test::new("Listener subscriptions", 2, vec![]);
+        test.notifier.clone().start();
+        let summary = |event_type, include_accepted_transaction_ids, all_addresses, address_count| SubscriptionSummary {
+            event_type,
+            include_accepted_transaction_ids,
+            all_addresses,
+            address_count,
+        };
+
+        let id = test.listeners[0];
+        assert!(test.notifier.listener_subscriptions(id).is_empty());
+        let scopes: [Scope; 3] = [
+            BlockAddedScope {}.into(),
+            VirtualChainChangedScope::new(true).into(),
+            UtxosChangedScope::new(get_3_addresses(false)).into(),
+        ];
+        for scope in scopes {
+            test.notifier.try_start_notify(id, scope).unwrap();
+        }
+        assert_eq!(
+            test.notifier.listener_subscriptions(id),
+            vec![
+                summary(EventType::BlockAdded, false, false, 0),
+                summary(EventType::VirtualChainChanged, true, false, 0),
+                summary(EventType::UtxosChanged, false, false, 3),
+            ]
+        );
+
+        // Other listeners are not affected
+        assert!(test.notifier.listener_subscriptions(test.listeners[1]).is_empty());
+
+        test.notifier.try_stop_notify(id, BlockAddedScope {}.into()).unwrap();
+        test.notifier.try_start_notify(id, UtxosChangedScope::new(vec![]).into()).unwrap();
+        assert_eq!(
+            test.notifier.listener_subscriptions(id),
+            vec![summary(EventType::VirtualChainChanged, true, false, 0), summary(EventType::UtxosChanged, false, true, 0)]
+        );
+
+        // Unregistered listeners have no subscriptions
+        test.notifier.unregister_listener(id).unwrap();
+        assert!(test.notifier.listener_subscriptions(id).is_empty());
+
+        test.notification_sender.close();
+        assert!(test.notifier.join().await.is_ok(), "notifier failed to stop");
+    }
+
+    #[tokio::test]
+    async fn test_max_listener_addresses() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        const IDENT: &str = "test";
+        let addresses = get_3_addresses(false);
+        let subscription_context = SubscriptionContext::with_options(None, Some(2));
+        let notifier =
+            TestNotifier::new(IDENT, EVENT_TYPE_ARRAY[..].into(), vec![], vec![], subscription_context, 1, Default::default());
+        let (sender, _receiver) = unbounded();
+        let id = notifier.register_new_listener(TestConnection::new(IDENT, sender, ChannelType::Closable), ListenerLifespan::Dynamic);
+
+        // Registering twice the same address does not count twice
+        notifier.try_start_notify(id, UtxosChangedScope::new(addresses[..2].to_vec()).into()).unwrap();
+        notifier.try_start_notify(id, UtxosChangedScope::new(addresses[1..2].to_vec()).into()).unwrap();
+
+        // Exceeding the maximum is rejected without any change to the address set
+        let result = notifier.try_start_notify(id, UtxosChangedScope::new(addresses[2..].to_vec()).into());
+        assert!(matches!(result, Err(Error::MaxListenerAddressesExceeded(3, 2))), "unexpected result {result:?}");
+        assert_eq!(notifier.listener_subscriptions(id)[0].address_count, 2);
+
+        // Subscribing to all addresses is not limited
+        notifier.try_start_notify(id, UtxosChangedScope::new(vec![]).into()).unwrap();
+        assert!(notifier.listener_subscriptions(id)[0].all_addresses);
+
+        // Static listeners are not limited
+        let (sender, _receiver) = unbounded();
+        let connection = TestConnection::new(IDENT, sender, ChannelType::Closable);
+        let id = notifier.register_new_listener(connection, ListenerLifespan::Static(Default::default()));
+        notifier.try_start_notify(id, UtxosChangedScope::new(addresses).into()).unwrap();
+        assert_eq!(notifier.listener_subscriptions(id)[0].address_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_replace_utxos_changed_addresses() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        let test = Test::new("Replace UtxosChanged addresses", 1, vec![]);
+        test.notifier.clone().start();
+        let id = test.listeners[0];
+        let addresses = get_3_addresses(true);
+        let a = |indexes: &[usize]| indexes.iter().map(|idx| addresses[*idx].clone()).collect::<Vec<_>>();
+        let expect = |command: Command, indexes: &[usize]| SubscriptionMessage {
+            listener_id: SUBSCRIPTION_MANAGER_ID,
+            mutation: Mutation::new(command, UtxosChangedScope::new(a(indexes)).into()),
+        };
+        let replace = |indexes: &[usize]| UtxosChangedScope::new(a(indexes)).with_replace(true).into();
+
+        // Without a selected address set, a replacement is a plain start
+        test.notifier.try_start_notify(id, replace(&[0, 1])).unwrap();
+        assert_eq!(test.subscription_receiver.recv().await.unwrap(), expect(Command::Start, &[0, 1]));
+
+        // Only the added and removed addresses are propagated upwards, without stopping the whole subscription
+        test.notifier.try_start_notify(id, replace(&[1, 2])).unwrap();
+        assert_eq!(test.subscription_receiver.recv().await.unwrap(), expect(Command::Start, &[2]));
+        assert_eq!(test.subscription_receiver.recv().await.unwrap(), expect(Command::Stop, &[0]));
+        assert!(test.subscription_receiver.is_empty());
+        assert_eq!(test.notifier.listener_subscriptions(id)[0].address_count, 2);
+
+        // Re-submitting the same address set yields no mutation at all
+        test.notifier.try_start_notify(id, replace(&[2, 1])).unwrap();
+        assert!(test.subscription_receiver.is_empty());
+
+        // A start command without the replace flag keeps adding to the address set
+        test.notifier.try_start_notify(id, UtxosChangedScope::new(a(&[0])).into()).unwrap();
+        assert_eq!(test.subscription_receiver.recv().await.unwrap(), expect(Command::Start, &[0]));
+        assert_eq!(test.notifier.listener_subscriptions(id)[0].address_count, 3);
+
+        test.notification_sender.close();
+        assert!(test.notifier.join().await.is_ok(), "notifier failed to stop");
+    }
 }
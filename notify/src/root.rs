@@ -1,6 +1,7 @@
 use crate::{
     error::Result,
     events::{EventArray, EventType},
+    firehose::{Firehose, FirehoseOverflow},
     listener::ListenerId,
     notification::Notification,
     notifier::Notify,
@@ -11,7 +12,7 @@ use crate::{
         UtxosChangedMutationPolicy,
     },
 };
-use async_channel::Sender;
+use async_channel::{Receiver, Sender};
 use async_trait::async_trait;
 use kaspa_core::{debug, trace};
 use parking_lot::RwLock;
@@ -24,6 +25,8 @@ use std::sync::Arc;
 ///
 /// It is a [`SubscriptionManager`], so the notification scope can be dynamically configured
 /// according to the needs of the whole notification system.
+///
+/// A [`Firehose`] can additionally be attached, receiving every notification unfiltered.
 #[derive(Clone, Debug)]
 pub struct Root<N>
 where
@@ -52,9 +55,32 @@ where
 
     pub fn close(&self) -> bool {
         debug!("[Notification root] closing");
+        if let Some(firehose) = self.inner.firehose.write().take() {
+            firehose.close();
+        }
         self.inner.sender.close()
     }
 
+    /// Attaches a firehose receiving every notification of the root, regardless of the subscriptions, and returns
+    /// the receiver of its channel. Replaces any previously attached firehose.
+    pub fn attach_firehose(&self, capacity: usize, overflow: FirehoseOverflow) -> Receiver<N> {
+        let (firehose, receiver) = Firehose::new(capacity, overflow);
+        if let Some(previous) = self.inner.firehose.write().replace(Arc::new(firehose)) {
+            previous.close();
+        }
+        receiver
+    }
+
+    /// Tells if a firehose is attached, in which case producers must build every notification in full
+    pub fn has_firehose(&self) -> bool {
+        self.inner.firehose.read().as_ref().is_some_and(|firehose| !firehose.is_closed())
+    }
+
+    /// Returns the number of notifications dropped by the attached firehose on overflow
+    pub fn firehose_dropped(&self) -> u64 {
+        self.inner.firehose.read().as_ref().map_or(0, |firehose| firehose.dropped())
+    }
+
     pub fn is_closed(&self) -> bool {
         self.inner.sender.is_closed()
     }
@@ -62,6 +88,13 @@ where
     pub fn has_subscription(&self, event: EventType) -> bool {
         self.inner.has_subscription(event)
     }
+
+    /// Returns the scope of the subscription to `event` if active.
+    ///
+    /// Lets a notification producer skip computing the parts of a notification no listener asked for.
+    pub fn subscription_scope(&self, event: EventType) -> Option<Scope> {
+        self.inner.subscription_scope(event)
+    }
 }
 
 impl<N> Notify<N> for Root<N>
@@ -100,6 +133,7 @@ where
     subscriptions: RwLock<EventArray<DynSubscription>>,
     subscription_context: SubscriptionContext,
     policies: MutationPolicies,
+    firehose: RwLock<Option<Arc<Firehose<N>>>>,
 }
 
 impl<N> Inner<N>
@@ -111,7 +145,7 @@ where
     fn new(sender: Sender<N>, subscription_context: SubscriptionContext) -> Self {
         let subscriptions = RwLock::new(ArrayBuilder::single(Self::ROOT_LISTENER_ID, None));
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::Wildcard);
-        Self { sender, subscriptions, subscription_context, policies }
+        Self { sender, subscriptions, subscription_context, policies, firehose: Default::default() }
     }
 
     fn send(&self, notification: N) -> Result<()> {
@@ -135,6 +169,7 @@ where
     }
 
     fn notify(&self, notification: N) -> Result<()> {
+        self.feed_firehose(&notification);
         let event = notification.event_type();
         let subscription = &self.subscriptions.read()[event];
         if subscription.active() {
@@ -145,6 +180,19 @@ where
         Ok(())
     }
 
+    fn feed_firehose(&self, notification: &N) {
+        // Cloned out of the lock so a blocking send does not hold it
+        let Some(firehose) = self.firehose.read().clone() else { return };
+        if firehose.send(notification.clone()).is_err() {
+            // The consumer dropped its receiver
+            debug!("[Notification root] detaching the closed firehose");
+            let mut current = self.firehose.write();
+            if current.as_ref().is_some_and(|current| Arc::ptr_eq(current, &firehose)) {
+                *current = None;
+            }
+        }
+    }
+
     fn stop_notify(&self, scope: Scope) -> Result<()> {
         self.execute_subscribe_command(scope, Command::Stop)
     }
@@ -153,6 +201,77 @@ where
         let subscription = &self.subscriptions.read()[event];
         subscription.active()
     }
+
+    fn subscription_scope(&self, event: EventType) -> Option<Scope> {
+        let subscription = &self.subscriptions.read()[event];
+        subscription.active().then(|| subscription.scope(&self.subscription_context))
+    }
 }
 
-// TODO: tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        notification::test_helpers::{BlockAddedNotification, TestNotification},
+        scope::{BlockAddedScope, VirtualChainChangedScope},
+    };
+    use async_channel::unbounded;
+
+    #[tokio::test]
+    async fn test_virtual_chain_changed_subscription_scope() {
+        let (sender, _receiver) = unbounded();
+        let root = Root::<TestNotification>::new(sender);
+        let id = Inner::<TestNotification>::ROOT_LISTENER_ID;
+        let event = EventType::VirtualChainChanged;
+        let scope = |include_accepted_transaction_ids| {
+            Scope::VirtualChainChanged(VirtualChainChangedScope::new(include_accepted_transaction_ids))
+        };
+
+        assert_eq!(root.subscription_scope(event), None);
+
+        // Without accepted transaction ids
+        root.start_notify(id, scope(false)).await.unwrap();
+        assert_eq!(root.subscription_scope(event), Some(scope(false)));
+
+        // Upgraded to accepted transaction ids
+        root.start_notify(id, scope(true)).await.unwrap();
+        assert_eq!(root.subscription_scope(event), Some(scope(true)));
+
+        // Downgraded back
+        root.start_notify(id, scope(false)).await.unwrap();
+        assert_eq!(root.subscription_scope(event), Some(scope(false)));
+
+        root.stop_notify(id, scope(false)).await.unwrap();
+        assert_eq!(root.subscription_scope(event), None);
+    }
+
+    #[tokio::test]
+    async fn test_firehose() {
+        let (sender, receiver) = unbounded();
+        let root = Root::<TestNotification>::new(sender);
+        let id = Inner::<TestNotification>::ROOT_LISTENER_ID;
+        let block_added = |data| TestNotification::BlockAdded(BlockAddedNotification { data });
+
+        // The firehose receives every notification while the channel only gets the subscribed ones
+        let firehose = root.attach_firehose(10, FirehoseOverflow::Block);
+        assert!(root.has_firehose());
+        root.notify(block_added(1)).unwrap();
+        root.start_notify(id, Scope::BlockAdded(BlockAddedScope {})).await.unwrap();
+        root.notify(block_added(2)).unwrap();
+        assert_eq!(firehose.try_recv(), Ok(block_added(1)));
+        assert_eq!(firehose.try_recv(), Ok(block_added(2)));
+        assert_eq!(receiver.try_recv(), Ok(block_added(2)));
+        assert!(receiver.try_recv().is_err());
+
+        // A firehose whose receiver is dropped gets detached
+        drop(firehose);
+        root.notify(block_added(3)).unwrap();
+        assert!(!root.has_firehose());
+        assert_eq!(receiver.try_recv(), Ok(block_added(3)));
+
+        // Closing the root closes the firehose
+        let firehose = root.attach_firehose(10, FirehoseOverflow::Drop);
+        root.close();
+        assert!(firehose.is_closed());
+    }
+}
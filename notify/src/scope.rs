@@ -44,6 +44,10 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    HealthAlert,
+    MempoolTransactionsEvicted,
+    JobCompleted,
+    IndexResyncProgress,
 }
 }
 
@@ -82,6 +86,9 @@ pub struct FinalityConflictResolvedScope {}
 #[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct UtxosChangedScope {
     pub addresses: Vec<Address>,
+    /// Start command only: the addresses replace the current address set of the listener instead of being added to it
+    #[serde(default)]
+    pub replace: bool,
 }
 
 impl std::fmt::Display for UtxosChangedScope {
@@ -91,7 +98,7 @@ impl std::fmt::Display for UtxosChangedScope {
             1 => format!("{}", self.addresses[0]),
             n => format!("{} addresses", n),
         };
-        write!(f, "UtxosChangedScope ({})", addresses)
+        write!(f, "UtxosChangedScope ({}){}", addresses, if self.replace { " replacing the address set" } else { "" })
     }
 }
 
@@ -105,7 +112,12 @@ impl Eq for UtxosChangedScope {}
 
 impl UtxosChangedScope {
     pub fn new(addresses: Vec<Address>) -> Self {
-        Self { addresses }
+        Self { addresses, replace: false }
+    }
+
+    pub fn with_replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
     }
 }
 
@@ -120,3 +132,15 @@ pub struct PruningPointUtxoSetOverrideScope {}
 
 #[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct NewBlockTemplateScope {}
+
+#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct HealthAlertScope {}
+
+#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct MempoolTransactionsEvictedScope {}
+
+#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct JobCompletedScope {}
+
+#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct IndexResyncProgressScope {}
@@ -0,0 +1,87 @@
+use crate::events::{EventArray, EventType, EVENT_TYPE_ARRAY};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+struct EventCounters {
+    emitted: AtomicU64,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Delivery statistics of the notifications of an event type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventStats {
+    pub event_type: EventType,
+    /// Notifications accepted by the notifier for broadcasting
+    pub emitted: u64,
+    /// Notifications sent to a listener connection
+    pub delivered: u64,
+    /// Notifications which could not be sent to a listener connection, either full or closed
+    pub dropped: u64,
+}
+
+impl EventStats {
+    /// Average number of listeners a notification was broadcast to
+    pub fn average_fan_out(&self) -> f64 {
+        match self.emitted {
+            0 => 0.0,
+            emitted => (self.delivered + self.dropped) as f64 / emitted as f64,
+        }
+    }
+}
+
+/// Notification delivery counters of a notifier, by event type
+#[derive(Debug, Default)]
+pub struct NotificationStats {
+    counters: EventArray<EventCounters>,
+}
+
+impl NotificationStats {
+    pub(crate) fn emitted(&self, event_type: EventType) {
+        self.counters[event_type].emitted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn delivered(&self, event_type: EventType) {
+        self.counters[event_type].delivered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dropped(&self, event_type: EventType) {
+        self.counters[event_type].dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the statistics of the event types having emitted notifications
+    pub fn snapshot(&self) -> Vec<EventStats> {
+        EVENT_TYPE_ARRAY
+            .iter()
+            .map(|&event_type| {
+                let counters = &self.counters[event_type];
+                EventStats {
+                    event_type,
+                    emitted: counters.emitted.load(Ordering::Relaxed),
+                    delivered: counters.delivered.load(Ordering::Relaxed),
+                    dropped: counters.dropped.load(Ordering::Relaxed),
+                }
+            })
+            .filter(|stats| stats.emitted > 0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_stats() {
+        let stats = NotificationStats::default();
+        (0..4).for_each(|_| stats.emitted(EventType::BlockAdded));
+        (0..6).for_each(|_| stats.delivered(EventType::BlockAdded));
+        (0..2).for_each(|_| stats.dropped(EventType::BlockAdded));
+        stats.dropped(EventType::UtxosChanged);
+
+        // Event types without emitted notifications are not reported
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot, vec![EventStats { event_type: EventType::BlockAdded, emitted: 4, delivered: 6, dropped: 2 }]);
+        assert_eq!(snapshot[0].average_fan_out(), 2.0);
+    }
+}
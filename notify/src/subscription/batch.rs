@@ -0,0 +1,92 @@
+//! Evaluation of a UtxosChanged notification against many subscriptions at once.
+//!
+//! Filtering a notification for every address-set subscription separately resolves the script public key of each
+//! of its entries once per subscription. A [`UtxosChangedBatch`] instead resolves them once into an inverted index
+//! of address tracker indexes to entry positions, against which every subscription is then matched by index only.
+
+use crate::{
+    address::tracker::Index,
+    subscription::{context::SubscriptionContext, single::UtxosChangedSubscriptionData},
+};
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use std::collections::HashMap;
+
+/// Positions of the entries of a UtxosChanged notification indexed by the address tracker index of their script public key.
+///
+/// Entries whose address is tracked by no subscription at all are left out.
+#[derive(Debug, Default)]
+pub struct UtxosChangedBatch {
+    positions: HashMap<Index, Vec<usize>>,
+}
+
+impl UtxosChangedBatch {
+    pub fn new<'a>(script_public_keys: impl Iterator<Item = &'a ScriptPublicKey>, context: &SubscriptionContext) -> Self {
+        let tracker_data = context.address_tracker.data();
+        let mut positions: HashMap<Index, Vec<usize>> = HashMap::new();
+        script_public_keys.enumerate().for_each(|(position, script_public_key)| {
+            if let Some((index, _)) = tracker_data.get(script_public_key) {
+                positions.entry(index).or_default().push(position);
+            }
+        });
+        Self { positions }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the positions, in ascending order, of the entries matching the addresses of `subscription`
+    pub fn select(&self, subscription: &UtxosChangedSubscriptionData) -> Vec<usize> {
+        // As an optimization, we iterate over the smaller set among the two below and check existence over the larger set
+        let mut selected = if self.positions.len() <= subscription.len() {
+            self.positions
+                .iter()
+                .filter(|(index, _)| subscription.contains_index(**index))
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect::<Vec<_>>()
+        } else {
+            subscription.iter().filter_map(|index| self.positions.get(index)).flatten().copied().collect::<Vec<_>>()
+        };
+        selected.sort_unstable();
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscription::{single::UtxosChangedSubscription, Subscription};
+    use kaspa_addresses::{Address, Prefix, Version};
+    use kaspa_math::Uint256;
+    use kaspa_txscript::pay_to_address_script;
+
+    #[test]
+    fn test_utxos_changed_batch() {
+        let addresses =
+            (0..6u64).map(|i| Address::new(Prefix::Mainnet, Version::PubKey, &Uint256::from_u64(i).to_le_bytes())).collect::<Vec<_>>();
+        let context = SubscriptionContext::new();
+        let subscriptions = [
+            UtxosChangedSubscription::with_addresses(true, addresses[0..2].to_vec(), 0, &context),
+            UtxosChangedSubscription::with_addresses(true, addresses[1..5].to_vec(), 1, &context),
+            UtxosChangedSubscription::with_addresses(true, addresses[4..5].to_vec(), 2, &context),
+        ];
+        assert!(subscriptions.iter().all(|subscription| subscription.active()));
+
+        // Entries of addresses 0, 1, 0, 3, 5 where address 5 is not tracked
+        let entries = [0, 1, 0, 3, 5].map(|i| pay_to_address_script(&addresses[i]));
+        let batch = UtxosChangedBatch::new(entries.iter(), &context);
+        assert!(!batch.is_empty());
+
+        let selected = subscriptions.iter().map(|subscription| batch.select(&subscription.data())).collect::<Vec<_>>();
+        assert_eq!(selected, vec![vec![0, 1, 2], vec![1, 3], vec![]]);
+
+        // The batch selects the same entries as matching every subscription separately
+        subscriptions.iter().zip(selected).for_each(|(subscription, selected)| {
+            let expected = (0..entries.len()).filter(|&i| subscription.data().contains(&entries[i], &context)).collect::<Vec<_>>();
+            assert_eq!(selected, expected);
+        });
+
+        let batch = UtxosChangedBatch::new([pay_to_address_script(&addresses[5])].iter(), &context);
+        assert!(batch.is_empty());
+    }
+}
@@ -15,20 +15,23 @@ use kaspa_addresses::Address;
 pub struct SubscriptionContextInner {
     pub address_tracker: Tracker,
     pub utxos_changed_subscription_to_all: DynSubscription,
+
+    /// Maximum number of addresses a dynamic listener can subscribe to UtxosChanged notifications for, if any
+    pub max_listener_addresses: Option<usize>,
 }
 
 impl SubscriptionContextInner {
     const CONTEXT_LISTENER_ID: ListenerId = ListenerId::MAX;
 
     pub fn new() -> Self {
-        Self::with_options(None)
+        Self::with_options(None, None)
     }
 
-    pub fn with_options(max_addresses: Option<usize>) -> Self {
+    pub fn with_options(max_addresses: Option<usize>, max_listener_addresses: Option<usize>) -> Self {
         let address_tracker = Tracker::new(max_addresses);
         let utxos_changed_subscription_all =
             Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all, max_listener_addresses }
     }
 
     #[cfg(test)]
@@ -36,7 +39,7 @@ impl SubscriptionContextInner {
         let address_tracker = Tracker::with_addresses(addresses);
         let utxos_changed_subscription_all =
             Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all, max_listener_addresses: None }
     }
 }
 
@@ -53,11 +56,13 @@ pub struct SubscriptionContext {
 
 impl SubscriptionContext {
     pub fn new() -> Self {
-        Self::with_options(None)
+        Self::with_options(None, None)
     }
 
-    pub fn with_options(max_addresses: Option<usize>) -> Self {
-        let inner = Arc::new(SubscriptionContextInner::with_options(max_addresses));
+    /// Creates a context tracking at most `max_addresses` addresses overall, each dynamic listener
+    /// subscribing to at most `max_listener_addresses` of them
+    pub fn with_options(max_addresses: Option<usize>, max_listener_addresses: Option<usize>) -> Self {
+        let inner = Arc::new(SubscriptionContextInner::with_options(max_addresses, max_listener_addresses));
         Self { inner }
     }
 
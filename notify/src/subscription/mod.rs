@@ -11,6 +11,7 @@ use std::{
 };
 
 pub mod array;
+pub mod batch;
 pub mod compounded;
 pub mod context;
 pub mod single;
@@ -1,5 +1,5 @@
 use crate::{
-    address::tracker::{Index, Indexes},
+    address::tracker::{Index, Indexer, Indexes},
     error::Result,
     events::EventType,
     listener::ListenerId,
@@ -249,10 +249,19 @@ impl UtxosChangedSubscriptionData {
         self.state = new_state;
     }
 
+    #[inline(always)]
+    pub fn state(&self) -> UtxosChangedState {
+        self.state
+    }
+
     pub fn contains(&self, spk: &ScriptPublicKey, context: &SubscriptionContext) -> bool {
         context.address_tracker.contains(&self.indexes, spk)
     }
 
+    pub fn contains_index(&self, index: Index) -> bool {
+        self.indexes.contains(index)
+    }
+
     pub fn len(&self) -> usize {
         self.indexes.len()
     }
@@ -582,6 +591,43 @@ impl BroadcastingSingle for DynSubscription {
     }
 }
 
+/// Description of an active single subscription
+///
+/// Unlike [`Subscription::scope`], building a summary does not require resolving the addresses of a UtxosChanged subscription.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubscriptionSummary {
+    pub event_type: EventType,
+
+    /// VirtualChainChanged only: notifications include the accepted transaction ids
+    pub include_accepted_transaction_ids: bool,
+
+    /// UtxosChanged only: notifications cover all addresses
+    pub all_addresses: bool,
+
+    /// UtxosChanged only: number of addresses in the server-side address set
+    pub address_count: usize,
+}
+
+impl From<&DynSubscription> for SubscriptionSummary {
+    fn from(subscription: &DynSubscription) -> Self {
+        let event_type = subscription.event_type();
+        let mut summary = Self { event_type, include_accepted_transaction_ids: false, all_addresses: false, address_count: 0 };
+        match event_type {
+            EventType::VirtualChainChanged => {
+                let subscription = subscription.as_any().downcast_ref::<VirtualChainChangedSubscription>().unwrap();
+                summary.include_accepted_transaction_ids = subscription.include_accepted_transaction_ids();
+            }
+            EventType::UtxosChanged => {
+                let data = subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap().data();
+                summary.all_addresses = data.to_all();
+                summary.address_count = data.len();
+            }
+            _ => {}
+        }
+        summary
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::*;
@@ -1,6 +1,7 @@
 use crate::flowcontext::{
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
+    sync_status::{IbdPhaseProgress, SyncStatusTracker},
     transactions::TransactionsSpread,
 };
 use crate::{v5, v6};
@@ -12,6 +13,7 @@ use kaspa_consensus_core::api::{BlockValidationFuture, BlockValidationFutures};
 use kaspa_consensus_core::block::Block;
 use kaspa_consensus_core::config::Config;
 use kaspa_consensus_core::errors::block::RuleError;
+use kaspa_consensus_core::network::NetworkId;
 use kaspa_consensus_core::tx::{Transaction, TransactionId};
 use kaspa_consensus_notify::{
     notification::{Notification, PruningPointUtxoSetOverrideNotification},
@@ -26,7 +28,8 @@ use kaspa_core::{
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::manager::MiningManagerProxy;
-use kaspa_mining::mempool::tx::{Orphan, Priority};
+use kaspa_mining::mempool::tx::{Orphan, Priority, RbfPolicy};
+use kaspa_mining::model::evicted_txs::ReevaluationTrigger;
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
@@ -60,6 +63,9 @@ use uuid::Uuid;
 /// The P2P protocol version. Currently the only one supported.
 const PROTOCOL_VERSION: u32 = 6;
 
+/// The P2P protocol versions a peer may be served with, see `initialize_peer`
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[u32] = &[5, PROTOCOL_VERSION];
+
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
 
@@ -219,6 +225,7 @@ pub struct FlowContextInner {
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
+    pub(crate) sync_status: Arc<SyncStatusTracker>,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
     mining_manager: MiningManagerProxy,
@@ -321,6 +328,7 @@ impl FlowContext {
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
+                sync_status: Default::default(),
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
@@ -380,6 +388,7 @@ impl FlowContext {
     pub fn try_set_ibd_running(&self, peer: PeerKey, relay_daa_score: u64) -> Option<IbdRunningGuard> {
         if self.is_ibd_running.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
             self.ibd_metadata.write().replace(IbdMetadata { peer, daa_score: relay_daa_score });
+            self.sync_status.clear();
             Some(IbdRunningGuard { indicator: self.is_ibd_running.clone() })
         } else {
             None
@@ -408,6 +417,15 @@ impl FlowContext {
         }
     }
 
+    /// If IBD is running, returns the progress of its current phase
+    pub fn ibd_phase_progress(&self) -> Option<IbdPhaseProgress> {
+        if self.is_ibd_running() {
+            self.sync_status.current()
+        } else {
+            None
+        }
+    }
+
     fn try_adding_request_impl(req: Hash, map: &Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>) -> Option<RequestScope<Hash>> {
         match map.lock().entry(req) {
             Entry::Occupied(mut e) => {
@@ -563,6 +581,16 @@ impl FlowContext {
         // TODO: Throttle these transactions as well if needed
         self.broadcast_transactions(transactions_to_broadcast, false).await;
 
+        // A pruning point move or a rule activation may invalidate mempool transactions without any block spending
+        // their inputs, so the whole mempool gets re-evaluated in such cases.
+        if let Some(trigger) = self.should_reevaluate_mempool(consensus).await {
+            let mining_manager = self.mining_manager().clone();
+            let consensus_clone = consensus.clone();
+            tokio::spawn(async move {
+                mining_manager.reevaluate_transactions(&consensus_clone, trigger).await;
+            });
+        }
+
         if self.should_run_mempool_scanning_task().await {
             // Spawn a task executing the removal of expired low priority transactions and, if time has come too,
             // the revalidation of high priority transactions.
@@ -611,15 +639,27 @@ impl FlowContext {
     ///
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
-    /// peers
+    /// peers.
+    ///
+    /// If provided, `submitter` identifies the rpc connection the transaction is attributed to, and the connection quota of
+    /// high-priority transactions is enforced.
     pub async fn submit_rpc_transaction(
         &self,
         consensus: &ConsensusProxy,
         transaction: Transaction,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
+        submitter: Option<String>,
     ) -> Result<(), ProtocolError> {
-        let accepted_transactions =
-            self.mining_manager().clone().validate_and_insert_transaction(consensus, transaction, Priority::High, orphan).await?;
+        let transaction_id = transaction.id();
+        let accepted_transactions = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_transaction(consensus, transaction, Priority::High, orphan, rbf_policy)
+            .await?;
+        if let Some(submitter) = submitter {
+            self.mining_manager().clone().track_high_priority_submission(submitter, vec![transaction_id]).await;
+        }
         self.broadcast_transactions(
             accepted_transactions.iter().map(|x| x.id()),
             false, // RPC transactions are considered high priority, so we don't want to throttle them
@@ -628,11 +668,49 @@ impl FlowContext {
         Ok(())
     }
 
+    /// Adds a list of possibly chained rpc-submitted transactions to the mempool, in topological order, and propagates
+    /// the accepted ones to peers.
+    ///
+    /// Returns a result per transaction, in submission order. See [`FlowContext::submit_rpc_transaction`] about `submitter`.
+    pub async fn submit_rpc_transactions(
+        &self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        orphan: Orphan,
+        submitter: Option<String>,
+    ) -> Vec<Result<(), ProtocolError>> {
+        let transaction_ids = transactions.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let insert_results = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_chained_transactions(consensus, transactions, Priority::High, orphan)
+            .await;
+        if let Some(submitter) = submitter {
+            let transaction_ids = transaction_ids.into_iter().zip(insert_results.iter()).filter(|(_, x)| x.is_ok()).map(|(id, _)| id);
+            self.mining_manager().clone().track_high_priority_submission(submitter, transaction_ids.collect()).await;
+        }
+        let accepted_transaction_ids =
+            insert_results.iter().filter_map(|x| x.as_ref().ok()).flatten().map(|x| x.id()).collect::<Vec<_>>();
+        self.broadcast_transactions(
+            accepted_transaction_ids,
+            false, // RPC transactions are considered high priority, so we don't want to throttle them
+        )
+        .await;
+        insert_results.into_iter().map(|x| x.map(|_| ()).map_err(ProtocolError::from)).collect()
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread.write().await.should_run_mempool_scanning_task()
     }
 
+    async fn should_reevaluate_mempool(&self, consensus: &ConsensusProxy) -> Option<ReevaluationTrigger> {
+        let pruning_point = consensus.async_pruning_point().await;
+        let virtual_daa_score = consensus.get_virtual_daa_score();
+        let activation_daa_scores = [self.config.sampling_activation_daa_score, self.config.storage_mass_activation_daa_score];
+        self.transactions_spread.write().await.should_reevaluate_mempool(pruning_point, virtual_daa_score, &activation_daa_scores)
+    }
+
     /// Returns true if the time has come for a rebroadcast of the mempool high priority transactions.
     async fn should_rebroadcast(&self) -> bool {
         self.transactions_spread.read().await.should_rebroadcast()
@@ -692,8 +770,11 @@ impl ConnectionInitializer for FlowContext {
             return Err(ProtocolError::LoopbackConnection(router.key()));
         }
 
-        if peer_version.network != network_name {
-            return Err(ProtocolError::WrongNetwork(network_name, peer_version.network));
+        // Compare the typed network ids so peers of another testnet suffix get a clear rejection
+        let peer_network = NetworkId::from_prefixed(&peer_version.network)
+            .map_err(|_| ProtocolError::UnknownNetwork(peer_version.network.clone()))?;
+        if peer_network != self.config.net {
+            return Err(ProtocolError::WrongNetwork(self.config.net, peer_network));
         }
 
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
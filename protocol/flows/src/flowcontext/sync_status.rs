@@ -0,0 +1,112 @@
+use parking_lot::RwLock;
+use std::time::{Duration, Instant};
+
+/// The phases of an IBD, in their order of execution. The proof download and the UTXO set phases only occur during
+/// an IBD with headers proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbdPhase {
+    /// Downloading and applying the pruning point proof and the trusted blocks
+    ProofDownload,
+    /// Downloading and validating the block headers
+    Headers,
+    /// Downloading the UTXO set of the pruning point
+    UtxoSet,
+    /// Downloading and validating the block bodies
+    BlockBodies,
+}
+
+/// A snapshot of the progress of the current IBD phase
+#[derive(Debug, Clone)]
+pub struct IbdPhaseProgress {
+    pub phase: IbdPhase,
+    /// Number of items (trusted blocks, headers, UTXOs or blocks) processed so far in the phase
+    pub processed: u64,
+    /// Progress of the phase in the `[0, 1]` range, if known
+    pub progress: Option<f64>,
+    /// Estimated remaining time of the phase, extrapolated from the progress rate since the phase started
+    pub estimated_remaining: Option<Duration>,
+}
+
+struct PhaseState {
+    phase: IbdPhase,
+    started: Instant,
+    processed: u64,
+    progress: Option<f64>,
+}
+
+/// Tracks the phase and progress of the IBD being run, if any
+#[derive(Default)]
+pub struct SyncStatusTracker {
+    state: RwLock<Option<PhaseState>>,
+}
+
+impl SyncStatusTracker {
+    pub fn start_phase(&self, phase: IbdPhase) {
+        self.state.write().replace(PhaseState { phase, started: Instant::now(), processed: 0, progress: None });
+    }
+
+    /// Reports `processed_delta` additional processed items and, if known, the current progress of the phase in `[0, 1]`
+    pub fn report(&self, processed_delta: u64, progress: Option<f64>) {
+        if let Some(state) = self.state.write().as_mut() {
+            state.processed += processed_delta;
+            state.progress = progress.map(|p| p.clamp(0.0, 1.0)).or(state.progress);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.state.write().take();
+    }
+
+    pub fn current(&self) -> Option<IbdPhaseProgress> {
+        self.state.read().as_ref().map(|state| IbdPhaseProgress {
+            phase: state.phase,
+            processed: state.processed,
+            progress: state.progress,
+            estimated_remaining: state
+                .progress
+                .filter(|&p| p > 0.0)
+                .map(|p| Duration::from_secs_f64(state.started.elapsed().as_secs_f64() * (1.0 - p) / p)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_status_tracker() {
+        let tracker = SyncStatusTracker::default();
+        assert!(tracker.current().is_none());
+        // Reports outside of a phase are ignored
+        tracker.report(10, Some(0.5));
+        assert!(tracker.current().is_none());
+
+        tracker.start_phase(IbdPhase::Headers);
+        let current = tracker.current().unwrap();
+        assert_eq!((current.phase, current.processed, current.progress), (IbdPhase::Headers, 0, None));
+        assert!(current.estimated_remaining.is_none());
+
+        tracker.report(100, None);
+        tracker.report(50, Some(0.25));
+        // An unknown progress keeps the last known one
+        tracker.report(50, None);
+        let current = tracker.current().unwrap();
+        assert_eq!((current.processed, current.progress), (200, Some(0.25)));
+        assert!(current.estimated_remaining.is_some());
+
+        // The progress is clamped and a completed phase has no remaining time
+        tracker.report(0, Some(1.5));
+        let current = tracker.current().unwrap();
+        assert_eq!(current.progress, Some(1.0));
+        assert_eq!(current.estimated_remaining, Some(Duration::ZERO));
+
+        // Starting the next phase resets the counters
+        tracker.start_phase(IbdPhase::BlockBodies);
+        let current = tracker.current().unwrap();
+        assert_eq!((current.phase, current.processed, current.progress), (IbdPhase::BlockBodies, 0, None));
+
+        tracker.clear();
+        assert!(tracker.current().is_none());
+    }
+}
@@ -2,6 +2,8 @@ use super::process_queue::ProcessQueue;
 use itertools::Itertools;
 use kaspa_consensus_core::tx::TransactionId;
 use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_mining::model::evicted_txs::ReevaluationTrigger;
 use kaspa_p2p_lib::{
     make_message,
     pb::{kaspad_message::Payload, InvTransactionsMessage, KaspadMessage},
@@ -22,6 +24,8 @@ pub struct TransactionsSpread {
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
+    /// Pruning point and virtual DAA score observed at the previous mempool re-evaluation check
+    reevaluation_state: Option<(Hash, u64)>,
 }
 
 impl TransactionsSpread {
@@ -33,6 +37,7 @@ impl TransactionsSpread {
             scanning_job_count: 0,
             transaction_ids: ProcessQueue::new(),
             last_broadcast_time: Instant::now(),
+            reevaluation_state: None,
         }
     }
 
@@ -69,6 +74,28 @@ impl TransactionsSpread {
         self.scanning_task_running = false;
     }
 
+    /// Records the current pruning point and virtual DAA score and returns the reason for re-evaluating the
+    /// mempool transactions, if the pruning point moved or any of `activation_daa_scores` was crossed since
+    /// the previous call.
+    ///
+    /// The first call only records the state.
+    pub fn should_reevaluate_mempool(
+        &mut self,
+        pruning_point: Hash,
+        virtual_daa_score: u64,
+        activation_daa_scores: &[u64],
+    ) -> Option<ReevaluationTrigger> {
+        let previous = self.reevaluation_state.replace((pruning_point, virtual_daa_score));
+        let (previous_pruning_point, previous_daa_score) = previous?;
+        if activation_daa_scores.iter().any(|&score| previous_daa_score < score && score <= virtual_daa_score) {
+            Some(ReevaluationTrigger::ParameterActivation)
+        } else if previous_pruning_point != pruning_point {
+            Some(ReevaluationTrigger::PruningPointMove)
+        } else {
+            None
+        }
+    }
+
     /// Add the given transactions IDs to a set of IDs to broadcast. The IDs will be broadcasted to all peers
     /// within transaction Inv messages.
     ///
@@ -8,7 +8,7 @@ use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
-    common::ProtocolError,
+    common::{ProtocolError, TraceId},
     dequeue, dequeue_with_timeout, make_message, make_request,
     pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
     IncomingRoute, Router, SharedIncomingRoute,
@@ -228,17 +228,24 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
-        self.router
-            .enqueue(make_request!(
-                Payload::RequestRelayBlocks,
-                RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] },
-                request_id
-            ))
-            .await?;
-        let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
-        let block: Block = msg.try_into()?;
+        let trace_id = TraceId::generate();
+        debug!("Requesting relay block {} from peer {} [trace {}]", requested_hash, self.router, trace_id);
+        let block: Block = async {
+            self.router
+                .enqueue(make_request!(
+                    Payload::RequestRelayBlocks,
+                    RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] },
+                    request_id
+                ))
+                .await?;
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
+            Result::<_, ProtocolError>::Ok(msg.try_into()?)
+        }
+        .await
+        .map_err(|err| err.with_trace(trace_id))?;
         if block.hash() != requested_hash {
-            Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
+            Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash()))
+                .with_trace(trace_id))
         } else {
             Ok(Some((block, request_scope)))
         }
@@ -332,15 +339,21 @@ impl HandleRelayInvsFlow {
         hash: Hash,
         request_id: u32,
     ) -> Result<bool, ProtocolError> {
-        self.router
-            .enqueue(make_request!(
-                Payload::RequestBlockLocator,
-                RequestBlockLocatorMessage { high_hash: Some(hash.into()), limit: self.ctx.orphan_resolution_range() },
-                request_id
-            ))
-            .await?;
-        let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockLocator)?;
-        let locator_hashes: Vec<Hash> = msg.try_into()?;
+        let trace_id = TraceId::generate();
+        debug!("Requesting the block locator of orphan {} from peer {} [trace {}]", hash, self.router, trace_id);
+        let locator_hashes: Vec<Hash> = async {
+            self.router
+                .enqueue(make_request!(
+                    Payload::RequestBlockLocator,
+                    RequestBlockLocatorMessage { high_hash: Some(hash.into()), limit: self.ctx.orphan_resolution_range() },
+                    request_id
+                ))
+                .await?;
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockLocator)?;
+            Result::<_, ProtocolError>::Ok(msg.try_into()?)
+        }
+        .await
+        .map_err(|err| err.with_trace(trace_id))?;
         // Locator hashes are sent from later to earlier, so it makes sense to query consensus in reverse. Technically
         // with current syncer-side implementations (in both go-kaspa and this codebase) we could query only the last one,
         // but we prefer not relying on such details for correctness
@@ -1,5 +1,6 @@
 use crate::{
     flow_context::FlowContext,
+    flowcontext::sync_status::IbdPhase,
     v5::{
         ibd::{HeadersChunkStream, TrustedEntryStream},
         Flow,
@@ -14,11 +15,11 @@ use kaspa_consensus_core::{
     BlockHashSet,
 };
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy, StagingConsensus};
-use kaspa_core::{debug, info, time::unix_now, warn};
+use kaspa_core::{debug, info, warn};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_p2p_lib::{
-    common::ProtocolError,
+    common::{ProtocolError, TraceId},
     convert::model::trusted::TrustedDataPackage,
     dequeue_with_timeout, make_message,
     pb::{
@@ -34,7 +35,7 @@ use std::{
 };
 use tokio::time::sleep;
 
-use super::{progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, IBD_BATCH_SIZE};
+use super::{progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, UtxosetChunkVerifier, IBD_BATCH_SIZE};
 
 /// Flow for managing IBD - Initial Block Download
 pub struct IbdFlow {
@@ -42,6 +43,9 @@ pub struct IbdFlow {
     pub(super) router: Arc<Router>,
     pub(super) incoming_route: IncomingRoute,
 
+    /// Trace id of the current IBD session, attached to its request logs and to its error
+    pub(super) trace_id: TraceId,
+
     // Receives relay blocks from relay flow which are out of orphan resolution range and hence trigger IBD
     relay_receiver: JobReceiver<Block>,
 }
@@ -72,16 +76,18 @@ struct QueueChunkOutput {
 
 impl IbdFlow {
     pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, relay_receiver: JobReceiver<Block>) -> Self {
-        Self { ctx, router, incoming_route, relay_receiver }
+        Self { ctx, router, incoming_route, trace_id: TraceId::generate(), relay_receiver }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         while let Ok(relay_block) = self.relay_receiver.recv().await {
             if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key(), relay_block.header.daa_score) {
-                info!("IBD started with peer {}", self.router);
+                self.trace_id = TraceId::generate();
+                info!("IBD started with peer {} [trace {}]", self.router, self.trace_id);
 
-                match self.ibd(relay_block).await {
-                    Ok(_) => info!("IBD with peer {} completed successfully", self.router),
+                let trace_id = self.trace_id;
+                match self.ibd(relay_block).await.map_err(|err| err.with_trace(trace_id)) {
+                    Ok(_) => info!("IBD with peer {} completed successfully [trace {}]", self.router, self.trace_id),
                     Err(e) => {
                         info!("IBD with peer {} completed with error: {}", self.router, e);
                         return Err(e);
@@ -127,7 +133,7 @@ impl IbdFlow {
                         session = self.ctx.consensus().session().await;
                     }
                     Err(e) => {
-                        info!("IBD with headers proof from {} was unsuccessful ({})", self.router, e);
+                        info!("IBD with headers proof from {} was unsuccessful ({}) [trace {}]", self.router, e, self.trace_id);
                         staging.cancel();
                         return Err(e);
                     }
@@ -189,10 +195,10 @@ impl IbdFlow {
         if relay_header.blue_score >= hst_header.blue_score + self.ctx.config.pruning_depth
             && relay_header.blue_work > hst_header.blue_work
         {
-            if unix_now() > consensus.async_creation_timestamp().await + self.ctx.config.finality_duration() {
+            if self.ctx.config.clock.unix_now() > consensus.async_creation_timestamp().await + self.ctx.config.finality_duration() {
                 let fp = consensus.async_finality_point().await;
                 let fp_ts = consensus.async_get_header(fp).await?.timestamp;
-                if unix_now() < fp_ts + self.ctx.config.finality_duration() * 3 / 2 {
+                if self.ctx.config.clock.unix_now() < fp_ts + self.ctx.config.finality_duration() * 3 / 2 {
                     // We reject the headers proof if the node has a relatively up-to-date finality point and current
                     // consensus has matured for long enough (and not recently synced). This is mostly a spam-protector
                     // since subsequent checks identify these violations as well
@@ -227,6 +233,8 @@ impl IbdFlow {
     }
 
     async fn sync_and_validate_pruning_proof(&mut self, staging: &ConsensusProxy) -> Result<Hash, ProtocolError> {
+        self.ctx.sync_status.start_phase(IbdPhase::ProofDownload);
+        debug!("IBD [trace {}]: requesting the pruning point proof from peer {}", self.trace_id, self.router);
         self.router.enqueue(make_message!(Payload::RequestPruningPointProof, RequestPruningPointProofMessage {})).await?;
 
         // Pruning proof generation and communication might take several minutes, so we allow a long 10 minute timeout
@@ -252,6 +260,7 @@ impl IbdFlow {
 
         drop(consensus);
 
+        debug!("IBD [trace {}]: requesting the pruning point and its anticone from peer {}", self.trace_id, self.router);
         self.router
             .enqueue(make_message!(Payload::RequestPruningPointAndItsAnticone, RequestPruningPointAndItsAnticoneMessage {}))
             .await?;
@@ -340,6 +349,7 @@ impl IbdFlow {
         // TODO: add logs to staging commit process
 
         info!("Starting to process {} trusted blocks", trusted_set.len());
+        let trusted_count = trusted_set.len();
         let mut last_time = Instant::now();
         let mut last_index: usize = 0;
         for (i, tb) in trusted_set.into_iter().enumerate() {
@@ -352,6 +362,7 @@ impl IbdFlow {
             }
             // TODO: queue and join in batches
             staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+            self.ctx.sync_status.report(1, Some((i + 1) as f64 / trusted_count as f64));
         }
         info!("Done processing trusted blocks");
         Ok(proof_pruning_point)
@@ -365,8 +376,18 @@ impl IbdFlow {
         relay_block: &Block,
     ) -> Result<(), ProtocolError> {
         let highest_shared_header_score = consensus.async_get_header(highest_known_syncer_chain_hash).await?.daa_score;
-        let mut progress_reporter = ProgressReporter::new(highest_shared_header_score, relay_block.header.daa_score, "block headers");
-
+        let mut progress_reporter = ProgressReporter::new(
+            highest_shared_header_score,
+            relay_block.header.daa_score,
+            "block headers",
+            self.ctx.sync_status.clone(),
+            IbdPhase::Headers,
+        );
+
+        debug!(
+            "IBD [trace {}]: requesting headers from {} to {} from peer {}",
+            self.trace_id, highest_known_syncer_chain_hash, syncer_virtual_selected_parent, self.router
+        );
         self.router
             .enqueue(make_message!(
                 Payload::RequestHeaders,
@@ -430,6 +451,10 @@ impl IbdFlow {
         // Send a special header request for the sink antipast. This is expected to
         // be a relatively small set since virtual and relay blocks should be close topologically.
         // See server-side handling of `RequestAnticone` for further details.
+        debug!(
+            "IBD [trace {}]: requesting the antipast of {} from peer {}",
+            self.trace_id, syncer_virtual_selected_parent, self.router
+        );
         self.router
             .enqueue(make_message!(
                 Payload::RequestAntipast,
@@ -478,15 +503,23 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
     }
 
     async fn sync_pruning_point_utxoset(&mut self, consensus: &ConsensusProxy, pruning_point: Hash) -> Result<(), ProtocolError> {
+        // The size of the UTXO set is unknown until fully received, so only the count of received UTXOs is reported
+        self.ctx.sync_status.start_phase(IbdPhase::UtxoSet);
+        debug!("IBD [trace {}]: requesting the UTXO set of pruning point {} from peer {}", self.trace_id, pruning_point, self.router);
         self.router
             .enqueue(make_message!(
                 Payload::RequestPruningPointUtxoSet,
                 RequestPruningPointUtxoSetMessage { pruning_point_hash: Some(pruning_point.into()) }
             ))
             .await?;
+        let pruning_point_daa_score = consensus.async_get_header(pruning_point).await?.daa_score;
+        let mut verifier = UtxosetChunkVerifier::new(pruning_point_daa_score);
         let mut chunk_stream = PruningPointUtxosetChunkStream::new(&self.router, &mut self.incoming_route);
         let mut multiset = MuHash::new();
         while let Some(chunk) = chunk_stream.next().await? {
+            // Abort on the first invalid chunk rather than only when the final UTXO commitment mismatches
+            verifier.verify(&chunk)?;
+            self.ctx.sync_status.report(chunk.len() as u64, None);
             multiset = consensus
                 .clone()
                 .spawn_blocking(move |c| {
@@ -524,7 +557,13 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
 
         let low_header = consensus.async_get_header(*hashes.first().expect("hashes was non empty")).await?;
         let high_header = consensus.async_get_header(*hashes.last().expect("hashes was non empty")).await?;
-        let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks");
+        let mut progress_reporter = ProgressReporter::new(
+            low_header.daa_score,
+            high_header.daa_score,
+            "blocks",
+            self.ctx.sync_status.clone(),
+            IbdPhase::BlockBodies,
+        );
 
         let mut iter = hashes.chunks(IBD_BATCH_SIZE);
         let QueueChunkOutput { jobs: mut prev_jobs, daa_score: mut prev_daa_score, timestamp: mut prev_timestamp } =
@@ -558,6 +597,14 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         let mut jobs = Vec::with_capacity(chunk.len());
         let mut current_daa_score = 0;
         let mut current_timestamp = 0;
+        debug!(
+            "IBD [trace {}]: requesting a chunk of {} blocks ({}, {}) from peer {}",
+            self.trace_id,
+            chunk.len(),
+            chunk[0],
+            chunk[chunk.len() - 1],
+            self.router
+        );
         self.router
             .enqueue(make_message!(
                 Payload::RequestIbdBlocks,
@@ -2,6 +2,8 @@ mod flow;
 mod negotiate;
 mod progress;
 mod streams;
+mod utxoset;
 
 pub use flow::*;
 pub use streams::*;
+pub use utxoset::*;
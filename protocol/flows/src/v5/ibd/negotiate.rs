@@ -170,6 +170,7 @@ impl IbdFlow {
         high: Option<Hash>,
         timeout: Duration,
     ) -> Result<Vec<Hash>, ProtocolError> {
+        debug!("IBD [trace {}]: requesting a chain block locator ({:?}, {:?}) from peer {}", self.trace_id, low, high, self.router);
         self.router
             .enqueue(make_message!(
                 Payload::RequestIbdChainBlockLocator,
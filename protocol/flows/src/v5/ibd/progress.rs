@@ -1,8 +1,13 @@
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use chrono::{Local, LocalResult, TimeZone};
 use kaspa_core::info;
 
+use crate::flowcontext::sync_status::{IbdPhase, SyncStatusTracker};
+
 /// Minimum number of items to report
 const REPORT_BATCH_GRANULARITY: usize = 500;
 /// Maximum time to go without report
@@ -16,14 +21,23 @@ pub struct ProgressReporter {
     last_log_time: Instant,
     current_batch: usize,
     processed: usize,
+    sync_status: Arc<SyncStatusTracker>,
 }
 
 impl ProgressReporter {
-    pub fn new(low_daa_score: u64, mut high_daa_score: u64, object_name: &'static str) -> Self {
+    /// Creates a reporter of the progress of an IBD phase, starting the phase in `sync_status`
+    pub fn new(
+        low_daa_score: u64,
+        mut high_daa_score: u64,
+        object_name: &'static str,
+        sync_status: Arc<SyncStatusTracker>,
+        phase: IbdPhase,
+    ) -> Self {
         if high_daa_score <= low_daa_score {
             // Avoid a zero or negative diff
             high_daa_score = low_daa_score + 1;
         }
+        sync_status.start_phase(phase);
         Self {
             low_daa_score,
             high_daa_score,
@@ -32,11 +46,13 @@ impl ProgressReporter {
             last_log_time: Instant::now(),
             current_batch: 0,
             processed: 0,
+            sync_status,
         }
     }
 
     pub fn report(&mut self, processed_delta: usize, current_daa_score: u64, current_timestamp: u64) {
         self.current_batch += processed_delta;
+        self.sync_status.report(processed_delta as u64, Some(self.progress(current_daa_score)));
         let now = Instant::now();
         if now - self.last_log_time < REPORT_TIME_GRANULARITY && self.current_batch < REPORT_BATCH_GRANULARITY && self.processed > 0 {
             return;
@@ -46,8 +62,7 @@ impl ProgressReporter {
         if current_daa_score > self.high_daa_score {
             self.high_daa_score = current_daa_score + 1; // + 1 for keeping it at 99%
         }
-        let relative_daa_score = if current_daa_score > self.low_daa_score { current_daa_score - self.low_daa_score } else { 0 };
-        let percent = ((relative_daa_score as f64 / (self.high_daa_score - self.low_daa_score) as f64) * 100.0) as i32;
+        let percent = (self.progress(current_daa_score) * 100.0) as i32;
         if percent > self.last_reported_percent {
             let date = match Local.timestamp_opt(current_timestamp as i64 / 1000, 1000 * (current_timestamp as u32 % 1000)) {
                 LocalResult::None | LocalResult::Ambiguous(_, _) => "cannot parse date".into(),
@@ -61,6 +76,12 @@ impl ProgressReporter {
 
     pub fn report_completion(mut self, processed_delta: usize) {
         self.processed += self.current_batch + processed_delta;
+        self.sync_status.report(processed_delta as u64, Some(1.0));
         info!("IBD: Processed {} {} (100%)", self.processed, self.object_name);
     }
+
+    fn progress(&self, current_daa_score: u64) -> f64 {
+        let relative_daa_score = if current_daa_score > self.low_daa_score { current_daa_score - self.low_daa_score } else { 0 };
+        (relative_daa_score as f64 / (self.high_daa_score - self.low_daa_score) as f64).min(1.0)
+    }
 }
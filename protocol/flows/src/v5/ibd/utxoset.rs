@@ -0,0 +1,72 @@
+use kaspa_consensus_core::{
+    constants::MAX_SOMPI,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use kaspa_p2p_lib::common::ProtocolError;
+
+/// Verifies the UTXO set chunks of a pruning point as they are received during IBD.
+///
+/// The UTXO commitment of the pruning point is a MuHash of the full set so it can only be checked once all
+/// chunks have been imported. Invariants every prefix of a valid UTXO set satisfies are however checked on
+/// each chunk, so that a peer sending an invalid set is detected and dropped on the first offending chunk
+/// instead of after the whole set was downloaded.
+pub struct UtxosetChunkVerifier {
+    /// DAA score of the pruning point, above which no UTXO of its set can have been created
+    pruning_point_daa_score: u64,
+    /// Sum of the amounts of all the UTXOs received so far
+    accumulated_amount: u64,
+}
+
+impl UtxosetChunkVerifier {
+    pub fn new(pruning_point_daa_score: u64) -> Self {
+        Self { pruning_point_daa_score, accumulated_amount: 0 }
+    }
+
+    pub fn verify(&mut self, chunk: &[(TransactionOutpoint, UtxoEntry)]) -> Result<(), ProtocolError> {
+        for (outpoint, entry) in chunk.iter() {
+            if entry.block_daa_score > self.pruning_point_daa_score {
+                return Err(ProtocolError::MisbehavingPeer(format!(
+                    "UTXO {} has a DAA score {} above the pruning point DAA score {}",
+                    outpoint, entry.block_daa_score, self.pruning_point_daa_score
+                )));
+            }
+            // The total amount of the set is bounded by the maximum supply
+            self.accumulated_amount =
+                self.accumulated_amount.checked_add(entry.amount).filter(|&amount| amount <= MAX_SOMPI).ok_or_else(|| {
+                    ProtocolError::MisbehavingPeer(format!("UTXO set amount exceeds the maximum supply at UTXO {}", outpoint))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+    use kaspa_hashes::Hash;
+
+    fn utxo(index: u32, amount: u64, block_daa_score: u64) -> (TransactionOutpoint, UtxoEntry) {
+        (
+            TransactionOutpoint::new(Hash::from_u64_word(1), index),
+            UtxoEntry::new(amount, ScriptPublicKey::default(), block_daa_score, false),
+        )
+    }
+
+    #[test]
+    fn test_utxoset_chunk_verifier() {
+        let mut verifier = UtxosetChunkVerifier::new(100);
+        assert!(verifier.verify(&[]).is_ok());
+        assert!(verifier.verify(&[utxo(0, MAX_SOMPI / 2, 0), utxo(1, 1, 100)]).is_ok());
+
+        // A UTXO created after the pruning point aborts on the first chunk containing it
+        assert!(matches!(verifier.verify(&[utxo(2, 1, 101)]), Err(ProtocolError::MisbehavingPeer(_))));
+
+        // So does a set whose accumulated amount exceeds the maximum supply
+        let mut verifier = UtxosetChunkVerifier::new(100);
+        assert!(verifier.verify(&[utxo(0, MAX_SOMPI / 2, 0)]).is_ok());
+        assert!(verifier.verify(&[utxo(1, MAX_SOMPI / 2, 0)]).is_ok());
+        assert!(matches!(verifier.verify(&[utxo(2, MAX_SOMPI / 2, 0)]), Err(ProtocolError::MisbehavingPeer(_))));
+        assert!(matches!(UtxosetChunkVerifier::new(100).verify(&[utxo(0, u64::MAX, 0)]), Err(ProtocolError::MisbehavingPeer(_))));
+    }
+}
@@ -1,12 +1,46 @@
 use crate::{convert::error::ConversionError, core::peer::PeerKey, KaspadMessagePayloadType};
-use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
+use kaspa_consensus_core::{
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    network::NetworkId,
+};
 use kaspa_mining_errors::manager::MiningManagerError;
-use std::time::Duration;
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        OnceLock,
+    },
+    time::Duration,
+};
 use thiserror::Error;
 
 /// Default P2P communication timeout
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120); // 2 minutes
 
+/// Identifier of a P2P request spawned by a flow, attached to the logs and errors related to this request so
+/// that a given peer interaction can be followed through the logs.
+///
+/// The high half is drawn randomly once per node run, keeping ids distinct across restarts, while the low half
+/// is a sequence number within the run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TraceId(u64);
+
+impl TraceId {
+    /// Generates a trace id which was never issued before by this node run
+    pub fn generate() -> Self {
+        static RUN_ID: OnceLock<u32> = OnceLock::new();
+        static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+        let run_id = *RUN_ID.get_or_init(rand::random);
+        Self(((run_id as u64) << 32) | SEQUENCE.fetch_add(1, Ordering::Relaxed) as u64)
+    }
+}
+
+impl Display for TraceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:08x}-{:08x}", self.0 >> 32, self.0 as u32)
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 pub enum ProtocolError {
     #[error("timeout expired after {0:?}")]
@@ -16,7 +50,10 @@ pub enum ProtocolError {
     VersionMismatch(u32, u32),
 
     #[error("Network mismatch - local: {0}, remote: {1}")]
-    WrongNetwork(String, String),
+    WrongNetwork(NetworkId, NetworkId),
+
+    #[error("peer announced an unknown network {0}")]
+    UnknownNetwork(String),
 
     #[error("expected message type/s {0} but got {1:?}")]
     UnexpectedMessage(&'static str, Option<KaspadMessagePayloadType>),
@@ -72,6 +109,9 @@ pub enum ProtocolError {
 
     #[error("got reject message: {0}")]
     IgnorableReject(String),
+
+    #[error("{1} [trace {0}]")]
+    Traced(TraceId, Box<ProtocolError>),
 }
 
 /// String used as a P2P convention to signal connection is rejected because we are connecting to ourselves
@@ -81,19 +121,43 @@ const LOOPBACK_CONNECTION_MESSAGE: &str = "LOOPBACK_CONNECTION";
 const DUPLICATE_CONNECTION_MESSAGE: &str = "DUPLICATE_CONNECTION";
 
 impl ProtocolError {
+    /// Attaches `trace_id` to the error, keeping the original trace id if the error is already traced
+    pub fn with_trace(self, trace_id: TraceId) -> Self {
+        match self {
+            Self::Traced(..) => self,
+            err => Self::Traced(trace_id, Box::new(err)),
+        }
+    }
+
+    pub fn trace_id(&self) -> Option<TraceId> {
+        match self {
+            Self::Traced(trace_id, _) => Some(*trace_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the error stripped of its trace id, if any
+    pub fn untraced(&self) -> &Self {
+        match self {
+            Self::Traced(_, err) => err,
+            err => err,
+        }
+    }
+
     pub fn is_connection_closed_error(&self) -> bool {
-        matches!(self, Self::ConnectionClosed)
+        matches!(self.untraced(), Self::ConnectionClosed)
     }
 
     pub fn can_send_outgoing_message(&self) -> bool {
-        !matches!(self, Self::ConnectionClosed | Self::OutgoingRouteCapacityReached(_))
+        !matches!(self.untraced(), Self::ConnectionClosed | Self::OutgoingRouteCapacityReached(_))
     }
 
+    /// Note that the trace id of a traced error is part of the message, allowing the peer to report it as well
     pub fn to_reject_message(&self) -> String {
-        match self {
+        match self.untraced() {
             Self::LoopbackConnection(_) => LOOPBACK_CONNECTION_MESSAGE.to_owned(),
             Self::PeerAlreadyExists(_) => DUPLICATE_CONNECTION_MESSAGE.to_owned(),
-            err => err.to_string(),
+            _ => self.to_string(),
         }
     }
 
@@ -220,3 +284,25 @@ macro_rules! dequeue_with_request_id {
         $crate::unwrap_message_with_request_id!($receiver.recv().await, $pattern)
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traced_errors() {
+        let (first, second) = (TraceId::generate(), TraceId::generate());
+        assert_ne!(first, second);
+        assert_eq!(first.to_string().len(), 17);
+
+        let err = ProtocolError::ConnectionClosed.with_trace(first).with_trace(second);
+        assert_eq!(err.trace_id(), Some(first), "the original trace id should be kept");
+        assert!(err.is_connection_closed_error());
+        assert!(!err.can_send_outgoing_message());
+        assert_eq!(err.to_string(), format!("peer connection is closed [trace {first}]"));
+        assert_eq!(err.to_reject_message(), err.to_string());
+
+        let err = ProtocolError::LoopbackConnection(PeerKey::new(Default::default(), "127.0.0.1".parse().unwrap())).with_trace(first);
+        assert_eq!(ProtocolError::from_reject_message(err.to_reject_message()).to_string(), "got reject message: LOOPBACK_CONNECTION");
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 };
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 
-use super::peer::PeerKey;
+use super::{peer::PeerKey, route_metrics::FlowRouteSnapshot};
 use rand::prelude::IteratorRandom;
 
 #[derive(Debug)]
@@ -187,6 +187,19 @@ impl Hub {
         !self.peers.read().is_empty()
     }
 
+    /// Returns the incoming route counters of all currently active peers, aggregated per flow and sorted by flow name
+    pub fn flow_route_snapshots(&self) -> Vec<(String, FlowRouteSnapshot)> {
+        let mut flows: HashMap<String, FlowRouteSnapshot> = HashMap::new();
+        for router in self.peers.read().values() {
+            for counters in router.route_counters() {
+                flows.entry(counters.flow().to_owned()).or_default().merge(&counters.snapshot());
+            }
+        }
+        let mut flows = flows.into_iter().collect::<Vec<_>>();
+        flows.sort_by(|(a, _), (b, _)| a.cmp(b));
+        flows
+    }
+
     /// Returns whether a peer matching `peer_key` is registered
     pub fn has_peer(&self, peer_key: PeerKey) -> bool {
         self.peers.read().contains_key(&peer_key)
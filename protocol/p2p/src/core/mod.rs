@@ -3,4 +3,6 @@ pub mod connection_handler;
 pub mod hub;
 pub mod payload_type;
 pub mod peer;
+pub mod route_metrics;
 pub mod router;
+pub mod traffic;
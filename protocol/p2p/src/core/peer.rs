@@ -1,3 +1,4 @@
+use crate::core::traffic::PeerTrafficSnapshot;
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_utils::networking::{IpAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
@@ -21,6 +22,7 @@ pub struct Peer {
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    traffic: PeerTrafficSnapshot,
 }
 
 impl Peer {
@@ -31,8 +33,9 @@ impl Peer {
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        traffic: PeerTrafficSnapshot,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration, traffic }
     }
 
     /// Internal identity of this peer
@@ -65,6 +68,11 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Traffic exchanged with this peer at the time this object was created
+    pub fn traffic(&self) -> &PeerTrafficSnapshot {
+        &self.traffic
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
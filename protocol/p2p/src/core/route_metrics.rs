@@ -0,0 +1,109 @@
+use kaspa_utils_tower::counters::{MethodCounters, MethodCountersSnapshot};
+use parking_lot::Mutex;
+use std::{collections::VecDeque, time::Instant};
+
+/// Name of the flow of the routes subscribed to no message type, which only receive responses addressed by route id
+pub const RESPONSE_FLOW: &str = "Response";
+
+/// Counters of an incoming route, shared by the router enqueuing the messages of the peer and the flow dequeuing them
+#[derive(Debug)]
+pub struct RouteCounters {
+    /// Name of the flow owning the route, the first message type it subscribed to
+    flow: String,
+    /// Enqueuing instants of the messages waiting in the route, in FIFO order
+    pending: Mutex<VecDeque<Instant>>,
+    /// Time spent by the dequeued messages waiting in the route for their flow
+    latencies: MethodCounters,
+}
+
+impl RouteCounters {
+    pub fn new(flow: String) -> Self {
+        Self { flow, pending: Default::default(), latencies: Default::default() }
+    }
+
+    pub fn flow(&self) -> &str {
+        &self.flow
+    }
+
+    /// Records a message about to be enqueued. Must be followed by [`Self::cancel_enqueued`] if the enqueuing fails.
+    pub fn record_enqueued(&self) {
+        self.pending.lock().push_back(Instant::now());
+    }
+
+    pub fn cancel_enqueued(&self) {
+        self.pending.lock().pop_back();
+    }
+
+    pub fn record_dequeued(&self) {
+        if let Some(enqueued) = self.pending.lock().pop_front() {
+            self.latencies.record(enqueued.elapsed(), false);
+        }
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    pub fn snapshot(&self) -> FlowRouteSnapshot {
+        FlowRouteSnapshot { queue_depth: self.queue_depth() as u64, latency: self.latencies.snapshot() }
+    }
+}
+
+/// A point in time copy of the [`RouteCounters`] of one or more routes of a flow
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlowRouteSnapshot {
+    /// Number of messages waiting in the routes
+    pub queue_depth: u64,
+    /// Messages dequeued from the routes, their latency being the time they waited for the flow
+    pub latency: MethodCountersSnapshot,
+}
+
+impl FlowRouteSnapshot {
+    /// Accumulates `other` into `self`, both having been taken from counters sharing the same latency buckets
+    pub fn merge(&mut self, other: &FlowRouteSnapshot) {
+        self.queue_depth += other.queue_depth;
+        self.latency.requests += other.latency.requests;
+        self.latency.errors += other.latency.errors;
+        self.latency.latency_sum_us += other.latency.latency_sum_us;
+        if self.latency.latency_buckets.is_empty() {
+            self.latency.latency_buckets = other.latency.latency_buckets.clone();
+        } else {
+            self.latency.latency_buckets.iter_mut().zip(other.latency.latency_buckets.iter()).for_each(|(bucket, (_, count))| {
+                bucket.1 += count;
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_counters() {
+        let counters = RouteCounters::new("InvTransactions".to_owned());
+        counters.record_enqueued();
+        counters.record_enqueued();
+        counters.record_enqueued();
+        counters.cancel_enqueued();
+        assert_eq!(counters.queue_depth(), 2);
+
+        counters.record_dequeued();
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.queue_depth, 1);
+        assert_eq!(snapshot.latency.requests, 1);
+
+        // Dequeuing more than was recorded as enqueued is ignored
+        counters.record_dequeued();
+        counters.record_dequeued();
+        assert_eq!(counters.queue_depth(), 0);
+        assert_eq!(counters.snapshot().latency.requests, 2);
+
+        let mut merged = FlowRouteSnapshot::default();
+        merged.merge(&snapshot);
+        merged.merge(&counters.snapshot());
+        assert_eq!(merged.queue_depth, 1);
+        assert_eq!(merged.latency.requests, 3);
+        assert_eq!(merged.latency.latency_buckets.last().unwrap().1, 3);
+    }
+}
@@ -20,10 +20,13 @@ use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
 use super::peer::{PeerKey, PeerProperties};
+use super::route_metrics::{RouteCounters, RESPONSE_FLOW};
+use super::traffic::PeerTrafficCounters;
 
 pub struct IncomingRoute {
     rx: MpscReceiver<KaspadMessage>,
     id: u32,
+    counters: Arc<RouteCounters>,
 }
 
 // BLANK_ROUTE_ID is the value that is used in the p2p when no request or response IDs
@@ -33,14 +36,25 @@ pub const BLANK_ROUTE_ID: u32 = 0;
 static ROUTE_ID: AtomicU32 = AtomicU32::new(BLANK_ROUTE_ID + 1);
 
 impl IncomingRoute {
-    pub fn new(rx: MpscReceiver<KaspadMessage>) -> Self {
+    pub fn new(rx: MpscReceiver<KaspadMessage>, counters: Arc<RouteCounters>) -> Self {
         let id = ROUTE_ID.fetch_add(1, Ordering::SeqCst);
-        Self { rx, id }
+        Self { rx, id, counters }
     }
 
     pub fn id(&self) -> u32 {
         self.id
     }
+
+    /// Receives the next message of the route, recording the time it waited in the route.
+    ///
+    /// Shadows the `recv` of the dereferenced receiver so that every flow dequeuing is accounted for.
+    pub async fn recv(&mut self) -> Option<KaspadMessage> {
+        let msg = self.rx.recv().await;
+        if msg.is_some() {
+            self.counters.record_dequeued();
+        }
+        msg
+    }
 }
 
 impl Deref for IncomingRoute {
@@ -127,9 +141,9 @@ pub struct Router {
     connection_started: Instant,
 
     /// Routing map for mapping messages to subscribed flows
-    routing_map_by_type: RwLock<HashMap<KaspadMessagePayloadType, MpscSender<KaspadMessage>>>,
+    routing_map_by_type: RwLock<HashMap<KaspadMessagePayloadType, RouteSender>>,
 
-    routing_map_by_id: RwLock<HashMap<u32, MpscSender<KaspadMessage>>>,
+    routing_map_by_id: RwLock<HashMap<u32, RouteSender>>,
 
     /// The outgoing route for sending messages to this peer
     outgoing_route: MpscSender<KaspadMessage>,
@@ -139,6 +153,9 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Counters of the traffic exchanged with this peer
+    traffic: PeerTrafficCounters,
 }
 
 impl Display for Router {
@@ -162,10 +179,18 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.traffic.snapshot(),
         )
     }
 }
 
+/// The sending end of an incoming route along with the counters shared with its receiving end
+#[derive(Clone)]
+struct RouteSender {
+    sender: MpscSender<KaspadMessage>,
+    counters: Arc<RouteCounters>,
+}
+
 fn message_summary(msg: &KaspadMessage) -> impl Debug {
     // TODO (low priority): display a concise summary of the message. Printing full messages
     // overflows the logs and is hardly useful, hence we currently only return the type
@@ -193,6 +218,7 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            traffic: Default::default(),
         });
 
         let router_clone = router.clone();
@@ -212,6 +238,11 @@ impl Router {
                     res = incoming_stream.message() => match res {
                         Ok(Some(msg)) => {
                             trace!("P2P msg: {:?}, router-id: {}, peer: {}", message_summary(&msg), router.identity(), router);
+                            router.traffic.record_received(&msg);
+                            #[cfg(feature = "fault-injection")]
+                            if let Some(delay) = kaspa_utils::fault::P2P_INCOMING_DELAY.delay() {
+                                tokio::time::sleep(delay).await;
+                            }
                             match router.route_to_flow(msg) {
                                 Ok(()) => {},
                                 Err(e) => {
@@ -294,6 +325,11 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Counters of the traffic exchanged with this peer
+    pub fn traffic(&self) -> &PeerTrafficCounters {
+        &self.traffic
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -321,7 +357,10 @@ impl Router {
     /// This should be used by `ConnectionInitializer` instances to register application-specific flows.
     pub fn subscribe_with_capacity(&self, msg_types: Vec<KaspadMessagePayloadType>, capacity: usize) -> IncomingRoute {
         let (sender, receiver) = mpsc_channel(capacity);
-        let incoming_route = IncomingRoute::new(receiver);
+        let flow = msg_types.first().map(|msg_type| format!("{msg_type:?}")).unwrap_or_else(|| RESPONSE_FLOW.to_owned());
+        let counters = Arc::new(RouteCounters::new(flow));
+        let sender = RouteSender { sender, counters: counters.clone() };
+        let incoming_route = IncomingRoute::new(receiver, counters);
         let mut map_by_type = self.routing_map_by_type.write();
         for msg_type in msg_types {
             match map_by_type.insert(msg_type, sender.clone()) {
@@ -380,8 +419,14 @@ impl Router {
             self.routing_map_by_type.read().get(&msg_type).cloned()
         };
 
-        if let Some(sender) = op {
-            match sender.try_send(msg) {
+        if let Some(RouteSender { sender, counters }) = op {
+            // The message is recorded before being sent so that the flow cannot dequeue it before
+            counters.record_enqueued();
+            let res = sender.try_send(msg);
+            if res.is_err() {
+                counters.cancel_enqueued();
+            }
+            match res {
                 Ok(_) => Ok(()),
                 Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
                 Err(TrySendError::Full(_)) => {
@@ -399,11 +444,20 @@ impl Router {
         }
     }
 
+    /// Returns the counters of every incoming route of this peer
+    pub fn route_counters(&self) -> Vec<Arc<RouteCounters>> {
+        self.routing_map_by_id.read().values().map(|route| route.counters.clone()).collect()
+    }
+
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
-        match self.outgoing_route.try_send(msg) {
-            Ok(_) => Ok(()),
+        match self.outgoing_route.try_reserve() {
+            Ok(permit) => {
+                self.traffic.record_sent(&msg);
+                permit.send(msg);
+                Ok(())
+            }
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
             Err(TrySendError::Full(_)) => Err(ProtocolError::OutgoingRouteCapacityReached(self.to_string())),
         }
@@ -0,0 +1,90 @@
+use crate::{pb::KaspadMessage, KaspadMessagePayloadType};
+use parking_lot::Mutex;
+use prost::Message;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Number of messages of a given type exchanged with a peer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageCounts {
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Counters of the traffic exchanged with a peer. Byte counts are the encoded sizes of the p2p messages, excluding
+/// the gRPC and transport overheads.
+#[derive(Debug, Default)]
+pub struct PeerTrafficCounters {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    messages: Mutex<HashMap<KaspadMessagePayloadType, MessageCounts>>,
+}
+
+impl PeerTrafficCounters {
+    pub fn record_sent(&self, msg: &KaspadMessage) {
+        self.bytes_sent.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+        if let Some(payload) = msg.payload.as_ref() {
+            self.messages.lock().entry(payload.into()).or_default().sent += 1;
+        }
+    }
+
+    pub fn record_received(&self, msg: &KaspadMessage) {
+        self.bytes_received.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+        if let Some(payload) = msg.payload.as_ref() {
+            self.messages.lock().entry(payload.into()).or_default().received += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> PeerTrafficSnapshot {
+        PeerTrafficSnapshot {
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            messages: self.messages.lock().clone(),
+        }
+    }
+}
+
+/// A point in time copy of the [`PeerTrafficCounters`] of a peer
+#[derive(Debug, Clone, Default)]
+pub struct PeerTrafficSnapshot {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub messages: HashMap<KaspadMessagePayloadType, MessageCounts>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        make_message,
+        pb::{kaspad_message::Payload, PingMessage, PongMessage},
+    };
+
+    #[test]
+    fn test_peer_traffic_counters() {
+        let counters = PeerTrafficCounters::default();
+        let ping = make_message!(Payload::Ping, PingMessage { nonce: 1 });
+        let pong = make_message!(Payload::Pong, PongMessage { nonce: 1 });
+
+        counters.record_sent(&ping);
+        counters.record_sent(&ping);
+        counters.record_received(&pong);
+        // Messages without a payload only count as bytes
+        let empty = KaspadMessage { payload: None, response_id: 0, request_id: 0 };
+        counters.record_received(&empty);
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.bytes_sent, 2 * ping.encoded_len() as u64);
+        assert_eq!(snapshot.bytes_received, (pong.encoded_len() + empty.encoded_len()) as u64);
+        assert_eq!(snapshot.messages.len(), 2);
+        assert_eq!(snapshot.messages[&KaspadMessagePayloadType::Ping], MessageCounts { sent: 2, received: 0 });
+        assert_eq!(snapshot.messages[&KaspadMessagePayloadType::Pong], MessageCounts { sent: 0, received: 1 });
+
+        // A snapshot is not affected by later traffic
+        counters.record_received(&ping);
+        assert_eq!(snapshot.messages[&KaspadMessagePayloadType::Ping].received, 0);
+        assert_eq!(counters.snapshot().messages[&KaspadMessagePayloadType::Ping], MessageCounts { sent: 2, received: 1 });
+    }
+}
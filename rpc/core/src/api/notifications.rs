@@ -44,6 +44,18 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "HealthAlert notification: {} (resolved: {})", "_0.alert", "_0.resolved")]
+    HealthAlert(HealthAlertNotification),
+
+    #[display(fmt = "MempoolTransactionsEvicted notification: {} transactions ({})", "_0.transaction_ids.len()", "_0.reason")]
+    MempoolTransactionsEvicted(MempoolTransactionsEvictedNotification),
+
+    #[display(fmt = "JobCompleted notification: {}", "_0.job")]
+    JobCompleted(JobCompletedNotification),
+
+    #[display(fmt = "IndexResyncProgress notification: {}", "_0.progress")]
+    IndexResyncProgress(IndexResyncProgressNotification),
 }
 }
 
@@ -60,6 +72,10 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::HealthAlert(v) => to_value(&v),
+            Notification::MempoolTransactionsEvicted(v) => to_value(&v),
+            Notification::JobCompleted(v) => to_value(&v),
+            Notification::IndexResyncProgress(v) => to_value(&v),
         }
     }
 }
@@ -108,9 +124,32 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_utxos_changed_subscriptions(
+        &self,
+        subscriptions: &[&UtxosChangedSubscription],
+        context: &SubscriptionContext,
+    ) -> Vec<Option<Self>> {
+        let Self::UtxosChanged(notification) = self else { return vec![None; subscriptions.len()] };
+        notification
+            .apply_utxos_changed_subscriptions(subscriptions, context)
+            .into_iter()
+            .map(|notification| notification.map(Self::UtxosChanged))
+            .collect()
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
+
+    fn coalesce(&mut self, next: &Self) -> bool {
+        match (self, next) {
+            (Self::UtxosChanged(notification), Self::UtxosChanged(next)) => {
+                notification.coalesce(next);
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -123,4 +162,37 @@ mod test {
         let notification = Notification::try_from_slice(bytes);
         println!("notification: {notification:?}");
     }
+
+    #[test]
+    fn test_utxos_changed_coalescing() {
+        use crate::model::{RpcTransactionOutpoint, RpcUtxoEntry, RpcUtxosByAddressesEntry};
+        use kaspa_consensus_core::tx::ScriptPublicKey;
+        use kaspa_hashes::Hash;
+
+        let entry = |index: u32| RpcUtxosByAddressesEntry {
+            address: None,
+            outpoint: RpcTransactionOutpoint::new(Hash::from_u64_word(1), index),
+            utxo_entry: RpcUtxoEntry::new(index as u64, ScriptPublicKey::default(), 0, false),
+        };
+        let utxos_changed = |added: &[u32], removed: &[u32]| {
+            Notification::UtxosChanged(UtxosChangedNotification {
+                added: Arc::new(added.iter().copied().map(entry).collect()),
+                removed: Arc::new(removed.iter().copied().map(entry).collect()),
+            })
+        };
+        let outpoints = |notification: &Notification| {
+            let Notification::UtxosChanged(notification) = notification else { panic!() };
+            let indexes = |entries: &[RpcUtxosByAddressesEntry]| entries.iter().map(|x| x.outpoint.index).collect::<Vec<_>>();
+            (indexes(&notification.added), indexes(&notification.removed))
+        };
+
+        // UTXO 1 is added then spent and cancels out, UTXO 3 is removed then added back
+        let mut notification = utxos_changed(&[0, 1], &[2, 3]);
+        assert!(notification.coalesce(&utxos_changed(&[3, 4], &[1, 5])));
+        assert_eq!(outpoints(&notification), (vec![0, 3, 4], vec![2, 5]));
+
+        // Other event types are not coalesced
+        assert!(!notification.coalesce(&Notification::SinkBlueScoreChanged(SinkBlueScoreChangedNotification { sink_blue_score: 0 })));
+        assert_eq!(outpoints(&notification), (vec![0, 3, 4], vec![2, 5]));
+    }
 }
@@ -114,6 +114,78 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+
+    // Ops added after v0.14.1 are appended so the Borsh discriminants of the former ones never change
+
+    // Methods
+    /// Submits a list of transactions, possibly chained, to the mempool
+    SubmitTransactions = 54,
+    /// Get the mass components of a transaction and the applicable mass limits
+    GetTransactionMass,
+    /// Select UTXOs and build an unsigned transaction
+    BuildUnsignedTransaction,
+    /// Get structural DAG stats over a window of recent chain blocks
+    GetDagStats,
+    /// Get the alerts currently raised by the node health monitor
+    GetHealth,
+    /// Get feerate estimates based on the mempool congestion
+    GetFeeEstimate,
+    /// Returns the notification scopes the calling connection is currently subscribed to
+    GetSubscriptions,
+    /// Get the current virtual parents along with the excluded parent candidates
+    GetVirtualParents,
+    /// Get the consensus parameters of the network
+    GetConsensusParams,
+    /// Returns the location and acceptance status of a transaction (requires --txindex)
+    GetTransactionById,
+    /// Samples random selected chain blocks weighted by the work they add to the chain
+    SampleChainBlocks,
+    /// Returns the p2p connections along with their traffic counters
+    GetConnections,
+    /// Returns the lowest selected chain block having a blue score greater than or equal to the requested one
+    GetBlockByBlueScore,
+    /// Returns the lowest selected chain block having a DAA score greater than or equal to the requested one
+    GetBlockByDaaScore,
+    /// Returns the selected chain block with the timestamp nearest to the requested one
+    GetChainBlockByTimestamp,
+    /// Pages through the UTXO set of the pruning point
+    GetPruningPointUtxos,
+    /// Returns the mempool policy in force and the mempool occupancy
+    GetMempoolInfo,
+    /// Adjusts the mempool policy at runtime
+    SetMempoolPolicy,
+    /// Get whether a block is currently blue or red, along with its merging block
+    GetCurrentBlockColor,
+    /// Get the active bans along with their remaining time
+    GetBans,
+    /// Returns the processing status of a block submitted without waiting for its processing
+    GetBlockProcessingStatus,
+    /// Starts a long-running node operation as a job
+    StartJob,
+    /// Polls the status of a job
+    GetJobStatus,
+    /// Returns the per-event-type delivery statistics of the node notifiers
+    GetNotificationStats,
+    /// Mines blocks on demand (simnet only)
+    GenerateBlocks,
+    /// Returns the RPC methods and the notifications supported by the node, letting clients degrade gracefully against nodes of another version
+    GetServerCapabilities,
+    /// Adds or removes the transaction ids and script public keys excluded from the block templates
+    UpdateBlockTemplateExclusions,
+    /// Returns the chain-quality samples (red rate, propagation delay, miner concentration) computed by the node
+    GetChainQuality,
+
+    // Subscription commands
+    NotifyHealthAlert,
+    NotifyMempoolTransactionsEvicted,
+    NotifyJobCompleted,
+    NotifyIndexResyncProgress,
+
+    // Notification ops
+    HealthAlertNotification,
+    MempoolTransactionsEvictedNotification,
+    JobCompletedNotification,
+    IndexResyncProgressNotification,
 }
 
 impl RpcApiOps {
@@ -129,6 +201,10 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyHealthAlert
+                | RpcApiOps::NotifyMempoolTransactionsEvicted
+                | RpcApiOps::NotifyJobCompleted
+                | RpcApiOps::NotifyIndexResyncProgress
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -155,6 +231,119 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::HealthAlert => RpcApiOps::HealthAlertNotification,
+            EventType::MempoolTransactionsEvicted => RpcApiOps::MempoolTransactionsEvictedNotification,
+            EventType::JobCompleted => RpcApiOps::JobCompletedNotification,
+            EventType::IndexResyncProgress => RpcApiOps::IndexResyncProgressNotification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The Borsh discriminant of every op, as seen by the wRPC Borsh clients. An op inserted anywhere but at the end
+    /// of the enum shifts the ops following it and fails this test.
+    #[test]
+    fn test_ops_discriminants() {
+        let pinned = [
+            (0, RpcApiOps::Ping),
+            (1, RpcApiOps::GetMetrics),
+            (2, RpcApiOps::GetServerInfo),
+            (3, RpcApiOps::GetSyncStatus),
+            (4, RpcApiOps::GetCurrentNetwork),
+            (5, RpcApiOps::SubmitBlock),
+            (6, RpcApiOps::GetBlockTemplate),
+            (7, RpcApiOps::GetPeerAddresses),
+            (8, RpcApiOps::GetSink),
+            (9, RpcApiOps::GetMempoolEntry),
+            (10, RpcApiOps::GetMempoolEntries),
+            (11, RpcApiOps::GetConnectedPeerInfo),
+            (12, RpcApiOps::AddPeer),
+            (13, RpcApiOps::SubmitTransaction),
+            (14, RpcApiOps::GetBlock),
+            (15, RpcApiOps::GetSubnetwork),
+            (16, RpcApiOps::GetVirtualChainFromBlock),
+            (17, RpcApiOps::GetBlocks),
+            (18, RpcApiOps::GetBlockCount),
+            (19, RpcApiOps::GetBlockDagInfo),
+            (20, RpcApiOps::ResolveFinalityConflict),
+            (21, RpcApiOps::Shutdown),
+            (22, RpcApiOps::GetHeaders),
+            (23, RpcApiOps::GetUtxosByAddresses),
+            (24, RpcApiOps::GetBalanceByAddress),
+            (25, RpcApiOps::GetBalancesByAddresses),
+            (26, RpcApiOps::GetSinkBlueScore),
+            (27, RpcApiOps::Ban),
+            (28, RpcApiOps::Unban),
+            (29, RpcApiOps::GetInfo),
+            (30, RpcApiOps::EstimateNetworkHashesPerSecond),
+            (31, RpcApiOps::GetMempoolEntriesByAddresses),
+            (32, RpcApiOps::GetCoinSupply),
+            (33, RpcApiOps::GetDaaScoreTimestampEstimate),
+            (34, RpcApiOps::NotifyBlockAdded),
+            (35, RpcApiOps::NotifyNewBlockTemplate),
+            (36, RpcApiOps::NotifyUtxosChanged),
+            (37, RpcApiOps::NotifyPruningPointUtxoSetOverride),
+            (38, RpcApiOps::NotifyFinalityConflict),
+            (39, RpcApiOps::NotifyFinalityConflictResolved),
+            (40, RpcApiOps::NotifyVirtualDaaScoreChanged),
+            (41, RpcApiOps::NotifyVirtualChainChanged),
+            (42, RpcApiOps::NotifySinkBlueScoreChanged),
+            (43, RpcApiOps::Subscribe),
+            (44, RpcApiOps::Unsubscribe),
+            (45, RpcApiOps::BlockAddedNotification),
+            (46, RpcApiOps::VirtualChainChangedNotification),
+            (47, RpcApiOps::FinalityConflictNotification),
+            (48, RpcApiOps::FinalityConflictResolvedNotification),
+            (49, RpcApiOps::UtxosChangedNotification),
+            (50, RpcApiOps::SinkBlueScoreChangedNotification),
+            (51, RpcApiOps::VirtualDaaScoreChangedNotification),
+            (52, RpcApiOps::PruningPointUtxoSetOverrideNotification),
+            (53, RpcApiOps::NewBlockTemplateNotification),
+            (54, RpcApiOps::SubmitTransactions),
+            (55, RpcApiOps::GetTransactionMass),
+            (56, RpcApiOps::BuildUnsignedTransaction),
+            (57, RpcApiOps::GetDagStats),
+            (58, RpcApiOps::GetHealth),
+            (59, RpcApiOps::GetFeeEstimate),
+            (60, RpcApiOps::GetSubscriptions),
+            (61, RpcApiOps::GetVirtualParents),
+            (62, RpcApiOps::GetConsensusParams),
+            (63, RpcApiOps::GetTransactionById),
+            (64, RpcApiOps::SampleChainBlocks),
+            (65, RpcApiOps::GetConnections),
+            (66, RpcApiOps::GetBlockByBlueScore),
+            (67, RpcApiOps::GetBlockByDaaScore),
+            (68, RpcApiOps::GetChainBlockByTimestamp),
+            (69, RpcApiOps::GetPruningPointUtxos),
+            (70, RpcApiOps::GetMempoolInfo),
+            (71, RpcApiOps::SetMempoolPolicy),
+            (72, RpcApiOps::GetCurrentBlockColor),
+            (73, RpcApiOps::GetBans),
+            (74, RpcApiOps::GetBlockProcessingStatus),
+            (75, RpcApiOps::StartJob),
+            (76, RpcApiOps::GetJobStatus),
+            (77, RpcApiOps::GetNotificationStats),
+            (78, RpcApiOps::GenerateBlocks),
+            (79, RpcApiOps::GetServerCapabilities),
+            (80, RpcApiOps::UpdateBlockTemplateExclusions),
+            (81, RpcApiOps::GetChainQuality),
+            (82, RpcApiOps::NotifyHealthAlert),
+            (83, RpcApiOps::NotifyMempoolTransactionsEvicted),
+            (84, RpcApiOps::NotifyJobCompleted),
+            (85, RpcApiOps::NotifyIndexResyncProgress),
+            (86, RpcApiOps::HealthAlertNotification),
+            (87, RpcApiOps::MempoolTransactionsEvictedNotification),
+            (88, RpcApiOps::JobCompletedNotification),
+            (89, RpcApiOps::IndexResyncProgressNotification),
+        ];
+        assert_eq!(pinned.len(), RpcApiOps::list().len(), "ops must be appended to this list too");
+        for (discriminant, op) in pinned {
+            assert_eq!(u32::from(op), discriminant, "{op:?}");
+            assert_eq!(borsh::to_vec(&op).unwrap(), vec![discriminant as u8], "{op:?}");
+            assert_eq!(RpcApiOps::try_from_slice(&[discriminant as u8]).unwrap(), op);
         }
     }
 }
@@ -4,13 +4,14 @@
 //! All data provided by the RCP server can be trusted by the client
 //! No data submitted by the client to the server can be trusted
 
-use crate::{model::*, notify::connection::ChannelConnection, RpcResult};
+use crate::{model::*, notify::connection::ChannelConnection, RpcError, RpcResult};
 use async_trait::async_trait;
 use downcast::{downcast_sync, AnySync};
 use kaspa_notify::{listener::ListenerId, scope::Scope, subscription::Command};
 use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
+pub const MAX_SAFE_SAMPLE_SIZE: u32 = 1_000;
 
 /// Client RPC Api
 ///
@@ -34,8 +35,18 @@ pub trait RpcApi: Sync + Send + AnySync {
         connection_metrics: bool,
         bandwidth_metrics: bool,
         consensus_metrics: bool,
+        storage_metrics: bool,
+        request_metrics: bool,
     ) -> RpcResult<GetMetricsResponse> {
-        self.get_metrics_call(GetMetricsRequest { process_metrics, connection_metrics, bandwidth_metrics, consensus_metrics }).await
+        self.get_metrics_call(GetMetricsRequest {
+            process_metrics,
+            connection_metrics,
+            bandwidth_metrics,
+            consensus_metrics,
+            storage_metrics,
+            request_metrics,
+        })
+        .await
     }
     async fn get_metrics_call(&self, request: GetMetricsRequest) -> RpcResult<GetMetricsResponse>;
 
@@ -46,7 +57,8 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn get_server_info_call(&self, request: GetServerInfoRequest) -> RpcResult<GetServerInfoResponse>;
 
-    // Get current sync status of the node (should be converted to a notification subscription)
+    /// Returns whether the node is synced. The full status, including the current sync phase and its progress,
+    /// is returned by [`RpcApi::get_sync_status_call`].
     async fn get_sync_status(&self) -> RpcResult<bool> {
         Ok(self.get_sync_status_call(GetSyncStatusRequest {}).await?.is_synced)
     }
@@ -60,6 +72,15 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn get_current_network_call(&self, request: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse>;
 
+    /// Checks that the node is running against the `expected` network, suffix included.
+    async fn verify_network_id(&self, expected: RpcNetworkId) -> RpcResult<()> {
+        let network_id = self.get_server_info().await?.network_id;
+        match network_id == expected {
+            true => Ok(()),
+            false => Err(RpcError::NetworkMismatch(expected, network_id)),
+        }
+    }
+
     /// Submit a block into the DAG.
     ///
     /// Blocks are generally expected to have been generated using the get_block_template call.
@@ -78,7 +99,7 @@ pub trait RpcApi: Sync + Send + AnySync {
 
     /// Requests the list of known kaspad addresses in the current network (mainnet, testnet, etc.)
     async fn get_peer_addresses(&self) -> RpcResult<GetPeerAddressesResponse> {
-        self.get_peer_addresses_call(GetPeerAddressesRequest {}).await
+        self.get_peer_addresses_call(GetPeerAddressesRequest::default()).await
     }
     async fn get_peer_addresses_call(&self, request: GetPeerAddressesRequest) -> RpcResult<GetPeerAddressesResponse>;
 
@@ -128,10 +149,45 @@ pub trait RpcApi: Sync + Send + AnySync {
 
     /// Submits a transaction to the mempool.
     async fn submit_transaction(&self, transaction: RpcTransaction, allow_orphan: bool) -> RpcResult<RpcTransactionId> {
-        Ok(self.submit_transaction_call(SubmitTransactionRequest { transaction, allow_orphan }).await?.transaction_id)
+        Ok(self.submit_transaction_call(SubmitTransactionRequest { transaction, allow_orphan, replace: false }).await?.transaction_id)
     }
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse>;
 
+    /// Submits a transaction to the mempool on behalf of the RPC connection identified by `submitter`, to which a quota
+    /// of high-priority transactions applies.
+    ///
+    /// Services not tracking submitters simply submit the transaction.
+    async fn submit_transaction_from_call(
+        &self,
+        _submitter: String,
+        request: SubmitTransactionRequest,
+    ) -> RpcResult<SubmitTransactionResponse> {
+        self.submit_transaction_call(request).await
+    }
+
+    /// Submits a list of transactions, possibly chained, to the mempool. The transactions are validated in topological
+    /// order, so transactions redeeming outputs of other transactions of the list are not orphaned.
+    ///
+    /// Returns a result per transaction, in submission order.
+    async fn submit_transactions(
+        &self,
+        transactions: Vec<RpcTransaction>,
+        allow_orphan: bool,
+    ) -> RpcResult<Vec<RpcSubmitTransactionResult>> {
+        Ok(self.submit_transactions_call(SubmitTransactionsRequest::new(transactions, allow_orphan)).await?.results)
+    }
+    async fn submit_transactions_call(&self, request: SubmitTransactionsRequest) -> RpcResult<SubmitTransactionsResponse>;
+
+    /// Submits a list of transactions on behalf of the RPC connection identified by `submitter`.
+    /// See [`RpcApi::submit_transaction_from_call`].
+    async fn submit_transactions_from_call(
+        &self,
+        _submitter: String,
+        request: SubmitTransactionsRequest,
+    ) -> RpcResult<SubmitTransactionsResponse> {
+        self.submit_transactions_call(request).await
+    }
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -200,7 +256,8 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn shutdown_call(&self, request: ShutdownRequest) -> RpcResult<ShutdownResponse>;
 
-    /// Requests headers between the given `start_hash` and the current virtual, up to the given limit.
+    /// Requests the headers of up to `limit` selected chain blocks, starting at `start_hash` and going towards the sink
+    /// if `is_ascending` or towards the pruning point otherwise.
     async fn get_headers(&self, start_hash: RpcHash, limit: u64, is_ascending: bool) -> RpcResult<Vec<RpcHeader>> {
         Ok(self.get_headers_call(GetHeadersRequest::new(start_hash, limit, is_ascending)).await?.headers)
     }
@@ -237,7 +294,7 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn get_sink_blue_score_call(&self, request: GetSinkBlueScoreRequest) -> RpcResult<GetSinkBlueScoreResponse>;
 
-    /// Bans the given ip.
+    /// Bans the given ip for the node default duration.
     async fn ban(&self, ip: RpcIpAddress) -> RpcResult<()> {
         self.ban_call(BanRequest::new(ip)).await?;
         Ok(())
@@ -290,7 +347,10 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse>;
 
+    /// Returns the maximum and circulating supplies, along with the part of the circulating supply held by
+    /// provably unspendable outputs.
     ///
+    /// This call is only available when this node was started with `--utxoindex`.
     async fn get_coin_supply(&self) -> RpcResult<GetCoinSupplyResponse> {
         self.get_coin_supply_call(GetCoinSupplyRequest {}).await
     }
@@ -304,6 +364,205 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Computes the compute mass components of a transaction along with the applicable mass limits.
+    async fn get_transaction_mass(&self, transaction: RpcTransaction) -> RpcResult<GetTransactionMassResponse> {
+        self.get_transaction_mass_call(GetTransactionMassRequest::new(transaction)).await
+    }
+    async fn get_transaction_mass_call(&self, request: GetTransactionMassRequest) -> RpcResult<GetTransactionMassResponse>;
+
+    /// Selects UTXOs of `from_addresses` and builds an unsigned transaction paying `outputs`,
+    /// with the remainder sent to `change_address`.
+    async fn build_unsigned_transaction(
+        &self,
+        from_addresses: Vec<RpcAddress>,
+        outputs: Vec<RpcPaymentOutput>,
+        change_address: RpcAddress,
+    ) -> RpcResult<BuildUnsignedTransactionResponse> {
+        self.build_unsigned_transaction_call(BuildUnsignedTransactionRequest::new(from_addresses, outputs, change_address)).await
+    }
+    async fn build_unsigned_transaction_call(
+        &self,
+        request: BuildUnsignedTransactionRequest,
+    ) -> RpcResult<BuildUnsignedTransactionResponse>;
+
+    /// Computes structural DAG stats (tips, average mergeset size, red block rate and average parents)
+    /// over the last `window_size` selected chain blocks.
+    async fn get_dag_stats(&self, window_size: u32) -> RpcResult<GetDagStatsResponse> {
+        self.get_dag_stats_call(GetDagStatsRequest::new(window_size)).await
+    }
+    async fn get_dag_stats_call(&self, request: GetDagStatsRequest) -> RpcResult<GetDagStatsResponse>;
+
+    /// Returns the abnormal DAG conditions currently observed by the node health monitor.
+    async fn get_health(&self) -> RpcResult<GetHealthResponse> {
+        self.get_health_call(GetHealthRequest {}).await
+    }
+    async fn get_health_call(&self, request: GetHealthRequest) -> RpcResult<GetHealthResponse>;
+
+    /// Returns low, normal and priority feerate buckets computed from the current mempool congestion.
+    async fn get_fee_estimate(&self) -> RpcResult<RpcFeeEstimate> {
+        Ok(self.get_fee_estimate_call(GetFeeEstimateRequest {}).await?.estimate)
+    }
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse>;
+
+    /// Returns the notification scopes this connection is currently subscribed to, along with the size of the
+    /// server-side address set of a UtxosChanged subscription.
+    ///
+    /// Only meaningful to a client connected to the node through gRPC or wRPC.
+    async fn get_subscriptions(&self) -> RpcResult<Vec<RpcSubscription>> {
+        Ok(self.get_subscriptions_call(GetSubscriptionsRequest {}).await?.subscriptions)
+    }
+    async fn get_subscriptions_call(&self, request: GetSubscriptionsRequest) -> RpcResult<GetSubscriptionsResponse>;
+
+    /// Returns the current virtual parents along with the candidate tips excluded by the last virtual parent selection
+    /// and the reason of their exclusion (mergeset size limit, bounded merge depth...).
+    async fn get_virtual_parents(&self) -> RpcResult<GetVirtualParentsResponse> {
+        self.get_virtual_parents_call(GetVirtualParentsRequest {}).await
+    }
+    async fn get_virtual_parents_call(&self, request: GetVirtualParentsRequest) -> RpcResult<GetVirtualParentsResponse>;
+
+    /// Returns the consensus parameters of the network the node is running on.
+    async fn get_consensus_params(&self) -> RpcResult<RpcConsensusParams> {
+        Ok(self.get_consensus_params_call(GetConsensusParamsRequest {}).await?.params)
+    }
+    async fn get_consensus_params_call(&self, request: GetConsensusParamsRequest) -> RpcResult<GetConsensusParamsResponse>;
+
+    /// Returns the block containing a transaction accepted by the selected chain, its acceptance status
+    /// and its number of confirmations.
+    ///
+    /// Requires the node to run with the transaction index enabled (`--txindex`).
+    async fn get_transaction_by_id(&self, transaction_id: RpcTransactionId) -> RpcResult<GetTransactionByIdResponse> {
+        self.get_transaction_by_id_call(GetTransactionByIdRequest::new(transaction_id)).await
+    }
+    async fn get_transaction_by_id_call(&self, request: GetTransactionByIdRequest) -> RpcResult<GetTransactionByIdResponse>;
+
+    /// Samples `count` random selected chain blocks having a blue score in `[low_blue_score, high_blue_score]`, each block
+    /// being drawn, with replacement, with a probability proportional to the work it adds to the chain. The range must lie
+    /// above the pruning point.
+    ///
+    /// Returns the headers of the sampled blocks in ascending blue score order.
+    async fn sample_chain_blocks(&self, low_blue_score: u64, high_blue_score: u64, count: u32) -> RpcResult<Vec<RpcHeader>> {
+        Ok(self.sample_chain_blocks_call(SampleChainBlocksRequest::new(low_blue_score, high_blue_score, count)).await?.headers)
+    }
+    async fn sample_chain_blocks_call(&self, request: SampleChainBlocksRequest) -> RpcResult<SampleChainBlocksResponse>;
+
+    /// Requests the p2p connections of the node along with the bytes and messages exchanged over each of them.
+    async fn get_connections(&self) -> RpcResult<Vec<RpcConnectionInfo>> {
+        Ok(self.get_connections_call(GetConnectionsRequest {}).await?.connections)
+    }
+    async fn get_connections_call(&self, request: GetConnectionsRequest) -> RpcResult<GetConnectionsResponse>;
+
+    /// Requests the lowest selected chain block having a blue score greater than or equal to `blue_score`.
+    async fn get_block_by_blue_score(&self, blue_score: u64, verbosity: RpcBlockVerbosity) -> RpcResult<RpcBlock> {
+        Ok(self.get_block_by_blue_score_call(GetBlockByBlueScoreRequest::new(blue_score, verbosity)).await?.block)
+    }
+    async fn get_block_by_blue_score_call(&self, request: GetBlockByBlueScoreRequest) -> RpcResult<GetBlockByBlueScoreResponse>;
+
+    /// Requests the lowest selected chain block having a DAA score greater than or equal to `daa_score`.
+    async fn get_block_by_daa_score(&self, daa_score: u64, verbosity: RpcBlockVerbosity) -> RpcResult<RpcBlock> {
+        Ok(self.get_block_by_daa_score_call(GetBlockByDaaScoreRequest::new(daa_score, verbosity)).await?.block)
+    }
+    async fn get_block_by_daa_score_call(&self, request: GetBlockByDaaScoreRequest) -> RpcResult<GetBlockByDaaScoreResponse>;
+
+    /// Requests the selected chain block with the timestamp nearest to `timestamp`, expressed as a unix time in milliseconds.
+    /// Timestamps above the one of the sink resolve to the sink while timestamps below the one of the pruning point are rejected.
+    async fn get_chain_block_by_timestamp(&self, timestamp: u64, verbosity: RpcBlockVerbosity) -> RpcResult<RpcBlock> {
+        Ok(self.get_chain_block_by_timestamp_call(GetChainBlockByTimestampRequest::new(timestamp, verbosity)).await?.block)
+    }
+    async fn get_chain_block_by_timestamp_call(
+        &self,
+        request: GetChainBlockByTimestampRequest,
+    ) -> RpcResult<GetChainBlockByTimestampResponse>;
+
+    /// Requests a page of the UTXO set of the pruning point, in outpoint order, starting right after `cursor`.
+    /// Paging fails if the pruning point moves from `pruning_point`, which defaults to the current one.
+    async fn get_pruning_point_utxos(
+        &self,
+        pruning_point: Option<RpcHash>,
+        cursor: Option<RpcTransactionOutpoint>,
+        limit: Option<u32>,
+    ) -> RpcResult<GetPruningPointUtxosResponse> {
+        self.get_pruning_point_utxos_call(GetPruningPointUtxosRequest::new(pruning_point, cursor, limit)).await
+    }
+    async fn get_pruning_point_utxos_call(&self, request: GetPruningPointUtxosRequest) -> RpcResult<GetPruningPointUtxosResponse>;
+
+    /// Returns the mempool policy in force along with the number of transactions and orphans in the mempool.
+    async fn get_mempool_info(&self) -> RpcResult<GetMempoolInfoResponse> {
+        self.get_mempool_info_call(GetMempoolInfoRequest {}).await
+    }
+    async fn get_mempool_info_call(&self, request: GetMempoolInfoRequest) -> RpcResult<GetMempoolInfoResponse>;
+
+    /// Replaces the mempool policy, returning the policy in force afterwards.
+    ///
+    /// This call is only available in unsafe RPC mode.
+    async fn set_mempool_policy(&self, policy: RpcMempoolPolicy) -> RpcResult<RpcMempoolPolicy> {
+        Ok(self.set_mempool_policy_call(SetMempoolPolicyRequest::new(policy)).await?.policy)
+    }
+    async fn set_mempool_policy_call(&self, request: SetMempoolPolicyRequest) -> RpcResult<SetMempoolPolicyResponse>;
+
+    /// Requests whether a block is currently blue or red from the perspective of the virtual, along with the
+    /// selected chain block merging it.
+    async fn get_current_block_color(&self, hash: RpcHash) -> RpcResult<GetCurrentBlockColorResponse> {
+        self.get_current_block_color_call(GetCurrentBlockColorRequest::new(hash)).await
+    }
+    async fn get_current_block_color_call(&self, request: GetCurrentBlockColorRequest) -> RpcResult<GetCurrentBlockColorResponse>;
+
+    /// Requests the active bans along with their reason and remaining time.
+    async fn get_bans(&self) -> RpcResult<Vec<RpcBan>> {
+        Ok(self.get_bans_call(GetBansRequest {}).await?.bans)
+    }
+    async fn get_bans_call(&self, request: GetBansRequest) -> RpcResult<GetBansResponse>;
+
+    /// Requests the processing status of a block submitted without waiting for its processing.
+    async fn get_block_processing_status(&self, ticket: u64) -> RpcResult<GetBlockProcessingStatusResponse> {
+        self.get_block_processing_status_call(GetBlockProcessingStatusRequest::new(ticket)).await
+    }
+    async fn get_block_processing_status_call(
+        &self,
+        request: GetBlockProcessingStatusRequest,
+    ) -> RpcResult<GetBlockProcessingStatusResponse>;
+
+    /// Starts a long-running node operation in the background and returns the id of its job.
+    async fn start_job(&self, kind: RpcJobKind) -> RpcResult<u64> {
+        Ok(self.start_job_call(StartJobRequest::new(kind)).await?.job_id)
+    }
+    async fn start_job_call(&self, request: StartJobRequest) -> RpcResult<StartJobResponse>;
+
+    /// Requests the status of a job started by [`RpcApi::start_job`].
+    async fn get_job_status(&self, job_id: u64) -> RpcResult<RpcJob> {
+        Ok(self.get_job_status_call(GetJobStatusRequest::new(job_id)).await?.job)
+    }
+    async fn get_job_status_call(&self, request: GetJobStatusRequest) -> RpcResult<GetJobStatusResponse>;
+
+    /// Requests the per-event-type notification delivery statistics of the node notifiers.
+    async fn get_notification_stats(&self) -> RpcResult<Vec<RpcNotifierStats>> {
+        Ok(self.get_notification_stats_call(GetNotificationStatsRequest {}).await?.notifiers)
+    }
+    async fn get_notification_stats_call(&self, request: GetNotificationStatsRequest) -> RpcResult<GetNotificationStatsResponse>;
+
+    /// Mines `count` blocks paying to `pay_address` and returns their hashes once processed (simnet only).
+    async fn generate_blocks(&self, pay_address: RpcAddress, count: u64) -> RpcResult<Vec<RpcHash>> {
+        Ok(self.generate_blocks_call(GenerateBlocksRequest::new(pay_address, count)).await?.block_hashes)
+    }
+    async fn generate_blocks_call(&self, request: GenerateBlocksRequest) -> RpcResult<GenerateBlocksResponse>;
+
+    /// Requests the RPC methods and the notification types supported by the node.
+    async fn get_server_capabilities(&self) -> RpcResult<GetServerCapabilitiesResponse> {
+        self.get_server_capabilities_call(GetServerCapabilitiesRequest {}).await
+    }
+    async fn get_server_capabilities_call(&self, request: GetServerCapabilitiesRequest) -> RpcResult<GetServerCapabilitiesResponse>;
+
+    /// Adds or removes transaction ids and script public keys excluded from the block templates built by the node.
+    async fn update_block_template_exclusions_call(
+        &self,
+        request: UpdateBlockTemplateExclusionsRequest,
+    ) -> RpcResult<UpdateBlockTemplateExclusionsResponse>;
+
+    /// Returns the chain-quality samples taken after the unix timestamp `since` (in milliseconds), oldest first.
+    async fn get_chain_quality(&self, since: u64) -> RpcResult<Vec<RpcChainQualitySample>> {
+        Ok(self.get_chain_quality_call(GetChainQualityRequest { since }).await?.samples)
+    }
+    async fn get_chain_quality_call(&self, request: GetChainQualityRequest) -> RpcResult<GetChainQualityResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
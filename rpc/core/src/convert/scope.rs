@@ -1,7 +1,8 @@
 use crate::{
-    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
-    NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest, NotifyVirtualChainChangedRequest,
-    NotifyVirtualDaaScoreChangedRequest,
+    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyHealthAlertRequest, NotifyIndexResyncProgressRequest,
+    NotifyJobCompletedRequest, NotifyMempoolTransactionsEvictedRequest, NotifyNewBlockTemplateRequest,
+    NotifyPruningPointUtxoSetOverrideRequest, NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest,
+    NotifyVirtualChainChangedRequest, NotifyVirtualDaaScoreChangedRequest,
 };
 use kaspa_notify::scope::*;
 
@@ -54,9 +55,13 @@ impl From<&NotifyFinalityConflictRequest> for FinalityConflictResolvedScope {
     }
 }
 from!(item: UtxosChanged, {
-    Self::new(item.addresses.clone())
+    Self::new(item.addresses.clone()).with_replace(item.replace)
 });
 from!(SinkBlueScoreChanged);
 from!(VirtualDaaScoreChanged);
 from!(PruningPointUtxoSetOverride);
 from!(NewBlockTemplate);
+from!(HealthAlert);
+from!(MempoolTransactionsEvicted);
+from!(JobCompleted);
+from!(IndexResyncProgress);
@@ -4,7 +4,7 @@ use std::{net::AddrParseError, num::TryFromIntError};
 use thiserror::Error;
 use workflow_core::channel::ChannelError;
 
-use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
+use crate::{api::ctl::RpcState, RpcHash, RpcJobKind, RpcNetworkId, RpcTransactionId, SubmitBlockRejectReason};
 
 #[derive(Clone, Debug, Error)]
 pub enum RpcError {
@@ -50,6 +50,9 @@ pub enum RpcError {
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
+    #[error("Block {0} was not merged by the selected chain yet.")]
+    MergerNotFound(RpcHash),
+
     #[error("If includeTransactions is set, then includeBlockVerboseData must be set as well.")]
     InvalidGetBlocksRequest,
 
@@ -59,27 +62,93 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Cursor address {0} is not one of the requested addresses.")]
+    InvalidUtxosCursor(String),
+
+    #[error("Method unavailable. Run the node with the --enable-transaction-builder-rpc argument.")]
+    TransactionBuilderDisabled,
+
+    #[error("Method unavailable. Run the node with the --chain-quality argument.")]
+    NoChainQuality,
+
+    #[error("Insufficient funds: {0} sompi are required but only {1} sompi are spendable.")]
+    InsufficientFunds(u64, u64),
+
+    #[error("Transaction mass {0} is larger than max allowed size of {1}.")]
+    TransactionMassAboveMax(u64, u64),
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
+    #[error("Method unavailable. It must be called by a client connected through gRPC or wRPC.")]
+    ConnectionRequired,
+
     #[error("Requested window size {0} is larger than max {1} allowed in RPC safe mode.")]
     WindowSizeExceedingMaximum(u32, u32),
 
     #[error("Requested window size {0} is larger than pruning point depth {1}.")]
     WindowSizeExceedingPruningDepth(u32, u64),
 
+    #[error("Requested min window size {0} is lower than the min {1} configured by the node.")]
+    MinWindowSizeBelowConfigured(u32, usize),
+
+    #[error("Invalid peer addresses filter: {0}")]
+    InvalidPeerAddressesFilter(String),
+
+    #[error("Requested sample size {0} is larger than max {1} allowed in RPC safe mode.")]
+    SampleSizeExceedingMaximum(u32, u32),
+
+    #[error("Request field {0} holds {1} items, more than the max of {2} allowed per request.")]
+    RequestSizeExceedingMaximum(String, usize, usize),
+
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable on a read-only secondary instance.")]
+    UnavailableOnSecondaryInstance,
+
+    #[error("Method unavailable. It is only available on simnet.")]
+    UnavailableOutsideSimnet,
+
+    #[error("Method {0} is not allowed on this RPC interface.")]
+    MethodNotAllowed(String),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
     #[error("IP {0} is not registered as banned.")]
     IpIsNotBanned(IpAddress),
 
+    #[error("Invalid IP network: {0}")]
+    InvalidIpNetwork(String),
+
+    #[error("Cannot ban network {0} because IP {1} has some permanent connection.")]
+    NetworkHasPermanentConnection(String, IpAddress),
+
+    #[error("Network {0} is not registered as banned.")]
+    NetworkIsNotBanned(String),
+
     #[error("Block was not submitted: {0}")]
     SubmitBlockError(SubmitBlockRejectReason),
 
+    #[error("Block processing ticket {0} is unknown or expired.")]
+    BlockProcessingTicketNotFound(u64),
+
+    #[error("Job {0} is unknown or expired.")]
+    JobNotFound(u64),
+
+    #[error("Job kind {0} is unavailable on this node.")]
+    JobKindUnavailable(RpcJobKind),
+
+    #[error("A {0} job is already running.")]
+    JobAlreadyRunning(RpcJobKind),
+
+    #[error("Network mismatch - expected: {0}, node: {1}")]
+    NetworkMismatch(RpcNetworkId, RpcNetworkId),
+
     #[error(transparent)]
     AddressError(#[from] kaspa_addresses::AddressError),
 
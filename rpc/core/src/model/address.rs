@@ -13,6 +13,20 @@ pub struct RpcUtxosByAddressesEntry {
     pub utxo_entry: RpcUtxoEntry,
 }
 
+/// Position of a UTXO entry in the ordered result of the `GetUtxosByAddresses` RPC, from which the next page starts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcUtxosByAddressesCursor {
+    pub address: RpcAddress,
+    pub outpoint: RpcTransactionOutpoint,
+}
+
+impl RpcUtxosByAddressesCursor {
+    pub fn new(address: RpcAddress, outpoint: RpcTransactionOutpoint) -> Self {
+        Self { address, outpoint }
+    }
+}
+
 /// Represents a balance of an address returned by the `GetBalancesByAddresses` RPC.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,3 +36,17 @@ pub struct RpcBalancesByAddressesEntry {
     /// Balance of `address` if available
     pub balance: Option<u64>,
 }
+
+/// Represents a payment to an address requested from the `BuildUnsignedTransaction` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPaymentOutput {
+    pub address: RpcAddress,
+    pub amount: u64,
+}
+
+impl RpcPaymentOutput {
+    pub fn new(address: RpcAddress, amount: u64) -> Self {
+        Self { address, amount }
+    }
+}
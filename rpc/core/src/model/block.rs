@@ -2,6 +2,41 @@ use crate::prelude::{RpcHash, RpcHeader, RpcTransaction};
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+/// Ordering of the transactions of a block template
+pub type RpcTemplateTxOrdering = kaspa_consensus_core::block::TemplateTxOrdering;
+
+/// Level of detail of the blocks returned by `GetBlock` and `GetBlocks`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcBlockVerbosity {
+    /// The block header along with its verbose data, without transaction ids. The block body is not fetched
+    HeaderOnly = 0,
+    /// The block header along with its verbose data, including the ids of the block transactions
+    #[default]
+    TransactionIds = 1,
+    /// The block header and verbose data along with the full block transactions and their verbose data
+    Full = 2,
+}
+
+impl RpcBlockVerbosity {
+    /// The verbosity matching the legacy `include_transactions` flag of the block requests
+    pub fn from_include_transactions(include_transactions: bool) -> Self {
+        if include_transactions {
+            RpcBlockVerbosity::Full
+        } else {
+            RpcBlockVerbosity::TransactionIds
+        }
+    }
+
+    pub fn includes_transaction_ids(&self) -> bool {
+        matches!(self, RpcBlockVerbosity::TransactionIds | RpcBlockVerbosity::Full)
+    }
+
+    pub fn includes_transactions(&self) -> bool {
+        matches!(self, RpcBlockVerbosity::Full)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcBlock {
@@ -23,6 +58,9 @@ pub struct RpcBlockVerboseData {
     pub merge_set_blues_hashes: Vec<RpcHash>,
     pub merge_set_reds_hashes: Vec<RpcHash>,
     pub is_chain_block: bool,
+    /// Virtual DAA score minus the DAA score of the chain block accepting the block, zero if not accepted yet
+    #[serde(default)]
+    pub confirmations: u64,
 }
 
 cfg_if::cfg_if! {
@@ -58,6 +96,7 @@ cfg_if::cfg_if! {
             mergeSetBluesHashes: HexString[];
             mergeSetRedsHashes: HexString[];
             isChainBlock: boolean;
+            confirmations: number;
         }
         "#;
     }
@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable description of a node build, allowing deployment tooling to gate on concrete capabilities
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBuildInfo {
+    /// Semantic version of the node
+    pub version: String,
+    /// Git commit the node was built from, if known
+    pub git_hash: Option<String>,
+    /// UTC date of the build as `YYYY-MM-DD`, if known
+    pub build_date: Option<String>,
+    /// Cargo features the node was built with
+    pub features: Vec<String>,
+    /// P2P protocol versions the node can speak with its peers
+    pub protocol_versions: Vec<u32>,
+    /// Version of the RPC API, see [`crate::api::ops::RPC_API_VERSION`]
+    pub rpc_api_version: [u16; 4],
+    /// Names of the RPC methods supported by the node
+    pub capabilities: Vec<String>,
+}
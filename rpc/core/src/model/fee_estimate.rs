@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// A feerate, in sompi per gram, along with the expected time for a transaction paying it to get into a block
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+/// Feerates a transaction should pay depending on the urgency of its inclusion, given the current mempool congestion
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimate {
+    /// Feerate for getting into the next block
+    pub priority_bucket: RpcFeerateBucket,
+    /// Feerate for getting into a block within about a minute
+    pub normal_bucket: RpcFeerateBucket,
+    /// Feerate for getting into a block within about an hour
+    pub low_bucket: RpcFeerateBucket,
+}
@@ -0,0 +1,75 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Kind of abnormal DAG condition detected by the node health monitor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcHealthAlertKind {
+    /// The number of DAG tips is above threshold, hinting at a wide fork or a flood of parallel blocks
+    TipCountAboveThreshold = 0,
+    /// The rate of red blocks over a window of recent chain blocks is above threshold
+    RedRateSpike = 1,
+    /// The blue work of the sink did not increase for a while, hinting at a network stall or a local partition
+    BlueWorkStagnation = 2,
+}
+
+impl std::fmt::Display for RpcHealthAlertKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcHealthAlertKind::TipCountAboveThreshold => "tip count above threshold",
+            RpcHealthAlertKind::RedRateSpike => "red rate spike",
+            RpcHealthAlertKind::BlueWorkStagnation => "blue work stagnation",
+        };
+        f.write_str(s)
+    }
+}
+
+/// An abnormal DAG condition currently observed by the node
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHealthAlert {
+    pub kind: RpcHealthAlertKind,
+    /// Observed value of the monitored metric (tip count, red rate or stagnation duration in seconds)
+    pub value: f64,
+    /// Threshold the observed value crossed
+    pub threshold: f64,
+    /// Unix timestamp in milliseconds at which the alert was raised
+    pub raised_at: u64,
+}
+
+impl RpcHealthAlert {
+    pub fn new(kind: RpcHealthAlertKind, value: f64, threshold: f64, raised_at: u64) -> Self {
+        Self { kind, value, threshold, raised_at }
+    }
+}
+
+impl std::fmt::Display for RpcHealthAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:.2} > {:.2})", self.kind, self.value, self.threshold)
+    }
+}
+
+/// Chain-quality metrics of the blocks accepted by the selected chain during a sampling interval
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcChainQualitySample {
+    /// Unix timestamp in milliseconds at which the sample was taken
+    pub timestamp: u64,
+    /// Number of blocks added to the selected chain during the interval
+    pub chain_block_count: u64,
+    /// Number of blocks merged by these chain blocks
+    pub block_count: u64,
+    /// Number of merged blocks which are red
+    pub red_block_count: u64,
+    /// Rate of red blocks among the merged blocks
+    pub red_rate: f64,
+    /// Average block propagation delay in milliseconds, inferred from the mergeset sizes and the block rate
+    /// given by the block timestamps
+    pub average_propagation_delay: u64,
+    /// Number of distinct miners, identified by the script public key of their coinbase payload
+    pub miner_count: u64,
+    /// Share of the merged blocks mined by the most productive miner
+    pub top_miner_share: f64,
+    /// Herfindahl-Hirschman index of the miner shares, from close to 0 for a spread hashrate to 1 for a single miner
+    pub miner_concentration: f64,
+}
@@ -0,0 +1,144 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Kind of long-running node operation which can be started as a job
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcJobKind {
+    /// Deletes and rebuilds the UTXO index from the consensus virtual UTXO set
+    UtxoIndexResync = 0,
+    /// Catches the transaction index up with the selected chain, rebuilding it if needed
+    TxIndexResync = 1,
+}
+
+impl std::fmt::Display for RpcJobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcJobKind::UtxoIndexResync => "utxoindex resync",
+            RpcJobKind::TxIndexResync => "txindex resync",
+        };
+        f.write_str(s)
+    }
+}
+
+impl std::str::FromStr for RpcJobKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "utxoindex-resync" => Ok(RpcJobKind::UtxoIndexResync),
+            "txindex-resync" => Ok(RpcJobKind::TxIndexResync),
+            _ => Err(format!("unknown job kind {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcJobState {
+    Running = 0,
+    Completed = 1,
+    Failed = 2,
+}
+
+impl std::fmt::Display for RpcJobState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcJobState::Running => "running",
+            RpcJobState::Completed => "completed",
+            RpcJobState::Failed => "failed",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Status of a long-running operation started by [`crate::StartJobRequest`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcJob {
+    pub id: u64,
+    pub kind: RpcJobKind,
+    pub state: RpcJobState,
+    /// Unix timestamp in milliseconds at which the job started
+    pub started_at: u64,
+    /// Unix timestamp in milliseconds at which the job completed or failed
+    pub finished_at: Option<u64>,
+    /// Reason of the failure of a failed job
+    pub error: Option<String>,
+}
+
+impl RpcJob {
+    pub fn new(id: u64, kind: RpcJobKind, started_at: u64) -> Self {
+        Self { id, kind, state: RpcJobState::Running, started_at, finished_at: None, error: None }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state != RpcJobState::Running
+    }
+}
+
+/// Progress of a running index resync, whether started as a job, on node startup or on a consensus reset
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcIndexResyncProgress {
+    pub kind: RpcJobKind,
+    /// Unix timestamp in milliseconds at which the resync started
+    pub started_at: u64,
+    /// Number of items (utxos or chain blocks) committed so far
+    pub processed: u64,
+    /// Estimated completion percentage
+    pub percent: f64,
+    /// Estimated remaining duration in seconds, unknown until some progress is made
+    pub eta: Option<u64>,
+}
+
+impl RpcIndexResyncProgress {
+    pub fn new(kind: RpcJobKind, started_at: u64, processed: u64, percent: f64, eta: Option<u64>) -> Self {
+        Self { kind, started_at, processed, percent, eta }
+    }
+}
+
+impl std::fmt::Display for RpcIndexResyncProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:.1}%", self.kind, self.percent)?;
+        if let Some(eta) = self.eta {
+            write!(f, " (eta {eta}s)")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RpcJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "job {} ({}) {}", self.id, self.kind, self.state)?;
+        if let Some(error) = self.error.as_ref() {
+            write!(f, ": {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_display() {
+        assert_eq!("utxoindex-resync".parse::<RpcJobKind>(), Ok(RpcJobKind::UtxoIndexResync));
+        assert_eq!("txindex-resync".parse::<RpcJobKind>(), Ok(RpcJobKind::TxIndexResync));
+        assert!("resync".parse::<RpcJobKind>().is_err());
+
+        let mut job = RpcJob::new(7, RpcJobKind::TxIndexResync, 1000);
+        assert!(!job.is_finished());
+        assert_eq!(job.to_string(), "job 7 (txindex resync) running");
+        job.state = RpcJobState::Failed;
+        job.error = Some("txindex is disabled".to_owned());
+        assert!(job.is_finished());
+        assert_eq!(job.to_string(), "job 7 (txindex resync) failed: txindex is disabled");
+
+        let progress = RpcIndexResyncProgress::new(RpcJobKind::UtxoIndexResync, 1000, 50, 12.345, None);
+        assert_eq!(progress.to_string(), "utxoindex resync 12.3%");
+        let progress = RpcIndexResyncProgress { eta: Some(60), ..progress };
+        assert_eq!(progress.to_string(), "utxoindex resync 12.3% (eta 60s)");
+    }
+}
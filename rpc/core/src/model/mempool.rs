@@ -8,11 +8,16 @@ pub struct RpcMempoolEntry {
     pub fee: u64,
     pub transaction: RpcTransaction,
     pub is_orphan: bool,
+    /// Mass of the transaction, including the storage mass once known (the compute mass only for orphans)
+    pub mass: u64,
+    /// Fee per gram of mass, 0 if the mass is unknown
+    pub feerate: f64,
 }
 
 impl RpcMempoolEntry {
-    pub fn new(fee: u64, transaction: RpcTransaction, is_orphan: bool) -> Self {
-        Self { fee, transaction, is_orphan }
+    pub fn new(fee: u64, transaction: RpcTransaction, is_orphan: bool, mass: u64) -> Self {
+        let feerate = if mass > 0 { fee as f64 / mass as f64 } else { 0.0 };
+        Self { fee, transaction, is_orphan, mass, feerate }
     }
 }
 
@@ -29,6 +34,18 @@ impl RpcMempoolEntryByAddress {
     }
 }
 
+/// Mempool parameters adjustable while the node is running
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolPolicy {
+    /// Time after which a low-priority transaction not included in a block expires
+    pub transaction_expire_interval_seconds: u64,
+    /// Time after which an orphan transaction expires
+    pub orphan_expire_interval_seconds: u64,
+    pub maximum_transaction_count: u64,
+    pub maximum_orphan_transaction_count: u64,
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -44,7 +61,54 @@ cfg_if::cfg_if! {
                 fee : bigint;
                 transaction : ITransaction;
                 isOrphan : boolean;
+                mass : bigint;
+                feerate : number;
             }
         "#;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_MEMPOOL_POLICY: &'static str = r#"
+            /**
+             * Mempool parameters adjustable while the node is running.
+             * 
+             * @category Node RPC
+             */
+            export interface IMempoolPolicy {
+                transactionExpireIntervalSeconds : bigint;
+                orphanExpireIntervalSeconds : bigint;
+                maximumTransactionCount : bigint;
+                maximumOrphanTransactionCount : bigint;
+            }
+        "#;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+
+    fn transaction() -> RpcTransaction {
+        RpcTransaction {
+            version: 0,
+            inputs: vec![],
+            outputs: vec![],
+            lock_time: 0,
+            subnetwork_id: SUBNETWORK_ID_NATIVE,
+            gas: 0,
+            payload: vec![],
+            mass: 0,
+            verbose_data: None,
+        }
+    }
+
+    #[test]
+    fn test_mempool_entry_feerate() {
+        let entry = RpcMempoolEntry::new(5000, transaction(), false, 2000);
+        assert_eq!((entry.mass, entry.feerate), (2000, 2.5));
+
+        // The feerate of a transaction of unknown mass is unknown as well
+        let entry = RpcMempoolEntry::new(5000, transaction(), true, 0);
+        assert_eq!((entry.mass, entry.feerate), (0, 0.0));
     }
 }
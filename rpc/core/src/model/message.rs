@@ -2,9 +2,10 @@ use crate::model::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_consensus_core::api::stats::BlockCount;
 use kaspa_core::debug;
-use kaspa_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
+use kaspa_notify::subscription::{batch::UtxosChangedBatch, context::SubscriptionContext, single::UtxosChangedSubscription, Command};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashSet,
     fmt::{Display, Formatter},
     sync::Arc,
 };
@@ -21,10 +22,19 @@ pub struct SubmitBlockRequest {
     pub block: RpcBlock,
     #[serde(alias = "allowNonDAABlocks")]
     pub allow_non_daa_blocks: bool,
+    /// Enqueues the block for processing and returns a ticket right away instead of waiting for the block
+    /// validation. The processing outcome is then available through [`GetBlockProcessingStatusRequest`].
+    #[serde(default)]
+    pub allow_non_blocking: bool,
 }
 impl SubmitBlockRequest {
     pub fn new(block: RpcBlock, allow_non_daa_blocks: bool) -> Self {
-        Self { block, allow_non_daa_blocks }
+        Self { block, allow_non_daa_blocks, allow_non_blocking: false }
+    }
+
+    pub fn with_allow_non_blocking(mut self, allow_non_blocking: bool) -> Self {
+        self.allow_non_blocking = allow_non_blocking;
+        self
     }
 }
 
@@ -51,7 +61,7 @@ impl Display for SubmitBlockRejectReason {
     }
 }
 
-#[derive(Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "lowercase")]
 #[serde(tag = "type", content = "reason")]
 pub enum SubmitBlockReport {
@@ -68,6 +78,21 @@ impl SubmitBlockReport {
 #[serde(rename_all = "camelCase")]
 pub struct SubmitBlockResponse {
     pub report: SubmitBlockReport,
+    /// Ticket of a block submitted with `allow_non_blocking`, in which case `report` only tells whether the block
+    /// was enqueued for processing
+    #[serde(default)]
+    pub ticket: Option<u64>,
+}
+
+impl SubmitBlockResponse {
+    pub fn new(report: SubmitBlockReport) -> Self {
+        Self { report, ticket: None }
+    }
+
+    pub fn with_ticket(mut self, ticket: u64) -> Self {
+        self.ticket = Some(ticket);
+        self
+    }
 }
 
 /// GetBlockTemplateRequest requests a current block template.
@@ -98,6 +123,11 @@ pub struct GetBlockTemplateResponse {
     /// That is because when kaspad isn't in sync with the rest of the network there's a high
     /// chance the block will never be accepted, thus the solving effort would have been wasted.
     pub is_synced: bool,
+
+    /// The ordering policy applied to the template transactions.
+    /// Under `deterministic`, nodes sharing the same selected transactions produce the same template body.
+    #[serde(default)]
+    pub tx_ordering: RpcTemplateTxOrdering,
 }
 
 /// GetBlockRequest requests information about a specific block
@@ -109,10 +139,23 @@ pub struct GetBlockRequest {
 
     /// Whether to include transaction data in the response
     pub include_transactions: bool,
+
+    /// Level of detail of the returned block, superseding `include_transactions` when set
+    #[serde(default)]
+    pub verbosity: Option<RpcBlockVerbosity>,
 }
 impl GetBlockRequest {
     pub fn new(hash: RpcHash, include_transactions: bool) -> Self {
-        Self { hash, include_transactions }
+        Self { hash, include_transactions, verbosity: None }
+    }
+
+    pub fn with_verbosity(self, verbosity: RpcBlockVerbosity) -> Self {
+        Self { include_transactions: verbosity.includes_transactions(), verbosity: Some(verbosity), ..self }
+    }
+
+    /// Level of detail of the returned block, falling back to `include_transactions` when `verbosity` is not set
+    pub fn block_verbosity(&self) -> RpcBlockVerbosity {
+        self.verbosity.unwrap_or(RpcBlockVerbosity::from_include_transactions(self.include_transactions))
     }
 }
 
@@ -137,6 +180,9 @@ pub struct GetInfoResponse {
     pub is_synced: bool,
     pub has_notify_command: bool,
     pub has_message_id: bool,
+    /// Machine-readable description of the node build
+    #[serde(default)]
+    pub build_info: RpcBuildInfo,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -147,28 +193,69 @@ pub struct GetCurrentNetworkRequest {}
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentNetworkResponse {
     pub network: RpcNetworkType,
+    /// Full network identifier including the suffix, as in `testnet-10`, missing if reported by an older node
+    #[serde(default)]
+    pub network_id: Option<RpcNetworkId>,
 }
 
 impl GetCurrentNetworkResponse {
-    pub fn new(network: RpcNetworkType) -> Self {
-        Self { network }
+    pub fn new(network_id: RpcNetworkId) -> Self {
+        Self { network: network_id.network_type(), network_id: Some(network_id) }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+/// GetPeerAddressesRequest requests the known and banned addresses of the node, optionally filtered and paginated.
+///
+/// Filters apply to both lists, except `recent_only` which applies to known addresses only. Pagination applies
+/// to each list independently, in address order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct GetPeerAddressesRequest {}
+pub struct GetPeerAddressesRequest {
+    /// Return the banned addresses only, leaving the known addresses empty
+    #[serde(default)]
+    pub banned_only: bool,
+    /// Return only the known addresses advertised or connected to within the last few hours
+    #[serde(default)]
+    pub recent_only: bool,
+    #[serde(default)]
+    pub ipv4_only: bool,
+    #[serde(default)]
+    pub ipv6_only: bool,
+    /// Return only the addresses within this network, in CIDR notation (e.g. `192.168.0.0/16`)
+    #[serde(default)]
+    pub network: Option<String>,
+    /// Number of matching addresses to skip
+    #[serde(default)]
+    pub offset: u32,
+    /// Max number of addresses to return per list
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetPeerAddressesResponse {
     pub known_addresses: Vec<RpcPeerAddress>,
     pub banned_addresses: Vec<RpcIpAddress>,
+    /// Number of known addresses matching the filters, regardless of pagination
+    #[serde(default)]
+    pub total_known_addresses: u64,
+    /// Number of banned addresses matching the filters, regardless of pagination
+    #[serde(default)]
+    pub total_banned_addresses: u64,
 }
 
 impl GetPeerAddressesResponse {
     pub fn new(known_addresses: Vec<RpcPeerAddress>, banned_addresses: Vec<RpcIpAddress>) -> Self {
-        Self { known_addresses, banned_addresses }
+        let total_known_addresses = known_addresses.len() as u64;
+        let total_banned_addresses = banned_addresses.len() as u64;
+        Self { known_addresses, banned_addresses, total_known_addresses, total_banned_addresses }
+    }
+
+    pub fn with_totals(mut self, total_known_addresses: u64, total_banned_addresses: u64) -> Self {
+        self.total_known_addresses = total_known_addresses;
+        self.total_banned_addresses = total_banned_addresses;
+        self
     }
 }
 
@@ -221,11 +308,41 @@ pub struct GetMempoolEntriesRequest {
     pub include_orphan_pool: bool,
     // TODO: replace with `include_transaction_pool`
     pub filter_transaction_pool: bool,
+    /// Include only transactions with a fee rate (in sompi per gram of mass) at least equal to this value
+    #[serde(default)]
+    pub min_fee_rate: Option<f64>,
+    /// Include only transactions added to the mempool at most this many DAA scores ago
+    #[serde(default)]
+    pub max_age_daa_score: Option<u64>,
+    /// Include only transactions having an output spendable by one of these addresses (disabled if empty)
+    #[serde(default)]
+    pub spendable_by_addresses: Vec<RpcAddress>,
 }
 
 impl GetMempoolEntriesRequest {
     pub fn new(include_orphan_pool: bool, filter_transaction_pool: bool) -> Self {
-        Self { include_orphan_pool, filter_transaction_pool }
+        Self {
+            include_orphan_pool,
+            filter_transaction_pool,
+            min_fee_rate: None,
+            max_age_daa_score: None,
+            spendable_by_addresses: vec![],
+        }
+    }
+
+    pub fn with_min_fee_rate(mut self, min_fee_rate: f64) -> Self {
+        self.min_fee_rate = Some(min_fee_rate);
+        self
+    }
+
+    pub fn with_max_age_daa_score(mut self, max_age_daa_score: u64) -> Self {
+        self.max_age_daa_score = Some(max_age_daa_score);
+        self
+    }
+
+    pub fn with_spendable_by_addresses(mut self, addresses: Vec<RpcAddress>) -> Self {
+        self.spendable_by_addresses = addresses;
+        self
     }
 }
 
@@ -279,11 +396,15 @@ pub struct AddPeerResponse {}
 pub struct SubmitTransactionRequest {
     pub transaction: RpcTransaction,
     pub allow_orphan: bool,
+    /// Replace the mempool transactions double spent by the transaction, along with their redeemers,
+    /// if it pays a sufficiently higher feerate (replace by fee)
+    #[serde(default)]
+    pub replace: bool,
 }
 
 impl SubmitTransactionRequest {
-    pub fn new(transaction: RpcTransaction, allow_orphan: bool) -> Self {
-        Self { transaction, allow_orphan }
+    pub fn new(transaction: RpcTransaction, allow_orphan: bool, replace: bool) -> Self {
+        Self { transaction, allow_orphan, replace }
     }
 }
 
@@ -299,6 +420,33 @@ impl SubmitTransactionResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionsRequest {
+    /// Transactions, possibly chained, in any order
+    pub transactions: Vec<RpcTransaction>,
+    pub allow_orphan: bool,
+}
+
+impl SubmitTransactionsRequest {
+    pub fn new(transactions: Vec<RpcTransaction>, allow_orphan: bool) -> Self {
+        Self { transactions, allow_orphan }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionsResponse {
+    /// A result per submitted transaction, in submission order
+    pub results: Vec<RpcSubmitTransactionResult>,
+}
+
+impl SubmitTransactionsResponse {
+    pub fn new(results: Vec<RpcSubmitTransactionResult>) -> Self {
+        Self { results }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkRequest {
@@ -328,20 +476,39 @@ impl GetSubnetworkResponse {
 pub struct GetVirtualChainFromBlockRequest {
     pub start_hash: RpcHash,
     pub include_accepted_transaction_ids: bool,
+    /// Maximum number of added chain blocks returned in a single batch, all of them if `None`
+    #[serde(default)]
+    pub max_blocks: Option<u32>,
 }
 
 impl GetVirtualChainFromBlockRequest {
     pub fn new(start_hash: RpcHash, include_accepted_transaction_ids: bool) -> Self {
-        Self { start_hash, include_accepted_transaction_ids }
+        Self { start_hash, include_accepted_transaction_ids, max_blocks: None }
+    }
+
+    pub fn with_max_blocks(mut self, max_blocks: u32) -> Self {
+        self.max_blocks = Some(max_blocks);
+        self
     }
 }
 
+/// The virtual chain path from the requested start hash.
+///
+/// When the request sets `max_blocks`, the chain path may be returned in batches: all the removed chain
+/// blocks are returned in the first batch, while the added chain blocks (and their accepted transaction ids)
+/// are truncated to `max_blocks`. In that case `next_start_hash` is set to the last added chain block and
+/// must be used as start hash of the request of the next batch. A reorg occurring between two batches is
+/// reported by the removed chain blocks of the next batch. `next_start_hash` is `None` once the path reaches
+/// the sink.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetVirtualChainFromBlockResponse {
     pub removed_chain_block_hashes: Vec<RpcHash>,
     pub added_chain_block_hashes: Vec<RpcHash>,
     pub accepted_transaction_ids: Vec<RpcAcceptedTransactionIds>,
+    /// Start hash of the next batch, if the chain path was truncated
+    #[serde(default)]
+    pub next_start_hash: Option<RpcHash>,
 }
 
 impl GetVirtualChainFromBlockResponse {
@@ -350,7 +517,12 @@ impl GetVirtualChainFromBlockResponse {
         added_chain_block_hashes: Vec<RpcHash>,
         accepted_transaction_ids: Vec<RpcAcceptedTransactionIds>,
     ) -> Self {
-        Self { removed_chain_block_hashes, added_chain_block_hashes, accepted_transaction_ids }
+        Self { removed_chain_block_hashes, added_chain_block_hashes, accepted_transaction_ids, next_start_hash: None }
+    }
+
+    pub fn with_next_start_hash(mut self, next_start_hash: Option<RpcHash>) -> Self {
+        self.next_start_hash = next_start_hash;
+        self
     }
 }
 
@@ -360,11 +532,23 @@ pub struct GetBlocksRequest {
     pub low_hash: Option<RpcHash>,
     pub include_blocks: bool,
     pub include_transactions: bool,
+    /// Level of detail of the returned blocks if included, superseding `include_transactions` when set
+    #[serde(default)]
+    pub verbosity: Option<RpcBlockVerbosity>,
 }
 
 impl GetBlocksRequest {
     pub fn new(low_hash: Option<RpcHash>, include_blocks: bool, include_transactions: bool) -> Self {
-        Self { low_hash, include_blocks, include_transactions }
+        Self { low_hash, include_blocks, include_transactions, verbosity: None }
+    }
+
+    pub fn with_verbosity(self, verbosity: RpcBlockVerbosity) -> Self {
+        Self { include_transactions: verbosity.includes_transactions(), verbosity: Some(verbosity), ..self }
+    }
+
+    /// Level of detail of the returned blocks, falling back to `include_transactions` when `verbosity` is not set
+    pub fn block_verbosity(&self) -> RpcBlockVerbosity {
+        self.verbosity.unwrap_or(RpcBlockVerbosity::from_include_transactions(self.include_transactions))
     }
 }
 
@@ -552,11 +736,27 @@ impl GetSinkBlueScoreResponse {
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesRequest {
     pub addresses: Vec<RpcAddress>,
+    /// Start the page right after this entry, as returned in the `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<RpcUtxosByAddressesCursor>,
+    /// Return at most this many entries, all of them if `None`. A zero limit is treated as 1
+    #[serde(default)]
+    pub limit: Option<u32>,
 }
 
 impl GetUtxosByAddressesRequest {
     pub fn new(addresses: Vec<RpcAddress>) -> Self {
-        Self { addresses }
+        Self { addresses, cursor: None, limit: None }
+    }
+
+    pub fn with_cursor(mut self, cursor: Option<RpcUtxosByAddressesCursor>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
     }
 }
 
@@ -564,11 +764,19 @@ impl GetUtxosByAddressesRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetUtxosByAddressesResponse {
     pub entries: Vec<RpcUtxosByAddressesEntry>,
+    /// Cursor of the next page when the page is full, `None` when all entries were returned
+    #[serde(default)]
+    pub next_cursor: Option<RpcUtxosByAddressesCursor>,
 }
 
 impl GetUtxosByAddressesResponse {
     pub fn new(entries: Vec<RpcUtxosByAddressesEntry>) -> Self {
-        Self { entries }
+        Self { entries, next_cursor: None }
+    }
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<RpcUtxosByAddressesCursor>) -> Self {
+        self.next_cursor = next_cursor;
+        self
     }
 }
 
@@ -576,11 +784,34 @@ impl GetUtxosByAddressesResponse {
 #[serde(rename_all = "camelCase")]
 pub struct BanRequest {
     pub ip: RpcIpAddress,
+    /// Ban duration in milliseconds, the node default applying if `None`
+    #[serde(default)]
+    pub duration: Option<u64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// When set, bans the whole `ip/prefix_length` network (CIDR notation) instead of the single `ip`
+    #[serde(default)]
+    pub prefix_length: Option<u8>,
 }
 
 impl BanRequest {
     pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+        Self { ip, duration: None, reason: None, prefix_length: None }
+    }
+
+    pub fn with_prefix_length(mut self, prefix_length: u8) -> Self {
+        self.prefix_length = Some(prefix_length);
+        self
+    }
+
+    pub fn with_duration(mut self, duration: u64) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
     }
 }
 
@@ -592,11 +823,19 @@ pub struct BanResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct UnbanRequest {
     pub ip: RpcIpAddress,
+    /// When set, lifts the ban of the whole `ip/prefix_length` network instead of the single `ip`
+    #[serde(default)]
+    pub prefix_length: Option<u8>,
 }
 
 impl UnbanRequest {
     pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+        Self { ip, prefix_length: None }
+    }
+
+    pub fn with_prefix_length(mut self, prefix_length: u8) -> Self {
+        self.prefix_length = Some(prefix_length);
+        self
     }
 }
 
@@ -609,11 +848,20 @@ pub struct UnbanResponse {}
 pub struct EstimateNetworkHashesPerSecondRequest {
     pub window_size: u32,
     pub start_hash: Option<RpcHash>,
+    /// Lowest number of blocks the window must hold, bounded below by the node configuration.
+    /// The node configured value applies if `None`
+    #[serde(default)]
+    pub min_window_size: Option<u32>,
 }
 
 impl EstimateNetworkHashesPerSecondRequest {
     pub fn new(window_size: u32, start_hash: Option<RpcHash>) -> Self {
-        Self { window_size, start_hash }
+        Self { window_size, start_hash, min_window_size: None }
+    }
+
+    pub fn with_min_window_size(mut self, min_window_size: u32) -> Self {
+        self.min_window_size = Some(min_window_size);
+        self
     }
 }
 
@@ -665,11 +913,14 @@ pub struct GetCoinSupplyRequest {}
 pub struct GetCoinSupplyResponse {
     pub max_sompi: u64,
     pub circulating_sompi: u64,
+    /// Part of the circulating supply held by provably unspendable outputs
+    #[serde(default)]
+    pub burned_sompi: u64,
 }
 
 impl GetCoinSupplyResponse {
-    pub fn new(max_sompi: u64, circulating_sompi: u64) -> Self {
-        Self { max_sompi, circulating_sompi }
+    pub fn new(max_sompi: u64, circulating_sompi: u64, burned_sompi: u64) -> Self {
+        Self { max_sompi, circulating_sompi, burned_sompi }
     }
 }
 
@@ -690,6 +941,10 @@ pub struct GetMetricsRequest {
     pub connection_metrics: bool,
     pub bandwidth_metrics: bool,
     pub consensus_metrics: bool,
+    #[serde(default)]
+    pub storage_metrics: bool,
+    #[serde(default)]
+    pub request_metrics: bool,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -754,6 +1009,114 @@ pub struct ConsensusMetrics {
     pub network_virtual_daa_score: u64,
 }
 
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnFamilyMetrics {
+    pub name: String,
+    pub sst_files_size: u64,
+    pub live_data_size: u64,
+    pub mem_tables_size: u64,
+    pub estimated_keys: u64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseMetrics {
+    pub name: String,
+    pub column_families: Vec<ColumnFamilyMetrics>,
+    pub running_compactions: u64,
+    pub compaction_pending: bool,
+    pub pending_compaction_bytes: u64,
+    pub block_cache_usage: u64,
+    pub block_cache_capacity: u64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMetrics {
+    /// Disk usage of the data directories, in bytes
+    pub data_dir_size: u64,
+    /// Reads served by the in-memory caches of the stores since the node started
+    pub cache_hits: u64,
+    /// Reads missing the in-memory caches of the stores since the node started
+    pub cache_misses: u64,
+    /// Ratio of the store reads served by the in-memory caches
+    pub cache_hit_rate: f64,
+    pub databases: Vec<DatabaseMetrics>,
+    /// Size of the values written compressed since the node started, before compression
+    #[serde(default)]
+    pub compression_input_bytes: u64,
+    /// Size of the values written compressed since the node started, after compression
+    #[serde(default)]
+    pub compression_output_bytes: u64,
+    /// Size of the values written compressed relative to their uncompressed size, 1 if none was written
+    #[serde(default)]
+    pub compression_ratio: f64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBucket {
+    /// Upper bound of the bucket, in milliseconds
+    pub upper_bound_ms: u64,
+    /// Number of requests answered within the bound, the buckets being cumulative
+    pub count: u64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodMetrics {
+    pub method: String,
+    pub requests: u64,
+    pub errors: u64,
+    /// Sum of the request latencies, in microseconds
+    pub latency_sum_us: u64,
+    /// Latency histogram, the requests slower than the last bound being only counted in `requests`
+    pub latency_buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowMetrics {
+    /// Name of the P2P flow, the first message type it subscribed to
+    pub flow: String,
+    /// Number of messages currently waiting in the incoming routes of the flow
+    pub queue_depth: u64,
+    /// Number of messages dequeued by the flow
+    pub messages: u64,
+    /// Sum of the time the dequeued messages waited in the incoming routes, in microseconds
+    pub latency_sum_us: u64,
+    /// Histogram of the time the dequeued messages waited in the incoming routes
+    pub latency_buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMetrics {
+    /// Name of the tenant, as configured on the gRPC server
+    pub name: String,
+    pub active_connections: u64,
+    pub active_subscriptions: u64,
+    /// Number of addresses tracked by the UtxosChanged subscriptions of the tenant connections
+    pub tracked_addresses: u64,
+    pub requests: u64,
+    /// Number of requests rejected for exceeding a tenant quota
+    pub rejected_requests: u64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMetrics {
+    /// gRPC requests per protowire payload variant, since the node started
+    pub grpc_methods: Vec<MethodMetrics>,
+    /// P2P messages routed to each flow, aggregated over the currently connected peers
+    #[serde(default)]
+    pub p2p_flows: Vec<FlowMetrics>,
+    /// Usage of the tenants sharing the gRPC server, sorted by tenant name
+    #[serde(default)]
+    pub grpc_tenants: Vec<TenantMetrics>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMetricsResponse {
@@ -762,6 +1125,8 @@ pub struct GetMetricsResponse {
     pub connection_metrics: Option<ConnectionMetrics>,
     pub bandwidth_metrics: Option<BandwidthMetrics>,
     pub consensus_metrics: Option<ConsensusMetrics>,
+    pub storage_metrics: Option<StorageMetrics>,
+    pub request_metrics: Option<RequestMetrics>,
 }
 
 impl GetMetricsResponse {
@@ -771,8 +1136,18 @@ impl GetMetricsResponse {
         connection_metrics: Option<ConnectionMetrics>,
         bandwidth_metrics: Option<BandwidthMetrics>,
         consensus_metrics: Option<ConsensusMetrics>,
+        storage_metrics: Option<StorageMetrics>,
+        request_metrics: Option<RequestMetrics>,
     ) -> Self {
-        Self { process_metrics, connection_metrics, bandwidth_metrics, consensus_metrics, server_time }
+        Self {
+            process_metrics,
+            connection_metrics,
+            bandwidth_metrics,
+            consensus_metrics,
+            storage_metrics,
+            request_metrics,
+            server_time,
+        }
     }
 }
 
@@ -799,6 +1174,20 @@ pub struct GetSyncStatusRequest {}
 #[serde(rename_all = "camelCase")]
 pub struct GetSyncStatusResponse {
     pub is_synced: bool,
+    /// Current synchronization phase
+    pub phase: RpcSyncPhase,
+    /// Progress of the current phase in percent, if known
+    pub phase_progress: Option<f64>,
+    /// Number of items (trusted blocks, headers, UTXOs or blocks) processed so far in the current IBD phase
+    pub processed: u64,
+    /// Estimated remaining time of the current phase in milliseconds, if known
+    pub estimated_remaining_time: Option<u64>,
+    /// DAA score of the sink of the virtual state
+    pub sink_daa_score: u64,
+    /// DAA score of the headers selected tip
+    pub headers_selected_tip_daa_score: u64,
+    /// Address of the peer the IBD is run against, if any
+    pub ibd_peer: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -825,193 +1214,919 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
-// ----------------------------------------------------------------------------
-// Subscriptions & notifications
-// ----------------------------------------------------------------------------
-
-// ~~~~~~~~~~~~~~~~~~~~~~
-// BlockAddedNotification
-
-/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
-///
-/// See: BlockAddedNotification
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedRequest {
-    pub command: Command,
+pub struct GetTransactionMassRequest {
+    pub transaction: RpcTransaction,
 }
-impl NotifyBlockAddedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+
+impl GetTransactionMassRequest {
+    pub fn new(transaction: RpcTransaction) -> Self {
+        Self { transaction }
     }
 }
 
+/// Compute mass breakdown of a transaction.
+///
+/// Storage mass (KIP-0009) requires the full UTXO context of the transaction and is not included.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionMassResponse {
+    /// Mass of the estimated serialized size of the transaction
+    pub size_mass: u64,
+    /// Mass of the script public keys of the transaction outputs
+    pub script_public_key_mass: u64,
+    /// Mass of the signature operations of the transaction inputs
+    pub sig_op_mass: u64,
+    /// Total compute mass, sum of the above components
+    pub compute_mass: u64,
+    /// Maximum mass of a transaction considered standard by the mempool
+    pub max_standard_transaction_mass: u64,
+    /// Maximum mass of a block
+    pub max_block_mass: u64,
+}
+
+/// Asks the node to select UTXOs of `from_addresses` covering `outputs` and the fee, and to build
+/// an unsigned transaction paying the remainder to `change_address`.
+///
+/// Requires the node to run with both `--utxoindex` and `--enable-transaction-builder-rpc`.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyBlockAddedResponse {}
+pub struct BuildUnsignedTransactionRequest {
+    pub from_addresses: Vec<RpcAddress>,
+    pub outputs: Vec<RpcPaymentOutput>,
+    pub change_address: RpcAddress,
+    /// Fee rate in sompi per gram of mass. Defaults to (and cannot go below) the minimum relay fee rate
+    #[serde(default)]
+    pub fee_rate: Option<f64>,
+}
+
+impl BuildUnsignedTransactionRequest {
+    pub fn new(from_addresses: Vec<RpcAddress>, outputs: Vec<RpcPaymentOutput>, change_address: RpcAddress) -> Self {
+        Self { from_addresses, outputs, change_address, fee_rate: None }
+    }
+
+    pub fn with_fee_rate(self, fee_rate: f64) -> Self {
+        Self { fee_rate: Some(fee_rate), ..self }
+    }
+}
 
-/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
-/// into the DAG.
-///
-/// See: NotifyBlockAddedRequest
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BlockAddedNotification {
-    pub block: Arc<RpcBlock>,
+pub struct BuildUnsignedTransactionResponse {
+    /// The unsigned transaction, with empty signature scripts
+    pub transaction: RpcTransaction,
+    /// The UTXO entries spent by the transaction, in the order of its inputs
+    pub utxo_entries: Vec<RpcUtxosByAddressesEntry>,
+    pub fee: u64,
+    /// Compute mass of the transaction once signed, assuming a Schnorr signature per input
+    pub mass: u64,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// VirtualChainChangedNotification
-
-// NotifyVirtualChainChangedRequest registers this connection for
-// virtualDaaScoreChanged notifications.
-//
-// See: VirtualChainChangedNotification
+/// Requests structural DAG stats computed over the mergesets of the last `window_size`
+/// selected chain blocks.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedRequest {
-    pub include_accepted_transaction_ids: bool,
-    pub command: Command,
+pub struct GetDagStatsRequest {
+    pub window_size: u32,
 }
 
-impl NotifyVirtualChainChangedRequest {
-    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
-        Self { include_accepted_transaction_ids, command }
+impl GetDagStatsRequest {
+    pub fn new(window_size: u32) -> Self {
+        Self { window_size }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyVirtualChainChangedResponse {}
+pub struct GetDagStatsResponse {
+    /// Current number of DAG tips
+    pub tip_count: u64,
+    /// Number of selected chain blocks actually covered, which might be lower than the requested
+    /// window size if the pruning point is reached first
+    pub chain_block_count: u64,
+    /// Number of blocks merged by the covered chain blocks
+    pub block_count: u64,
+    pub average_mergeset_size: f64,
+    /// Ratio of red blocks among the merged blocks
+    pub red_block_rate: f64,
+    /// Average number of direct parents of the merged blocks
+    pub average_parents: f64,
+}
 
-// VirtualChainChangedNotification is sent whenever the DAG's selected parent
-// chain had changed.
-//
-// See: NotifyVirtualChainChangedRequest
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct VirtualChainChangedNotification {
-    pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
-    pub added_chain_block_hashes: Arc<Vec<RpcHash>>,
-    pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
-}
-
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// FinalityConflictNotification
+pub struct GetHealthRequest {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictRequest {
-    pub command: Command,
+pub struct GetHealthResponse {
+    /// Whether some abnormal DAG condition is currently observed
+    pub degraded: bool,
+    /// Currently active alerts
+    pub alerts: Vec<RpcHealthAlert>,
+    /// Progress of the index resyncs currently running, during which the indexes are unavailable
+    #[serde(default)]
+    pub index_resyncs: Vec<RpcIndexResyncProgress>,
 }
 
-impl NotifyFinalityConflictRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+impl GetHealthResponse {
+    pub fn new(alerts: Vec<RpcHealthAlert>, index_resyncs: Vec<RpcIndexResyncProgress>) -> Self {
+        Self { degraded: !alerts.is_empty(), alerts, index_resyncs }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResponse {}
+pub struct GetFeeEstimateRequest {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FinalityConflictNotification {
-    pub violating_block_hash: RpcHash,
+pub struct GetFeeEstimateResponse {
+    pub estimate: RpcFeeEstimate,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// FinalityConflictResolvedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSubscriptionsRequest {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResolvedRequest {
-    pub command: Command,
+pub struct GetSubscriptionsResponse {
+    /// Active subscriptions of the calling connection
+    pub subscriptions: Vec<RpcSubscription>,
 }
 
-impl NotifyFinalityConflictResolvedRequest {
-    pub fn new(command: Command) -> Self {
-        Self { command }
+impl GetSubscriptionsResponse {
+    pub fn new(subscriptions: Vec<RpcSubscription>) -> Self {
+        Self { subscriptions }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyFinalityConflictResolvedResponse {}
+pub struct GetVirtualParentsRequest {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct FinalityConflictResolvedNotification {
-    pub finality_block_hash: RpcHash,
+pub struct GetVirtualParentsResponse {
+    /// The virtual selected parent, aka the sink
+    pub selected_parent: RpcHash,
+    /// The virtual parents, selected parent first
+    pub parents: Vec<RpcHash>,
+    /// The candidates considered by the last virtual parent selection and excluded, in order of exclusion
+    pub excluded_candidates: Vec<RpcExcludedParentCandidate>,
 }
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~
-// UtxosChangedNotification
+impl GetVirtualParentsResponse {
+    pub fn new(selected_parent: RpcHash, parents: Vec<RpcHash>, excluded_candidates: Vec<RpcExcludedParentCandidate>) -> Self {
+        Self { selected_parent, parents, excluded_candidates }
+    }
+}
 
-// NotifyUtxosChangedRequestMessage registers this connection for utxoChanged notifications
-// for the given addresses. Depending on the provided `command`, notifications will
-// start or stop for the provided `addresses`.
-//
-// If `addresses` is empty, the notifications will start or stop for all addresses.
-//
-// This call is only available when this kaspad was started with `--utxoindex`
-//
-// See: UtxosChangedNotification
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyUtxosChangedRequest {
-    pub addresses: Vec<RpcAddress>,
-    pub command: Command,
+pub struct GetConsensusParamsRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConsensusParamsResponse {
+    pub params: RpcConsensusParams,
 }
 
-impl NotifyUtxosChangedRequest {
-    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
-        Self { addresses, command }
+impl GetConsensusParamsResponse {
+    pub fn new(params: RpcConsensusParams) -> Self {
+        Self { params }
     }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct NotifyUtxosChangedResponse {}
+pub struct GetTransactionByIdRequest {
+    pub transaction_id: RpcTransactionId,
+}
 
-// UtxosChangedNotificationMessage is sent whenever the UTXO index had been updated.
-//
-// See: NotifyUtxosChangedRequest
-#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+impl GetTransactionByIdRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct UtxosChangedNotification {
-    pub added: Arc<Vec<RpcUtxosByAddressesEntry>>,
-    pub removed: Arc<Vec<RpcUtxosByAddressesEntry>>,
+pub struct GetTransactionByIdResponse {
+    pub transaction_id: RpcTransactionId,
+    /// The block containing the transaction
+    pub containing_block_hash: RpcHash,
+    /// The chain block accepting the transaction, if it is accepted by the selected chain
+    pub accepting_block_hash: Option<RpcHash>,
+    pub is_accepted: bool,
+    /// Virtual DAA score minus the DAA score of the accepting block, as reported for the transactions of GetBlock.
+    /// Zero if the transaction is not accepted
+    pub confirmations: u64,
 }
 
-impl UtxosChangedNotification {
-    pub(crate) fn apply_utxos_changed_subscription(
-        &self,
-        subscription: &UtxosChangedSubscription,
-        context: &SubscriptionContext,
-    ) -> Option<Self> {
-        if subscription.to_all() {
-            Some(self.clone())
-        } else {
-            let added = Self::filter_utxos(&self.added, subscription, context);
-            let removed = Self::filter_utxos(&self.removed, subscription, context);
-            if added.is_empty() && removed.is_empty() {
-                None
-            } else {
-                debug!("CRPC, Creating UtxosChanged notifications with {} added and {} removed utxos", added.len(), removed.len());
-                Some(Self { added: Arc::new(added), removed: Arc::new(removed) })
-            }
+impl GetTransactionByIdResponse {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        containing_block_hash: RpcHash,
+        accepting_block_hash: Option<RpcHash>,
+        confirmations: u64,
+    ) -> Self {
+        Self {
+            transaction_id,
+            containing_block_hash,
+            is_accepted: accepting_block_hash.is_some(),
+            accepting_block_hash,
+            confirmations,
         }
     }
+}
 
-    fn filter_utxos(
-        utxo_set: &[RpcUtxosByAddressesEntry],
-        subscription: &UtxosChangedSubscription,
-        context: &SubscriptionContext,
-    ) -> Vec<RpcUtxosByAddressesEntry> {
-        let subscription_data = subscription.data();
-        utxo_set.iter().filter(|x| subscription_data.contains(&x.utxo_entry.script_public_key, context)).cloned().collect()
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SampleChainBlocksRequest {
+    pub low_blue_score: u64,
+    pub high_blue_score: u64,
+    /// Number of sampled blocks, a same block possibly being sampled several times
+    pub count: u32,
+}
+
+impl SampleChainBlocksRequest {
+    pub fn new(low_blue_score: u64, high_blue_score: u64, count: u32) -> Self {
+        Self { low_blue_score, high_blue_score, count }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SampleChainBlocksResponse {
+    /// Headers of the sampled blocks, in ascending blue score order
+    pub headers: Vec<RpcHeader>,
+}
+
+impl SampleChainBlocksResponse {
+    pub fn new(headers: Vec<RpcHeader>) -> Self {
+        Self { headers }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConnectionsRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConnectionsResponse {
+    pub connections: Vec<RpcConnectionInfo>,
+}
+
+impl GetConnectionsResponse {
+    pub fn new(connections: Vec<RpcConnectionInfo>) -> Self {
+        Self { connections }
+    }
+}
+
+/// GetBlockByBlueScoreRequest requests the lowest selected chain block having a blue score greater than or equal to `blue_score`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockByBlueScoreRequest {
+    pub blue_score: u64,
+
+    /// Level of detail of the returned block
+    pub verbosity: RpcBlockVerbosity,
+}
+
+impl GetBlockByBlueScoreRequest {
+    pub fn new(blue_score: u64, verbosity: RpcBlockVerbosity) -> Self {
+        Self { blue_score, verbosity }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockByBlueScoreResponse {
+    pub block: RpcBlock,
+}
+
+/// GetBlockByDaaScoreRequest requests the lowest selected chain block having a DAA score greater than or equal to `daa_score`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockByDaaScoreRequest {
+    pub daa_score: u64,
+
+    /// Level of detail of the returned block
+    pub verbosity: RpcBlockVerbosity,
+}
+
+impl GetBlockByDaaScoreRequest {
+    pub fn new(daa_score: u64, verbosity: RpcBlockVerbosity) -> Self {
+        Self { daa_score, verbosity }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockByDaaScoreResponse {
+    pub block: RpcBlock,
+}
+
+/// GetChainBlockByTimestampRequest requests the selected chain block with the timestamp nearest to `timestamp`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByTimestampRequest {
+    /// Unix time in milliseconds
+    pub timestamp: u64,
+
+    /// Level of detail of the returned block
+    pub verbosity: RpcBlockVerbosity,
+}
+
+impl GetChainBlockByTimestampRequest {
+    pub fn new(timestamp: u64, verbosity: RpcBlockVerbosity) -> Self {
+        Self { timestamp, verbosity }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainBlockByTimestampResponse {
+    pub block: RpcBlock,
+}
+
+/// GetPruningPointUtxosRequest requests a page of the UTXO set of the pruning point, in outpoint order
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningPointUtxosRequest {
+    /// Pruning point the page is expected to belong to, the current one if `None`.
+    /// The request fails if the pruning point moved, so paging must then start over.
+    #[serde(default)]
+    pub pruning_point: Option<RpcHash>,
+    /// Start the page right after this outpoint, as returned in the `next_cursor` of the previous page
+    #[serde(default)]
+    pub cursor: Option<RpcTransactionOutpoint>,
+    /// Return at most this many entries, the node capping the value
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl GetPruningPointUtxosRequest {
+    pub fn new(pruning_point: Option<RpcHash>, cursor: Option<RpcTransactionOutpoint>, limit: Option<u32>) -> Self {
+        Self { pruning_point, cursor, limit }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningPointUtxosResponse {
+    pub pruning_point: RpcHash,
+    /// UTXO commitment of the pruning point header, matching the MuHash of its full UTXO set
+    pub utxo_commitment: RpcHash,
+    /// Entries of the page, with no address
+    pub entries: Vec<RpcUtxosByAddressesEntry>,
+    /// Cursor of the next page when the page is full, `None` when the UTXO set was fully returned
+    #[serde(default)]
+    pub next_cursor: Option<RpcTransactionOutpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolInfoRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolInfoResponse {
+    pub transaction_count: u64,
+    pub orphan_count: u64,
+    pub policy: RpcMempoolPolicy,
+    /// Lowest transaction version relayed and mined by the node
+    pub minimum_standard_transaction_version: u16,
+    /// Highest transaction version relayed and mined by the node, which may be lower than the consensus-valid one
+    pub maximum_standard_transaction_version: u16,
+}
+
+/// SetMempoolPolicyRequest replaces the mempool policy of the node.
+///
+/// Every parameter must lie within the bounds set by the node configuration, maximum counts being unable to
+/// exceed the ones the node started with. The method is only available in unsafe RPC mode.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMempoolPolicyRequest {
+    pub policy: RpcMempoolPolicy,
+}
+
+impl SetMempoolPolicyRequest {
+    pub fn new(policy: RpcMempoolPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMempoolPolicyResponse {
+    /// Policy in force after the update
+    pub policy: RpcMempoolPolicy,
+}
+
+/// GetCurrentBlockColorRequest requests whether a block is currently blue or red from the perspective of the virtual.
+///
+/// The block must be in the future of the pruning point and merged by the selected chain already.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCurrentBlockColorRequest {
+    pub hash: RpcHash,
+}
+
+impl GetCurrentBlockColorRequest {
+    pub fn new(hash: RpcHash) -> Self {
+        Self { hash }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCurrentBlockColorResponse {
+    pub blue: bool,
+    /// The selected chain block having the block in its mergeset
+    pub merging_block_hash: RpcHash,
+}
+
+impl GetCurrentBlockColorResponse {
+    pub fn new(blue: bool, merging_block_hash: RpcHash) -> Self {
+        Self { blue, merging_block_hash }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBansRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBansResponse {
+    pub bans: Vec<RpcBan>,
+}
+
+impl GetBansResponse {
+    pub fn new(bans: Vec<RpcBan>) -> Self {
+        Self { bans }
+    }
+}
+
+/// Requests the processing status of a block submitted with [`SubmitBlockRequest::allow_non_blocking`].
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockProcessingStatusRequest {
+    pub ticket: u64,
+}
+
+impl GetBlockProcessingStatusRequest {
+    pub fn new(ticket: u64) -> Self {
+        Self { ticket }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockProcessingStatusResponse {
+    pub block_hash: RpcHash,
+    /// The submission report, `None` while the block is still being processed
+    pub report: Option<SubmitBlockReport>,
+}
+
+impl GetBlockProcessingStatusResponse {
+    pub fn new(block_hash: RpcHash, report: Option<SubmitBlockReport>) -> Self {
+        Self { block_hash, report }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.report.is_none()
+    }
+}
+
+/// Starts a long-running node operation in the background, its status being then available
+/// through [`GetJobStatusRequest`] and [`JobCompletedNotification`].
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartJobRequest {
+    pub kind: RpcJobKind,
+}
+
+impl StartJobRequest {
+    pub fn new(kind: RpcJobKind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartJobResponse {
+    pub job_id: u64,
+}
+
+impl StartJobResponse {
+    pub fn new(job_id: u64) -> Self {
+        Self { job_id }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetJobStatusRequest {
+    pub job_id: u64,
+}
+
+impl GetJobStatusRequest {
+    pub fn new(job_id: u64) -> Self {
+        Self { job_id }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetJobStatusResponse {
+    pub job: RpcJob,
+}
+
+impl GetJobStatusResponse {
+    pub fn new(job: RpcJob) -> Self {
+        Self { job }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNotificationStatsRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNotificationStatsResponse {
+    pub notifiers: Vec<RpcNotifierStats>,
+}
+
+impl GetNotificationStatsResponse {
+    pub fn new(notifiers: Vec<RpcNotifierStats>) -> Self {
+        Self { notifiers }
+    }
+}
+
+/// GenerateBlocksRequest mines blocks on top of the virtual, one after another, each block including
+/// the transactions of the mempool. It is only available on simnet, where it allows tests to produce
+/// blocks instantly instead of relying on an external miner.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateBlocksRequest {
+    /// Which kaspa address should the coinbase block reward transactions pay into
+    pub pay_address: RpcAddress,
+    pub count: u64,
+}
+
+impl GenerateBlocksRequest {
+    pub fn new(pay_address: RpcAddress, count: u64) -> Self {
+        Self { pay_address, count }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateBlocksResponse {
+    /// Hashes of the generated blocks, in mining order
+    pub block_hashes: Vec<RpcHash>,
+}
+
+impl GenerateBlocksResponse {
+    pub fn new(block_hashes: Vec<RpcHash>) -> Self {
+        Self { block_hashes }
+    }
+}
+
+/// GetServerCapabilitiesRequest lists what the node supports, so a client can check the availability of
+/// a method or a notification before relying on it and degrade gracefully against nodes of another version.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetServerCapabilitiesRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetServerCapabilitiesResponse {
+    /// Version of the RPC API, see [`crate::api::ops::RPC_API_VERSION`]
+    pub rpc_api_version: [u16; 4],
+    /// Names of the RPC methods served to the caller
+    pub methods: Vec<String>,
+    /// Names of the notification types the caller can subscribe to
+    pub notifications: Vec<String>,
+}
+
+impl GetServerCapabilitiesResponse {
+    pub fn new(rpc_api_version: [u16; 4], methods: Vec<String>, notifications: Vec<String>) -> Self {
+        Self { rpc_api_version, methods, notifications }
+    }
+
+    /// Tells if the method named `method` is supported, the name being case-insensitive
+    pub fn has_method(&self, method: &str) -> bool {
+        self.methods.iter().any(|x| x.eq_ignore_ascii_case(method))
+    }
+
+    /// Tells if the notification type named `notification` is supported, the name being case-insensitive
+    pub fn has_notification(&self, notification: &str) -> bool {
+        self.notifications.iter().any(|x| x.eq_ignore_ascii_case(notification))
+    }
+}
+
+/// UpdateBlockTemplateExclusionsRequest adds or removes transaction ids and script public keys excluded from
+/// the block templates built by the node. A transaction is excluded if its id is or if it spends from or pays
+/// to an excluded script public key. Removals are applied after additions and an empty request only returns
+/// the exclusions in force.
+///
+/// Requires the node to run with the `--unsaferpc` flag.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBlockTemplateExclusionsRequest {
+    pub add_transaction_ids: Vec<RpcTransactionId>,
+    pub remove_transaction_ids: Vec<RpcTransactionId>,
+    pub add_script_public_keys: Vec<RpcScriptPublicKey>,
+    pub remove_script_public_keys: Vec<RpcScriptPublicKey>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBlockTemplateExclusionsResponse {
+    /// Transaction ids excluded after the update
+    pub transaction_ids: Vec<RpcTransactionId>,
+    /// Script public keys excluded after the update
+    pub script_public_keys: Vec<RpcScriptPublicKey>,
+}
+
+/// GetChainQualityRequest requests the rolling chain-quality samples (red rate, propagation delay, miner
+/// concentration) computed by the node at regular intervals and retained for a day.
+///
+/// Requires the node to run with the `--chain-quality` flag.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainQualityRequest {
+    /// Unix timestamp in milliseconds after which the returned samples were taken, 0 for all the retained samples
+    #[serde(default)]
+    pub since: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChainQualityResponse {
+    /// Samples ordered oldest first
+    pub samples: Vec<RpcChainQualitySample>,
+}
+
+// ----------------------------------------------------------------------------
+// Subscriptions & notifications
+// ----------------------------------------------------------------------------
+
+// ~~~~~~~~~~~~~~~~~~~~~~
+// BlockAddedNotification
+
+/// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
+///
+/// See: BlockAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedRequest {
+    pub command: Command,
+}
+impl NotifyBlockAddedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyBlockAddedResponse {}
+
+/// BlockAddedNotification is sent whenever a blocks has been added (NOT accepted)
+/// into the DAG.
+///
+/// See: NotifyBlockAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAddedNotification {
+    pub block: Arc<RpcBlock>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// VirtualChainChangedNotification
+
+// NotifyVirtualChainChangedRequest registers this connection for
+// virtualDaaScoreChanged notifications.
+//
+// See: VirtualChainChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedRequest {
+    pub include_accepted_transaction_ids: bool,
+    pub command: Command,
+}
+
+impl NotifyVirtualChainChangedRequest {
+    pub fn new(include_accepted_transaction_ids: bool, command: Command) -> Self {
+        Self { include_accepted_transaction_ids, command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyVirtualChainChangedResponse {}
+
+// VirtualChainChangedNotification is sent whenever the DAG's selected parent
+// chain had changed.
+//
+// See: NotifyVirtualChainChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VirtualChainChangedNotification {
+    pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
+    pub added_chain_block_hashes: Arc<Vec<RpcHash>>,
+    pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FinalityConflictNotification
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictRequest {
+    pub command: Command,
+}
+
+impl NotifyFinalityConflictRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityConflictNotification {
+    pub violating_block_hash: RpcHash,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FinalityConflictResolvedNotification
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResolvedRequest {
+    pub command: Command,
+}
+
+impl NotifyFinalityConflictResolvedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyFinalityConflictResolvedResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityConflictResolvedNotification {
+    pub finality_block_hash: RpcHash,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~
+// UtxosChangedNotification
+
+// NotifyUtxosChangedRequestMessage registers this connection for utxoChanged notifications
+// for the given addresses. Depending on the provided `command`, notifications will
+// start or stop for the provided `addresses`.
+//
+// If `addresses` is empty, the notifications will start or stop for all addresses.
+//
+// This call is only available when this kaspad was started with `--utxoindex`
+//
+// See: UtxosChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyUtxosChangedRequest {
+    pub addresses: Vec<RpcAddress>,
+    /// Start only: the addresses replace the address set of the listener instead of being added to it, only the
+    /// added and removed addresses being applied
+    #[serde(default)]
+    pub replace: bool,
+    pub command: Command,
+}
+
+impl NotifyUtxosChangedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, replace: false, command }
+    }
+
+    pub fn with_replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyUtxosChangedResponse {}
+
+// UtxosChangedNotificationMessage is sent whenever the UTXO index had been updated.
+//
+// See: NotifyUtxosChangedRequest
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosChangedNotification {
+    pub added: Arc<Vec<RpcUtxosByAddressesEntry>>,
+    pub removed: Arc<Vec<RpcUtxosByAddressesEntry>>,
+}
+
+impl UtxosChangedNotification {
+    pub(crate) fn apply_utxos_changed_subscription(
+        &self,
+        subscription: &UtxosChangedSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        if subscription.to_all() {
+            Some(self.clone())
+        } else {
+            let added = Self::filter_utxos(&self.added, subscription, context);
+            let removed = Self::filter_utxos(&self.removed, subscription, context);
+            if added.is_empty() && removed.is_empty() {
+                None
+            } else {
+                debug!("CRPC, Creating UtxosChanged notifications with {} added and {} removed utxos", added.len(), removed.len());
+                Some(Self { added: Arc::new(added), removed: Arc::new(removed) })
+            }
+        }
+    }
+
+    /// Applies a batch of subscriptions, resolving the address of every utxo only once for all of them,
+    /// inactive subscriptions getting no notification
+    pub(crate) fn apply_utxos_changed_subscriptions(
+        &self,
+        subscriptions: &[&UtxosChangedSubscription],
+        context: &SubscriptionContext,
+    ) -> Vec<Option<Self>> {
+        let added_batch = UtxosChangedBatch::new(self.added.iter().map(|x| &x.utxo_entry.script_public_key), context);
+        let removed_batch = UtxosChangedBatch::new(self.removed.iter().map(|x| &x.utxo_entry.script_public_key), context);
+        subscriptions
+            .iter()
+            .map(|subscription| {
+                let subscription_data = subscription.data();
+                if !subscription_data.state().active() {
+                    return None;
+                }
+                if subscription_data.to_all() {
+                    return Some(self.clone());
+                }
+                if added_batch.is_empty() && removed_batch.is_empty() {
+                    return None;
+                }
+                let added = added_batch.select(&subscription_data).into_iter().map(|i| self.added[i].clone()).collect::<Vec<_>>();
+                let removed =
+                    removed_batch.select(&subscription_data).into_iter().map(|i| self.removed[i].clone()).collect::<Vec<_>>();
+                if added.is_empty() && removed.is_empty() {
+                    None
+                } else {
+                    Some(Self { added: Arc::new(added), removed: Arc::new(removed) })
+                }
+            })
+            .collect()
+    }
+
+    /// Merges the UTXO changes of `next`, a later notification, into `self`.
+    ///
+    /// A UTXO added by `self` then removed by `next` cancels out and appears in none of the merged lists,
+    /// while a UTXO removed by `self` then added back by `next` only appears in the added list.
+    pub fn coalesce(&mut self, next: &Self) {
+        let next_added = next.added.iter().map(|x| x.outpoint).collect::<HashSet<_>>();
+        let next_removed = next.removed.iter().map(|x| x.outpoint).collect::<HashSet<_>>();
+        let self_added = self.added.iter().map(|x| x.outpoint).collect::<HashSet<_>>();
+        let added =
+            self.added.iter().filter(|x| !next_removed.contains(&x.outpoint)).chain(next.added.iter()).cloned().collect::<Vec<_>>();
+        let removed = self
+            .removed
+            .iter()
+            .filter(|x| !next_added.contains(&x.outpoint))
+            .chain(next.removed.iter().filter(|x| !self_added.contains(&x.outpoint)))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.added = Arc::new(added);
+        self.removed = Arc::new(removed);
+    }
+
+    fn filter_utxos(
+        utxo_set: &[RpcUtxosByAddressesEntry],
+        subscription: &UtxosChangedSubscription,
+        context: &SubscriptionContext,
+    ) -> Vec<RpcUtxosByAddressesEntry> {
+        let subscription_data = subscription.data();
+        utxo_set.iter().filter(|x| subscription_data.contains(&x.utxo_entry.script_public_key, context)).cloned().collect()
     }
 }
 
@@ -1133,6 +2248,134 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~~
+// HealthAlertNotification
+
+/// NotifyHealthAlertRequest registers this connection for healthAlert notifications.
+///
+/// See: HealthAlertNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyHealthAlertRequest {
+    pub command: Command,
+}
+impl NotifyHealthAlertRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyHealthAlertResponse {}
+
+/// HealthAlertNotification is sent whenever the node health monitor raises an alert
+/// or resolves a previously raised one.
+///
+/// See: NotifyHealthAlertRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthAlertNotification {
+    pub alert: RpcHealthAlert,
+    /// Whether the alert condition ended
+    pub resolved: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionsEvictedNotification
+
+/// NotifyMempoolTransactionsEvictedRequest registers this connection for mempoolTransactionsEvicted notifications.
+///
+/// See: MempoolTransactionsEvictedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionsEvictedRequest {
+    pub command: Command,
+}
+impl NotifyMempoolTransactionsEvictedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionsEvictedResponse {}
+
+/// MempoolTransactionsEvictedNotification is sent whenever a re-evaluation of the mempool, triggered
+/// by a pruning point move or a consensus parameter activation, evicts transactions that became invalid.
+///
+/// See: NotifyMempoolTransactionsEvictedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionsEvictedNotification {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    /// Event which triggered the re-evaluation
+    pub reason: String,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~
+// JobCompletedNotification
+
+/// NotifyJobCompletedRequest registers this connection for jobCompleted notifications.
+///
+/// See: JobCompletedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyJobCompletedRequest {
+    pub command: Command,
+}
+impl NotifyJobCompletedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyJobCompletedResponse {}
+
+/// JobCompletedNotification is sent whenever a job started by [`StartJobRequest`] completes or fails.
+///
+/// See: NotifyJobCompletedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobCompletedNotification {
+    pub job: RpcJob,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~
+// IndexResyncProgressNotification
+
+/// NotifyIndexResyncProgressRequest registers this connection for indexResyncProgress notifications.
+///
+/// See: IndexResyncProgressNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyIndexResyncProgressRequest {
+    pub command: Command,
+}
+impl NotifyIndexResyncProgressRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyIndexResyncProgressResponse {}
+
+/// IndexResyncProgressNotification is sent periodically while an index resync runs and once it is over.
+///
+/// See: NotifyIndexResyncProgressRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexResyncProgressNotification {
+    pub progress: RpcIndexResyncProgress,
+    /// Whether the resync is over, the index being available again
+    pub completed: bool,
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -1154,3 +2397,157 @@ impl SubscribeResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeResponse {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{header::Header, subnets::SUBNETWORK_ID_NATIVE};
+    use kaspa_notify::scope::Scope;
+
+    fn transaction() -> RpcTransaction {
+        RpcTransaction {
+            version: 0,
+            inputs: vec![],
+            outputs: vec![],
+            lock_time: 0,
+            subnetwork_id: SUBNETWORK_ID_NATIVE,
+            gas: 0,
+            payload: vec![],
+            mass: 0,
+            verbose_data: None,
+        }
+    }
+
+    /// Removes `field` from the JSON encoding of `value`, as sent by a client predating the field
+    fn without_field<T: Serialize>(value: &T, field: &str) -> serde_json::Value {
+        let mut json = serde_json::to_value(value).unwrap();
+        assert!(json.as_object_mut().unwrap().remove(field).is_some());
+        json
+    }
+
+    #[test]
+    fn test_submit_transaction_request_defaults() {
+        let request = SubmitTransactionRequest::new(transaction(), true, true);
+        let request: SubmitTransactionRequest = serde_json::from_value(without_field(&request, "replace")).unwrap();
+        assert!(request.allow_orphan);
+        assert!(!request.replace);
+    }
+
+    #[test]
+    fn test_submit_block_defaults() {
+        // Submissions predating the non-blocking mode wait for the block processing
+        let block = RpcBlock {
+            header: Header::from_precomputed_hash(RpcHash::from_u64_word(1), vec![]),
+            transactions: vec![],
+            verbose_data: None,
+        };
+        let request = SubmitBlockRequest::new(block, false).with_allow_non_blocking(true);
+        let request: SubmitBlockRequest = serde_json::from_value(without_field(&request, "allowNonBlocking")).unwrap();
+        assert!(!request.allow_non_blocking);
+        let response = SubmitBlockResponse::new(SubmitBlockReport::Success).with_ticket(1);
+        let response: SubmitBlockResponse = serde_json::from_value(without_field(&response, "ticket")).unwrap();
+        assert_eq!((response.report, response.ticket), (SubmitBlockReport::Success, None));
+
+        assert!(GetBlockProcessingStatusResponse::new(RpcHash::from_u64_word(1), None).is_pending());
+        assert!(!GetBlockProcessingStatusResponse::new(RpcHash::from_u64_word(1), Some(SubmitBlockReport::Success)).is_pending());
+    }
+
+    #[test]
+    fn test_notify_utxos_changed_request_replace() {
+        let address = RpcAddress::try_from("kaspatest:qp3w5h9hp9ude4vjpllsm4qpe8rcc5dmeealkl0cnxlgtj4ly7rczqxcdamvr").unwrap();
+        let request = NotifyUtxosChangedRequest::new(vec![address], Command::Start).with_replace(true);
+        assert!(matches!(Scope::from(request.clone()), Scope::UtxosChanged(scope) if scope.replace));
+
+        // Requests predating the flag add to the address set
+        let request: NotifyUtxosChangedRequest = serde_json::from_value(without_field(&request, "replace")).unwrap();
+        assert!(matches!(Scope::from(request), Scope::UtxosChanged(scope) if !scope.replace));
+    }
+
+    #[test]
+    fn test_get_current_network_response() {
+        let network_id = RpcNetworkId::with_suffix(RpcNetworkType::Testnet, 11);
+        let response = GetCurrentNetworkResponse::new(network_id);
+        assert_eq!((response.network, response.network_id), (RpcNetworkType::Testnet, Some(network_id)));
+
+        // Responses from nodes predating the network id only carry the network type
+        let response: GetCurrentNetworkResponse = serde_json::from_value(without_field(&response, "networkId")).unwrap();
+        assert_eq!((response.network, response.network_id), (RpcNetworkType::Testnet, None));
+    }
+
+    #[test]
+    fn test_block_request_verbosity() {
+        // Requests predating the verbosity fall back to the include transactions flag
+        let request = GetBlockRequest::new(RpcHash::from_u64_word(1), true);
+        let request: GetBlockRequest = serde_json::from_value(without_field(&request, "verbosity")).unwrap();
+        assert_eq!(request.verbosity, None);
+        assert_eq!(request.block_verbosity(), RpcBlockVerbosity::Full);
+        let request = GetBlocksRequest::new(None, true, false);
+        let request: GetBlocksRequest = serde_json::from_value(without_field(&request, "verbosity")).unwrap();
+        assert_eq!(request.block_verbosity(), RpcBlockVerbosity::TransactionIds);
+
+        // The verbosity supersedes the flag when set
+        let request = GetBlockRequest::new(RpcHash::from_u64_word(1), true).with_verbosity(RpcBlockVerbosity::HeaderOnly);
+        assert!(!request.include_transactions);
+        assert_eq!(request.block_verbosity(), RpcBlockVerbosity::HeaderOnly);
+        let request =
+            GetBlocksRequest { verbosity: Some(RpcBlockVerbosity::TransactionIds), ..GetBlocksRequest::new(None, true, true) };
+        assert_eq!(request.block_verbosity(), RpcBlockVerbosity::TransactionIds);
+    }
+
+    #[test]
+    fn test_coin_supply_response_defaults() {
+        let response = GetCoinSupplyResponse::new(100, 50, 10);
+        let response: GetCoinSupplyResponse = serde_json::from_value(without_field(&response, "burnedSompi")).unwrap();
+        assert_eq!((response.max_sompi, response.circulating_sompi, response.burned_sompi), (100, 50, 0));
+    }
+
+    #[test]
+    fn test_virtual_chain_batch_defaults() {
+        // Requests and responses predating the batches are unbounded and complete
+        let request = GetVirtualChainFromBlockRequest::new(RpcHash::from_u64_word(1), true).with_max_blocks(10);
+        let request: GetVirtualChainFromBlockRequest = serde_json::from_value(without_field(&request, "maxBlocks")).unwrap();
+        assert_eq!(request.max_blocks, None);
+        let response = GetVirtualChainFromBlockResponse::new(vec![], vec![RpcHash::from_u64_word(2)], vec![])
+            .with_next_start_hash(Some(RpcHash::from_u64_word(2)));
+        let response: GetVirtualChainFromBlockResponse = serde_json::from_value(without_field(&response, "nextStartHash")).unwrap();
+        assert_eq!(response.next_start_hash, None);
+    }
+
+    #[test]
+    fn test_estimate_network_hashes_per_second_request_defaults() {
+        // Requests predating the min window size defer to the node configuration
+        let request = EstimateNetworkHashesPerSecondRequest::new(1000, None).with_min_window_size(500);
+        assert_eq!(request.min_window_size, Some(500));
+        let request: EstimateNetworkHashesPerSecondRequest = serde_json::from_value(without_field(&request, "minWindowSize")).unwrap();
+        assert_eq!((request.window_size, request.min_window_size), (1000, None));
+    }
+
+    #[test]
+    fn test_sync_status_response_encoding() {
+        let response = GetSyncStatusResponse {
+            is_synced: false,
+            phase: RpcSyncPhase::BlockBodies,
+            phase_progress: Some(42.5),
+            processed: 1000,
+            estimated_remaining_time: Some(60_000),
+            sink_daa_score: 10,
+            headers_selected_tip_daa_score: 20,
+            ibd_peer: Some("127.0.0.1:16111".to_owned()),
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["phase"], "blockBodies");
+        assert_eq!(json["phaseProgress"], 42.5);
+        assert_eq!(json["estimatedRemainingTime"], 60_000);
+        assert_eq!(json["headersSelectedTipDaaScore"], 20);
+
+        let decoded: GetSyncStatusResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.phase, RpcSyncPhase::BlockBodies);
+        assert_eq!(decoded.ibd_peer, response.ibd_peer);
+        let decoded = GetSyncStatusResponse::try_from_slice(&response.try_to_vec().unwrap()).unwrap();
+        assert_eq!((decoded.phase, decoded.phase_progress, decoded.processed), (RpcSyncPhase::BlockBodies, Some(42.5), 1000));
+        assert_eq!(decoded.estimated_remaining_time, Some(60_000));
+
+        assert_eq!(RpcSyncPhase::default(), RpcSyncPhase::Idle);
+        assert_eq!(RpcSyncPhase::VirtualResolution.to_string(), "virtual resolution");
+    }
+}
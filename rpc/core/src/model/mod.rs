@@ -1,26 +1,42 @@
 pub mod address;
 pub mod block;
 pub mod blue_work;
+pub mod build;
+pub mod fee_estimate;
 pub mod hash;
 pub mod header;
+pub mod health;
 pub mod hex_cnv;
+pub mod job;
 pub mod mempool;
 pub mod message;
 pub mod network;
+pub mod params;
+pub mod parents;
 pub mod peer;
 pub mod script_class;
 pub mod subnets;
+pub mod subscription;
+pub mod sync;
 pub mod tx;
 
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
+pub use build::*;
+pub use fee_estimate::*;
 pub use hash::*;
 pub use header::*;
+pub use health::*;
 pub use hex_cnv::*;
+pub use job::*;
 pub use mempool::*;
 pub use message::*;
 pub use network::*;
+pub use params::*;
+pub use parents::*;
 pub use peer::*;
 pub use subnets::*;
+pub use subscription::*;
+pub use sync::*;
 pub use tx::*;
@@ -0,0 +1,140 @@
+use crate::{RpcHash, RpcNetworkId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::config::params::Params;
+use serde::{Deserialize, Serialize};
+
+/// The consensus parameters of the network the node is running on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConsensusParams {
+    pub network_id: RpcNetworkId,
+    pub genesis_hash: RpcHash,
+    pub ghostdag_k: u32,
+
+    /// Target time per block (in milliseconds)
+    pub target_time_per_block: u64,
+
+    /// Legacy timestamp deviation tolerance (in seconds)
+    pub legacy_timestamp_deviation_tolerance: u64,
+    /// New timestamp deviation tolerance (in seconds, activated with sampling)
+    pub new_timestamp_deviation_tolerance: u64,
+    /// Block sample rate for filling the past median time window (selects one every N blocks)
+    pub past_median_time_sample_rate: u64,
+    /// Size of the sampled blocks window used to calculate the past median time
+    pub past_median_time_sampled_window_size: u64,
+    /// Block sample rate for filling the difficulty window (selects one every N blocks)
+    pub difficulty_sample_rate: u64,
+    /// Size of the sampled blocks window used to calculate the required difficulty
+    pub sampled_difficulty_window_size: u64,
+    /// Size of the full blocks window used to calculate the required difficulty before sampling activation
+    pub legacy_difficulty_window_size: u64,
+    /// The minimum length a difficulty window must have to trigger a DAA calculation
+    pub min_difficulty_window_len: u64,
+    /// DAA score from which the window sampling starts
+    pub sampling_activation_daa_score: u64,
+
+    pub max_block_parents: u32,
+    pub mergeset_size_limit: u64,
+    pub merge_depth: u64,
+    pub finality_depth: u64,
+    pub pruning_depth: u64,
+    pub pruning_proof_m: u64,
+    pub max_block_level: u32,
+
+    pub coinbase_payload_script_public_key_max_len: u32,
+    pub max_coinbase_payload_len: u64,
+    pub max_tx_inputs: u64,
+    pub max_tx_outputs: u64,
+    pub max_signature_script_len: u64,
+    pub max_script_public_key_len: u64,
+
+    pub mass_per_tx_byte: u64,
+    pub mass_per_script_pub_key_byte: u64,
+    pub mass_per_sig_op: u64,
+    pub max_block_mass: u64,
+    /// The parameter for scaling inverse KAS value to mass units (KIP-0009)
+    pub storage_mass_parameter: u64,
+    /// DAA score from which storage mass calculation is activated as a consensus rule
+    pub storage_mass_activation_daa_score: u64,
+    /// DAA score after which transactions of version 2 are consensus-valid
+    pub tx_version_2_activation_daa_score: u64,
+
+    /// DAA score after which the pre-deflationary period switches to the deflationary period
+    pub deflationary_phase_daa_score: u64,
+    /// Block subsidy (in sompi) during the pre-deflationary period
+    pub pre_deflationary_phase_base_subsidy: u64,
+    pub coinbase_maturity: u64,
+    pub skip_proof_of_work: bool,
+}
+
+impl From<&Params> for RpcConsensusParams {
+    fn from(item: &Params) -> Self {
+        Self {
+            network_id: item.net,
+            genesis_hash: item.genesis.hash,
+            ghostdag_k: item.ghostdag_k as u32,
+            target_time_per_block: item.target_time_per_block,
+            legacy_timestamp_deviation_tolerance: item.legacy_timestamp_deviation_tolerance,
+            new_timestamp_deviation_tolerance: item.new_timestamp_deviation_tolerance,
+            past_median_time_sample_rate: item.past_median_time_sample_rate,
+            past_median_time_sampled_window_size: item.past_median_time_sampled_window_size,
+            difficulty_sample_rate: item.difficulty_sample_rate,
+            sampled_difficulty_window_size: item.sampled_difficulty_window_size as u64,
+            legacy_difficulty_window_size: item.legacy_difficulty_window_size as u64,
+            min_difficulty_window_len: item.min_difficulty_window_len as u64,
+            sampling_activation_daa_score: item.sampling_activation_daa_score,
+            max_block_parents: item.max_block_parents as u32,
+            mergeset_size_limit: item.mergeset_size_limit,
+            merge_depth: item.merge_depth,
+            finality_depth: item.finality_depth,
+            pruning_depth: item.pruning_depth,
+            pruning_proof_m: item.pruning_proof_m,
+            max_block_level: item.max_block_level as u32,
+            coinbase_payload_script_public_key_max_len: item.coinbase_payload_script_public_key_max_len as u32,
+            max_coinbase_payload_len: item.max_coinbase_payload_len as u64,
+            max_tx_inputs: item.max_tx_inputs as u64,
+            max_tx_outputs: item.max_tx_outputs as u64,
+            max_signature_script_len: item.max_signature_script_len as u64,
+            max_script_public_key_len: item.max_script_public_key_len as u64,
+            mass_per_tx_byte: item.mass_per_tx_byte,
+            mass_per_script_pub_key_byte: item.mass_per_script_pub_key_byte,
+            mass_per_sig_op: item.mass_per_sig_op,
+            max_block_mass: item.max_block_mass,
+            storage_mass_parameter: item.storage_mass_parameter,
+            storage_mass_activation_daa_score: item.storage_mass_activation_daa_score,
+            tx_version_2_activation_daa_score: item.tx_version_2_activation_daa_score,
+            deflationary_phase_daa_score: item.deflationary_phase_daa_score,
+            pre_deflationary_phase_base_subsidy: item.pre_deflationary_phase_base_subsidy,
+            coinbase_maturity: item.coinbase_maturity,
+            skip_proof_of_work: item.skip_proof_of_work,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::config::params::{MAINNET_PARAMS, TESTNET11_PARAMS};
+
+    #[test]
+    fn test_consensus_params_from_params() {
+        let params = RpcConsensusParams::from(&MAINNET_PARAMS);
+        assert_eq!(params.network_id, MAINNET_PARAMS.net);
+        assert_eq!(params.genesis_hash, MAINNET_PARAMS.genesis.hash);
+        assert_eq!(params.target_time_per_block, 1000);
+        assert_eq!(params.max_block_parents as u8, MAINNET_PARAMS.max_block_parents);
+        assert_eq!(params.storage_mass_parameter, MAINNET_PARAMS.storage_mass_parameter);
+        assert!(!params.skip_proof_of_work);
+
+        // Networks differ by their parameters, and the JSON encoding of these is camel cased
+        let testnet_params = RpcConsensusParams::from(&TESTNET11_PARAMS);
+        assert_ne!(testnet_params, params);
+        assert_eq!(testnet_params.target_time_per_block, TESTNET11_PARAMS.target_time_per_block);
+        let json = serde_json::to_value(&testnet_params).unwrap();
+        assert_eq!(json["ghostdagK"], TESTNET11_PARAMS.ghostdag_k);
+        assert_eq!(json["sampledDifficultyWindowSize"], TESTNET11_PARAMS.sampled_difficulty_window_size as u64);
+
+        let decoded: RpcConsensusParams = RpcConsensusParams::try_from_slice(&borsh::to_vec(&testnet_params).unwrap()).unwrap();
+        assert_eq!(decoded, testnet_params);
+    }
+}
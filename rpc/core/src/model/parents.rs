@@ -0,0 +1,43 @@
+use crate::RpcHash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::api::{ExcludedParentCandidate, ParentExclusionReason};
+use serde::{Deserialize, Serialize};
+
+/// Reason for a virtual parent candidate not being selected as a virtual parent
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcParentExclusionReason {
+    /// The candidate was left out of the random sample taken when candidates exceed the max number considered
+    NotSampled = 0,
+    /// The max number of block parents was already reached
+    MaxBlockParents = 1,
+    /// Merging the candidate would make the virtual mergeset exceed the mergeset size limit
+    MergesetSizeLimit = 2,
+    /// Merging the candidate would make virtual merge a red block violating the bounded merge depth rule
+    BoundedMergeDepth = 3,
+}
+
+impl From<ParentExclusionReason> for RpcParentExclusionReason {
+    fn from(item: ParentExclusionReason) -> Self {
+        match item {
+            ParentExclusionReason::NotSampled => RpcParentExclusionReason::NotSampled,
+            ParentExclusionReason::MaxBlockParents => RpcParentExclusionReason::MaxBlockParents,
+            ParentExclusionReason::MergesetSizeLimit => RpcParentExclusionReason::MergesetSizeLimit,
+            ParentExclusionReason::BoundedMergeDepth => RpcParentExclusionReason::BoundedMergeDepth,
+        }
+    }
+}
+
+/// A virtual parent candidate excluded from the virtual parents
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcExcludedParentCandidate {
+    pub hash: RpcHash,
+    pub reason: RpcParentExclusionReason,
+}
+
+impl From<ExcludedParentCandidate> for RpcExcludedParentCandidate {
+    fn from(item: ExcludedParentCandidate) -> Self {
+        Self { hash: item.hash, reason: item.reason.into() }
+    }
+}
@@ -21,3 +21,44 @@ pub struct RpcPeerInfo {
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
 }
+
+/// Number of p2p messages of a given type exchanged with a peer
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMessageCounts {
+    pub message_type: String,
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// A p2p connection along with the traffic it carried so far
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConnectionInfo {
+    pub id: RpcNodeId,
+    pub address: RpcPeerAddress,
+    pub is_outbound: bool,
+    /// Age of the connection in milliseconds
+    pub connection_age: u64,
+    /// Encoded size of the p2p messages sent to the peer, excluding transport overheads
+    pub bytes_sent: u64,
+    /// Encoded size of the p2p messages received from the peer, excluding transport overheads
+    pub bytes_received: u64,
+    /// Message counts by message type, sorted by message type
+    pub messages: Vec<RpcMessageCounts>,
+}
+
+/// An active ban of an IP address or of an IP network
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBan {
+    pub ip: RpcIpAddress,
+    /// Unix time in milliseconds at which the ban was issued
+    pub timestamp: u64,
+    /// Remaining ban time in milliseconds
+    pub remaining_time: u64,
+    pub reason: Option<String>,
+    /// Set if the ban covers the whole `ip/prefix_length` network
+    #[serde(default)]
+    pub prefix_length: Option<u8>,
+}
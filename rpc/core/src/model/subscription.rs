@@ -0,0 +1,68 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_notify::{events::EventType, stats::EventStats, subscription::single::SubscriptionSummary};
+use serde::{Deserialize, Serialize};
+
+/// A notification scope a connection is currently subscribed to
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSubscription {
+    pub event_type: EventType,
+    /// VirtualChainChanged only: notifications include the accepted transaction ids
+    pub include_accepted_transaction_ids: bool,
+    /// UtxosChanged only: notifications cover all addresses
+    pub all_addresses: bool,
+    /// UtxosChanged only: number of addresses in the server-side address set
+    pub address_count: u64,
+}
+
+impl From<SubscriptionSummary> for RpcSubscription {
+    fn from(item: SubscriptionSummary) -> Self {
+        Self {
+            event_type: item.event_type,
+            include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+            all_addresses: item.all_addresses,
+            address_count: item.address_count as u64,
+        }
+    }
+}
+
+/// Delivery statistics of the notifications of an event type
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNotificationEventStats {
+    pub event_type: EventType,
+    /// Notifications accepted by the notifier for broadcasting
+    pub emitted: u64,
+    /// Notifications sent to a listener
+    pub delivered: u64,
+    /// Notifications lost because the listener connection was full or closed
+    pub dropped: u64,
+    /// Average number of listeners a notification was broadcast to
+    pub average_fan_out: f64,
+}
+
+impl From<EventStats> for RpcNotificationEventStats {
+    fn from(item: EventStats) -> Self {
+        Self {
+            event_type: item.event_type,
+            emitted: item.emitted,
+            delivered: item.delivered,
+            dropped: item.dropped,
+            average_fan_out: item.average_fan_out(),
+        }
+    }
+}
+
+/// Notification delivery statistics of a node notifier
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNotifierStats {
+    pub name: String,
+    pub events: Vec<RpcNotificationEventStats>,
+}
+
+impl RpcNotifierStats {
+    pub fn new(name: String, events: Vec<RpcNotificationEventStats>) -> Self {
+        Self { name, events }
+    }
+}
@@ -0,0 +1,38 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Synchronization phase of the node, as reported by `GetSyncStatus`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcSyncPhase {
+    /// The node is synced with the network
+    Synced = 0,
+    /// The node is not synced and no IBD is currently running, typically while waiting for peers
+    #[default]
+    Idle = 1,
+    /// Downloading and applying the pruning point proof and the trusted blocks
+    ProofDownload = 2,
+    /// Downloading and validating the block headers
+    Headers = 3,
+    /// Downloading the UTXO set of the pruning point
+    UtxoSet = 4,
+    /// Downloading and validating the block bodies
+    BlockBodies = 5,
+    /// Resolving the virtual state up to the headers selected tip
+    VirtualResolution = 6,
+}
+
+impl std::fmt::Display for RpcSyncPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcSyncPhase::Synced => "synced",
+            RpcSyncPhase::Idle => "idle",
+            RpcSyncPhase::ProofDownload => "proof download",
+            RpcSyncPhase::Headers => "headers",
+            RpcSyncPhase::UtxoSet => "UTXO set",
+            RpcSyncPhase::BlockBodies => "block bodies",
+            RpcSyncPhase::VirtualResolution => "virtual resolution",
+        };
+        f.write_str(s)
+    }
+}
@@ -106,6 +106,18 @@ pub struct RpcTransactionVerboseData {
     pub mass: u64,
     pub block_hash: RpcHash,
     pub block_time: u64,
+    /// Confirmations of the containing block, zero for a mempool transaction
+    #[serde(default)]
+    pub confirmations: u64,
+}
+
+/// Represents the result of the submission of a transaction within a list
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSubmitTransactionResult {
+    pub transaction_id: RpcTransactionId,
+    /// The reason of the rejection of the transaction, None if it was accepted (possibly as an orphan)
+    pub error: Option<String>,
 }
 
 /// Represents accepted transaction ids
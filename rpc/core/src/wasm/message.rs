@@ -40,6 +40,248 @@ const TS_ACCEPTED_TRANSACTION_IDS: &'static str = r#"
     }
 "#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_FEERATE_BUCKET: &'static str = r#"
+    /**
+     * Feerate, in sompi per gram, along with the expected time in seconds for a
+     * transaction paying it to get into a block.
+     * 
+     * @category Node RPC
+     */
+    export interface IFeerateBucket {
+        feerate : number;
+        estimatedSeconds : number;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_HEALTH_ALERT_ENTRY: &'static str = r#"
+    /**
+     * Abnormal DAG condition observed by the node.
+     * 
+     * @category Node RPC
+     */
+    export interface IHealthAlertEntry {
+        kind : "tipCountAboveThreshold" | "redRateSpike" | "blueWorkStagnation";
+        value : number;
+        threshold : number;
+        raisedAt : bigint;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CHAIN_QUALITY_SAMPLE: &'static str = r#"
+    /**
+     * Chain-quality metrics of the blocks accepted by the selected chain during a sampling interval.
+     * The average propagation delay is in milliseconds.
+     * 
+     * @category Node RPC
+     */
+    export interface IChainQualitySample {
+        timestamp : bigint;
+        chainBlockCount : bigint;
+        blockCount : bigint;
+        redBlockCount : bigint;
+        redRate : number;
+        averagePropagationDelay : bigint;
+        minerCount : bigint;
+        topMinerShare : number;
+        minerConcentration : number;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_JOB: &'static str = r#"
+    /**
+     * Status of a long-running node operation started with {@link RpcClient.startJob}.
+     * 
+     * @category Node RPC
+     */
+    export interface IJob {
+        id : bigint;
+        kind : "utxoIndexResync" | "txIndexResync";
+        state : "running" | "completed" | "failed";
+        startedAt : bigint;
+        finishedAt? : bigint;
+        error? : string;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_INDEX_RESYNC_PROGRESS: &'static str = r#"
+    /**
+     * Progress of a running index resync, during which the index is unavailable.
+     * 
+     * @category Node RPC
+     */
+    export interface IIndexResyncProgress {
+        kind : "utxoIndexResync" | "txIndexResync";
+        startedAt : bigint;
+        processed : bigint;
+        percent : number;
+        /** Estimated remaining seconds */
+        eta? : bigint;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CONNECTION_INFO: &'static str = r#"
+    /**
+     * P2P connection along with the bytes and messages exchanged over it.
+     * 
+     * @category Node RPC
+     */
+    export interface IConnectionInfo {
+        id : string;
+        address : string;
+        isOutbound : boolean;
+        /** Age of the connection in milliseconds */
+        connectionAge : bigint;
+        bytesSent : bigint;
+        bytesReceived : bigint;
+        messages : { messageType : string; sent : bigint; received : bigint; }[];
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_BAN: &'static str = r#"
+    /**
+     * Active ban of an IP address.
+     * 
+     * @category Node RPC
+     */
+    export interface IBan {
+        ip : string;
+        /** Unix time in milliseconds at which the ban was issued */
+        timestamp : bigint;
+        /** Remaining ban time in milliseconds */
+        remainingTime : bigint;
+        reason? : string;
+        /** Set if the ban covers the whole `ip/prefixLength` network */
+        prefixLength? : number;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_SUBSCRIPTION_ENTRY: &'static str = r#"
+    /**
+     * Notification scope a client is currently subscribed to.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubscriptionEntry {
+        eventType : string;
+        includeAcceptedTransactionIds : boolean;
+        allAddresses : boolean;
+        addressCount : bigint;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_NOTIFIER_STATS: &'static str = r#"
+    /**
+     * Notification delivery statistics of a node notifier, by event type.
+     * 
+     * @category Node RPC
+     */
+    export interface INotifierStats {
+        name : string;
+        events : {
+            eventType : string;
+            emitted : bigint;
+            delivered : bigint;
+            dropped : bigint;
+            averageFanOut : number;
+        }[];
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CONSENSUS_PARAMS: &'static str = r#"
+    /**
+     * Consensus parameters of a network.
+     * 
+     * @category Node RPC
+     */
+    export interface IConsensusParams {
+        networkId : string;
+        genesisHash : HexString;
+        ghostdagK : number;
+        targetTimePerBlock : bigint;
+        legacyTimestampDeviationTolerance : bigint;
+        newTimestampDeviationTolerance : bigint;
+        pastMedianTimeSampleRate : bigint;
+        pastMedianTimeSampledWindowSize : bigint;
+        difficultySampleRate : bigint;
+        sampledDifficultyWindowSize : bigint;
+        legacyDifficultyWindowSize : bigint;
+        minDifficultyWindowLen : bigint;
+        samplingActivationDaaScore : bigint;
+        maxBlockParents : number;
+        mergesetSizeLimit : bigint;
+        mergeDepth : bigint;
+        finalityDepth : bigint;
+        pruningDepth : bigint;
+        pruningProofM : bigint;
+        maxBlockLevel : number;
+        coinbasePayloadScriptPublicKeyMaxLen : number;
+        maxCoinbasePayloadLen : bigint;
+        maxTxInputs : bigint;
+        maxTxOutputs : bigint;
+        maxSignatureScriptLen : bigint;
+        maxScriptPublicKeyLen : bigint;
+        massPerTxByte : bigint;
+        massPerScriptPubKeyByte : bigint;
+        massPerSigOp : bigint;
+        maxBlockMass : bigint;
+        storageMassParameter : bigint;
+        storageMassActivationDaaScore : bigint;
+        deflationaryPhaseDaaScore : bigint;
+        preDeflationaryPhaseBaseSubsidy : bigint;
+        coinbaseMaturity : bigint;
+        skipProofOfWork : boolean;
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_BLOCK_VERBOSITY: &'static str = r#"
+    /**
+     * Level of detail of the blocks returned by 'getBlock' and 'getBlocks':
+     * - 'headerOnly': the header and verbose data, without transaction ids
+     * - 'transactionIds': the header and verbose data, including the transaction ids
+     * - 'full': the header and verbose data along with the full transactions
+     * 
+     * @category Node RPC
+     */
+    export type BlockVerbosity = "headerOnly" | "transactionIds" | "full";
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_EXCLUDED_PARENT_CANDIDATE: &'static str = r#"
+    /**
+     * Virtual parent candidate excluded from the virtual parents.
+     * 
+     * @category Node RPC
+     */
+    export interface IExcludedParentCandidate {
+        hash : HexString;
+        reason : "notSampled" | "maxBlockParents" | "mergesetSizeLimit" | "boundedMergeDepth";
+    }
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_UTXOS_BY_ADDRESSES_CURSOR: &'static str = r#"
+    /**
+     * Position of a UTXO entry in the result of a paged 'getUtxosByAddresses' request.
+     * 
+     * @category Node RPC
+     */
+    export interface IUtxosByAddressesCursor {
+        address : Address | string;
+        outpoint : ITransactionOutpoint;
+    }
+"#;
+
 // ---
 
 declare! {
@@ -171,6 +413,7 @@ declare! {
     export interface IGetCoinSupplyResponse {
         maxSompi: bigint;
         circulatingSompi: bigint;
+        burnedSompi: bigint;
     }
     "#,
 }
@@ -243,6 +486,19 @@ declare! {
         hasNotifyCommand : boolean;
         /** GRPC ONLY */
         hasMessageId : boolean;
+        /**
+         * Machine-readable description of the node build: version, git hash, build date,
+         * cargo features, P2P protocol versions, RPC API version and supported RPC methods.
+         */
+        buildInfo : {
+            version : string;
+            gitHash? : string;
+            buildDate? : string;
+            features : string[];
+            protocolVersions : number[];
+            rpcApiVersion : number[];
+            capabilities : string[];
+        };
     }
     "#,
 }
@@ -257,9 +513,24 @@ declare! {
     IGetPeerAddressesRequest,
     r#"
     /**
+     * Requests the known and banned addresses of the node, optionally filtered and paginated.
+     * Filters apply to both lists, except `recentOnly` which applies to known addresses only.
+     * 
      * @category Node RPC
      */
-    export interface IGetPeerAddressesRequest { }
+    export interface IGetPeerAddressesRequest {
+        /** Return the banned addresses only */
+        bannedOnly? : boolean;
+        /** Return only the known addresses advertised or connected to within the last few hours */
+        recentOnly? : boolean;
+        ipv4Only? : boolean;
+        ipv6Only? : boolean;
+        /** Network in CIDR notation, such as `192.168.0.0/16` */
+        network? : string;
+        offset? : number;
+        /** Max number of addresses to return per list */
+        limit? : number;
+    }
     "#,
 }
 
@@ -470,6 +741,15 @@ declare! {
      */
     export interface IGetSyncStatusResponse {
         isSynced : boolean;
+        phase : "synced" | "idle" | "proofDownload" | "headers" | "utxoSet" | "blockBodies" | "virtualResolution";
+        /** Progress of the current phase in percent */
+        phaseProgress? : number;
+        processed : bigint;
+        /** Estimated remaining time of the current phase in milliseconds */
+        estimatedRemainingTime? : bigint;
+        sinkDaaScore : bigint;
+        headersSelectedTipDaaScore : bigint;
+        ibdPeer? : string;
     }
     "#,
 }
@@ -478,6 +758,176 @@ try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
     Ok(to_value(&args)?.into())
 });
 
+declare! {
+    IGetHealthRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetHealthRequest { }
+    "#,
+}
+
+try_from! ( args: IGetHealthRequest, GetHealthRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetHealthResponse,
+    r#"
+    /**
+     * Abnormal DAG conditions currently observed by the node.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetHealthResponse {
+        degraded : boolean;
+        alerts : IHealthAlertEntry[];
+        indexResyncs : IIndexResyncProgress[];
+    }
+    "#,
+}
+
+try_from! ( args: GetHealthResponse, IGetHealthResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetFeeEstimateRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateRequest { }
+    "#,
+}
+
+try_from! ( args: IGetFeeEstimateRequest, GetFeeEstimateRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetFeeEstimateResponse,
+    r#"
+    /**
+     * Feerates (in sompi per gram) a transaction should pay depending on the urgency
+     * of its inclusion, along with the expected time to get into a block.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateResponse {
+        estimate : {
+            priorityBucket : IFeerateBucket;
+            normalBucket : IFeerateBucket;
+            lowBucket : IFeerateBucket;
+        };
+    }
+    "#,
+}
+
+try_from! ( args: GetFeeEstimateResponse, IGetFeeEstimateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetSubscriptionsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetSubscriptionsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetSubscriptionsRequest, GetSubscriptionsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetSubscriptionsResponse,
+    r#"
+    /**
+     * Notification scopes the client is currently subscribed to.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetSubscriptionsResponse {
+        subscriptions : ISubscriptionEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetSubscriptionsResponse, IGetSubscriptionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetVirtualParentsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetVirtualParentsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetVirtualParentsRequest, GetVirtualParentsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetVirtualParentsResponse,
+    r#"
+    /**
+     * Current virtual parents along with the candidate tips excluded by the last
+     * virtual parent selection.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetVirtualParentsResponse {
+        selectedParent : HexString;
+        parents : HexString[];
+        excludedCandidates : IExcludedParentCandidate[];
+    }
+    "#,
+}
+
+try_from! ( args: GetVirtualParentsResponse, IGetVirtualParentsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetConsensusParamsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetConsensusParamsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetConsensusParamsRequest, GetConsensusParamsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetConsensusParamsResponse,
+    r#"
+    /**
+     * Consensus parameters of the network the node is running on.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetConsensusParamsResponse {
+        params : IConsensusParams;
+    }
+    "#,
+}
+
+try_from! ( args: GetConsensusParamsResponse, IGetConsensusParamsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 /*
     Interfaces for methods with arguments
 */
@@ -531,6 +981,15 @@ declare! {
          * IPv4 or IPv6 address to ban.
          */
         ip : string;
+        /**
+         * Ban duration in milliseconds, the node default applying if omitted.
+         */
+        duration? : bigint;
+        reason? : string;
+        /**
+         * When set, bans the whole `ip/prefixLength` network (CIDR notation) instead of the single `ip`.
+         */
+        prefixLength? : number;
     }
     "#,
 }
@@ -566,6 +1025,10 @@ declare! {
     export interface IEstimateNetworkHashesPerSecondRequest {
         windowSize : number;
         startHash? : HexString;
+        /**
+         * Lowest number of blocks the window must hold, bounded below by the node configuration.
+         */
+        minWindowSize? : number;
     }
     "#,
 }
@@ -700,6 +1163,7 @@ declare! {
     export interface IGetBlockRequest {
         hash : HexString;
         includeTransactions : boolean;
+        verbosity? : BlockVerbosity;
     }
     "#,
 }
@@ -740,6 +1204,7 @@ declare! {
         lowHash? : HexString;
         includeBlocks : boolean;
         includeTransactions : boolean;
+        verbosity? : BlockVerbosity;
     }
     "#,
 }
@@ -814,6 +1279,11 @@ declare! {
      */
     export interface IGetBlockTemplateResponse {
         block : IBlock;
+        isSynced : boolean;
+        /**
+         * Ordering policy applied to the template transactions: `selection` or `deterministic`.
+         */
+        txOrdering : string;
     }
     "#,
 }
@@ -863,31 +1333,905 @@ try_from! ( args: GetDaaScoreTimestampEstimateResponse, IGetDaaScoreTimestampEst
 // ---
 
 declare! {
-    IGetCurrentNetworkRequest,
+    IGetTransactionMassRequest,
     r#"
     /**
-     * 
+     * Request the mass breakdown of a transaction.
      * 
      * @category Node RPC
      */
-    export interface IGetCurrentNetworkRequest { }
+    export interface IGetTransactionMassRequest {
+        transaction : Transaction,
+    }
     "#,
 }
 
-try_from! ( args: IGetCurrentNetworkRequest, GetCurrentNetworkRequest, {
-    Ok(from_value(args.into())?)
+try_from! ( args: IGetTransactionMassRequest, GetTransactionMassRequest, {
+    let transaction = args.try_get_value("transaction")?.unwrap_or_else(|| args.into());
+    let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+        GetTransactionMassRequest { transaction : transaction.into() }
+    } else {
+        from_value(transaction)?
+    };
+    Ok(request)
 });
 
 declare! {
-    IGetCurrentNetworkResponse,
+    IGetTransactionMassResponse,
     r#"
     /**
-     * 
+     * Compute mass breakdown of a transaction and applicable mass limits.
      * 
      * @category Node RPC
      */
-    export interface IGetCurrentNetworkResponse {
+    export interface IGetTransactionMassResponse {
+        sizeMass : bigint;
+        scriptPublicKeyMass : bigint;
+        sigOpMass : bigint;
+        computeMass : bigint;
+        maxStandardTransactionMass : bigint;
+        maxBlockMass : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionMassResponse, IGetTransactionMassResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IBuildUnsignedTransactionRequest,
+    r#"
+    /**
+     * Request the node to select UTXOs of `fromAddresses` and build an unsigned
+     * transaction paying `outputs`, with the remainder sent to `changeAddress`.
+     * 
+     * @category Node RPC
+     */
+    export interface IBuildUnsignedTransactionRequest {
+        fromAddresses : Address[] | string[];
+        outputs : { address : Address | string, amount : bigint }[];
+        changeAddress : Address | string;
+        /**
+         * Fee rate in sompi per gram of mass (defaults to the minimum relay fee rate).
+         */
+        feeRate? : number;
+    }
+    "#,
+}
+
+try_from! ( args: IBuildUnsignedTransactionRequest, BuildUnsignedTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IBuildUnsignedTransactionResponse,
+    r#"
+    /**
+     * Unsigned transaction along with the UTXO entries spent by its inputs.
+     * 
+     * @category Node RPC
+     */
+    export interface IBuildUnsignedTransactionResponse {
+        transaction : ITransaction;
+        utxoEntries : IUtxoEntry[];
+        fee : bigint;
+        mass : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: BuildUnsignedTransactionResponse, IBuildUnsignedTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetDagStatsRequest,
+    r#"
+    /**
+     * Request structural DAG stats over the last `windowSize` selected chain blocks.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetDagStatsRequest {
+        windowSize : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetDagStatsRequest, GetDagStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetDagStatsResponse,
+    r#"
+    /**
+     * Structural DAG stats over a window of recent selected chain blocks.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetDagStatsResponse {
+        tipCount : bigint;
+        chainBlockCount : bigint;
+        blockCount : bigint;
+        averageMergesetSize : number;
+        redBlockRate : number;
+        averageParents : number;
+    }
+    "#,
+}
+
+try_from! ( args: GetDagStatsResponse, IGetDagStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionByIdRequest,
+    r#"
+    /**
+     * Request the location and acceptance status of a transaction (requires `--txindex`).
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionByIdRequest {
+        transactionId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionByIdRequest, GetTransactionByIdRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionByIdResponse,
+    r#"
+    /**
+     * Location and acceptance status of a transaction accepted by the selected chain.
+     * `acceptingBlockHash` is not set and `confirmations` is zero if the transaction
+     * is no longer accepted.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionByIdResponse {
+        transactionId : HexString;
+        containingBlockHash : HexString;
+        acceptingBlockHash? : HexString;
+        isAccepted : boolean;
+        confirmations : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionByIdResponse, IGetTransactionByIdResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISampleChainBlocksRequest,
+    r#"
+    /**
+     * Request random selected chain blocks of a blue score range, each block being
+     * drawn, with replacement, with a probability proportional to the work it adds
+     * to the chain. The range must lie above the pruning point.
+     * 
+     * @category Node RPC
+     */
+    export interface ISampleChainBlocksRequest {
+        lowBlueScore : bigint;
+        highBlueScore : bigint;
+        count : number;
+    }
+    "#,
+}
+
+try_from! ( args: ISampleChainBlocksRequest, SampleChainBlocksRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISampleChainBlocksResponse,
+    r#"
+    /**
+     * Headers of the sampled blocks, in ascending blue score order.
+     * 
+     * @category Node RPC
+     */
+    export interface ISampleChainBlocksResponse {
+        headers : IHeader[];
+    }
+    "#,
+}
+
+try_from! ( args: SampleChainBlocksResponse, ISampleChainBlocksResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetConnectionsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetConnectionsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetConnectionsRequest, GetConnectionsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetConnectionsResponse,
+    r#"
+    /**
+     * P2P connections of the node along with the traffic they carried so far.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetConnectionsResponse {
+        connections : IConnectionInfo[];
+    }
+    "#,
+}
+
+try_from! ( args: GetConnectionsResponse, IGetConnectionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockByBlueScoreRequest,
+    r#"
+    /**
+     * Requests the lowest selected chain block having a blue score greater than or equal to `blueScore`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockByBlueScoreRequest {
+        blueScore : bigint;
+        verbosity : BlockVerbosity;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockByBlueScoreRequest, GetBlockByBlueScoreRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockByBlueScoreResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockByBlueScoreResponse {
+        block : IBlock;
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockByBlueScoreResponse, IGetBlockByBlueScoreResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockByDaaScoreRequest,
+    r#"
+    /**
+     * Requests the lowest selected chain block having a DAA score greater than or equal to `daaScore`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockByDaaScoreRequest {
+        daaScore : bigint;
+        verbosity : BlockVerbosity;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockByDaaScoreRequest, GetBlockByDaaScoreRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockByDaaScoreResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockByDaaScoreResponse {
+        block : IBlock;
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockByDaaScoreResponse, IGetBlockByDaaScoreResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetChainBlockByTimestampRequest,
+    r#"
+    /**
+     * Requests the selected chain block with the timestamp nearest to `timestamp`, a unix time in milliseconds.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByTimestampRequest {
+        timestamp : bigint;
+        verbosity : BlockVerbosity;
+    }
+    "#,
+}
+
+try_from! ( args: IGetChainBlockByTimestampRequest, GetChainBlockByTimestampRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetChainBlockByTimestampResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainBlockByTimestampResponse {
+        block : IBlock;
+    }
+    "#,
+}
+
+try_from! ( args: GetChainBlockByTimestampResponse, IGetChainBlockByTimestampResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetPruningPointUtxosRequest,
+    r#"
+    /**
+     * Requests a page of the UTXO set of the pruning point, in outpoint order.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetPruningPointUtxosRequest {
+        pruningPoint? : HexString;
+        cursor? : ITransactionOutpoint;
+        limit? : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetPruningPointUtxosRequest, GetPruningPointUtxosRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetPruningPointUtxosResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetPruningPointUtxosResponse {
+        pruningPoint : HexString;
+        utxoCommitment : HexString;
+        entries : IUtxoEntry[];
+        nextCursor? : ITransactionOutpoint;
+    }
+    "#,
+}
+
+try_from! ( args: GetPruningPointUtxosResponse, IGetPruningPointUtxosResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IGetMempoolInfoRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetMempoolInfoRequest { }
+    "#,
+}
+
+try_from! ( args: IGetMempoolInfoRequest, GetMempoolInfoRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMempoolInfoResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetMempoolInfoResponse {
+        transactionCount : bigint;
+        orphanCount : bigint;
+        policy : IMempoolPolicy;
+        minimumStandardTransactionVersion : number;
+        maximumStandardTransactionVersion : number;
+    }
+    "#,
+}
+
+try_from! ( args: GetMempoolInfoResponse, IGetMempoolInfoResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    ISetMempoolPolicyRequest,
+    r#"
+    /**
+     * Replaces the mempool policy of the node, within the bounds set by its configuration.
+     * 
+     * @category Node RPC
+     */
+    export interface ISetMempoolPolicyRequest {
+        policy : IMempoolPolicy;
+    }
+    "#,
+}
+
+try_from! ( args: ISetMempoolPolicyRequest, SetMempoolPolicyRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISetMempoolPolicyResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISetMempoolPolicyResponse {
+        policy : IMempoolPolicy;
+    }
+    "#,
+}
+
+try_from! ( args: SetMempoolPolicyResponse, ISetMempoolPolicyResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetCurrentBlockColorRequest,
+    r#"
+    /**
+     * Requests whether a block is currently blue or red from the perspective of the virtual.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetCurrentBlockColorRequest {
+        hash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetCurrentBlockColorRequest, GetCurrentBlockColorRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetCurrentBlockColorResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetCurrentBlockColorResponse {
+        blue : boolean;
+        mergingBlockHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBansRequest,
+    r#"
+    /**
+     * Requests the active bans along with their reason and remaining time.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBansRequest { }
+    "#,
+}
+
+try_from! ( args: IGetBansRequest, GetBansRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBansResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBansResponse {
+        bans : IBan[];
+    }
+    "#,
+}
+
+try_from! ( args: GetBansResponse, IGetBansResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetBlockProcessingStatusRequest,
+    r#"
+    /**
+     * Requests the processing status of a block submitted with `allowNonBlocking`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockProcessingStatusRequest {
+        ticket : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBlockProcessingStatusRequest, GetBlockProcessingStatusRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBlockProcessingStatusResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetBlockProcessingStatusResponse {
+        blockHash : HexString;
+        /**
+         * The submission report, undefined while the block is still being processed.
+         */
+        report? : ISubmitBlockReport;
+    }
+    "#,
+}
+
+try_from! ( args: GetBlockProcessingStatusResponse, IGetBlockProcessingStatusResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IStartJobRequest,
+    r#"
+    /**
+     * Starts a long-running node operation in the background.
+     * 
+     * @category Node RPC
+     */
+    export interface IStartJobRequest {
+        kind : "utxoIndexResync" | "txIndexResync";
+    }
+    "#,
+}
+
+try_from! ( args: IStartJobRequest, StartJobRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IStartJobResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IStartJobResponse {
+        jobId : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: StartJobResponse, IStartJobResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetJobStatusRequest,
+    r#"
+    /**
+     * Requests the status of a job started with {@link RpcClient.startJob}.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetJobStatusRequest {
+        jobId : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetJobStatusRequest, GetJobStatusRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetJobStatusResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetJobStatusResponse {
+        job : IJob;
+    }
+    "#,
+}
+
+try_from! ( args: GetJobStatusResponse, IGetJobStatusResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetNotificationStatsRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetNotificationStatsRequest { }
+    "#,
+}
+
+try_from! ( args: IGetNotificationStatsRequest, GetNotificationStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetNotificationStatsResponse,
+    r#"
+    /**
+     * Notification delivery statistics of the node notifiers.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetNotificationStatsResponse {
+        notifiers : INotifierStats[];
+    }
+    "#,
+}
+
+try_from! ( args: GetNotificationStatsResponse, IGetNotificationStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGenerateBlocksRequest,
+    r#"
+    /**
+     * Mines blocks paying to `payAddress` (simnet only).
+     * 
+     * @category Node RPC
+     */
+    export interface IGenerateBlocksRequest {
+        payAddress : Address | string;
+        count : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGenerateBlocksRequest, GenerateBlocksRequest, {
+    let pay_address = args.get_cast::<Address>("payAddress")?.into_owned();
+    let count = args.get_u64("count")?;
+    Ok(GenerateBlocksRequest { pay_address, count })
+});
+
+declare! {
+    IGenerateBlocksResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGenerateBlocksResponse {
+        blockHashes : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: GenerateBlocksResponse, IGenerateBlocksResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetServerCapabilitiesRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetServerCapabilitiesRequest { }
+    "#,
+}
+
+try_from! ( args: IGetServerCapabilitiesRequest, GetServerCapabilitiesRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetServerCapabilitiesResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetServerCapabilitiesResponse {
+        rpcApiVersion : number[];
+        methods : string[];
+        notifications : string[];
+    }
+    "#,
+}
+
+try_from! ( args: GetServerCapabilitiesResponse, IGetServerCapabilitiesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IUpdateBlockTemplateExclusionsRequest,
+    r#"
+    /**
+     * Script public keys are hex strings of their version, as 2 big-endian bytes, followed by their script.
+     *
+     * @category Node RPC
+     */
+    export interface IUpdateBlockTemplateExclusionsRequest {
+        addTransactionIds? : HexString[];
+        removeTransactionIds? : HexString[];
+        addScriptPublicKeys? : HexString[];
+        removeScriptPublicKeys? : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: IUpdateBlockTemplateExclusionsRequest, UpdateBlockTemplateExclusionsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IUpdateBlockTemplateExclusionsResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IUpdateBlockTemplateExclusionsResponse {
+        transactionIds : HexString[];
+        scriptPublicKeys : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: UpdateBlockTemplateExclusionsResponse, IUpdateBlockTemplateExclusionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetChainQualityRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetChainQualityRequest {
+        /**
+         * Unix timestamp in milliseconds after which the returned samples were taken, 0 for all the retained samples.
+         */
+        since? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetChainQualityRequest, GetChainQualityRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetChainQualityResponse,
+    r#"
+    /**
+     * Rolling chain-quality samples, oldest first.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetChainQualityResponse {
+        samples : IChainQualitySample[];
+    }
+    "#,
+}
+
+try_from! ( args: GetChainQualityResponse, IGetChainQualityResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetCurrentNetworkRequest,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetCurrentNetworkRequest { }
+    "#,
+}
+
+try_from! ( args: IGetCurrentNetworkRequest, GetCurrentNetworkRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetCurrentNetworkResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetCurrentNetworkResponse {
         network : string;
+        networkId? : string;
     }
     "#,
 }
@@ -902,7 +2246,8 @@ declare! {
     IGetHeadersRequest,
     r#"
     /**
-     * 
+     * Requests the headers of up to `limit` selected chain blocks, starting at `startHash`
+     * and going towards the sink if `isAscending` or towards the pruning point otherwise.
      * 
      * @category Node RPC
      */
@@ -949,6 +2294,19 @@ declare! {
     export interface IGetMempoolEntriesRequest {
         includeOrphanPool? : boolean;
         filterTransactionPool? : boolean;
+        /**
+         * Minimum fee rate (in sompi per gram of mass) of the returned transactions.
+         * Orphan transactions have no known fee rate and are excluded by this filter.
+         */
+        minFeeRate? : number;
+        /**
+         * Maximum age, in DAA score units, of the returned transactions.
+         */
+        maxAgeDaaScore? : bigint;
+        /**
+         * Return only transactions having an output spendable by one of these addresses.
+         */
+        spendableByAddresses? : Address[] | string[];
     }
     "#,
 }
@@ -1105,7 +2463,15 @@ declare! {
      * @category Node RPC
      */
     export interface IGetUtxosByAddressesRequest { 
-        addresses : Address[] | string[]
+        addresses : Address[] | string[];
+        /**
+         * Start the page right after this entry, as returned in the 'nextCursor' of the previous page
+         */
+        cursor? : IUtxosByAddressesCursor;
+        /**
+         * Maximum number of entries to return
+         */
+        limit? : number;
     }
     "#,
 }
@@ -1113,7 +2479,7 @@ declare! {
 try_from! ( args: IGetUtxosByAddressesRequest, GetUtxosByAddressesRequest, {
     let js_value = JsValue::from(args);
     let request = if let Ok(addresses) = Vec::<Address>::try_from(AddressOrStringArrayT::from(js_value.clone())) {
-        GetUtxosByAddressesRequest { addresses }
+        GetUtxosByAddressesRequest::new(addresses)
     } else {
         from_value::<GetUtxosByAddressesRequest>(js_value)?
     };
@@ -1130,16 +2496,23 @@ declare! {
      */
     export interface IGetUtxosByAddressesResponse {
         entries : IUtxoEntry[];
+        /**
+         * Set when the page is full, to be sent as the cursor of the next page request
+         */
+        nextCursor? : IUtxosByAddressesCursor;
     }
     "#,
 }
 
 try_from! ( args: GetUtxosByAddressesResponse, IGetUtxosByAddressesResponse, {
-    let GetUtxosByAddressesResponse { entries } = args;
+    let GetUtxosByAddressesResponse { entries, next_cursor } = args;
     let entries = entries.into_iter().map(UtxoEntryReference::from).collect::<Vec<UtxoEntryReference>>();
     let entries = js_sys::Array::from_iter(entries.into_iter().map(JsValue::from));
     let response = IGetUtxosByAddressesResponse::default();
     response.set("entries", entries.as_ref())?;
+    if let Some(next_cursor) = next_cursor {
+        response.set("nextCursor", &to_value(&next_cursor)?)?;
+    }
     Ok(response)
 });
 
@@ -1156,6 +2529,10 @@ declare! {
     export interface IGetVirtualChainFromBlockRequest {
         startHash : HexString;
         includeAcceptedTransactionIds: boolean;
+        /**
+         * Maximum number of added chain blocks returned in a single batch
+         */
+        maxBlocks? : number;
     }
     "#,
 }
@@ -1176,6 +2553,11 @@ declare! {
         removedChainBlockHashes : HexString[];
         addedChainBlockHashes : HexString[];
         acceptedTransactionIds : IAcceptedTransactionIds[];
+        /**
+         * Set when the added chain blocks were truncated to 'maxBlocks', to be used as
+         * the start hash of the request of the next batch
+         */
+        nextStartHash? : HexString;
     }
     "#,
 }
@@ -1233,6 +2615,11 @@ declare! {
     export interface ISubmitBlockRequest {
         block : IBlock;
         allowNonDAABlocks: boolean;
+        /**
+         * Returns a ticket right away instead of waiting for the block processing,
+         * see {@link IGetBlockProcessingStatusRequest}.
+         */
+        allowNonBlocking? : boolean;
     }
     "#,
 }
@@ -1282,6 +2669,10 @@ declare! {
      */
     export interface ISubmitBlockResponse {
         report : ISubmitBlockReport;
+        /**
+         * Ticket of a block submitted with `allowNonBlocking`.
+         */
+        ticket? : bigint;
     }
     "#,
 }
@@ -1303,23 +2694,30 @@ declare! {
      */
     export interface ISubmitTransactionRequest {
         transaction : Transaction,
-        allowOrphan? : boolean
+        allowOrphan? : boolean,
+        /**
+         * Replace the mempool transactions double spent by the transaction
+         * if it pays a sufficiently higher feerate (replace by fee).
+         */
+        replace? : boolean
     }
     "#,
 }
 
 try_from! ( args: ISubmitTransactionRequest, SubmitTransactionRequest, {
-    let (transaction, allow_orphan) = if let Some(transaction) = args.try_get_value("transaction")? {
+    let (transaction, allow_orphan, replace) = if let Some(transaction) = args.try_get_value("transaction")? {
         let allow_orphan = args.try_get_bool("allowOrphan")?.unwrap_or(false);
-        (transaction, allow_orphan)
+        let replace = args.try_get_bool("replace")?.unwrap_or(false);
+        (transaction, allow_orphan, replace)
     } else {
-        (args.into(), false)
+        (args.into(), false, false)
     };
 
     let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
         SubmitTransactionRequest {
             transaction : transaction.into(),
             allow_orphan,
+            replace,
         }
     } else {
         from_value(transaction)?
@@ -1347,6 +2745,55 @@ try_from! ( args: SubmitTransactionResponse, ISubmitTransactionResponse, {
 
 // ---
 
+declare! {
+    ISubmitTransactionsRequest,
+    r#"
+    /**
+     * Submit a list of transactions, possibly chained, to the node.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionsRequest {
+        transactions : Transaction[],
+        allowOrphan? : boolean
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionsRequest, SubmitTransactionsRequest, {
+    let allow_orphan = args.try_get_bool("allowOrphan")?.unwrap_or(false);
+    let transactions = args
+        .try_get_value("transactions")?
+        .ok_or_else(|| Error::MissingRpcFieldError("SubmitTransactionsRequest".to_string(), "transactions".to_string()))?;
+    let transactions = js_sys::Array::from(&transactions)
+        .iter()
+        .map(|transaction| {
+            Transaction::try_owned_from(&transaction).map(|transaction| transaction.into()).map_err(|err| Error::General(err.to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SubmitTransactionsRequest { transactions, allow_orphan })
+});
+
+declare! {
+    ISubmitTransactionsResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionsResponse {
+        results : { transactionId : HexString, error? : string }[];
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionsResponse, ISubmitTransactionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IUnbanRequest,
     r#"
@@ -1360,6 +2807,10 @@ declare! {
          * IPv4 or IPv6 address to unban.
          */
         ip : string;
+        /**
+         * When set, lifts the ban of the whole `ip/prefixLength` network instead of the single `ip`.
+         */
+        prefixLength? : number;
     }
     "#,
 }
@@ -37,13 +37,23 @@ pub enum Error {
     #[error("Missing response payload")]
     MissingResponsePayload,
 
+    /// The server does not know the request, most probably added by a later version of the protocol
+    #[error("The server does not support the request")]
+    UnsupportedRequest,
+
     #[error("Not connected to server")]
     NotConnected,
+
+    #[error("A client pool needs at least one client")]
+    EmptyPool,
 }
 
 impl From<Error> for RpcError {
     fn from(value: Error) -> Self {
-        RpcError::General(value.to_string())
+        match value {
+            Error::UnsupportedRequest => RpcError::UnsupportedFeature,
+            value => RpcError::General(value.to_string()),
+        }
     }
 }
 
@@ -20,7 +20,7 @@ use kaspa_notify::{
     events::{EventArray, EventType, EVENT_TYPE_ARRAY},
     listener::{ListenerId, ListenerLifespan},
     notifier::{DynNotify, Notifier},
-    scope::Scope,
+    scope::{Scope, UtxosChangedScope},
     subscriber::{Subscriber, SubscriptionManager},
     subscription::{
         array::ArrayBuilder, context::SubscriptionContext, Command, DynSubscription, MutateSingle, Mutation, MutationPolicies,
@@ -40,6 +40,7 @@ use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{measure_request_body_size_layer, CountBytesBody, MapResponseBodyLayer, ServiceBuilder},
 };
+pub use pool::GrpcClientPool;
 use regex::Regex;
 use std::{
     sync::{
@@ -60,6 +61,7 @@ mod resolver;
 mod route;
 
 mod client_pool;
+mod pool;
 
 pub type GrpcClientCollector = CollectorFrom<RpcCoreConverter>;
 pub type GrpcClientNotify = DynNotify<Notification>;
@@ -104,7 +106,8 @@ impl GrpcClient {
     /// `subscription_context`: it is advised to provide a clone of the same instance if multiple clients dealing with
     /// [`UtxosChangedNotifications`] are connected concurrently in order to optimize the memory footprint.
     ///
-    /// `reconnect`: features an automatic reconnection to the server, reactivating all subscriptions on success.
+    /// `reconnect`: features an automatic reconnection to the server with exponential backoff, reactivating all subscriptions
+    /// on success. A failed reactivation is retried until it succeeds.
     ///
     /// `connection_event_sender`: when provided will notify of connection and disconnection events via the channel.
     ///
@@ -253,6 +256,7 @@ impl RpcApi for GrpcClient {
     route!(get_connected_peer_info_call, GetConnectedPeerInfo);
     route!(add_peer_call, AddPeer);
     route!(submit_transaction_call, SubmitTransaction);
+    route!(submit_transactions_call, SubmitTransactions);
     route!(get_subnetwork_call, GetSubnetwork);
     route!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
     route!(get_blocks_call, GetBlocks);
@@ -271,6 +275,33 @@ impl RpcApi for GrpcClient {
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(get_transaction_mass_call, GetTransactionMass);
+    route!(build_unsigned_transaction_call, BuildUnsignedTransaction);
+    route!(get_dag_stats_call, GetDagStats);
+    route!(get_health_call, GetHealth);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(get_subscriptions_call, GetSubscriptions);
+    route!(get_virtual_parents_call, GetVirtualParents);
+    route!(get_consensus_params_call, GetConsensusParams);
+    route!(get_transaction_by_id_call, GetTransactionById);
+    route!(sample_chain_blocks_call, SampleChainBlocks);
+    route!(get_connections_call, GetConnections);
+    route!(get_block_by_blue_score_call, GetBlockByBlueScore);
+    route!(get_block_by_daa_score_call, GetBlockByDaaScore);
+    route!(get_chain_block_by_timestamp_call, GetChainBlockByTimestamp);
+    route!(get_pruning_point_utxos_call, GetPruningPointUtxos);
+    route!(get_mempool_info_call, GetMempoolInfo);
+    route!(set_mempool_policy_call, SetMempoolPolicy);
+    route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_bans_call, GetBans);
+    route!(get_block_processing_status_call, GetBlockProcessingStatus);
+    route!(start_job_call, StartJob);
+    route!(get_job_status_call, GetJobStatus);
+    route!(get_notification_stats_call, GetNotificationStats);
+    route!(generate_blocks_call, GenerateBlocks);
+    route!(get_server_capabilities_call, GetServerCapabilities);
+    route!(update_block_template_exclusions_call, UpdateBlockTemplateExclusions);
+    route!(get_chain_quality_call, GetChainQuality);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -309,7 +340,17 @@ impl RpcApi for GrpcClient {
             NotificationMode::Direct => {
                 if self.inner.will_reconnect() {
                     let event = scope.event_type();
-                    self.subscriptions.as_ref().unwrap().lock().await[event].mutate(
+                    let mut subscriptions = self.subscriptions.as_ref().unwrap().lock().await;
+                    // The mirrored subscriptions are only renewed on reconnection, so a replacement can simply
+                    // clear the address set first
+                    if let Scope::UtxosChanged(UtxosChangedScope { replace: true, .. }) = scope {
+                        subscriptions[event].mutate(
+                            Mutation::new(Command::Stop, UtxosChangedScope::default().into()),
+                            self.policies,
+                            &self.subscription_context,
+                        )?;
+                    }
+                    subscriptions[event].mutate(
                         Mutation::new(Command::Start, scope.clone()),
                         self.policies,
                         &self.subscription_context,
@@ -351,6 +392,12 @@ pub const CONNECT_TIMEOUT_DURATION: u64 = 20_000;
 pub const REQUEST_TIMEOUT_DURATION: u64 = 5_000;
 pub const TIMEOUT_MONITORING_INTERVAL: u64 = 10_000;
 pub const RECONNECT_INTERVAL: u64 = 2_000;
+pub const MAX_RECONNECT_INTERVAL: u64 = 60_000;
+
+/// Returns the delay before the next reconnection attempt following a failed attempt after `interval` ms
+fn next_reconnect_interval(interval: u64) -> u64 {
+    interval.saturating_mul(2).min(MAX_RECONNECT_INTERVAL)
+}
 
 type KaspadRequestSender = async_channel::Sender<KaspadRequest>;
 type KaspadRequestReceiver = async_channel::Receiver<KaspadRequest>;
@@ -362,7 +409,8 @@ struct ServerFeatures {
 }
 
 /// A struct to handle messages flowing to (requests) and from (responses) a protowire server.
-/// Incoming responses are associated to pending requests based on their matching operation
+/// Incoming responses are associated to pending requests by the request id the server echoes.
+/// With a server not echoing ids, they are associated based on their matching operation
 /// type and, for some operations like [`ClientApiOps::GetBlock`], on their properties.
 ///
 /// Data flow:
@@ -424,6 +472,7 @@ struct Inner {
     connector_is_running: AtomicBool,
     connector_shutdown: DuplexTrigger,
     connector_timer_interval: u64,
+    subscriptions_renewal_pending: AtomicBool,
 
     // Connection event channel
     connection_event_sender: Option<Sender<ConnectionEvent>>,
@@ -467,6 +516,7 @@ impl Inner {
             connector_is_running: AtomicBool::new(false),
             connector_shutdown: DuplexTrigger::new(),
             connector_timer_interval: RECONNECT_INTERVAL,
+            subscriptions_renewal_pending: AtomicBool::new(false),
             connection_event_sender,
             override_handle_stop_notify,
             counters,
@@ -617,7 +667,20 @@ impl Inner {
 
         // Start the response receiving task
         self.clone().spawn_response_receiver_task(stream);
+        debug!("GRPC client: reconnected");
+
+        // The server knows nothing of our subscriptions until they are all renewed
+        self.subscriptions_renewal_pending.store(true, Ordering::SeqCst);
+        self.renew_subscriptions(notifier, subscriptions, subscription_context).await
+    }
 
+    /// Re-registers all active subscriptions on the server, keeping the renewal pending on failure
+    async fn renew_subscriptions(
+        &self,
+        notifier: Option<Arc<GrpcClientNotifier>>,
+        subscriptions: Option<Arc<DirectSubscriptions>>,
+        subscription_context: &SubscriptionContext,
+    ) -> RpcResult<()> {
         // Re-register the compounded subscription state of the notifier in MultiListener mode
         if let Some(notifier) = notifier.as_ref() {
             notifier.try_renew_subscriptions()?;
@@ -628,12 +691,13 @@ impl Inner {
             let subscriptions = subscriptions.lock().await;
             for event in EVENT_TYPE_ARRAY {
                 if subscriptions[event].active() {
-                    self.clone().start_notify_to_client(subscriptions[event].scope(subscription_context)).await?;
+                    self.start_notify_to_client(subscriptions[event].scope(subscription_context)).await?;
                 }
             }
         }
 
-        debug!("GRPC client: reconnected");
+        self.subscriptions_renewal_pending.store(false, Ordering::SeqCst);
+        debug!("GRPC client: subscriptions renewed");
         Ok(())
     }
 
@@ -688,7 +752,12 @@ impl Inner {
             if request.payload.is_some() {
                 let receiver = self.resolver().register_request(op, &request);
                 self.request_sender.send(request).await.map_err(|_| Error::ChannelRecvError)?;
-                receiver.await?
+                let response = receiver.await??;
+                // The server answers a request having a payload it does not know with an empty payload
+                match response.payload {
+                    Some(_) => Ok(response),
+                    None => Err(Error::UnsupportedRequest),
+                }
             } else {
                 Err(Error::MissingRequestPayload)
             }
@@ -824,22 +893,29 @@ impl Inner {
             trace!("GRPC client: connection monitor task - started");
             let shutdown = self.connector_shutdown.request.listener.clone().fuse();
             pin_mut!(shutdown);
+            // Delay before the next check, doubled on every failed attempt up to a maximum and reset on success
+            let mut connector_timer_interval = self.connector_timer_interval;
             loop {
-                let connector_timer_interval = Duration::from_millis(self.connector_timer_interval);
-                let delay = tokio::time::sleep(connector_timer_interval).fuse();
+                let delay = tokio::time::sleep(Duration::from_millis(connector_timer_interval)).fuse();
                 pin_mut!(delay);
                 select! {
                     _ = shutdown => { break; },
                     _ = delay => {
                         trace!("GRPC client: connection monitor task - running");
-                        if !self.is_connected() {
-                            match self.clone().reconnect(notifier.clone(), subscriptions.clone(), &subscription_context).await {
-                                Ok(_) => {
-                                    trace!("GRPC client: reconnection to server succeeded");
-                                },
-                                Err(err) => {
-                                    trace!("GRPC client: reconnection to server failed with error {err:?}");
-                                }
+                        let result = if !self.is_connected() {
+                            self.clone().reconnect(notifier.clone(), subscriptions.clone(), &subscription_context).await
+                        } else if self.subscriptions_renewal_pending.load(Ordering::SeqCst) {
+                            self.renew_subscriptions(notifier.clone(), subscriptions.clone(), &subscription_context).await
+                        } else {
+                            Ok(())
+                        };
+                        match result {
+                            Ok(_) => {
+                                connector_timer_interval = self.connector_timer_interval;
+                            },
+                            Err(err) => {
+                                connector_timer_interval = next_reconnect_interval(connector_timer_interval);
+                                debug!("GRPC client: reconnection to server failed with error {err:?}, next attempt in {connector_timer_interval} ms");
                             }
                         }
                     },
@@ -873,6 +949,10 @@ impl Inner {
             }
         } else if response.payload.is_some() {
             self.resolver().handle_response(response);
+        } else if response.id != 0 && self.handle_message_id() {
+            // The server did not recognize the request and echoed its id, so the pending call can fail right away
+            trace!("GRPC client: handle_response received an empty response to request {}", response.id);
+            self.resolver().handle_response(response);
         }
     }
 
@@ -944,3 +1024,17 @@ impl SubscriptionManager for Inner {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_reconnect_interval() {
+        let intervals = std::iter::successors(Some(RECONNECT_INTERVAL), |&interval| Some(next_reconnect_interval(interval)))
+            .take(8)
+            .collect::<Vec<_>>();
+        assert_eq!(intervals, [2_000, 4_000, 8_000, 16_000, 32_000, 60_000, 60_000, 60_000]);
+        assert_eq!(next_reconnect_interval(u64::MAX), MAX_RECONNECT_INTERVAL);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{
+    error::{Error, Result},
+    GrpcClient,
+};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use kaspa_notify::{listener::ListenerId, scope::Scope, subscription::context::SubscriptionContext};
+use kaspa_rpc_core::{
+    api::rpc::RpcApi,
+    error::RpcResult,
+    model::message::*,
+    notify::{connection::ChannelConnection, mode::NotificationMode},
+};
+use kaspa_utils_tower::counters::TowerConnectionCounters;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+struct PooledClient {
+    client: GrpcClient,
+    in_flight: AtomicUsize,
+}
+
+/// Keeps a request accounted as in flight on a pooled client until dropped
+struct InFlightGuard<'a> {
+    pooled: &'a PooledClient,
+}
+
+impl InFlightGuard<'_> {
+    fn client(&self) -> &GrpcClient {
+        &self.pooled.client
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.pooled.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A pool of gRPC clients connected to the same server, each over its own connection.
+///
+/// Unary requests are load-balanced across the pool, each going to the connected client having the fewest
+/// requests in flight, ties being broken in a round-robin fashion. This suits consumers issuing a high rate
+/// of concurrent requests that a single connection would serialize.
+///
+/// Notifications are handled by the first client of the pool only.
+pub struct GrpcClientPool {
+    clients: Vec<PooledClient>,
+    next: AtomicUsize,
+}
+
+impl GrpcClientPool {
+    pub fn new(clients: Vec<GrpcClient>) -> Result<Self> {
+        if clients.is_empty() {
+            return Err(Error::EmptyPool);
+        }
+        let clients = clients.into_iter().map(|client| PooledClient { client, in_flight: AtomicUsize::new(0) }).collect();
+        Ok(Self { clients, next: AtomicUsize::new(0) })
+    }
+
+    pub async fn connect(url: String, size: usize) -> Result<Self> {
+        Self::connect_with_args(size, NotificationMode::Direct, url, None, false, None, Default::default()).await
+    }
+
+    /// Connects a pool of `size` clients to a gRPC server.
+    ///
+    /// The first client gets the provided `notification_mode` and handles all notifications, the others are
+    /// in `Direct` mode. See [`GrpcClient::connect_with_args`] for the other arguments, which apply to every client.
+    pub async fn connect_with_args(
+        size: usize,
+        notification_mode: NotificationMode,
+        url: String,
+        subscription_context: Option<SubscriptionContext>,
+        reconnect: bool,
+        timeout_duration: Option<u64>,
+        counters: Arc<TowerConnectionCounters>,
+    ) -> Result<Self> {
+        let clients = try_join_all((0..size).map(|i| {
+            let notification_mode = if i == 0 { notification_mode } else { NotificationMode::Direct };
+            GrpcClient::connect_with_args(
+                notification_mode,
+                url.clone(),
+                subscription_context.clone(),
+                reconnect,
+                None,
+                false,
+                timeout_duration,
+                counters.clone(),
+            )
+        }))
+        .await?;
+        Self::new(clients)
+    }
+
+    /// Returns the number of clients in the pool
+    pub fn size(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns the client handling the notifications
+    pub fn notification_client(&self) -> &GrpcClient {
+        &self.clients[0].client
+    }
+
+    /// Returns whether at least one client of the pool is connected
+    pub fn is_connected(&self) -> bool {
+        self.clients.iter().any(|x| x.client.is_connected())
+    }
+
+    /// Returns the number of requests currently in flight on each client of the pool
+    pub fn in_flight(&self) -> Vec<usize> {
+        self.clients.iter().map(|x| x.in_flight.load(Ordering::Relaxed)).collect()
+    }
+
+    pub async fn disconnect(&self) -> Result<()> {
+        try_join_all(self.clients.iter().map(|x| x.client.disconnect())).await?;
+        Ok(())
+    }
+
+    fn acquire(&self) -> InFlightGuard<'_> {
+        let len = self.clients.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let index = (0..len)
+            .map(|i| (start + i) % len)
+            .min_by_key(|&i| (!self.clients[i].client.is_connected(), self.clients[i].in_flight.load(Ordering::Relaxed)))
+            .unwrap();
+        let pooled = &self.clients[index];
+        pooled.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard { pooled }
+    }
+}
+
+#[async_trait]
+impl RpcApi for GrpcClientPool {
+    pool_route!(ping_call, Ping);
+    pool_route!(get_sync_status_call, GetSyncStatus);
+    pool_route!(get_server_info_call, GetServerInfo);
+    pool_route!(get_metrics_call, GetMetrics);
+    pool_route!(submit_block_call, SubmitBlock);
+    pool_route!(get_block_template_call, GetBlockTemplate);
+    pool_route!(get_block_call, GetBlock);
+    pool_route!(get_info_call, GetInfo);
+    pool_route!(get_current_network_call, GetCurrentNetwork);
+    pool_route!(get_peer_addresses_call, GetPeerAddresses);
+    pool_route!(get_sink_call, GetSink);
+    pool_route!(get_mempool_entry_call, GetMempoolEntry);
+    pool_route!(get_mempool_entries_call, GetMempoolEntries);
+    pool_route!(get_connected_peer_info_call, GetConnectedPeerInfo);
+    pool_route!(add_peer_call, AddPeer);
+    pool_route!(submit_transaction_call, SubmitTransaction);
+    pool_route!(submit_transactions_call, SubmitTransactions);
+    pool_route!(get_subnetwork_call, GetSubnetwork);
+    pool_route!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
+    pool_route!(get_blocks_call, GetBlocks);
+    pool_route!(get_block_count_call, GetBlockCount);
+    pool_route!(get_block_dag_info_call, GetBlockDagInfo);
+    pool_route!(resolve_finality_conflict_call, ResolveFinalityConflict);
+    pool_route!(shutdown_call, Shutdown);
+    pool_route!(get_headers_call, GetHeaders);
+    pool_route!(get_utxos_by_addresses_call, GetUtxosByAddresses);
+    pool_route!(get_balance_by_address_call, GetBalanceByAddress);
+    pool_route!(get_balances_by_addresses_call, GetBalancesByAddresses);
+    pool_route!(get_sink_blue_score_call, GetSinkBlueScore);
+    pool_route!(ban_call, Ban);
+    pool_route!(unban_call, Unban);
+    pool_route!(estimate_network_hashes_per_second_call, EstimateNetworkHashesPerSecond);
+    pool_route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
+    pool_route!(get_coin_supply_call, GetCoinSupply);
+    pool_route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    pool_route!(get_transaction_mass_call, GetTransactionMass);
+    pool_route!(build_unsigned_transaction_call, BuildUnsignedTransaction);
+    pool_route!(get_dag_stats_call, GetDagStats);
+    pool_route!(get_health_call, GetHealth);
+    pool_route!(get_fee_estimate_call, GetFeeEstimate);
+    pool_route!(get_subscriptions_call, GetSubscriptions);
+    pool_route!(get_virtual_parents_call, GetVirtualParents);
+    pool_route!(get_consensus_params_call, GetConsensusParams);
+    pool_route!(get_transaction_by_id_call, GetTransactionById);
+    pool_route!(sample_chain_blocks_call, SampleChainBlocks);
+    pool_route!(get_connections_call, GetConnections);
+    pool_route!(get_block_by_blue_score_call, GetBlockByBlueScore);
+    pool_route!(get_block_by_daa_score_call, GetBlockByDaaScore);
+    pool_route!(get_chain_block_by_timestamp_call, GetChainBlockByTimestamp);
+    pool_route!(get_pruning_point_utxos_call, GetPruningPointUtxos);
+    pool_route!(get_mempool_info_call, GetMempoolInfo);
+    pool_route!(set_mempool_policy_call, SetMempoolPolicy);
+    pool_route!(get_current_block_color_call, GetCurrentBlockColor);
+    pool_route!(get_bans_call, GetBans);
+    pool_route!(get_block_processing_status_call, GetBlockProcessingStatus);
+    pool_route!(start_job_call, StartJob);
+    pool_route!(get_job_status_call, GetJobStatus);
+    pool_route!(get_notification_stats_call, GetNotificationStats);
+    pool_route!(generate_blocks_call, GenerateBlocks);
+    pool_route!(get_server_capabilities_call, GetServerCapabilities);
+    pool_route!(update_block_template_exclusions_call, UpdateBlockTemplateExclusions);
+    pool_route!(get_chain_quality_call, GetChainQuality);
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Notification API
+
+    fn register_new_listener(&self, connection: ChannelConnection) -> ListenerId {
+        self.notification_client().register_new_listener(connection)
+    }
+
+    async fn unregister_listener(&self, id: ListenerId) -> RpcResult<()> {
+        self.notification_client().unregister_listener(id).await
+    }
+
+    async fn start_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+        self.notification_client().start_notify(id, scope).await
+    }
+
+    async fn stop_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+        self.notification_client().stop_notify(id, scope).await
+    }
+}
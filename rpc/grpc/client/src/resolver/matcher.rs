@@ -31,6 +31,11 @@ impl Matcher<&kaspad_response::Payload> for kaspad_request::Payload {
 
 impl Matcher<&KaspadResponse> for KaspadRequest {
     fn is_matching(&self, response: &KaspadResponse) -> bool {
+        // A server echoing the request ids lets the response be correlated to its request unambiguously,
+        // otherwise fall back to matching the payloads
+        if self.id != 0 && response.id != 0 {
+            return self.id == response.id;
+        }
         if let Some(ref response) = response.payload {
             if let Some(ref request) = self.payload {
                 return request.is_matching(response);
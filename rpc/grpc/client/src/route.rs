@@ -31,3 +31,24 @@ macro_rules! route {
         }
     };
 }
+
+macro_rules! pool_route {
+    ($fn:ident, $name:tt) => {
+        paste::paste! {
+            #[allow(clippy::type_complexity, clippy::type_repetition_in_bounds)]
+            fn $fn<'life0, 'async_trait>(
+                &'life0 self,
+                request: [<$name Request>],
+            ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = RpcResult<[<$name Response>]>> + ::core::marker::Send + 'async_trait>>
+            where
+                'life0: 'async_trait,
+                Self: 'async_trait,
+            {
+                Box::pin(async move {
+                    let pooled = self.acquire();
+                    pooled.client().$fn(request).await
+                })
+            }
+        }
+    };
+}
@@ -10,6 +10,14 @@ from!(item: &kaspa_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalances
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::RpcPaymentOutput, protowire::RpcPaymentOutput, {
+    Self { address: (&item.address).into(), amount: item.amount }
+});
+
+from!(item: &kaspa_rpc_core::RpcUtxosByAddressesCursor, protowire::RpcUtxosByAddressesCursor, {
+    Self { address: (&item.address).into(), outpoint: Some((&item.outpoint).into()) }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +26,18 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, kaspa_rpc_core::RpcBala
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+try_from!(item: &protowire::RpcPaymentOutput, kaspa_rpc_core::RpcPaymentOutput, {
+    Self { address: item.address.as_str().try_into()?, amount: item.amount }
+});
+
+try_from!(item: &protowire::RpcUtxosByAddressesCursor, kaspa_rpc_core::RpcUtxosByAddressesCursor, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        outpoint: item
+            .outpoint
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("UtxosByAddressesCursor".to_string(), "outpoint".to_string()))?
+            .try_into()?,
+    }
+});
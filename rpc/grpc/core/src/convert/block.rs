@@ -1,6 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::{RpcError, RpcHash};
+use kaspa_rpc_core::{RpcBlockVerbosity, RpcError, RpcHash, RpcResult, RpcTemplateTxOrdering};
 use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
@@ -27,6 +27,22 @@ from!(item: &kaspa_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseData
         merge_set_blues_hashes: item.merge_set_blues_hashes.iter().map(|x| x.to_string()).collect(),
         merge_set_reds_hashes: item.merge_set_reds_hashes.iter().map(|x| x.to_string()).collect(),
         is_chain_block: item.is_chain_block,
+        confirmations: item.confirmations,
+    }
+});
+
+from!(item: &RpcBlockVerbosity, protowire::RpcBlockVerbosity, {
+    match item {
+        RpcBlockVerbosity::HeaderOnly => protowire::RpcBlockVerbosity::HeaderOnly,
+        RpcBlockVerbosity::TransactionIds => protowire::RpcBlockVerbosity::TransactionIds,
+        RpcBlockVerbosity::Full => protowire::RpcBlockVerbosity::Full,
+    }
+});
+
+from!(item: &RpcTemplateTxOrdering, protowire::RpcTemplateTxOrdering, {
+    match item {
+        RpcTemplateTxOrdering::Selection => protowire::RpcTemplateTxOrdering::Selection,
+        RpcTemplateTxOrdering::Deterministic => protowire::RpcTemplateTxOrdering::Deterministic,
     }
 });
 
@@ -34,6 +50,28 @@ from!(item: &kaspa_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseData
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
 
+/// Converts the verbosity of a block request, `None` standing for an unspecified verbosity
+pub fn try_optional_block_verbosity(verbosity: i32) -> RpcResult<Option<RpcBlockVerbosity>> {
+    match protowire::RpcBlockVerbosity::try_from(verbosity).map_err(|_| RpcError::PrimitiveToEnumConversionError)? {
+        protowire::RpcBlockVerbosity::Unspecified => Ok(None),
+        protowire::RpcBlockVerbosity::HeaderOnly => Ok(Some(RpcBlockVerbosity::HeaderOnly)),
+        protowire::RpcBlockVerbosity::TransactionIds => Ok(Some(RpcBlockVerbosity::TransactionIds)),
+        protowire::RpcBlockVerbosity::Full => Ok(Some(RpcBlockVerbosity::Full)),
+    }
+}
+
+/// Resolves the verbosity of a block request, falling back to the legacy `includeTransactions` flag when unspecified
+pub fn try_block_verbosity(verbosity: i32, include_transactions: bool) -> RpcResult<RpcBlockVerbosity> {
+    Ok(try_optional_block_verbosity(verbosity)?.unwrap_or(RpcBlockVerbosity::from_include_transactions(include_transactions)))
+}
+
+pub fn try_template_tx_ordering(tx_ordering: i32) -> RpcResult<RpcTemplateTxOrdering> {
+    match protowire::RpcTemplateTxOrdering::try_from(tx_ordering).map_err(|_| RpcError::PrimitiveToEnumConversionError)? {
+        protowire::RpcTemplateTxOrdering::Selection => Ok(RpcTemplateTxOrdering::Selection),
+        protowire::RpcTemplateTxOrdering::Deterministic => Ok(RpcTemplateTxOrdering::Deterministic),
+    }
+}
+
 try_from!(item: &protowire::RpcBlock, kaspa_rpc_core::RpcBlock, {
     Self {
         header: item
@@ -74,5 +112,6 @@ try_from!(item: &protowire::RpcBlockVerboseData, kaspa_rpc_core::RpcBlockVerbose
             .map(|x| RpcHash::from_str(x))
             .collect::<Result<Vec<kaspa_rpc_core::RpcHash>, faster_hex::Error>>()?,
         is_chain_block: item.is_chain_block,
+        confirmations: item.confirmations,
     }
 });
@@ -0,0 +1,63 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcBuildInfo, protowire::RpcBuildInfo, {
+    Self {
+        version: item.version.clone(),
+        git_hash: item.git_hash.clone().unwrap_or_default(),
+        build_date: item.build_date.clone().unwrap_or_default(),
+        features: item.features.clone(),
+        protocol_versions: item.protocol_versions.clone(),
+        rpc_api_version: item.rpc_api_version.iter().map(|&x| x as u32).collect(),
+        capabilities: item.capabilities.clone(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcBuildInfo, kaspa_rpc_core::RpcBuildInfo, {
+    let invalid_rpc_api_version = || RpcError::General(format!("invalid RPC API version {:?}", item.rpc_api_version));
+    let rpc_api_version = item.rpc_api_version.iter().map(|&x| u16::try_from(x).ok()).collect::<Option<Vec<_>>>();
+    Self {
+        version: item.version.clone(),
+        git_hash: Some(item.git_hash.clone()).filter(|x| !x.is_empty()),
+        build_date: Some(item.build_date.clone()).filter(|x| !x.is_empty()),
+        features: item.features.clone(),
+        protocol_versions: item.protocol_versions.clone(),
+        rpc_api_version: rpc_api_version.and_then(|x| x.try_into().ok()).ok_or_else(invalid_rpc_api_version)?,
+        capabilities: item.capabilities.clone(),
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use crate::protowire;
+    use kaspa_rpc_core::RpcBuildInfo;
+
+    #[test]
+    fn test_build_info_round_trip() {
+        let build_info = RpcBuildInfo {
+            version: "1.2.3".to_string(),
+            git_hash: Some("0123456789ab".to_string()),
+            build_date: None,
+            features: vec!["heap".to_string()],
+            protocol_versions: vec![5, 6],
+            rpc_api_version: [0, 1, 0, 0],
+            capabilities: vec!["GetInfo".to_string(), "GetBlock".to_string()],
+        };
+        let message = protowire::RpcBuildInfo::from(&build_info);
+        assert!(message.build_date.is_empty());
+        assert_eq!(RpcBuildInfo::try_from(&message).unwrap(), build_info);
+
+        // An RPC API version with a wrong component count is rejected
+        let message = protowire::RpcBuildInfo { rpc_api_version: vec![0, 1, 0], ..message };
+        assert!(RpcBuildInfo::try_from(&message).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHealthAlertKind};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcHealthAlertKind, protowire::RpcHealthAlertKind, {
+    match item {
+        RpcHealthAlertKind::TipCountAboveThreshold => protowire::RpcHealthAlertKind::TipCountAboveThreshold,
+        RpcHealthAlertKind::RedRateSpike => protowire::RpcHealthAlertKind::RedRateSpike,
+        RpcHealthAlertKind::BlueWorkStagnation => protowire::RpcHealthAlertKind::BlueWorkStagnation,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcHealthAlert, protowire::RpcHealthAlert, {
+    Self {
+        kind: protowire::RpcHealthAlertKind::from(&item.kind) as i32,
+        value: item.value,
+        threshold: item.threshold,
+        raised_at: item.raised_at,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcChainQualitySample, protowire::RpcChainQualitySample, {
+    Self {
+        timestamp: item.timestamp,
+        chain_block_count: item.chain_block_count,
+        block_count: item.block_count,
+        red_block_count: item.red_block_count,
+        red_rate: item.red_rate,
+        average_propagation_delay: item.average_propagation_delay,
+        miner_count: item.miner_count,
+        top_miner_share: item.top_miner_share,
+        miner_concentration: item.miner_concentration,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcHealthAlertKind, RpcHealthAlertKind, {
+    match item {
+        protowire::RpcHealthAlertKind::TipCountAboveThreshold => RpcHealthAlertKind::TipCountAboveThreshold,
+        protowire::RpcHealthAlertKind::RedRateSpike => RpcHealthAlertKind::RedRateSpike,
+        protowire::RpcHealthAlertKind::BlueWorkStagnation => RpcHealthAlertKind::BlueWorkStagnation,
+    }
+});
+
+try_from!(item: &protowire::RpcHealthAlert, kaspa_rpc_core::RpcHealthAlert, {
+    let kind = protowire::RpcHealthAlertKind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self { kind: kind.into(), value: item.value, threshold: item.threshold, raised_at: item.raised_at }
+});
+
+from!(item: &protowire::RpcChainQualitySample, kaspa_rpc_core::RpcChainQualitySample, {
+    Self {
+        timestamp: item.timestamp,
+        chain_block_count: item.chain_block_count,
+        block_count: item.block_count,
+        red_block_count: item.red_block_count,
+        red_rate: item.red_rate,
+        average_propagation_delay: item.average_propagation_delay,
+        miner_count: item.miner_count,
+        top_miner_share: item.top_miner_share,
+        miner_concentration: item.miner_concentration,
+    }
+});
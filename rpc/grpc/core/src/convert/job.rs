@@ -0,0 +1,86 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcJobKind, RpcJobState};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcJobKind, protowire::RpcJobKind, {
+    match item {
+        RpcJobKind::UtxoIndexResync => protowire::RpcJobKind::UtxoIndexResync,
+        RpcJobKind::TxIndexResync => protowire::RpcJobKind::TxIndexResync,
+    }
+});
+
+from!(item: &RpcJobState, protowire::RpcJobState, {
+    match item {
+        RpcJobState::Running => protowire::RpcJobState::Running,
+        RpcJobState::Completed => protowire::RpcJobState::Completed,
+        RpcJobState::Failed => protowire::RpcJobState::Failed,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcJob, protowire::RpcJob, {
+    Self {
+        id: item.id,
+        kind: protowire::RpcJobKind::from(&item.kind) as i32,
+        state: protowire::RpcJobState::from(&item.state) as i32,
+        started_at: item.started_at,
+        finished_at: item.finished_at.unwrap_or_default(),
+        error: item.error.clone().unwrap_or_default(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcIndexResyncProgress, protowire::RpcIndexResyncProgress, {
+    Self {
+        kind: protowire::RpcJobKind::from(&item.kind) as i32,
+        started_at: item.started_at,
+        processed: item.processed,
+        percent: item.percent,
+        eta: item.eta.map_or(-1, |eta| eta as i64),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcJobKind, RpcJobKind, {
+    match item {
+        protowire::RpcJobKind::UtxoIndexResync => RpcJobKind::UtxoIndexResync,
+        protowire::RpcJobKind::TxIndexResync => RpcJobKind::TxIndexResync,
+    }
+});
+
+from!(item: protowire::RpcJobState, RpcJobState, {
+    match item {
+        protowire::RpcJobState::Running => RpcJobState::Running,
+        protowire::RpcJobState::Completed => RpcJobState::Completed,
+        protowire::RpcJobState::Failed => RpcJobState::Failed,
+    }
+});
+
+try_from!(item: &protowire::RpcJob, kaspa_rpc_core::RpcJob, {
+    let kind = protowire::RpcJobKind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    let state = protowire::RpcJobState::try_from(item.state).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self {
+        id: item.id,
+        kind: kind.into(),
+        state: state.into(),
+        started_at: item.started_at,
+        finished_at: (item.finished_at != 0).then_some(item.finished_at),
+        error: (!item.error.is_empty()).then(|| item.error.clone()),
+    }
+});
+
+try_from!(item: &protowire::RpcIndexResyncProgress, kaspa_rpc_core::RpcIndexResyncProgress, {
+    let kind = protowire::RpcJobKind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self {
+        kind: kind.into(),
+        started_at: item.started_at,
+        processed: item.processed,
+        percent: item.percent,
+        eta: (item.eta >= 0).then_some(item.eta as u64),
+    }
+});
@@ -1,4 +1,4 @@
-use crate::protowire::{kaspad_request, KaspadRequest, KaspadResponse};
+use crate::protowire::{kaspad_request, kaspad_response, KaspadRequest, KaspadResponse};
 
 impl From<kaspad_request::Payload> for KaspadRequest {
     fn from(item: kaspad_request::Payload) -> Self {
@@ -6,6 +6,28 @@ impl From<kaspad_request::Payload> for KaspadRequest {
     }
 }
 
+impl From<kaspad_response::Payload> for KaspadResponse {
+    fn from(item: kaspad_response::Payload) -> Self {
+        KaspadResponse { id: 0, payload: Some(item) }
+    }
+}
+
+/// Requests and responses are built with an unset id (0). A client correlating concurrent requests
+/// over a single stream sets a distinct id on each request, which the server echoes on the response.
+impl KaspadRequest {
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+}
+
+impl KaspadResponse {
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+}
+
 impl AsRef<KaspadRequest> for KaspadRequest {
     fn as_ref(&self) -> &Self {
         self
@@ -36,6 +58,7 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetConnectedPeerInfo);
     impl_into_kaspad_request!(AddPeer);
     impl_into_kaspad_request!(SubmitTransaction);
+    impl_into_kaspad_request!(SubmitTransactions);
     impl_into_kaspad_request!(GetSubnetwork);
     impl_into_kaspad_request!(GetVirtualChainFromBlock);
     impl_into_kaspad_request!(GetBlocks);
@@ -57,9 +80,40 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetServerInfo);
     impl_into_kaspad_request!(GetSyncStatus);
     impl_into_kaspad_request!(GetDaaScoreTimestampEstimate);
+    impl_into_kaspad_request!(GetTransactionMass);
+    impl_into_kaspad_request!(BuildUnsignedTransaction);
+    impl_into_kaspad_request!(GetDagStats);
+    impl_into_kaspad_request!(GetHealth);
+    impl_into_kaspad_request!(GetFeeEstimate);
+    impl_into_kaspad_request!(GetSubscriptions);
+    impl_into_kaspad_request!(GetVirtualParents);
+    impl_into_kaspad_request!(GetConsensusParams);
+    impl_into_kaspad_request!(GetTransactionById);
+    impl_into_kaspad_request!(SampleChainBlocks);
+    impl_into_kaspad_request!(GetConnections);
+    impl_into_kaspad_request!(GetBlockByBlueScore);
+    impl_into_kaspad_request!(GetBlockByDaaScore);
+    impl_into_kaspad_request!(GetChainBlockByTimestamp);
+    impl_into_kaspad_request!(GetPruningPointUtxos);
+    impl_into_kaspad_request!(GetMempoolInfo);
+    impl_into_kaspad_request!(SetMempoolPolicy);
+    impl_into_kaspad_request!(GetCurrentBlockColor);
+    impl_into_kaspad_request!(GetBans);
+    impl_into_kaspad_request!(GetBlockProcessingStatus);
+    impl_into_kaspad_request!(StartJob);
+    impl_into_kaspad_request!(GetJobStatus);
+    impl_into_kaspad_request!(GetNotificationStats);
+    impl_into_kaspad_request!(GenerateBlocks);
+    impl_into_kaspad_request!(GetServerCapabilities);
+    impl_into_kaspad_request!(UpdateBlockTemplateExclusions);
+    impl_into_kaspad_request!(GetChainQuality);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
+    impl_into_kaspad_request!(NotifyHealthAlert);
+    impl_into_kaspad_request!(NotifyMempoolTransactionsEvicted);
+    impl_into_kaspad_request!(NotifyJobCompleted);
+    impl_into_kaspad_request!(NotifyIndexResyncProgress);
     impl_into_kaspad_request!(NotifyUtxosChanged);
     impl_into_kaspad_request!(NotifyPruningPointUtxoSetOverride);
     impl_into_kaspad_request!(NotifyFinalityConflict);
@@ -167,6 +221,7 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetConnectedPeerInfo);
     impl_into_kaspad_response!(AddPeer);
     impl_into_kaspad_response!(SubmitTransaction);
+    impl_into_kaspad_response!(SubmitTransactions);
     impl_into_kaspad_response!(GetSubnetwork);
     impl_into_kaspad_response!(GetVirtualChainFromBlock);
     impl_into_kaspad_response!(GetBlocks);
@@ -188,9 +243,40 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetServerInfo);
     impl_into_kaspad_response!(GetSyncStatus);
     impl_into_kaspad_response!(GetDaaScoreTimestampEstimate);
+    impl_into_kaspad_response!(GetTransactionMass);
+    impl_into_kaspad_response!(BuildUnsignedTransaction);
+    impl_into_kaspad_response!(GetDagStats);
+    impl_into_kaspad_response!(GetHealth);
+    impl_into_kaspad_response!(GetFeeEstimate);
+    impl_into_kaspad_response!(GetSubscriptions);
+    impl_into_kaspad_response!(GetVirtualParents);
+    impl_into_kaspad_response!(GetConsensusParams);
+    impl_into_kaspad_response!(GetTransactionById);
+    impl_into_kaspad_response!(SampleChainBlocks);
+    impl_into_kaspad_response!(GetConnections);
+    impl_into_kaspad_response!(GetBlockByBlueScore);
+    impl_into_kaspad_response!(GetBlockByDaaScore);
+    impl_into_kaspad_response!(GetChainBlockByTimestamp);
+    impl_into_kaspad_response!(GetPruningPointUtxos);
+    impl_into_kaspad_response!(GetMempoolInfo);
+    impl_into_kaspad_response!(SetMempoolPolicy);
+    impl_into_kaspad_response!(GetCurrentBlockColor);
+    impl_into_kaspad_response!(GetBans);
+    impl_into_kaspad_response!(GetBlockProcessingStatus);
+    impl_into_kaspad_response!(StartJob);
+    impl_into_kaspad_response!(GetJobStatus);
+    impl_into_kaspad_response!(GetNotificationStats);
+    impl_into_kaspad_response!(GenerateBlocks);
+    impl_into_kaspad_response!(GetServerCapabilities);
+    impl_into_kaspad_response!(UpdateBlockTemplateExclusions);
+    impl_into_kaspad_response!(GetChainQuality);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
+    impl_into_kaspad_notify_response!(NotifyHealthAlert);
+    impl_into_kaspad_notify_response!(NotifyMempoolTransactionsEvicted);
+    impl_into_kaspad_notify_response!(NotifyJobCompleted);
+    impl_into_kaspad_notify_response!(NotifyIndexResyncProgress);
     impl_into_kaspad_notify_response!(NotifyUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride);
     impl_into_kaspad_notify_response!(NotifyFinalityConflict);
@@ -348,3 +434,15 @@ pub mod kaspad_response_convert {
     }
     use impl_into_kaspad_notify_response_ex;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::protowire::KaspadResponse;
+    use kaspa_rpc_core::{GetInfoResponse, RpcError};
+
+    #[test]
+    fn test_response_without_payload() {
+        let response = KaspadResponse { id: 1, payload: None };
+        assert!(matches!(GetInfoResponse::try_from(&response), Err(RpcError::MissingRpcFieldError(..))));
+    }
+}
@@ -7,7 +7,13 @@ use kaspa_rpc_core::RpcError;
 // ----------------------------------------------------------------------------
 
 from!(item: &kaspa_rpc_core::RpcMempoolEntry, protowire::RpcMempoolEntry, {
-    Self { fee: item.fee, transaction: Some((&item.transaction).into()), is_orphan: item.is_orphan }
+    Self {
+        fee: item.fee,
+        transaction: Some((&item.transaction).into()),
+        is_orphan: item.is_orphan,
+        mass: item.mass,
+        feerate: item.feerate,
+    }
 });
 
 from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntryByAddress, {
@@ -18,6 +24,15 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcMempoolPolicy, protowire::RpcMempoolPolicy, {
+    Self {
+        transaction_expire_interval_seconds: item.transaction_expire_interval_seconds,
+        orphan_expire_interval_seconds: item.orphan_expire_interval_seconds,
+        maximum_transaction_count: item.maximum_transaction_count,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -30,6 +45,7 @@ try_from!(item: &protowire::RpcMempoolEntry, kaspa_rpc_core::RpcMempoolEntry, {
             .ok_or_else(|| RpcError::MissingRpcFieldError("RpcMempoolEntry".to_string(), "transaction".to_string()))?
             .try_into()?,
         item.is_orphan,
+        item.mass,
     )
 });
 
@@ -40,3 +56,12 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+from!(item: &protowire::RpcMempoolPolicy, kaspa_rpc_core::RpcMempoolPolicy, {
+    Self {
+        transaction_expire_interval_seconds: item.transaction_expire_interval_seconds,
+        orphan_expire_interval_seconds: item.orphan_expire_interval_seconds,
+        maximum_transaction_count: item.maximum_transaction_count,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+    }
+});
@@ -18,6 +18,7 @@
 //!
 //! The SubmitBlockResponse is a notable exception to this general rule.
 
+use crate::convert::block::{try_block_verbosity, try_optional_block_verbosity, try_template_tx_ordering};
 use crate::protowire::{self, submit_block_response_message::RejectReason};
 use kaspa_consensus_core::network::NetworkId;
 use kaspa_core::debug;
@@ -137,7 +138,11 @@ from!(item: &kaspa_rpc_core::SubmitBlockReport, RejectReason, {
 });
 
 from!(item: &kaspa_rpc_core::SubmitBlockRequest, protowire::SubmitBlockRequestMessage, {
-    Self { block: Some((&item.block).into()), allow_non_daa_blocks: item.allow_non_daa_blocks }
+    Self {
+        block: Some((&item.block).into()),
+        allow_non_daa_blocks: item.allow_non_daa_blocks,
+        allow_non_blocking: item.allow_non_blocking,
+    }
 });
 // This conversion breaks the general conversion convention (see file header) since the message may
 // contain both a non default reject_reason and a matching error message. In the RouteIsFull case
@@ -148,7 +153,7 @@ from!(item: RpcResult<&kaspa_rpc_core::SubmitBlockResponse>, protowire::SubmitBl
         kaspa_rpc_core::SubmitBlockReport::Success => None,
         kaspa_rpc_core::SubmitBlockReport::Reject(reason) => Some(RpcError::SubmitBlockError(reason).into())
     };
-    Self { reject_reason: RejectReason::from(&item.report) as i32, error }
+    Self { reject_reason: RejectReason::from(&item.report) as i32, ticket: item.ticket.unwrap_or_default(), error }
 });
 
 from!(item: &kaspa_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplateRequestMessage, {
@@ -158,11 +163,20 @@ from!(item: &kaspa_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplat
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
-    Self { block: Some((&item.block).into()), is_synced: item.is_synced, error: None }
+    Self {
+        block: Some((&item.block).into()),
+        is_synced: item.is_synced,
+        tx_ordering: protowire::RpcTemplateTxOrdering::from(&item.tx_ordering) as i32,
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetBlockRequest, protowire::GetBlockRequestMessage, {
-    Self { hash: item.hash.to_string(), include_transactions: item.include_transactions }
+    Self {
+        hash: item.hash.to_string(),
+        include_transactions: item.include_transactions,
+        verbosity: item.verbosity.as_ref().map_or(protowire::RpcBlockVerbosity::Unspecified, |x| x.into()) as i32,
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlockResponse>, protowire::GetBlockResponseMessage, {
     Self { block: Some((&item.block).into()), error: None }
@@ -183,6 +197,7 @@ from!(item: RpcResult<&kaspa_rpc_core::GetInfoResponse>, protowire::GetInfoRespo
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        build_info: Some((&item.build_info).into()),
         error: None,
     }
 });
@@ -192,18 +207,57 @@ from!(item: &kaspa_rpc_core::NotifyNewBlockTemplateRequest, protowire::NotifyNew
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyNewBlockTemplateResponse>, protowire::NotifyNewBlockTemplateResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyHealthAlertRequest, protowire::NotifyHealthAlertRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyHealthAlertResponse>, protowire::NotifyHealthAlertResponseMessage);
+
+from!(item: &kaspa_rpc_core::NotifyMempoolTransactionsEvictedRequest, protowire::NotifyMempoolTransactionsEvictedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(
+    RpcResult<&kaspa_rpc_core::NotifyMempoolTransactionsEvictedResponse>,
+    protowire::NotifyMempoolTransactionsEvictedResponseMessage
+);
+
+from!(item: &kaspa_rpc_core::NotifyJobCompletedRequest, protowire::NotifyJobCompletedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyJobCompletedResponse>, protowire::NotifyJobCompletedResponseMessage);
+
+from!(item: &kaspa_rpc_core::NotifyIndexResyncProgressRequest, protowire::NotifyIndexResyncProgressRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyIndexResyncProgressResponse>, protowire::NotifyIndexResyncProgressResponseMessage);
+
 // ~~~
 
 from!(&kaspa_rpc_core::GetCurrentNetworkRequest, protowire::GetCurrentNetworkRequestMessage);
 from!(item: RpcResult<&kaspa_rpc_core::GetCurrentNetworkResponse>, protowire::GetCurrentNetworkResponseMessage, {
-    Self { current_network: item.network.to_string(), error: None }
+    Self {
+        current_network: item.network.to_string(),
+        network_id: item.network_id.map(|network_id| network_id.to_prefixed()).unwrap_or_default(),
+        error: None,
+    }
 });
 
-from!(&kaspa_rpc_core::GetPeerAddressesRequest, protowire::GetPeerAddressesRequestMessage);
+from!(item: &kaspa_rpc_core::GetPeerAddressesRequest, protowire::GetPeerAddressesRequestMessage, {
+    Self {
+        banned_only: item.banned_only,
+        recent_only: item.recent_only,
+        ipv4_only: item.ipv4_only,
+        ipv6_only: item.ipv6_only,
+        network: item.network.clone().unwrap_or_default(),
+        offset: item.offset,
+        limit: item.limit.unwrap_or_default(),
+    }
+});
 from!(item: RpcResult<&kaspa_rpc_core::GetPeerAddressesResponse>, protowire::GetPeerAddressesResponseMessage, {
     Self {
         addresses: item.known_addresses.iter().map(|x| x.into()).collect(),
         banned_addresses: item.banned_addresses.iter().map(|x| x.into()).collect(),
+        total_known_addresses: item.total_known_addresses,
+        total_banned_addresses: item.total_banned_addresses,
         error: None,
     }
 });
@@ -225,7 +279,13 @@ from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntryResponse>, protowire::GetM
 });
 
 from!(item: &kaspa_rpc_core::GetMempoolEntriesRequest, protowire::GetMempoolEntriesRequestMessage, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        min_fee_rate: item.min_fee_rate,
+        max_age_daa_score: item.max_age_daa_score,
+        spendable_by_addresses: item.spendable_by_addresses.iter().map(|x| x.into()).collect(),
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMempoolEntriesResponse>, protowire::GetMempoolEntriesResponseMessage, {
     Self { entries: item.mempool_entries.iter().map(|x| x.into()).collect(), error: None }
@@ -242,12 +302,19 @@ from!(item: &kaspa_rpc_core::AddPeerRequest, protowire::AddPeerRequestMessage, {
 from!(RpcResult<&kaspa_rpc_core::AddPeerResponse>, protowire::AddPeerResponseMessage);
 
 from!(item: &kaspa_rpc_core::SubmitTransactionRequest, protowire::SubmitTransactionRequestMessage, {
-    Self { transaction: Some((&item.transaction).into()), allow_orphan: item.allow_orphan }
+    Self { transaction: Some((&item.transaction).into()), allow_orphan: item.allow_orphan, replace: item.replace }
 });
 from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionResponse>, protowire::SubmitTransactionResponseMessage, {
     Self { transaction_id: item.transaction_id.to_string(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::SubmitTransactionsRequest, protowire::SubmitTransactionsRequestMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), allow_orphan: item.allow_orphan }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionsResponse>, protowire::SubmitTransactionsResponseMessage, {
+    Self { results: item.results.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
     Self { subnetwork_id: item.subnetwork_id.to_string() }
 });
@@ -258,13 +325,18 @@ from!(item: RpcResult<&kaspa_rpc_core::GetSubnetworkResponse>, protowire::GetSub
 // ~~~
 
 from!(item: &kaspa_rpc_core::GetVirtualChainFromBlockRequest, protowire::GetVirtualChainFromBlockRequestMessage, {
-    Self { start_hash: item.start_hash.to_string(), include_accepted_transaction_ids: item.include_accepted_transaction_ids }
+    Self {
+        start_hash: item.start_hash.to_string(),
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        max_blocks: item.max_blocks.unwrap_or_default(),
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetVirtualChainFromBlockResponse>, protowire::GetVirtualChainFromBlockResponseMessage, {
     Self {
         removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
         added_chain_block_hashes: item.added_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
         accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.into()).collect(),
+        next_start_hash: item.next_start_hash.map_or(Default::default(), |x| x.to_string()),
         error: None,
     }
 });
@@ -274,6 +346,7 @@ from!(item: &kaspa_rpc_core::GetBlocksRequest, protowire::GetBlocksRequestMessag
         low_hash: item.low_hash.map_or(Default::default(), |x| x.to_string()),
         include_blocks: item.include_blocks,
         include_transactions: item.include_transactions,
+        verbosity: item.verbosity.as_ref().map_or(protowire::RpcBlockVerbosity::Unspecified, |x| x.into()) as i32,
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlocksResponse>, protowire::GetBlocksResponseMessage, {
@@ -320,15 +393,27 @@ from!(item: &kaspa_rpc_core::GetHeadersRequest, protowire::GetHeadersRequestMess
     Self { start_hash: item.start_hash.to_string(), limit: item.limit, is_ascending: item.is_ascending }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetHeadersResponse>, protowire::GetHeadersResponseMessage, {
-    Self { headers: item.headers.iter().map(|x| x.hash.to_string()).collect(), error: None }
+    Self {
+        headers: item.headers.iter().map(|x| x.hash.to_string()).collect(),
+        block_headers: item.headers.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetUtxosByAddressesRequest, protowire::GetUtxosByAddressesRequestMessage, {
-    Self { addresses: item.addresses.iter().map(|x| x.into()).collect() }
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
+        limit: item.limit.unwrap_or_default(),
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetUtxosByAddressesResponse>, protowire::GetUtxosByAddressesResponseMessage, {
     debug!("GRPC, Creating GetUtxosByAddresses message with {} entries", item.entries.len());
-    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.as_ref().map(|x| x.into()),
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetBalanceByAddressRequest, protowire::GetBalanceByAddressRequestMessage, {
@@ -352,14 +437,27 @@ from!(item: RpcResult<&kaspa_rpc_core::GetSinkBlueScoreResponse>, protowire::Get
     Self { blue_score: item.blue_score, error: None }
 });
 
-from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, {
+    Self {
+        ip: item.ip.to_string(),
+        duration: item.duration.unwrap_or_default(),
+        reason: item.reason.clone().unwrap_or_default(),
+        prefix_length: item.prefix_length.unwrap_or_default() as u32,
+    }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::BanResponse>, protowire::BanResponseMessage, { Self { error: None } });
 
-from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, {
+    Self { ip: item.ip.to_string(), prefix_length: item.prefix_length.unwrap_or_default() as u32 }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::UnbanResponse>, protowire::UnbanResponseMessage, { Self { error: None } });
 
 from!(item: &kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, protowire::EstimateNetworkHashesPerSecondRequestMessage, {
-    Self { window_size: item.window_size, start_hash: item.start_hash.map_or(Default::default(), |x| x.to_string()) }
+    Self {
+        window_size: item.window_size,
+        start_hash: item.start_hash.map_or(Default::default(), |x| x.to_string()),
+        min_window_size: item.min_window_size.unwrap_or_default(),
+    }
 });
 from!(
     item: RpcResult<&kaspa_rpc_core::EstimateNetworkHashesPerSecondResponse>,
@@ -382,7 +480,7 @@ from!(
 
 from!(&kaspa_rpc_core::GetCoinSupplyRequest, protowire::GetCoinSupplyRequestMessage);
 from!(item: RpcResult<&kaspa_rpc_core::GetCoinSupplyResponse>, protowire::GetCoinSupplyResponseMessage, {
-    Self { max_sompi: item.max_sompi, circulating_sompi: item.circulating_sompi, error: None }
+    Self { max_sompi: item.max_sompi, circulating_sompi: item.circulating_sompi, burned_sompi: item.burned_sompi, error: None }
 });
 
 from!(item: &kaspa_rpc_core::GetDaaScoreTimestampEstimateRequest, protowire::GetDaaScoreTimestampEstimateRequestMessage, {
@@ -394,6 +492,251 @@ from!(item: RpcResult<&kaspa_rpc_core::GetDaaScoreTimestampEstimateResponse>, pr
     Self { timestamps: item.timestamps.clone(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::GetTransactionMassRequest, protowire::GetTransactionMassRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionMassResponse>, protowire::GetTransactionMassResponseMessage, {
+    Self {
+        size_mass: item.size_mass,
+        script_public_key_mass: item.script_public_key_mass,
+        sig_op_mass: item.sig_op_mass,
+        compute_mass: item.compute_mass,
+        max_standard_transaction_mass: item.max_standard_transaction_mass,
+        max_block_mass: item.max_block_mass,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::BuildUnsignedTransactionRequest, protowire::BuildUnsignedTransactionRequestMessage, {
+    Self {
+        from_addresses: item.from_addresses.iter().map(|x| x.into()).collect(),
+        outputs: item.outputs.iter().map(|x| x.into()).collect(),
+        change_address: (&item.change_address).into(),
+        fee_rate: item.fee_rate.unwrap_or_default(),
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::BuildUnsignedTransactionResponse>, protowire::BuildUnsignedTransactionResponseMessage, {
+    Self {
+        transaction: Some((&item.transaction).into()),
+        utxo_entries: item.utxo_entries.iter().map(|x| x.into()).collect(),
+        fee: item.fee,
+        mass: item.mass,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetDagStatsRequest, protowire::GetDagStatsRequestMessage, { Self { window_size: item.window_size } });
+from!(item: RpcResult<&kaspa_rpc_core::GetDagStatsResponse>, protowire::GetDagStatsResponseMessage, {
+    Self {
+        tip_count: item.tip_count,
+        chain_block_count: item.chain_block_count,
+        block_count: item.block_count,
+        average_mergeset_size: item.average_mergeset_size,
+        red_block_rate: item.red_block_rate,
+        average_parents: item.average_parents,
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetHealthRequest, protowire::GetHealthRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetHealthResponse>, protowire::GetHealthResponseMessage, {
+    Self {
+        degraded: item.degraded,
+        alerts: item.alerts.iter().map(|x| x.into()).collect(),
+        index_resyncs: item.index_resyncs.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetFeeEstimateRequest, protowire::GetFeeEstimateRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFeeEstimateResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), error: None }
+});
+
+from!(&kaspa_rpc_core::GetSubscriptionsRequest, protowire::GetSubscriptionsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetSubscriptionsResponse>, protowire::GetSubscriptionsResponseMessage, {
+    Self { subscriptions: item.subscriptions.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetVirtualParentsRequest, protowire::GetVirtualParentsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetVirtualParentsResponse>, protowire::GetVirtualParentsResponseMessage, {
+    Self {
+        selected_parent: item.selected_parent.to_string(),
+        parents: item.parents.iter().map(|x| x.to_string()).collect(),
+        excluded_candidates: item.excluded_candidates.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetConsensusParamsRequest, protowire::GetConsensusParamsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetConsensusParamsResponse>, protowire::GetConsensusParamsResponseMessage, {
+    Self { params: Some((&item.params).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionByIdRequest, protowire::GetTransactionByIdRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionByIdResponse>, protowire::GetTransactionByIdResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        containing_block_hash: item.containing_block_hash.to_string(),
+        accepting_block_hash: item.accepting_block_hash.map_or(Default::default(), |x| x.to_string()),
+        is_accepted: item.is_accepted,
+        confirmations: item.confirmations,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::SampleChainBlocksRequest, protowire::SampleChainBlocksRequestMessage, {
+    Self { low_blue_score: item.low_blue_score, high_blue_score: item.high_blue_score, count: item.count }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SampleChainBlocksResponse>, protowire::SampleChainBlocksResponseMessage, {
+    Self { headers: item.headers.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetConnectionsRequest, protowire::GetConnectionsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetConnectionsResponse>, protowire::GetConnectionsResponseMessage, {
+    Self { connections: item.connections.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlockByBlueScoreRequest, protowire::GetBlockByBlueScoreRequestMessage, {
+    Self { blue_score: item.blue_score, verbosity: protowire::RpcBlockVerbosity::from(&item.verbosity) as i32 }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlockByBlueScoreResponse>, protowire::GetBlockByBlueScoreResponseMessage, {
+    Self { block: Some((&item.block).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlockByDaaScoreRequest, protowire::GetBlockByDaaScoreRequestMessage, {
+    Self { daa_score: item.daa_score, verbosity: protowire::RpcBlockVerbosity::from(&item.verbosity) as i32 }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlockByDaaScoreResponse>, protowire::GetBlockByDaaScoreResponseMessage, {
+    Self { block: Some((&item.block).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetChainBlockByTimestampRequest, protowire::GetChainBlockByTimestampRequestMessage, {
+    Self { timestamp: item.timestamp, verbosity: protowire::RpcBlockVerbosity::from(&item.verbosity) as i32 }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetChainBlockByTimestampResponse>, protowire::GetChainBlockByTimestampResponseMessage, {
+    Self { block: Some((&item.block).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetPruningPointUtxosRequest, protowire::GetPruningPointUtxosRequestMessage, {
+    Self {
+        pruning_point: item.pruning_point.map_or(Default::default(), |x| x.to_string()),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
+        limit: item.limit.unwrap_or_default(),
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetPruningPointUtxosResponse>, protowire::GetPruningPointUtxosResponseMessage, {
+    Self {
+        pruning_point: item.pruning_point.to_string(),
+        utxo_commitment: item.utxo_commitment.to_string(),
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.as_ref().map(|x| x.into()),
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetMempoolInfoRequest, protowire::GetMempoolInfoRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolInfoResponse>, protowire::GetMempoolInfoResponseMessage, {
+    Self {
+        transaction_count: item.transaction_count,
+        orphan_count: item.orphan_count,
+        policy: Some((&item.policy).into()),
+        minimum_standard_transaction_version: item.minimum_standard_transaction_version.into(),
+        maximum_standard_transaction_version: item.maximum_standard_transaction_version.into(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::SetMempoolPolicyRequest, protowire::SetMempoolPolicyRequestMessage, {
+    Self { policy: Some((&item.policy).into()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SetMempoolPolicyResponse>, protowire::SetMempoolPolicyResponseMessage, {
+    Self { policy: Some((&item.policy).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetCurrentBlockColorRequest, protowire::GetCurrentBlockColorRequestMessage, {
+    Self { hash: item.hash.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetCurrentBlockColorResponse>, protowire::GetCurrentBlockColorResponseMessage, {
+    Self { blue: item.blue, merging_block_hash: item.merging_block_hash.to_string(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetBansRequest, protowire::GetBansRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetBansResponse>, protowire::GetBansResponseMessage, {
+    Self { bans: item.bans.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlockProcessingStatusRequest, protowire::GetBlockProcessingStatusRequestMessage, {
+    Self { ticket: item.ticket }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlockProcessingStatusResponse>, protowire::GetBlockProcessingStatusResponseMessage, {
+    Self {
+        block_hash: item.block_hash.to_string(),
+        is_pending: item.is_pending(),
+        reject_reason: item.report.as_ref().map_or(RejectReason::None, RejectReason::from) as i32,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::StartJobRequest, protowire::StartJobRequestMessage, {
+    Self { kind: protowire::RpcJobKind::from(&item.kind) as i32 }
+});
+from!(item: RpcResult<&kaspa_rpc_core::StartJobResponse>, protowire::StartJobResponseMessage, {
+    Self { job_id: item.job_id, error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetJobStatusRequest, protowire::GetJobStatusRequestMessage, {
+    Self { job_id: item.job_id }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetJobStatusResponse>, protowire::GetJobStatusResponseMessage, {
+    Self { job: Some((&item.job).into()), error: None }
+});
+
+from!(&kaspa_rpc_core::GetNotificationStatsRequest, protowire::GetNotificationStatsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetNotificationStatsResponse>, protowire::GetNotificationStatsResponseMessage, {
+    Self { notifiers: item.notifiers.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GenerateBlocksRequest, protowire::GenerateBlocksRequestMessage, {
+    Self { pay_address: (&item.pay_address).into(), count: item.count }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GenerateBlocksResponse>, protowire::GenerateBlocksResponseMessage, {
+    Self { block_hashes: item.block_hashes.iter().map(|x| x.to_string()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetServerCapabilitiesRequest, protowire::GetServerCapabilitiesRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetServerCapabilitiesResponse>, protowire::GetServerCapabilitiesResponseMessage, {
+    Self {
+        rpc_api_version: item.rpc_api_version.iter().map(|x| *x as u32).collect(),
+        methods: item.methods.clone(),
+        notifications: item.notifications.clone(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::UpdateBlockTemplateExclusionsRequest, protowire::UpdateBlockTemplateExclusionsRequestMessage, {
+    Self {
+        add_transaction_ids: item.add_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        remove_transaction_ids: item.remove_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        add_script_public_keys: item.add_script_public_keys.iter().map(|x| x.into()).collect(),
+        remove_script_public_keys: item.remove_script_public_keys.iter().map(|x| x.into()).collect(),
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::UpdateBlockTemplateExclusionsResponse>, protowire::UpdateBlockTemplateExclusionsResponseMessage, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        script_public_keys: item.script_public_keys.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetChainQualityRequest, protowire::GetChainQualityRequestMessage, { Self { since: item.since } });
+from!(item: RpcResult<&kaspa_rpc_core::GetChainQualityResponse>, protowire::GetChainQualityResponseMessage, {
+    Self { samples: item.samples.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -403,6 +746,8 @@ from!(item: &kaspa_rpc_core::GetMetricsRequest, protowire::GetMetricsRequestMess
         connection_metrics: item.connection_metrics,
         bandwidth_metrics: item.bandwidth_metrics,
         consensus_metrics: item.consensus_metrics,
+        storage_metrics: item.storage_metrics,
+        request_metrics: item.request_metrics,
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetMetricsResponse>, protowire::GetMetricsResponseMessage, {
@@ -412,6 +757,8 @@ from!(item: RpcResult<&kaspa_rpc_core::GetMetricsResponse>, protowire::GetMetric
         connection_metrics: item.connection_metrics.as_ref().map(|x| x.into()),
         bandwidth_metrics: item.bandwidth_metrics.as_ref().map(|x| x.into()),
         consensus_metrics: item.consensus_metrics.as_ref().map(|x| x.into()),
+        storage_metrics: item.storage_metrics.as_ref().map(|x| x.into()),
+        request_metrics: item.request_metrics.as_ref().map(|x| x.into()),
         error: None,
     }
 });
@@ -432,12 +779,19 @@ from!(&kaspa_rpc_core::GetSyncStatusRequest, protowire::GetSyncStatusRequestMess
 from!(item: RpcResult<&kaspa_rpc_core::GetSyncStatusResponse>, protowire::GetSyncStatusResponseMessage, {
     Self {
         is_synced: item.is_synced,
+        phase: protowire::RpcSyncPhase::from(&item.phase) as i32,
+        phase_progress: item.phase_progress.unwrap_or_default(),
+        processed: item.processed,
+        estimated_remaining_time: item.estimated_remaining_time.unwrap_or_default(),
+        sink_daa_score: item.sink_daa_score,
+        headers_selected_tip_daa_score: item.headers_selected_tip_daa_score,
+        ibd_peer: item.ibd_peer.clone().unwrap_or_default(),
         error: None,
     }
 });
 
 from!(item: &kaspa_rpc_core::NotifyUtxosChangedRequest, protowire::NotifyUtxosChangedRequestMessage, {
-    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), replace: item.replace, command: item.command.into() }
 });
 from!(item: &kaspa_rpc_core::NotifyUtxosChangedRequest, protowire::StopNotifyingUtxosChangedRequestMessage, {
     Self { addresses: item.addresses.iter().map(|x| x.into()).collect() }
@@ -498,6 +852,7 @@ try_from!(item: &protowire::SubmitBlockRequestMessage, kaspa_rpc_core::SubmitBlo
             .ok_or_else(|| RpcError::MissingRpcFieldError("SubmitBlockRequestMessage".to_string(), "block".to_string()))?
             .try_into()?,
         allow_non_daa_blocks: item.allow_non_daa_blocks,
+        allow_non_blocking: item.allow_non_blocking,
     }
 });
 impl TryFrom<&protowire::SubmitBlockResponseMessage> for kaspa_rpc_core::SubmitBlockResponse {
@@ -513,15 +868,15 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for kaspa_rpc_core::SubmitB
             match report {
                 SubmitBlockReport::Success => {
                     if err.message == RpcError::SubmitBlockError(SubmitBlockRejectReason::RouteIsFull).to_string() {
-                        Ok(Self { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) })
+                        Ok(Self::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull)))
                     } else {
                         Err(err.into())
                     }
                 }
-                SubmitBlockReport::Reject(_) => Ok(Self { report }),
+                SubmitBlockReport::Reject(_) => Ok(Self::new(report)),
             }
         } else {
-            Ok(Self { report })
+            Ok(Self { report, ticket: (item.ticket != 0).then_some(item.ticket) })
         }
     }
 }
@@ -537,11 +892,16 @@ try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateResponseMessage".to_string(), "block".to_string()))?
             .try_into()?,
         is_synced: item.is_synced,
+        tx_ordering: try_template_tx_ordering(item.tx_ordering)?,
     }
 });
 
 try_from!(item: &protowire::GetBlockRequestMessage, kaspa_rpc_core::GetBlockRequest, {
-    Self { hash: RpcHash::from_str(&item.hash)?, include_transactions: item.include_transactions }
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        include_transactions: item.include_transactions,
+        verbosity: try_optional_block_verbosity(item.verbosity)?,
+    }
 });
 try_from!(item: &protowire::GetBlockResponseMessage, RpcResult<kaspa_rpc_core::GetBlockResponse>, {
     Self {
@@ -568,6 +928,7 @@ try_from!(item: &protowire::GetInfoResponseMessage, RpcResult<kaspa_rpc_core::Ge
         is_synced: item.is_synced,
         has_notify_command: item.has_notify_command,
         has_message_id: item.has_message_id,
+        build_info: item.build_info.as_ref().map(kaspa_rpc_core::RpcBuildInfo::try_from).transpose()?.unwrap_or_default(),
     }
 });
 
@@ -576,6 +937,29 @@ try_from!(item: &protowire::NotifyNewBlockTemplateRequestMessage, kaspa_rpc_core
 });
 try_from!(&protowire::NotifyNewBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::NotifyNewBlockTemplateResponse>);
 
+try_from!(item: &protowire::NotifyHealthAlertRequestMessage, kaspa_rpc_core::NotifyHealthAlertRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyHealthAlertResponseMessage, RpcResult<kaspa_rpc_core::NotifyHealthAlertResponse>);
+
+try_from!(item: &protowire::NotifyMempoolTransactionsEvictedRequestMessage, kaspa_rpc_core::NotifyMempoolTransactionsEvictedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(
+    &protowire::NotifyMempoolTransactionsEvictedResponseMessage,
+    RpcResult<kaspa_rpc_core::NotifyMempoolTransactionsEvictedResponse>
+);
+
+try_from!(item: &protowire::NotifyJobCompletedRequestMessage, kaspa_rpc_core::NotifyJobCompletedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyJobCompletedResponseMessage, RpcResult<kaspa_rpc_core::NotifyJobCompletedResponse>);
+
+try_from!(item: &protowire::NotifyIndexResyncProgressRequestMessage, kaspa_rpc_core::NotifyIndexResyncProgressRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyIndexResyncProgressResponseMessage, RpcResult<kaspa_rpc_core::NotifyIndexResyncProgressResponse>);
+
 // ~~~
 
 try_from!(&protowire::GetCurrentNetworkRequestMessage, kaspa_rpc_core::GetCurrentNetworkRequest);
@@ -583,14 +967,32 @@ try_from!(item: &protowire::GetCurrentNetworkResponseMessage, RpcResult<kaspa_rp
     // Note that current_network is first converted to lowercase because the golang implementation
     // returns a "human readable" version with a capital first letter while the rusty version
     // is fully lowercase.
-    Self { network: RpcNetworkType::from_str(&item.current_network.to_lowercase())? }
+    Self {
+        network: RpcNetworkType::from_str(&item.current_network.to_lowercase())?,
+        network_id: match item.network_id.is_empty() {
+            true => None,
+            false => Some(kaspa_rpc_core::RpcNetworkId::from_prefixed(&item.network_id)?),
+        },
+    }
 });
 
-try_from!(&protowire::GetPeerAddressesRequestMessage, kaspa_rpc_core::GetPeerAddressesRequest);
+try_from!(item: &protowire::GetPeerAddressesRequestMessage, kaspa_rpc_core::GetPeerAddressesRequest, {
+    Self {
+        banned_only: item.banned_only,
+        recent_only: item.recent_only,
+        ipv4_only: item.ipv4_only,
+        ipv6_only: item.ipv6_only,
+        network: (!item.network.is_empty()).then(|| item.network.clone()),
+        offset: item.offset,
+        limit: (item.limit != 0).then_some(item.limit),
+    }
+});
 try_from!(item: &protowire::GetPeerAddressesResponseMessage, RpcResult<kaspa_rpc_core::GetPeerAddressesResponse>, {
     Self {
         known_addresses: item.addresses.iter().map(RpcPeerAddress::try_from).collect::<Result<Vec<_>, _>>()?,
         banned_addresses: item.banned_addresses.iter().map(RpcIpAddress::try_from).collect::<Result<Vec<_>, _>>()?,
+        total_known_addresses: item.total_known_addresses,
+        total_banned_addresses: item.total_banned_addresses,
     }
 });
 
@@ -617,7 +1019,13 @@ try_from!(item: &protowire::GetMempoolEntryResponseMessage, RpcResult<kaspa_rpc_
 });
 
 try_from!(item: &protowire::GetMempoolEntriesRequestMessage, kaspa_rpc_core::GetMempoolEntriesRequest, {
-    Self { include_orphan_pool: item.include_orphan_pool, filter_transaction_pool: item.filter_transaction_pool }
+    Self {
+        include_orphan_pool: item.include_orphan_pool,
+        filter_transaction_pool: item.filter_transaction_pool,
+        min_fee_rate: item.min_fee_rate,
+        max_age_daa_score: item.max_age_daa_score,
+        spendable_by_addresses: item.spendable_by_addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
 });
 try_from!(item: &protowire::GetMempoolEntriesResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolEntriesResponse>, {
     Self { mempool_entries: item.entries.iter().map(kaspa_rpc_core::RpcMempoolEntry::try_from).collect::<Result<Vec<_>, _>>()? }
@@ -641,12 +1049,23 @@ try_from!(item: &protowire::SubmitTransactionRequestMessage, kaspa_rpc_core::Sub
             .ok_or_else(|| RpcError::MissingRpcFieldError("SubmitTransactionRequestMessage".to_string(), "transaction".to_string()))?
             .try_into()?,
         allow_orphan: item.allow_orphan,
+        replace: item.replace,
     }
 });
 try_from!(item: &protowire::SubmitTransactionResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionResponse>, {
     Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
 });
 
+try_from!(item: &protowire::SubmitTransactionsRequestMessage, kaspa_rpc_core::SubmitTransactionsRequest, {
+    Self {
+        transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        allow_orphan: item.allow_orphan,
+    }
+});
+try_from!(item: &protowire::SubmitTransactionsResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionsResponse>, {
+    Self { results: item.results.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, kaspa_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: kaspa_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -655,7 +1074,11 @@ try_from!(item: &protowire::GetSubnetworkResponseMessage, RpcResult<kaspa_rpc_co
 });
 
 try_from!(item: &protowire::GetVirtualChainFromBlockRequestMessage, kaspa_rpc_core::GetVirtualChainFromBlockRequest, {
-    Self { start_hash: RpcHash::from_str(&item.start_hash)?, include_accepted_transaction_ids: item.include_accepted_transaction_ids }
+    Self {
+        start_hash: RpcHash::from_str(&item.start_hash)?,
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        max_blocks: (item.max_blocks > 0).then_some(item.max_blocks),
+    }
 });
 try_from!(item: &protowire::GetVirtualChainFromBlockResponseMessage, RpcResult<kaspa_rpc_core::GetVirtualChainFromBlockResponse>, {
     Self {
@@ -666,6 +1089,7 @@ try_from!(item: &protowire::GetVirtualChainFromBlockResponseMessage, RpcResult<k
             .collect::<Result<Vec<_>, _>>()?,
         added_chain_block_hashes: item.added_chain_block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
         accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_start_hash: if item.next_start_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.next_start_hash)?) },
     }
 });
 
@@ -674,6 +1098,7 @@ try_from!(item: &protowire::GetBlocksRequestMessage, kaspa_rpc_core::GetBlocksRe
         low_hash: if item.low_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.low_hash)?) },
         include_blocks: item.include_blocks,
         include_transactions: item.include_transactions,
+        verbosity: try_optional_block_verbosity(item.verbosity)?,
     }
 });
 try_from!(item: &protowire::GetBlocksResponseMessage, RpcResult<kaspa_rpc_core::GetBlocksResponse>, {
@@ -716,15 +1141,21 @@ try_from!(item: &protowire::GetHeadersRequestMessage, kaspa_rpc_core::GetHeaders
     Self { start_hash: RpcHash::from_str(&item.start_hash)?, limit: item.limit, is_ascending: item.is_ascending }
 });
 try_from!(item: &protowire::GetHeadersResponseMessage, RpcResult<kaspa_rpc_core::GetHeadersResponse>, {
-    // TODO
-    Self { headers: vec![] }
+    Self { headers: item.block_headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
 });
 
 try_from!(item: &protowire::GetUtxosByAddressesRequestMessage, kaspa_rpc_core::GetUtxosByAddressesRequest, {
-    Self { addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
+        limit: (item.limit > 0).then_some(item.limit),
+    }
 });
 try_from!(item: &protowire::GetUtxosByAddressesResponseMessage, RpcResult<kaspa_rpc_core::GetUtxosByAddressesResponse>, {
-    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: item.next_cursor.as_ref().map(|x| x.try_into()).transpose()?,
+    }
 });
 
 try_from!(item: &protowire::GetBalanceByAddressRequestMessage, kaspa_rpc_core::GetBalanceByAddressRequest, {
@@ -746,16 +1177,26 @@ try_from!(item: &protowire::GetSinkBlueScoreResponseMessage, RpcResult<kaspa_rpc
     Self { blue_score: item.blue_score }
 });
 
-try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        duration: (item.duration != 0).then_some(item.duration),
+        reason: (!item.reason.is_empty()).then(|| item.reason.clone()),
+        prefix_length: (item.prefix_length != 0).then(|| u8::try_from(item.prefix_length)).transpose()?,
+    }
+});
 try_from!(&protowire::BanResponseMessage, RpcResult<kaspa_rpc_core::BanResponse>);
 
-try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, {
+    Self { ip: RpcIpAddress::from_str(&item.ip)?, prefix_length: (item.prefix_length != 0).then(|| u8::try_from(item.prefix_length)).transpose()? }
+});
 try_from!(&protowire::UnbanResponseMessage, RpcResult<kaspa_rpc_core::UnbanResponse>);
 
 try_from!(item: &protowire::EstimateNetworkHashesPerSecondRequestMessage, kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, {
     Self {
         window_size: item.window_size,
         start_hash: if item.start_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.start_hash)?) },
+        min_window_size: (item.min_window_size != 0).then_some(item.min_window_size),
     }
 });
 try_from!(
@@ -779,7 +1220,7 @@ try_from!(
 
 try_from!(&protowire::GetCoinSupplyRequestMessage, kaspa_rpc_core::GetCoinSupplyRequest);
 try_from!(item: &protowire::GetCoinSupplyResponseMessage, RpcResult<kaspa_rpc_core::GetCoinSupplyResponse>, {
-    Self { max_sompi: item.max_sompi, circulating_sompi: item.circulating_sompi }
+    Self { max_sompi: item.max_sompi, circulating_sompi: item.circulating_sompi, burned_sompi: item.burned_sompi }
 });
 
 try_from!(item: &protowire::GetDaaScoreTimestampEstimateRequestMessage, kaspa_rpc_core::GetDaaScoreTimestampEstimateRequest , {
@@ -791,11 +1232,317 @@ try_from!(item: &protowire::GetDaaScoreTimestampEstimateResponseMessage, RpcResu
     Self { timestamps: item.timestamps.clone() }
 });
 
+try_from!(item: &protowire::GetTransactionMassRequestMessage, kaspa_rpc_core::GetTransactionMassRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionMassRequestMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::GetTransactionMassResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionMassResponse>, {
+    Self {
+        size_mass: item.size_mass,
+        script_public_key_mass: item.script_public_key_mass,
+        sig_op_mass: item.sig_op_mass,
+        compute_mass: item.compute_mass,
+        max_standard_transaction_mass: item.max_standard_transaction_mass,
+        max_block_mass: item.max_block_mass,
+    }
+});
+
+try_from!(item: &protowire::BuildUnsignedTransactionRequestMessage, kaspa_rpc_core::BuildUnsignedTransactionRequest, {
+    Self {
+        from_addresses: item.from_addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        outputs: item.outputs.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        change_address: item.change_address.as_str().try_into()?,
+        fee_rate: (item.fee_rate > 0.0).then_some(item.fee_rate),
+    }
+});
+try_from!(item: &protowire::BuildUnsignedTransactionResponseMessage, RpcResult<kaspa_rpc_core::BuildUnsignedTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("BuildUnsignedTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        utxo_entries: item.utxo_entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        fee: item.fee,
+        mass: item.mass,
+    }
+});
+
+try_from!(item: &protowire::GetDagStatsRequestMessage, kaspa_rpc_core::GetDagStatsRequest, {
+    Self { window_size: item.window_size }
+});
+try_from!(item: &protowire::GetDagStatsResponseMessage, RpcResult<kaspa_rpc_core::GetDagStatsResponse>, {
+    Self {
+        tip_count: item.tip_count,
+        chain_block_count: item.chain_block_count,
+        block_count: item.block_count,
+        average_mergeset_size: item.average_mergeset_size,
+        red_block_rate: item.red_block_rate,
+        average_parents: item.average_parents,
+    }
+});
+
+try_from!(&protowire::GetHealthRequestMessage, kaspa_rpc_core::GetHealthRequest);
+try_from!(item: &protowire::GetHealthResponseMessage, RpcResult<kaspa_rpc_core::GetHealthResponse>, {
+    Self {
+        degraded: item.degraded,
+        alerts: item.alerts.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        index_resyncs: item.index_resyncs.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(&protowire::GetFeeEstimateRequestMessage, kaspa_rpc_core::GetFeeEstimateRequest);
+try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateResponse>, {
+    Self {
+        estimate: item
+            .estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateResponseMessage".to_string(), "estimate".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(&protowire::GetSubscriptionsRequestMessage, kaspa_rpc_core::GetSubscriptionsRequest);
+try_from!(item: &protowire::GetSubscriptionsResponseMessage, RpcResult<kaspa_rpc_core::GetSubscriptionsResponse>, {
+    Self { subscriptions: item.subscriptions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetVirtualParentsRequestMessage, kaspa_rpc_core::GetVirtualParentsRequest);
+try_from!(item: &protowire::GetVirtualParentsResponseMessage, RpcResult<kaspa_rpc_core::GetVirtualParentsResponse>, {
+    Self {
+        selected_parent: RpcHash::from_str(&item.selected_parent)?,
+        parents: item.parents.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        excluded_candidates: item.excluded_candidates.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(&protowire::GetConsensusParamsRequestMessage, kaspa_rpc_core::GetConsensusParamsRequest);
+try_from!(item: &protowire::GetConsensusParamsResponseMessage, RpcResult<kaspa_rpc_core::GetConsensusParamsResponse>, {
+    Self {
+        params: item
+            .params
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetConsensusParamsResponse".to_string(), "params".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionByIdRequestMessage, kaspa_rpc_core::GetTransactionByIdRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetTransactionByIdResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionByIdResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        containing_block_hash: RpcHash::from_str(&item.containing_block_hash)?,
+        accepting_block_hash: if item.accepting_block_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.accepting_block_hash)?) },
+        is_accepted: item.is_accepted,
+        confirmations: item.confirmations,
+    }
+});
+
+try_from!(item: &protowire::SampleChainBlocksRequestMessage, kaspa_rpc_core::SampleChainBlocksRequest, {
+    Self { low_blue_score: item.low_blue_score, high_blue_score: item.high_blue_score, count: item.count }
+});
+try_from!(item: &protowire::SampleChainBlocksResponseMessage, RpcResult<kaspa_rpc_core::SampleChainBlocksResponse>, {
+    Self { headers: item.headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetConnectionsRequestMessage, kaspa_rpc_core::GetConnectionsRequest);
+try_from!(item: &protowire::GetConnectionsResponseMessage, RpcResult<kaspa_rpc_core::GetConnectionsResponse>, {
+    Self { connections: item.connections.iter().map(kaspa_rpc_core::RpcConnectionInfo::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetBlockByBlueScoreRequestMessage, kaspa_rpc_core::GetBlockByBlueScoreRequest, {
+    Self { blue_score: item.blue_score, verbosity: try_block_verbosity(item.verbosity, false)? }
+});
+try_from!(item: &protowire::GetBlockByBlueScoreResponseMessage, RpcResult<kaspa_rpc_core::GetBlockByBlueScoreResponse>, {
+    Self {
+        block: item
+            .block
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockByBlueScoreResponseMessage".to_string(), "block".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetBlockByDaaScoreRequestMessage, kaspa_rpc_core::GetBlockByDaaScoreRequest, {
+    Self { daa_score: item.daa_score, verbosity: try_block_verbosity(item.verbosity, false)? }
+});
+try_from!(item: &protowire::GetBlockByDaaScoreResponseMessage, RpcResult<kaspa_rpc_core::GetBlockByDaaScoreResponse>, {
+    Self {
+        block: item
+            .block
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockByDaaScoreResponseMessage".to_string(), "block".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetChainBlockByTimestampRequestMessage, kaspa_rpc_core::GetChainBlockByTimestampRequest, {
+    Self { timestamp: item.timestamp, verbosity: try_block_verbosity(item.verbosity, false)? }
+});
+try_from!(item: &protowire::GetChainBlockByTimestampResponseMessage, RpcResult<kaspa_rpc_core::GetChainBlockByTimestampResponse>, {
+    Self {
+        block: item
+            .block
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetChainBlockByTimestampResponseMessage".to_string(), "block".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetPruningPointUtxosRequestMessage, kaspa_rpc_core::GetPruningPointUtxosRequest, {
+    Self {
+        pruning_point: if item.pruning_point.is_empty() { None } else { Some(RpcHash::from_str(&item.pruning_point)?) },
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
+        limit: (item.limit > 0).then_some(item.limit),
+    }
+});
+try_from!(item: &protowire::GetPruningPointUtxosResponseMessage, RpcResult<kaspa_rpc_core::GetPruningPointUtxosResponse>, {
+    Self {
+        pruning_point: RpcHash::from_str(&item.pruning_point)?,
+        utxo_commitment: RpcHash::from_str(&item.utxo_commitment)?,
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: item.next_cursor.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(&protowire::GetMempoolInfoRequestMessage, kaspa_rpc_core::GetMempoolInfoRequest);
+try_from!(item: &protowire::GetMempoolInfoResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolInfoResponse>, {
+    Self {
+        transaction_count: item.transaction_count,
+        orphan_count: item.orphan_count,
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetMempoolInfoResponseMessage".to_string(), "policy".to_string()))?
+            .into(),
+        minimum_standard_transaction_version: item.minimum_standard_transaction_version.try_into()?,
+        maximum_standard_transaction_version: item.maximum_standard_transaction_version.try_into()?,
+    }
+});
+
+try_from!(item: &protowire::SetMempoolPolicyRequestMessage, kaspa_rpc_core::SetMempoolPolicyRequest, {
+    Self {
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("SetMempoolPolicyRequestMessage".to_string(), "policy".to_string()))?
+            .into(),
+    }
+});
+try_from!(item: &protowire::SetMempoolPolicyResponseMessage, RpcResult<kaspa_rpc_core::SetMempoolPolicyResponse>, {
+    Self {
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("SetMempoolPolicyResponseMessage".to_string(), "policy".to_string()))?
+            .into(),
+    }
+});
+
+try_from!(item: &protowire::GetCurrentBlockColorRequestMessage, kaspa_rpc_core::GetCurrentBlockColorRequest, {
+    Self { hash: RpcHash::from_str(&item.hash)? }
+});
+try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<kaspa_rpc_core::GetCurrentBlockColorResponse>, {
+    Self { blue: item.blue, merging_block_hash: RpcHash::from_str(&item.merging_block_hash)? }
+});
+
+try_from!(&protowire::GetBansRequestMessage, kaspa_rpc_core::GetBansRequest);
+try_from!(item: &protowire::GetBansResponseMessage, RpcResult<kaspa_rpc_core::GetBansResponse>, {
+    Self { bans: item.bans.iter().map(kaspa_rpc_core::RpcBan::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetBlockProcessingStatusRequestMessage, kaspa_rpc_core::GetBlockProcessingStatusRequest, {
+    Self { ticket: item.ticket }
+});
+try_from!(item: &protowire::GetBlockProcessingStatusResponseMessage, RpcResult<kaspa_rpc_core::GetBlockProcessingStatusResponse>, {
+    let report = match item.is_pending {
+        true => None,
+        false => Some(RejectReason::try_from(item.reject_reason).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into()),
+    };
+    Self { block_hash: RpcHash::from_str(&item.block_hash)?, report }
+});
+
+try_from!(item: &protowire::StartJobRequestMessage, kaspa_rpc_core::StartJobRequest, {
+    let kind = protowire::RpcJobKind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self { kind: kind.into() }
+});
+try_from!(item: &protowire::StartJobResponseMessage, RpcResult<kaspa_rpc_core::StartJobResponse>, {
+    Self { job_id: item.job_id }
+});
+
+try_from!(item: &protowire::GetJobStatusRequestMessage, kaspa_rpc_core::GetJobStatusRequest, {
+    Self { job_id: item.job_id }
+});
+try_from!(item: &protowire::GetJobStatusResponseMessage, RpcResult<kaspa_rpc_core::GetJobStatusResponse>, {
+    Self {
+        job: item
+            .job
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetJobStatusResponseMessage".to_string(), "job".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(&protowire::GetNotificationStatsRequestMessage, kaspa_rpc_core::GetNotificationStatsRequest);
+try_from!(item: &protowire::GetNotificationStatsResponseMessage, RpcResult<kaspa_rpc_core::GetNotificationStatsResponse>, {
+    Self { notifiers: item.notifiers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GenerateBlocksRequestMessage, kaspa_rpc_core::GenerateBlocksRequest, {
+    Self { pay_address: item.pay_address.clone().try_into()?, count: item.count }
+});
+try_from!(item: &protowire::GenerateBlocksResponseMessage, RpcResult<kaspa_rpc_core::GenerateBlocksResponse>, {
+    Self { block_hashes: item.block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetServerCapabilitiesRequestMessage, kaspa_rpc_core::GetServerCapabilitiesRequest);
+try_from!(item: &protowire::GetServerCapabilitiesResponseMessage, RpcResult<kaspa_rpc_core::GetServerCapabilitiesResponse>, {
+    Self {
+        rpc_api_version: item.rpc_api_version.iter().map(|x| *x as u16).collect::<Vec<_>>().as_slice().try_into().map_err(|_| RpcError::RpcApiVersionFormatError)?,
+        methods: item.methods.clone(),
+        notifications: item.notifications.clone(),
+    }
+});
+
+try_from!(item: &protowire::UpdateBlockTemplateExclusionsRequestMessage, kaspa_rpc_core::UpdateBlockTemplateExclusionsRequest, {
+    Self {
+        add_transaction_ids: item.add_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        remove_transaction_ids: item.remove_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        add_script_public_keys: item.add_script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        remove_script_public_keys: item.remove_script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+try_from!(item: &protowire::UpdateBlockTemplateExclusionsResponseMessage, RpcResult<kaspa_rpc_core::UpdateBlockTemplateExclusionsResponse>, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        script_public_keys: item.script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetChainQualityRequestMessage, kaspa_rpc_core::GetChainQualityRequest, { Self { since: item.since } });
+try_from!(item: &protowire::GetChainQualityResponseMessage, RpcResult<kaspa_rpc_core::GetChainQualityResponse>, {
+    Self { samples: item.samples.iter().map(|x| x.into()).collect() }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
 try_from!(item: &protowire::GetMetricsRequestMessage, kaspa_rpc_core::GetMetricsRequest, {
-    Self { process_metrics: item.process_metrics, connection_metrics: item.connection_metrics, bandwidth_metrics:item.bandwidth_metrics, consensus_metrics: item.consensus_metrics }
+    Self {
+        process_metrics: item.process_metrics,
+        connection_metrics: item.connection_metrics,
+        bandwidth_metrics: item.bandwidth_metrics,
+        consensus_metrics: item.consensus_metrics,
+        storage_metrics: item.storage_metrics,
+        request_metrics: item.request_metrics,
+    }
 });
 try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<kaspa_rpc_core::GetMetricsResponse>, {
     Self {
@@ -804,6 +1551,8 @@ try_from!(item: &protowire::GetMetricsResponseMessage, RpcResult<kaspa_rpc_core:
         connection_metrics: item.connection_metrics.as_ref().map(|x| x.try_into()).transpose()?,
         bandwidth_metrics: item.bandwidth_metrics.as_ref().map(|x| x.try_into()).transpose()?,
         consensus_metrics: item.consensus_metrics.as_ref().map(|x| x.try_into()).transpose()?,
+        storage_metrics: item.storage_metrics.as_ref().map(|x| x.try_into()).transpose()?,
+        request_metrics: item.request_metrics.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
@@ -821,20 +1570,30 @@ try_from!(item: &protowire::GetServerInfoResponseMessage, RpcResult<kaspa_rpc_co
 
 try_from!(&protowire::GetSyncStatusRequestMessage, kaspa_rpc_core::GetSyncStatusRequest);
 try_from!(item: &protowire::GetSyncStatusResponseMessage, RpcResult<kaspa_rpc_core::GetSyncStatusResponse>, {
+    let phase = protowire::RpcSyncPhase::try_from(item.phase).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
     Self {
         is_synced: item.is_synced,
+        phase: phase.into(),
+        phase_progress: (item.phase_progress > 0.0).then_some(item.phase_progress),
+        processed: item.processed,
+        estimated_remaining_time: (item.estimated_remaining_time > 0).then_some(item.estimated_remaining_time),
+        sink_daa_score: item.sink_daa_score,
+        headers_selected_tip_daa_score: item.headers_selected_tip_daa_score,
+        ibd_peer: Some(item.ibd_peer.clone()).filter(|peer| !peer.is_empty()),
     }
 });
 
 try_from!(item: &protowire::NotifyUtxosChangedRequestMessage, kaspa_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        replace: item.replace,
         command: item.command.into(),
     }
 });
 try_from!(item: &protowire::StopNotifyingUtxosChangedRequestMessage, kaspa_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        replace: false,
         command: Command::Stop,
     }
 });
@@ -888,7 +1647,10 @@ try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<kaspa
 
 #[cfg(test)]
 mod tests {
-    use kaspa_rpc_core::{RpcError, RpcResult, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+    use kaspa_rpc_core::{
+        GetCurrentNetworkResponse, GetMempoolEntriesRequest, RpcError, RpcNetworkId, RpcNetworkType, RpcResult,
+        SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse,
+    };
 
     use crate::protowire::{self, submit_block_response_message::RejectReason, SubmitBlockResponseMessage};
 
@@ -908,31 +1670,38 @@ mod tests {
         }
         let tests = vec![
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Success }),
-                SubmitBlockResponseMessage { reject_reason: RejectReason::None as i32, error: None },
+                Ok(SubmitBlockResponse::new(SubmitBlockReport::Success)),
+                SubmitBlockResponseMessage { reject_reason: RejectReason::None as i32, ticket: 0, error: None },
+            ),
+            Test::new(
+                Ok(SubmitBlockResponse::new(SubmitBlockReport::Success).with_ticket(7)),
+                SubmitBlockResponseMessage { reject_reason: RejectReason::None as i32, ticket: 7, error: None },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) }),
+                Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid))),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::BlockInvalid as i32,
+                    ticket: 0,
                     error: Some(protowire::RpcError {
                         message: RpcError::SubmitBlockError(SubmitBlockRejectReason::BlockInvalid).to_string(),
                     }),
                 },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD) }),
+                Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD))),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::IsInIbd as i32,
+                    ticket: 0,
                     error: Some(protowire::RpcError {
                         message: RpcError::SubmitBlockError(SubmitBlockRejectReason::IsInIBD).to_string(),
                     }),
                 },
             ),
             Test::new(
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) }),
+                Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull))),
                 SubmitBlockResponseMessage {
                     reject_reason: RejectReason::None as i32, // This rpc core reject reason has no matching protowire variant
+                    ticket: 0,
                     error: Some(protowire::RpcError {
                         message: RpcError::SubmitBlockError(SubmitBlockRejectReason::RouteIsFull).to_string(),
                     }),
@@ -943,6 +1712,7 @@ mod tests {
         for test in tests {
             let cnv_protowire: SubmitBlockResponseMessage = test.rpc_core.as_ref().map_err(|x| x.clone()).into();
             assert_eq!(cnv_protowire.reject_reason, test.protowire.reject_reason);
+            assert_eq!(cnv_protowire.ticket, test.protowire.ticket);
             assert_eq!(cnv_protowire.error.is_some(), test.protowire.error.is_some());
             assert_eq!(cnv_protowire.error, test.protowire.error);
 
@@ -952,6 +1722,7 @@ mod tests {
                 Ok(ref cnv_response) => {
                     let Ok(ref response) = test.rpc_core else { panic!() };
                     assert_eq!(cnv_response.report, response.report);
+                    assert_eq!(cnv_response.ticket, response.ticket);
                 }
                 Err(ref cnv_err) => {
                     let Err(ref err) = test.rpc_core else { panic!() };
@@ -960,4 +1731,44 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_get_mempool_entries_request_filters() {
+        // Unset filters stay unset while a zero filter is kept as such
+        for (min_fee_rate, max_age_daa_score) in [(None, None), (Some(0.0), Some(0)), (Some(1.5), Some(100))] {
+            let mut request = GetMempoolEntriesRequest::new(true, false);
+            request.min_fee_rate = min_fee_rate;
+            request.max_age_daa_score = max_age_daa_score;
+
+            let cnv_protowire: protowire::GetMempoolEntriesRequestMessage = (&request).into();
+            assert_eq!(cnv_protowire.min_fee_rate, min_fee_rate);
+            assert_eq!(cnv_protowire.max_age_daa_score, max_age_daa_score);
+
+            let cnv_rpc_core: GetMempoolEntriesRequest = (&cnv_protowire).try_into().unwrap();
+            assert_eq!(cnv_rpc_core.min_fee_rate, min_fee_rate);
+            assert_eq!(cnv_rpc_core.max_age_daa_score, max_age_daa_score);
+        }
+    }
+
+    #[test]
+    fn test_get_current_network_response() {
+        let network_id = RpcNetworkId::with_suffix(RpcNetworkType::Testnet, 11);
+        let cnv_protowire: protowire::GetCurrentNetworkResponseMessage =
+            Ok::<_, RpcError>(&GetCurrentNetworkResponse::new(network_id)).into();
+        assert_eq!((cnv_protowire.current_network.as_str(), cnv_protowire.network_id.as_str()), ("testnet", "kaspa-testnet-11"));
+        let cnv_rpc_core: GetCurrentNetworkResponse = (&cnv_protowire).try_into().unwrap();
+        assert_eq!((cnv_rpc_core.network, cnv_rpc_core.network_id), (RpcNetworkType::Testnet, Some(network_id)));
+
+        // Older nodes only report a capitalized network type
+        let legacy = protowire::GetCurrentNetworkResponseMessage {
+            current_network: "Mainnet".to_owned(),
+            network_id: String::new(),
+            error: None,
+        };
+        let cnv_rpc_core: GetCurrentNetworkResponse = (&legacy).try_into().unwrap();
+        assert_eq!((cnv_rpc_core.network, cnv_rpc_core.network_id), (RpcNetworkType::Mainnet, None));
+
+        let invalid = protowire::GetCurrentNetworkResponseMessage { network_id: "testnet-x".to_owned(), ..legacy };
+        assert!(GetCurrentNetworkResponse::try_from(&invalid).is_err());
+    }
 }
@@ -66,6 +66,84 @@ from!(item: &kaspa_rpc_core::ConsensusMetrics, protowire::ConsensusMetrics, {
     }
 });
 
+from!(item: &kaspa_rpc_core::ColumnFamilyMetrics, protowire::ColumnFamilyMetrics, {
+    Self {
+        name: item.name.clone(),
+        sst_files_size: item.sst_files_size,
+        live_data_size: item.live_data_size,
+        mem_tables_size: item.mem_tables_size,
+        estimated_keys: item.estimated_keys,
+    }
+});
+
+from!(item: &kaspa_rpc_core::DatabaseMetrics, protowire::DatabaseMetrics, {
+    Self {
+        name: item.name.clone(),
+        column_families: item.column_families.iter().map(|x| x.into()).collect(),
+        running_compactions: item.running_compactions,
+        compaction_pending: item.compaction_pending,
+        pending_compaction_bytes: item.pending_compaction_bytes,
+        block_cache_usage: item.block_cache_usage,
+        block_cache_capacity: item.block_cache_capacity,
+    }
+});
+
+from!(item: &kaspa_rpc_core::StorageMetrics, protowire::StorageMetrics, {
+    Self {
+        data_dir_size: item.data_dir_size,
+        cache_hits: item.cache_hits,
+        cache_misses: item.cache_misses,
+        cache_hit_rate: item.cache_hit_rate,
+        databases: item.databases.iter().map(|x| x.into()).collect(),
+        compression_input_bytes: item.compression_input_bytes,
+        compression_output_bytes: item.compression_output_bytes,
+        compression_ratio: item.compression_ratio,
+    }
+});
+
+from!(item: &kaspa_rpc_core::LatencyBucket, protowire::LatencyBucket, {
+    Self { upper_bound_ms: item.upper_bound_ms, count: item.count }
+});
+
+from!(item: &kaspa_rpc_core::MethodMetrics, protowire::MethodMetrics, {
+    Self {
+        method: item.method.clone(),
+        requests: item.requests,
+        errors: item.errors,
+        latency_sum_us: item.latency_sum_us,
+        latency_buckets: item.latency_buckets.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::FlowMetrics, protowire::FlowMetrics, {
+    Self {
+        flow: item.flow.clone(),
+        queue_depth: item.queue_depth,
+        messages: item.messages,
+        latency_sum_us: item.latency_sum_us,
+        latency_buckets: item.latency_buckets.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::TenantMetrics, protowire::TenantMetrics, {
+    Self {
+        name: item.name.clone(),
+        active_connections: item.active_connections,
+        active_subscriptions: item.active_subscriptions,
+        tracked_addresses: item.tracked_addresses,
+        requests: item.requests,
+        rejected_requests: item.rejected_requests,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RequestMetrics, protowire::RequestMetrics, {
+    Self {
+        grpc_methods: item.grpc_methods.iter().map(|x| x.into()).collect(),
+        p2p_flows: item.p2p_flows.iter().map(|x| x.into()).collect(),
+        grpc_tenants: item.grpc_tenants.iter().map(|x| x.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -129,3 +207,81 @@ try_from!(item: &protowire::ConsensusMetrics, kaspa_rpc_core::ConsensusMetrics,
         network_virtual_daa_score: item.virtual_daa_score,
     }
 });
+
+try_from!(item: &protowire::ColumnFamilyMetrics, kaspa_rpc_core::ColumnFamilyMetrics, {
+    Self {
+        name: item.name.clone(),
+        sst_files_size: item.sst_files_size,
+        live_data_size: item.live_data_size,
+        mem_tables_size: item.mem_tables_size,
+        estimated_keys: item.estimated_keys,
+    }
+});
+
+try_from!(item: &protowire::DatabaseMetrics, kaspa_rpc_core::DatabaseMetrics, {
+    Self {
+        name: item.name.clone(),
+        column_families: item.column_families.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+        running_compactions: item.running_compactions,
+        compaction_pending: item.compaction_pending,
+        pending_compaction_bytes: item.pending_compaction_bytes,
+        block_cache_usage: item.block_cache_usage,
+        block_cache_capacity: item.block_cache_capacity,
+    }
+});
+
+try_from!(item: &protowire::StorageMetrics, kaspa_rpc_core::StorageMetrics, {
+    Self {
+        data_dir_size: item.data_dir_size,
+        cache_hits: item.cache_hits,
+        cache_misses: item.cache_misses,
+        cache_hit_rate: item.cache_hit_rate,
+        databases: item.databases.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+        compression_input_bytes: item.compression_input_bytes,
+        compression_output_bytes: item.compression_output_bytes,
+        compression_ratio: item.compression_ratio,
+    }
+});
+
+try_from!(item: &protowire::LatencyBucket, kaspa_rpc_core::LatencyBucket, {
+    Self { upper_bound_ms: item.upper_bound_ms, count: item.count }
+});
+
+try_from!(item: &protowire::MethodMetrics, kaspa_rpc_core::MethodMetrics, {
+    Self {
+        method: item.method.clone(),
+        requests: item.requests,
+        errors: item.errors,
+        latency_sum_us: item.latency_sum_us,
+        latency_buckets: item.latency_buckets.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+    }
+});
+
+try_from!(item: &protowire::FlowMetrics, kaspa_rpc_core::FlowMetrics, {
+    Self {
+        flow: item.flow.clone(),
+        queue_depth: item.queue_depth,
+        messages: item.messages,
+        latency_sum_us: item.latency_sum_us,
+        latency_buckets: item.latency_buckets.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+    }
+});
+
+try_from!(item: &protowire::TenantMetrics, kaspa_rpc_core::TenantMetrics, {
+    Self {
+        name: item.name.clone(),
+        active_connections: item.active_connections,
+        active_subscriptions: item.active_subscriptions,
+        tracked_addresses: item.tracked_addresses,
+        requests: item.requests,
+        rejected_requests: item.rejected_requests,
+    }
+});
+
+try_from!(item: &protowire::RequestMetrics, kaspa_rpc_core::RequestMetrics, {
+    Self {
+        grpc_methods: item.grpc_methods.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+        p2p_flows: item.p2p_flows.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+        grpc_tenants: item.grpc_tenants.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+    }
+});
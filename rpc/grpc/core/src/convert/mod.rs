@@ -1,11 +1,19 @@
 pub mod address;
 pub mod block;
+pub mod build;
 pub mod error;
+pub mod fee_estimate;
 pub mod header;
+pub mod health;
+pub mod job;
 pub mod kaspad;
 pub mod mempool;
 pub mod message;
 pub mod metrics;
 pub mod notification;
+pub mod params;
+pub mod parents;
 pub mod peer;
+pub mod subscription;
+pub mod sync;
 pub mod tx;
@@ -2,16 +2,18 @@ use crate::protowire::{
     kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
-    StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
-    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
+    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, HealthAlertNotificationMessage,
+    IndexResyncProgressNotificationMessage, JobCompletedNotificationMessage, MempoolTransactionsEvictedNotificationMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
+    SinkBlueScoreChangedNotificationMessage, StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage, StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage,
+    VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use kaspa_notify::subscription::Command;
-use kaspa_rpc_core::{Notification, RpcError, RpcHash};
+use kaspa_rpc_core::{Notification, RpcError, RpcHash, RpcTransactionId};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -34,6 +36,12 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::HealthAlert(ref notification) => Payload::HealthAlertNotification(notification.into()),
+        Notification::MempoolTransactionsEvicted(ref notification) => {
+            Payload::MempoolTransactionsEvictedNotification(notification.into())
+        }
+        Notification::JobCompleted(ref notification) => Payload::JobCompletedNotification(notification.into()),
+        Notification::IndexResyncProgress(ref notification) => Payload::IndexResyncProgressNotification(notification.into()),
     }
 });
 
@@ -74,6 +82,22 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &kaspa_rpc_core::HealthAlertNotification, HealthAlertNotificationMessage, {
+    Self { alert: Some((&item.alert).into()), resolved: item.resolved }
+});
+
+from!(item: &kaspa_rpc_core::MempoolTransactionsEvictedNotification, MempoolTransactionsEvictedNotificationMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), reason: item.reason.clone() }
+});
+
+from!(item: &kaspa_rpc_core::JobCompletedNotification, JobCompletedNotificationMessage, {
+    Self { job: Some((&item.job).into()) }
+});
+
+from!(item: &kaspa_rpc_core::IndexResyncProgressNotification, IndexResyncProgressNotificationMessage, {
+    Self { progress: Some((&item.progress).into()), completed: item.completed }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -82,7 +106,7 @@ from!(item: Command, RpcNotifyCommand, {
 });
 
 from!(item: &StopNotifyingUtxosChangedRequestMessage, NotifyUtxosChangedRequestMessage, {
-    Self { addresses: item.addresses.clone(), command: Command::Stop.into() }
+    Self { addresses: item.addresses.clone(), replace: false, command: Command::Stop.into() }
 });
 
 from!(_item: &StopNotifyingPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage, {
@@ -117,6 +141,12 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::HealthAlertNotification(ref notification) => Notification::HealthAlert(notification.try_into()?),
+        Payload::MempoolTransactionsEvictedNotification(ref notification) => {
+            Notification::MempoolTransactionsEvicted(notification.try_into()?)
+        }
+        Payload::JobCompletedNotification(ref notification) => Notification::JobCompleted(notification.try_into()?),
+        Payload::IndexResyncProgressNotification(ref notification) => Notification::IndexResyncProgress(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +201,47 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &HealthAlertNotificationMessage, kaspa_rpc_core::HealthAlertNotification, {
+    Self {
+        alert: item
+            .alert
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("HealthAlertNotificationMessage".to_string(), "alert".to_string()))?
+            .try_into()?,
+        resolved: item.resolved,
+    }
+});
+
+try_from!(item: &MempoolTransactionsEvictedNotificationMessage, kaspa_rpc_core::MempoolTransactionsEvictedNotification, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcTransactionId::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        reason: item.reason.clone(),
+    }
+});
+
+try_from!(item: &JobCompletedNotificationMessage, kaspa_rpc_core::JobCompletedNotification, {
+    Self {
+        job: item
+            .job
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("JobCompletedNotificationMessage".to_string(), "job".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &IndexResyncProgressNotificationMessage, kaspa_rpc_core::IndexResyncProgressNotification, {
+    Self {
+        progress: item
+            .progress
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("IndexResyncProgressNotificationMessage".to_string(), "progress".to_string())
+            })?
+            .try_into()?,
+        completed: item.completed,
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
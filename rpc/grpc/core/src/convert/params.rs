@@ -0,0 +1,97 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcConsensusParams, protowire::RpcConsensusParams, {
+    Self {
+        network_id: item.network_id.to_string(),
+        genesis_hash: item.genesis_hash.to_string(),
+        ghostdag_k: item.ghostdag_k,
+        target_time_per_block: item.target_time_per_block,
+        legacy_timestamp_deviation_tolerance: item.legacy_timestamp_deviation_tolerance,
+        new_timestamp_deviation_tolerance: item.new_timestamp_deviation_tolerance,
+        past_median_time_sample_rate: item.past_median_time_sample_rate,
+        past_median_time_sampled_window_size: item.past_median_time_sampled_window_size,
+        difficulty_sample_rate: item.difficulty_sample_rate,
+        sampled_difficulty_window_size: item.sampled_difficulty_window_size,
+        legacy_difficulty_window_size: item.legacy_difficulty_window_size,
+        min_difficulty_window_len: item.min_difficulty_window_len,
+        sampling_activation_daa_score: item.sampling_activation_daa_score,
+        max_block_parents: item.max_block_parents,
+        mergeset_size_limit: item.mergeset_size_limit,
+        merge_depth: item.merge_depth,
+        finality_depth: item.finality_depth,
+        pruning_depth: item.pruning_depth,
+        pruning_proof_m: item.pruning_proof_m,
+        max_block_level: item.max_block_level,
+        coinbase_payload_script_public_key_max_len: item.coinbase_payload_script_public_key_max_len,
+        max_coinbase_payload_len: item.max_coinbase_payload_len,
+        max_tx_inputs: item.max_tx_inputs,
+        max_tx_outputs: item.max_tx_outputs,
+        max_signature_script_len: item.max_signature_script_len,
+        max_script_public_key_len: item.max_script_public_key_len,
+        mass_per_tx_byte: item.mass_per_tx_byte,
+        mass_per_script_pub_key_byte: item.mass_per_script_pub_key_byte,
+        mass_per_sig_op: item.mass_per_sig_op,
+        max_block_mass: item.max_block_mass,
+        storage_mass_parameter: item.storage_mass_parameter,
+        storage_mass_activation_daa_score: item.storage_mass_activation_daa_score,
+        tx_version_2_activation_daa_score: item.tx_version_2_activation_daa_score,
+        deflationary_phase_daa_score: item.deflationary_phase_daa_score,
+        pre_deflationary_phase_base_subsidy: item.pre_deflationary_phase_base_subsidy,
+        coinbase_maturity: item.coinbase_maturity,
+        skip_proof_of_work: item.skip_proof_of_work,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcConsensusParams, kaspa_rpc_core::RpcConsensusParams, {
+    Self {
+        network_id: NetworkId::from_str(&item.network_id)?,
+        genesis_hash: RpcHash::from_str(&item.genesis_hash)?,
+        ghostdag_k: item.ghostdag_k,
+        target_time_per_block: item.target_time_per_block,
+        legacy_timestamp_deviation_tolerance: item.legacy_timestamp_deviation_tolerance,
+        new_timestamp_deviation_tolerance: item.new_timestamp_deviation_tolerance,
+        past_median_time_sample_rate: item.past_median_time_sample_rate,
+        past_median_time_sampled_window_size: item.past_median_time_sampled_window_size,
+        difficulty_sample_rate: item.difficulty_sample_rate,
+        sampled_difficulty_window_size: item.sampled_difficulty_window_size,
+        legacy_difficulty_window_size: item.legacy_difficulty_window_size,
+        min_difficulty_window_len: item.min_difficulty_window_len,
+        sampling_activation_daa_score: item.sampling_activation_daa_score,
+        max_block_parents: item.max_block_parents,
+        mergeset_size_limit: item.mergeset_size_limit,
+        merge_depth: item.merge_depth,
+        finality_depth: item.finality_depth,
+        pruning_depth: item.pruning_depth,
+        pruning_proof_m: item.pruning_proof_m,
+        max_block_level: item.max_block_level,
+        coinbase_payload_script_public_key_max_len: item.coinbase_payload_script_public_key_max_len,
+        max_coinbase_payload_len: item.max_coinbase_payload_len,
+        max_tx_inputs: item.max_tx_inputs,
+        max_tx_outputs: item.max_tx_outputs,
+        max_signature_script_len: item.max_signature_script_len,
+        max_script_public_key_len: item.max_script_public_key_len,
+        mass_per_tx_byte: item.mass_per_tx_byte,
+        mass_per_script_pub_key_byte: item.mass_per_script_pub_key_byte,
+        mass_per_sig_op: item.mass_per_sig_op,
+        max_block_mass: item.max_block_mass,
+        storage_mass_parameter: item.storage_mass_parameter,
+        storage_mass_activation_daa_score: item.storage_mass_activation_daa_score,
+        tx_version_2_activation_daa_score: item.tx_version_2_activation_daa_score,
+        deflationary_phase_daa_score: item.deflationary_phase_daa_score,
+        pre_deflationary_phase_base_subsidy: item.pre_deflationary_phase_base_subsidy,
+        coinbase_maturity: item.coinbase_maturity,
+        skip_proof_of_work: item.skip_proof_of_work,
+    }
+});
@@ -0,0 +1,39 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash, RpcParentExclusionReason};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcParentExclusionReason, protowire::RpcParentExclusionReason, {
+    match item {
+        RpcParentExclusionReason::NotSampled => protowire::RpcParentExclusionReason::NotSampled,
+        RpcParentExclusionReason::MaxBlockParents => protowire::RpcParentExclusionReason::MaxBlockParents,
+        RpcParentExclusionReason::MergesetSizeLimit => protowire::RpcParentExclusionReason::MergesetSizeLimit,
+        RpcParentExclusionReason::BoundedMergeDepth => protowire::RpcParentExclusionReason::BoundedMergeDepth,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcExcludedParentCandidate, protowire::RpcExcludedParentCandidate, {
+    Self { hash: item.hash.to_string(), reason: protowire::RpcParentExclusionReason::from(&item.reason) as i32 }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcParentExclusionReason, RpcParentExclusionReason, {
+    match item {
+        protowire::RpcParentExclusionReason::NotSampled => RpcParentExclusionReason::NotSampled,
+        protowire::RpcParentExclusionReason::MaxBlockParents => RpcParentExclusionReason::MaxBlockParents,
+        protowire::RpcParentExclusionReason::MergesetSizeLimit => RpcParentExclusionReason::MergesetSizeLimit,
+        protowire::RpcParentExclusionReason::BoundedMergeDepth => RpcParentExclusionReason::BoundedMergeDepth,
+    }
+});
+
+try_from!(item: &protowire::RpcExcludedParentCandidate, kaspa_rpc_core::RpcExcludedParentCandidate, {
+    let reason = protowire::RpcParentExclusionReason::try_from(item.reason).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self { hash: RpcHash::from_str(&item.hash)?, reason: reason.into() }
+});
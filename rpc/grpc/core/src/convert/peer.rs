@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+use kaspa_rpc_core::{RpcError, RpcIpAddress, RpcNodeId, RpcPeerAddress};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -22,6 +22,32 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcMessageCounts, protowire::RpcMessageCounts, {
+    Self { message_type: item.message_type.clone(), sent: item.sent, received: item.received }
+});
+
+from!(item: &kaspa_rpc_core::RpcConnectionInfo, protowire::RpcConnectionInfo, {
+    Self {
+        id: item.id.to_string(),
+        address: item.address.to_string(),
+        is_outbound: item.is_outbound,
+        connection_age: item.connection_age,
+        bytes_sent: item.bytes_sent,
+        bytes_received: item.bytes_received,
+        messages: item.messages.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcBan, protowire::RpcBan, {
+    Self {
+        ip: item.ip.to_string(),
+        timestamp: item.timestamp,
+        remaining_time: item.remaining_time,
+        reason: item.reason.clone().unwrap_or_default(),
+        prefix_length: item.prefix_length.unwrap_or_default() as u32,
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcPeerAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 from!(item: &kaspa_rpc_core::RpcIpAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 
@@ -43,5 +69,31 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
     }
 });
 
+from!(item: &protowire::RpcMessageCounts, kaspa_rpc_core::RpcMessageCounts, {
+    Self { message_type: item.message_type.clone(), sent: item.sent, received: item.received }
+});
+
+try_from!(item: &protowire::RpcConnectionInfo, kaspa_rpc_core::RpcConnectionInfo, {
+    Self {
+        id: RpcNodeId::from_str(&item.id)?,
+        address: RpcPeerAddress::from_str(&item.address)?,
+        is_outbound: item.is_outbound,
+        connection_age: item.connection_age,
+        bytes_sent: item.bytes_sent,
+        bytes_received: item.bytes_received,
+        messages: item.messages.iter().map(|x| x.into()).collect(),
+    }
+});
+
+try_from!(item: &protowire::RpcBan, kaspa_rpc_core::RpcBan, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        timestamp: item.timestamp,
+        remaining_time: item.remaining_time,
+        reason: (!item.reason.is_empty()).then(|| item.reason.clone()),
+        prefix_length: (item.prefix_length != 0).then(|| u8::try_from(item.prefix_length)).transpose()?,
+    }
+});
+
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcPeerAddress, { Self::from_str(&item.addr)? });
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcIpAddress, { Self::from_str(&item.addr)? });
@@ -0,0 +1,96 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_notify::events::EventType;
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &EventType, protowire::RpcEventType, {
+    match item {
+        EventType::BlockAdded => protowire::RpcEventType::BlockAdded,
+        EventType::VirtualChainChanged => protowire::RpcEventType::VirtualChainChanged,
+        EventType::FinalityConflict => protowire::RpcEventType::FinalityConflict,
+        EventType::FinalityConflictResolved => protowire::RpcEventType::FinalityConflictResolved,
+        EventType::UtxosChanged => protowire::RpcEventType::UtxosChanged,
+        EventType::SinkBlueScoreChanged => protowire::RpcEventType::SinkBlueScoreChanged,
+        EventType::VirtualDaaScoreChanged => protowire::RpcEventType::VirtualDaaScoreChanged,
+        EventType::PruningPointUtxoSetOverride => protowire::RpcEventType::PruningPointUtxoSetOverride,
+        EventType::NewBlockTemplate => protowire::RpcEventType::NewBlockTemplate,
+        EventType::HealthAlert => protowire::RpcEventType::HealthAlert,
+        EventType::MempoolTransactionsEvicted => protowire::RpcEventType::MempoolTransactionsEvicted,
+        EventType::JobCompleted => protowire::RpcEventType::JobCompleted,
+        EventType::IndexResyncProgress => protowire::RpcEventType::IndexResyncProgress,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcSubscription, protowire::RpcSubscription, {
+    Self {
+        event_type: protowire::RpcEventType::from(&item.event_type) as i32,
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        all_addresses: item.all_addresses,
+        address_count: item.address_count,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcNotificationEventStats, protowire::RpcNotificationEventStats, {
+    Self {
+        event_type: protowire::RpcEventType::from(&item.event_type) as i32,
+        emitted: item.emitted,
+        delivered: item.delivered,
+        dropped: item.dropped,
+        average_fan_out: item.average_fan_out,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcNotifierStats, protowire::RpcNotifierStats, {
+    Self { name: item.name.clone(), events: item.events.iter().map(|x| x.into()).collect() }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcEventType, EventType, {
+    match item {
+        protowire::RpcEventType::BlockAdded => EventType::BlockAdded,
+        protowire::RpcEventType::VirtualChainChanged => EventType::VirtualChainChanged,
+        protowire::RpcEventType::FinalityConflict => EventType::FinalityConflict,
+        protowire::RpcEventType::FinalityConflictResolved => EventType::FinalityConflictResolved,
+        protowire::RpcEventType::UtxosChanged => EventType::UtxosChanged,
+        protowire::RpcEventType::SinkBlueScoreChanged => EventType::SinkBlueScoreChanged,
+        protowire::RpcEventType::VirtualDaaScoreChanged => EventType::VirtualDaaScoreChanged,
+        protowire::RpcEventType::PruningPointUtxoSetOverride => EventType::PruningPointUtxoSetOverride,
+        protowire::RpcEventType::NewBlockTemplate => EventType::NewBlockTemplate,
+        protowire::RpcEventType::HealthAlert => EventType::HealthAlert,
+        protowire::RpcEventType::MempoolTransactionsEvicted => EventType::MempoolTransactionsEvicted,
+        protowire::RpcEventType::JobCompleted => EventType::JobCompleted,
+        protowire::RpcEventType::IndexResyncProgress => EventType::IndexResyncProgress,
+    }
+});
+
+try_from!(item: &protowire::RpcSubscription, kaspa_rpc_core::RpcSubscription, {
+    let event_type = protowire::RpcEventType::try_from(item.event_type).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self {
+        event_type: event_type.into(),
+        include_accepted_transaction_ids: item.include_accepted_transaction_ids,
+        all_addresses: item.all_addresses,
+        address_count: item.address_count,
+    }
+});
+
+try_from!(item: &protowire::RpcNotificationEventStats, kaspa_rpc_core::RpcNotificationEventStats, {
+    let event_type = protowire::RpcEventType::try_from(item.event_type).map_err(|_| RpcError::PrimitiveToEnumConversionError)?;
+    Self {
+        event_type: event_type.into(),
+        emitted: item.emitted,
+        delivered: item.delivered,
+        dropped: item.dropped,
+        average_fan_out: item.average_fan_out,
+    }
+});
+
+try_from!(item: &protowire::RpcNotifierStats, kaspa_rpc_core::RpcNotifierStats, {
+    Self { name: item.name.clone(), events: item.events.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
@@ -0,0 +1,35 @@
+use crate::from;
+use crate::protowire;
+use kaspa_rpc_core::RpcSyncPhase;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcSyncPhase, protowire::RpcSyncPhase, {
+    match item {
+        RpcSyncPhase::Synced => protowire::RpcSyncPhase::Synced,
+        RpcSyncPhase::Idle => protowire::RpcSyncPhase::Idle,
+        RpcSyncPhase::ProofDownload => protowire::RpcSyncPhase::ProofDownload,
+        RpcSyncPhase::Headers => protowire::RpcSyncPhase::Headers,
+        RpcSyncPhase::UtxoSet => protowire::RpcSyncPhase::UtxoSet,
+        RpcSyncPhase::BlockBodies => protowire::RpcSyncPhase::BlockBodies,
+        RpcSyncPhase::VirtualResolution => protowire::RpcSyncPhase::VirtualResolution,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: protowire::RpcSyncPhase, RpcSyncPhase, {
+    match item {
+        protowire::RpcSyncPhase::Synced => RpcSyncPhase::Synced,
+        protowire::RpcSyncPhase::Idle => RpcSyncPhase::Idle,
+        protowire::RpcSyncPhase::ProofDownload => RpcSyncPhase::ProofDownload,
+        protowire::RpcSyncPhase::Headers => RpcSyncPhase::Headers,
+        protowire::RpcSyncPhase::UtxoSet => RpcSyncPhase::UtxoSet,
+        protowire::RpcSyncPhase::BlockBodies => RpcSyncPhase::BlockBodies,
+        protowire::RpcSyncPhase::VirtualResolution => RpcSyncPhase::VirtualResolution,
+    }
+});
@@ -63,6 +63,7 @@ from!(item: &kaspa_rpc_core::RpcTransactionVerboseData, protowire::RpcTransactio
         mass: item.mass,
         block_hash: item.block_hash.to_string(),
         block_time: item.block_time,
+        confirmations: item.confirmations,
     }
 });
 
@@ -75,6 +76,10 @@ from!(item: &kaspa_rpc_core::RpcTransactionOutputVerboseData, protowire::RpcTran
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcSubmitTransactionResult, protowire::RpcSubmitTransactionResult, {
+    Self { transaction_id: item.transaction_id.to_string(), error: item.error.clone().unwrap_or_default() }
+});
+
 from!(item: &kaspa_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTransactionIds, {
     Self {
         accepting_block_hash: item.accepting_block_hash.to_string(),
@@ -170,6 +175,7 @@ try_from!(item: &protowire::RpcTransactionVerboseData, kaspa_rpc_core::RpcTransa
         mass: item.mass,
         block_hash: RpcHash::from_str(&item.block_hash)?,
         block_time: item.block_time,
+        confirmations: item.confirmations,
     }
 });
 
@@ -182,6 +188,13 @@ try_from!(item: &protowire::RpcTransactionOutputVerboseData, kaspa_rpc_core::Rpc
     }
 });
 
+try_from!(item: &protowire::RpcSubmitTransactionResult, kaspa_rpc_core::RpcSubmitTransactionResult, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        error: if item.error.is_empty() { None } else { Some(item.error.clone()) },
+    }
+});
+
 try_from!(item: &protowire::RpcAcceptedTransactionIds, kaspa_rpc_core::RpcAcceptedTransactionIds, {
     Self {
         accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
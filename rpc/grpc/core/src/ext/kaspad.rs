@@ -2,9 +2,10 @@ use kaspa_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyFinalityConflictRequestMessage, NotifyHealthAlertRequestMessage, NotifyIndexResyncProgressRequestMessage,
+    NotifyJobCompletedRequestMessage, NotifyMempoolTransactionsEvictedRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
@@ -47,6 +48,7 @@ impl kaspad_request::Payload {
             }
             Scope::UtxosChanged(ref scope) => kaspad_request::Payload::NotifyUtxosChangedRequest(NotifyUtxosChangedRequestMessage {
                 addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                replace: scope.replace,
                 command: command.into(),
             }),
             Scope::SinkBlueScoreChanged(_) => {
@@ -64,6 +66,22 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::HealthAlert(_) => {
+                kaspad_request::Payload::NotifyHealthAlertRequest(NotifyHealthAlertRequestMessage { command: command.into() })
+            }
+            Scope::MempoolTransactionsEvicted(_) => {
+                kaspad_request::Payload::NotifyMempoolTransactionsEvictedRequest(NotifyMempoolTransactionsEvictedRequestMessage {
+                    command: command.into(),
+                })
+            }
+            Scope::JobCompleted(_) => {
+                kaspad_request::Payload::NotifyJobCompletedRequest(NotifyJobCompletedRequestMessage { command: command.into() })
+            }
+            Scope::IndexResyncProgress(_) => {
+                kaspad_request::Payload::NotifyIndexResyncProgressRequest(NotifyIndexResyncProgressRequestMessage {
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +97,10 @@ impl kaspad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyHealthAlertRequest(_)
+                | Payload::NotifyMempoolTransactionsEvictedRequest(_)
+                | Payload::NotifyJobCompletedRequest(_)
+                | Payload::NotifyIndexResyncProgressRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +130,10 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::HealthAlertNotification(_) => true,
+            Payload::MempoolTransactionsEvictedNotification(_) => true,
+            Payload::JobCompletedNotification(_) => true,
+            Payload::IndexResyncProgressNotification(_) => true,
             _ => false,
         }
     }
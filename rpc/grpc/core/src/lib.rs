@@ -4,9 +4,15 @@ pub mod ext;
 pub mod macros;
 pub mod ops;
 
+#[cfg(test)]
+mod tests;
+
 /// Maximum decoded gRPC message size to send and receive
 pub const RPC_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
 
 pub mod protowire {
     tonic::include_proto!("protowire");
+
+    /// Encoded file descriptor set of the protowire schema, for the gRPC reflection service
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("protowire_descriptor");
 }
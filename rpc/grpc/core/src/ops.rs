@@ -39,6 +39,24 @@ macro_rules! payload_type_enum {
                 }
             }
 
+            impl ResponsePayload {
+                /// Tells if the payload is a response carrying an error
+                pub fn is_error(&self) -> bool {
+                    match self {
+                        $(ResponsePayload::[<$variant_name Response>](message) => message.error.is_some()),*,
+                        _ => false
+                    }
+                }
+
+                /// Returns the message of the error carried by the response, if any
+                pub fn error_message(&self) -> Option<&str> {
+                    match self {
+                        $(ResponsePayload::[<$variant_name Response>](message) => message.error.as_ref().map(|error| error.message.as_str())),*,
+                        _ => None
+                    }
+                }
+            }
+
         }
     }
 }
@@ -61,6 +79,7 @@ pub enum KaspadPayloadOps {
     GetConnectedPeerInfo,
     AddPeer,
     SubmitTransaction,
+    SubmitTransactions,
     GetSubnetwork,
     GetVirtualChainFromBlock,
     GetBlockCount,
@@ -81,6 +100,33 @@ pub enum KaspadPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    GetTransactionMass,
+    BuildUnsignedTransaction,
+    GetDagStats,
+    GetHealth,
+    GetFeeEstimate,
+    GetSubscriptions,
+    GetVirtualParents,
+    GetConsensusParams,
+    GetTransactionById,
+    SampleChainBlocks,
+    GetConnections,
+    GetBlockByBlueScore,
+    GetBlockByDaaScore,
+    GetChainBlockByTimestamp,
+    GetPruningPointUtxos,
+    GetMempoolInfo,
+    SetMempoolPolicy,
+    GetCurrentBlockColor,
+    GetBans,
+    GetBlockProcessingStatus,
+    StartJob,
+    GetJobStatus,
+    GetNotificationStats,
+    GenerateBlocks,
+    GetServerCapabilities,
+    UpdateBlockTemplateExclusions,
+    GetChainQuality,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -91,6 +137,10 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyHealthAlert,
+    NotifyMempoolTransactionsEvicted,
+    NotifyJobCompleted,
+    NotifyIndexResyncProgress,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -0,0 +1,301 @@
+//! Protowire wire compatibility tests
+//!
+//! Fixture files under `testdata/protowire` hold messages encoded by previous releases. Decoding
+//! them with the current definitions and encoding them back must give the exact same bytes, which
+//! breaks as soon as a field gets removed, renumbered or has its type changed. Release fixtures are
+//! frozen and never edited.
+//!
+//! The `current` fixture holds every message type as encoded by the current definitions, so the messages
+//! and fields added since the last release get covered as well. On release, it gets copied to a fixture
+//! named after the release and added to [`RELEASE_FIXTURES`].
+
+use crate::protowire;
+use prost::{DecodeError, Message};
+use regex::Regex;
+use std::collections::HashSet;
+
+const RELEASE_FIXTURES: &[(&str, &str)] = &[("v0.14.1", include_str!("../../testdata/protowire/v0.14.1.txt"))];
+
+const CURRENT_FIXTURE: (&str, &str) = ("current", include_str!("../../testdata/protowire/current.txt"));
+
+const PROTO_FILES: &[&str] = &[include_str!("../../proto/messages.proto"), include_str!("../../proto/rpc.proto")];
+
+fn round_trip<T: Message + Default>(bytes: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    Ok(T::decode(bytes)?.encode_to_vec())
+}
+
+macro_rules! round_trip_by_name {
+    ($($message:ident),* $(,)?) => {
+        /// Decodes `bytes` as the message type named `name` and encodes it back.
+        ///
+        /// Listing the types here makes a renamed or removed message a compile-time failure.
+        fn round_trip_by_name(name: &str, bytes: &[u8]) -> Option<Result<Vec<u8>, DecodeError>> {
+            match name {
+                $(stringify!($message) => Some(round_trip::<protowire::$message>(bytes)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+round_trip_by_name!(
+    AddPeerRequestMessage,
+    AddPeerResponseMessage,
+    BanRequestMessage,
+    BanResponseMessage,
+    BandwidthMetrics,
+    BlockAddedNotificationMessage,
+    BuildUnsignedTransactionRequestMessage,
+    BuildUnsignedTransactionResponseMessage,
+    ColumnFamilyMetrics,
+    ConnectionMetrics,
+    ConsensusMetrics,
+    DatabaseMetrics,
+    EstimateNetworkHashesPerSecondRequestMessage,
+    EstimateNetworkHashesPerSecondResponseMessage,
+    ExportPruningPointUtxoSetChunkMessage,
+    ExportPruningPointUtxoSetRequestMessage,
+    FinalityConflictNotificationMessage,
+    FinalityConflictResolvedNotificationMessage,
+    FlowMetrics,
+    GenerateBlocksRequestMessage,
+    GenerateBlocksResponseMessage,
+    GetBalanceByAddressRequestMessage,
+    GetBalanceByAddressResponseMessage,
+    GetBalancesByAddressesRequestMessage,
+    GetBalancesByAddressesResponseMessage,
+    GetBansRequestMessage,
+    GetBansResponseMessage,
+    GetBlockByBlueScoreRequestMessage,
+    GetBlockByBlueScoreResponseMessage,
+    GetBlockByDaaScoreRequestMessage,
+    GetBlockByDaaScoreResponseMessage,
+    GetBlockCountRequestMessage,
+    GetBlockCountResponseMessage,
+    GetBlockDagInfoRequestMessage,
+    GetBlockDagInfoResponseMessage,
+    GetBlockProcessingStatusRequestMessage,
+    GetBlockProcessingStatusResponseMessage,
+    GetBlockRequestMessage,
+    GetBlockResponseMessage,
+    GetBlockTemplateRequestMessage,
+    GetBlockTemplateResponseMessage,
+    GetBlocksRequestMessage,
+    GetBlocksResponseMessage,
+    GetChainBlockByTimestampRequestMessage,
+    GetChainBlockByTimestampResponseMessage,
+    GetChainQualityRequestMessage,
+    GetChainQualityResponseMessage,
+    GetCoinSupplyRequestMessage,
+    GetCoinSupplyResponseMessage,
+    GetConnectedPeerInfoMessage,
+    GetConnectedPeerInfoRequestMessage,
+    GetConnectedPeerInfoResponseMessage,
+    GetConnectionsRequestMessage,
+    GetConnectionsResponseMessage,
+    GetConsensusParamsRequestMessage,
+    GetConsensusParamsResponseMessage,
+    GetCurrentBlockColorRequestMessage,
+    GetCurrentBlockColorResponseMessage,
+    GetCurrentNetworkRequestMessage,
+    GetCurrentNetworkResponseMessage,
+    GetDaaScoreTimestampEstimateRequestMessage,
+    GetDaaScoreTimestampEstimateResponseMessage,
+    GetDagStatsRequestMessage,
+    GetDagStatsResponseMessage,
+    GetFeeEstimateRequestMessage,
+    GetFeeEstimateResponseMessage,
+    GetHeadersRequestMessage,
+    GetHeadersResponseMessage,
+    GetHealthRequestMessage,
+    GetHealthResponseMessage,
+    GetInfoRequestMessage,
+    GetInfoResponseMessage,
+    GetJobStatusRequestMessage,
+    GetJobStatusResponseMessage,
+    GetMempoolEntriesByAddressesRequestMessage,
+    GetMempoolEntriesByAddressesResponseMessage,
+    GetMempoolEntriesRequestMessage,
+    GetMempoolEntriesResponseMessage,
+    GetMempoolEntryRequestMessage,
+    GetMempoolEntryResponseMessage,
+    GetMempoolInfoRequestMessage,
+    GetMempoolInfoResponseMessage,
+    GetMetricsRequestMessage,
+    GetMetricsResponseMessage,
+    GetNotificationStatsRequestMessage,
+    GetNotificationStatsResponseMessage,
+    GetPeerAddressesKnownAddressMessage,
+    GetPeerAddressesRequestMessage,
+    GetPeerAddressesResponseMessage,
+    GetPruningPointUtxosRequestMessage,
+    GetPruningPointUtxosResponseMessage,
+    GetServerCapabilitiesRequestMessage,
+    GetServerCapabilitiesResponseMessage,
+    GetServerInfoRequestMessage,
+    GetServerInfoResponseMessage,
+    GetSinkBlueScoreRequestMessage,
+    GetSinkBlueScoreResponseMessage,
+    GetSinkRequestMessage,
+    GetSinkResponseMessage,
+    GetSubnetworkRequestMessage,
+    GetSubnetworkResponseMessage,
+    GetSubscriptionsRequestMessage,
+    GetSubscriptionsResponseMessage,
+    GetSyncStatusRequestMessage,
+    GetSyncStatusResponseMessage,
+    GetTransactionByIdRequestMessage,
+    GetTransactionByIdResponseMessage,
+    GetTransactionMassRequestMessage,
+    GetTransactionMassResponseMessage,
+    GetUtxosByAddressesRequestMessage,
+    GetUtxosByAddressesResponseMessage,
+    GetVirtualChainFromBlockRequestMessage,
+    GetVirtualChainFromBlockResponseMessage,
+    GetVirtualParentsRequestMessage,
+    GetVirtualParentsResponseMessage,
+    HealthAlertNotificationMessage,
+    IndexResyncProgressNotificationMessage,
+    JobCompletedNotificationMessage,
+    KaspadRequest,
+    KaspadResponse,
+    LatencyBucket,
+    MempoolTransactionsEvictedNotificationMessage,
+    MethodMetrics,
+    NewBlockTemplateNotificationMessage,
+    NotifyBlockAddedRequestMessage,
+    NotifyBlockAddedResponseMessage,
+    NotifyFinalityConflictRequestMessage,
+    NotifyFinalityConflictResponseMessage,
+    NotifyHealthAlertRequestMessage,
+    NotifyHealthAlertResponseMessage,
+    NotifyIndexResyncProgressRequestMessage,
+    NotifyIndexResyncProgressResponseMessage,
+    NotifyJobCompletedRequestMessage,
+    NotifyJobCompletedResponseMessage,
+    NotifyMempoolTransactionsEvictedRequestMessage,
+    NotifyMempoolTransactionsEvictedResponseMessage,
+    NotifyNewBlockTemplateRequestMessage,
+    NotifyNewBlockTemplateResponseMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage,
+    NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifySinkBlueScoreChangedRequestMessage,
+    NotifySinkBlueScoreChangedResponseMessage,
+    NotifyUtxosChangedRequestMessage,
+    NotifyUtxosChangedResponseMessage,
+    NotifyVirtualChainChangedRequestMessage,
+    NotifyVirtualChainChangedResponseMessage,
+    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyVirtualDaaScoreChangedResponseMessage,
+    PingRequestMessage,
+    PingResponseMessage,
+    ProcessMetrics,
+    PruningPointUtxoSetOverrideNotificationMessage,
+    RequestMetrics,
+    ResolveFinalityConflictRequestMessage,
+    ResolveFinalityConflictResponseMessage,
+    RpcAcceptedTransactionIds,
+    RpcBalancesByAddressesEntry,
+    RpcBan,
+    RpcBlock,
+    RpcBlockHeader,
+    RpcBlockLevelParents,
+    RpcBlockVerboseData,
+    RpcBuildInfo,
+    RpcChainQualitySample,
+    RpcConnectionInfo,
+    RpcConsensusParams,
+    RpcError,
+    RpcExcludedParentCandidate,
+    RpcFeeEstimate,
+    RpcFeerateBucket,
+    RpcHealthAlert,
+    RpcIndexResyncProgress,
+    RpcJob,
+    RpcMempoolEntry,
+    RpcMempoolEntryByAddress,
+    RpcMempoolPolicy,
+    RpcMessageCounts,
+    RpcNotificationEventStats,
+    RpcNotifierStats,
+    RpcOutpoint,
+    RpcPaymentOutput,
+    RpcScriptPublicKey,
+    RpcSubmitTransactionResult,
+    RpcSubscription,
+    RpcTransaction,
+    RpcTransactionInput,
+    RpcTransactionInputVerboseData,
+    RpcTransactionOutput,
+    RpcTransactionOutputVerboseData,
+    RpcTransactionVerboseData,
+    RpcUtxoEntry,
+    RpcUtxosByAddressesCursor,
+    RpcUtxosByAddressesEntry,
+    SampleChainBlocksRequestMessage,
+    SampleChainBlocksResponseMessage,
+    SetMempoolPolicyRequestMessage,
+    SetMempoolPolicyResponseMessage,
+    ShutdownRequestMessage,
+    ShutdownResponseMessage,
+    SinkBlueScoreChangedNotificationMessage,
+    StartJobRequestMessage,
+    StartJobResponseMessage,
+    StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
+    StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage,
+    StorageMetrics,
+    StreamBlocksBlockMessage,
+    StreamBlocksRequestMessage,
+    SubmitBlockRequestMessage,
+    SubmitBlockResponseMessage,
+    SubmitTransactionRequestMessage,
+    SubmitTransactionResponseMessage,
+    SubmitTransactionsRequestMessage,
+    SubmitTransactionsResponseMessage,
+    TenantMetrics,
+    UnbanRequestMessage,
+    UnbanResponseMessage,
+    UpdateBlockTemplateExclusionsRequestMessage,
+    UpdateBlockTemplateExclusionsResponseMessage,
+    UtxosChangedNotificationMessage,
+    VirtualChainChangedNotificationMessage,
+    VirtualDaaScoreChangedNotificationMessage,
+);
+
+/// Yields the message name and encoded bytes of every entry of a fixture file
+fn entries(fixture: &str) -> impl Iterator<Item = (&str, Vec<u8>)> {
+    fixture.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).map(|line| {
+        let (name, hex) = line.split_once(' ').unwrap_or((line, ""));
+        let mut bytes = vec![0u8; hex.len() / 2];
+        faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap_or_else(|err| panic!("invalid hex for {name}: {err}"));
+        (name, bytes)
+    })
+}
+
+#[test]
+fn test_fixtures_round_trip() {
+    for (release, fixture) in RELEASE_FIXTURES.iter().chain(std::iter::once(&CURRENT_FIXTURE)) {
+        for (name, bytes) in entries(fixture) {
+            let encoded = round_trip_by_name(name, &bytes)
+                .unwrap_or_else(|| panic!("{release}: message {name} no longer exists"))
+                .unwrap_or_else(|err| panic!("{release}: message {name} cannot be decoded: {err}"));
+            assert_eq!(encoded, bytes, "{release}: message {name} does not round-trip");
+        }
+    }
+}
+
+#[test]
+fn test_fixtures_cover_all_messages() {
+    // Generated type names differ from proto names by case only (ie. RPCError -> RpcError)
+    let re = Regex::new(r"(?m)^\s*message\s+(\w+)").unwrap();
+    let (release, fixture) = CURRENT_FIXTURE;
+    let covered = entries(fixture).map(|(name, _)| name.to_lowercase()).collect::<HashSet<_>>();
+    for proto in PROTO_FILES {
+        for captures in re.captures_iter(proto) {
+            let name = &captures[1];
+            assert!(covered.contains(&name.to_lowercase()), "message {name} has no fixture in {release}");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Structured access log of the gRPC requests.
+//!
+//! Every request answered by the server is recorded as a JSON line under the [`ACCESS_LOG_TARGET`] log target,
+//! which kaspad routes to a rolling file of its own when started with `--access-log`. The records are built only
+//! while the target is enabled, so a disabled access log costs a single level check per request.
+
+use crate::connection::ConnectionId;
+use kaspa_core::{log::ACCESS_LOG_TARGET, time::unix_now};
+use log::{log_enabled, Level};
+use serde::Serialize;
+use std::{net::SocketAddr, time::Duration};
+
+/// A gRPC request as recorded in the access log
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogRecord<'a> {
+    /// Unix time of the response, in milliseconds
+    pub timestamp: u64,
+    pub remote_address: SocketAddr,
+    pub connection_id: ConnectionId,
+    /// Name of the tenant the connection authenticated as, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<&'a str>,
+    pub method: &'a str,
+    pub latency_us: u64,
+    /// Size of the encoded response, in bytes
+    pub response_size: usize,
+    /// Message of the RPC error the request was answered with, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a str>,
+}
+
+impl<'a> AccessLogRecord<'a> {
+    pub fn new(
+        remote_address: SocketAddr,
+        connection_id: ConnectionId,
+        tenant: Option<&'a str>,
+        method: &'a str,
+        latency: Duration,
+        response_size: usize,
+        error: Option<&'a str>,
+    ) -> Self {
+        Self {
+            timestamp: unix_now(),
+            remote_address,
+            connection_id,
+            tenant,
+            method,
+            latency_us: latency.as_micros() as u64,
+            response_size,
+            error,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("an access log record is always serializable")
+    }
+}
+
+/// Tells if the access log records are written anywhere
+pub fn is_enabled() -> bool {
+    log_enabled!(target: ACCESS_LOG_TARGET, Level::Info)
+}
+
+pub fn record(record: &AccessLogRecord) {
+    log::info!(target: ACCESS_LOG_TARGET, "{}", record.to_json());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_log_record_json() {
+        let address: SocketAddr = "10.0.0.1:52000".parse().unwrap();
+        let connection_id = ConnectionId::nil();
+        let mut record = AccessLogRecord::new(
+            address,
+            connection_id,
+            None,
+            "GetBlockDagInfo",
+            Duration::from_micros(1_250),
+            312,
+            Some("block not found"),
+        );
+        record.timestamp = 1_700_000_000_000;
+        assert_eq!(
+            record.to_json(),
+            r#"{"timestamp":1700000000000,"remoteAddress":"10.0.0.1:52000","connectionId":"00000000-0000-0000-0000-000000000000","method":"GetBlockDagInfo","latencyUs":1250,"responseSize":312,"error":"block not found"}"#
+        );
+
+        let record = AccessLogRecord::new(address, connection_id, Some("explorer"), "GetInfo", Duration::ZERO, 0, None);
+        let json = record.to_json();
+        assert!(json.contains(r#""tenant":"explorer""#));
+        assert!(!json.contains("error"));
+    }
+}
@@ -1,15 +1,20 @@
-use crate::{connection_handler::ConnectionHandler, manager::Manager};
+use crate::{
+    compression::GrpcCompressionConfig, connection_handler::ConnectionHandler, deadline::RequestDeadlines, health::GrpcHealth,
+    listen::GrpcEndpoint, manager::Manager, outgoing::OutgoingRouteConfig, rate_limit::RateLimitConfig, tenant::Tenants,
+    tls::GrpcTlsConfig, transport::GrpcTransportConfig,
+};
 use kaspa_core::debug;
-use kaspa_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
+use kaspa_notify::{notifier::Notifier, stats::NotificationStats, subscription::context::SubscriptionContext};
 use kaspa_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use kaspa_rpc_service::access::RpcAccessPolicy;
 use kaspa_utils::networking::NetAddress;
-use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_utils_tower::counters::{TowerConnectionCounters, TowerRequestCounters};
 use std::{ops::Deref, sync::Arc};
 use tokio::sync::{mpsc::channel as mpsc_channel, oneshot::Sender as OneshotSender};
 
 pub struct Adaptor {
-    /// If a server was started, it will get cleaned up when this sender is dropped or invoked
-    _server_termination: Option<OneshotSender<()>>,
+    /// The servers started by the adaptor, one per listening address, get cleaned up when these senders are dropped or invoked
+    _server_terminations: Vec<OneshotSender<()>>,
 
     /// An object for handling new connections coming from clients
     connection_handler: ConnectionHandler,
@@ -17,22 +22,25 @@ pub struct Adaptor {
     /// An object for managing a list of active connections
     manager: Manager,
 
-    /// The network address of the server
-    serve_address: NetAddress,
+    /// The endpoints the server listens on
+    serve_endpoints: Vec<GrpcEndpoint>,
 }
 
 impl Adaptor {
     fn new(
-        server_termination: Option<OneshotSender<()>>,
+        server_terminations: Vec<OneshotSender<()>>,
         connection_handler: ConnectionHandler,
         manager: Manager,
-        serve_address: NetAddress,
+        serve_endpoints: Vec<GrpcEndpoint>,
     ) -> Self {
-        Self { _server_termination: server_termination, connection_handler, manager, serve_address }
+        Self { _server_terminations: server_terminations, connection_handler, manager, serve_endpoints }
     }
 
+    /// Creates a server listening on every endpoint of `bindings` and enforcing the access policy
+    /// paired with it, all the connections sharing the same manager and notifier
+    #[allow(clippy::too_many_arguments)]
     pub fn server(
-        serve_address: NetAddress,
+        bindings: Vec<(GrpcEndpoint, Arc<RpcAccessPolicy>)>,
         network_bps: u64,
         manager: Manager,
         core_service: DynRpcService,
@@ -40,6 +48,13 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        tenants: Option<Arc<Tenants>>,
+        tls: Option<GrpcTlsConfig>,
+        rate_limit: Option<RateLimitConfig>,
+        deadlines: RequestDeadlines,
+        compression: GrpcCompressionConfig,
+        transport: GrpcTransportConfig,
+        outgoing: OutgoingRouteConfig,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -50,22 +65,55 @@ impl Adaptor {
             subscription_context,
             broadcasters,
             counters,
+            tenants,
+            tls,
+            rate_limit,
+            deadlines,
+            compression,
+            transport,
+            outgoing,
         );
-        let server_termination = connection_handler.serve(serve_address);
-        let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
+        let server_terminations = bindings
+            .iter()
+            .map(|(serve_endpoint, access_policy)| connection_handler.serve(serve_endpoint.clone(), access_policy.clone()))
+            .collect();
+        let serve_endpoints = bindings.into_iter().map(|(serve_endpoint, _)| serve_endpoint).collect();
+        let adaptor = Arc::new(Adaptor::new(server_terminations, connection_handler, manager, serve_endpoints));
         adaptor.manager.clone().start_event_loop(manager_receiver);
         adaptor.start();
         adaptor
     }
 
+    /// Returns the first network address of the server
     pub fn serve_address(&self) -> NetAddress {
-        self.serve_address
+        self.serve_addresses()[0]
+    }
+
+    /// Returns the network addresses of the server, excluding its unix domain sockets
+    pub fn serve_addresses(&self) -> Vec<NetAddress> {
+        self.serve_endpoints.iter().filter_map(GrpcEndpoint::net_address).collect()
+    }
+
+    pub fn serve_endpoints(&self) -> &[GrpcEndpoint] {
+        &self.serve_endpoints
     }
 
     pub fn start(&self) {
         self.connection_handler.start()
     }
 
+    pub fn notification_stats(&self) -> Arc<NotificationStats> {
+        self.connection_handler.notification_stats()
+    }
+
+    pub fn health(&self) -> GrpcHealth {
+        self.connection_handler.health()
+    }
+
+    pub fn request_counters(&self) -> Arc<TowerRequestCounters> {
+        self.connection_handler.request_counters()
+    }
+
     /// Terminates all connections and cleans up any additional async resources
     pub async fn stop(&self) -> RpcResult<()> {
         debug!("GRPC, Stopping the adaptor");
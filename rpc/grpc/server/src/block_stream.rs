@@ -0,0 +1,80 @@
+use futures::Stream;
+use kaspa_core::debug;
+use kaspa_grpc_core::protowire::{self, StreamBlocksBlockMessage};
+use kaspa_rpc_core::{api::rpc::DynRpcService, GetBlockRequest, GetBlocksRequest, RpcBlock, RpcBlockVerbosity, RpcHash};
+use std::collections::HashSet;
+
+fn block_message(index: u64, block: &RpcBlock, is_last: bool) -> StreamBlocksBlockMessage {
+    StreamBlocksBlockMessage { index, block: Some(block.into()), is_last, error: None }
+}
+
+/// Builds the stream of the blocks from `low_hash` (included, genesis if `None`) up to the sink and its anticone,
+/// paging through the block hashes with the `GetBlocks` method of `core_service` and reading every block with
+/// `GetBlock` only when the client polls the stream, so a slow client throttles the server through the transport
+/// flow control.
+///
+/// Every page starts at the last hash of the previous one, the hashes already streamed in the previous page being
+/// skipped. The stream ends when a page brings no new block, after `max_blocks` blocks if some, or after a message
+/// reporting an error.
+pub fn block_stream(
+    core_service: DynRpcService,
+    low_hash: Option<RpcHash>,
+    verbosity: RpcBlockVerbosity,
+    max_blocks: Option<u64>,
+) -> impl Stream<Item = Result<StreamBlocksBlockMessage, tonic::Status>> + Send + 'static {
+    async_stream::stream! {
+        let mut low_hash = low_hash;
+        let mut previous_page = HashSet::new();
+        // A block is held back until the next one is read so the last block of the stream can be flagged as such
+        let mut pending: Option<RpcBlock> = None;
+        let mut index = 0;
+        let mut block_count = 0;
+        let mut error = None;
+        let limit_reached = |block_count| max_blocks.is_some_and(|max| block_count >= max);
+        'paging: while !limit_reached(block_count) {
+            // The hashes only, so the page stays small whatever the verbosity
+            let request = GetBlocksRequest::new(low_hash, false, false).with_verbosity(RpcBlockVerbosity::HeaderOnly);
+            let page = match core_service.get_blocks_call(request).await {
+                Ok(page) => page,
+                Err(err) => {
+                    error = Some(err);
+                    break;
+                }
+            };
+            let hashes = page.block_hashes.iter().copied().filter(|hash| !previous_page.contains(hash)).collect::<Vec<_>>();
+            if hashes.is_empty() {
+                break;
+            }
+            for hash in hashes {
+                if limit_reached(block_count) {
+                    break 'paging;
+                }
+                let block = match core_service.get_block_call(GetBlockRequest::new(hash, false).with_verbosity(verbosity)).await {
+                    Ok(response) => response.block,
+                    Err(err) => {
+                        error = Some(err);
+                        break 'paging;
+                    }
+                };
+                block_count += 1;
+                if let Some(block) = pending.replace(block) {
+                    yield Ok(block_message(index, &block, false));
+                    index += 1;
+                }
+            }
+            low_hash = page.block_hashes.last().copied();
+            previous_page = page.block_hashes.into_iter().collect();
+        }
+        if let Some(block) = pending.take() {
+            yield Ok(block_message(index, &block, error.is_none()));
+            index += 1;
+        }
+        match error {
+            None => debug!("GRPC, streamed {} blocks", index),
+            Some(err) => {
+                debug!("GRPC, block stream failed at block {}: {}", index, err);
+                yield Ok(StreamBlocksBlockMessage { index, error: Some(protowire::RpcError::from(err)), ..Default::default() });
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use tonic::codec::CompressionEncoding;
+
+/// A compression algorithm of the gRPC messages
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GrpcCompression {
+    Gzip,
+    None,
+}
+
+impl GrpcCompression {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrpcCompression::Gzip => "gzip",
+            GrpcCompression::None => "none",
+        }
+    }
+
+    fn encoding(&self) -> Option<CompressionEncoding> {
+        match self {
+            GrpcCompression::Gzip => Some(CompressionEncoding::Gzip),
+            GrpcCompression::None => None,
+        }
+    }
+}
+
+impl Display for GrpcCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for GrpcCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gzip" => Ok(GrpcCompression::Gzip),
+            "none" => Ok(GrpcCompression::None),
+            _ => Err(format!("unknown gRPC compression {s}, expected one of gzip, none")),
+        }
+    }
+}
+
+/// The compression algorithms enabled on the gRPC server.
+///
+/// The server accepts requests compressed with any enabled algorithm. Responses and notifications are compressed
+/// with the first algorithm of the client `grpc-accept-encoding` header which is enabled, and sent uncompressed
+/// if there is none, so every client negotiates its own algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcCompressionConfig {
+    encodings: Vec<CompressionEncoding>,
+}
+
+impl GrpcCompressionConfig {
+    pub fn new(algorithms: &[GrpcCompression]) -> Result<Self, String> {
+        if algorithms.contains(&GrpcCompression::None) && algorithms.len() > 1 {
+            return Err("gRPC compression none cannot be combined with other algorithms".to_string());
+        }
+        let mut encodings = Vec::with_capacity(algorithms.len());
+        for encoding in algorithms.iter().filter_map(GrpcCompression::encoding) {
+            if !encodings.contains(&encoding) {
+                encodings.push(encoding);
+            }
+        }
+        Ok(Self { encodings })
+    }
+
+    pub(crate) fn encodings(&self) -> &[CompressionEncoding] {
+        &self.encodings
+    }
+}
+
+impl Default for GrpcCompressionConfig {
+    fn default() -> Self {
+        Self { encodings: vec![CompressionEncoding::Gzip] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_config() {
+        let config = GrpcCompressionConfig::new(&[GrpcCompression::Gzip, GrpcCompression::Gzip]).unwrap();
+        assert_eq!(config.encodings(), &[CompressionEncoding::Gzip]);
+        assert_eq!(GrpcCompressionConfig::new(&[]).unwrap(), GrpcCompressionConfig::new(&[GrpcCompression::None]).unwrap());
+        assert!(GrpcCompressionConfig::new(&[GrpcCompression::None]).unwrap().encodings().is_empty());
+        assert!(GrpcCompressionConfig::new(&[GrpcCompression::None, GrpcCompression::Gzip]).is_err());
+    }
+}
@@ -2,11 +2,14 @@ use crate::{
     connection_handler::ServerContext,
     error::{GrpcServerError, GrpcServerResult},
     manager::ManagerEvent,
+    outgoing::{OutgoingMessage, OutgoingSendError, OutgoingSender},
     request_handler::{
         factory::Factory,
         interface::{Interface, KaspadRoutingPolicy},
         method::RoutingPolicy,
     },
+    request_limits::check_request_limits,
+    tenant::{Tenant, TenantSubscriptions},
 };
 use async_channel::{bounded, Receiver as MpmcReceiver, Sender as MpmcSender, TrySendError as MpmcTrySendError};
 use itertools::Itertools;
@@ -21,7 +24,7 @@ use kaspa_notify::{
     listener::{ListenerId, ListenerLifespan},
     notifier::Notifier,
 };
-use kaspa_rpc_core::Notification;
+use kaspa_rpc_core::{error::RpcError, Notification};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -33,15 +36,15 @@ use std::{
         Arc,
     },
 };
+use tokio::select;
 use tokio::sync::mpsc::Sender as MpscSender;
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
-use tokio::{select, sync::mpsc::error::TrySendError};
 use tonic::Streaming;
 use uuid::Uuid;
 
 pub type IncomingRoute = MpmcReceiver<KaspadRequest>;
 pub type GrpcNotifier = Notifier<Notification, Connection>;
-pub type GrpcSender = MpscSender<KaspadResponse>;
+pub type GrpcSender = OutgoingSender;
 pub type StatusResult<T> = Result<T, tonic::Status>;
 pub type ConnectionId = Uuid;
 
@@ -79,6 +82,9 @@ struct Inner {
     /// The server RPC core service and notifier
     server_context: ServerContext,
 
+    /// The tenant this client authenticated as, if any
+    tenant: Option<Arc<Tenant>>,
+
     /// Used for managing connection mutable state
     mutable_state: Mutex<InnerMutableState>,
 
@@ -125,11 +131,14 @@ struct Router {
 
     /// The interface providing the RPC methods to the request handlers
     interface: Arc<Interface>,
+
+    /// Notification subscriptions counted against the connection tenant quota
+    tenant_subscriptions: TenantSubscriptions,
 }
 
 impl Router {
     fn new(server_context: ServerContext, interface: Arc<Interface>) -> Self {
-        Self { routing_map: Default::default(), server_context, interface }
+        Self { routing_map: Default::default(), server_context, interface, tenant_subscriptions: Default::default() }
     }
 
     fn get_or_subscribe(&mut self, connection: &Connection, rpc_op: KaspadPayloadOps) -> &Route {
@@ -172,10 +181,47 @@ impl Router {
 
     async fn route_to_handler(&mut self, connection: &Connection, request: KaspadRequest) -> GrpcServerResult<()> {
         if request.payload.is_none() {
-            debug!("GRPC, Route to handler got empty payload, client: {}", connection);
-            return Err(GrpcServerError::InvalidRequestPayload);
+            // Requests truly missing their payload are rejected when decoded, so the payload of this one is unknown to
+            // this version of the protocol, most probably sent by a newer client. It gets answered with an empty payload,
+            // letting the client fail the call as unsupported without waiting for a timeout
+            debug!("GRPC, Route to handler got an unknown payload, client: {}", connection);
+            if request.id == 0 {
+                return Err(GrpcServerError::InvalidRequestPayload);
+            }
+            connection.enqueue(KaspadResponse { id: request.id, payload: None }).await?;
+            return Ok(());
+        }
+        let rpc_op: KaspadPayloadOps = request.payload.as_ref().unwrap().into();
+        if let Err(err) = self.server_context.access_policy.check(&format!("{rpc_op:?}")) {
+            debug!("GRPC, request rejected: {}, client: {}", err, connection);
+            let response = KaspadResponse { id: request.id, payload: Some(rpc_op.to_error_response(err)) };
+            connection.enqueue(response).await?;
+            return Ok(());
+        }
+        if let Err(err) = check_request_limits(request.payload.as_ref().unwrap()) {
+            debug!("GRPC, request rejected: {}, client: {}", err, connection);
+            let response = KaspadResponse { id: request.id, payload: Some(rpc_op.to_error_response(err)) };
+            connection.enqueue(response).await?;
+            return Ok(());
+        }
+        if let Some(ref rate_limiter) = self.server_context.rate_limiter {
+            if let Err(err) = rate_limiter.check(connection.net_address().ip(), rpc_op) {
+                debug!("GRPC, request rejected: {}, client: {}", err, connection);
+                let response =
+                    KaspadResponse { id: request.id, payload: Some(rpc_op.to_error_response(RpcError::General(err.to_string()))) };
+                connection.enqueue(response).await?;
+                return Ok(());
+            }
+        }
+        if let Some(tenant) = connection.tenant() {
+            if let Err(err) = tenant.admit(request.payload.as_ref().unwrap(), &mut self.tenant_subscriptions) {
+                debug!("GRPC, request rejected: {}, client: {}", err, connection);
+                let response =
+                    KaspadResponse { id: request.id, payload: Some(rpc_op.to_error_response(RpcError::General(err.to_string()))) };
+                connection.enqueue(response).await?;
+                return Ok(());
+            }
         }
-        let rpc_op = request.payload.as_ref().unwrap().into();
         let route = self.get_or_subscribe(connection, rpc_op);
         match route.policy {
             RoutingPolicy::Enqueue => match route.send(request).await {
@@ -196,8 +242,11 @@ impl Router {
         }
     }
 
-    fn unsubscribe_all(&mut self) {
+    fn unsubscribe_all(&mut self, connection: &Connection) {
         self.routing_map.clear();
+        if let Some(tenant) = connection.tenant() {
+            tenant.disconnect(&mut self.tenant_subscriptions);
+        }
     }
 }
 
@@ -217,12 +266,16 @@ impl Connection {
         net_address: SocketAddr,
         server_context: ServerContext,
         interface: Arc<Interface>,
+        tenant: Option<Arc<Tenant>>,
         manager_sender: MpscSender<ManagerEvent>,
         mut incoming_stream: Streaming<KaspadRequest>,
         outgoing_route: GrpcSender,
     ) -> Self {
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
         let mut router = Router::new(server_context.clone(), interface.clone());
+        if let Some(ref tenant) = tenant {
+            tenant.connect();
+        }
         let connection = Self {
             inner: Arc::new(Inner {
                 connection_id: Uuid::new_v4(),
@@ -230,6 +283,7 @@ impl Connection {
                 outgoing_route,
                 manager_sender,
                 server_context,
+                tenant,
                 mutable_state: Mutex::new(InnerMutableState::new(Some(shutdown_sender))),
                 is_closed: AtomicBool::new(false),
             }),
@@ -279,7 +333,7 @@ impl Connection {
             connection.unregister_listener();
 
             // Drop all routes, triggering the drop of all handlers
-            router.unsubscribe_all();
+            router.unsubscribe_all(&connection);
 
             // Mark as closed
             connection.close();
@@ -314,10 +368,19 @@ impl Connection {
         self.inner.connection_id
     }
 
+    pub fn tenant(&self) -> Option<&Arc<Tenant>> {
+        self.inner.tenant.as_ref()
+    }
+
     pub fn notifier(&self) -> Arc<GrpcNotifier> {
         self.inner.server_context.notifier.clone()
     }
 
+    /// Returns the notification listener id of the connection, if it ever subscribed to some notifications
+    pub fn listener_id(&self) -> Option<ListenerId> {
+        self.inner.mutable_state.lock().listener_id
+    }
+
     pub fn get_or_register_listener_id(&self) -> GrpcServerResult<ListenerId> {
         match self.is_closed() {
             false => Ok(*self.inner.mutable_state.lock().listener_id.get_or_insert_with(|| {
@@ -344,11 +407,19 @@ impl Connection {
 
     /// Enqueues a response to be sent to the client
     pub async fn enqueue(&self, response: KaspadResponse) -> GrpcServerResult<()> {
-        assert!(response.payload.is_some(), "Kaspad gRPC message should always have a value");
-        match self.inner.outgoing_route.try_send(response) {
-            Ok(_) => Ok(()),
-            Err(TrySendError::Closed(_)) => Err(GrpcServerError::ConnectionClosed),
-            Err(TrySendError::Full(_)) => {
+        assert!(response.payload.is_some() || response.id != 0, "Kaspad gRPC message should always have a value or an id");
+        self.enqueue_message(response.into())
+    }
+
+    fn enqueue_message(&self, message: OutgoingMessage) -> GrpcServerResult<()> {
+        match self.inner.outgoing_route.try_send(message) {
+            Ok(0) => Ok(()),
+            Ok(dropped) => {
+                debug!("GRPC, Outgoing route of client {} is full, dropped {} notification(s)", self, dropped);
+                Ok(())
+            }
+            Err(OutgoingSendError::Closed) => Err(GrpcServerError::ConnectionClosed),
+            Err(OutgoingSendError::Full) => {
                 // If the outgoing route reaches full capacity, with high probability something is going wrong
                 // with this connection so we disconnect the client.
                 self.close();
@@ -404,7 +475,7 @@ pub enum GrpcEncoding {
 #[async_trait::async_trait]
 impl ConnectionT for Connection {
     type Notification = Notification;
-    type Message = Arc<KaspadResponse>;
+    type Message = OutgoingMessage;
     type Encoding = GrpcEncoding;
     type Error = super::error::GrpcServerError;
 
@@ -413,12 +484,14 @@ impl ConnectionT for Connection {
     }
 
     fn into_message(notification: &kaspa_rpc_core::Notification, _: &Self::Encoding) -> Self::Message {
-        Arc::new((notification).into())
+        // Encoded once for all the connections it is broadcast to
+        OutgoingMessage::encode(&notification.into())
     }
 
     async fn send(&self, message: Self::Message) -> Result<(), Self::Error> {
         match !self.is_closed() {
-            true => self.enqueue((*message).clone()).await,
+            // The encoding of the notification is shared with the other connections
+            true => self.enqueue_message(message),
             false => Err(NotificationError::ConnectionClosed.into()),
         }
     }
@@ -1,52 +1,72 @@
 use crate::{
+    block_stream::block_stream,
     collector::{GrpcServiceCollector, GrpcServiceConverter},
+    compression::GrpcCompressionConfig,
     connection::Connection,
-    manager::{ManagerEvent, RegistrationRequest},
+    deadline::RequestDeadlines,
+    health::{GrpcHealth, HEALTH_CHECK_INTERVAL},
+    listen::GrpcEndpoint,
+    manager::{ManagerEvent, RegistrationError, RegistrationRequest},
+    message_stream::ProtowireServer,
+    outgoing::{outgoing_channel, OutgoingReceiver, OutgoingRouteConfig},
+    rate_limit::{RateLimitConfig, RateLimiter},
     request_handler::{factory::Factory, interface::Interface},
+    tenant::{Tenants, API_KEY_METADATA_KEY},
+    tls::GrpcTlsConfig,
+    transport::GrpcTransportConfig,
+    utxo_export::pruning_point_utxo_set_stream,
 };
 use futures::{FutureExt, Stream};
 use kaspa_core::{debug, info, warn};
 use kaspa_grpc_core::{
+    convert::block::try_block_verbosity,
     protowire::{
-        rpc_server::{Rpc, RpcServer},
-        KaspadRequest, KaspadResponse,
+        rpc_server::Rpc, ExportPruningPointUtxoSetChunkMessage, ExportPruningPointUtxoSetRequestMessage, KaspadRequest,
+        KaspadResponse, StreamBlocksBlockMessage, StreamBlocksRequestMessage, FILE_DESCRIPTOR_SET,
     },
-    RPC_MAX_MESSAGE_SIZE,
 };
 use kaspa_notify::{
     connection::ChannelType,
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
     notifier::Notifier,
+    stats::NotificationStats,
     subscriber::Subscriber,
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::rpc::DynRpcService,
+    api::{
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection},
-    Notification, RpcResult,
+    Notification, RpcHash, RpcResult,
 };
-use kaspa_utils::networking::NetAddress;
+use kaspa_rpc_service::access::{op_name, RpcAccessPolicy};
 use kaspa_utils_tower::{
-    counters::TowerConnectionCounters,
+    counters::{TowerConnectionCounters, TowerRequestCounters},
     middleware::{measure_request_body_size_layer, CountBytesBody, MapResponseBodyLayer},
 };
 use std::fmt::Debug;
 use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     pin::Pin,
+    str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
     time::Duration,
 };
-use tokio::sync::mpsc::{channel as mpsc_channel, Sender as MpscSender};
+use tokio::sync::mpsc::Sender as MpscSender;
 use tokio::{
     sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender},
     time::timeout,
 };
-use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-use tonic::{codec::CompressionEncoding, transport::Server as TonicServer, Request, Response};
+#[cfg(unix)]
+use tokio_stream::wrappers::UnixListenerStream;
+use tokio_stream::StreamExt;
+use tonic::{transport::Server as TonicServer, Request, Response};
 
 #[derive(Clone)]
 pub struct ServerContext {
@@ -54,11 +74,26 @@ pub struct ServerContext {
     pub core_service: DynRpcService,
     /// The notifier relaying RPC core notifications to connections
     pub notifier: Arc<Notifier<Notification, Connection>>,
+    /// The RPC methods served on the listening address of the connections
+    pub access_policy: Arc<RpcAccessPolicy>,
+    /// The request rate limiter of the clients, if any
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// The deadlines of the requests
+    pub deadlines: Arc<RequestDeadlines>,
+    /// The request counters of the RPC methods
+    pub request_counters: Arc<TowerRequestCounters>,
 }
 
 impl ServerContext {
-    pub fn new(core_service: DynRpcService, notifier: Arc<Notifier<Notification, Connection>>) -> Self {
-        Self { core_service, notifier }
+    pub fn new(
+        core_service: DynRpcService,
+        notifier: Arc<Notifier<Notification, Connection>>,
+        access_policy: Arc<RpcAccessPolicy>,
+        rate_limiter: Option<Arc<RateLimiter>>,
+        deadlines: Arc<RequestDeadlines>,
+        request_counters: Arc<TowerRequestCounters>,
+    ) -> Self {
+        Self { core_service, notifier, access_policy, rate_limiter, deadlines, request_counters }
     }
 }
 
@@ -76,11 +111,24 @@ pub struct ConnectionHandler {
     interface: Arc<Interface>,
     running: Arc<AtomicBool>,
     counters: Arc<TowerConnectionCounters>,
+    tenants: Option<Arc<Tenants>>,
+    tls: Option<GrpcTlsConfig>,
+    compression: GrpcCompressionConfig,
+    transport: GrpcTransportConfig,
+    outgoing: OutgoingRouteConfig,
+    /// Whether the connections come from a unix domain socket, see `serve`
+    unix_socket: bool,
+    /// The health status reported by the grpc.health.v1 service of every listening address
+    health: GrpcHealth,
 }
 
 const GRPC_SERVER: &str = "grpc-server";
 
+/// Address standing for the peers of a unix domain socket, which are local by nature but have no network address
+const UNIX_SOCKET_PEER_ADDRESS: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+
 impl ConnectionHandler {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         network_bps: u64,
         manager_sender: MpscSender<ManagerEvent>,
@@ -89,6 +137,13 @@ impl ConnectionHandler {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        tenants: Option<Arc<Tenants>>,
+        tls: Option<GrpcTlsConfig>,
+        rate_limit: Option<RateLimitConfig>,
+        deadlines: RequestDeadlines,
+        compression: GrpcCompressionConfig,
+        transport: GrpcTransportConfig,
+        outgoing: OutgoingRouteConfig,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -114,46 +169,94 @@ impl ConnectionHandler {
             broadcasters,
             policies,
         ));
-        let server_context = ServerContext::new(core_service, notifier);
+        let rate_limiter = rate_limit.map(|config| Arc::new(RateLimiter::new(config)));
+        // The access policy is set by every listening address, see `serve`
+        let server_context =
+            ServerContext::new(core_service, notifier, Default::default(), rate_limiter, Arc::new(deadlines), Default::default());
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
-        Self { manager_sender, server_context, interface, running, counters }
+        Self {
+            manager_sender,
+            server_context,
+            interface,
+            running,
+            counters,
+            tenants,
+            tls,
+            compression,
+            transport,
+            outgoing,
+            unix_socket: false,
+            health: Default::default(),
+        }
     }
 
-    /// Launches a gRPC server listener loop
-    pub(crate) fn serve(&self, serve_address: NetAddress) -> OneshotSender<()> {
+    /// Launches a gRPC server listener loop enforcing `access_policy` on the connections to `serve_address`
+    pub(crate) fn serve(&self, serve_address: GrpcEndpoint, access_policy: Arc<RpcAccessPolicy>) -> OneshotSender<()> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let (signal_sender, signal_receiver) = oneshot_channel::<()>();
-        let connection_handler = self.clone();
-        info!("GRPC Server starting on: {}", serve_address);
+        let mut connection_handler = self.clone();
+        connection_handler.server_context.access_policy = access_policy;
+        connection_handler.unix_socket = serve_address.is_unix();
+        let tls = self.tls.clone();
+        let compression = self.compression.clone();
+        let transport = self.transport;
+        let health = self.health.clone();
+        match tls {
+            Some(ref tls) if tls.requires_client_certificate() => {
+                info!("GRPC Server starting on: {} (TLS, client certificate required)", serve_address)
+            }
+            Some(_) => info!("GRPC Server starting on: {} (TLS)", serve_address),
+            None => info!("GRPC Server starting on: {}", serve_address),
+        }
 
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
 
         // Spawn server task
+        let termination_address = serve_address.clone();
         let server_handle = tokio::spawn(async move {
-            let protowire_server = RpcServer::new(connection_handler)
-                .accept_compressed(CompressionEncoding::Gzip)
-                .send_compressed(CompressionEncoding::Gzip)
-                .max_decoding_message_size(RPC_MAX_MESSAGE_SIZE);
-
-            // TODO: check whether we should set tcp_keepalive
-            // const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(5);
-            // const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(120);
-            let serve_result = TonicServer::builder()
-                // .http2_keepalive_interval(Some(GRPC_KEEP_ALIVE_PING_INTERVAL))
-                // .http2_keepalive_timeout(Some(GRPC_KEEP_ALIVE_PING_TIMEOUT))
+            let protowire_server = ProtowireServer::new(connection_handler, &compression);
+
+            // Lets tools like grpcurl discover the protowire schema
+            let reflection_server = tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+                .build()
+                .expect("the protowire file descriptor set is valid");
+
+            let mut server = transport.apply(TonicServer::builder());
+            // With a client CA, the TLS handshake rejects any client not presenting a certificate issued by it
+            if let Some(tls) = tls {
+                server = server
+                    .tls_config(tls.server_config())
+                    .unwrap_or_else(|err| panic!("GRPC Server {serve_address} TLS configuration error: {err:?}"));
+            }
+            let router = server
                 .layer(measure_request_body_size_layer(bytes_rx, |b| b))
                 .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, bytes_tx.clone())))
                 .add_service(protowire_server)
-                .serve_with_shutdown(
-                    serve_address.into(),
-                    signal_receiver.map(|_| {
-                        debug!("GRPC, Server received the shutdown signal");
-                    }),
-                )
-                .await;
+                .add_service(reflection_server)
+                .add_service(health.server());
+            let shutdown_signal = signal_receiver.map(|_| {
+                debug!("GRPC, Server received the shutdown signal");
+            });
+            let serve_result = match serve_address {
+                GrpcEndpoint::Tcp(address) => router.serve_with_shutdown(address.into(), shutdown_signal).await,
+                #[cfg(unix)]
+                GrpcEndpoint::Unix(ref path) => {
+                    remove_stale_unix_socket(path);
+                    let listener = tokio::net::UnixListener::bind(path)
+                        .unwrap_or_else(|err| panic!("GRPC Server {serve_address} failed to bind: {err}"));
+                    let serve_result = router.serve_with_incoming_shutdown(UnixListenerStream::new(listener), shutdown_signal).await;
+                    remove_stale_unix_socket(path);
+                    serve_result
+                }
+                #[cfg(not(unix))]
+                GrpcEndpoint::Unix(_) => {
+                    panic!("GRPC Server {serve_address}: unix domain sockets are not supported on this platform")
+                }
+            };
 
             match serve_result {
                 Ok(_) => info!("GRPC Server stopped on: {}", serve_address),
@@ -166,7 +269,7 @@ impl ConnectionHandler {
             let _ = termination_receiver.await;
             signal_sender.send(()).expect("send signal");
             if (timeout(Duration::from_secs(1), server_handle).await).is_err() {
-                warn!("GRPC Server stopped forcefully on: {}", serve_address);
+                warn!("GRPC Server stopped forcefully on: {}", termination_address);
             }
         });
         termination_sender
@@ -192,6 +295,15 @@ impl ConnectionHandler {
         self.server_context.notifier.clone()
     }
 
+    /// Returns the delivery statistics of the notifications relayed to the connections
+    pub fn notification_stats(&self) -> Arc<NotificationStats> {
+        self.notifier().stats()
+    }
+
+    pub fn request_counters(&self) -> Arc<TowerRequestCounters> {
+        self.server_context.request_counters.clone()
+    }
+
     pub fn start(&self) {
         debug!("GRPC, Starting the connection handler");
 
@@ -200,6 +312,22 @@ impl ConnectionHandler {
 
         // Accept new incoming connections
         self.running.store(true, Ordering::SeqCst);
+
+        // Report the server as serving while the node is synced
+        let (health, running, core_service) = (self.health.clone(), self.running.clone(), self.server_context.core_service.clone());
+        tokio::spawn(async move {
+            while running.load(Ordering::SeqCst) {
+                let is_synced = core_service.get_sync_status().await.unwrap_or_default();
+                health.set_serving(is_synced && running.load(Ordering::SeqCst));
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+            health.set_serving(false);
+        });
+    }
+
+    /// Returns the health status reported by the grpc.health.v1 service
+    pub fn health(&self) -> GrpcHealth {
+        self.health.clone()
     }
 
     pub async fn stop(&self) -> RpcResult<()> {
@@ -207,6 +335,7 @@ impl ConnectionHandler {
 
         // Refuse new incoming connections
         self.running.store(false, Ordering::SeqCst);
+        self.health.set_serving(false);
 
         // Wait for the internal notifier to stop
         // Note that this requires the core service it is listening to have closed its listener
@@ -222,40 +351,44 @@ impl ConnectionHandler {
         Ok(())
     }
 
-    pub fn outgoing_route_channel_size() -> usize {
-        1024
-    }
-}
-
-impl Drop for ConnectionHandler {
-    fn drop(&mut self) {
-        debug!("GRPC, Dropping connection handler, refs {}", Arc::strong_count(&self.running));
-    }
-}
-
-#[tonic::async_trait]
-impl Rpc for ConnectionHandler {
-    type MessageStreamStream = Pin<Box<dyn Stream<Item = Result<KaspadResponse, tonic::Status>> + Send + Sync + 'static>>;
-
-    /// Handle the new arriving client connection
-    async fn message_stream(
+    /// Handles a new arriving client connection, registering it and returning the receiver of its outgoing route
+    pub(crate) async fn open_message_stream(
         &self,
         request: Request<tonic::Streaming<KaspadRequest>>,
-    ) -> Result<Response<Self::MessageStreamStream>, tonic::Status> {
+    ) -> Result<OutgoingReceiver, tonic::Status> {
         const SERVICE_IS_DOWN: &str = "The gRPC service is down";
 
         if !self.running.load(Ordering::SeqCst) {
             return Err(tonic::Status::new(tonic::Code::Unavailable, SERVICE_IS_DOWN));
         }
 
-        let remote_address = request.remote_addr().ok_or_else(|| {
-            tonic::Status::new(tonic::Code::InvalidArgument, "Incoming connection opening request has no remote address")
-        })?;
+        let remote_address = match request.remote_addr() {
+            Some(remote_address) => remote_address,
+            None if self.unix_socket => UNIX_SOCKET_PEER_ADDRESS,
+            None => {
+                return Err(tonic::Status::new(
+                    tonic::Code::InvalidArgument,
+                    "Incoming connection opening request has no remote address",
+                ))
+            }
+        };
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        // Resolve the client tenant
+        let tenant = match self.tenants {
+            Some(ref tenants) => {
+                let api_key = request.metadata().get(API_KEY_METADATA_KEY).and_then(|value| value.to_str().ok());
+                tenants.authenticate(api_key).map_err(|err| {
+                    debug!("GRPC, refusing incoming message stream from {:?} - {}", remote_address, err);
+                    tonic::Status::new(tonic::Code::Unauthenticated, err.to_string())
+                })?
+            }
+            None => None,
+        };
+
         // Build the in/out pipes
-        let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
+        let (outgoing_route, outgoing_receiver) = outgoing_channel(self.outgoing);
         let incoming_stream = request.into_inner();
 
         // Build the connection object
@@ -263,6 +396,7 @@ impl Rpc for ConnectionHandler {
             remote_address,
             self.server_context(),
             self.interface(),
+            tenant,
             self.manager_sender(),
             incoming_stream,
             outgoing_route,
@@ -275,10 +409,13 @@ impl Rpc for ConnectionHandler {
                 Ok(Ok(())) => {}
                 Ok(Err(err)) => {
                     warn!("GRPC, refusing incoming message stream from {:?} - {}", remote_address, err);
-                    return Err(tonic::Status::new(
-                        tonic::Code::ResourceExhausted,
-                        "The gRPC service has reached full capacity and accepts no new connection",
-                    ));
+                    let message = match err {
+                        RegistrationError::CapacityReached(_) => {
+                            "The gRPC service has reached full capacity and accepts no new connection"
+                        }
+                        RegistrationError::IpCapacityReached(..) => "The gRPC service accepts no new connection from this address",
+                    };
+                    return Err(tonic::Status::new(tonic::Code::ResourceExhausted, message));
                 }
                 Err(err) => {
                     debug!(
@@ -297,7 +434,102 @@ impl Rpc for ConnectionHandler {
             }
         }
 
-        // Give tonic a receiver stream (messages sent to it will be forwarded to the client)
-        Ok(Response::new(Box::pin(ReceiverStream::new(outgoing_receiver).map(Ok)) as Self::MessageStreamStream))
+        // The receiver is turned into the stream of the response (messages sent to it will be forwarded to the client)
+        Ok(outgoing_receiver)
+    }
+}
+
+impl Drop for ConnectionHandler {
+    fn drop(&mut self) {
+        debug!("GRPC, Dropping connection handler, refs {}", Arc::strong_count(&self.running));
+    }
+}
+
+#[tonic::async_trait]
+impl Rpc for ConnectionHandler {
+    type MessageStreamStream = Pin<Box<dyn Stream<Item = Result<KaspadResponse, tonic::Status>> + Send + Sync + 'static>>;
+    type ExportPruningPointUtxoSetStream =
+        Pin<Box<dyn Stream<Item = Result<ExportPruningPointUtxoSetChunkMessage, tonic::Status>> + Send + 'static>>;
+    type StreamBlocksStream = Pin<Box<dyn Stream<Item = Result<StreamBlocksBlockMessage, tonic::Status>> + Send + 'static>>;
+
+    /// Handle the new arriving client connection.
+    ///
+    /// Message streams are actually served by [`ProtowireServer`](crate::message_stream::ProtowireServer), which shares
+    /// the encoding of the notifications across the connections. This implementation yields the same messages.
+    async fn message_stream(
+        &self,
+        request: Request<tonic::Streaming<KaspadRequest>>,
+    ) -> Result<Response<Self::MessageStreamStream>, tonic::Status> {
+        let outgoing_receiver = self.open_message_stream(request).await?;
+        Ok(Response::new(
+            Box::pin(outgoing_receiver.into_stream().map(|message| Ok(message.into_response()))) as Self::MessageStreamStream
+        ))
+    }
+
+    /// Stream the UTXO set of the pruning point in chunks, outside of any message stream
+    async fn export_pruning_point_utxo_set(
+        &self,
+        request: Request<ExportPruningPointUtxoSetRequestMessage>,
+    ) -> Result<Response<Self::ExportPruningPointUtxoSetStream>, tonic::Status> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err(tonic::Status::new(tonic::Code::Unavailable, "The gRPC service is down"));
+        }
+
+        // The export pages through the UTXO set with GetPruningPointUtxos so it is subject to the same access rules
+        if let Some(ref tenants) = self.tenants {
+            let api_key = request.metadata().get(API_KEY_METADATA_KEY).and_then(|value| value.to_str().ok());
+            tenants.authenticate(api_key).map_err(|err| tonic::Status::new(tonic::Code::Unauthenticated, err.to_string()))?;
+        }
+        self.server_context
+            .access_policy
+            .check(&op_name(&RpcApiOps::GetPruningPointUtxos))
+            .map_err(|err| tonic::Status::new(tonic::Code::PermissionDenied, err.to_string()))?;
+
+        debug!("GRPC, Incoming pruning point UTXO set export from {:?}", request.remote_addr());
+        let stream = pruning_point_utxo_set_stream(self.server_context.core_service.clone(), request.into_inner().chunk_size);
+        Ok(Response::new(Box::pin(stream) as Self::ExportPruningPointUtxoSetStream))
+    }
+
+    /// Stream the blocks from a low hash one at a time, outside of any message stream
+    async fn stream_blocks(
+        &self,
+        request: Request<StreamBlocksRequestMessage>,
+    ) -> Result<Response<Self::StreamBlocksStream>, tonic::Status> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err(tonic::Status::new(tonic::Code::Unavailable, "The gRPC service is down"));
+        }
+
+        // The stream pages through the blocks with GetBlocks so it is subject to the same access rules
+        if let Some(ref tenants) = self.tenants {
+            let api_key = request.metadata().get(API_KEY_METADATA_KEY).and_then(|value| value.to_str().ok());
+            tenants.authenticate(api_key).map_err(|err| tonic::Status::new(tonic::Code::Unauthenticated, err.to_string()))?;
+        }
+        self.server_context
+            .access_policy
+            .check(&op_name(&RpcApiOps::GetBlocks))
+            .map_err(|err| tonic::Status::new(tonic::Code::PermissionDenied, err.to_string()))?;
+
+        debug!("GRPC, Incoming block stream from {:?}", request.remote_addr());
+        let message = request.into_inner();
+        let low_hash = (!message.low_hash.is_empty())
+            .then(|| RpcHash::from_str(&message.low_hash))
+            .transpose()
+            .map_err(|err| tonic::Status::new(tonic::Code::InvalidArgument, format!("invalid low hash: {err}")))?;
+        let verbosity = try_block_verbosity(message.verbosity, false)
+            .map_err(|err| tonic::Status::new(tonic::Code::InvalidArgument, err.to_string()))?;
+        let max_blocks = (message.max_blocks > 0).then_some(message.max_blocks);
+        let stream = block_stream(self.server_context.core_service.clone(), low_hash, verbosity, max_blocks);
+        Ok(Response::new(Box::pin(stream) as Self::StreamBlocksStream))
+    }
+}
+
+/// Removes the socket file left at `path` by a former server, leaving any other kind of file untouched
+#[cfg(unix)]
+fn remove_stale_unix_socket(path: &std::path::Path) {
+    use std::os::unix::fs::FileTypeExt;
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        if let Err(err) = std::fs::remove_file(path) {
+            warn!("GRPC, failed to remove the unix socket {}: {}", path.display(), err);
+        }
     }
 }
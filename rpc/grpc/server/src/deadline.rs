@@ -0,0 +1,78 @@
+//! Server-side deadlines of the gRPC requests.
+//!
+//! A request still running when its deadline expires is cancelled and answered with a deadline exceeded
+//! error, releasing the resources it holds, like a consensus session.
+
+use kaspa_grpc_core::ops::KaspadPayloadOps;
+use std::{collections::HashMap, time::Duration};
+use workflow_core::enums::Describe;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestDeadlines {
+    /// Deadline of the methods without a specific one, if any
+    default: Option<Duration>,
+
+    /// Method specific deadlines
+    methods: HashMap<KaspadPayloadOps, Duration>,
+}
+
+impl RequestDeadlines {
+    /// Creates deadlines applying `default` to every method, `None` for no deadline
+    pub fn new(default: Option<Duration>) -> Self {
+        Self { default, methods: Default::default() }
+    }
+
+    pub fn with_method(mut self, op: KaspadPayloadOps, deadline: Duration) -> Self {
+        self.methods.insert(op, deadline);
+        self
+    }
+
+    /// Builds the deadlines from a default in seconds, 0 for none, and method overrides
+    /// formatted as `METHOD=SECONDS`, as in `GetVirtualChainFromBlock=30`.
+    ///
+    /// Method names are case-insensitive and must match a [`KaspadPayloadOps`] variant.
+    pub fn parse(default_secs: u64, overrides: &[String]) -> Result<Self, String> {
+        let default = (default_secs > 0).then(|| Duration::from_secs(default_secs));
+        overrides.iter().try_fold(Self::new(default), |deadlines, item| {
+            let (name, secs) =
+                item.split_once('=').ok_or_else(|| format!("invalid method deadline {item}, expected METHOD=SECONDS"))?;
+            let op = KaspadPayloadOps::list()
+                .into_iter()
+                .find(|op| format!("{op:?}").eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| format!("unknown RPC method {}", name.trim()))?;
+            let secs = secs.trim().parse::<u64>().map_err(|err| format!("invalid deadline of method {op:?}: {err}"))?;
+            if secs == 0 {
+                return Err(format!("the deadline of method {op:?} must be positive"));
+            }
+            Ok(deadlines.with_method(op, Duration::from_secs(secs)))
+        })
+    }
+
+    /// Returns the deadline of a method, if any
+    pub fn get(&self, op: KaspadPayloadOps) -> Option<Duration> {
+        self.methods.get(&op).copied().or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_deadlines() {
+        let deadlines = RequestDeadlines::default();
+        assert_eq!(deadlines.get(KaspadPayloadOps::GetInfo), None);
+
+        let deadlines = RequestDeadlines::parse(10, &["getvirtualchainfromblock=30".to_string()]).unwrap();
+        assert_eq!(deadlines.get(KaspadPayloadOps::GetVirtualChainFromBlock), Some(Duration::from_secs(30)));
+        assert_eq!(deadlines.get(KaspadPayloadOps::GetInfo), Some(Duration::from_secs(10)));
+
+        let deadlines = RequestDeadlines::parse(0, &["GetBlocks=5".to_string()]).unwrap();
+        assert_eq!(deadlines.get(KaspadPayloadOps::GetBlocks), Some(Duration::from_secs(5)));
+        assert_eq!(deadlines.get(KaspadPayloadOps::GetInfo), None);
+
+        assert!(RequestDeadlines::parse(0, &["GetBlocks".to_string()]).is_err());
+        assert!(RequestDeadlines::parse(0, &["NoSuchMethod=5".to_string()]).is_err());
+        assert!(RequestDeadlines::parse(0, &["GetBlocks=0".to_string()]).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use kaspa_grpc_core::ops::KaspadPayloadOps;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::mpsc::error::TrySendError;
 
@@ -27,6 +28,9 @@ pub enum GrpcServerError {
 
     #[error("outgoing route capacity has been reached (client: {0})")]
     OutgoingRouteCapacityReached(String),
+
+    #[error("{0:?} request exceeded its deadline of {1:?}")]
+    DeadlineExceeded(KaspadPayloadOps, Duration),
 }
 
 impl From<GrpcServerError> for kaspa_rpc_core::error::RpcError {
@@ -0,0 +1,79 @@
+//! The standard gRPC health checking service (grpc.health.v1).
+//!
+//! The node reports SERVING only once the connection handler is started and the node is synced, so load balancers
+//! can route around nodes still syncing. Both the overall server health, queried with an empty service name, and
+//! the health of the protowire service are reported.
+
+use crate::connection_handler::ConnectionHandler;
+use futures::Stream;
+use kaspa_grpc_core::protowire::rpc_server::RpcServer;
+use std::{pin::Pin, sync::Arc, time::Duration};
+use tokio::sync::watch;
+use tokio_stream::{wrappers::WatchStream, StreamExt};
+use tonic::{server::NamedService, Request, Response, Status};
+use tonic_health::pb::{
+    health_check_response::ServingStatus,
+    health_server::{Health, HealthServer},
+    HealthCheckRequest, HealthCheckResponse,
+};
+
+/// Interval at which the sync state of the node is polled to refresh the health status
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
+pub struct GrpcHealth {
+    status: Arc<watch::Sender<ServingStatus>>,
+}
+
+impl GrpcHealth {
+    pub fn new() -> Self {
+        Self { status: Arc::new(watch::channel(ServingStatus::NotServing).0) }
+    }
+
+    pub fn set_serving(&self, serving: bool) {
+        let status = if serving { ServingStatus::Serving } else { ServingStatus::NotServing };
+        self.status.send_if_modified(|current| {
+            let modified = *current != status;
+            *current = status;
+            modified
+        });
+    }
+
+    pub fn is_serving(&self) -> bool {
+        *self.status.borrow() == ServingStatus::Serving
+    }
+
+    pub fn server(&self) -> HealthServer<Self> {
+        HealthServer::new(self.clone())
+    }
+
+    fn check_service(service: &str) -> Result<(), Status> {
+        match service.is_empty() || service == <RpcServer<ConnectionHandler> as NamedService>::NAME {
+            true => Ok(()),
+            false => Err(Status::not_found(format!("unknown service {service}"))),
+        }
+    }
+}
+
+impl Default for GrpcHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tonic::async_trait]
+impl Health for GrpcHealth {
+    async fn check(&self, request: Request<HealthCheckRequest>) -> Result<Response<HealthCheckResponse>, Status> {
+        Self::check_service(&request.get_ref().service)?;
+        let status = *self.status.borrow();
+        Ok(Response::new(HealthCheckResponse { status: status.into() }))
+    }
+
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<HealthCheckResponse, Status>> + Send + 'static>>;
+
+    async fn watch(&self, request: Request<HealthCheckRequest>) -> Result<Response<Self::WatchStream>, Status> {
+        Self::check_service(&request.get_ref().service)?;
+        let stream = WatchStream::new(self.status.subscribe()).map(|status| Ok(HealthCheckResponse { status: status.into() }));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -1,11 +1,25 @@
+pub mod access_log;
 pub mod adaptor;
+pub mod block_stream;
 pub mod collector;
+pub mod compression;
 pub mod connection;
 pub mod connection_handler;
+pub mod deadline;
 pub mod error;
+pub mod health;
+pub mod listen;
 pub mod manager;
+pub mod message_stream;
+pub mod outgoing;
+pub mod rate_limit;
 pub mod request_handler;
+pub mod request_limits;
 pub mod service;
+pub mod tenant;
+pub mod tls;
+pub mod transport;
+pub mod utxo_export;
 
 #[cfg(test)]
 pub mod tests;
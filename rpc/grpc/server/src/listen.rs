@@ -0,0 +1,146 @@
+//! Listening addresses of the gRPC server.
+//!
+//! The server can listen on several addresses at once, each one optionally overriding the access profile
+//! of the gRPC interface, like a loopback address serving all the methods next to a public one.
+//!
+//! Besides TCP, the server can listen on a unix domain socket, sparing co-located clients like wallet daemons
+//! and indexers the TCP overhead and any firewall configuration.
+
+use kaspa_rpc_service::access::RpcAccessProfile;
+use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// A gRPC listening address formatted as `IP[:PORT][/PROFILE]`, as in `0.0.0.0:16110/public`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcListenAddress {
+    pub address: ContextualNetAddress,
+    /// Access profile of the connections to this address, the one of the gRPC interface if `None`
+    pub profile: Option<RpcAccessProfile>,
+}
+
+impl GrpcListenAddress {
+    pub fn new(address: ContextualNetAddress, profile: Option<RpcAccessProfile>) -> Self {
+        Self { address, profile }
+    }
+
+    pub fn normalize(&self, default_port: u16) -> GrpcBinding {
+        GrpcBinding::new(self.address.normalize(default_port), self.profile)
+    }
+}
+
+impl FromStr for GrpcListenAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, profile) = match s.rsplit_once('/') {
+            Some((address, profile)) => (address, Some(profile.parse()?)),
+            None => (s, None),
+        };
+        let address = address.parse::<ContextualNetAddress>().map_err(|err| format!("invalid gRPC listening address {s}: {err}"))?;
+        Ok(Self::new(address, profile))
+    }
+}
+
+impl Display for GrpcListenAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.profile {
+            Some(profile) => write!(f, "{}/{}", self.address, profile),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+/// A resolved endpoint the gRPC server listens on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrpcEndpoint {
+    Tcp(NetAddress),
+    /// Path of a unix domain socket, only supported on unix platforms
+    Unix(PathBuf),
+}
+
+impl GrpcEndpoint {
+    pub fn net_address(&self) -> Option<NetAddress> {
+        match self {
+            GrpcEndpoint::Tcp(address) => Some(*address),
+            GrpcEndpoint::Unix(_) => None,
+        }
+    }
+
+    pub fn is_unix(&self) -> bool {
+        matches!(self, GrpcEndpoint::Unix(_))
+    }
+}
+
+impl From<NetAddress> for GrpcEndpoint {
+    fn from(address: NetAddress) -> Self {
+        GrpcEndpoint::Tcp(address)
+    }
+}
+
+impl Display for GrpcEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrpcEndpoint::Tcp(address) => write!(f, "{}", address),
+            GrpcEndpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// A resolved gRPC listening endpoint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcBinding {
+    pub endpoint: GrpcEndpoint,
+    pub profile: Option<RpcAccessProfile>,
+}
+
+impl GrpcBinding {
+    pub fn new(endpoint: impl Into<GrpcEndpoint>, profile: Option<RpcAccessProfile>) -> Self {
+        Self { endpoint: endpoint.into(), profile }
+    }
+
+    /// Creates a binding to the unix domain socket at `path`
+    pub fn unix(path: PathBuf, profile: Option<RpcAccessProfile>) -> Self {
+        Self::new(GrpcEndpoint::Unix(path), profile)
+    }
+}
+
+impl Display for GrpcBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.profile {
+            Some(profile) => write!(f, "{} ({})", self.endpoint, profile),
+            None => write!(f, "{}", self.endpoint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_listen_address() {
+        let listen = "127.0.0.1".parse::<GrpcListenAddress>().unwrap();
+        assert_eq!(listen.profile, None);
+        assert_eq!(listen.normalize(16110).endpoint.to_string(), "127.0.0.1:16110");
+
+        let listen = "0.0.0.0:17110/public".parse::<GrpcListenAddress>().unwrap();
+        assert_eq!(listen.profile, Some(RpcAccessProfile::Public));
+        assert_eq!(listen.normalize(16110).endpoint.to_string(), "0.0.0.0:17110");
+        assert_eq!(listen.to_string(), "0.0.0.0:17110/public");
+
+        let listen = "[::1]:16110/private".parse::<GrpcListenAddress>().unwrap();
+        assert_eq!(listen.profile, Some(RpcAccessProfile::Private));
+
+        assert!("0.0.0.0/admin".parse::<GrpcListenAddress>().is_err());
+        assert!("localhost:port".parse::<GrpcListenAddress>().is_err());
+
+        let binding = GrpcBinding::unix("/run/kaspad/rpc.sock".into(), Some(RpcAccessProfile::Private));
+        assert!(binding.endpoint.is_unix());
+        assert_eq!(binding.endpoint.net_address(), None);
+        assert_eq!(binding.to_string(), "unix:/run/kaspad/rpc.sock (private)");
+    }
+}
@@ -4,6 +4,7 @@ use kaspa_notify::connection::Connection as ConnectionT;
 use parking_lot::RwLock;
 use std::{
     collections::{hash_map::Entry::Occupied, HashMap, HashSet},
+    net::IpAddr,
     sync::Arc,
     time::Duration,
 };
@@ -15,6 +16,9 @@ use tokio::{sync::mpsc::Receiver as MpscReceiver, time::sleep};
 pub(crate) enum RegistrationError {
     #[error("reached connection capacity of {0}")]
     CapacityReached(usize),
+
+    #[error("reached connection capacity of {1} for address {0}")]
+    IpCapacityReached(IpAddr, usize),
 }
 pub(crate) type RegistrationResult = Result<(), RegistrationError>;
 
@@ -34,15 +38,36 @@ pub(crate) enum ManagerEvent {
     ConnectionClosing(Connection),
 }
 
+/// Per remote IP connection cap, preventing a single client from holding every available slot
+#[derive(Clone, Debug, Default)]
+pub struct IpConnectionLimit {
+    /// Maximum number of connections per remote IP, 0 for no limit
+    max_connections: usize,
+
+    /// Remote IPs exempted from the per IP cap (they remain subject to the global capacity)
+    trusted: HashSet<IpAddr>,
+}
+
+impl IpConnectionLimit {
+    pub fn new(max_connections: usize, trusted: impl IntoIterator<Item = IpAddr>) -> Self {
+        Self { max_connections, trusted: trusted.into_iter().collect() }
+    }
+
+    fn applies_to(&self, ip: &IpAddr) -> bool {
+        self.max_connections > 0 && !self.trusted.contains(ip)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Manager {
     connections: Arc<RwLock<HashMap<ConnectionId, Connection>>>,
     max_connections: usize,
+    ip_limit: Arc<IpConnectionLimit>,
 }
 
 impl Manager {
-    pub fn new(max_connections: usize) -> Self {
-        Self { connections: Default::default(), max_connections }
+    pub fn new(max_connections: usize, ip_limit: IpConnectionLimit) -> Self {
+        Self { connections: Default::default(), max_connections, ip_limit: Arc::new(ip_limit) }
     }
 
     /// Starts a loop for receiving central manager events from all connections. This mechanism is used for
@@ -78,6 +103,14 @@ impl Manager {
             return Err(RegistrationError::CapacityReached(self.max_connections));
         }
 
+        // Check if the remote IP has room for a new connection
+        let ip = connection.net_address().ip();
+        if self.ip_limit.applies_to(&ip)
+            && connections_write.values().filter(|x| x.net_address().ip() == ip).count() >= self.ip_limit.max_connections
+        {
+            return Err(RegistrationError::IpCapacityReached(ip, self.ip_limit.max_connections));
+        }
+
         debug!("GRPC, Registering a new connection from {connection}");
         let previous_connection = connections_write.insert(connection.identity(), connection.clone());
         info!("GRPC, new incoming connection {} #{}", connection, connections_write.len());
@@ -147,3 +180,22 @@ impl Drop for Manager {
         debug!("GRPC, Dropping Manager, refs count {}", Arc::strong_count(&self.connections));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_connection_limit() {
+        let local: IpAddr = "127.0.0.1".parse().unwrap();
+        let remote: IpAddr = "10.0.0.1".parse().unwrap();
+
+        let disabled = IpConnectionLimit::default();
+        assert!(!disabled.applies_to(&local));
+        assert!(!disabled.applies_to(&remote));
+
+        let limit = IpConnectionLimit::new(2, [local]);
+        assert!(!limit.applies_to(&local), "trusted addresses are exempted");
+        assert!(limit.applies_to(&remote));
+    }
+}
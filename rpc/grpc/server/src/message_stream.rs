@@ -0,0 +1,163 @@
+//! The protowire RPC service as served to the clients.
+//!
+//! The message streams get served with an [`OutgoingCodec`] writing the queued [`OutgoingMessage`]s as they are, so
+//! a notification broadcast to many connections is encoded once and its encoding shared by all of them. The other
+//! methods of the service are delegated to the generated [`RpcServer`].
+
+use crate::{compression::GrpcCompressionConfig, connection_handler::ConnectionHandler, outgoing::OutgoingMessage};
+use futures::{Stream, StreamExt};
+use kaspa_grpc_core::{
+    protowire::{rpc_server::RpcServer, KaspadRequest},
+    RPC_MAX_MESSAGE_SIZE,
+};
+use prost::{
+    bytes::{Buf, Bytes},
+    encoding::{decode_key, skip_field, DecodeContext},
+    Message,
+};
+use std::{convert::Infallible, pin::Pin};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError},
+    server::{Grpc, NamedService, StreamingService},
+    Code, Request, Response, Status, Streaming,
+};
+
+const MESSAGE_STREAM_PATH: &str = "/protowire.RPC/MessageStream";
+
+/// The codec of the message streams, decoding the requests of the client and writing the outgoing messages
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OutgoingCodec;
+
+impl Codec for OutgoingCodec {
+    type Encode = OutgoingMessage;
+    type Decode = KaspadRequest;
+    type Encoder = OutgoingEncoder;
+    type Decoder = RequestDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        OutgoingEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        RequestDecoder
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OutgoingEncoder;
+
+impl Encoder for OutgoingEncoder {
+    type Item = OutgoingMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, buf: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.write_to(buf);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestDecoder;
+
+impl Decoder for RequestDecoder {
+    type Item = KaspadRequest;
+    type Error = Status;
+
+    fn decode(&mut self, buf: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        decode_request(buf.copy_to_bytes(buf.remaining())).map(Some)
+    }
+}
+
+/// Tag of the `id` field of a [`KaspadRequest`]
+const REQUEST_ID_TAG: u32 = 101;
+
+/// Decodes a request, rejecting the ones missing a payload.
+///
+/// A payload unknown to this version of the protocol, most probably sent by a newer client, gets decoded as a missing
+/// payload too, but is kept apart by the presence of a field other than the id. Such a request is accepted and its
+/// payload left empty, so the router can answer it as unsupported.
+pub(crate) fn decode_request(bytes: Bytes) -> Result<KaspadRequest, Status> {
+    let request = KaspadRequest::decode(bytes.clone()).map_err(|err| Status::new(Code::Internal, err.to_string()))?;
+    if request.payload.is_none() && !has_unknown_field(bytes).map_err(|err| Status::new(Code::Internal, err.to_string()))? {
+        return Err(Status::new(Code::InvalidArgument, "the request has no payload"));
+    }
+    Ok(request)
+}
+
+/// Tells if the encoded request has a field other than the id, its payload being unknown if it was decoded as missing
+fn has_unknown_field(mut bytes: Bytes) -> Result<bool, prost::DecodeError> {
+    while bytes.has_remaining() {
+        let (tag, wire_type) = decode_key(&mut bytes)?;
+        if tag != REQUEST_ID_TAG {
+            return Ok(true);
+        }
+        skip_field(wire_type, tag, &mut bytes, DecodeContext::default())?;
+    }
+    Ok(false)
+}
+
+struct MessageStreamService(ConnectionHandler);
+
+impl StreamingService<KaspadRequest> for MessageStreamService {
+    type Response = OutgoingMessage;
+    type ResponseStream = Pin<Box<dyn Stream<Item = Result<OutgoingMessage, Status>> + Send + 'static>>;
+    type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+    fn call(&mut self, request: Request<Streaming<KaspadRequest>>) -> Self::Future {
+        let connection_handler = self.0.clone();
+        Box::pin(async move {
+            let outgoing_receiver = connection_handler.open_message_stream(request).await?;
+            Ok(Response::new(Box::pin(outgoing_receiver.into_stream().map(Ok)) as Self::ResponseStream))
+        })
+    }
+}
+
+/// The protowire RPC service, serving the message streams with an [`OutgoingCodec`]
+#[derive(Clone)]
+pub(crate) struct ProtowireServer {
+    connection_handler: ConnectionHandler,
+    inner: RpcServer<ConnectionHandler>,
+    compression_encodings: Vec<CompressionEncoding>,
+}
+
+impl ProtowireServer {
+    pub(crate) fn new(connection_handler: ConnectionHandler, compression: &GrpcCompressionConfig) -> Self {
+        let mut inner = RpcServer::new(connection_handler.clone()).max_decoding_message_size(RPC_MAX_MESSAGE_SIZE);
+        for &encoding in compression.encodings() {
+            inner = inner.accept_compressed(encoding).send_compressed(encoding);
+        }
+        Self { connection_handler, inner, compression_encodings: compression.encodings().to_vec() }
+    }
+}
+
+impl<B> Service<http::Request<B>> for ProtowireServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        if request.uri().path() != MESSAGE_STREAM_PATH {
+            return self.inner.call(request);
+        }
+        let mut grpc = Grpc::new(OutgoingCodec).max_decoding_message_size(RPC_MAX_MESSAGE_SIZE);
+        for &encoding in self.compression_encodings.iter() {
+            grpc = grpc.accept_compressed(encoding).send_compressed(encoding);
+        }
+        let service = MessageStreamService(self.connection_handler.clone());
+        Box::pin(async move { Ok(grpc.streaming(service, request).await) })
+    }
+}
+
+impl NamedService for ProtowireServer {
+    const NAME: &'static str = <RpcServer<ConnectionHandler> as NamedService>::NAME;
+}
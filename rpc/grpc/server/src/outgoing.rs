@@ -0,0 +1,324 @@
+//! The outgoing route of a gRPC connection, queuing the responses and notifications sent to the client.
+//!
+//! A client reading its stream slower than the node produces messages fills the queue of its connection. What
+//! happens then is ruled by the [`OutgoingOverflowPolicy`] of the connection, which either disconnects the client
+//! or drops its oldest queued notification, responses to requests never being dropped.
+
+use futures::Stream;
+use kaspa_grpc_core::protowire::KaspadResponse;
+use parking_lot::Mutex;
+use prost::{
+    bytes::{BufMut, Bytes},
+    Message,
+};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::Notify;
+
+/// Default number of messages the outgoing route of a connection can hold
+pub const DEFAULT_OUTGOING_ROUTE_CAPACITY: usize = 1024;
+
+/// What to do when a message is sent to a full outgoing route
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutgoingOverflowPolicy {
+    /// Disconnect the client
+    #[default]
+    Disconnect,
+    /// Drop the oldest queued notification to make room for the message, disconnecting the client if the route
+    /// holds responses only
+    DropOldestNotification,
+}
+
+impl OutgoingOverflowPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutgoingOverflowPolicy::Disconnect => "disconnect",
+            OutgoingOverflowPolicy::DropOldestNotification => "drop-oldest",
+        }
+    }
+}
+
+impl Display for OutgoingOverflowPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutgoingOverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disconnect" => Ok(OutgoingOverflowPolicy::Disconnect),
+            "drop-oldest" => Ok(OutgoingOverflowPolicy::DropOldestNotification),
+            _ => Err(format!("unknown outgoing overflow policy {s}, expected one of disconnect, drop-oldest")),
+        }
+    }
+}
+
+/// The outgoing route settings of the gRPC server connections
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutgoingRouteConfig {
+    /// Number of messages the outgoing route of a connection can hold
+    pub capacity: usize,
+
+    pub overflow_policy: OutgoingOverflowPolicy,
+}
+
+impl OutgoingRouteConfig {
+    /// Builds a config holding at least one message
+    pub fn new(capacity: usize, overflow_policy: OutgoingOverflowPolicy) -> Self {
+        Self { capacity: capacity.max(1), overflow_policy }
+    }
+}
+
+impl Default for OutgoingRouteConfig {
+    fn default() -> Self {
+        Self { capacity: DEFAULT_OUTGOING_ROUTE_CAPACITY, overflow_policy: Default::default() }
+    }
+}
+
+/// A message queued in an outgoing route
+#[derive(Clone, Debug)]
+pub enum OutgoingMessage {
+    /// A response to a request of the client, encoded by the stream of the connection
+    Response(KaspadResponse),
+    /// A notification encoded once and shared by all the connections it is broadcast to. Cloning it only
+    /// increments the reference count of the encoding, which the stream of every connection writes as is.
+    Encoded(Bytes),
+}
+
+impl OutgoingMessage {
+    /// Encodes a notification, so it gets shared by the connections it is broadcast to
+    pub fn encode(notification: &KaspadResponse) -> Self {
+        OutgoingMessage::Encoded(notification.encode_to_vec().into())
+    }
+
+    pub fn is_notification(&self) -> bool {
+        match self {
+            OutgoingMessage::Response(response) => response.is_notification(),
+            OutgoingMessage::Encoded(_) => true,
+        }
+    }
+
+    /// Writes the protowire encoding of the message to `buf`
+    pub fn write_to(&self, buf: &mut impl BufMut) {
+        match self {
+            OutgoingMessage::Response(response) => response.encode(buf).expect("Message only errors if not enough space"),
+            OutgoingMessage::Encoded(encoding) => buf.put_slice(encoding),
+        }
+    }
+
+    /// Returns the message as a response, decoding it if it is encoded
+    pub fn into_response(self) -> KaspadResponse {
+        match self {
+            OutgoingMessage::Response(response) => response,
+            OutgoingMessage::Encoded(encoding) => KaspadResponse::decode(encoding).expect("the message was encoded from a response"),
+        }
+    }
+}
+
+impl From<KaspadResponse> for OutgoingMessage {
+    fn from(response: KaspadResponse) -> Self {
+        OutgoingMessage::Response(response)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OutgoingSendError {
+    /// The receiving stream was dropped
+    Closed,
+    /// The route is full and the overflow policy requires the client to be disconnected
+    Full,
+}
+
+#[derive(Debug)]
+struct Shared {
+    /// The queue keeps the buffer it grew into, so a warmed up route enqueues messages without allocating
+    queue: Mutex<VecDeque<OutgoingMessage>>,
+    /// Signals the receiver that a message was queued or the sender was dropped
+    notify: Notify,
+    sender_dropped: AtomicBool,
+    receiver_dropped: AtomicBool,
+}
+
+/// Creates an outgoing route as a sender and a receiver, the latter being turned into the stream of the connection
+pub(crate) fn outgoing_channel(config: OutgoingRouteConfig) -> (OutgoingSender, OutgoingReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(VecDeque::new()),
+        notify: Notify::new(),
+        sender_dropped: AtomicBool::new(false),
+        receiver_dropped: AtomicBool::new(false),
+    });
+    (OutgoingSender { shared: shared.clone(), config }, OutgoingReceiver { shared })
+}
+
+/// The sending half of an outgoing route. The stream of the receiving half ends when it is dropped.
+#[derive(Debug)]
+pub struct OutgoingSender {
+    shared: Arc<Shared>,
+    config: OutgoingRouteConfig,
+}
+
+impl OutgoingSender {
+    /// Queues `message` without waiting, applying the overflow policy if the route is full.
+    ///
+    /// Returns the number of notifications dropped to make room for `message`.
+    pub fn try_send(&self, message: impl Into<OutgoingMessage>) -> Result<usize, OutgoingSendError> {
+        if self.shared.receiver_dropped.load(Ordering::SeqCst) {
+            return Err(OutgoingSendError::Closed);
+        }
+        let mut dropped = 0;
+        {
+            let mut queue = self.shared.queue.lock();
+            if queue.len() >= self.config.capacity {
+                match self.config.overflow_policy {
+                    OutgoingOverflowPolicy::Disconnect => return Err(OutgoingSendError::Full),
+                    OutgoingOverflowPolicy::DropOldestNotification => {
+                        match queue.iter().position(|message| message.is_notification()) {
+                            Some(index) => {
+                                queue.remove(index);
+                                dropped += 1;
+                            }
+                            None => return Err(OutgoingSendError::Full),
+                        }
+                    }
+                }
+            }
+            queue.push_back(message.into());
+        }
+        self.shared.notify.notify_one();
+        Ok(dropped)
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.queue.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for OutgoingSender {
+    fn drop(&mut self) {
+        self.shared.sender_dropped.store(true, Ordering::SeqCst);
+        self.shared.notify.notify_one();
+    }
+}
+
+/// The receiving half of an outgoing route
+#[derive(Debug)]
+pub struct OutgoingReceiver {
+    shared: Arc<Shared>,
+}
+
+impl OutgoingReceiver {
+    /// Receives the next queued message, or `None` once the queue is drained and the sender dropped
+    pub async fn recv(&self) -> Option<OutgoingMessage> {
+        loop {
+            if let Some(message) = self.shared.queue.lock().pop_front() {
+                return Some(message);
+            }
+            if self.shared.sender_dropped.load(Ordering::SeqCst) {
+                return None;
+            }
+            // A permit is stored if the sender notified since the last wait, so no message can be missed
+            self.shared.notify.notified().await;
+        }
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = OutgoingMessage> + Send + Sync + 'static {
+        futures::stream::unfold(self, |receiver| async move { receiver.recv().await.map(|message| (message, receiver)) })
+    }
+}
+
+impl Drop for OutgoingReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_dropped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use kaspa_grpc_core::protowire::{kaspad_response::Payload, BlockAddedNotificationMessage, PingResponseMessage};
+
+    fn response(id: u64) -> KaspadResponse {
+        KaspadResponse { id, payload: Some(Payload::PingResponse(PingResponseMessage { error: None })) }
+    }
+
+    fn notification() -> KaspadResponse {
+        KaspadResponse { id: 0, payload: Some(Payload::BlockAddedNotification(BlockAddedNotificationMessage::default())) }
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_route_disconnect() {
+        let (sender, receiver) = outgoing_channel(OutgoingRouteConfig::new(2, OutgoingOverflowPolicy::Disconnect));
+        assert_eq!(sender.try_send(notification()), Ok(0));
+        assert_eq!(sender.try_send(response(1)), Ok(0));
+        assert_eq!(sender.try_send(response(2)), Err(OutgoingSendError::Full));
+
+        assert!(receiver.recv().await.unwrap().is_notification());
+        assert_eq!(sender.try_send(response(2)), Ok(0));
+        drop(sender);
+        let ids: Vec<u64> = receiver.into_stream().map(|message| message.into_response().id).collect().await;
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_route_drop_oldest_notification() {
+        let (sender, receiver) = outgoing_channel(OutgoingRouteConfig::new(3, OutgoingOverflowPolicy::DropOldestNotification));
+        assert_eq!(sender.try_send(response(1)), Ok(0));
+        assert_eq!(sender.try_send(notification()), Ok(0));
+        assert_eq!(sender.try_send(response(2)), Ok(0));
+
+        // The notification makes room for the new message, keeping the responses in order
+        assert_eq!(sender.try_send(response(3)), Ok(1));
+        assert_eq!(sender.len(), 3);
+        // With responses only left, the client gets disconnected
+        assert_eq!(sender.try_send(notification()), Err(OutgoingSendError::Full));
+
+        assert_eq!(receiver.recv().await.unwrap().into_response().id, 1);
+        drop(receiver);
+        assert_eq!(sender.try_send(response(4)), Err(OutgoingSendError::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_route_shared_notification() {
+        let config = OutgoingRouteConfig::new(4, OutgoingOverflowPolicy::DropOldestNotification);
+        let (sender1, receiver1) = outgoing_channel(config);
+        let (sender2, receiver2) = outgoing_channel(config);
+        let message = OutgoingMessage::encode(&notification());
+        let OutgoingMessage::Encoded(ref encoding) = message else { panic!("a notification is encoded") };
+        let encoding_ptr = encoding.as_ptr();
+        sender1.try_send(message.clone()).unwrap();
+        sender2.try_send(message).unwrap();
+
+        // Both streams yield the very same encoding, which decodes to the notification
+        for receiver in [receiver1, receiver2] {
+            let message = receiver.recv().await.unwrap();
+            assert!(matches!(message, OutgoingMessage::Encoded(ref encoding) if encoding.as_ptr() == encoding_ptr));
+            let mut buf = Vec::new();
+            message.write_to(&mut buf);
+            assert_eq!(buf, notification().encode_to_vec());
+            assert_eq!(message.into_response(), notification());
+        }
+    }
+
+    #[test]
+    fn test_outgoing_overflow_policy_from_str() {
+        for policy in [OutgoingOverflowPolicy::Disconnect, OutgoingOverflowPolicy::DropOldestNotification] {
+            assert_eq!(policy.to_string().parse::<OutgoingOverflowPolicy>(), Ok(policy));
+        }
+        assert!("drop-newest".parse::<OutgoingOverflowPolicy>().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Request rate limiting of the gRPC clients, keyed by remote address.
+//!
+//! Every remote IP address owns a token bucket shared by all its connections. The bucket refills at
+//! a constant rate up to its burst capacity and every request consumes the weight of its method, so
+//! heavy calls exhaust the bucket faster than lightweight ones.
+
+use kaspa_grpc_core::ops::KaspadPayloadOps;
+use parking_lot::Mutex;
+use std::{collections::HashMap, net::IpAddr, time::Instant};
+use thiserror::Error;
+
+/// Number of tracked addresses above which the idle buckets are dropped
+const MAX_TRACKED_ADDRESSES: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Request weight units refilled per second
+    pub requests_per_second: u32,
+
+    /// Maximum request weight units a client can spend at once
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    pub fn new(requests_per_second: u32, burst: Option<u32>) -> Self {
+        assert!(requests_per_second > 0);
+        // The burst must at least allow the heaviest method
+        let burst = burst.unwrap_or(requests_per_second).max(MAX_METHOD_WEIGHT);
+        Self { requests_per_second, burst }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("client {0} exceeded its rate limit of {1} requests per second")]
+pub struct RateLimitError(pub IpAddr, pub u32);
+
+const MAX_METHOD_WEIGHT: u32 = 10;
+
+/// Cost of a request in weight units, proportional to the load it puts on the node
+pub fn method_weight(op: KaspadPayloadOps) -> u32 {
+    match op {
+        KaspadPayloadOps::GetBlocks
+        | KaspadPayloadOps::GetHeaders
+        | KaspadPayloadOps::GetVirtualChainFromBlock
+        | KaspadPayloadOps::GetUtxosByAddresses
+        | KaspadPayloadOps::GetPruningPointUtxos
+        | KaspadPayloadOps::SampleChainBlocks => MAX_METHOD_WEIGHT,
+        KaspadPayloadOps::GetMempoolEntries
+        | KaspadPayloadOps::GetMempoolEntriesByAddresses
+        | KaspadPayloadOps::GetBalancesByAddresses
+        | KaspadPayloadOps::EstimateNetworkHashesPerSecond
+        | KaspadPayloadOps::GetDagStats
+        | KaspadPayloadOps::GetChainBlockByTimestamp
+        | KaspadPayloadOps::SubmitTransactions => 5,
+        _ => 1,
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: Default::default() }
+    }
+
+    /// Consumes the weight of `op` from the bucket of `address`, failing if the bucket holds too few tokens
+    pub fn check(&self, address: IpAddr, op: KaspadPayloadOps) -> Result<(), RateLimitError> {
+        self.check_at(address, op, Instant::now())
+    }
+
+    fn check_at(&self, address: IpAddr, op: KaspadPayloadOps, now: Instant) -> Result<(), RateLimitError> {
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_ADDRESSES && !buckets.contains_key(&address) {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.config.burst as f64);
+        }
+        let bucket = buckets.entry(address).or_insert(TokenBucket { tokens: self.config.burst as f64, updated: now });
+        let tokens = self.refill(bucket, now);
+        let weight = method_weight(op) as f64;
+        if tokens < weight {
+            return Err(RateLimitError(address, self.config.requests_per_second));
+        }
+        bucket.tokens = tokens - weight;
+        Ok(())
+    }
+
+    /// Refills `bucket` according to the time elapsed since its last update and returns its tokens
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.config.requests_per_second as f64).min(self.config.burst as f64);
+        bucket.updated = now;
+        bucket.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(10, Some(20)));
+        let (a, b) = ("10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap());
+        let start = Instant::now();
+
+        // The burst allows two heavy calls, after which even a lightweight one is rejected
+        assert!(limiter.check_at(a, KaspadPayloadOps::GetBlocks, start).is_ok());
+        assert!(limiter.check_at(a, KaspadPayloadOps::GetBlocks, start).is_ok());
+        assert_eq!(limiter.check_at(a, KaspadPayloadOps::GetInfo, start), Err(RateLimitError(a, 10)));
+
+        // Other addresses own their bucket
+        assert!(limiter.check_at(b, KaspadPayloadOps::GetBlocks, start).is_ok());
+
+        // A refill of half a second allows 5 weight units
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check_at(a, KaspadPayloadOps::GetBalancesByAddresses, later).is_ok());
+        assert!(limiter.check_at(a, KaspadPayloadOps::GetInfo, later).is_err());
+
+        // A full refill is capped by the burst
+        let much_later = later + Duration::from_secs(60);
+        for _ in 0..20 {
+            assert!(limiter.check_at(a, KaspadPayloadOps::GetInfo, much_later).is_ok());
+        }
+        assert!(limiter.check_at(a, KaspadPayloadOps::GetInfo, much_later).is_err());
+    }
+}
@@ -14,8 +14,9 @@ use crate::{
 use kaspa_grpc_core::protowire::{kaspad_request::Payload, *};
 use kaspa_grpc_core::{ops::KaspadPayloadOps, protowire::NotifyFinalityConflictResponseMessage};
 use kaspa_notify::{scope::FinalityConflictResolvedScope, subscriber::SubscriptionManager};
-use kaspa_rpc_core::{SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+use kaspa_rpc_core::{GetSubscriptionsResponse, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
 use kaspa_rpc_macros::build_grpc_server_interface;
+use kaspa_rpc_service::access::RpcInterface;
 
 pub struct Factory {}
 
@@ -55,6 +56,7 @@ impl Factory {
                 GetConnectedPeerInfo,
                 AddPeer,
                 SubmitTransaction,
+                SubmitTransactions,
                 GetSubnetwork,
                 GetVirtualChainFromBlock,
                 GetBlockCount,
@@ -75,6 +77,33 @@ impl Factory {
                 GetServerInfo,
                 GetSyncStatus,
                 GetDaaScoreTimestampEstimate,
+                GetTransactionMass,
+                BuildUnsignedTransaction,
+                GetDagStats,
+                GetHealth,
+                GetFeeEstimate,
+                GetSubscriptions,
+                GetVirtualParents,
+                GetConsensusParams,
+                GetTransactionById,
+                SampleChainBlocks,
+                GetConnections,
+                GetBlockByBlueScore,
+                GetBlockByDaaScore,
+                GetChainBlockByTimestamp,
+                GetPruningPointUtxos,
+                GetMempoolInfo,
+                SetMempoolPolicy,
+                GetCurrentBlockColor,
+                GetBans,
+                GetBlockProcessingStatus,
+                StartJob,
+                GetJobStatus,
+                GetNotificationStats,
+                GenerateBlocks,
+                GetServerCapabilities,
+                UpdateBlockTemplateExclusions,
+                GetChainQuality,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -83,6 +112,10 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyHealthAlert,
+                NotifyMempoolTransactionsEvicted,
+                NotifyJobCompleted,
+                NotifyIndexResyncProgress,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -129,6 +162,101 @@ impl Factory {
         });
         interface.replace_method(KaspadPayloadOps::NotifyFinalityConflict, method);
 
+        // Manually reimplementing the GetSubscriptionsRequest method since the subscriptions
+        // are those of the connection listener.
+        let method: KaspadMethod = Method::new(|server_ctx: ServerContext, connection: Connection, request: KaspadRequest| {
+            Box::pin(async move {
+                let mut response: KaspadResponse = match request.payload {
+                    Some(Payload::GetSubscriptionsRequest(ref request)) => {
+                        match kaspa_rpc_core::GetSubscriptionsRequest::try_from(request) {
+                            Ok(_) => {
+                                let subscriptions = connection
+                                    .listener_id()
+                                    .map(|listener_id| server_ctx.notifier.listener_subscriptions(listener_id))
+                                    .unwrap_or_default();
+                                let response = GetSubscriptionsResponse::new(subscriptions.into_iter().map(Into::into).collect());
+                                GetSubscriptionsResponseMessage::from(Ok(&response)).into()
+                            }
+                            Err(err) => GetSubscriptionsResponseMessage::from(err).into(),
+                        }
+                    }
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(KaspadPayloadOps::GetSubscriptions, method);
+
+        // Manually reimplementing the SubmitTransactionRequest and SubmitTransactionsRequest methods so the submitted
+        // transactions get attributed to the connection, which is subject to a quota of high-priority transactions.
+        let method: KaspadMethod = Method::new(|server_ctx: ServerContext, connection: Connection, request: KaspadRequest| {
+            Box::pin(async move {
+                let mut response: KaspadResponse = match request.payload {
+                    Some(Payload::SubmitTransactionRequest(ref request)) => match request.try_into() {
+                        Ok(request) => {
+                            server_ctx.core_service.submit_transaction_from_call(submitter(&connection), request).await.into()
+                        }
+                        Err(err) => SubmitTransactionResponseMessage::from(err).into(),
+                    },
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(KaspadPayloadOps::SubmitTransaction, method);
+
+        let method: KaspadMethod = Method::new(|server_ctx: ServerContext, connection: Connection, request: KaspadRequest| {
+            Box::pin(async move {
+                let mut response: KaspadResponse = match request.payload {
+                    Some(Payload::SubmitTransactionsRequest(ref request)) => match request.try_into() {
+                        Ok(request) => {
+                            server_ctx.core_service.submit_transactions_from_call(submitter(&connection), request).await.into()
+                        }
+                        Err(err) => SubmitTransactionsResponseMessage::from(err).into(),
+                    },
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(KaspadPayloadOps::SubmitTransactions, method);
+
+        // Manually reimplementing the GetServerCapabilitiesRequest method so the reported methods are restricted
+        // to those served on the listening address of the connection.
+        let method: KaspadMethod = Method::new(|server_ctx: ServerContext, _: Connection, request: KaspadRequest| {
+            Box::pin(async move {
+                let mut response: KaspadResponse = match request.payload {
+                    Some(Payload::GetServerCapabilitiesRequest(ref request)) => match request.try_into() {
+                        Ok(request) => server_ctx
+                            .core_service
+                            .get_server_capabilities_call(request)
+                            .await
+                            .map(|mut response| {
+                                response.methods.retain(|method| server_ctx.access_policy.is_allowed(method));
+                                response
+                            })
+                            .into(),
+                        Err(err) => GetServerCapabilitiesResponseMessage::from(err).into(),
+                    },
+                    _ => {
+                        return Err(GrpcServerError::InvalidRequestPayload);
+                    }
+                };
+                response.id = request.id;
+                Ok(response)
+            })
+        });
+        interface.replace_method(KaspadPayloadOps::GetServerCapabilities, method);
+
         // Methods with special properties
         let network_bps = network_bps as usize;
         interface.set_method_properties(
@@ -136,10 +264,15 @@ impl Factory {
             network_bps,
             10.max(network_bps * 2),
             KaspadRoutingPolicy::DropIfFull(Arc::new(Box::new(|_: &KaspadRequest| {
-                Ok(Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull) }).into())
+                Ok(Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::RouteIsFull))).into())
             }))),
         );
 
         interface
     }
 }
+
+/// Returns the label attributing submitted transactions to `connection`
+fn submitter(connection: &Connection) -> String {
+    format!("{}/{}", RpcInterface::Grpc, connection.identity())
+}
@@ -3,15 +3,19 @@ use super::{
     interface::{DynKaspadMethod, Interface},
 };
 use crate::{
+    access_log::{self, AccessLogRecord},
     connection::{Connection, IncomingRoute},
     connection_handler::ServerContext,
-    error::GrpcServerResult,
+    error::{GrpcServerError, GrpcServerResult},
 };
 use kaspa_core::debug;
 use kaspa_grpc_core::{
     ops::KaspadPayloadOps,
     protowire::{KaspadRequest, KaspadResponse},
 };
+use kaspa_utils_tower::counters::MethodCounters;
+use std::{sync::Arc, time::Instant};
+use tokio::time::timeout;
 
 pub struct RequestHandler {
     rpc_op: KaspadPayloadOps,
@@ -19,6 +23,7 @@ pub struct RequestHandler {
     server_ctx: ServerContext,
     method: DynKaspadMethod,
     connection: Connection,
+    counters: Arc<MethodCounters>,
 }
 
 impl RequestHandler {
@@ -30,12 +35,20 @@ impl RequestHandler {
         connection: Connection,
     ) -> Self {
         let method = interface.get_method(&rpc_op);
-        Self { rpc_op, incoming_route, server_ctx: server_context, method, connection }
+        let counters = server_context.request_counters.method(&format!("{rpc_op:?}"));
+        Self { rpc_op, incoming_route, server_ctx: server_context, method, connection, counters }
     }
 
     pub async fn handle_request(&self, request: KaspadRequest) -> GrpcServerResult<KaspadResponse> {
         let id = request.id;
-        let mut response = self.method.call(self.server_ctx.clone(), self.connection.clone(), request).await?;
+        let call = self.method.call(self.server_ctx.clone(), self.connection.clone(), request);
+        // Dropping the call future on timeout cancels the request
+        let mut response = match self.server_ctx.deadlines.get(self.rpc_op) {
+            Some(deadline) => {
+                timeout(deadline, call).await.map_err(|_| GrpcServerError::DeadlineExceeded(self.rpc_op, deadline))??
+            }
+            None => call.await?,
+        };
         response.id = id;
         Ok(response)
     }
@@ -46,16 +59,31 @@ impl Handler for RequestHandler {
     async fn start(&mut self) {
         debug!("GRPC, Starting request handler {:?} for client {}", self.rpc_op, self.connection);
         while let Ok(request) = self.incoming_route.recv().await {
-            let response = self.handle_request(request).await;
-            match response {
-                Ok(response) => {
-                    if self.connection.enqueue(response).await.is_err() {
-                        break;
-                    }
-                }
+            let id = request.id;
+            let start = Instant::now();
+            let response = match self.handle_request(request).await {
+                Ok(response) => response,
                 Err(e) => {
                     debug!("GRPC, Request handling error {} for client {}", e, self.connection);
+                    // Answer anyway so the client can resolve the pending request carrying this id
+                    KaspadResponse::from(self.rpc_op.to_error_response(e.into())).with_id(id)
                 }
+            };
+            let latency = start.elapsed();
+            self.counters.record(latency, response.payload.as_ref().is_some_and(|payload| payload.is_error()));
+            if access_log::is_enabled() {
+                access_log::record(&AccessLogRecord::new(
+                    self.connection.net_address(),
+                    self.connection.identity(),
+                    self.connection.tenant().map(|tenant| tenant.name()),
+                    &format!("{:?}", self.rpc_op),
+                    latency,
+                    prost::Message::encoded_len(&response),
+                    response.payload.as_ref().and_then(|payload| payload.error_message()),
+                ));
+            }
+            if self.connection.enqueue(response).await.is_err() {
+                break;
             }
         }
         debug!("GRPC, Exiting request handler {:?} for client {}", self.rpc_op, self.connection);
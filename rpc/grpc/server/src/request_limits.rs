@@ -0,0 +1,90 @@
+//! Per-method limits of the number of items a gRPC request can hold.
+//!
+//! The message size limit of the transport lets a single request carry millions of addresses or transactions,
+//! whose conversion and processing would spike the memory of the node. Requests holding more items than the
+//! limit of their method are rejected by the routing layer with an error response, before being converted.
+
+use kaspa_grpc_core::protowire::kaspad_request::Payload;
+use kaspa_rpc_core::{RpcError, RpcResult};
+
+/// Maximum number of addresses in a request querying or tracking addresses
+pub const MAX_REQUEST_ADDRESSES: usize = 100_000;
+
+/// Maximum number of transactions submitted in a single request
+pub const MAX_REQUEST_TRANSACTIONS: usize = 1_000;
+
+/// Maximum number of DAA scores converted to timestamps in a single request
+pub const MAX_REQUEST_DAA_SCORES: usize = 10_000;
+
+/// Maximum number of payment outputs of a transaction built in a single request
+pub const MAX_REQUEST_OUTPUTS: usize = 1_000;
+
+/// Maximum number of ids or script public keys in each list of a block template exclusions update
+pub const MAX_REQUEST_EXCLUSIONS: usize = 10_000;
+
+/// Checks the item lists of `payload` against the limits of its method
+pub fn check_request_limits(payload: &Payload) -> RpcResult<()> {
+    match payload {
+        Payload::GetUtxosByAddressesRequest(request) => check("addresses", request.addresses.len(), MAX_REQUEST_ADDRESSES),
+        Payload::GetBalancesByAddressesRequest(request) => check("addresses", request.addresses.len(), MAX_REQUEST_ADDRESSES),
+        Payload::GetMempoolEntriesByAddressesRequest(request) => check("addresses", request.addresses.len(), MAX_REQUEST_ADDRESSES),
+        Payload::NotifyUtxosChangedRequest(request) => check("addresses", request.addresses.len(), MAX_REQUEST_ADDRESSES),
+        Payload::StopNotifyingUtxosChangedRequest(request) => check("addresses", request.addresses.len(), MAX_REQUEST_ADDRESSES),
+        Payload::GetMempoolEntriesRequest(request) => {
+            check("spendableByAddresses", request.spendable_by_addresses.len(), MAX_REQUEST_ADDRESSES)
+        }
+        Payload::SubmitTransactionsRequest(request) => check("transactions", request.transactions.len(), MAX_REQUEST_TRANSACTIONS),
+        Payload::GetDaaScoreTimestampEstimateRequest(request) => check("daaScores", request.daa_scores.len(), MAX_REQUEST_DAA_SCORES),
+        Payload::BuildUnsignedTransactionRequest(request) => {
+            check("fromAddresses", request.from_addresses.len(), MAX_REQUEST_ADDRESSES)
+                .and_then(|_| check("outputs", request.outputs.len(), MAX_REQUEST_OUTPUTS))
+        }
+        Payload::UpdateBlockTemplateExclusionsRequest(request) => {
+            check("addTransactionIds", request.add_transaction_ids.len(), MAX_REQUEST_EXCLUSIONS)
+                .and_then(|_| check("removeTransactionIds", request.remove_transaction_ids.len(), MAX_REQUEST_EXCLUSIONS))
+                .and_then(|_| check("addScriptPublicKeys", request.add_script_public_keys.len(), MAX_REQUEST_EXCLUSIONS))
+                .and_then(|_| check("removeScriptPublicKeys", request.remove_script_public_keys.len(), MAX_REQUEST_EXCLUSIONS))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check(field: &'static str, count: usize, limit: usize) -> RpcResult<()> {
+    match count > limit {
+        true => Err(RpcError::RequestSizeExceedingMaximum(field.to_string(), count, limit)),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_grpc_core::protowire::{
+        GetBlockCountRequestMessage, GetUtxosByAddressesRequestMessage, SubmitTransactionsRequestMessage,
+    };
+
+    #[test]
+    fn test_check_request_limits() {
+        let addresses = |count: usize| {
+            Payload::GetUtxosByAddressesRequest(GetUtxosByAddressesRequestMessage {
+                addresses: vec![String::new(); count],
+                ..Default::default()
+            })
+        };
+        assert!(check_request_limits(&addresses(MAX_REQUEST_ADDRESSES)).is_ok());
+        assert!(matches!(
+            check_request_limits(&addresses(MAX_REQUEST_ADDRESSES + 1)),
+            Err(RpcError::RequestSizeExceedingMaximum(field, count, MAX_REQUEST_ADDRESSES))
+                if field == "addresses" && count == MAX_REQUEST_ADDRESSES + 1
+        ));
+
+        let transactions = SubmitTransactionsRequestMessage {
+            transactions: vec![Default::default(); MAX_REQUEST_TRANSACTIONS + 1],
+            ..Default::default()
+        };
+        assert!(check_request_limits(&Payload::SubmitTransactionsRequest(transactions)).is_err());
+
+        // Methods without item lists are never limited
+        assert!(check_request_limits(&Payload::GetBlockCountRequest(GetBlockCountRequestMessage {})).is_ok());
+    }
+}
@@ -1,50 +1,90 @@
-use crate::{adaptor::Adaptor, manager::Manager};
+use crate::{
+    adaptor::Adaptor,
+    compression::GrpcCompressionConfig,
+    deadline::RequestDeadlines,
+    listen::GrpcBinding,
+    manager::{IpConnectionLimit, Manager},
+    outgoing::OutgoingRouteConfig,
+    rate_limit::RateLimitConfig,
+    tenant::Tenants,
+    tls::GrpcTlsConfig,
+    transport::GrpcTransportConfig,
+};
 use kaspa_consensus_core::config::Config;
 use kaspa_core::{
     debug,
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
-use kaspa_rpc_service::service::RpcCoreService;
-use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
+use kaspa_rpc_service::{access::RpcInterface, service::RpcCoreService};
+use kaspa_utils::triggers::SingleTrigger;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::sync::Arc;
 use triggered::Listener;
 
 pub struct GrpcService {
-    net_address: NetAddress,
+    bindings: Vec<GrpcBinding>,
     config: Arc<Config>,
     core_service: Arc<RpcCoreService>,
     rpc_max_clients: usize,
+    ip_limit: IpConnectionLimit,
     broadcasters: usize,
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    tenants: Option<Arc<Tenants>>,
+    tls: Option<GrpcTlsConfig>,
+    rate_limit: Option<RateLimitConfig>,
+    deadlines: RequestDeadlines,
+    compression: GrpcCompressionConfig,
+    transport: GrpcTransportConfig,
+    outgoing: OutgoingRouteConfig,
 }
 
 impl GrpcService {
     pub const IDENT: &'static str = "grpc-service";
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        address: NetAddress,
+        bindings: Vec<GrpcBinding>,
         config: Arc<Config>,
         core_service: Arc<RpcCoreService>,
         rpc_max_clients: usize,
+        ip_limit: IpConnectionLimit,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        tenants: Option<Arc<Tenants>>,
+        tls: Option<GrpcTlsConfig>,
+        rate_limit: Option<RateLimitConfig>,
+        deadlines: RequestDeadlines,
+        compression: GrpcCompressionConfig,
+        transport: GrpcTransportConfig,
+        outgoing: OutgoingRouteConfig,
     ) -> Self {
         Self {
-            net_address: address,
+            bindings,
             config,
             core_service,
             rpc_max_clients,
+            ip_limit,
             broadcasters,
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            tenants,
+            tls,
+            rate_limit,
+            deadlines,
+            compression,
+            transport,
+            outgoing,
         }
     }
 
+    pub fn tenants(&self) -> Option<Arc<Tenants>> {
+        self.tenants.clone()
+    }
+
     pub fn started(&self) -> Listener {
         self.started.listener.clone()
     }
@@ -61,9 +101,16 @@ impl AsyncService for GrpcService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let manager = Manager::new(self.rpc_max_clients);
+        let manager = Manager::new(self.rpc_max_clients, self.ip_limit.clone());
+        let bindings = self
+            .bindings
+            .iter()
+            .map(|binding| {
+                (binding.endpoint.clone(), self.core_service.access_policy_with_profile(RpcInterface::Grpc, binding.profile))
+            })
+            .collect();
         let grpc_adaptor = Adaptor::server(
-            self.net_address,
+            bindings,
             self.config.bps(),
             manager,
             self.core_service.clone(),
@@ -71,7 +118,19 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.tenants.clone(),
+            self.tls.clone(),
+            self.rate_limit,
+            self.deadlines.clone(),
+            self.compression.clone(),
+            self.transport,
+            self.outgoing,
         );
+        self.core_service.register_notification_stats(RpcInterface::Grpc.as_str(), grpc_adaptor.notification_stats());
+        self.core_service.register_grpc_request_counters(grpc_adaptor.request_counters());
+        if let Some(tenants) = self.tenants.clone() {
+            self.core_service.register_grpc_tenants(tenants);
+        }
 
         // Signal the server was started
         self.started.trigger.trigger();
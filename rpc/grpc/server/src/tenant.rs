@@ -0,0 +1,567 @@
+//! Virtual RPC namespaces sharing a single gRPC server.
+//!
+//! Every tenant is identified by an API key provided by the client in the `x-api-key`
+//! request metadata when opening its message stream. The tenant quotas (request rate,
+//! active subscriptions and address set sizes) are enforced over all the connections
+//! sharing the same API key, and the tenant usage is tracked for reporting.
+
+use kaspa_grpc_core::{ops::KaspadPayloadOps, protowire::kaspad_request::Payload};
+use kaspa_notify::subscription::Command;
+use kaspa_rpc_core::TenantMetrics;
+use kaspa_rpc_service::service::TenantUsageProvider;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Metadata key carrying the client API key
+pub const API_KEY_METADATA_KEY: &str = "x-api-key";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TenantQuota {
+    /// Maximum number of requests per second over all the tenant connections
+    pub requests_per_second: Option<u32>,
+
+    /// Maximum number of active notification subscriptions over all the tenant connections
+    pub max_subscriptions: Option<u64>,
+
+    /// Maximum number of addresses in a single request, and of addresses tracked by the UtxosChanged
+    /// subscriptions over all the tenant connections
+    pub max_addresses: Option<usize>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TenantConfig {
+    pub name: String,
+    pub api_key: String,
+    #[serde(default)]
+    pub quota: TenantQuota,
+}
+
+/// Tenants configuration, typically loaded from a TOML file:
+///
+/// ```toml
+/// require-api-key = true
+///
+/// [[tenant]]
+/// name = "explorer"
+/// api-key = "..."
+/// quota = { requests-per-second = 100, max-subscriptions = 10, max-addresses = 1000 }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TenantsConfig {
+    /// Refuse the connections not providing the API key of a configured tenant
+    #[serde(default)]
+    pub require_api_key: bool,
+
+    #[serde(default, rename = "tenant")]
+    pub tenants: Vec<TenantConfig>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QuotaError {
+    #[error("tenant {0} exceeded its rate limit of {1} requests per second")]
+    RateLimit(String, u32),
+
+    #[error("tenant {0} reached its limit of {1} active subscriptions")]
+    MaxSubscriptions(String, u64),
+
+    #[error("tenant {0} requested {1} addresses, exceeding its limit of {2}")]
+    MaxAddresses(String, usize, usize),
+
+    #[error("tenant {0} would track {1} addresses, exceeding its limit of {2}")]
+    MaxTrackedAddresses(String, u64, usize),
+
+    #[error("tenant {0} cannot track all addresses with its limit of {1} tracked addresses")]
+    AllAddresses(String, usize),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TenantUsageSnapshot {
+    pub active_connections: u64,
+    pub active_subscriptions: u64,
+    pub tracked_addresses: u64,
+    pub requests: u64,
+    pub rejected_requests: u64,
+}
+
+#[derive(Debug, Default)]
+struct TenantUsage {
+    active_connections: AtomicU64,
+    active_subscriptions: AtomicU64,
+    tracked_addresses: AtomicU64,
+    requests: AtomicU64,
+    rejected_requests: AtomicU64,
+}
+
+#[derive(Debug)]
+struct RateWindow {
+    start: Instant,
+    count: u32,
+}
+
+#[derive(Debug)]
+pub struct Tenant {
+    name: String,
+    quota: TenantQuota,
+    usage: TenantUsage,
+    window: Mutex<RateWindow>,
+}
+
+impl Tenant {
+    pub fn new(name: String, quota: TenantQuota) -> Self {
+        Self { name, quota, usage: Default::default(), window: Mutex::new(RateWindow { start: Instant::now(), count: 0 }) }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn quota(&self) -> &TenantQuota {
+        &self.quota
+    }
+
+    pub fn usage(&self) -> TenantUsageSnapshot {
+        TenantUsageSnapshot {
+            active_connections: self.usage.active_connections.load(Ordering::Relaxed),
+            active_subscriptions: self.usage.active_subscriptions.load(Ordering::Relaxed),
+            tracked_addresses: self.usage.tracked_addresses.load(Ordering::Relaxed),
+            requests: self.usage.requests.load(Ordering::Relaxed),
+            rejected_requests: self.usage.rejected_requests.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn connect(&self) {
+        self.usage.active_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Releases the resources held by a closing connection
+    pub(crate) fn disconnect(&self, subscriptions: &mut TenantSubscriptions) {
+        self.usage.active_subscriptions.fetch_sub(subscriptions.active.len() as u64, Ordering::Relaxed);
+        subscriptions.active.clear();
+        self.release_addresses(subscriptions);
+        self.usage.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Checks `payload` against the tenant quotas, updating the connection `subscriptions` on success
+    pub(crate) fn admit(&self, payload: &Payload, subscriptions: &mut TenantSubscriptions) -> Result<(), QuotaError> {
+        self.usage.requests.fetch_add(1, Ordering::Relaxed);
+        let result =
+            self.check_rate().and_then(|_| self.check_addresses(payload)).and_then(|_| self.subscribe(payload, subscriptions));
+        if result.is_err() {
+            self.usage.rejected_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn check_rate(&self) -> Result<(), QuotaError> {
+        let Some(limit) = self.quota.requests_per_second else {
+            return Ok(());
+        };
+        let mut window = self.window.lock();
+        let now = Instant::now();
+        if now.duration_since(window.start) >= Duration::from_secs(1) {
+            window.start = now;
+            window.count = 0;
+        }
+        if window.count >= limit {
+            return Err(QuotaError::RateLimit(self.name.clone(), limit));
+        }
+        window.count += 1;
+        Ok(())
+    }
+
+    fn check_addresses(&self, payload: &Payload) -> Result<(), QuotaError> {
+        let Some(limit) = self.quota.max_addresses else {
+            return Ok(());
+        };
+        let count = match payload {
+            Payload::GetUtxosByAddressesRequest(request) => request.addresses.len(),
+            Payload::GetBalancesByAddressesRequest(request) => request.addresses.len(),
+            Payload::GetMempoolEntriesByAddressesRequest(request) => request.addresses.len(),
+            _ => return Ok(()),
+        };
+        match count > limit {
+            true => Err(QuotaError::MaxAddresses(self.name.clone(), count, limit)),
+            false => Ok(()),
+        }
+    }
+
+    fn subscribe(&self, payload: &Payload, subscriptions: &mut TenantSubscriptions) -> Result<(), QuotaError> {
+        let Some((op, command)) = subscription_command(payload) else {
+            return Ok(());
+        };
+        let is_utxos_changed = op == KaspadPayloadOps::NotifyUtxosChanged;
+        match command {
+            Command::Start => {
+                let is_new = !subscriptions.active.contains(&op);
+                if is_new {
+                    let active = self.usage.active_subscriptions.fetch_add(1, Ordering::Relaxed);
+                    if let Some(limit) = self.quota.max_subscriptions {
+                        if active >= limit {
+                            self.usage.active_subscriptions.fetch_sub(1, Ordering::Relaxed);
+                            return Err(QuotaError::MaxSubscriptions(self.name.clone(), limit));
+                        }
+                    }
+                }
+                if is_utxos_changed {
+                    let addresses = utxos_changed_addresses(payload);
+                    let result = match is_utxos_changed_replace(payload) {
+                        true => self.replace_addresses(addresses, subscriptions),
+                        false => self.track_addresses(addresses, subscriptions),
+                    };
+                    if let Err(err) = result {
+                        if is_new {
+                            self.usage.active_subscriptions.fetch_sub(1, Ordering::Relaxed);
+                        }
+                        return Err(err);
+                    }
+                }
+                subscriptions.active.insert(op);
+            }
+            Command::Stop => {
+                if is_utxos_changed {
+                    let addresses = utxos_changed_addresses(payload);
+                    if addresses.is_empty() {
+                        self.release_addresses(subscriptions);
+                    } else {
+                        let released = addresses.iter().filter(|address| subscriptions.addresses.remove(*address)).count();
+                        self.usage.tracked_addresses.fetch_sub(released as u64, Ordering::Relaxed);
+                        // The subscription remains active as long as it tracks some address
+                        if !subscriptions.addresses.is_empty() {
+                            return Ok(());
+                        }
+                    }
+                }
+                if subscriptions.active.remove(&op) {
+                    self.usage.active_subscriptions.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds `addresses` to the addresses tracked by the UtxosChanged subscription of a connection, checking the
+    /// total number of addresses tracked over all the tenant connections against the quota.
+    ///
+    /// An empty `addresses` stands for all addresses, which cannot be tracked by a tenant with an address quota.
+    fn track_addresses(&self, addresses: &[String], subscriptions: &mut TenantSubscriptions) -> Result<(), QuotaError> {
+        if addresses.is_empty() {
+            if let Some(limit) = self.quota.max_addresses {
+                return Err(QuotaError::AllAddresses(self.name.clone(), limit));
+            }
+            self.release_addresses(subscriptions);
+            return Ok(());
+        }
+        let added = addresses.iter().filter(|address| !subscriptions.addresses.contains(*address)).collect::<HashSet<_>>();
+        let count = added.len() as u64;
+        let tracked = self.usage.tracked_addresses.fetch_add(count, Ordering::Relaxed) + count;
+        if let Some(limit) = self.quota.max_addresses {
+            if tracked > limit as u64 {
+                self.usage.tracked_addresses.fetch_sub(count, Ordering::Relaxed);
+                return Err(QuotaError::MaxTrackedAddresses(self.name.clone(), tracked, limit));
+            }
+        }
+        subscriptions.addresses.extend(added.into_iter().cloned());
+        Ok(())
+    }
+
+    /// Replaces the addresses tracked by the UtxosChanged subscription of a connection with `addresses`, checking the
+    /// total number of addresses tracked over all the tenant connections against the quota.
+    fn replace_addresses(&self, addresses: &[String], subscriptions: &mut TenantSubscriptions) -> Result<(), QuotaError> {
+        if addresses.is_empty() {
+            return self.track_addresses(addresses, subscriptions);
+        }
+        let addresses = addresses.iter().collect::<HashSet<_>>();
+        let added = addresses.iter().filter(|address| !subscriptions.addresses.contains(**address)).count() as u64;
+        let released = subscriptions.addresses.iter().filter(|address| !addresses.contains(address)).count() as u64;
+        let tracked = self.usage.tracked_addresses.fetch_add(added, Ordering::Relaxed) + added - released;
+        if let Some(limit) = self.quota.max_addresses {
+            if tracked > limit as u64 {
+                self.usage.tracked_addresses.fetch_sub(added, Ordering::Relaxed);
+                return Err(QuotaError::MaxTrackedAddresses(self.name.clone(), tracked, limit));
+            }
+        }
+        self.usage.tracked_addresses.fetch_sub(released, Ordering::Relaxed);
+        subscriptions.addresses = addresses.into_iter().cloned().collect();
+        Ok(())
+    }
+
+    fn release_addresses(&self, subscriptions: &mut TenantSubscriptions) {
+        self.usage.tracked_addresses.fetch_sub(subscriptions.addresses.len() as u64, Ordering::Relaxed);
+        subscriptions.addresses.clear();
+    }
+}
+
+/// Notification subscriptions held by a single tenant connection
+#[derive(Debug, Default)]
+pub(crate) struct TenantSubscriptions {
+    active: HashSet<KaspadPayloadOps>,
+    /// Addresses tracked by the UtxosChanged subscription
+    addresses: HashSet<String>,
+}
+
+/// Returns the addresses of a UtxosChanged subscription payload
+fn utxos_changed_addresses(payload: &Payload) -> &[String] {
+    match payload {
+        Payload::NotifyUtxosChangedRequest(request) => &request.addresses,
+        Payload::StopNotifyingUtxosChangedRequest(request) => &request.addresses,
+        _ => &[],
+    }
+}
+
+/// Tells if a UtxosChanged subscription payload replaces the address set of the connection
+fn is_utxos_changed_replace(payload: &Payload) -> bool {
+    matches!(payload, Payload::NotifyUtxosChangedRequest(request) if request.replace)
+}
+
+/// Returns the subscription op and command of a notification subscription payload
+fn subscription_command(payload: &Payload) -> Option<(KaspadPayloadOps, Command)> {
+    let (op, command) = match payload {
+        Payload::NotifyBlockAddedRequest(request) => (KaspadPayloadOps::NotifyBlockAdded, request.command),
+        Payload::NotifyNewBlockTemplateRequest(request) => (KaspadPayloadOps::NotifyNewBlockTemplate, request.command),
+        Payload::NotifyFinalityConflictRequest(request) => (KaspadPayloadOps::NotifyFinalityConflict, request.command),
+        Payload::NotifyUtxosChangedRequest(request) => (KaspadPayloadOps::NotifyUtxosChanged, request.command),
+        Payload::NotifySinkBlueScoreChangedRequest(request) => (KaspadPayloadOps::NotifySinkBlueScoreChanged, request.command),
+        Payload::NotifyPruningPointUtxoSetOverrideRequest(request) => {
+            (KaspadPayloadOps::NotifyPruningPointUtxoSetOverride, request.command)
+        }
+        Payload::NotifyVirtualDaaScoreChangedRequest(request) => (KaspadPayloadOps::NotifyVirtualDaaScoreChanged, request.command),
+        Payload::NotifyVirtualChainChangedRequest(request) => (KaspadPayloadOps::NotifyVirtualChainChanged, request.command),
+        Payload::NotifyHealthAlertRequest(request) => (KaspadPayloadOps::NotifyHealthAlert, request.command),
+        Payload::NotifyMempoolTransactionsEvictedRequest(request) => {
+            (KaspadPayloadOps::NotifyMempoolTransactionsEvicted, request.command)
+        }
+        Payload::NotifyJobCompletedRequest(request) => (KaspadPayloadOps::NotifyJobCompleted, request.command),
+        Payload::NotifyIndexResyncProgressRequest(request) => (KaspadPayloadOps::NotifyIndexResyncProgress, request.command),
+        Payload::StopNotifyingUtxosChangedRequest(_) => return Some((KaspadPayloadOps::NotifyUtxosChanged, Command::Stop)),
+        Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_) => {
+            return Some((KaspadPayloadOps::NotifyPruningPointUtxoSetOverride, Command::Stop))
+        }
+        _ => return None,
+    };
+    Some((op, command.into()))
+}
+
+#[derive(Debug, Error)]
+pub enum TenantAuthError {
+    #[error("missing API key")]
+    MissingApiKey,
+
+    #[error("unknown API key")]
+    UnknownApiKey,
+}
+
+/// The set of tenants known to the server, indexed by API key
+#[derive(Debug, Default)]
+pub struct Tenants {
+    by_api_key: HashMap<String, Arc<Tenant>>,
+    require_api_key: bool,
+}
+
+impl Tenants {
+    pub fn new(config: TenantsConfig) -> Self {
+        let by_api_key =
+            config.tenants.into_iter().map(|tenant| (tenant.api_key, Arc::new(Tenant::new(tenant.name, tenant.quota)))).collect();
+        Self { by_api_key, require_api_key: config.require_api_key }
+    }
+
+    /// Resolves the tenant of a connection opened with `api_key`.
+    ///
+    /// Returns `Ok(None)` for a connection without API key when API keys are not required.
+    pub fn authenticate(&self, api_key: Option<&str>) -> Result<Option<Arc<Tenant>>, TenantAuthError> {
+        match api_key {
+            Some(api_key) => self.by_api_key.get(api_key).cloned().map(Some).ok_or(TenantAuthError::UnknownApiKey),
+            None if self.require_api_key => Err(TenantAuthError::MissingApiKey),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the usage of every tenant, sorted by tenant name
+    pub fn usage(&self) -> Vec<(String, TenantUsageSnapshot)> {
+        let mut usage = self.by_api_key.values().map(|tenant| (tenant.name.clone(), tenant.usage())).collect::<Vec<_>>();
+        usage.sort_by(|(a, _), (b, _)| a.cmp(b));
+        usage
+    }
+}
+
+impl TenantUsageProvider for Tenants {
+    fn tenant_metrics(&self) -> Vec<TenantMetrics> {
+        self.usage()
+            .into_iter()
+            .map(|(name, usage)| TenantMetrics {
+                name,
+                active_connections: usage.active_connections,
+                active_subscriptions: usage.active_subscriptions,
+                tracked_addresses: usage.tracked_addresses,
+                requests: usage.requests,
+                rejected_requests: usage.rejected_requests,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_grpc_core::protowire::{
+        GetUtxosByAddressesRequestMessage, NotifyBlockAddedRequestMessage, NotifyUtxosChangedRequestMessage, RpcNotifyCommand,
+    };
+
+    #[test]
+    fn test_tenant_quotas() {
+        let tenant = Tenant::new(
+            "test".to_string(),
+            TenantQuota { requests_per_second: Some(3), max_subscriptions: Some(1), max_addresses: Some(2) },
+        );
+        let mut subscriptions = TenantSubscriptions::default();
+        tenant.connect();
+
+        let addresses = |n: usize| {
+            Payload::GetUtxosByAddressesRequest(GetUtxosByAddressesRequestMessage {
+                addresses: vec!["address".to_string(); n],
+                ..Default::default()
+            })
+        };
+        let notify =
+            |command: RpcNotifyCommand| Payload::NotifyBlockAddedRequest(NotifyBlockAddedRequestMessage { command: command as i32 });
+
+        assert!(tenant.admit(&addresses(2), &mut subscriptions).is_ok());
+        assert_eq!(tenant.admit(&addresses(3), &mut subscriptions), Err(QuotaError::MaxAddresses("test".to_string(), 3, 2)));
+        assert!(tenant.admit(&notify(RpcNotifyCommand::NotifyStart), &mut subscriptions).is_ok());
+        assert_eq!(
+            tenant.admit(&notify(RpcNotifyCommand::NotifyStart), &mut subscriptions),
+            Err(QuotaError::RateLimit("test".to_string(), 3))
+        );
+        assert_eq!(tenant.usage().active_subscriptions, 1);
+
+        tenant.disconnect(&mut subscriptions);
+        assert_eq!(
+            tenant.usage(),
+            TenantUsageSnapshot {
+                active_connections: 0,
+                active_subscriptions: 0,
+                tracked_addresses: 0,
+                requests: 4,
+                rejected_requests: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_tenant_tracked_addresses() {
+        let tenant = Tenant::new(
+            "test".to_string(),
+            TenantQuota { requests_per_second: None, max_subscriptions: None, max_addresses: Some(3) },
+        );
+        let (mut first, mut second) = (TenantSubscriptions::default(), TenantSubscriptions::default());
+        tenant.connect();
+        tenant.connect();
+
+        let utxos_changed = |command: RpcNotifyCommand, addresses: &[&str]| {
+            Payload::NotifyUtxosChangedRequest(NotifyUtxosChangedRequestMessage {
+                addresses: addresses.iter().map(|address| address.to_string()).collect(),
+                replace: false,
+                command: command as i32,
+            })
+        };
+        let replace_utxos_changed = |addresses: &[&str]| {
+            Payload::NotifyUtxosChangedRequest(NotifyUtxosChangedRequestMessage {
+                addresses: addresses.iter().map(|address| address.to_string()).collect(),
+                replace: true,
+                command: RpcNotifyCommand::NotifyStart as i32,
+            })
+        };
+        let usage = |tenant: &Tenant| (tenant.usage().active_subscriptions, tenant.usage().tracked_addresses);
+
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &["a", "b"]), &mut first).is_ok());
+        assert_eq!(usage(&tenant), (1, 2));
+
+        // The quota covers the addresses tracked by all the tenant connections
+        assert_eq!(
+            tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &["c", "d"]), &mut second),
+            Err(QuotaError::MaxTrackedAddresses("test".to_string(), 4, 3))
+        );
+        assert_eq!(usage(&tenant), (1, 2));
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &["a"]), &mut second).is_ok());
+        assert_eq!(usage(&tenant), (2, 3));
+
+        // Addresses already tracked by the connection are not counted twice
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &["a", "b"]), &mut first).is_ok());
+        assert_eq!(usage(&tenant), (2, 3));
+
+        // Tracking all addresses is incompatible with an address quota
+        assert_eq!(
+            tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &[]), &mut first),
+            Err(QuotaError::AllAddresses("test".to_string(), 3))
+        );
+
+        // Stopping some addresses keeps the subscription active, stopping all of them ends it
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStop, &["a"]), &mut first).is_ok());
+        assert_eq!(usage(&tenant), (2, 2));
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStop, &[]), &mut second).is_ok());
+        assert_eq!(usage(&tenant), (1, 1));
+        assert!(tenant.admit(&utxos_changed(RpcNotifyCommand::NotifyStart, &["c", "d"]), &mut second).is_ok());
+        assert_eq!(usage(&tenant), (2, 3));
+
+        // A replacement only counts the difference with the addresses tracked by the connection
+        assert!(tenant.admit(&replace_utxos_changed(&["c", "e"]), &mut second).is_ok());
+        assert_eq!(usage(&tenant), (2, 3));
+        assert_eq!(
+            tenant.admit(&replace_utxos_changed(&["e", "f", "g"]), &mut second),
+            Err(QuotaError::MaxTrackedAddresses("test".to_string(), 4, 3))
+        );
+        assert_eq!(usage(&tenant), (2, 3));
+
+        // A closing connection releases its addresses
+        tenant.disconnect(&mut first);
+        assert_eq!(usage(&tenant), (1, 2));
+        tenant.disconnect(&mut second);
+        assert_eq!(usage(&tenant), (0, 0));
+    }
+
+    #[test]
+    fn test_tenant_metrics() {
+        let tenant = |name: &str| TenantConfig { name: name.to_string(), api_key: format!("key-{name}"), quota: Default::default() };
+        let tenants = Tenants::new(TenantsConfig { require_api_key: false, tenants: vec![tenant("b"), tenant("a")] });
+        let a = tenants.authenticate(Some("key-a")).unwrap().unwrap();
+        let mut subscriptions = TenantSubscriptions::default();
+        a.connect();
+        let payload = Payload::NotifyUtxosChangedRequest(NotifyUtxosChangedRequestMessage {
+            addresses: vec!["x".to_string(), "y".to_string()],
+            replace: false,
+            command: RpcNotifyCommand::NotifyStart as i32,
+        });
+        assert!(a.admit(&payload, &mut subscriptions).is_ok());
+
+        let metrics = tenants.tenant_metrics();
+        assert_eq!(metrics.iter().map(|metrics| metrics.name.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(
+            (metrics[0].active_connections, metrics[0].active_subscriptions, metrics[0].tracked_addresses, metrics[0].requests),
+            (1, 1, 2, 1)
+        );
+        assert_eq!(metrics[1].requests, 0);
+    }
+
+    #[test]
+    fn test_tenants_authentication() {
+        let config = TenantsConfig {
+            require_api_key: true,
+            tenants: vec![TenantConfig { name: "a".to_string(), api_key: "key-a".to_string(), quota: Default::default() }],
+        };
+        let tenants = Tenants::new(config);
+        assert_eq!(tenants.authenticate(Some("key-a")).unwrap().unwrap().name(), "a");
+        assert!(matches!(tenants.authenticate(Some("key-b")), Err(TenantAuthError::UnknownApiKey)));
+        assert!(matches!(tenants.authenticate(None), Err(TenantAuthError::MissingApiKey)));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::outgoing::{outgoing_channel, OutgoingMessage, OutgoingRouteConfig};
+use futures::FutureExt;
+use kaspa_grpc_core::protowire::{
+    kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, PingResponseMessage, RpcBlock, RpcBlockHeader,
+    RpcBlockLevelParents, RpcTransaction,
+};
+use prost::Message;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the allocations of the current thread, so tests running concurrently do not interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the number of allocations performed by `f`
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(|count| count.get());
+    f();
+    ALLOCATIONS.with(|count| count.get()) - before
+}
+
+fn block_added_notification() -> KaspadResponse {
+    let header = RpcBlockHeader {
+        parents: vec![RpcBlockLevelParents { parent_hashes: vec!["ab".repeat(32); 10] }; 5],
+        hash_merkle_root: "cd".repeat(32),
+        ..Default::default()
+    };
+    let transactions = vec![RpcTransaction { payload: "ef".repeat(100), ..Default::default() }; 20];
+    let block = RpcBlock { header: Some(header), transactions, verbose_data: None };
+    KaspadResponse { id: 0, payload: Some(Payload::BlockAddedNotification(BlockAddedNotificationMessage { block: Some(block) })) }
+}
+
+#[test]
+fn test_broadcast_allocations() {
+    const CONNECTIONS: usize = 100;
+
+    let routes = (0..CONNECTIONS).map(|_| outgoing_channel(OutgoingRouteConfig::default())).collect::<Vec<_>>();
+    // Warm the queues up, so they hold a buffer like the ones of live connections
+    for (sender, receiver) in routes.iter() {
+        sender.try_send(KaspadResponse { id: 1, payload: Some(Payload::PingResponse(PingResponseMessage { error: None })) }).unwrap();
+        receiver.recv().now_or_never().flatten().unwrap();
+    }
+    let notification = block_added_notification();
+    let mut frame = Vec::with_capacity(2 * notification.encoded_len());
+
+    // The notification gets encoded once per broadcast...
+    let mut message = None;
+    let encoding_allocations = allocations(|| message = Some(OutgoingMessage::encode(&notification)));
+    let message = message.unwrap();
+    assert!(encoding_allocations > 0);
+
+    // ...and the encoding is shared by the connections, each of them writing it to the frame of its stream
+    let broadcast_allocations = allocations(|| {
+        for (sender, receiver) in routes.iter() {
+            sender.try_send(message.clone()).unwrap();
+            let received = receiver.recv().now_or_never().flatten().unwrap();
+            frame.clear();
+            received.write_to(&mut frame);
+        }
+    });
+    // Sharing the encoding may allocate its reference count on the first clone, whatever the number of connections
+    assert!(broadcast_allocations <= 1, "{broadcast_allocations} allocations for {CONNECTIONS} connections");
+
+    // Whereas cloning the notification for every connection allocates many times per connection
+    let clone_allocations = allocations(|| {
+        for _ in 0..CONNECTIONS {
+            drop(notification.clone());
+        }
+    });
+    assert!(clone_allocations >= CONNECTIONS * 50, "{clone_allocations} allocations for {CONNECTIONS} connections");
+}
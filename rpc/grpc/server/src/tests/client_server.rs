@@ -1,11 +1,32 @@
-use super::rpc_core_mock::RpcCoreMock;
-use crate::{adaptor::Adaptor, manager::Manager};
+use super::rpc_core_mock::{mock_block_hash, RpcCoreMock, MOCK_BLOCK_COUNT, MOCK_NETWORK_ID, MOCK_PRUNING_POINT_UTXO_COUNT};
+use crate::{adaptor::Adaptor, listen::GrpcEndpoint, manager::Manager};
+use kaspa_consensus_core::muhash::MuHashExtensions;
 use kaspa_core::info;
-use kaspa_grpc_client::GrpcClient;
-use kaspa_notify::scope::{NewBlockTemplateScope, Scope};
-use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_grpc_client::{GrpcClient, GrpcClientPool};
+use kaspa_grpc_core::{
+    ops::KaspadPayloadOps,
+    protowire::{
+        kaspad_request, rpc_client::RpcClient, ExportPruningPointUtxoSetRequestMessage, KaspadRequest, KaspadResponse,
+        StreamBlocksRequestMessage,
+    },
+};
+use kaspa_muhash::MuHash;
+use kaspa_notify::{
+    events::EventType,
+    scope::{NewBlockTemplateScope, Scope, UtxosChangedScope},
+};
+use kaspa_rpc_core::{
+    api::rpc::RpcApi, notify::mode::NotificationMode, GetCurrentNetworkRequest, RpcAddress, RpcError, RpcNetworkId, RpcNetworkType,
+    RpcUtxosByAddressesEntry,
+};
+use kaspa_rpc_service::access::{RpcAccessPolicy, NODE_CONTROL_OPS};
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
+use tonic_health::pb::{health_check_response::ServingStatus, health_client::HealthClient, HealthCheckRequest};
+use tonic_reflection::pb::{
+    server_reflection_client::ServerReflectionClient, server_reflection_request::MessageRequest,
+    server_reflection_response::MessageResponse, ServerReflectionRequest,
+};
 
 #[tokio::test]
 async fn test_client_server_sanity_check() {
@@ -36,6 +57,468 @@ async fn test_client_server_sanity_check() {
     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 }
 
+#[tokio::test]
+async fn test_client_pool() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+
+    // Connect a pool, each client having its own connection
+    let server_url = format!("grpc://localhost:{}", server.serve_address().port);
+    let pool = GrpcClientPool::connect(server_url, 3).await.unwrap();
+    assert_eq!(pool.size(), 3);
+    assert_eq!(server.active_connections().len(), 3, "the pool failed to connect all its clients");
+
+    // Issue concurrent requests distributed across the pool
+    let responses = futures::future::join_all((0..MOCK_BLOCK_COUNT).map(|i| pool.get_block(mock_block_hash(i), false))).await;
+    for (i, response) in responses.into_iter().enumerate() {
+        assert_eq!(response.unwrap().verbose_data.unwrap().hash, mock_block_hash(i as u64), "unexpected block");
+    }
+    assert_eq!(pool.in_flight(), vec![0; 3], "no request should remain in flight");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+
+    assert!(pool.disconnect().await.is_ok(), "pool failed to disconnect");
+    drop(pool);
+
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_access_policy() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server denying GetServerInfo on top of the node control methods
+    let denied = NODE_CONTROL_OPS.iter().map(|op| format!("{op:?}")).chain(["getserverinfo".to_string()]).collect();
+    let server = create_server_with_policy(rpc_core_service.clone(), Arc::new(RpcAccessPolicy::new(None, denied)));
+    let client = create_client(server.serve_address()).await;
+
+    // Denied methods are rejected before reaching the core service
+    let err = client.get_server_info().await.expect_err("GetServerInfo should be denied");
+    assert!(err.to_string().contains("not allowed"), "unexpected error: {err}");
+    let err = client.shutdown().await.expect_err("Shutdown should be denied");
+    assert!(err.to_string().contains("not allowed"), "unexpected error: {err}");
+
+    // Allowed methods still reach the core service
+    assert!(client.get_info().await.is_ok(), "GetInfo should be allowed");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+
+    assert!(client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(client);
+
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_multiple_addresses() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server listening on a private address and on a public one denying GetServerInfo
+    let public_policy = Arc::new(RpcAccessPolicy::new(None, vec!["getserverinfo".to_string()]));
+    let server = create_server_with_bindings(
+        rpc_core_service.clone(),
+        vec![(get_free_net_address(), Default::default()), (get_free_net_address(), public_policy)],
+    );
+    assert_eq!(server.serve_addresses().len(), 2);
+    let private_client = create_client(server.serve_addresses()[0]).await;
+    let public_client = create_client(server.serve_addresses()[1]).await;
+
+    // Every address enforces its own access policy
+    assert!(private_client.get_server_info().await.is_ok(), "GetServerInfo should be allowed on the private address");
+    let err = public_client.get_server_info().await.expect_err("GetServerInfo should be denied on the public address");
+    assert!(err.to_string().contains("not allowed"), "unexpected error: {err}");
+    assert!(public_client.get_info().await.is_ok(), "GetInfo should be allowed on the public address");
+
+    // Connections to both addresses share the same manager
+    assert_eq!(server.active_connections().len(), 2);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+
+    assert!(private_client.disconnect().await.is_ok(), "client failed to disconnect");
+    assert!(public_client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(private_client);
+    drop(public_client);
+
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_client_server_unix_socket() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server listening on a TCP address and on a unix domain socket
+    let socket_dir = tempfile::tempdir().unwrap();
+    let socket_path = socket_dir.path().join("kaspad.sock");
+    let server = create_server_with_endpoints(
+        rpc_core_service.clone(),
+        vec![(get_free_net_address().into(), Default::default()), (GrpcEndpoint::Unix(socket_path.clone()), Default::default())],
+    );
+    assert_eq!(server.serve_addresses().len(), 1);
+    assert_eq!(server.serve_endpoints()[1], GrpcEndpoint::Unix(socket_path.clone()));
+
+    // Connect over the unix domain socket, the URI being ignored by the connector
+    let channel = {
+        let mut retries = 0;
+        loop {
+            let socket_path = socket_path.clone();
+            let connector = tower::service_fn(move |_: tonic::transport::Uri| tokio::net::UnixStream::connect(socket_path.clone()));
+            match tonic::transport::Endpoint::from_static("http://[::]:0").connect_with_connector(connector).await {
+                Ok(channel) => break channel,
+                // The server binds its socket asynchronously
+                Err(_) if retries < 20 => {
+                    retries += 1;
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                }
+                Err(err) => panic!("failed to connect to the unix socket: {err}"),
+            }
+        }
+    };
+    let mut client = RpcClient::new(channel);
+    let requests = vec![KaspadRequest::from(kaspad_request::Payload::GetInfoRequest(Default::default())).with_id(1)];
+    let mut stream = client.message_stream(tokio_stream::iter(requests)).await.unwrap().into_inner();
+    let response = stream.message().await.unwrap().expect("the stream should not end before the response is received");
+    assert_eq!(response.id, 1);
+    let op: KaspadPayloadOps = response.payload.as_ref().unwrap().try_into().unwrap();
+    assert_eq!(op, KaspadPayloadOps::GetInfo);
+    assert_eq!(server.active_connections().len(), 1);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(stream);
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_export_pruning_point_utxo_set() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let server_url = format!("http://localhost:{}", server.serve_address().port);
+    let mut client = RpcClient::connect(server_url).await.unwrap();
+
+    // Export the UTXO set in chunks of 2 entries
+    const CHUNK_SIZE: u32 = 2;
+    let mut stream = client
+        .export_pruning_point_utxo_set(ExportPruningPointUtxoSetRequestMessage { chunk_size: CHUNK_SIZE })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut chunks = vec![];
+    while let Some(chunk) = stream.message().await.unwrap() {
+        chunks.push(chunk);
+    }
+    let expected_chunk_count = MOCK_PRUNING_POINT_UTXO_COUNT.div_ceil(CHUNK_SIZE) as usize;
+    assert_eq!(expected_chunk_count, chunks.len(), "unexpected number of chunks");
+
+    // Every chunk carries the MuHash of the entries streamed so far, the last one matching the UTXO commitment
+    let mut multiset = MuHash::new();
+    let mut entry_count = 0;
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert!(chunk.error.is_none(), "chunk {i} reports an error: {:?}", chunk.error);
+        assert_eq!(i as u64, chunk.chunk_index);
+        assert_eq!(i + 1 == expected_chunk_count, chunk.is_last);
+        for entry in chunk.entries.iter() {
+            let entry: RpcUtxosByAddressesEntry = entry.try_into().unwrap();
+            multiset.add_utxo(&entry.outpoint, &entry.utxo_entry);
+            entry_count += 1;
+        }
+        assert_eq!(multiset.finalize().to_string(), chunk.multiset_hash, "chunk {i} has an unexpected multiset hash");
+    }
+    assert_eq!(MOCK_PRUNING_POINT_UTXO_COUNT as usize, entry_count);
+    let last_chunk = chunks.last().unwrap();
+    assert_eq!(last_chunk.utxo_commitment, last_chunk.multiset_hash, "the exported UTXO set should match the commitment");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_stream_blocks() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let server_url = format!("http://localhost:{}", server.serve_address().port);
+    let mut client = RpcClient::connect(server_url).await.unwrap();
+
+    struct Test {
+        low_index: Option<u64>,
+        max_blocks: u64,
+        expected: std::ops::Range<u64>,
+    }
+    let tests = [
+        Test { low_index: None, max_blocks: 0, expected: 0..MOCK_BLOCK_COUNT },
+        Test { low_index: Some(2), max_blocks: 0, expected: 2..MOCK_BLOCK_COUNT },
+        Test { low_index: Some(1), max_blocks: 5, expected: 1..6 },
+        Test { low_index: Some(MOCK_BLOCK_COUNT - 1), max_blocks: 0, expected: MOCK_BLOCK_COUNT - 1..MOCK_BLOCK_COUNT },
+    ];
+    for (i, test) in tests.into_iter().enumerate() {
+        let request = StreamBlocksRequestMessage {
+            low_hash: test.low_index.map(|index| mock_block_hash(index).to_string()).unwrap_or_default(),
+            max_blocks: test.max_blocks,
+            ..Default::default()
+        };
+        let mut stream = client.stream_blocks(request).await.unwrap().into_inner();
+        let mut messages = vec![];
+        while let Some(message) = stream.message().await.unwrap() {
+            messages.push(message);
+        }
+
+        // Every block is streamed once, in order, the last one being flagged as such
+        assert_eq!(test.expected.clone().count(), messages.len(), "test #{i}: unexpected number of blocks");
+        for (j, (message, expected_index)) in messages.iter().zip(test.expected.clone()).enumerate() {
+            assert!(message.error.is_none(), "test #{i}: message {j} reports an error: {:?}", message.error);
+            assert_eq!(j as u64, message.index);
+            assert_eq!(j + 1 == messages.len(), message.is_last);
+            let hash = &message.block.as_ref().unwrap().verbose_data.as_ref().unwrap().hash;
+            assert_eq!(&mock_block_hash(expected_index).to_string(), hash, "test #{i}: unexpected block at index {j}");
+        }
+    }
+
+    // An unknown low hash ends the stream with an error
+    let request = StreamBlocksRequestMessage { low_hash: mock_block_hash(MOCK_BLOCK_COUNT).to_string(), ..Default::default() };
+    let mut stream = client.stream_blocks(request).await.unwrap().into_inner();
+    let message = stream.message().await.unwrap().unwrap();
+    assert!(message.error.is_some() && message.block.is_none(), "the stream should report an error");
+    assert!(stream.message().await.unwrap().is_none(), "the stream should end after an error");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_reflection() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let server_url = format!("http://localhost:{}", server.serve_address().port);
+    let mut client = ServerReflectionClient::connect(server_url).await.unwrap();
+
+    // The protowire service is discoverable
+    let request = ServerReflectionRequest { host: String::new(), message_request: Some(MessageRequest::ListServices(String::new())) };
+    let mut stream = client.server_reflection_info(tokio_stream::once(request)).await.unwrap().into_inner();
+    let response = stream.message().await.unwrap().unwrap();
+    let Some(MessageResponse::ListServicesResponse(services)) = response.message_response else {
+        panic!("unexpected reflection response {:?}", response.message_response);
+    };
+    assert!(services.service.iter().any(|service| service.name == "protowire.RPC"), "the protowire service is not listed");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_health() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service, not synced yet
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let server_url = format!("http://localhost:{}", server.serve_address().port);
+    let mut client = HealthClient::connect(server_url).await.unwrap();
+
+    // The server is not serving while the node is syncing
+    let request = |service: &str| HealthCheckRequest { service: service.to_string() };
+    for service in ["", "protowire.RPC"] {
+        let response = client.check(request(service)).await.unwrap().into_inner();
+        assert_eq!(response.status, ServingStatus::NotServing as i32, "service '{service}'");
+    }
+    let status = client.check(request("unknown.Service")).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    // The server reports serving as soon as the node is synced
+    let mut stream = client.watch(request("")).await.unwrap().into_inner();
+    assert_eq!(stream.message().await.unwrap().unwrap().status, ServingStatus::NotServing as i32);
+    rpc_core_service.set_synced(true);
+    assert_eq!(stream.message().await.unwrap().unwrap().status, ServingStatus::Serving as i32);
+    assert!(server.health().is_serving());
+
+    // Stopping the server reports it as not serving
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    assert!(!server.health().is_serving());
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    drop(stream);
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_message_ids() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let server_url = format!("http://localhost:{}", server.serve_address().port);
+    let mut client = RpcClient::connect(server_url).await.unwrap();
+
+    // Send concurrent requests over a single stream, each with its own id
+    let requests: Vec<KaspadRequest> = vec![
+        KaspadRequest::from(kaspad_request::Payload::GetInfoRequest(Default::default())).with_id(7),
+        KaspadRequest::from(kaspad_request::Payload::PingRequest(Default::default())).with_id(11),
+        KaspadRequest::from(kaspad_request::Payload::GetServerInfoRequest(Default::default())).with_id(13),
+    ];
+    let expected: HashMap<u64, KaspadPayloadOps> =
+        requests.iter().map(|request| (request.id, request.payload.as_ref().unwrap().into())).collect();
+    let mut stream = client.message_stream(tokio_stream::iter(requests)).await.unwrap().into_inner();
+
+    // Every response echoes the id of the request it answers
+    let mut received = HashMap::new();
+    while received.len() < expected.len() {
+        let response = stream.message().await.unwrap().expect("the stream should not end before all responses are received");
+        let op: KaspadPayloadOps = response.payload.as_ref().unwrap().try_into().unwrap();
+        assert!(received.insert(response.id, op).is_none(), "response id {} was received twice", response.id);
+    }
+    assert_eq!(expected, received);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(stream);
+    drop(client);
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+/// A request as sent by a client knowing a payload the server does not, or missing its payload
+#[derive(Clone, PartialEq, prost::Message)]
+struct FutureRequest {
+    #[prost(uint64, tag = "101")]
+    id: u64,
+    #[prost(message, optional, tag = "9999")]
+    payload: Option<kaspa_grpc_core::protowire::PingRequestMessage>,
+}
+
+/// Opens a message stream sending `requests` as they are
+async fn future_message_stream(server_address: NetAddress, requests: Vec<FutureRequest>) -> tonic::Streaming<KaspadResponse> {
+    let channel =
+        tonic::transport::Endpoint::new(format!("http://localhost:{}", server_address.port)).unwrap().connect().await.unwrap();
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await.unwrap();
+    let codec = tonic::codec::ProstCodec::<FutureRequest, KaspadResponse>::default();
+    let path = tonic::codegen::http::uri::PathAndQuery::from_static("/protowire.RPC/MessageStream");
+    grpc.streaming(tonic::Request::new(tokio_stream::iter(requests)), path, codec).await.unwrap().into_inner()
+}
+
+#[tokio::test]
+async fn test_client_server_unknown_payload() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+
+    // A payload unknown to the server is answered with an empty payload, the connection staying open
+    let requests =
+        vec![FutureRequest { id: 7, payload: Some(Default::default()) }, FutureRequest { id: 11, payload: Some(Default::default()) }];
+    let mut stream = future_message_stream(server.serve_address(), requests).await;
+    for id in [7, 11] {
+        let response = stream.message().await.unwrap().expect("the request should be answered");
+        assert_eq!(response.id, id);
+        assert!(response.payload.is_none());
+    }
+    drop(stream);
+
+    // A request missing its payload is rejected, closing the connection
+    let mut stream = future_message_stream(server.serve_address(), vec![FutureRequest { id: 13, payload: None }]).await;
+    assert!(!matches!(stream.message().await, Ok(Some(_))), "the request should not be answered");
+    drop(stream);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
 #[tokio::test]
 async fn test_client_server_connections() {
     enum ClosingEnd {
@@ -188,10 +671,174 @@ async fn test_client_server_notifications() {
     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 }
 
+#[tokio::test]
+async fn test_client_server_utxos_changed_replace() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let client = create_client(server.serve_address()).await;
+
+    let addresses = [
+        "kaspatest:qp3w5h9hp9ude4vjpllsm4qpe8rcc5dmeealkl0cnxlgtj4ly7rczqxcdamvr",
+        "kaspatest:qp6l8n5meyut2yvpyw2dqrrcgc3t6jxflheh9j8s2f75quepdl4qvg4hnfexd",
+        "kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c",
+    ]
+    .map(|address| RpcAddress::try_from(address).unwrap());
+    let scope = |indexes: &[usize], replace: bool| -> Scope {
+        UtxosChangedScope::new(indexes.iter().map(|&i| addresses[i].clone()).collect()).with_replace(replace).into()
+    };
+    async fn address_count(client: &GrpcClient) -> u64 {
+        let subscriptions = client.get_subscriptions().await.unwrap();
+        subscriptions.into_iter().find(|subscription| subscription.event_type == EventType::UtxosChanged).unwrap().address_count
+    }
+
+    // A plain start adds to the server-side address set of the connection
+    client.start_notify(0, scope(&[0], false)).await.unwrap();
+    client.start_notify(0, scope(&[1], false)).await.unwrap();
+    assert_eq!(address_count(&client).await, 2);
+
+    // A replacing start goes through the diffing path of the server notifier
+    client.start_notify(0, scope(&[1, 2], true)).await.unwrap();
+    assert_eq!(address_count(&client).await, 2);
+    client.start_notify(0, scope(&[2], true)).await.unwrap();
+    assert_eq!(address_count(&client).await, 1);
+
+    // A plain start still adds to the replaced address set
+    client.start_notify(0, scope(&[0], false)).await.unwrap();
+    assert_eq!(address_count(&client).await, 2);
+
+    assert!(client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(client);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_network_id() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start the server
+    let server = create_server(rpc_core_service.clone());
+    let client = create_client(server.serve_address()).await;
+
+    // The network id is reported along with the legacy network type
+    let response = client.get_current_network_call(GetCurrentNetworkRequest {}).await.unwrap();
+    assert_eq!((response.network, response.network_id), (RpcNetworkType::Testnet, Some(MOCK_NETWORK_ID)));
+
+    // The network verification tells apart the suffixes of a same network type
+    assert!(client.verify_network_id(MOCK_NETWORK_ID).await.is_ok());
+    let other_network_id = RpcNetworkId::with_suffix(RpcNetworkType::Testnet, 10);
+    match client.verify_network_id(other_network_id).await {
+        Err(RpcError::NetworkMismatch(expected, network_id)) => {
+            assert_eq!((expected, network_id), (other_network_id, MOCK_NETWORK_ID))
+        }
+        result => panic!("unexpected network verification result {result:?}"),
+    }
+
+    assert!(client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(client);
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_reconnection() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service and a server
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+    let server = create_server(rpc_core_service.clone());
+    let server_address = server.serve_address();
+
+    // Connect a reconnecting client and subscribe it to NewBlockTemplate notifications
+    let server_url = format!("grpc://localhost:{}", server_address.port);
+    let client =
+        GrpcClient::connect_with_args(NotificationMode::Direct, server_url, None, true, None, false, None, Default::default())
+            .await
+            .unwrap();
+    assert!(client.start_notify(0, Scope::NewBlockTemplate(NewBlockTemplateScope::default())).await.is_ok());
+
+    // Stop the server and the fake service
+    rpc_core_service.join().await;
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    for _ in 0..50 {
+        if !client.is_connected() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert!(!client.is_connected(), "the client failed to detect the disconnection");
+
+    // Restart a server on the same address
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+    let server = create_server_with_bindings(rpc_core_service.clone(), vec![(server_address, Default::default())]);
+
+    // The client reconnects on its own and renews its subscription to the new server
+    let mut received = false;
+    for _ in 0..60 {
+        if client.is_connected() {
+            assert!(rpc_core_service.notify_new_block_template().is_ok());
+            rpc_core_service.notify_complete().await;
+            if tokio::time::timeout(std::time::Duration::from_millis(500), client.notification_channel_receiver().recv()).await.is_ok()
+            {
+                received = true;
+                break;
+            }
+        } else {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+    assert!(received, "the client failed to reconnect and renew its subscription");
+    assert_eq!(server.active_connections().len(), 1);
+
+    assert!(client.disconnect().await.is_ok(), "client failed to disconnect");
+    drop(client);
+    rpc_core_service.join().await;
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
 fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
-    let manager = Manager::new(128);
+    create_server_with_policy(core_service, Default::default())
+}
+
+fn create_server_with_policy(core_service: Arc<RpcCoreMock>, access_policy: Arc<RpcAccessPolicy>) -> Arc<Adaptor> {
+    create_server_with_bindings(core_service, vec![(get_free_net_address(), access_policy)])
+}
+
+fn create_server_with_bindings(core_service: Arc<RpcCoreMock>, bindings: Vec<(NetAddress, Arc<RpcAccessPolicy>)>) -> Arc<Adaptor> {
+    create_server_with_endpoints(core_service, bindings.into_iter().map(|(address, policy)| (address.into(), policy)).collect())
+}
+
+fn create_server_with_endpoints(core_service: Arc<RpcCoreMock>, bindings: Vec<(GrpcEndpoint, Arc<RpcAccessPolicy>)>) -> Arc<Adaptor> {
+    let manager = Manager::new(128, Default::default());
     Adaptor::server(
-        get_free_net_address(),
+        bindings,
         1,
         manager,
         core_service.clone(),
@@ -199,6 +846,13 @@ fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
         core_service.subscription_context(),
         3,
         Default::default(),
+        None,
+        None,
+        None,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
     )
 }
 
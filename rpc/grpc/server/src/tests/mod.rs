@@ -1,3 +1,4 @@
 mod rpc_core_mock;
 
+mod broadcast;
 mod client_server;
@@ -1,5 +1,7 @@
 use async_channel::{unbounded, Receiver};
 use async_trait::async_trait;
+use kaspa_consensus_core::muhash::MuHashExtensions;
+use kaspa_muhash::MuHash;
 use kaspa_notify::events::EVENT_TYPE_ARRAY;
 use kaspa_notify::listener::{ListenerId, ListenerLifespan};
 use kaspa_notify::notifier::{Notifier, Notify};
@@ -8,13 +10,43 @@ use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
 use kaspa_rpc_core::{api::rpc::RpcApi, *};
 use kaspa_rpc_core::{notify::connection::ChannelConnection, RpcResult};
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 pub(super) type RpcCoreNotifier = Notifier<Notification, ChannelConnection>;
 
+pub(super) const MOCK_PRUNING_POINT: u64 = 1;
+pub(super) const MOCK_PRUNING_POINT_UTXO_COUNT: u32 = 5;
+pub(super) const MOCK_BLOCK_COUNT: u64 = 10;
+const MOCK_BLOCKS_PAGE_SIZE: usize = 4;
+pub(super) const MOCK_NETWORK_ID: RpcNetworkId = RpcNetworkId::with_suffix(RpcNetworkType::Testnet, 11);
+
+/// Hash of the block at `index` in the mock chain, starting with genesis at index 0
+pub(super) fn mock_block_hash(index: u64) -> RpcHash {
+    RpcHash::from_u64_word(100 + index)
+}
+
+fn mock_block_index(hash: RpcHash) -> RpcResult<u64> {
+    (0..MOCK_BLOCK_COUNT).find(|&i| mock_block_hash(i) == hash).ok_or(RpcError::General(format!("block {hash} not found")))
+}
+
+/// The UTXO set of the mock pruning point, in outpoint order
+pub(super) fn mock_pruning_point_utxos() -> Vec<RpcUtxosByAddressesEntry> {
+    (0..MOCK_PRUNING_POINT_UTXO_COUNT)
+        .map(|i| RpcUtxosByAddressesEntry {
+            address: None,
+            outpoint: RpcTransactionOutpoint::new(RpcHash::from_u64_word(2), i),
+            utxo_entry: RpcUtxoEntry::new(1_000 + i as u64, Default::default(), 0, false),
+        })
+        .collect()
+}
+
 pub(super) struct RpcCoreMock {
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
+    is_synced: AtomicBool,
 }
 
 impl RpcCoreMock {
@@ -32,7 +64,7 @@ impl RpcCoreMock {
             policies,
             Some(sync_sender),
         ));
-        Self { core_notifier, _sync_receiver: sync_receiver }
+        Self { core_notifier, _sync_receiver: sync_receiver, is_synced: AtomicBool::new(false) }
     }
 
     pub(super) fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
@@ -54,6 +86,11 @@ impl RpcCoreMock {
         assert!(self._sync_receiver.recv().await.is_ok(), "the notifier sync channel is unexpectedly empty and closed");
     }
 
+    #[allow(dead_code)]
+    pub(super) fn set_synced(&self, is_synced: bool) {
+        self.is_synced.store(is_synced, Ordering::SeqCst);
+    }
+
     pub(super) fn start(&self) {
         self.core_notifier.clone().start();
     }
@@ -75,6 +112,7 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: true,
             has_message_id: true,
+            build_info: Default::default(),
         })
     }
 
@@ -87,15 +125,32 @@ impl RpcApi for RpcCoreMock {
     }
 
     async fn get_server_info_call(&self, _request: GetServerInfoRequest) -> RpcResult<GetServerInfoResponse> {
-        Err(RpcError::NotImplemented)
+        Ok(GetServerInfoResponse {
+            rpc_api_version: [0, 1, 0, 0],
+            server_version: "mock".to_owned(),
+            network_id: MOCK_NETWORK_ID,
+            has_utxo_index: false,
+            is_synced: self.is_synced.load(Ordering::SeqCst),
+            virtual_daa_score: 0,
+        })
     }
 
     async fn get_sync_status_call(&self, _request: GetSyncStatusRequest) -> RpcResult<GetSyncStatusResponse> {
-        Err(RpcError::NotImplemented)
+        let is_synced = self.is_synced.load(Ordering::SeqCst);
+        Ok(GetSyncStatusResponse {
+            is_synced,
+            phase: if is_synced { RpcSyncPhase::Synced } else { RpcSyncPhase::Idle },
+            phase_progress: None,
+            processed: 0,
+            estimated_remaining_time: None,
+            sink_daa_score: 0,
+            headers_selected_tip_daa_score: 0,
+            ibd_peer: None,
+        })
     }
 
     async fn get_current_network_call(&self, _request: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
-        Err(RpcError::NotImplemented)
+        Ok(GetCurrentNetworkResponse::new(MOCK_NETWORK_ID))
     }
 
     async fn submit_block_call(&self, _request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
@@ -134,10 +189,33 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+    async fn submit_transactions_call(&self, _request: SubmitTransactionsRequest) -> RpcResult<SubmitTransactionsResponse> {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_block_call(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
+        let index = mock_block_index(request.hash)?;
+        let selected_parent_hash = if index > 0 { mock_block_hash(index - 1) } else { RpcHash::from_u64_word(0) };
+        let block = RpcBlock {
+            header: RpcHeader::from_precomputed_hash(request.hash, vec![selected_parent_hash]),
+            transactions: vec![],
+            verbose_data: Some(RpcBlockVerboseData {
+                hash: request.hash,
+                difficulty: 1.0,
+                selected_parent_hash,
+                transaction_ids: vec![],
+                is_header_only: false,
+                blue_score: index,
+                children_hashes: vec![],
+                merge_set_blues_hashes: vec![],
+                merge_set_reds_hashes: vec![],
+                is_chain_block: true,
+                confirmations: MOCK_BLOCK_COUNT - index,
+            }),
+        };
+        Ok(GetBlockResponse { block })
+    }
+
     async fn get_subnetwork_call(&self, _request: GetSubnetworkRequest) -> RpcResult<GetSubnetworkResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -149,8 +227,11 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_blocks_call(&self, _request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_blocks_call(&self, request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
+        // A single chain paged in the same way as the node, the low hash included
+        let low_index = request.low_hash.map(mock_block_index).transpose()?.unwrap_or_default();
+        let block_hashes = (low_index..MOCK_BLOCK_COUNT).take(MOCK_BLOCKS_PAGE_SIZE).map(mock_block_hash).collect();
+        Ok(GetBlocksResponse { block_hashes, blocks: vec![] })
     }
 
     async fn get_block_count_call(&self, _request: GetBlockCountRequest) -> RpcResult<GetBlockCountResponse> {
@@ -228,6 +309,137 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_mass_call(&self, _request: GetTransactionMassRequest) -> RpcResult<GetTransactionMassResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn build_unsigned_transaction_call(
+        &self,
+        _request: BuildUnsignedTransactionRequest,
+    ) -> RpcResult<BuildUnsignedTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_dag_stats_call(&self, _request: GetDagStatsRequest) -> RpcResult<GetDagStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_health_call(&self, _request: GetHealthRequest) -> RpcResult<GetHealthResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_subscriptions_call(&self, _request: GetSubscriptionsRequest) -> RpcResult<GetSubscriptionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_virtual_parents_call(&self, _request: GetVirtualParentsRequest) -> RpcResult<GetVirtualParentsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_consensus_params_call(&self, _request: GetConsensusParamsRequest) -> RpcResult<GetConsensusParamsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_by_id_call(&self, _request: GetTransactionByIdRequest) -> RpcResult<GetTransactionByIdResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn sample_chain_blocks_call(&self, _request: SampleChainBlocksRequest) -> RpcResult<SampleChainBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_connections_call(&self, _request: GetConnectionsRequest) -> RpcResult<GetConnectionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_by_blue_score_call(&self, _request: GetBlockByBlueScoreRequest) -> RpcResult<GetBlockByBlueScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_by_daa_score_call(&self, _request: GetBlockByDaaScoreRequest) -> RpcResult<GetBlockByDaaScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_block_by_timestamp_call(
+        &self,
+        _request: GetChainBlockByTimestampRequest,
+    ) -> RpcResult<GetChainBlockByTimestampResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_pruning_point_utxos_call(&self, request: GetPruningPointUtxosRequest) -> RpcResult<GetPruningPointUtxosResponse> {
+        let pruning_point = RpcHash::from_u64_word(MOCK_PRUNING_POINT);
+        if request.pruning_point.is_some_and(|x| x != pruning_point) {
+            return Err(RpcError::General("unexpected pruning point".to_string()));
+        }
+        let utxos = mock_pruning_point_utxos();
+        let mut multiset = MuHash::new();
+        utxos.iter().for_each(|x| multiset.add_utxo(&x.outpoint, &x.utxo_entry));
+        let start = request.cursor.map_or(0, |cursor| utxos.iter().position(|x| x.outpoint == cursor).unwrap() + 1);
+        let limit = request.limit.map_or(usize::MAX, |x| x as usize);
+        let entries = utxos[start..].iter().take(limit).cloned().collect::<Vec<_>>();
+        let next_cursor = (start + entries.len() < utxos.len()).then(|| entries.last().unwrap().outpoint);
+        Ok(GetPruningPointUtxosResponse { pruning_point, utxo_commitment: multiset.finalize(), entries, next_cursor })
+    }
+
+    async fn get_mempool_info_call(&self, _request: GetMempoolInfoRequest) -> RpcResult<GetMempoolInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_mempool_policy_call(&self, _request: SetMempoolPolicyRequest) -> RpcResult<SetMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_current_block_color_call(&self, _request: GetCurrentBlockColorRequest) -> RpcResult<GetCurrentBlockColorResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_bans_call(&self, _request: GetBansRequest) -> RpcResult<GetBansResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_processing_status_call(
+        &self,
+        _request: GetBlockProcessingStatusRequest,
+    ) -> RpcResult<GetBlockProcessingStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn start_job_call(&self, _request: StartJobRequest) -> RpcResult<StartJobResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_job_status_call(&self, _request: GetJobStatusRequest) -> RpcResult<GetJobStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_notification_stats_call(&self, _request: GetNotificationStatsRequest) -> RpcResult<GetNotificationStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn generate_blocks_call(&self, _request: GenerateBlocksRequest) -> RpcResult<GenerateBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_server_capabilities_call(&self, _request: GetServerCapabilitiesRequest) -> RpcResult<GetServerCapabilitiesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn update_block_template_exclusions_call(
+        &self,
+        _request: UpdateBlockTemplateExclusionsRequest,
+    ) -> RpcResult<UpdateBlockTemplateExclusionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_quality_call(&self, _request: GetChainQualityRequest) -> RpcResult<GetChainQualityResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,73 @@
+use std::{fs, io, path::Path};
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
+
+/// TLS settings of the gRPC server, loaded from PEM files.
+///
+/// When a client CA is configured, the TLS handshake requires every client to present a certificate
+/// issued by this CA (mutual TLS), so only the operators holding such a certificate can reach the server
+/// and its node control methods.
+#[derive(Clone, Debug)]
+pub struct GrpcTlsConfig {
+    config: ServerTlsConfig,
+    requires_client_certificate: bool,
+}
+
+impl GrpcTlsConfig {
+    pub fn load(
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+        client_ca_path: Option<impl AsRef<Path>>,
+    ) -> io::Result<Self> {
+        let identity = Identity::from_pem(read_pem(cert_path)?, read_pem(key_path)?);
+        let mut config = ServerTlsConfig::new().identity(identity);
+        let requires_client_certificate = client_ca_path.is_some();
+        if let Some(client_ca_path) = client_ca_path {
+            config = config.client_ca_root(Certificate::from_pem(read_pem(client_ca_path)?));
+        }
+        Ok(Self { config, requires_client_certificate })
+    }
+
+    pub fn requires_client_certificate(&self) -> bool {
+        self.requires_client_certificate
+    }
+
+    pub(crate) fn server_config(&self) -> ServerTlsConfig {
+        self.config.clone()
+    }
+}
+
+fn read_pem(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let path = path.as_ref();
+    fs::read(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_tls_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("server.pem");
+        let key_path = dir.path().join("server.key");
+        let client_ca_path = dir.path().join("client-ca.pem");
+        for path in [&cert_path, &key_path, &client_ca_path] {
+            fs::write(path, b"-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n").unwrap();
+        }
+
+        let tls = GrpcTlsConfig::load(&cert_path, &key_path, None::<&Path>).unwrap();
+        assert!(!tls.requires_client_certificate());
+        let tls = GrpcTlsConfig::load(&cert_path, &key_path, Some(&client_ca_path)).unwrap();
+        assert!(tls.requires_client_certificate());
+
+        // Missing files are reported along with their path
+        let missing_path = dir.path().join("missing.pem");
+        for (cert_path, key_path, client_ca_path) in
+            [(&missing_path, &key_path, None), (&cert_path, &missing_path, None), (&cert_path, &key_path, Some(&missing_path))]
+        {
+            let err = GrpcTlsConfig::load(cert_path, key_path, client_ca_path).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::NotFound);
+            assert!(err.to_string().starts_with(&missing_path.display().to_string()));
+        }
+    }
+}
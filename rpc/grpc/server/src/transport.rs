@@ -0,0 +1,82 @@
+use std::time::Duration;
+use tonic::transport::Server;
+
+/// Default interval of the TCP keepalive probes, short enough to keep idle connections open through most NATs
+pub const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+/// Default time waited for the acknowledgement of an HTTP/2 keepalive ping before closing the connection
+pub const DEFAULT_HTTP2_KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The transport settings of the gRPC server connections.
+///
+/// Long-lived connections, like the ones of notification subscribers, can stay idle for a long time and get
+/// silently dropped by NATs and load balancers. TCP keepalive probes and HTTP/2 keepalive pings keep them open,
+/// the latter also detecting and closing the connections of unresponsive clients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GrpcTransportConfig {
+    /// Interval of the TCP keepalive probes, disabled if `None`
+    pub tcp_keepalive: Option<Duration>,
+
+    /// Interval of the HTTP/2 keepalive pings sent to the clients, disabled if `None`
+    pub http2_keepalive_interval: Option<Duration>,
+
+    /// Time waited for the acknowledgement of an HTTP/2 keepalive ping before closing the connection
+    pub http2_keepalive_timeout: Duration,
+
+    /// Maximum number of concurrent HTTP/2 streams of a connection, unlimited if `None`
+    pub max_concurrent_streams: Option<u32>,
+}
+
+impl GrpcTransportConfig {
+    /// Builds a config from command line values, where zero disables the setting
+    pub fn from_secs(
+        tcp_keepalive: u64,
+        http2_keepalive_interval: u64,
+        http2_keepalive_timeout: u64,
+        max_concurrent_streams: u32,
+    ) -> Self {
+        let duration = |secs| (secs > 0).then(|| Duration::from_secs(secs));
+        Self {
+            tcp_keepalive: duration(tcp_keepalive),
+            http2_keepalive_interval: duration(http2_keepalive_interval),
+            http2_keepalive_timeout: duration(http2_keepalive_timeout).unwrap_or(DEFAULT_HTTP2_KEEPALIVE_TIMEOUT),
+            max_concurrent_streams: (max_concurrent_streams > 0).then_some(max_concurrent_streams),
+        }
+    }
+
+    pub(crate) fn apply(&self, server: Server) -> Server {
+        server
+            .tcp_keepalive(self.tcp_keepalive)
+            .http2_keepalive_interval(self.http2_keepalive_interval)
+            .http2_keepalive_timeout(Some(self.http2_keepalive_timeout))
+            .max_concurrent_streams(self.max_concurrent_streams)
+    }
+}
+
+impl Default for GrpcTransportConfig {
+    fn default() -> Self {
+        Self {
+            tcp_keepalive: Some(DEFAULT_TCP_KEEPALIVE),
+            http2_keepalive_interval: None,
+            http2_keepalive_timeout: DEFAULT_HTTP2_KEEPALIVE_TIMEOUT,
+            max_concurrent_streams: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transport_config_from_secs() {
+        let config = GrpcTransportConfig::from_secs(60, 0, 0, 0);
+        assert_eq!(config, GrpcTransportConfig::default());
+
+        let config = GrpcTransportConfig::from_secs(0, 30, 10, 200);
+        assert_eq!(config.tcp_keepalive, None);
+        assert_eq!(config.http2_keepalive_interval, Some(Duration::from_secs(30)));
+        assert_eq!(config.http2_keepalive_timeout, Duration::from_secs(10));
+        assert_eq!(config.max_concurrent_streams, Some(200));
+    }
+}
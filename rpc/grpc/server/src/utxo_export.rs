@@ -0,0 +1,60 @@
+use futures::Stream;
+use kaspa_consensus_core::muhash::MuHashExtensions;
+use kaspa_core::debug;
+use kaspa_grpc_core::protowire::{self, ExportPruningPointUtxoSetChunkMessage};
+use kaspa_muhash::MuHash;
+use kaspa_rpc_core::{api::rpc::DynRpcService, GetPruningPointUtxosRequest, GetPruningPointUtxosResponse, RpcResult};
+
+/// Builds the stream of chunks exporting the UTXO set of the current pruning point, paging through it with
+/// the `GetPruningPointUtxos` method of `core_service`.
+///
+/// Every chunk carries the MuHash of all the entries streamed so far. The stream ends after the last chunk,
+/// whose MuHash must match the UTXO commitment of the pruning point, or after a chunk reporting an error.
+pub fn pruning_point_utxo_set_stream(
+    core_service: DynRpcService,
+    chunk_size: u32,
+) -> impl Stream<Item = Result<ExportPruningPointUtxoSetChunkMessage, tonic::Status>> + Send + 'static {
+    let limit = (chunk_size > 0).then_some(chunk_size);
+    async_stream::stream! {
+        let mut multiset = MuHash::new();
+        let mut pruning_point = None;
+        let mut cursor = None;
+        let mut chunk_index = 0;
+        loop {
+            let request = GetPruningPointUtxosRequest::new(pruning_point, cursor, limit);
+            let response: RpcResult<GetPruningPointUtxosResponse> = core_service.get_pruning_point_utxos_call(request).await;
+            match response {
+                Ok(page) => {
+                    page.entries.iter().for_each(|entry| multiset.add_utxo(&entry.outpoint, &entry.utxo_entry));
+                    let is_last = page.next_cursor.is_none();
+                    let chunk = ExportPruningPointUtxoSetChunkMessage {
+                        pruning_point: page.pruning_point.to_string(),
+                        utxo_commitment: page.utxo_commitment.to_string(),
+                        chunk_index,
+                        entries: page.entries.iter().map(|x| x.into()).collect(),
+                        multiset_hash: multiset.finalize().to_string(),
+                        is_last,
+                        error: None,
+                    };
+                    yield Ok(chunk);
+                    if is_last {
+                        debug!("GRPC, exported the UTXO set of pruning point {} in {} chunks", page.pruning_point, chunk_index + 1);
+                        break;
+                    }
+                    pruning_point = Some(page.pruning_point);
+                    cursor = page.next_cursor;
+                    chunk_index += 1;
+                }
+                Err(err) => {
+                    debug!("GRPC, pruning point UTXO set export failed at chunk {}: {}", chunk_index, err);
+                    yield Ok(ExportPruningPointUtxoSetChunkMessage {
+                        chunk_index,
+                        error: Some(protowire::RpcError::from(err)),
+                        ..Default::default()
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_rpc_core::RpcError;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid parameter {0}: {1}")]
+    InvalidParameter(&'static str, String),
+
+    #[error("RPC error: {0}")]
+    RpcError(#[from] RpcError),
+}
+
+impl Error {
+    /// HTTP status code of the response reporting the error
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Error::InvalidParameter(..) => StatusCode::BAD_REQUEST,
+            Error::RpcError(err) => match err {
+                RpcError::ConsensusError(ConsensusError::BlockNotFound(_) | ConsensusError::HeaderNotFound(_)) => {
+                    StatusCode::NOT_FOUND
+                }
+                RpcError::AddressError(_) | RpcError::NetworkMismatch(..) => StatusCode::BAD_REQUEST,
+                RpcError::NoUtxoIndex | RpcError::NoTxIndex => StatusCode::SERVICE_UNAVAILABLE,
+                RpcError::MethodNotAllowed(_) => StatusCode::FORBIDDEN,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+        }
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        (self.status(), Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_hashes::Hash;
+
+    #[test]
+    fn test_error_status() {
+        let tests = [
+            (Error::InvalidParameter("hash", "odd length".to_owned()), StatusCode::BAD_REQUEST),
+            (Error::RpcError(ConsensusError::BlockNotFound(Hash::from_u64_word(1)).into()), StatusCode::NOT_FOUND),
+            (Error::RpcError(ConsensusError::HeaderNotFound(Hash::from_u64_word(1)).into()), StatusCode::NOT_FOUND),
+            (Error::RpcError(RpcError::NoUtxoIndex), StatusCode::SERVICE_UNAVAILABLE),
+            (Error::RpcError(RpcError::General("failure".to_owned())), StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+        for (error, status) in tests {
+            assert_eq!(error.status(), status, "{error}");
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Kaspa HTTP gateway (AsyncService) module
+//!
+//! Maps a read-only subset of the [`RpcApi`](kaspa_rpc_core::api::rpc::RpcApi) onto plain GET endpoints answering
+//! JSON, for monitoring systems and light integrations which can speak neither gRPC nor wRPC. The served endpoints
+//! are described by an OpenAPI document available at [`OPENAPI_ROUTE`](router::OPENAPI_ROUTE). kaspad registers an
+//! [`HttpService`](service::HttpService) when started with `--rpclisten-http`.
+pub mod error;
+pub mod openapi;
+pub mod result;
+pub mod router;
+pub mod service;
@@ -0,0 +1,137 @@
+//! OpenAPI description of the gateway endpoints.
+//!
+//! The document is generated from [`ENDPOINTS`], which refers to the routes of the [`router`](crate::router) so both
+//! cannot drift apart.
+
+use crate::router::{ADDRESS_UTXOS_ROUTE, BLOCK_ROUTE, INFO_ROUTE, OPENAPI_ROUTE};
+use serde_json::{json, Map, Value};
+
+/// A parameter of an endpoint, either a segment of its path or a query parameter
+pub struct Parameter {
+    pub name: &'static str,
+    pub location: &'static str,
+    pub description: &'static str,
+    /// Values the parameter is restricted to, any string if empty
+    pub values: &'static [&'static str],
+}
+
+pub struct Endpoint {
+    pub route: &'static str,
+    pub summary: &'static str,
+    pub parameters: &'static [Parameter],
+    pub response: &'static str,
+}
+
+pub const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        route: INFO_ROUTE,
+        summary: "General information about the node",
+        parameters: &[],
+        response: "The node information, as returned by the GetInfo RPC method",
+    },
+    Endpoint {
+        route: BLOCK_ROUTE,
+        summary: "Block by hash",
+        parameters: &[
+            Parameter { name: "hash", location: "path", description: "Hex encoded hash of the block", values: &[] },
+            Parameter {
+                name: "verbosity",
+                location: "query",
+                description: "Level of detail of the returned block (default: transactionIds)",
+                values: &["headerOnly", "transactionIds", "full"],
+            },
+        ],
+        response: "The block, as returned by the GetBlock RPC method",
+    },
+    Endpoint {
+        route: ADDRESS_UTXOS_ROUTE,
+        summary: "UTXOs of an address, requires the node to run with --utxoindex",
+        parameters: &[Parameter { name: "address", location: "path", description: "Address with its network prefix", values: &[] }],
+        response: "The UTXO entries of the address, as returned by the GetUtxosByAddresses RPC method",
+    },
+    Endpoint { route: OPENAPI_ROUTE, summary: "This OpenAPI document", parameters: &[], response: "The OpenAPI document" },
+];
+
+/// Converts an axum route into an OpenAPI path template, `/blocks/:hash` becoming `/blocks/{hash}`
+pub fn openapi_path(route: &str) -> String {
+    route
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{name}}}"),
+            None => segment.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+pub fn openapi_document() -> Value {
+    let paths = ENDPOINTS
+        .iter()
+        .map(|endpoint| {
+            let parameters = endpoint
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    let mut schema = json!({ "type": "string" });
+                    if !parameter.values.is_empty() {
+                        schema["enum"] = json!(parameter.values);
+                    }
+                    json!({
+                        "name": parameter.name,
+                        "in": parameter.location,
+                        "required": parameter.location == "path",
+                        "description": parameter.description,
+                        "schema": schema,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let operation = json!({
+                "get": {
+                    "summary": endpoint.summary,
+                    "parameters": parameters,
+                    "responses": {
+                        "200": { "description": endpoint.response, "content": { "application/json": { "schema": { "type": "object" } } } },
+                        "default": { "description": "An error, described by the error field of the returned object" },
+                    },
+                }
+            });
+            (openapi_path(endpoint.route), operation)
+        })
+        .collect::<Map<_, _>>();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "Kaspa HTTP gateway", "version": env!("CARGO_PKG_VERSION") },
+        "paths": paths,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_document() {
+        assert_eq!(openapi_path(BLOCK_ROUTE), "/blocks/{hash}");
+        assert_eq!(openapi_path(ADDRESS_UTXOS_ROUTE), "/addresses/{address}/utxos");
+        assert_eq!(openapi_path(INFO_ROUTE), "/info");
+
+        let document = openapi_document();
+        let paths = document["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), ENDPOINTS.len());
+        for endpoint in ENDPOINTS.iter() {
+            let operation = &paths[&openapi_path(endpoint.route)]["get"];
+            // Every path segment parameter of the route is documented
+            let documented = operation["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|parameter| parameter["in"] == "path")
+                .map(|parameter| format!(":{}", parameter["name"].as_str().unwrap()))
+                .collect::<Vec<_>>();
+            let expected = endpoint.route.split('/').filter(|segment| segment.starts_with(':')).collect::<Vec<_>>();
+            assert_eq!(documented, expected, "{}", endpoint.route);
+        }
+        assert_eq!(paths["/blocks/{hash}"]["get"]["parameters"][1]["schema"]["enum"], json!(["headerOnly", "transactionIds", "full"]));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{error::Error, openapi::openapi_document, result::Result};
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use kaspa_addresses::Address;
+use kaspa_rpc_core::{
+    api::rpc::RpcApi, GetBlockRequest, GetInfoResponse, RpcBlock, RpcBlockVerbosity, RpcHash, RpcUtxosByAddressesEntry,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{str::FromStr, sync::Arc};
+
+pub const INFO_ROUTE: &str = "/info";
+pub const BLOCK_ROUTE: &str = "/blocks/:hash";
+pub const ADDRESS_UTXOS_ROUTE: &str = "/addresses/:address/utxos";
+pub const OPENAPI_ROUTE: &str = "/openapi.json";
+
+/// Builds the router serving every gateway endpoint by calling `rpc_api`
+pub fn router(rpc_api: Arc<dyn RpcApi>) -> Router {
+    Router::new()
+        .route(INFO_ROUTE, get(get_info))
+        .route(BLOCK_ROUTE, get(get_block))
+        .route(ADDRESS_UTXOS_ROUTE, get(get_address_utxos))
+        .route(OPENAPI_ROUTE, get(get_openapi))
+        .with_state(rpc_api)
+}
+
+async fn get_info(State(rpc_api): State<Arc<dyn RpcApi>>) -> Result<Json<GetInfoResponse>> {
+    Ok(Json(rpc_api.get_info().await?))
+}
+
+#[derive(Deserialize)]
+struct BlockQuery {
+    #[serde(default)]
+    verbosity: RpcBlockVerbosity,
+}
+
+async fn get_block(
+    State(rpc_api): State<Arc<dyn RpcApi>>,
+    Path(hash): Path<String>,
+    Query(query): Query<BlockQuery>,
+) -> Result<Json<RpcBlock>> {
+    let hash = RpcHash::from_str(&hash).map_err(|err| Error::InvalidParameter("hash", err.to_string()))?;
+    Ok(Json(rpc_api.get_block_call(GetBlockRequest::new(hash, false).with_verbosity(query.verbosity)).await?.block))
+}
+
+async fn get_address_utxos(
+    State(rpc_api): State<Arc<dyn RpcApi>>,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<RpcUtxosByAddressesEntry>>> {
+    let address = Address::try_from(address.as_str()).map_err(|err| Error::InvalidParameter("address", err.to_string()))?;
+    Ok(Json(rpc_api.get_utxos_by_addresses(vec![address]).await?))
+}
+
+async fn get_openapi() -> Json<Value> {
+    Json(openapi_document())
+}
@@ -0,0 +1,75 @@
+use crate::router::router;
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace,
+};
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_utils::triggers::SingleTrigger;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Options for configuring the HTTP gateway
+pub struct Options {
+    pub listen_address: String,
+}
+
+///
+///  HTTP gateway - Serves the read-only endpoints of the router until the service is signaled to exit
+///
+pub struct HttpService {
+    rpc_api: Arc<dyn RpcApi>,
+    options: Options,
+    shutdown: SingleTrigger,
+}
+
+impl HttpService {
+    pub fn new(rpc_api: Arc<dyn RpcApi>, options: Options) -> Self {
+        Self { rpc_api, options, shutdown: SingleTrigger::default() }
+    }
+}
+
+const HTTP_SERVER: &str = "http-service";
+
+impl AsyncService for HttpService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        HTTP_SERVER
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", HTTP_SERVER);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+        let listen_address = self.options.listen_address.clone();
+        let app = router(self.rpc_api.clone());
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(&listen_address)
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("HTTP Server unable to listen on {listen_address}: `{err}`")))?;
+
+            // Keep the server running until a service shutdown signal is received
+            info!("HTTP Server starting on: {}", listen_address);
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal)
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("HTTP Server error: `{err}`")))?;
+            info!("HTTP Server stopped on: {}", listen_address);
+
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", HTTP_SERVER);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", HTTP_SERVER);
+            Ok(())
+        })
+    }
+}
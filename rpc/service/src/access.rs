@@ -0,0 +1,200 @@
+//! Method-level access control of the RPC listening interfaces
+//!
+//! Every listening interface is either private, serving all the RPC methods, or public,
+//! serving only the methods allowed by the public access policy. By default, the public
+//! policy denies the methods controlling the node, like [`RpcApiOps::Shutdown`].
+
+use kaspa_rpc_core::{api::ops::RpcApiOps, RpcError, RpcResult};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// Methods controlling the node, denied on public interfaces unless configured otherwise
+pub const NODE_CONTROL_OPS: [RpcApiOps; 9] = [
+    RpcApiOps::Shutdown,
+    RpcApiOps::AddPeer,
+    RpcApiOps::Ban,
+    RpcApiOps::Unban,
+    RpcApiOps::ResolveFinalityConflict,
+    RpcApiOps::SetMempoolPolicy,
+    RpcApiOps::StartJob,
+    RpcApiOps::GenerateBlocks,
+    RpcApiOps::UpdateBlockTemplateExclusions,
+];
+
+/// An RPC listening interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcInterface {
+    Grpc,
+    WrpcBorsh,
+    WrpcJson,
+}
+
+impl RpcInterface {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcInterface::Grpc => "grpc",
+            RpcInterface::WrpcBorsh => "wrpc-borsh",
+            RpcInterface::WrpcJson => "wrpc-json",
+        }
+    }
+}
+
+impl Display for RpcInterface {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcInterface {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grpc" => Ok(RpcInterface::Grpc),
+            "wrpc-borsh" => Ok(RpcInterface::WrpcBorsh),
+            "wrpc-json" => Ok(RpcInterface::WrpcJson),
+            _ => Err(format!("unknown RPC interface {s}, expected one of grpc, wrpc-borsh, wrpc-json")),
+        }
+    }
+}
+
+/// Access profile of a listening address, overriding the one of its interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcAccessProfile {
+    /// Serving all the RPC methods
+    Private,
+    /// Serving only the methods allowed by the public access policy
+    Public,
+}
+
+impl RpcAccessProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcAccessProfile::Private => "private",
+            RpcAccessProfile::Public => "public",
+        }
+    }
+}
+
+impl Display for RpcAccessProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcAccessProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "private" => Ok(RpcAccessProfile::Private),
+            "public" => Ok(RpcAccessProfile::Public),
+            _ => Err(format!("unknown RPC access profile {s}, expected one of private, public")),
+        }
+    }
+}
+
+/// Returns the method name of an RPC op, as used in access policies
+pub fn op_name(op: &RpcApiOps) -> String {
+    format!("{op:?}")
+}
+
+/// Parses a comma-separated list of RPC method names, as in `Shutdown,AddPeer`.
+///
+/// Names are case-insensitive and must match an [`RpcApiOps`] variant.
+pub fn parse_ops(s: &str) -> Result<Vec<String>, String> {
+    let known = RpcApiOps::list().into_iter().map(|op| op_name(&op)).collect::<Vec<_>>();
+    s.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| known.iter().find(|x| x.eq_ignore_ascii_case(name)).cloned().ok_or_else(|| format!("unknown RPC method {name}")))
+        .collect()
+}
+
+/// Set of RPC methods served on a listening interface
+#[derive(Clone, Debug, Default)]
+pub struct RpcAccessPolicy {
+    /// If set, only these methods are served
+    allowed: Option<HashSet<String>>,
+    /// Methods never served
+    denied: HashSet<String>,
+}
+
+impl RpcAccessPolicy {
+    /// A policy serving all the methods
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// A policy serving the `allowed` methods, or all of them if `None`, except the `denied` ones.
+    ///
+    /// Method names are expected to be validated, see [`parse_ops`].
+    pub fn new(allowed: Option<Vec<String>>, denied: Vec<String>) -> Self {
+        let normalize = |names: Vec<String>| names.into_iter().map(|x| x.to_ascii_lowercase()).collect::<HashSet<_>>();
+        Self { allowed: allowed.map(normalize), denied: normalize(denied) }
+    }
+
+    /// The default policy of public interfaces, denying the [`NODE_CONTROL_OPS`]
+    pub fn public() -> Self {
+        Self::new(None, NODE_CONTROL_OPS.iter().map(op_name).collect())
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed.is_none() && self.denied.is_empty()
+    }
+
+    pub fn is_allowed(&self, method: &str) -> bool {
+        let method = method.to_ascii_lowercase();
+        !self.denied.contains(&method) && self.allowed.as_ref().map_or(true, |allowed| allowed.contains(&method))
+    }
+
+    pub fn check(&self, method: &str) -> RpcResult<()> {
+        match self.is_allowed(method) {
+            true => Ok(()),
+            false => Err(RpcError::MethodNotAllowed(method.to_string())),
+        }
+    }
+}
+
+/// Access policies of all the RPC listening interfaces
+#[derive(Clone, Debug, Default)]
+pub struct RpcAccessPolicies {
+    public_interfaces: HashSet<RpcInterface>,
+    public_policy: Arc<RpcAccessPolicy>,
+    unrestricted: Arc<RpcAccessPolicy>,
+}
+
+impl RpcAccessPolicies {
+    pub fn new(public_interfaces: impl IntoIterator<Item = RpcInterface>, public_policy: RpcAccessPolicy) -> Self {
+        Self {
+            public_interfaces: public_interfaces.into_iter().collect(),
+            public_policy: Arc::new(public_policy),
+            unrestricted: Default::default(),
+        }
+    }
+
+    pub fn is_public(&self, interface: RpcInterface) -> bool {
+        self.public_interfaces.contains(&interface)
+    }
+
+    /// Returns the policy enforced on `interface`
+    pub fn get(&self, interface: RpcInterface) -> Arc<RpcAccessPolicy> {
+        match self.is_public(interface) {
+            true => self.public_policy.clone(),
+            false => self.unrestricted.clone(),
+        }
+    }
+
+    /// Returns the policy enforced on a listening address of `interface` with an optional specific `profile`
+    pub fn get_with_profile(&self, interface: RpcInterface, profile: Option<RpcAccessProfile>) -> Arc<RpcAccessPolicy> {
+        match profile {
+            Some(RpcAccessProfile::Public) => self.public_policy.clone(),
+            Some(RpcAccessProfile::Private) => self.unrestricted.clone(),
+            None => self.get(interface),
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Rolling chain-quality analytics: red rate, inferred propagation delay and miner concentration
+
+use kaspa_consensus_core::{coinbase::coinbase_payload_script_public_key, tx::ScriptPublicKey};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    time::unix_now,
+    trace, warn,
+};
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::RpcChainQualitySample;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+const MONITOR: &str = "chain-quality-monitor";
+
+/// Interval between two samples
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Number of retained samples, covering a day
+const RETENTION: usize = 24 * 60 * 60 / SAMPLE_INTERVAL.as_secs() as usize;
+
+/// Maximum number of chain blocks a sample is computed over, bounding the work of a sample following a long pause
+const MAX_SAMPLE_CHAIN_BLOCKS: usize = 10_000;
+
+/// Chain-quality metrics as persisted in the meta database
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct ChainQualitySample {
+    pub timestamp: u64,
+    pub chain_block_count: u64,
+    pub block_count: u64,
+    pub red_block_count: u64,
+    pub average_propagation_delay: u64,
+    pub miner_count: u64,
+    pub top_miner_share: f64,
+    pub miner_concentration: f64,
+}
+
+impl MemSizeEstimator for ChainQualitySample {}
+
+impl From<ChainQualitySample> for RpcChainQualitySample {
+    fn from(sample: ChainQualitySample) -> Self {
+        let red_rate = match sample.block_count {
+            0 => 0.0,
+            count => sample.red_block_count as f64 / count as f64,
+        };
+        Self {
+            timestamp: sample.timestamp,
+            chain_block_count: sample.chain_block_count,
+            block_count: sample.block_count,
+            red_block_count: sample.red_block_count,
+            red_rate,
+            average_propagation_delay: sample.average_propagation_delay,
+            miner_count: sample.miner_count,
+            top_miner_share: sample.top_miner_share,
+            miner_concentration: sample.miner_concentration,
+        }
+    }
+}
+
+/// Samples are keyed by their big-endian timestamp so the store iterates them oldest first
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct SampleKey([u8; 8]);
+
+impl AsRef<[u8]> for SampleKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<u64> for SampleKey {
+    fn from(timestamp: u64) -> Self {
+        Self(timestamp.to_be_bytes())
+    }
+}
+
+#[derive(Clone)]
+pub struct DbChainQualityStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<SampleKey, ChainQualitySample>,
+}
+
+impl DbChainQualityStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::ChainQualitySamples.into()),
+        }
+    }
+
+    /// Returns all the stored samples, oldest first. Unreadable entries are skipped.
+    pub fn samples(&self) -> Vec<ChainQualitySample> {
+        self.access.iterator().filter_map(|item| item.ok().map(|(_, sample)| sample)).collect()
+    }
+
+    pub fn insert(&self, sample: ChainQualitySample) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), sample.timestamp.into(), sample)
+    }
+
+    pub fn remove(&self, timestamp: u64) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), timestamp.into())
+    }
+}
+
+/// A service sampling the blocks added to the selected chain at regular intervals and computing their red rate,
+/// the average block propagation delay inferred from the mergeset sizes and the miner concentration.
+///
+/// The samples of the last day are kept in memory and persisted in the meta database, so they survive a restart.
+/// No sample is taken while the node is syncing, its chain then reflecting the history of the DAG.
+pub struct ChainQualityMonitor {
+    consensus_manager: Arc<ConsensusManager>,
+    store: DbChainQualityStore,
+    samples: Mutex<VecDeque<ChainQualitySample>>,
+    tick_service: Arc<TickService>,
+}
+
+impl ChainQualityMonitor {
+    /// Creates the service and restores the samples persisted by former runs of the node
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>, tick_service: Arc<TickService>) -> Self {
+        let store = DbChainQualityStore::new(db);
+        let mut samples = VecDeque::from(store.samples());
+        while samples.len() > RETENTION {
+            let sample = samples.pop_front().unwrap();
+            store.remove(sample.timestamp).unwrap_or_else(|err| warn!("Failed to remove a chain-quality sample: {err}"));
+        }
+        Self { consensus_manager, store, samples: Mutex::new(samples), tick_service }
+    }
+
+    /// Returns the retained samples taken after `since`, oldest first
+    pub fn samples(&self, since: u64) -> Vec<RpcChainQualitySample> {
+        self.samples.lock().iter().filter(|sample| sample.timestamp > since).map(|&sample| sample.into()).collect()
+    }
+
+    fn push(&self, sample: ChainQualitySample) {
+        let mut samples = self.samples.lock();
+        if let Err(err) = self.store.insert(sample) {
+            warn!("Failed to persist a chain-quality sample: {err}");
+        }
+        samples.push_back(sample);
+        while samples.len() > RETENTION {
+            let evicted = samples.pop_front().unwrap();
+            self.store.remove(evicted.timestamp).unwrap_or_else(|err| warn!("Failed to remove a chain-quality sample: {err}"));
+        }
+    }
+
+    /// Computes a sample over the chain blocks added since `last_sink`, or `None` if the chain did not
+    /// progress or if some data is missing, typically because it got pruned
+    async fn sample(&self, session: &ConsensusProxy, last_sink: Hash) -> Option<ChainQualitySample> {
+        let chain_path = session.async_get_virtual_chain_from_block(last_sink).await.ok()?;
+        let added = &chain_path.added[chain_path.added.len().saturating_sub(MAX_SAMPLE_CHAIN_BLOCKS)..];
+        let (first, last) = (*added.first()?, *added.last()?);
+
+        let mut block_count = 0u64;
+        let mut red_block_count = 0u64;
+        let mut miners: HashMap<ScriptPublicKey, u64> = HashMap::new();
+        let mut from = None;
+        for &hash in added {
+            let ghostdag_data = session.async_get_ghostdag_data(hash).await.ok()?;
+            if hash == first {
+                from = Some(ghostdag_data.selected_parent);
+            }
+            block_count += (ghostdag_data.mergeset_blues.len() + ghostdag_data.mergeset_reds.len()) as u64;
+            red_block_count += ghostdag_data.mergeset_reds.len() as u64;
+            for merged in ghostdag_data.mergeset_blues.iter().chain(ghostdag_data.mergeset_reds.iter()) {
+                let Ok(block) = session.async_get_block(*merged).await else {
+                    continue;
+                };
+                if let Some(script_public_key) =
+                    block.transactions.first().and_then(|coinbase| coinbase_payload_script_public_key(&coinbase.payload))
+                {
+                    *miners.entry(script_public_key).or_default() += 1;
+                }
+            }
+        }
+
+        let chain_block_count = added.len() as u64;
+        let from_timestamp = session.async_get_header(from?).await.ok()?.timestamp;
+        let span = session.async_get_header(last).await.ok()?.timestamp.saturating_sub(from_timestamp);
+        // With a block rate λ and a propagation delay D, a chain block merges about 1 + λ·D blocks, so D is
+        // inferred from the average mergeset size B/C and the block rate B/span
+        let average_propagation_delay = match block_count {
+            0 => 0,
+            _ => {
+                ((block_count - chain_block_count.min(block_count)) as u128 * span as u128
+                    / (block_count as u128 * chain_block_count as u128)) as u64
+            }
+        };
+
+        let mined_count = miners.values().sum::<u64>();
+        let (top_miner_share, miner_concentration) = match mined_count {
+            0 => (0.0, 0.0),
+            total => {
+                let shares = miners.values().map(|&count| count as f64 / total as f64);
+                (shares.clone().fold(0.0, f64::max), shares.map(|share| share * share).sum())
+            }
+        };
+
+        Some(ChainQualitySample {
+            timestamp: unix_now(),
+            chain_block_count,
+            block_count,
+            red_block_count,
+            average_propagation_delay,
+            miner_count: miners.len() as u64,
+            top_miner_share,
+            miner_concentration,
+        })
+    }
+
+    pub async fn worker(self: &Arc<ChainQualityMonitor>) {
+        let mut last_sink = None;
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(SAMPLE_INTERVAL).await {
+                break;
+            }
+            let session = self.consensus_manager.consensus().unguarded_session();
+            if !session.async_is_nearly_synced().await {
+                last_sink = None;
+                continue;
+            }
+            let sink = session.async_get_sink().await;
+            // The first observation of a synced node only sets the start of the next sample
+            if let Some(last_sink) = last_sink.filter(|&last_sink| last_sink != sink) {
+                if let Some(sample) = self.sample(&session, last_sink).await {
+                    self.push(sample);
+                }
+            }
+            last_sink = Some(sink);
+        }
+
+        trace!("{} thread exiting", MONITOR);
+    }
+}
+
+// service trait implementation for ChainQualityMonitor
+impl AsyncService for ChainQualityMonitor {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MONITOR
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            info!("Chain-quality monitor starting with {} retained samples", self.samples.lock().len());
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MONITOR);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", MONITOR);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        block::Block,
+        errors::consensus::{ConsensusError, ConsensusResult},
+        header::Header,
+        subnets::SUBNETWORK_ID_COINBASE,
+        trusted::ExternalGhostdagData,
+        tx::Transaction,
+        ChainPath,
+    };
+    use kaspa_consensusmanager::ConsensusCtl;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use std::thread::JoinHandle;
+
+    /// A chain where `1` merges itself and `10`, then `2` merges itself and the red `11`, all mined by the miner
+    /// `1` except `11`, one second apart
+    struct ConsensusMock;
+
+    fn miner(hash: Hash) -> u8 {
+        match hash.to_le_u64()[0] {
+            11 => 2,
+            _ => 1,
+        }
+    }
+
+    impl ConsensusApi for ConsensusMock {
+        fn get_virtual_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
+            assert_eq!(hash, Hash::from_u64_word(0));
+            Ok(ChainPath { added: vec![Hash::from_u64_word(1), Hash::from_u64_word(2)], removed: vec![] })
+        }
+
+        fn get_ghostdag_data(&self, hash: Hash) -> ConsensusResult<ExternalGhostdagData> {
+            let (selected_parent, mergeset_blues, mergeset_reds) = match hash.to_le_u64()[0] {
+                1 => (0, vec![1, 10], vec![]),
+                2 => (1, vec![2], vec![11]),
+                _ => return Err(ConsensusError::MissingData(hash)),
+            };
+            Ok(ExternalGhostdagData {
+                blue_score: 0,
+                blue_work: Default::default(),
+                selected_parent: Hash::from_u64_word(selected_parent),
+                mergeset_blues: mergeset_blues.into_iter().map(Hash::from_u64_word).collect(),
+                mergeset_reds: mergeset_reds.into_iter().map(Hash::from_u64_word).collect(),
+                blues_anticone_sizes: Default::default(),
+            })
+        }
+
+        fn get_header(&self, hash: Hash) -> ConsensusResult<Arc<Header>> {
+            let mut header = Header::from_precomputed_hash(hash, vec![]);
+            header.timestamp = hash.to_le_u64()[0] * 1000;
+            Ok(Arc::new(header))
+        }
+
+        fn get_block(&self, hash: Hash) -> ConsensusResult<Block> {
+            // Blue score and subsidy, followed by the script public key version, length and script
+            let mut payload = vec![0u8; 16];
+            payload.extend_from_slice(&0u16.to_le_bytes());
+            payload.extend_from_slice(&[1, miner(hash)]);
+            let coinbase = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_COINBASE, 0, payload);
+            Ok(Block::new(Header::from_precomputed_hash(hash, vec![]), vec![coinbase]))
+        }
+    }
+
+    impl ConsensusCtl for ConsensusMock {
+        fn start(&self) -> Vec<JoinHandle<()>> {
+            vec![]
+        }
+
+        fn stop(&self) {}
+
+        fn make_active(&self) {}
+    }
+
+    fn monitor(db: &Arc<DB>) -> ChainQualityMonitor {
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(Arc::new(ConsensusMock)));
+        ChainQualityMonitor::new(consensus_manager, db.clone(), Arc::new(TickService::new()))
+    }
+
+    #[tokio::test]
+    async fn test_chain_quality_sample() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let monitor = monitor(&db);
+        let session = monitor.consensus_manager.consensus().unguarded_session();
+        let sample = monitor.sample(&session, Hash::from_u64_word(0)).await.unwrap();
+        assert_eq!((sample.chain_block_count, sample.block_count, sample.red_block_count), (2, 4, 1));
+        // 2 blocks merged on top of the chain blocks over a 2 seconds span
+        assert_eq!(sample.average_propagation_delay, 500);
+        assert_eq!(sample.miner_count, 2);
+        assert_eq!(sample.top_miner_share, 0.75);
+        assert_eq!(sample.miner_concentration, 0.625);
+
+        let sample = RpcChainQualitySample::from(sample);
+        assert_eq!(sample.red_rate, 0.25);
+        assert_eq!(RpcChainQualitySample::from(ChainQualitySample::default()).red_rate, 0.0);
+    }
+
+    #[test]
+    fn test_chain_quality_persistence() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let first_run = monitor(&db);
+        first_run.push(ChainQualitySample { timestamp: 1, chain_block_count: 1, ..Default::default() });
+        first_run.push(ChainQualitySample { timestamp: 2, chain_block_count: 2, ..Default::default() });
+        assert_eq!(first_run.samples(1).iter().map(|sample| sample.chain_block_count).collect::<Vec<_>>(), vec![2]);
+
+        // Samples survive a restart
+        let second_run = monitor(&db);
+        assert_eq!(second_run.samples(0).iter().map(|sample| sample.timestamp).collect::<Vec<_>>(), vec![1, 2]);
+
+        // Samples beyond the retention are evicted, from memory as well as from the store
+        for timestamp in 3..RETENTION as u64 + 3 {
+            second_run.push(ChainQualitySample { timestamp, ..Default::default() });
+        }
+        let samples = second_run.samples(0);
+        assert_eq!((samples.len(), samples[0].timestamp), (RETENTION, 3));
+        assert_eq!(second_run.store.samples().len(), RETENTION);
+        assert_eq!(monitor(&db).samples(0), samples);
+    }
+}
@@ -1,6 +1,13 @@
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter};
-use kaspa_notify::collector::CollectorFrom;
+use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, mining::MiningConverter};
+use kaspa_notify::{collector::CollectorFrom, converter::ConverterFrom};
+use kaspa_rpc_core::Notification;
 
 pub(crate) type CollectorFromConsensus = CollectorFrom<ConsensusConverter>;
 
 pub(crate) type CollectorFromIndex = CollectorFrom<IndexConverter>;
+
+pub(crate) type CollectorFromHealth = CollectorFrom<ConverterFrom<Notification, Notification>>;
+
+pub(crate) type CollectorFromMining = CollectorFrom<MiningConverter>;
+
+pub(crate) type CollectorFromJobs = CollectorFrom<ConverterFrom<Notification, Notification>>;
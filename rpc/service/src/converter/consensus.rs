@@ -3,10 +3,11 @@ use kaspa_addresses::Address;
 use kaspa_consensus_core::{
     block::Block,
     config::Config,
+    errors::consensus::ConsensusError,
     hashing::tx::hash,
     header::Header,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput},
-    ChainPath,
+    BlockHashMap, ChainPath,
 };
 use kaspa_consensus_notify::notification::{self as consensus_notify, Notification as ConsensusNotification};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
@@ -14,13 +15,55 @@ use kaspa_math::Uint256;
 use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcBlockVerbosity, RpcHash,
+    RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput,
+    RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
 };
 use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
+/// Chain context shared by the block conversions of a single RPC call.
+///
+/// Each entry is fetched from consensus on first use only, and then reused by the following conversions. This spares
+/// repeated lookups when converting batches of blocks, which typically share their merging chain blocks, and keeps the
+/// confirmations reported across the batch relative to the same virtual DAA score.
+#[derive(Default)]
+pub struct ConversionContext {
+    virtual_daa_score: Option<u64>,
+    chain_blocks: BlockHashMap<bool>,
+    chain_block_daa_scores: BlockHashMap<u64>,
+}
+
+impl ConversionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn virtual_daa_score(&mut self, consensus: &ConsensusProxy) -> u64 {
+        *self.virtual_daa_score.get_or_insert_with(|| consensus.get_virtual_daa_score())
+    }
+
+    async fn is_chain_block(&mut self, consensus: &ConsensusProxy, hash: RpcHash) -> RpcResult<bool> {
+        if let Some(is_chain_block) = self.chain_blocks.get(&hash) {
+            return Ok(*is_chain_block);
+        }
+        let is_chain_block = consensus.async_is_chain_block(hash).await?;
+        self.chain_blocks.insert(hash, is_chain_block);
+        Ok(is_chain_block)
+    }
+
+    /// Returns the DAA score of `hash`, a block known to be on the selected chain
+    async fn chain_block_daa_score(&mut self, consensus: &ConsensusProxy, hash: RpcHash) -> RpcResult<u64> {
+        if let Some(daa_score) = self.chain_block_daa_scores.get(&hash) {
+            return Ok(*daa_score);
+        }
+        let daa_score = consensus.async_get_header(hash).await?.daa_score;
+        self.chain_block_daa_scores.insert(hash, daa_score);
+        self.chain_blocks.insert(hash, true);
+        Ok(daa_score)
+    }
+}
+
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
     consensus_manager: Arc<ConsensusManager>,
@@ -43,39 +86,50 @@ impl ConsensusConverter {
         self.config.max_difficulty_target_f64 / target.as_f64()
     }
 
-    /// Converts a consensus [`Block`] into an [`RpcBlock`], optionally including transaction verbose data.
+    /// Converts a consensus [`Block`] into an [`RpcBlock`] with the level of detail set by `verbosity`.
     ///
     /// _GO-KASPAD: PopulateBlockWithVerboseData_
-    pub async fn get_block(
+    pub async fn get_block(&self, consensus: &ConsensusProxy, block: &Block, verbosity: RpcBlockVerbosity) -> RpcResult<RpcBlock> {
+        self.get_block_with_context(consensus, block, verbosity, &mut ConversionContext::new()).await
+    }
+
+    /// Converts a consensus [`Block`] like [`Self::get_block`] does, sharing the chain context lookups with the other
+    /// conversions of the same call through `context`
+    pub async fn get_block_with_context(
         &self,
         consensus: &ConsensusProxy,
         block: &Block,
-        include_transactions: bool,
-        include_transaction_verbose_data: bool,
+        verbosity: RpcBlockVerbosity,
+        context: &mut ConversionContext,
     ) -> RpcResult<RpcBlock> {
         let hash = block.hash();
         let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
         let block_status = consensus.async_get_block_status(hash).await.unwrap();
         let children = consensus.async_get_block_children(hash).await.unwrap_or_default();
-        let is_chain_block = consensus.async_is_chain_block(hash).await?;
+        let is_chain_block = context.is_chain_block(consensus, hash).await?;
+        let confirmations = self.get_block_confirmations(consensus, hash, Some(block.header.daa_score), context).await?;
         let verbose_data = Some(RpcBlockVerboseData {
             hash,
             difficulty: self.get_difficulty_ratio(block.header.bits),
             selected_parent_hash: ghostdag_data.selected_parent,
-            transaction_ids: block.transactions.iter().map(|x| x.id()).collect(),
+            transaction_ids: match verbosity.includes_transaction_ids() {
+                true => block.transactions.iter().map(|x| x.id()).collect(),
+                false => vec![],
+            },
             is_header_only: block_status.is_header_only(),
             blue_score: ghostdag_data.blue_score,
             children_hashes: children,
             merge_set_blues_hashes: ghostdag_data.mergeset_blues,
             merge_set_reds_hashes: ghostdag_data.mergeset_reds,
             is_chain_block,
+            confirmations,
         });
 
-        let transactions = if include_transactions {
+        let transactions = if verbosity.includes_transactions() {
             block
                 .transactions
                 .iter()
-                .map(|x| self.get_transaction(consensus, x, Some(&block.header), include_transaction_verbose_data))
+                .map(|x| self.get_transaction(consensus, x, Some(&block.header), confirmations, true))
                 .collect::<Vec<_>>()
         } else {
             vec![]
@@ -84,10 +138,50 @@ impl ConsensusConverter {
         Ok(RpcBlock { header: (*block.header).clone(), transactions, verbose_data })
     }
 
+    /// Returns the virtual DAA score minus the DAA score of the chain block accepting the block `hash`, or zero if the
+    /// block was not merged by the selected chain yet. `daa_score` is the DAA score of the block, if already known.
+    ///
+    /// This is the single definition of the confirmations reported by the RPC: the transactions accepted along with a
+    /// block share its confirmations, and a chain block accepts itself, so the confirmations of a transaction are those
+    /// of its accepting block.
+    pub async fn get_block_confirmations(
+        &self,
+        consensus: &ConsensusProxy,
+        hash: RpcHash,
+        daa_score: Option<u64>,
+        context: &mut ConversionContext,
+    ) -> RpcResult<u64> {
+        let accepting_daa_score = match consensus.async_get_current_block_color(hash).await {
+            Ok(Some(color)) => context.chain_block_daa_score(consensus, color.merging_block).await?,
+            Ok(None) => return Ok(0),
+            // The chain block accepting a block below the pruning point is not indexed anymore. Such a block is
+            // deeply confirmed anyway, so its own DAA score is a fair approximation. Once the block itself is pruned,
+            // as may be the accepting block of a transaction kept by the txindex, the pruning point DAA score bounds
+            // the confirmations from below
+            Err(ConsensusError::BlockNotInPruningPointFuture(_) | ConsensusError::HeaderNotFound(_)) => match daa_score {
+                Some(daa_score) => daa_score,
+                None => match consensus.async_get_header(hash).await {
+                    Ok(header) => header.daa_score,
+                    Err(ConsensusError::HeaderNotFound(_)) => {
+                        consensus.async_get_header(consensus.async_pruning_point().await).await?.daa_score
+                    }
+                    Err(err) => return Err(err.into()),
+                },
+            },
+            Err(err) => return Err(err.into()),
+        };
+        Ok(context.virtual_daa_score(consensus).saturating_sub(accepting_daa_score))
+    }
+
     pub fn get_mempool_entry(&self, consensus: &ConsensusProxy, transaction: &MutableTransaction) -> RpcMempoolEntry {
         let is_orphan = !transaction.is_fully_populated();
-        let rpc_transaction = self.get_transaction(consensus, &transaction.tx, None, true);
-        RpcMempoolEntry::new(transaction.calculated_fee.unwrap_or_default(), rpc_transaction, is_orphan)
+        let rpc_transaction = self.get_transaction(consensus, &transaction.tx, None, 0, true);
+        // The contextual mass is only set once the transaction is fully validated
+        let mass = match transaction.tx.mass() {
+            0 => transaction.calculated_compute_mass.unwrap_or_default(),
+            mass => mass,
+        };
+        RpcMempoolEntry::new(transaction.calculated_fee.unwrap_or_default(), rpc_transaction, is_orphan, mass)
     }
 
     pub fn get_mempool_entries_by_address(
@@ -112,6 +206,7 @@ impl ConsensusConverter {
     }
 
     /// Converts a consensus [`Transaction`] into an [`RpcTransaction`], optionally including verbose data.
+    /// `confirmations` are the ones of the block `header`, if any.
     ///
     /// _GO-KASPAD: PopulateTransactionWithVerboseData
     pub fn get_transaction(
@@ -119,6 +214,7 @@ impl ConsensusConverter {
         consensus: &ConsensusProxy,
         transaction: &Transaction,
         header: Option<&Header>,
+        confirmations: u64,
         include_verbose_data: bool,
     ) -> RpcTransaction {
         if include_verbose_data {
@@ -129,6 +225,7 @@ impl ConsensusConverter {
                 // TODO: make block_hash an option
                 block_hash: header.map_or_else(RpcHash::default, |x| x.hash),
                 block_time: header.map_or(0, |x| x.timestamp),
+                confirmations,
             });
             RpcTransaction {
                 version: transaction.version,
@@ -189,7 +286,9 @@ impl Converter for ConsensusConverter {
             consensus_notify::Notification::BlockAdded(msg) => {
                 let session = self.consensus_manager.consensus().unguarded_session();
                 // If get_block fails, rely on the infallible From implementation which will lack verbose data
-                let block = Arc::new(self.get_block(&session, &msg.block, true, true).await.unwrap_or_else(|_| (&msg.block).into()));
+                let block = Arc::new(
+                    self.get_block(&session, &msg.block, RpcBlockVerbosity::Full).await.unwrap_or_else(|_| (&msg.block).into()),
+                );
                 Notification::BlockAdded(BlockAddedNotification { block })
             }
             _ => (&incoming).into(),
@@ -202,3 +301,187 @@ impl Debug for ConsensusConverter {
         f.debug_struct("ConsensusConverter").field("consensus_manager", &"").field("config", &self.config).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::{BlockColor, ConsensusApi},
+        blockstatus::BlockStatus,
+        config::params::MAINNET_PARAMS,
+        errors::consensus::ConsensusResult,
+        subnets::SUBNETWORK_ID_COINBASE,
+        trusted::ExternalGhostdagData,
+        tx::ScriptPublicKey,
+    };
+    use kaspa_consensusmanager::ConsensusCtl;
+    use kaspa_hashes::Hash;
+    use std::{
+        sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+        thread::JoinHandle,
+    };
+
+    /// A DAG where the chain blocks `1` and `3` respectively merge the blocks `1` and `2`, `4` is not merged yet and
+    /// `5` is below the pruning point `8` while `6` is pruned
+    struct ConsensusMock {
+        virtual_daa_score: AtomicU64,
+        header_lookups: AtomicUsize,
+        chain_lookups: AtomicUsize,
+    }
+
+    impl ConsensusMock {
+        fn new(virtual_daa_score: u64) -> Self {
+            Self { virtual_daa_score: virtual_daa_score.into(), header_lookups: Default::default(), chain_lookups: Default::default() }
+        }
+    }
+
+    fn daa_score(hash: Hash) -> u64 {
+        hash.to_le_u64()[0] * 10
+    }
+
+    impl ConsensusApi for ConsensusMock {
+        fn calculate_transaction_compute_mass(&self, _transaction: &Transaction) -> u64 {
+            1000
+        }
+
+        fn get_virtual_daa_score(&self) -> u64 {
+            self.virtual_daa_score.load(Ordering::Relaxed)
+        }
+
+        fn get_header(&self, hash: Hash) -> ConsensusResult<Arc<Header>> {
+            self.header_lookups.fetch_add(1, Ordering::Relaxed);
+            if hash == Hash::from_u64_word(6) {
+                return Err(ConsensusError::HeaderNotFound(hash));
+            }
+            let mut header = Header::from_precomputed_hash(hash, vec![]);
+            header.daa_score = daa_score(hash);
+            Ok(Arc::new(header))
+        }
+
+        fn get_ghostdag_data(&self, hash: Hash) -> ConsensusResult<ExternalGhostdagData> {
+            Ok(ExternalGhostdagData {
+                blue_score: daa_score(hash),
+                blue_work: Default::default(),
+                selected_parent: Hash::from_u64_word(0),
+                mergeset_blues: vec![],
+                mergeset_reds: vec![],
+                blues_anticone_sizes: Default::default(),
+            })
+        }
+
+        fn get_block_children(&self, _hash: Hash) -> Option<Vec<Hash>> {
+            Some(vec![])
+        }
+
+        fn get_block_status(&self, _hash: Hash) -> Option<BlockStatus> {
+            Some(BlockStatus::StatusUTXOValid)
+        }
+
+        fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
+            self.chain_lookups.fetch_add(1, Ordering::Relaxed);
+            Ok([1, 3].map(Hash::from_u64_word).contains(&hash))
+        }
+
+        fn get_current_block_color(&self, hash: Hash) -> ConsensusResult<Option<BlockColor>> {
+            match hash.to_le_u64()[0] {
+                1 => Ok(Some(BlockColor { is_blue: true, merging_block: Hash::from_u64_word(1) })),
+                2 | 3 => Ok(Some(BlockColor { is_blue: true, merging_block: Hash::from_u64_word(3) })),
+                5 => Err(ConsensusError::BlockNotInPruningPointFuture(hash)),
+                6 => Err(ConsensusError::HeaderNotFound(hash)),
+                _ => Ok(None),
+            }
+        }
+
+        fn pruning_point(&self) -> Hash {
+            Hash::from_u64_word(8)
+        }
+    }
+
+    impl ConsensusCtl for ConsensusMock {
+        fn start(&self) -> Vec<JoinHandle<()>> {
+            vec![]
+        }
+
+        fn stop(&self) {}
+
+        fn make_active(&self) {}
+    }
+
+    fn block(hash: u64) -> Block {
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(hash), vec![]);
+        header.daa_score = daa_score(header.hash);
+        let coinbase = Transaction::new(
+            0,
+            vec![],
+            vec![TransactionOutput::new(1, ScriptPublicKey::default())],
+            0,
+            SUBNETWORK_ID_COINBASE,
+            0,
+            vec![],
+        );
+        Block::new(header, vec![coinbase])
+    }
+
+    fn converter(consensus: Arc<ConsensusMock>) -> (ConsensusConverter, ConsensusProxy) {
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(consensus));
+        let session = consensus_manager.consensus().unguarded_session();
+        (ConsensusConverter::new(consensus_manager, Arc::new(Config::new(MAINNET_PARAMS))), session)
+    }
+
+    fn confirmations(block: &RpcBlock) -> u64 {
+        block.verbose_data.as_ref().unwrap().confirmations
+    }
+
+    #[tokio::test]
+    async fn test_block_confirmations() {
+        let (converter, session) = converter(Arc::new(ConsensusMock::new(100)));
+        for (hash, expected) in [(1, 90), (2, 70), (3, 70), (4, 0), (5, 50)] {
+            let block = converter.get_block(&session, &block(hash), RpcBlockVerbosity::Full).await.unwrap();
+            assert_eq!(confirmations(&block), expected, "block {hash}");
+            // Transactions share the confirmations of their block
+            let verbose_data = block.transactions[0].verbose_data.as_ref().unwrap();
+            assert_eq!(verbose_data.confirmations, expected, "block {hash}");
+            assert_eq!(verbose_data.block_hash, Hash::from_u64_word(hash));
+        }
+
+        // Transactions accepted by a chain block get the confirmations of the blocks it accepts, and those accepted by a
+        // pruned block at least the confirmations of the pruning point
+        for (hash, expected) in [(3, 70), (5, 50), (6, 20)] {
+            let confirmations = converter
+                .get_block_confirmations(&session, Hash::from_u64_word(hash), None, &mut ConversionContext::new())
+                .await
+                .unwrap();
+            assert_eq!(confirmations, expected, "accepting block {hash}");
+        }
+
+        // Mempool transactions are not confirmed
+        let entry = converter.get_mempool_entry(&session, &MutableTransaction::from_tx(block(1).transactions[0].clone()));
+        assert_eq!(entry.transaction.verbose_data.unwrap().confirmations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conversion_context() {
+        let consensus = Arc::new(ConsensusMock::new(100));
+        let (converter, session) = converter(consensus.clone());
+        let lookups = || (consensus.header_lookups.load(Ordering::Relaxed), consensus.chain_lookups.load(Ordering::Relaxed));
+
+        // The blocks merged by the same chain block only fetch its header once, which also answers whether it is a chain block
+        let mut context = ConversionContext::new();
+        let mut blocks = vec![];
+        for hash in [2, 3] {
+            let block = converter.get_block_with_context(&session, &block(hash), RpcBlockVerbosity::Full, &mut context).await;
+            blocks.push(block.unwrap());
+            consensus.virtual_daa_score.store(200, Ordering::Relaxed);
+        }
+        assert_eq!(lookups(), (1, 1));
+        assert!(!blocks[0].verbose_data.as_ref().unwrap().is_chain_block);
+        assert!(blocks[1].verbose_data.as_ref().unwrap().is_chain_block);
+        // Confirmations across the conversions of a context are relative to the same virtual DAA score
+        assert_eq!(blocks.iter().map(confirmations).collect::<Vec<_>>(), [70, 70]);
+
+        // Independent conversions fetch the chain context again
+        let block = converter.get_block(&session, &block(2), RpcBlockVerbosity::Full).await.unwrap();
+        assert_eq!(lookups(), (2, 2));
+        assert_eq!(confirmations(&block), 170);
+    }
+}
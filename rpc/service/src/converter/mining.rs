@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use kaspa_mining::model::evicted_txs::EvictedTransactions;
+use kaspa_notify::converter::Converter;
+use kaspa_rpc_core::{MempoolTransactionsEvictedNotification, Notification};
+
+/// Conversion of mining events to rpc_core notifications
+#[derive(Debug, Default)]
+pub struct MiningConverter {}
+
+impl MiningConverter {
+    pub fn get_mempool_transactions_evicted_notification(
+        &self,
+        evicted: EvictedTransactions,
+    ) -> MempoolTransactionsEvictedNotification {
+        MempoolTransactionsEvictedNotification { transaction_ids: evicted.transaction_ids, reason: evicted.trigger.to_string() }
+    }
+}
+
+#[async_trait]
+impl Converter for MiningConverter {
+    type Incoming = EvictedTransactions;
+    type Outgoing = Notification;
+
+    async fn convert(&self, incoming: EvictedTransactions) -> Notification {
+        Notification::MempoolTransactionsEvicted(self.get_mempool_transactions_evicted_notification(incoming))
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use kaspa_p2p_flows::flow_context::FlowContext;
 use kaspa_p2p_lib::{Peer, PeerKey};
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_rpc_core::{RpcConnectionInfo, RpcMessageCounts, RpcPeerInfo};
 
 pub struct ProtocolConverter {
     flow_context: Arc<FlowContext>,
@@ -32,4 +32,27 @@ impl ProtocolConverter {
         let ibd_peer_key = self.flow_context.ibd_peer_key();
         peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key)).collect()
     }
+
+    pub fn get_connection_info(&self, peer: &Peer) -> RpcConnectionInfo {
+        let traffic = peer.traffic();
+        let mut messages = traffic
+            .messages
+            .iter()
+            .map(|(message_type, counts)| RpcMessageCounts {
+                message_type: format!("{:?}", message_type),
+                sent: counts.sent,
+                received: counts.received,
+            })
+            .collect::<Vec<_>>();
+        messages.sort_by(|a, b| a.message_type.cmp(&b.message_type));
+        RpcConnectionInfo {
+            id: peer.identity(),
+            address: peer.net_address().into(),
+            is_outbound: peer.is_outbound(),
+            connection_age: peer.time_connected(),
+            bytes_sent: traffic.bytes_sent,
+            bytes_received: traffic.bytes_received,
+            messages,
+        }
+    }
 }
@@ -0,0 +1,380 @@
+//! Monitoring of abnormal DAG conditions and of the index resyncs
+
+use kaspa_consensus_core::{config::Config, BlueWorkType};
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    time::unix_now,
+    trace, warn,
+};
+use kaspa_index_core::resync::ResyncProgressSnapshot;
+use kaspa_notify::collector::CollectorNotificationReceiver;
+use kaspa_rpc_core::{
+    HealthAlertNotification, IndexResyncProgressNotification, Notification, RpcHealthAlert, RpcHealthAlertKind,
+    RpcIndexResyncProgress, RpcJobKind,
+};
+use kaspa_txindex::api::TxIndexProxy;
+use kaspa_utils::channel::Channel;
+use kaspa_utxoindex::api::UtxoIndexProxy;
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+const MONITOR: &str = "health-monitor";
+
+/// Interval between two checks of the DAG conditions
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of selected chain blocks the red rate is computed over
+const RED_RATE_WINDOW_SIZE: usize = 600;
+
+/// Rate of red blocks over the window above which an alert is raised
+const RED_RATE_THRESHOLD: f64 = 0.1;
+
+/// Number of expected block intervals without any sink blue work increase above which an alert is raised
+const STAGNATION_BLOCK_INTERVALS: u64 = 60;
+
+/// Tracks the last observed increase of the sink blue work
+struct BlueWorkProgress {
+    blue_work: BlueWorkType,
+    timestamp: u64,
+}
+
+/// A service periodically checking the DAG for abnormal conditions (tip count above threshold,
+/// red rate spikes, blue work stagnation).
+///
+/// Every condition starting or ceasing to be observed is logged and sent as a [`Notification::HealthAlert`]
+/// to the notification channel, meant to be collected by the rpc-core notifier.
+///
+/// The progress of the running index resyncs is sent as well, as [`Notification::IndexResyncProgress`], at every check.
+pub struct HealthMonitor {
+    consensus_manager: Arc<ConsensusManager>,
+    config: Arc<Config>,
+    notification_channel: Channel<Notification>,
+    alerts: Mutex<HashMap<RpcHealthAlertKind, RpcHealthAlert>>,
+    utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    tick_service: Arc<TickService>,
+}
+
+impl HealthMonitor {
+    pub fn new(consensus_manager: Arc<ConsensusManager>, config: Arc<Config>, tick_service: Arc<TickService>) -> Self {
+        Self {
+            consensus_manager,
+            config,
+            notification_channel: Channel::default(),
+            alerts: Default::default(),
+            utxoindex: None,
+            txindex: None,
+            tick_service,
+        }
+    }
+
+    /// Monitors the resyncs of the provided indexes
+    pub fn with_indexes(self, utxoindex: Option<UtxoIndexProxy>, txindex: Option<TxIndexProxy>) -> Self {
+        Self { utxoindex, txindex, ..self }
+    }
+
+    pub fn notification_receiver(&self) -> CollectorNotificationReceiver<Notification> {
+        self.notification_channel.receiver()
+    }
+
+    /// Returns the currently active alerts, oldest first
+    pub fn alerts(&self) -> Vec<RpcHealthAlert> {
+        let mut alerts = self.alerts.lock().values().cloned().collect::<Vec<_>>();
+        alerts.sort_by_key(|alert| alert.raised_at);
+        alerts
+    }
+
+    /// Returns the progress of the index resyncs currently running
+    pub fn index_resyncs(&self) -> Vec<RpcIndexResyncProgress> {
+        let now = unix_now();
+        [
+            (RpcJobKind::UtxoIndexResync, self.utxoindex.as_ref().and_then(|utxoindex| utxoindex.resync_progress())),
+            (RpcJobKind::TxIndexResync, self.txindex.as_ref().and_then(|txindex| txindex.resync_progress())),
+        ]
+        .into_iter()
+        .filter_map(|(kind, snapshot)| snapshot.map(|snapshot| index_resync_progress(kind, snapshot, now)))
+        .collect()
+    }
+
+    /// Notifies the progress of the running index resyncs and the completion of the ones observed running
+    /// at the previous check
+    fn check_index_resyncs(&self, running: &mut HashMap<RpcJobKind, RpcIndexResyncProgress>) {
+        let resyncs = self.index_resyncs();
+        let completed =
+            running.keys().filter(|kind| !resyncs.iter().any(|progress| progress.kind == **kind)).copied().collect::<Vec<_>>();
+        for kind in completed {
+            let mut progress = running.remove(&kind).unwrap();
+            progress.percent = 100.0;
+            progress.eta = Some(0);
+            self.notify_index_resync_progress(IndexResyncProgressNotification { progress, completed: true });
+        }
+        for progress in resyncs {
+            running.insert(progress.kind, progress.clone());
+            self.notify_index_resync_progress(IndexResyncProgressNotification { progress, completed: false });
+        }
+    }
+
+    fn notify_index_resync_progress(&self, notification: IndexResyncProgressNotification) {
+        // The channel only gets closed on exit
+        let _ = self.notification_channel.try_send(Notification::IndexResyncProgress(notification));
+    }
+
+    fn tip_count_threshold(&self) -> f64 {
+        (self.config.max_block_parents as u64 * 2) as f64
+    }
+
+    fn stagnation_threshold(&self) -> f64 {
+        (self.config.target_time_per_block * STAGNATION_BLOCK_INTERVALS) as f64 / 1000.0
+    }
+
+    fn red_rate_window_size(&self) -> usize {
+        RED_RATE_WINDOW_SIZE.min(self.config.pruning_depth as usize)
+    }
+
+    /// Raises or resolves the alert of kind `kind` depending on `value` crossing `threshold`
+    fn update(&self, kind: RpcHealthAlertKind, value: f64, threshold: f64) {
+        let mut alerts = self.alerts.lock();
+        let notification = match (value > threshold, alerts.contains_key(&kind)) {
+            (true, false) => {
+                let alert = RpcHealthAlert::new(kind, value, threshold, unix_now());
+                warn!("Health alert raised: {}", alert);
+                alerts.insert(kind, alert.clone());
+                HealthAlertNotification { alert, resolved: false }
+            }
+            (true, true) => {
+                alerts.get_mut(&kind).unwrap().value = value;
+                return;
+            }
+            (false, true) => {
+                let mut alert = alerts.remove(&kind).unwrap();
+                alert.value = value;
+                info!("Health alert resolved: {}", alert.kind);
+                HealthAlertNotification { alert, resolved: true }
+            }
+            (false, false) => return,
+        };
+        // The channel only gets closed on exit
+        let _ = self.notification_channel.try_send(Notification::HealthAlert(notification));
+    }
+
+    async fn check(&self, progress: &mut Option<BlueWorkProgress>) {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let is_nearly_synced = session.async_is_nearly_synced().await;
+
+        let tip_count = session.async_get_tips_len().await;
+        self.update(RpcHealthAlertKind::TipCountAboveThreshold, tip_count as f64, self.tip_count_threshold());
+
+        // The red rate of a syncing node reflects the history of the DAG rather than its current condition
+        if is_nearly_synced {
+            let stats = session.async_get_dag_stats(self.red_rate_window_size()).await;
+            let red_rate = match stats.merged_block_count {
+                0 => 0.0,
+                count => stats.red_block_count as f64 / count as f64,
+            };
+            self.update(RpcHealthAlertKind::RedRateSpike, red_rate, RED_RATE_THRESHOLD);
+        }
+
+        // Blue work stagnation is tracked from the first time the node is nearly synced only, since
+        // a stalled network makes the node fall out of sync
+        let sink = session.async_get_sink().await;
+        let Ok(header) = session.async_get_header(sink).await else {
+            return;
+        };
+        let now = unix_now();
+        match progress {
+            Some(progress) if header.blue_work > progress.blue_work => {
+                progress.blue_work = header.blue_work;
+                progress.timestamp = now;
+            }
+            Some(_) => {}
+            None if is_nearly_synced => *progress = Some(BlueWorkProgress { blue_work: header.blue_work, timestamp: now }),
+            None => return,
+        }
+        let stagnation = now.saturating_sub(progress.as_ref().unwrap().timestamp) as f64 / 1000.0;
+        self.update(RpcHealthAlertKind::BlueWorkStagnation, stagnation, self.stagnation_threshold());
+    }
+
+    pub async fn worker(self: &Arc<HealthMonitor>) {
+        let mut progress = None;
+        let mut index_resyncs = HashMap::new();
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(CHECK_INTERVAL).await {
+                break;
+            }
+            self.check_index_resyncs(&mut index_resyncs);
+            self.check(&mut progress).await;
+        }
+
+        trace!("{} thread exiting", MONITOR);
+    }
+}
+
+fn index_resync_progress(kind: RpcJobKind, snapshot: ResyncProgressSnapshot, now: u64) -> RpcIndexResyncProgress {
+    let eta = snapshot.eta(now).map(|eta| eta.as_secs());
+    RpcIndexResyncProgress::new(kind, snapshot.started_at, snapshot.processed, snapshot.percent(), eta)
+}
+
+// service trait implementation for HealthMonitor
+impl AsyncService for HealthMonitor {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MONITOR
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MONITOR);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.notification_channel.close();
+            trace!("{} stopped", MONITOR);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::{ConsensusApi, DagStats},
+        config::params::MAINNET_PARAMS,
+        errors::consensus::ConsensusResult,
+        header::Header,
+    };
+    use kaspa_consensusmanager::ConsensusCtl;
+    use kaspa_hashes::Hash;
+    use std::thread::JoinHandle;
+
+    #[derive(Default)]
+    struct ConsensusState {
+        is_nearly_synced: bool,
+        tips_len: usize,
+        red_block_count: u64,
+        blue_work: u64,
+    }
+
+    #[derive(Default)]
+    struct ConsensusMock {
+        state: Mutex<ConsensusState>,
+    }
+
+    impl ConsensusApi for ConsensusMock {
+        fn is_nearly_synced(&self) -> bool {
+            self.state.lock().is_nearly_synced
+        }
+
+        fn get_tips_len(&self) -> usize {
+            self.state.lock().tips_len
+        }
+
+        fn get_dag_stats(&self, _window_size: usize) -> DagStats {
+            DagStats { merged_block_count: 100, red_block_count: self.state.lock().red_block_count, ..Default::default() }
+        }
+
+        fn get_sink(&self) -> Hash {
+            Hash::default()
+        }
+
+        fn get_header(&self, hash: Hash) -> ConsensusResult<Arc<Header>> {
+            let mut header = Header::from_precomputed_hash(hash, vec![]);
+            header.blue_work = self.state.lock().blue_work.into();
+            Ok(Arc::new(header))
+        }
+    }
+
+    impl ConsensusCtl for ConsensusMock {
+        fn start(&self) -> Vec<JoinHandle<()>> {
+            vec![]
+        }
+
+        fn stop(&self) {}
+
+        fn make_active(&self) {}
+    }
+
+    fn received_alerts(receiver: &CollectorNotificationReceiver<Notification>) -> Vec<HealthAlertNotification> {
+        std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|notification| match notification {
+                Notification::HealthAlert(notification) => notification,
+                notification => panic!("unexpected notification {notification:?}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_health_alerts() {
+        let consensus = Arc::new(ConsensusMock::default());
+        let monitor = HealthMonitor::new(
+            Arc::new(ConsensusManager::from_consensus(consensus.clone())),
+            Arc::new(Config::new(MAINNET_PARAMS)),
+            Arc::new(TickService::new()),
+        );
+        let receiver = monitor.notification_receiver();
+        let mut progress = None;
+
+        // A healthy DAG raises no alert
+        *consensus.state.lock() = ConsensusState { is_nearly_synced: true, tips_len: 5, ..Default::default() };
+        monitor.check(&mut progress).await;
+        assert!(monitor.alerts().is_empty());
+        assert!(received_alerts(&receiver).is_empty());
+
+        // Crossing the tip count threshold raises an alert, which only gets its value updated while still crossing it
+        let threshold = monitor.tip_count_threshold();
+        consensus.state.lock().tips_len = 25;
+        monitor.check(&mut progress).await;
+        consensus.state.lock().tips_len = 30;
+        monitor.check(&mut progress).await;
+        let notifications = received_alerts(&receiver);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].alert.kind, RpcHealthAlertKind::TipCountAboveThreshold);
+        assert_eq!((notifications[0].alert.value, notifications[0].alert.threshold), (25.0, threshold));
+        assert!(!notifications[0].resolved);
+        assert_eq!(monitor.alerts().len(), 1);
+        assert_eq!(monitor.alerts()[0].value, 30.0);
+
+        // The red rate is only checked while nearly synced
+        *consensus.state.lock() = ConsensusState { is_nearly_synced: false, tips_len: 30, red_block_count: 20, blue_work: 0 };
+        monitor.check(&mut progress).await;
+        assert!(received_alerts(&receiver).is_empty());
+        consensus.state.lock().is_nearly_synced = true;
+        monitor.check(&mut progress).await;
+        let notifications = received_alerts(&receiver);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].alert.kind, RpcHealthAlertKind::RedRateSpike);
+        assert_eq!(notifications[0].alert.value, 0.2);
+
+        // Going back below the thresholds resolves the alerts
+        *consensus.state.lock() = ConsensusState { is_nearly_synced: true, tips_len: 5, ..Default::default() };
+        monitor.check(&mut progress).await;
+        let notifications = received_alerts(&receiver);
+        assert_eq!(notifications.len(), 2);
+        assert!(notifications.iter().all(|notification| notification.resolved));
+        assert!(monitor.alerts().is_empty());
+
+        // The sink blue work not increasing for long raises an alert, resolved as soon as it increases
+        progress.as_mut().unwrap().timestamp = unix_now() - 3_600_000;
+        monitor.check(&mut progress).await;
+        let notifications = received_alerts(&receiver);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].alert.kind, RpcHealthAlertKind::BlueWorkStagnation);
+        assert!(notifications[0].alert.value >= 3600.0);
+        consensus.state.lock().blue_work = 1;
+        monitor.check(&mut progress).await;
+        let notifications = received_alerts(&receiver);
+        assert_eq!(notifications.len(), 1);
+        assert!(notifications[0].resolved);
+    }
+}
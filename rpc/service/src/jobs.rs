@@ -0,0 +1,136 @@
+//! Long-running node operations run in the background as jobs
+
+use crate::tickets::TicketRegistry;
+use kaspa_core::{info, time::unix_now, warn};
+use kaspa_notify::collector::CollectorNotificationReceiver;
+use kaspa_rpc_core::{JobCompletedNotification, Notification, RpcError, RpcJob, RpcJobKind, RpcJobState, RpcResult};
+use kaspa_utils::channel::Channel;
+use parking_lot::Mutex;
+use std::{collections::HashSet, future::Future, sync::Arc};
+
+/// Maximum number of jobs kept at once, the oldest ones being evicted first
+pub const MAX_JOBS: usize = 256;
+
+/// Runs long-running node operations (index resyncs) in the background instead of blocking the RPC call
+/// which requested them.
+///
+/// Each job is given an id by which its status can be polled until the job gets evicted by newer ones.
+/// Jobs of a same kind are exclusive. Every job completion or failure is sent as a [`Notification::JobCompleted`]
+/// to the notification channel, meant to be collected by the rpc-core notifier.
+pub struct JobRegistry {
+    jobs: TicketRegistry<RpcJob>,
+    running: Mutex<HashSet<RpcJobKind>>,
+    notification_channel: Channel<Notification>,
+}
+
+impl JobRegistry {
+    pub fn new(capacity: usize) -> Self {
+        Self { jobs: TicketRegistry::new(capacity), running: Default::default(), notification_channel: Channel::default() }
+    }
+
+    pub fn notification_receiver(&self) -> CollectorNotificationReceiver<Notification> {
+        self.notification_channel.receiver()
+    }
+
+    /// Spawns `task` as a job of `kind` and returns the job id
+    pub fn start<F>(self: &Arc<Self>, kind: RpcJobKind, task: F) -> RpcResult<u64>
+    where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        if !self.running.lock().insert(kind) {
+            return Err(RpcError::JobAlreadyRunning(kind));
+        }
+        let started_at = unix_now();
+        let job = RpcJob::new(self.jobs.register(|id| RpcJob::new(id, kind, started_at)), kind, started_at);
+        info!("Starting {}", job);
+
+        let id = job.id;
+        let this = self.clone();
+        tokio::spawn(async move {
+            let result = task.await;
+            this.finish(job, result);
+        });
+        Ok(id)
+    }
+
+    fn finish(&self, mut job: RpcJob, result: Result<(), String>) {
+        job.finished_at = Some(unix_now());
+        match result {
+            Ok(()) => {
+                job.state = RpcJobState::Completed;
+                info!("{}", job);
+            }
+            Err(err) => {
+                job.state = RpcJobState::Failed;
+                job.error = Some(err);
+                warn!("{}", job);
+            }
+        }
+        self.jobs.update(job.id, |entry| *entry = job.clone());
+        self.running.lock().remove(&job.kind);
+        let _ = self.notification_channel.try_send(Notification::JobCompleted(JobCompletedNotification { job }));
+    }
+
+    pub fn get(&self, id: u64) -> Option<RpcJob> {
+        self.jobs.get(id)
+    }
+
+    pub fn close(&self) {
+        self.notification_channel.close();
+    }
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self::new(MAX_JOBS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    async fn completed_job(receiver: &CollectorNotificationReceiver<Notification>) -> RpcJob {
+        match receiver.recv().await.unwrap() {
+            Notification::JobCompleted(notification) => notification.job,
+            notification => panic!("unexpected notification {notification:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_registry() {
+        let jobs = Arc::new(JobRegistry::new(2));
+        let receiver = jobs.notification_receiver();
+
+        let (sender, task_receiver) = oneshot::channel();
+        let id = jobs.start(RpcJobKind::UtxoIndexResync, async move { task_receiver.await.unwrap() }).unwrap();
+        assert_eq!(id, 1);
+        let job = jobs.get(id).unwrap();
+        assert_eq!((job.kind, job.state, job.finished_at), (RpcJobKind::UtxoIndexResync, RpcJobState::Running, None));
+
+        // Jobs of a same kind are exclusive, unlike jobs of distinct kinds
+        assert!(matches!(
+            jobs.start(RpcJobKind::UtxoIndexResync, async { Ok(()) }),
+            Err(RpcError::JobAlreadyRunning(RpcJobKind::UtxoIndexResync))
+        ));
+        let failing_id = jobs.start(RpcJobKind::TxIndexResync, async { Err("no txindex".to_owned()) }).unwrap();
+        let job = completed_job(&receiver).await;
+        assert_eq!((job.id, job.state, job.error.as_deref()), (failing_id, RpcJobState::Failed, Some("no txindex")));
+        assert_eq!(jobs.get(failing_id), Some(job));
+
+        sender.send(Ok(())).unwrap();
+        let job = completed_job(&receiver).await;
+        assert_eq!((job.id, job.state, job.error.as_deref()), (id, RpcJobState::Completed, None));
+        assert!(job.is_finished() && job.finished_at.is_some());
+
+        // Once finished, a job of the same kind can be started again, evicting the oldest job
+        let id = jobs.start(RpcJobKind::UtxoIndexResync, async { Ok(()) }).unwrap();
+        assert_eq!(completed_job(&receiver).await.id, id);
+        assert!(jobs.get(1).is_none());
+        assert_eq!(jobs.get(id).unwrap().state, RpcJobState::Completed);
+
+        jobs.close();
+        assert!(receiver.recv().await.is_err());
+    }
+}
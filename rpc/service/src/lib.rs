@@ -1,3 +1,10 @@
+pub mod access;
+pub mod chain_quality;
 pub mod collector;
 pub mod converter;
+pub mod health;
+pub mod jobs;
 pub mod service;
+pub mod submission;
+pub mod tickets;
+pub(crate) mod tx_builder;
@@ -1,9 +1,20 @@
 //! Core server implementation for ClientAPI
 
-use super::collector::{CollectorFromConsensus, CollectorFromIndex};
-use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
+use super::collector::{CollectorFromConsensus, CollectorFromHealth, CollectorFromIndex, CollectorFromJobs, CollectorFromMining};
+use crate::access::{RpcAccessPolicies, RpcAccessPolicy, RpcAccessProfile, RpcInterface};
+use crate::chain_quality::ChainQualityMonitor;
+use crate::converter::{
+    consensus::{ConsensusConverter, ConversionContext},
+    index::IndexConverter,
+    protocol::ProtocolConverter,
+};
+use crate::health::HealthMonitor;
+use crate::jobs::JobRegistry;
 use crate::service::NetworkType::{Mainnet, Testnet};
+use crate::submission::BlockProcessingTickets;
+use crate::tx_builder::build_unsigned_transaction;
 use async_trait::async_trait;
+use kaspa_addressmanager::{AddressQuery, IpFamily, IpNetwork};
 use kaspa_consensus_core::api::counters::ProcessingCounters;
 use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::{
@@ -11,14 +22,15 @@ use kaspa_consensus_core::{
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
+    mass::{calc_tx_compute_mass_components, ComputeMassComponents},
     network::NetworkType,
-    tx::{Transaction, COINBASE_TRANSACTION_INDEX},
+    tx::{Transaction, TransactionOutput, COINBASE_TRANSACTION_INDEX},
 };
 use kaspa_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
 use kaspa_core::time::unix_now;
 use kaspa_core::{
     core::Core,
@@ -29,13 +41,24 @@ use kaspa_core::{
     task::tick::TickService,
     trace, warn,
 };
+use kaspa_database::metrics::StorageMetricsProvider;
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
 use kaspa_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
-use kaspa_mining::model::tx_query::TransactionQuery;
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
+use kaspa_mining::model::{
+    fee_estimate::FeerateBucket,
+    template_exclusions::TemplateExclusionsUpdate,
+    tx_query::{TransactionFilter, TransactionQuery},
+};
+use kaspa_mining::{
+    manager::MiningManagerProxy,
+    mempool::{
+        config::{MempoolPolicy, MAXIMUM_STANDARD_TRANSACTION_MASS},
+        tx::{Orphan, RbfPolicy},
+    },
+};
 use kaspa_notify::listener::ListenerLifespan;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
@@ -46,33 +69,43 @@ use kaspa_notify::{
     listener::ListenerId,
     notifier::Notifier,
     scope::Scope,
+    stats::NotificationStats,
     subscriber::{Subscriber, SubscriptionManager},
 };
-use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_p2p_flows::{flow_context::FlowContext, flowcontext::sync_status::IbdPhase};
 use kaspa_p2p_lib::common::ProtocolError;
 use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
     api::{
         ops::RPC_API_VERSION,
-        rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
+        rpc::{RpcApi, MAX_SAFE_SAMPLE_SIZE, MAX_SAFE_WINDOW_SIZE},
     },
     model::*,
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
-use kaspa_utils_tower::counters::TowerConnectionCounters;
+use kaspa_utils_tower::counters::{TowerConnectionCounters, TowerRequestCounters};
 use kaspa_utxoindex::api::UtxoIndexProxy;
+use parking_lot::Mutex;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     iter::once,
     sync::{atomic::Ordering, Arc},
+    time::Duration,
     vec,
 };
 use tokio::join;
 use workflow_rpc::server::WebSocketCounters as WrpcServerCounters;
 
+/// Reports the usage of the tenants sharing an RPC server, so it gets reported by [`RpcApi::get_metrics`]
+pub trait TenantUsageProvider: Send + Sync {
+    /// Returns the usage of every tenant, sorted by tenant name
+    fn tenant_metrics(&self) -> Vec<TenantMetrics>;
+}
+
 /// A service implementing the Rpc API at kaspa_rpc_core level.
 ///
 /// Collects notifications from the consensus and forwards them to
@@ -96,6 +129,7 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -109,10 +143,23 @@ pub struct RpcCoreService {
     perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
+    health_monitor: Arc<HealthMonitor>,
+    chain_quality_monitor: Option<Arc<ChainQualityMonitor>>,
+    access_policies: RpcAccessPolicies,
+    storage_metrics: Arc<StorageMetricsProvider>,
+    block_processing_tickets: Arc<BlockProcessingTickets>,
+    jobs: Arc<JobRegistry>,
+    notification_stats: Mutex<Vec<(String, Arc<NotificationStats>)>>,
+    grpc_request_counters: Mutex<Option<Arc<TowerRequestCounters>>>,
+    grpc_tenants: Mutex<Option<Arc<dyn TenantUsageProvider>>>,
+    build_info: RpcBuildInfo,
 }
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Maximum number of 10ms polls of the UTXO index waiting for it to process the blocks generated on simnet
+const GENERATED_BLOCKS_INDEXING_MAX_POLLS: usize = 500;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -125,6 +172,7 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -133,6 +181,10 @@ impl RpcCoreService {
         perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
+        health_monitor: Arc<HealthMonitor>,
+        access_policies: RpcAccessPolicies,
+        storage_metrics: Arc<StorageMetricsProvider>,
+        build_info: RpcBuildInfo,
     ) -> Self {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
         let policies = match index_notifier {
@@ -151,6 +203,10 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        consensus_events[EventType::HealthAlert] = false;
+        consensus_events[EventType::MempoolTransactionsEvicted] = false;
+        consensus_events[EventType::JobCompleted] = false;
+        consensus_events[EventType::IndexResyncProgress] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
@@ -160,7 +216,24 @@ impl RpcCoreService {
         let consensus_subscriber =
             Arc::new(Subscriber::new("rpc-core => consensus", consensus_events, consensus_notifier, consensus_notify_listener_id));
 
-        let mut collectors: Vec<DynCollector<Notification>> = vec![consensus_collector];
+        // Health alerts and index resync progress are produced by the health monitor and need no subscription
+        let health_collector = Arc::new(CollectorFromHealth::new(
+            "rpc-core <= health",
+            health_monitor.notification_receiver(),
+            Arc::new(Default::default()),
+        ));
+
+        // Mempool evictions are produced by the mining manager and need no subscription either
+        let mining_collector =
+            Arc::new(CollectorFromMining::new("rpc-core <= mining", mining_manager.eviction_receiver(), Arc::new(Default::default())));
+
+        // Job completions are produced by the job registry of this service
+        let jobs = Arc::new(JobRegistry::default());
+        let jobs_collector =
+            Arc::new(CollectorFromJobs::new("rpc-core <= jobs", jobs.notification_receiver(), Arc::new(Default::default())));
+
+        let mut collectors: Vec<DynCollector<Notification>> =
+            vec![consensus_collector, health_collector, mining_collector, jobs_collector];
         let mut subscribers = vec![consensus_subscriber];
 
         // Prepare index-processor objects if an IndexService is provided
@@ -186,8 +259,15 @@ impl RpcCoreService {
         let protocol_converter = Arc::new(ProtocolConverter::new(flow_context.clone()));
 
         // Create the rcp-core notifier
-        let notifier =
-            Arc::new(Notifier::new(RPC_CORE, EVENT_TYPE_ARRAY[..].into(), collectors, subscribers, subscription_context, 1, policies));
+        let mut notifier =
+            Notifier::new(RPC_CORE, EVENT_TYPE_ARRAY[..].into(), collectors, subscribers, subscription_context, 1, policies);
+        // Coalescing at the root notifier lowers the UtxosChanged rate of the gRPC and wRPC clients alike
+        if let Some(window) = config.utxos_changed_coalescing_window {
+            notifier = notifier.with_utxos_changed_coalescing(Duration::from_millis(window));
+        }
+        let notifier = Arc::new(notifier);
+
+        let notification_stats = Mutex::new(vec![(RPC_CORE.to_string(), notifier.stats())]);
 
         Self {
             consensus_manager,
@@ -195,6 +275,7 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
             config,
             consensus_converter,
             index_converter,
@@ -208,6 +289,52 @@ impl RpcCoreService {
             perf_monitor,
             p2p_tower_counters,
             grpc_tower_counters,
+            health_monitor,
+            chain_quality_monitor: None,
+            access_policies,
+            storage_metrics,
+            block_processing_tickets: Default::default(),
+            jobs,
+            notification_stats,
+            grpc_request_counters: Default::default(),
+            grpc_tenants: Default::default(),
+            build_info,
+        }
+    }
+
+    /// Serves the chain-quality samples of `chain_quality_monitor`, if provided
+    pub fn with_chain_quality_monitor(self, chain_quality_monitor: Option<Arc<ChainQualityMonitor>>) -> Self {
+        Self { chain_quality_monitor, ..self }
+    }
+
+    /// Submits a block to consensus, waiting for its validation, and reports the outcome
+    async fn process_submitted_block(
+        flow_context: &FlowContext,
+        config: &Config,
+        session: &ConsensusProxy,
+        block: Block,
+    ) -> SubmitBlockReport {
+        match flow_context.submit_rpc_block(session, block.clone()).await {
+            Ok(_) => SubmitBlockReport::Success,
+            Err(ProtocolError::RuleError(RuleError::BadMerkleRoot(h1, h2))) => {
+                warn!(
+                    "The RPC submitted block triggered a {} error: {}. 
+NOTE: This error usually indicates an RPC conversion error between the node and the miner. If you are on TN11 this is likely to reflect using a NON-SUPPORTED miner.",
+                    stringify!(RuleError::BadMerkleRoot),
+                    RuleError::BadMerkleRoot(h1, h2)
+                );
+                if config.net.is_mainnet() {
+                    warn!("Printing the full block for debug purposes:\n{:?}", block);
+                }
+                SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)
+            }
+            Err(err) => {
+                warn!(
+                    "The RPC submitted block triggered an error: {}\nPrinting the full header for debug purposes:\n{:?}",
+                    err, block
+                );
+                SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)
+            }
         }
     }
 
@@ -217,6 +344,7 @@ impl RpcCoreService {
 
     pub async fn join(&self) -> RpcResult<()> {
         trace!("{} joining notifier", Self::IDENT);
+        self.jobs.close();
         self.notifier().join().await?;
         Ok(())
     }
@@ -231,6 +359,34 @@ impl RpcCoreService {
         self.notifier.subscription_context().clone()
     }
 
+    /// Registers the delivery statistics of a server notifier relaying the rpc-core notifications to clients,
+    /// so they get reported by [`RpcApi::get_notification_stats`]
+    pub fn register_notification_stats(&self, name: &str, stats: Arc<NotificationStats>) {
+        let mut notification_stats = self.notification_stats.lock();
+        notification_stats.retain(|(x, _)| x != name);
+        notification_stats.push((name.to_string(), stats));
+    }
+
+    /// Registers the request counters of the gRPC server, so they get reported by [`RpcApi::get_metrics`]
+    pub fn register_grpc_request_counters(&self, counters: Arc<TowerRequestCounters>) {
+        *self.grpc_request_counters.lock() = Some(counters);
+    }
+
+    /// Registers the tenants of the gRPC server, so their usage gets reported by [`RpcApi::get_metrics`]
+    pub fn register_grpc_tenants(&self, tenants: Arc<dyn TenantUsageProvider>) {
+        *self.grpc_tenants.lock() = Some(tenants);
+    }
+
+    /// Returns the access policy the server listening on `interface` must enforce
+    pub fn access_policy(&self, interface: RpcInterface) -> Arc<RpcAccessPolicy> {
+        self.access_policies.get(interface)
+    }
+
+    /// Returns the access policy a listening address of `interface` with an optional specific `profile` must enforce
+    pub fn access_policy_with_profile(&self, interface: RpcInterface, profile: Option<RpcAccessProfile>) -> Arc<RpcAccessPolicy> {
+        self.access_policies.get_with_profile(interface, profile)
+    }
+
     pub fn core_shutdown_request_listener(&self) -> triggered::Listener {
         self.core_shutdown_request.listener.clone()
     }
@@ -261,6 +417,15 @@ impl RpcCoreService {
         !matches!(self.flow_context.config.net.network_type, Mainnet | Testnet) || self.flow_context.hub().has_peers()
     }
 
+    fn rpc_mempool_policy(policy: MempoolPolicy) -> RpcMempoolPolicy {
+        RpcMempoolPolicy {
+            transaction_expire_interval_seconds: policy.transaction_expire_interval_seconds,
+            orphan_expire_interval_seconds: policy.orphan_expire_interval_seconds,
+            maximum_transaction_count: policy.maximum_transaction_count,
+            maximum_orphan_transaction_count: policy.maximum_orphan_transaction_count,
+        }
+    }
+
     fn extract_tx_query(&self, filter_transaction_pool: bool, include_orphan_pool: bool) -> RpcResult<TransactionQuery> {
         match (filter_transaction_pool, include_orphan_pool) {
             (true, true) => Ok(TransactionQuery::OrphansOnly),
@@ -271,11 +436,101 @@ impl RpcCoreService {
             (false, false) => Ok(TransactionQuery::TransactionsOnly),
         }
     }
+
+    /// Gets the block `hash`, without fetching its body if the requested `verbosity` has no use of it
+    async fn get_block_with_verbosity(
+        &self,
+        session: &ConsensusProxy,
+        hash: RpcHash,
+        verbosity: RpcBlockVerbosity,
+    ) -> RpcResult<Block> {
+        match verbosity {
+            RpcBlockVerbosity::HeaderOnly => Ok(Block::from_header_arc(session.async_get_header(hash).await?)),
+            RpcBlockVerbosity::TransactionIds | RpcBlockVerbosity::Full => {
+                Ok(session.async_get_block_even_if_header_only(hash).await?)
+            }
+        }
+    }
+
+    /// Submits a transaction, attributing it to the RPC connection `submitter` if provided
+    async fn submit_transaction_impl(
+        &self,
+        request: SubmitTransactionRequest,
+        submitter: Option<String>,
+    ) -> RpcResult<SubmitTransactionResponse> {
+        if self.config.is_secondary {
+            return Err(RpcError::UnavailableOnSecondaryInstance);
+        }
+        let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
+        if !self.config.unsafe_rpc && request.allow_orphan {
+            warn!("SubmitTransaction RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
+        }
+
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let orphan = match allow_orphan {
+            true => Orphan::Allowed,
+            false => Orphan::Forbidden,
+        };
+        let rbf_policy = match request.replace {
+            true => RbfPolicy::Allowed,
+            false => RbfPolicy::Forbidden,
+        };
+        self.flow_context.submit_rpc_transaction(&session, transaction, orphan, rbf_policy, submitter).await.map_err(|err| {
+            let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+            debug!("{err}");
+            err
+        })?;
+        Ok(SubmitTransactionResponse::new(transaction_id))
+    }
+
+    /// Submits a list of transactions, attributing them to the RPC connection `submitter` if provided
+    async fn submit_transactions_impl(
+        &self,
+        request: SubmitTransactionsRequest,
+        submitter: Option<String>,
+    ) -> RpcResult<SubmitTransactionsResponse> {
+        if self.config.is_secondary {
+            return Err(RpcError::UnavailableOnSecondaryInstance);
+        }
+        let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
+        if !self.config.unsafe_rpc && request.allow_orphan {
+            warn!("SubmitTransactions RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
+        }
+
+        let transactions: Vec<Transaction> = request.transactions.iter().map(|x| x.try_into()).collect::<Result<_, _>>()?;
+        let transaction_ids = transactions.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let orphan = match allow_orphan {
+            true => Orphan::Allowed,
+            false => Orphan::Forbidden,
+        };
+        let results = self
+            .flow_context
+            .submit_rpc_transactions(&session, transactions, orphan, submitter)
+            .await
+            .into_iter()
+            .zip(transaction_ids)
+            .map(|(result, transaction_id)| {
+                let error = result.err().map(|err| {
+                    let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                    debug!("{err}");
+                    err.to_string()
+                });
+                RpcSubmitTransactionResult { transaction_id, error }
+            })
+            .collect();
+        Ok(SubmitTransactionsResponse::new(results))
+    }
 }
 
 #[async_trait]
 impl RpcApi for RpcCoreService {
     async fn submit_block_call(&self, request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
+        if self.config.is_secondary {
+            return Err(RpcError::UnavailableOnSecondaryInstance);
+        }
         let session = self.consensus_manager.consensus().unguarded_session();
 
         // TODO: consider adding an error field to SubmitBlockReport to document both the report and error fields
@@ -283,14 +538,14 @@ impl RpcApi for RpcCoreService {
 
         if !self.config.enable_unsynced_mining && !is_synced {
             // error = "Block not submitted - node is not synced"
-            return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD) });
+            return Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::IsInIBD)));
         }
 
         let try_block: RpcResult<Block> = (&request.block).try_into();
         if let Err(err) = &try_block {
             trace!("incoming SubmitBlockRequest with block conversion error: {}", err);
             // error = format!("Could not parse block: {0}", err)
-            return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) });
+            return Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)));
         }
         let block = try_block?;
         let hash = block.hash();
@@ -304,38 +559,31 @@ impl RpcApi for RpcCoreService {
             if virtual_daa_score > daa_window_block_duration && block.header.daa_score < virtual_daa_score - daa_window_block_duration
             {
                 // error = format!("Block rejected. Reason: block DAA score {0} is too far behind virtual's DAA score {1}", block.header.daa_score, virtual_daa_score)
-                return Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) });
+                return Ok(SubmitBlockResponse::new(SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)));
             }
         }
 
         trace!("incoming SubmitBlockRequest for block {}", hash);
-        match self.flow_context.submit_rpc_block(&session, block.clone()).await {
-            Ok(_) => Ok(SubmitBlockResponse { report: SubmitBlockReport::Success }),
-            Err(ProtocolError::RuleError(RuleError::BadMerkleRoot(h1, h2))) => {
-                warn!(
-                    "The RPC submitted block triggered a {} error: {}. 
-NOTE: This error usually indicates an RPC conversion error between the node and the miner. If you are on TN11 this is likely to reflect using a NON-SUPPORTED miner.",
-                    stringify!(RuleError::BadMerkleRoot),
-                    RuleError::BadMerkleRoot(h1, h2)
-                );
-                if self.config.net.is_mainnet() {
-                    warn!("Printing the full block for debug purposes:\n{:?}", block);
-                }
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) })
-            }
-            Err(err) => {
-                warn!(
-                    "The RPC submitted block triggered an error: {}\nPrinting the full header for debug purposes:\n{:?}",
-                    err, block
-                );
-                Ok(SubmitBlockResponse { report: SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) })
-            }
+        if request.allow_non_blocking {
+            let ticket = self.block_processing_tickets.register(hash);
+            let (flow_context, config, tickets) =
+                (self.flow_context.clone(), self.config.clone(), self.block_processing_tickets.clone());
+            tokio::spawn(async move {
+                let report = Self::process_submitted_block(&flow_context, &config, &session, block).await;
+                tickets.complete(ticket, report);
+            });
+            return Ok(SubmitBlockResponse::new(SubmitBlockReport::Success).with_ticket(ticket));
         }
+        Ok(SubmitBlockResponse::new(Self::process_submitted_block(&self.flow_context, &self.config, &session, block).await))
     }
 
     async fn get_block_template_call(&self, request: GetBlockTemplateRequest) -> RpcResult<GetBlockTemplateResponse> {
         trace!("incoming GetBlockTemplate request");
 
+        if self.config.is_secondary {
+            return Err(RpcError::UnavailableOnSecondaryInstance);
+        }
+
         if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
             return Err(RpcError::General("Mining on mainnet is not supported for initial Rust versions".to_owned()));
         }
@@ -362,24 +610,22 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetBlockTemplateResponse {
             block: (&block_template.block).into(),
             is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
+            tx_ordering: self.mining_manager.template_tx_ordering(),
         })
     }
 
     async fn get_block_call(&self, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         // TODO: test
         let session = self.consensus_manager.consensus().session().await;
-        let block = session.async_get_block_even_if_header_only(request.hash).await?;
-        Ok(GetBlockResponse {
-            block: self
-                .consensus_converter
-                .get_block(&session, &block, request.include_transactions, request.include_transactions)
-                .await?,
-        })
+        let verbosity = request.block_verbosity();
+        let block = self.get_block_with_verbosity(&session, request.hash, verbosity).await?;
+        Ok(GetBlockResponse { block: self.consensus_converter.get_block(&session, &block, verbosity).await? })
     }
 
     async fn get_blocks_call(&self, request: GetBlocksRequest) -> RpcResult<GetBlocksResponse> {
-        // Validate that user didn't set include_transactions without setting include_blocks
-        if !request.include_blocks && request.include_transactions {
+        // Validate that user didn't request full transactions without setting include_blocks
+        let verbosity = request.block_verbosity();
+        if !request.include_blocks && verbosity.includes_transactions() {
             return Err(RpcError::InvalidGetBlocksRequest);
         }
 
@@ -411,12 +657,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let block_hashes = once(low_hash).chain(block_hashes).chain(sink_anticone).collect::<Vec<_>>();
         let blocks = if request.include_blocks {
             let mut blocks = Vec::with_capacity(block_hashes.len());
+            let mut context = ConversionContext::new();
             for hash in block_hashes.iter().copied() {
-                let block = session.async_get_block_even_if_header_only(hash).await?;
-                let rpc_block = self
-                    .consensus_converter
-                    .get_block(&session, &block, request.include_transactions, request.include_transactions)
-                    .await?;
+                let block = self.get_block_with_verbosity(&session, hash, verbosity).await?;
+                let rpc_block = self.consensus_converter.get_block_with_context(&session, &block, verbosity, &mut context).await?;
                 blocks.push(rpc_block)
             }
             blocks
@@ -436,6 +680,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             is_synced: self.has_sufficient_peer_connectivity() && is_nearly_synced,
             has_notify_command: true,
             has_message_id: true,
+            build_info: self.build_info.clone(),
         })
     }
 
@@ -451,7 +696,12 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn get_mempool_entries_call(&self, request: GetMempoolEntriesRequest) -> RpcResult<GetMempoolEntriesResponse> {
         let query = self.extract_tx_query(request.filter_transaction_pool, request.include_orphan_pool)?;
         let session = self.consensus_manager.consensus().unguarded_session();
-        let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query).await;
+        let filter = TransactionFilter {
+            min_fee_rate: request.min_fee_rate,
+            min_added_at_daa_score: request.max_age_daa_score.map(|max_age| session.get_virtual_daa_score().saturating_sub(max_age)),
+            script_public_keys: request.spendable_by_addresses.iter().map(pay_to_address_script).collect(),
+        };
+        let (transactions, orphans) = self.mining_manager.clone().get_all_transactions(query, filter).await;
         let mempool_entries = transactions
             .iter()
             .chain(orphans.iter())
@@ -486,28 +736,31 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
-        let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
-        if !self.config.unsafe_rpc && request.allow_orphan {
-            warn!("SubmitTransaction RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
-        }
+        self.submit_transaction_impl(request, None).await
+    }
 
-        let transaction: Transaction = (&request.transaction).try_into()?;
-        let transaction_id = transaction.id();
-        let session = self.consensus_manager.consensus().unguarded_session();
-        let orphan = match allow_orphan {
-            true => Orphan::Allowed,
-            false => Orphan::Forbidden,
-        };
-        self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await.map_err(|err| {
-            let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
-            debug!("{err}");
-            err
-        })?;
-        Ok(SubmitTransactionResponse::new(transaction_id))
+    async fn submit_transaction_from_call(
+        &self,
+        submitter: String,
+        request: SubmitTransactionRequest,
+    ) -> RpcResult<SubmitTransactionResponse> {
+        self.submit_transaction_impl(request, Some(submitter)).await
+    }
+
+    async fn submit_transactions_call(&self, request: SubmitTransactionsRequest) -> RpcResult<SubmitTransactionsResponse> {
+        self.submit_transactions_impl(request, None).await
+    }
+
+    async fn submit_transactions_from_call(
+        &self,
+        submitter: String,
+        request: SubmitTransactionsRequest,
+    ) -> RpcResult<SubmitTransactionsResponse> {
+        self.submit_transactions_impl(request, Some(submitter)).await
     }
 
     async fn get_current_network_call(&self, _: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
-        Ok(GetCurrentNetworkResponse::new(*self.config.net))
+        Ok(GetCurrentNetworkResponse::new(self.config.net))
     }
 
     async fn get_subnetwork_call(&self, _: GetSubnetworkRequest) -> RpcResult<GetSubnetworkResponse> {
@@ -528,13 +781,19 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         request: GetVirtualChainFromBlockRequest,
     ) -> RpcResult<GetVirtualChainFromBlockResponse> {
         let session = self.consensus_manager.consensus().session().await;
-        let virtual_chain = session.async_get_virtual_chain_from_block(request.start_hash).await?;
+        let mut virtual_chain = session.async_get_virtual_chain_from_block(request.start_hash).await?;
+        // Truncate the added chain blocks to the batch size, the last one being the start hash of the next batch
+        let next_start_hash = match request.max_blocks {
+            Some(max_blocks) if max_blocks > 0 => virtual_chain.truncate_added(max_blocks as usize),
+            _ => None,
+        };
         let accepted_transaction_ids = if request.include_accepted_transaction_ids {
             self.consensus_converter.get_virtual_chain_accepted_transaction_ids(&session, &virtual_chain).await?
         } else {
             vec![]
         };
-        Ok(GetVirtualChainFromBlockResponse::new(virtual_chain.removed, virtual_chain.added, accepted_transaction_ids))
+        Ok(GetVirtualChainFromBlockResponse::new(virtual_chain.removed, virtual_chain.added, accepted_transaction_ids)
+            .with_next_start_hash(next_start_hash))
     }
 
     async fn get_block_count_call(&self, _: GetBlockCountRequest) -> RpcResult<GetBlockCountResponse> {
@@ -547,8 +806,41 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         // TODO: discuss if the entry order is part of the method requirements
         //       (the current impl does not retain an entry order matching the request addresses order)
-        let entry_map = self.get_utxo_set_by_script_public_key(request.addresses.iter()).await;
-        Ok(GetUtxosByAddressesResponse::new(self.index_converter.get_utxos_by_addresses_entries(&entry_map)))
+        if request.cursor.is_none() && request.limit.is_none() {
+            let entry_map = self.get_utxo_set_by_script_public_key(request.addresses.iter()).await;
+            return Ok(GetUtxosByAddressesResponse::new(self.index_converter.get_utxos_by_addresses_entries(&entry_map)));
+        }
+
+        // Paged requests walk the addresses in request order, resuming at the cursor address
+        let (start, start_after) = match request.cursor {
+            Some(cursor) => {
+                let start = request
+                    .addresses
+                    .iter()
+                    .position(|address| *address == cursor.address)
+                    .ok_or_else(|| RpcError::InvalidUtxosCursor(cursor.address.to_string()))?;
+                (start, Some(cursor.outpoint))
+            }
+            None => (0, None),
+        };
+        let addresses = &request.addresses[start..];
+        // A zero limit would return an empty page without a cursor, which reads as the last page
+        let limit = request.limit.map_or(usize::MAX, |limit| (limit as usize).max(1));
+        let page = self
+            .utxoindex
+            .clone()
+            .unwrap()
+            .get_utxos_page_by_script_public_keys(addresses.iter().map(pay_to_address_script).collect(), start_after, limit)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        let next_cursor = page.last.and_then(|(script_public_key, outpoint)| {
+            addresses
+                .iter()
+                .find(|address| pay_to_address_script(address) == script_public_key)
+                .map(|address| RpcUtxosByAddressesCursor::new(address.clone(), outpoint))
+        });
+        Ok(GetUtxosByAddressesResponse::new(self.index_converter.get_utxos_by_addresses_entries(&page.entries))
+            .with_next_cursor(next_cursor))
     }
 
     async fn get_balance_by_address_call(&self, request: GetBalanceByAddressRequest) -> RpcResult<GetBalanceByAddressResponse> {
@@ -584,9 +876,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if !self.config.utxoindex {
             return Err(RpcError::NoUtxoIndex);
         }
-        let circulating_sompi =
-            self.utxoindex.clone().unwrap().get_circulating_supply().await.map_err(|e| RpcError::General(e.to_string()))?;
-        Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi))
+        let utxoindex = self.utxoindex.clone().unwrap();
+        let (circulating_sompi, burned_sompi) = join!(utxoindex.clone().get_circulating_supply(), utxoindex.get_burned_supply());
+        let circulating_sompi = circulating_sompi.map_err(|e| RpcError::General(e.to_string()))?;
+        let burned_sompi = burned_sompi.map_err(|e| RpcError::General(e.to_string()))?;
+        Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi, burned_sompi))
     }
 
     async fn get_daa_score_timestamp_estimate_call(
@@ -647,12 +941,207 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetDaaScoreTimestampEstimateResponse::new(timestamps))
     }
 
+    async fn get_transaction_mass_call(&self, request: GetTransactionMassRequest) -> RpcResult<GetTransactionMassResponse> {
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let components = match transaction.is_coinbase() {
+            true => ComputeMassComponents::default(),
+            false => calc_tx_compute_mass_components(
+                &transaction,
+                self.config.mass_per_tx_byte,
+                self.config.mass_per_script_pub_key_byte,
+                self.config.mass_per_sig_op,
+            ),
+        };
+        Ok(GetTransactionMassResponse {
+            size_mass: components.size_mass,
+            script_public_key_mass: components.script_public_key_mass,
+            sig_op_mass: components.sig_op_mass,
+            compute_mass: components.total(),
+            max_standard_transaction_mass: MAXIMUM_STANDARD_TRANSACTION_MASS,
+            max_block_mass: self.config.max_block_mass,
+        })
+    }
+
+    async fn build_unsigned_transaction_call(
+        &self,
+        request: BuildUnsignedTransactionRequest,
+    ) -> RpcResult<BuildUnsignedTransactionResponse> {
+        if !self.config.enable_transaction_builder_rpc {
+            return Err(RpcError::TransactionBuilderDisabled);
+        }
+        if !self.config.utxoindex {
+            return Err(RpcError::NoUtxoIndex);
+        }
+
+        // Make sure all address prefixes match the config network type
+        let payment_addresses = request.outputs.iter().map(|output| &output.address);
+        if let Some(address) = request
+            .from_addresses
+            .iter()
+            .chain(payment_addresses)
+            .chain(once(&request.change_address))
+            .find(|address| address.prefix != self.config.prefix())
+        {
+            return Err(kaspa_addresses::AddressError::InvalidPrefix(address.prefix.to_string()))?;
+        }
+        let fee_rate = request.fee_rate.unwrap_or_default();
+        if !fee_rate.is_finite() || fee_rate < 0.0 {
+            return Err(RpcError::General(format!("invalid fee rate {}", fee_rate)));
+        }
+
+        // Exclude immature coinbase UTXOs and the ones already spent by mempool transactions
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let virtual_daa_score = session.get_virtual_daa_score();
+        let script_public_keys = request.from_addresses.iter().map(pay_to_address_script).collect();
+        let mempool_txs = self.mining_manager.clone().get_transactions_by_addresses(script_public_keys, TransactionQuery::All).await;
+        let spent_outpoints: HashSet<_> =
+            mempool_txs.transactions.values().flat_map(|tx| tx.tx.inputs.iter().map(|input| input.previous_outpoint)).collect();
+        let entry_map = self.get_utxo_set_by_script_public_key(request.from_addresses.iter()).await;
+        let utxos = self
+            .index_converter
+            .get_utxos_by_addresses_entries(&entry_map)
+            .into_iter()
+            .filter(|utxo| {
+                !spent_outpoints.contains(&utxo.outpoint)
+                    && (!utxo.utxo_entry.is_coinbase
+                        || utxo.utxo_entry.block_daa_score + self.config.coinbase_maturity <= virtual_daa_score)
+            })
+            .collect();
+
+        let outputs = request
+            .outputs
+            .iter()
+            .map(|output| TransactionOutput::new(output.amount, pay_to_address_script(&output.address)))
+            .collect();
+        let unsigned = build_unsigned_transaction(
+            &self.config,
+            utxos,
+            outputs,
+            pay_to_address_script(&request.change_address),
+            fee_rate,
+            MAXIMUM_STANDARD_TRANSACTION_MASS,
+            virtual_daa_score > self.config.storage_mass_activation_daa_score,
+            &self.mining_manager,
+        )?;
+        Ok(BuildUnsignedTransactionResponse {
+            transaction: (&unsigned.transaction).into(),
+            utxo_entries: unsigned.utxo_entries,
+            fee: unsigned.fee,
+            mass: unsigned.mass,
+        })
+    }
+
+    async fn get_dag_stats_call(&self, request: GetDagStatsRequest) -> RpcResult<GetDagStatsResponse> {
+        if !self.config.unsafe_rpc && request.window_size > MAX_SAFE_WINDOW_SIZE {
+            return Err(RpcError::WindowSizeExceedingMaximum(request.window_size, MAX_SAFE_WINDOW_SIZE));
+        }
+        if request.window_size as u64 > self.config.pruning_depth {
+            return Err(RpcError::WindowSizeExceedingPruningDepth(request.window_size, self.config.pruning_depth));
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        let stats = session.async_get_dag_stats(request.window_size as usize).await;
+        let ratio = |numerator: u64, denominator: u64| if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 };
+        Ok(GetDagStatsResponse {
+            tip_count: stats.num_tips,
+            chain_block_count: stats.chain_block_count,
+            block_count: stats.merged_block_count,
+            average_mergeset_size: ratio(stats.merged_block_count, stats.chain_block_count),
+            red_block_rate: ratio(stats.red_block_count, stats.merged_block_count),
+            average_parents: ratio(stats.parent_count, stats.merged_block_count),
+        })
+    }
+
+    async fn get_health_call(&self, _: GetHealthRequest) -> RpcResult<GetHealthResponse> {
+        Ok(GetHealthResponse::new(self.health_monitor.alerts(), self.health_monitor.index_resyncs()))
+    }
+
+    async fn get_fee_estimate_call(&self, _: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        let estimate = self.mining_manager.clone().get_fee_estimate().await;
+        let bucket = |bucket: FeerateBucket| RpcFeerateBucket { feerate: bucket.feerate, estimated_seconds: bucket.estimated_seconds };
+        Ok(GetFeeEstimateResponse {
+            estimate: RpcFeeEstimate {
+                priority_bucket: bucket(estimate.priority_bucket),
+                normal_bucket: bucket(estimate.normal_bucket),
+                low_bucket: bucket(estimate.low_bucket),
+            },
+        })
+    }
+
+    async fn get_virtual_parents_call(&self, _: GetVirtualParentsRequest) -> RpcResult<GetVirtualParentsResponse> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let selection = session.async_get_virtual_parents_selection().await;
+        Ok(GetVirtualParentsResponse::new(
+            selection.selected_parent,
+            selection.parents,
+            selection.excluded.into_iter().map(RpcExcludedParentCandidate::from).collect(),
+        ))
+    }
+
+    async fn get_consensus_params_call(&self, _: GetConsensusParamsRequest) -> RpcResult<GetConsensusParamsResponse> {
+        Ok(GetConsensusParamsResponse::new(RpcConsensusParams::from(&**self.config)))
+    }
+
+    async fn get_transaction_by_id_call(&self, request: GetTransactionByIdRequest) -> RpcResult<GetTransactionByIdResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let entry = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        // The confirmations are defined as for the blocks and transactions returned by GetBlock
+        let confirmations = match entry.accepting_block_hash {
+            Some(accepting_block_hash) => {
+                let session = self.consensus_manager.consensus().unguarded_session();
+                self.consensus_converter
+                    .get_block_confirmations(&session, accepting_block_hash, None, &mut ConversionContext::new())
+                    .await?
+            }
+            None => 0,
+        };
+        Ok(GetTransactionByIdResponse::new(
+            request.transaction_id,
+            entry.containing_block_hash,
+            entry.accepting_block_hash,
+            confirmations,
+        ))
+    }
+
+    async fn sample_chain_blocks_call(&self, request: SampleChainBlocksRequest) -> RpcResult<SampleChainBlocksResponse> {
+        if !self.config.unsafe_rpc && request.count > MAX_SAFE_SAMPLE_SIZE {
+            return Err(RpcError::SampleSizeExceedingMaximum(request.count, MAX_SAFE_SAMPLE_SIZE));
+        }
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let hashes =
+            session.async_sample_chain_blocks_by_work(request.low_blue_score, request.high_blue_score, request.count as usize).await?;
+        let mut headers = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            headers.push(session.async_get_header(hash).await?.as_ref().clone());
+        }
+        Ok(SampleChainBlocksResponse::new(headers))
+    }
+
+    async fn get_subscriptions_call(&self, _: GetSubscriptionsRequest) -> RpcResult<GetSubscriptionsResponse> {
+        // Subscriptions belong to the listener of a connection, so the call is served by the gRPC and wRPC servers
+        Err(RpcError::ConnectionRequired)
+    }
+
     async fn ping_call(&self, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
 
-    async fn get_headers_call(&self, _request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_headers_call(&self, request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
+        // Maximum number of headers returned by a single call
+        const MAX_HEADERS_LIMIT: u64 = 2_000;
+
+        let limit = request.limit.min(MAX_HEADERS_LIMIT) as usize;
+        let session = self.consensus_manager.consensus().session().await;
+        let headers = session.async_get_chain_headers(request.start_hash, limit, request.is_ascending).await?;
+        Ok(GetHeadersResponse::new(headers.iter().map(|header| header.as_ref().clone()).collect()))
     }
 
     async fn get_block_dag_info_call(&self, _: GetBlockDagInfoRequest) -> RpcResult<GetBlockDagInfoResponse> {
@@ -683,6 +1172,13 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         if request.window_size as u64 > self.config.pruning_depth {
             return Err(RpcError::WindowSizeExceedingPruningDepth(request.window_size, self.config.pruning_depth));
         }
+        let min_window_size = match request.min_window_size {
+            Some(min_window_size) if (min_window_size as usize) < self.config.min_hashrate_estimation_window_size => {
+                return Err(RpcError::MinWindowSizeBelowConfigured(min_window_size, self.config.min_hashrate_estimation_window_size));
+            }
+            Some(min_window_size) => min_window_size as usize,
+            None => self.config.min_hashrate_estimation_window_size,
+        };
 
         // In the previous golang implementation the convention for virtual was the following const.
         // In the current implementation, consensus behaves the same when it gets a None instead.
@@ -699,7 +1195,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 .consensus()
                 .session()
                 .await
-                .async_estimate_network_hashes_per_second(start_hash, request.window_size as usize)
+                .async_estimate_network_hashes_per_second(start_hash, request.window_size as usize, min_window_size)
                 .await?,
         ))
     }
@@ -718,9 +1214,31 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(AddPeerResponse {})
     }
 
-    async fn get_peer_addresses_call(&self, _: GetPeerAddressesRequest) -> RpcResult<GetPeerAddressesResponse> {
-        let address_manager = self.flow_context.address_manager.lock();
-        Ok(GetPeerAddressesResponse::new(address_manager.get_all_addresses(), address_manager.get_all_banned_addresses()))
+    async fn get_peer_addresses_call(&self, request: GetPeerAddressesRequest) -> RpcResult<GetPeerAddressesResponse> {
+        let ip_family = match (request.ipv4_only, request.ipv6_only) {
+            (true, true) => return Err(RpcError::InvalidPeerAddressesFilter("ipv4 only and ipv6 only are exclusive".to_owned())),
+            (true, false) => Some(IpFamily::V4),
+            (false, true) => Some(IpFamily::V6),
+            (false, false) => None,
+        };
+        let network = request
+            .network
+            .as_deref()
+            .map(str::parse::<IpNetwork>)
+            .transpose()
+            .map_err(|err| RpcError::InvalidPeerAddressesFilter(err.to_string()))?;
+        let query = AddressQuery {
+            recent_only: request.recent_only,
+            ip_family,
+            network,
+            offset: request.offset as usize,
+            limit: request.limit.map(|limit| limit as usize),
+        };
+
+        let mut address_manager = self.flow_context.address_manager.lock();
+        let (known_addresses, total_known) = if request.banned_only { (vec![], 0) } else { address_manager.query_addresses(&query) };
+        let (banned_addresses, total_banned) = address_manager.query_banned_addresses(&query);
+        Ok(GetPeerAddressesResponse::new(known_addresses, banned_addresses).with_totals(total_known as u64, total_banned as u64))
     }
 
     async fn ban_call(&self, request: BanRequest) -> RpcResult<BanResponse> {
@@ -730,10 +1248,21 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         if let Some(connection_manager) = self.flow_context.connection_manager() {
             let ip = request.ip.into();
-            if connection_manager.ip_has_permanent_connection(ip).await {
-                return Err(RpcError::IpHasPermanentConnection(request.ip));
+            match request.prefix_length {
+                Some(prefix_length) => {
+                    let network = IpNetwork::new(ip, prefix_length).map_err(|err| RpcError::InvalidIpNetwork(err.to_string()))?;
+                    if let Some(permanent_ip) = connection_manager.network_permanent_connection(&network).await {
+                        return Err(RpcError::NetworkHasPermanentConnection(network.to_string(), permanent_ip.into()));
+                    }
+                    connection_manager.ban_network(network, request.duration, request.reason).await;
+                }
+                None => {
+                    if connection_manager.ip_has_permanent_connection(ip).await {
+                        return Err(RpcError::IpHasPermanentConnection(request.ip));
+                    }
+                    connection_manager.ban(ip, request.duration, request.reason).await;
+                }
             }
-            connection_manager.ban(ip).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -746,10 +1275,23 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             return Err(RpcError::UnavailableInSafeMode);
         }
         let mut address_manager = self.flow_context.address_manager.lock();
-        if address_manager.is_banned(request.ip) {
-            address_manager.unban(request.ip)
-        } else {
-            return Err(RpcError::IpIsNotBanned(request.ip));
+        match request.prefix_length {
+            Some(prefix_length) => {
+                let network =
+                    IpNetwork::new(request.ip.into(), prefix_length).map_err(|err| RpcError::InvalidIpNetwork(err.to_string()))?;
+                if address_manager.is_network_banned(network) {
+                    address_manager.unban_network(network)
+                } else {
+                    return Err(RpcError::NetworkIsNotBanned(network.to_string()));
+                }
+            }
+            None => {
+                if address_manager.is_banned(request.ip) {
+                    address_manager.unban(request.ip)
+                } else {
+                    return Err(RpcError::IpIsNotBanned(request.ip));
+                }
+            }
         }
         Ok(UnbanResponse {})
     }
@@ -760,6 +1302,190 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetConnectedPeerInfoResponse::new(peer_info))
     }
 
+    async fn get_block_by_blue_score_call(&self, request: GetBlockByBlueScoreRequest) -> RpcResult<GetBlockByBlueScoreResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let hash = session.async_get_chain_block_by_blue_score(request.blue_score).await?;
+        let block = self.get_block_with_verbosity(&session, hash, request.verbosity).await?;
+        Ok(GetBlockByBlueScoreResponse { block: self.consensus_converter.get_block(&session, &block, request.verbosity).await? })
+    }
+
+    async fn get_block_by_daa_score_call(&self, request: GetBlockByDaaScoreRequest) -> RpcResult<GetBlockByDaaScoreResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let hash = session.async_get_chain_block_by_daa_score(request.daa_score).await?;
+        let block = self.get_block_with_verbosity(&session, hash, request.verbosity).await?;
+        Ok(GetBlockByDaaScoreResponse { block: self.consensus_converter.get_block(&session, &block, request.verbosity).await? })
+    }
+
+    async fn get_chain_block_by_timestamp_call(
+        &self,
+        request: GetChainBlockByTimestampRequest,
+    ) -> RpcResult<GetChainBlockByTimestampResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let hash = session.async_get_chain_block_by_timestamp(request.timestamp).await?;
+        let block = self.get_block_with_verbosity(&session, hash, request.verbosity).await?;
+        Ok(GetChainBlockByTimestampResponse { block: self.consensus_converter.get_block(&session, &block, request.verbosity).await? })
+    }
+
+    async fn get_pruning_point_utxos_call(&self, request: GetPruningPointUtxosRequest) -> RpcResult<GetPruningPointUtxosResponse> {
+        // Maximum number of entries returned in a single page
+        const MAX_PRUNING_POINT_UTXOS_LIMIT: usize = 10_000;
+
+        let limit = request.limit.map_or(MAX_PRUNING_POINT_UTXOS_LIMIT, |x| (x as usize).clamp(1, MAX_PRUNING_POINT_UTXOS_LIMIT));
+        let session = self.consensus_manager.consensus().session().await;
+        let pruning_point = match request.pruning_point {
+            Some(pruning_point) => pruning_point,
+            None => session.async_pruning_point().await,
+        };
+        let utxo_commitment = session.async_get_header(pruning_point).await?.utxo_commitment;
+        let skip_first = request.cursor.is_some();
+        let utxos = session.async_get_pruning_point_utxos(pruning_point, request.cursor, limit, skip_first).await?;
+        let next_cursor = (utxos.len() == limit).then(|| utxos.last().unwrap().0);
+        let entries =
+            utxos.into_iter().map(|(outpoint, utxo_entry)| RpcUtxosByAddressesEntry { address: None, outpoint, utxo_entry }).collect();
+        Ok(GetPruningPointUtxosResponse { pruning_point, utxo_commitment, entries, next_cursor })
+    }
+
+    async fn get_mempool_info_call(&self, _: GetMempoolInfoRequest) -> RpcResult<GetMempoolInfoResponse> {
+        let (transaction_count, orphan_count, policy) = join!(
+            self.mining_manager.clone().transaction_count(TransactionQuery::TransactionsOnly),
+            self.mining_manager.clone().transaction_count(TransactionQuery::OrphansOnly),
+            self.mining_manager.clone().mempool_policy()
+        );
+        let standard_transaction_versions = self.mining_manager.standard_transaction_versions();
+        Ok(GetMempoolInfoResponse {
+            transaction_count: transaction_count as u64,
+            orphan_count: orphan_count as u64,
+            policy: Self::rpc_mempool_policy(policy),
+            minimum_standard_transaction_version: *standard_transaction_versions.start(),
+            maximum_standard_transaction_version: *standard_transaction_versions.end(),
+        })
+    }
+
+    async fn set_mempool_policy_call(&self, request: SetMempoolPolicyRequest) -> RpcResult<SetMempoolPolicyResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetMempoolPolicy RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let policy = MempoolPolicy {
+            transaction_expire_interval_seconds: request.policy.transaction_expire_interval_seconds,
+            orphan_expire_interval_seconds: request.policy.orphan_expire_interval_seconds,
+            maximum_transaction_count: request.policy.maximum_transaction_count,
+            maximum_orphan_transaction_count: request.policy.maximum_orphan_transaction_count,
+        };
+        let policy = self.mining_manager.clone().set_mempool_policy(policy).await?;
+        Ok(SetMempoolPolicyResponse { policy: Self::rpc_mempool_policy(policy) })
+    }
+
+    async fn get_current_block_color_call(&self, request: GetCurrentBlockColorRequest) -> RpcResult<GetCurrentBlockColorResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        let color = session.async_get_current_block_color(request.hash).await?.ok_or(RpcError::MergerNotFound(request.hash))?;
+        Ok(GetCurrentBlockColorResponse::new(color.is_blue, color.merging_block))
+    }
+
+    async fn get_block_processing_status_call(
+        &self,
+        request: GetBlockProcessingStatusRequest,
+    ) -> RpcResult<GetBlockProcessingStatusResponse> {
+        let (block_hash, report) =
+            self.block_processing_tickets.get(request.ticket).ok_or(RpcError::BlockProcessingTicketNotFound(request.ticket))?;
+        Ok(GetBlockProcessingStatusResponse::new(block_hash, report))
+    }
+
+    async fn start_job_call(&self, request: StartJobRequest) -> RpcResult<StartJobResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("StartJob RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let kind = request.kind;
+        let job_id = match kind {
+            RpcJobKind::UtxoIndexResync => {
+                let utxoindex = self.utxoindex.clone().ok_or(RpcError::JobKindUnavailable(kind))?;
+                self.jobs.start(kind, async move { utxoindex.resync().await.map_err(|err| err.to_string()) })?
+            }
+            RpcJobKind::TxIndexResync => {
+                let txindex = self.txindex.clone().ok_or(RpcError::JobKindUnavailable(kind))?;
+                self.jobs.start(kind, async move { txindex.resync().await.map_err(|err| err.to_string()) })?
+            }
+        };
+        Ok(StartJobResponse::new(job_id))
+    }
+
+    async fn get_job_status_call(&self, request: GetJobStatusRequest) -> RpcResult<GetJobStatusResponse> {
+        let job = self.jobs.get(request.job_id).ok_or(RpcError::JobNotFound(request.job_id))?;
+        Ok(GetJobStatusResponse::new(job))
+    }
+
+    async fn get_notification_stats_call(&self, _: GetNotificationStatsRequest) -> RpcResult<GetNotificationStatsResponse> {
+        let notifiers = self
+            .notification_stats
+            .lock()
+            .iter()
+            .map(|(name, stats)| RpcNotifierStats::new(name.clone(), stats.snapshot().into_iter().map(Into::into).collect()))
+            .collect();
+        Ok(GetNotificationStatsResponse::new(notifiers))
+    }
+
+    async fn generate_blocks_call(&self, request: GenerateBlocksRequest) -> RpcResult<GenerateBlocksResponse> {
+        if *self.config.net != NetworkType::Simnet {
+            return Err(RpcError::UnavailableOutsideSimnet);
+        }
+        if self.config.is_secondary {
+            return Err(RpcError::UnavailableOnSecondaryInstance);
+        }
+        if request.pay_address.prefix != self.config.prefix() {
+            return Err(kaspa_addresses::AddressError::InvalidPrefix(request.pay_address.prefix.to_string()))?;
+        }
+
+        // Simnet skips the proof of work, so every template can be submitted as is. Each submission waits
+        // for the virtual to be resolved, so the next template is built on top of the previous block.
+        let miner_data = MinerData::new(kaspa_txscript::pay_to_address_script(&request.pay_address), version().as_bytes().to_vec());
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let mut block_hashes = Vec::new();
+        for _ in 0..request.count {
+            let block = self.mining_manager.clone().get_block_template(&session, miner_data.clone()).await?.block.to_immutable();
+            let hash = block.hash();
+            self.flow_context.submit_rpc_block(&session, block).await.map_err(|err| RpcError::General(err.to_string()))?;
+            block_hashes.push(hash);
+        }
+
+        // Let the UTXO index catch up, so the address-based methods reflect the generated blocks upon return
+        if let (Some(utxoindex), Some(sink)) = (self.utxoindex.as_ref(), block_hashes.last()) {
+            for _ in 0..GENERATED_BLOCKS_INDEXING_MAX_POLLS {
+                let tips = utxoindex.clone().get_utxo_index_tips().await.map_err(|err| RpcError::General(err.to_string()))?;
+                if tips.contains(sink) {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        }
+        Ok(GenerateBlocksResponse::new(block_hashes))
+    }
+
+    async fn get_bans_call(&self, _: GetBansRequest) -> RpcResult<GetBansResponse> {
+        let now = unix_now();
+        let mut address_manager = self.flow_context.address_manager.lock();
+        let ip_bans = address_manager.get_active_bans().into_iter().map(|(ip, ban)| RpcBan {
+            ip,
+            timestamp: ban.timestamp,
+            remaining_time: ban.remaining(now),
+            reason: ban.reason,
+            prefix_length: None,
+        });
+        let network_bans = address_manager.get_active_network_bans().into_iter().map(|(network, ban)| RpcBan {
+            ip: network.address().into(),
+            timestamp: ban.timestamp,
+            remaining_time: ban.remaining(now),
+            reason: ban.reason,
+            prefix_length: Some(network.prefix_len()),
+        });
+        Ok(GetBansResponse::new(ip_bans.chain(network_bans).collect()))
+    }
+
+    async fn get_connections_call(&self, _: GetConnectionsRequest) -> RpcResult<GetConnectionsResponse> {
+        let peers = self.flow_context.hub().active_peers();
+        Ok(GetConnectionsResponse::new(peers.iter().map(|peer| self.protocol_converter.get_connection_info(peer)).collect()))
+    }
+
     async fn shutdown_call(&self, _: ShutdownRequest) -> RpcResult<ShutdownResponse> {
         if !self.config.unsafe_rpc {
             warn!("Shutdown RPC command called while node in safe RPC mode -- ignoring.");
@@ -783,13 +1509,15 @@ NOTE: This error usually indicates an RPC conversion error between the node and
 
     async fn resolve_finality_conflict_call(
         &self,
-        _request: ResolveFinalityConflictRequest,
+        request: ResolveFinalityConflictRequest,
     ) -> RpcResult<ResolveFinalityConflictResponse> {
         if !self.config.unsafe_rpc {
             warn!("ResolveFinalityConflict RPC command called while node in safe RPC mode -- ignoring.");
             return Err(RpcError::UnavailableInSafeMode);
         }
-        Err(RpcError::NotImplemented)
+        let session = self.consensus_manager.consensus().session().await;
+        session.async_resolve_finality_conflict(request.finality_block_hash).await?;
+        Ok(ResolveFinalityConflictResponse {})
     }
 
     async fn get_metrics_call(&self, req: GetMetricsRequest) -> RpcResult<GetMetricsResponse> {
@@ -866,9 +1594,97 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             None
         };
 
+        let storage_metrics = req.storage_metrics.then(|| {
+            let metrics = self.storage_metrics.metrics();
+            StorageMetrics {
+                data_dir_size: metrics.data_dir_size,
+                cache_hits: metrics.cache_hits,
+                cache_misses: metrics.cache_misses,
+                cache_hit_rate: metrics.cache_hit_rate(),
+                compression_input_bytes: metrics.compression_input_bytes,
+                compression_output_bytes: metrics.compression_output_bytes,
+                compression_ratio: metrics.compression_ratio(),
+                databases: metrics
+                    .databases
+                    .into_iter()
+                    .map(|db| DatabaseMetrics {
+                        name: db.name,
+                        column_families: db
+                            .column_families
+                            .into_iter()
+                            .map(|cf| ColumnFamilyMetrics {
+                                name: cf.name,
+                                sst_files_size: cf.sst_files_size,
+                                live_data_size: cf.live_data_size,
+                                mem_tables_size: cf.mem_tables_size,
+                                estimated_keys: cf.estimated_keys,
+                            })
+                            .collect(),
+                        running_compactions: db.running_compactions,
+                        compaction_pending: db.compaction_pending,
+                        pending_compaction_bytes: db.pending_compaction_bytes,
+                        block_cache_usage: db.block_cache_usage,
+                        block_cache_capacity: db.block_cache_capacity,
+                    })
+                    .collect(),
+            }
+        });
+
+        let request_metrics = req.request_metrics.then(|| RequestMetrics {
+            grpc_methods: self
+                .grpc_request_counters
+                .lock()
+                .as_ref()
+                .map(|counters| {
+                    counters
+                        .snapshot()
+                        .into_iter()
+                        .map(|(method, snapshot)| MethodMetrics {
+                            method,
+                            requests: snapshot.requests,
+                            errors: snapshot.errors,
+                            latency_sum_us: snapshot.latency_sum_us,
+                            latency_buckets: snapshot
+                                .latency_buckets
+                                .into_iter()
+                                .map(|(upper_bound_ms, count)| LatencyBucket { upper_bound_ms, count })
+                                .collect(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            p2p_flows: self
+                .flow_context
+                .hub()
+                .flow_route_snapshots()
+                .into_iter()
+                .map(|(flow, snapshot)| FlowMetrics {
+                    flow,
+                    queue_depth: snapshot.queue_depth,
+                    messages: snapshot.latency.requests,
+                    latency_sum_us: snapshot.latency.latency_sum_us,
+                    latency_buckets: snapshot
+                        .latency
+                        .latency_buckets
+                        .into_iter()
+                        .map(|(upper_bound_ms, count)| LatencyBucket { upper_bound_ms, count })
+                        .collect(),
+                })
+                .collect(),
+            grpc_tenants: self.grpc_tenants.lock().as_ref().map(|tenants| tenants.tenant_metrics()).unwrap_or_default(),
+        });
+
         let server_time = unix_now();
 
-        let response = GetMetricsResponse { server_time, process_metrics, connection_metrics, bandwidth_metrics, consensus_metrics };
+        let response = GetMetricsResponse {
+            server_time,
+            process_metrics,
+            connection_metrics,
+            bandwidth_metrics,
+            consensus_metrics,
+            storage_metrics,
+            request_metrics,
+        };
 
         Ok(response)
     }
@@ -888,10 +1704,70 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         })
     }
 
+    async fn get_server_capabilities_call(&self, _request: GetServerCapabilitiesRequest) -> RpcResult<GetServerCapabilitiesResponse> {
+        let notifications = EVENT_TYPE_ARRAY.iter().map(|event| event.to_string()).collect();
+        Ok(GetServerCapabilitiesResponse::new(RPC_API_VERSION, self.build_info.capabilities.clone(), notifications))
+    }
+
+    async fn update_block_template_exclusions_call(
+        &self,
+        request: UpdateBlockTemplateExclusionsRequest,
+    ) -> RpcResult<UpdateBlockTemplateExclusionsResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("UpdateBlockTemplateExclusions RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let update = TemplateExclusionsUpdate {
+            add_transaction_ids: request.add_transaction_ids,
+            remove_transaction_ids: request.remove_transaction_ids,
+            add_script_public_keys: request.add_script_public_keys,
+            remove_script_public_keys: request.remove_script_public_keys,
+        };
+        let exclusions = self.mining_manager.clone().update_template_exclusions(update).await;
+        Ok(UpdateBlockTemplateExclusionsResponse {
+            transaction_ids: exclusions.transaction_ids().iter().copied().collect(),
+            script_public_keys: exclusions.script_public_keys().iter().cloned().collect(),
+        })
+    }
+
+    async fn get_chain_quality_call(&self, request: GetChainQualityRequest) -> RpcResult<GetChainQualityResponse> {
+        let Some(ref chain_quality_monitor) = self.chain_quality_monitor else {
+            return Err(RpcError::NoChainQuality);
+        };
+        Ok(GetChainQualityResponse { samples: chain_quality_monitor.samples(request.since) })
+    }
+
     async fn get_sync_status_call(&self, _request: GetSyncStatusRequest) -> RpcResult<GetSyncStatusResponse> {
         let session = self.consensus_manager.consensus().unguarded_session();
         let is_synced: bool = self.has_sufficient_peer_connectivity() && session.async_is_nearly_synced().await;
-        Ok(GetSyncStatusResponse { is_synced })
+        let sink_daa_score = session.async_get_header(session.async_get_sink().await).await?.daa_score;
+        let headers_selected_tip_daa_score = session.async_get_header(session.async_get_headers_selected_tip().await).await?.daa_score;
+        let mut response = GetSyncStatusResponse {
+            is_synced,
+            phase: if is_synced { RpcSyncPhase::Synced } else { RpcSyncPhase::Idle },
+            phase_progress: None,
+            processed: 0,
+            estimated_remaining_time: None,
+            sink_daa_score,
+            headers_selected_tip_daa_score,
+            ibd_peer: self.flow_context.ibd_peer_key().map(|peer| peer.to_string()),
+        };
+        if let Some(progress) = self.flow_context.ibd_phase_progress() {
+            response.phase = match progress.phase {
+                IbdPhase::ProofDownload => RpcSyncPhase::ProofDownload,
+                IbdPhase::Headers => RpcSyncPhase::Headers,
+                IbdPhase::UtxoSet => RpcSyncPhase::UtxoSet,
+                IbdPhase::BlockBodies => RpcSyncPhase::BlockBodies,
+            };
+            response.phase_progress = progress.progress.map(|p| p * 100.0);
+            response.processed = progress.processed;
+            response.estimated_remaining_time = progress.estimated_remaining.map(|d| d.as_millis() as u64);
+        } else if sink_daa_score < headers_selected_tip_daa_score {
+            // Block bodies are all known but the virtual state has yet to catch up with the headers selected tip
+            response.phase = RpcSyncPhase::VirtualResolution;
+            response.phase_progress = Some(sink_daa_score as f64 * 100.0 / headers_selected_tip_daa_score as f64);
+        }
+        Ok(response)
     }
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -0,0 +1,77 @@
+use crate::tickets::TicketRegistry;
+use kaspa_rpc_core::{RpcHash, SubmitBlockReport};
+
+/// Maximum number of tickets kept at once, the oldest ones being evicted first
+pub const MAX_BLOCK_PROCESSING_TICKETS: usize = 1024;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    block_hash: RpcHash,
+    report: Option<SubmitBlockReport>,
+}
+
+/// Tracks the processing outcome of the blocks submitted without waiting for their processing.
+///
+/// Each such block is given a ticket, starting from 1, by which its status can be queried until the
+/// ticket gets evicted by newer ones.
+pub struct BlockProcessingTickets {
+    tickets: TicketRegistry<Entry>,
+}
+
+impl BlockProcessingTickets {
+    pub fn new(capacity: usize) -> Self {
+        Self { tickets: TicketRegistry::new(capacity) }
+    }
+
+    /// Registers a block whose processing is pending and returns its ticket
+    pub fn register(&self, block_hash: RpcHash) -> u64 {
+        self.tickets.register(|_| Entry { block_hash, report: None })
+    }
+
+    /// Records the processing outcome of the block of `ticket`, if still tracked
+    pub fn complete(&self, ticket: u64, report: SubmitBlockReport) {
+        self.tickets.update(ticket, |entry| entry.report = Some(report));
+    }
+
+    /// Returns the block hash and, once processed, the submission report of `ticket`
+    pub fn get(&self, ticket: u64) -> Option<(RpcHash, Option<SubmitBlockReport>)> {
+        self.tickets.get(ticket).map(|entry| (entry.block_hash, entry.report))
+    }
+}
+
+impl Default for BlockProcessingTickets {
+    fn default() -> Self {
+        Self::new(MAX_BLOCK_PROCESSING_TICKETS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_rpc_core::SubmitBlockRejectReason;
+
+    #[test]
+    fn test_block_processing_tickets() {
+        let tickets = BlockProcessingTickets::new(2);
+        let hashes = [1, 2, 3].map(RpcHash::from_u64_word);
+        assert_eq!(tickets.register(hashes[0]), 1);
+        assert_eq!(tickets.register(hashes[1]), 2);
+        assert_eq!(tickets.get(1), Some((hashes[0], None)));
+
+        tickets.complete(1, SubmitBlockReport::Success);
+        tickets.complete(2, SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid));
+        assert_eq!(tickets.get(1), Some((hashes[0], Some(SubmitBlockReport::Success))));
+        assert_eq!(tickets.get(2), Some((hashes[1], Some(SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid)))));
+
+        // The oldest ticket gets evicted and completing it is a no-op
+        assert_eq!(tickets.register(hashes[2]), 3);
+        assert_eq!(tickets.get(1), None);
+        tickets.complete(1, SubmitBlockReport::Success);
+        assert_eq!(tickets.get(1), None);
+        assert_eq!(tickets.get(3), Some((hashes[2], None)));
+
+        // Unknown tickets are not tracked
+        assert_eq!(tickets.get(0), None);
+        assert_eq!(tickets.get(4), None);
+    }
+}
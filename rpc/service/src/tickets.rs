@@ -0,0 +1,74 @@
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+
+struct Inner<T> {
+    last_ticket: u64,
+    entries: HashMap<u64, T>,
+    order: VecDeque<u64>,
+}
+
+/// A bounded registry of entries identified by tickets, given sequentially starting from 1.
+///
+/// Entries can be queried by their ticket until they get evicted by newer ones, the oldest being evicted first.
+pub struct TicketRegistry<T> {
+    capacity: usize,
+    inner: Mutex<Inner<T>>,
+}
+
+impl<T: Clone> TicketRegistry<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self { capacity, inner: Mutex::new(Inner { last_ticket: 0, entries: HashMap::new(), order: VecDeque::new() }) }
+    }
+
+    /// Registers the entry built by `entry` from its ticket and returns the ticket
+    pub fn register(&self, entry: impl FnOnce(u64) -> T) -> u64 {
+        let mut inner = self.inner.lock();
+        inner.last_ticket += 1;
+        let ticket = inner.last_ticket;
+        if inner.order.len() == self.capacity {
+            let evicted = inner.order.pop_front().unwrap();
+            inner.entries.remove(&evicted);
+        }
+        inner.entries.insert(ticket, entry(ticket));
+        inner.order.push_back(ticket);
+        ticket
+    }
+
+    /// Applies `update` to the entry of `ticket`, if still tracked
+    pub fn update(&self, ticket: u64, update: impl FnOnce(&mut T)) {
+        if let Some(entry) = self.inner.lock().entries.get_mut(&ticket) {
+            update(entry);
+        }
+    }
+
+    pub fn get(&self, ticket: u64) -> Option<T> {
+        self.inner.lock().entries.get(&ticket).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_registry() {
+        let registry = TicketRegistry::new(3);
+        for expected in 1..=3 {
+            assert_eq!(registry.register(|ticket| ticket * 10), expected);
+        }
+        registry.update(2, |entry| *entry += 1);
+        assert_eq!([1, 2, 3].map(|ticket| registry.get(ticket)), [Some(10), Some(21), Some(30)]);
+
+        // Tickets keep increasing while the oldest entries get evicted
+        assert_eq!(registry.register(|ticket| ticket * 10), 4);
+        assert_eq!(registry.register(|ticket| ticket * 10), 5);
+        assert_eq!([1, 2, 3, 4, 5].map(|ticket| registry.get(ticket)), [None, None, Some(30), Some(40), Some(50)]);
+
+        // Updating an evicted or unknown ticket is a no-op
+        registry.update(1, |entry| *entry += 1);
+        registry.update(6, |entry| *entry += 1);
+        assert_eq!(registry.get(1), None);
+        assert_eq!(registry.get(6), None);
+    }
+}
@@ -0,0 +1,307 @@
+use kaspa_consensus_core::{
+    config::params::Params,
+    constants::TX_VERSION,
+    mass::{Kip9Version, MassCalculator},
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{PopulatedTransaction, ScriptPublicKey, Transaction, TransactionInput, TransactionOutput, UtxoEntry},
+};
+use kaspa_mining::manager::MiningManagerProxy;
+use kaspa_rpc_core::{RpcError, RpcResult, RpcUtxosByAddressesEntry};
+use std::cmp::Reverse;
+
+/// Length of a Schnorr signature script (OP_DATA_65, 64 bytes of signature and the sighash type),
+/// used as a placeholder while estimating the mass of the transaction once signed
+const SCHNORR_SIGNATURE_SCRIPT_LEN: usize = 66;
+
+/// An unsigned transaction along with the UTXOs spent by its inputs
+pub(crate) struct UnsignedTransaction {
+    pub transaction: Transaction,
+    pub utxo_entries: Vec<RpcUtxosByAddressesEntry>,
+    pub fee: u64,
+    pub mass: u64,
+}
+
+/// Selects UTXOs, largest first, until they cover `outputs` along with the fee of the transaction
+/// and builds an unsigned transaction paying the remainder to `change_script_public_key`.
+///
+/// The fee is the mass of the signed transaction (assuming a single Schnorr signature per input) multiplied
+/// by `fee_rate`, and never below the minimum relay fee of the mempool. Once `storage_mass_activated`, the mass
+/// is the consensus overall mass, which adds the storage mass (KIP-9) of the transaction to its compute mass,
+/// and is committed to by the transaction. A change which would be dust or would not cover its own storage
+/// mass is left to the fee instead.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_unsigned_transaction(
+    params: &Params,
+    mut utxos: Vec<RpcUtxosByAddressesEntry>,
+    outputs: Vec<TransactionOutput>,
+    change_script_public_key: ScriptPublicKey,
+    fee_rate: f64,
+    max_mass: u64,
+    storage_mass_activated: bool,
+    mining_manager: &MiningManagerProxy,
+) -> RpcResult<UnsignedTransaction> {
+    // Storage mass is undefined for outputs of zero value
+    if outputs.iter().any(|output| output.value == 0) {
+        return Err(RpcError::General("the amount of an output must be positive".to_string()));
+    }
+    let payment = outputs.iter().try_fold(0u64, |acc, output| acc.checked_add(output.value));
+    let payment = payment.ok_or_else(|| RpcError::General("total amount of the outputs overflows".to_string()))?;
+    let mass_calculator = MassCalculator::new(
+        params.mass_per_tx_byte,
+        params.mass_per_script_pub_key_byte,
+        params.mass_per_sig_op,
+        params.storage_mass_parameter,
+    );
+    let mass_of = |tx: &Transaction, entries: &[UtxoEntry]| {
+        let compute_mass = mass_calculator.calc_tx_compute_mass(tx);
+        if storage_mass_activated {
+            let populated = PopulatedTransaction::new(tx, entries.to_vec());
+            mass_calculator.calc_tx_overall_mass(&populated, Some(compute_mass), Kip9Version::Alpha).unwrap_or(u64::MAX)
+        } else {
+            compute_mass
+        }
+    };
+    let fee_of = |mass: u64| ((mass as f64 * fee_rate).ceil() as u64).max(mining_manager.minimum_required_transaction_relay_fee(mass));
+
+    utxos.sort_by_key(|utxo| Reverse(utxo.utxo_entry.amount));
+
+    let mut inputs = Vec::new();
+    let mut entries = Vec::new();
+    let mut selected = Vec::new();
+    let mut funds = 0u64;
+    let mut required = payment;
+    let mut mass_above_max = None;
+    for utxo in utxos {
+        funds += utxo.utxo_entry.amount;
+        inputs.push(TransactionInput::new(utxo.outpoint, vec![0; SCHNORR_SIGNATURE_SCRIPT_LEN], 0, 1));
+        entries.push(utxo.utxo_entry.clone());
+        selected.push(utxo);
+
+        // The compute mass only grows with the inputs, so exceeding the limit here is final
+        let mut tx_outputs = outputs.clone();
+        tx_outputs.push(TransactionOutput::new(0, change_script_public_key.clone()));
+        let mut tx = Transaction::new_non_finalized(TX_VERSION, inputs.clone(), tx_outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let compute_mass = mass_calculator.calc_tx_compute_mass(&tx);
+        if compute_mass > max_mass {
+            return Err(RpcError::TransactionMassAboveMax(compute_mass, max_mass));
+        }
+
+        // Look for the largest change whose transaction mass is covered by the fee. Lowering the change raises
+        // the storage mass of the transaction, so the fee is raised until it covers the resulting mass
+        let mut fee = fee_of(compute_mass);
+        let mut mass = None;
+        while funds >= payment.saturating_add(fee) {
+            let change = funds - payment - fee;
+            if change == 0
+                || mining_manager.is_transaction_output_dust(&TransactionOutput::new(change, change_script_public_key.clone()))
+            {
+                break;
+            }
+            tx.outputs.last_mut().unwrap().value = change;
+            let change_mass = mass_of(&tx, &entries);
+            if change_mass > max_mass {
+                mass_above_max = Some(change_mass);
+                break;
+            }
+            if fee_of(change_mass) <= fee {
+                mass = Some(change_mass);
+                break;
+            }
+            fee = fee_of(change_mass);
+        }
+
+        let mass = match mass {
+            Some(mass) => mass,
+            None => {
+                // The change is left to the miner
+                tx.outputs.pop();
+                let mass = mass_of(&tx, &entries);
+                if mass > max_mass {
+                    mass_above_max = Some(mass);
+                    continue;
+                }
+                required = payment.saturating_add(fee_of(mass));
+                if funds < required {
+                    continue;
+                }
+                fee = funds - payment;
+                mass
+            }
+        };
+
+        if storage_mass_activated {
+            tx.set_mass(mass);
+        }
+        tx.inputs.iter_mut().for_each(|input| input.signature_script.clear());
+        tx.finalize();
+        return Ok(UnsignedTransaction { transaction: tx, utxo_entries: selected, fee, mass });
+    }
+
+    match mass_above_max {
+        Some(mass) => Err(RpcError::TransactionMassAboveMax(mass, max_mass)),
+        None => Err(RpcError::InsufficientFunds(required, funds)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{config::params::MAINNET_PARAMS, constants::SOMPI_PER_KASPA, tx::TransactionOutpoint};
+    use kaspa_hashes::Hash;
+    use kaspa_mining::{manager::MiningManager, MiningCounters};
+    use std::sync::Arc;
+
+    const MAX_MASS: u64 = 100_000;
+
+    fn mining_manager() -> MiningManagerProxy {
+        let counters = Arc::new(MiningCounters::default());
+        MiningManagerProxy::new(Arc::new(MiningManager::new(MAINNET_PARAMS.target_time_per_block, false, 500_000, None, counters)))
+    }
+
+    fn script_public_key(tag: u8) -> ScriptPublicKey {
+        let mut script = vec![0x20];
+        script.extend([tag; 32]);
+        script.push(0xac);
+        ScriptPublicKey::from_vec(0, script)
+    }
+
+    fn utxos(amounts: &[u64]) -> Vec<RpcUtxosByAddressesEntry> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, &amount)| RpcUtxosByAddressesEntry {
+                address: None,
+                outpoint: TransactionOutpoint::new(Hash::from_u64_word(i as u64 + 1), 0),
+                utxo_entry: UtxoEntry::new(amount, script_public_key(1), 0, false),
+            })
+            .collect()
+    }
+
+    fn build(
+        utxo_amounts: &[u64],
+        payment: u64,
+        max_mass: u64,
+        storage_mass_activated: bool,
+        mining_manager: &MiningManagerProxy,
+    ) -> RpcResult<UnsignedTransaction> {
+        build_unsigned_transaction(
+            &MAINNET_PARAMS,
+            utxos(utxo_amounts),
+            vec![TransactionOutput::new(payment, script_public_key(2))],
+            script_public_key(3),
+            0.0,
+            max_mass,
+            storage_mass_activated,
+            mining_manager,
+        )
+    }
+
+    /// Returns the compute and storage masses of `unsigned` once signed with a Schnorr signature per input
+    fn signed_masses(unsigned: &UnsignedTransaction) -> (u64, u64) {
+        let mass_calculator = MassCalculator::new(
+            MAINNET_PARAMS.mass_per_tx_byte,
+            MAINNET_PARAMS.mass_per_script_pub_key_byte,
+            MAINNET_PARAMS.mass_per_sig_op,
+            MAINNET_PARAMS.storage_mass_parameter,
+        );
+        let mut tx = unsigned.transaction.clone();
+        tx.inputs.iter_mut().for_each(|input| input.signature_script = vec![0; SCHNORR_SIGNATURE_SCRIPT_LEN]);
+        let entries = unsigned.utxo_entries.iter().map(|utxo| utxo.utxo_entry.clone()).collect();
+        let storage_mass = mass_calculator.calc_tx_storage_mass(&PopulatedTransaction::new(&tx, entries), Kip9Version::Alpha).unwrap();
+        (mass_calculator.calc_tx_compute_mass(&tx), storage_mass)
+    }
+
+    #[test]
+    fn test_build_selects_largest_utxos_first() {
+        let mining_manager = mining_manager();
+        let unsigned = build(
+            &[SOMPI_PER_KASPA, 10 * SOMPI_PER_KASPA, 5 * SOMPI_PER_KASPA],
+            12 * SOMPI_PER_KASPA,
+            MAX_MASS,
+            false,
+            &mining_manager,
+        )
+        .unwrap();
+
+        let selected: Vec<_> = unsigned.utxo_entries.iter().map(|utxo| utxo.utxo_entry.amount).collect();
+        assert_eq!(selected, vec![10 * SOMPI_PER_KASPA, 5 * SOMPI_PER_KASPA]);
+        assert_eq!(unsigned.transaction.inputs.len(), 2);
+        assert!(unsigned.transaction.inputs.iter().all(|input| input.signature_script.is_empty()));
+
+        // Before the activation of the storage mass, the mass is the compute mass of the signed transaction
+        let (compute_mass, _) = signed_masses(&unsigned);
+        assert_eq!(unsigned.mass, compute_mass);
+        assert_eq!(unsigned.transaction.mass(), 0);
+        assert_eq!(unsigned.fee, mining_manager.minimum_required_transaction_relay_fee(compute_mass));
+    }
+
+    #[test]
+    fn test_build_change() {
+        let mining_manager = mining_manager();
+        let funds = 10 * SOMPI_PER_KASPA;
+        let payment = 3 * SOMPI_PER_KASPA;
+        let unsigned = build(&[funds], payment, MAX_MASS, false, &mining_manager).unwrap();
+
+        let outputs = &unsigned.transaction.outputs;
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].value, payment);
+        assert_eq!(outputs[1].script_public_key, script_public_key(3));
+        assert_eq!(outputs[1].value, funds - payment - unsigned.fee);
+
+        // A change which would be dust is left to the fee
+        let dust_change = 100;
+        let fee = unsigned.fee;
+        let unsigned = build(&[payment + fee + dust_change], payment, MAX_MASS, false, &mining_manager).unwrap();
+        assert_eq!(unsigned.transaction.outputs.len(), 1);
+        assert_eq!(unsigned.fee, fee + dust_change);
+        assert!(unsigned.fee >= mining_manager.minimum_required_transaction_relay_fee(unsigned.mass));
+    }
+
+    #[test]
+    fn test_build_storage_mass() {
+        let mining_manager = mining_manager();
+        let funds = 10 * SOMPI_PER_KASPA;
+        let payment = funds - SOMPI_PER_KASPA / 2;
+
+        // Once activated, the small change gives the transaction a storage mass which is covered by the fee
+        let unsigned = build(&[funds], payment, MAX_MASS, true, &mining_manager).unwrap();
+        let (compute_mass, storage_mass) = signed_masses(&unsigned);
+        assert!(storage_mass > compute_mass);
+        assert_eq!(unsigned.transaction.outputs.len(), 2);
+        assert_eq!(unsigned.mass, compute_mass + storage_mass);
+        assert_eq!(unsigned.transaction.mass(), unsigned.mass);
+        assert!(unsigned.fee >= mining_manager.minimum_required_transaction_relay_fee(unsigned.mass));
+        assert_eq!(unsigned.transaction.outputs[1].value, funds - payment - unsigned.fee);
+
+        // The same transaction ignores the storage mass before the activation
+        let unsigned = build(&[funds], payment, MAX_MASS, false, &mining_manager).unwrap();
+        assert_eq!(unsigned.mass, signed_masses(&unsigned).0);
+    }
+
+    #[test]
+    fn test_build_mass_limits() {
+        let mining_manager = mining_manager();
+
+        // The compute mass of the many inputs needed to cover the payment exceeds the limit
+        let amounts = vec![SOMPI_PER_KASPA; 200];
+        let result = build(&amounts, 150 * SOMPI_PER_KASPA, MAX_MASS, false, &mining_manager);
+        assert!(matches!(result, Err(RpcError::TransactionMassAboveMax(mass, MAX_MASS)) if mass > MAX_MASS));
+
+        // The storage mass of a tiny payment exceeds the limit once activated
+        let result = build(&[10 * SOMPI_PER_KASPA], 1000, MAX_MASS, true, &mining_manager);
+        assert!(matches!(result, Err(RpcError::TransactionMassAboveMax(mass, MAX_MASS)) if mass > MAX_MASS));
+        assert!(build(&[10 * SOMPI_PER_KASPA], 1000, MAX_MASS, false, &mining_manager).is_ok());
+    }
+
+    #[test]
+    fn test_build_insufficient_funds() {
+        let mining_manager = mining_manager();
+        let result = build(&[SOMPI_PER_KASPA, SOMPI_PER_KASPA], 2 * SOMPI_PER_KASPA, MAX_MASS, false, &mining_manager);
+        assert!(
+            matches!(result, Err(RpcError::InsufficientFunds(required, funds)) if required > funds && funds == 2 * SOMPI_PER_KASPA)
+        );
+
+        let result = build(&[SOMPI_PER_KASPA], 0, MAX_MASS, false, &mining_manager);
+        assert!(matches!(result, Err(RpcError::General(_))));
+    }
+}
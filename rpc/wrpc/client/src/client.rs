@@ -67,6 +67,10 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::HealthAlertNotification,
+            RpcApiOps::MempoolTransactionsEvictedNotification,
+            RpcApiOps::JobCompletedNotification,
+            RpcApiOps::IndexResyncProgressNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -617,7 +621,35 @@ impl RpcApi for KaspaRpcClient {
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            SubmitTransactions,
             Unban,
+            GetTransactionMass,
+            BuildUnsignedTransaction,
+            GetDagStats,
+            GetHealth,
+            GetFeeEstimate,
+            GetVirtualParents,
+            GetConsensusParams,
+            GetTransactionById,
+            SampleChainBlocks,
+            GetConnections,
+            GetBlockByBlueScore,
+            GetBlockByDaaScore,
+            GetChainBlockByTimestamp,
+            GetPruningPointUtxos,
+            GetMempoolInfo,
+            SetMempoolPolicy,
+            GetCurrentBlockColor,
+            GetBans,
+            GetBlockProcessingStatus,
+            StartJob,
+            GetJobStatus,
+            GetNotificationStats,
+            GenerateBlocks,
+            GetServerCapabilities,
+            UpdateBlockTemplateExclusions,
+            GetChainQuality,
+            GetSubscriptions,
         ]
     );
 
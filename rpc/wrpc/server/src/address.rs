@@ -14,27 +14,12 @@ pub enum WrpcNetAddress {
 
 impl WrpcNetAddress {
     pub fn to_address(&self, network_type: &NetworkType, encoding: &WrpcEncoding) -> ContextualNetAddress {
+        let port = Self::default_port(network_type, encoding);
         match self {
-            WrpcNetAddress::Default => {
-                let port = match encoding {
-                    WrpcEncoding::Borsh => network_type.default_borsh_rpc_port(),
-                    WrpcEncoding::SerdeJson => network_type.default_json_rpc_port(),
-                };
-                format!("127.0.0.1:{port}").parse().unwrap()
-            }
-            WrpcNetAddress::Public => {
-                let port = match encoding {
-                    WrpcEncoding::Borsh => network_type.default_borsh_rpc_port(),
-                    WrpcEncoding::SerdeJson => network_type.default_json_rpc_port(),
-                };
-                format!("0.0.0.0:{port}").parse().unwrap()
-            }
+            WrpcNetAddress::Default => format!("127.0.0.1:{port}").parse().unwrap(),
+            WrpcNetAddress::Public => format!("0.0.0.0:{port}").parse().unwrap(),
             WrpcNetAddress::Custom(address) => {
                 if address.port_not_specified() {
-                    let port = match encoding {
-                        WrpcEncoding::Borsh => network_type.default_borsh_rpc_port(),
-                        WrpcEncoding::SerdeJson => network_type.default_json_rpc_port(),
-                    };
                     address.with_port(port)
                 } else {
                     *address
@@ -42,6 +27,14 @@ impl WrpcNetAddress {
             }
         }
     }
+
+    /// Returns the default port of the wRPC server of `network_type`, which differs by `encoding`
+    pub fn default_port(network_type: &NetworkType, encoding: &WrpcEncoding) -> u16 {
+        match encoding {
+            WrpcEncoding::Borsh => network_type.default_borsh_rpc_port(),
+            WrpcEncoding::SerdeJson => network_type.default_json_rpc_port(),
+        }
+    }
 }
 
 impl FromStr for WrpcNetAddress {
@@ -133,7 +133,7 @@ impl Connection {
     {
         match encoding {
             Encoding::Borsh => workflow_rpc::server::protocol::borsh::create_serialized_notification_message(op, msg),
-            Encoding::SerdeJson => workflow_rpc::server::protocol::borsh::create_serialized_notification_message(op, msg),
+            Encoding::SerdeJson => workflow_rpc::server::protocol::serde_json::create_serialized_notification_message(op, msg),
         }
     }
 }
@@ -181,3 +181,25 @@ impl ConnectionT for Connection {
 }
 
 pub type ConnectionReference = Arc<Connection>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_rpc_core::VirtualDaaScoreChangedNotification;
+
+    #[test]
+    fn test_serialized_notification_message_encoding() {
+        let notification = Notification::VirtualDaaScoreChanged(VirtualDaaScoreChangedNotification { virtual_daa_score: 1234 });
+        let op: RpcApiOps = notification.event_type().into();
+
+        // Clients of a JSON endpoint must receive notifications as JSON text, like the responses to their requests
+        let message = Connection::create_serialized_notification_message(Encoding::SerdeJson, op, notification.clone()).unwrap();
+        match message {
+            Message::Text(text) => assert!(text.contains("\"virtualDaaScore\":1234"), "unexpected JSON notification {text}"),
+            message => panic!("a JSON notification should be a text message but got {message:?}"),
+        }
+
+        let message = Connection::create_serialized_notification_message(Encoding::Borsh, op, notification).unwrap();
+        assert!(matches!(message, Message::Binary(_)), "a Borsh notification should be a binary message");
+    }
+}
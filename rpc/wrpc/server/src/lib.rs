@@ -1,4 +1,12 @@
 //! Kaspa wRPC Server (AsyncService) module
+//!
+//! Exposes the same [`RpcApi`](kaspa_rpc_core::api::rpc::RpcApi) as the gRPC server over WebSocket, letting browser
+//! and lightweight clients connect without gRPC tooling. Messages are framed either with Borsh, the compact binary
+//! encoding, or with JSON, see [`WrpcEncoding`](service::WrpcEncoding). kaspad registers one [`WrpcService`](service::WrpcService)
+//! per enabled encoding (`--rpclisten-borsh` and `--rpclisten-json`) next to the gRPC service.
+//!
+//! JSON messages, notifications included, carry the serde representation of the `kaspa_rpc_core` model types with
+//! camelCase field names, so scripting languages can call the node with a plain WebSocket and JSON library.
 pub mod address;
 pub mod collector;
 pub mod connection;
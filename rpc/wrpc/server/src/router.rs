@@ -25,6 +25,8 @@ impl Router {
         // as well as create `Request` and `Response` typenames and using these typenames
         // it will create the RPC method handler.
         // ... `GetInfo` yields: get_info_call() + GetInfoRequest + GetInfoResponse
+        //
+        // `SubmitTransaction` and `SubmitTransactions` are implemented manually below.
         #[allow(unreachable_patterns)]
         let mut interface = build_wrpc_server_interface!(
             server_context.clone(),
@@ -65,11 +67,87 @@ impl Router {
                 ResolveFinalityConflict,
                 Shutdown,
                 SubmitBlock,
-                SubmitTransaction,
                 Unban,
+                GetTransactionMass,
+                BuildUnsignedTransaction,
+                GetDagStats,
+                GetHealth,
+                GetFeeEstimate,
+                GetVirtualParents,
+                GetConsensusParams,
+                GetTransactionById,
+                SampleChainBlocks,
+                GetConnections,
+                GetBlockByBlueScore,
+                GetBlockByDaaScore,
+                GetChainBlockByTimestamp,
+                GetPruningPointUtxos,
+                GetMempoolInfo,
+                SetMempoolPolicy,
+                GetCurrentBlockColor,
+                GetBans,
+                GetBlockProcessingStatus,
+                StartJob,
+                GetJobStatus,
+                GetNotificationStats,
+                GenerateBlocks,
+                UpdateBlockTemplateExclusions,
+                GetChainQuality,
             ]
         );
 
+        // Submitted transactions are attributed to the connection, which is subject to a quota of high-priority transactions
+        interface.method(
+            RpcApiOps::SubmitTransaction,
+            workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, request: SubmitTransactionRequest| {
+                Box::pin(async move {
+                    manager.check_access(RpcApiOps::SubmitTransaction).map_err(|err| err.to_string())?;
+                    let submitter = manager.submitter(&connection);
+                    let response = manager
+                        .rpc_service(&connection)
+                        .submit_transaction_from_call(submitter, request)
+                        .await
+                        .map_err(|err| err.to_string())?;
+                    Ok(response)
+                })
+            }),
+        );
+
+        interface.method(
+            RpcApiOps::SubmitTransactions,
+            workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, request: SubmitTransactionsRequest| {
+                Box::pin(async move {
+                    manager.check_access(RpcApiOps::SubmitTransactions).map_err(|err| err.to_string())?;
+                    let submitter = manager.submitter(&connection);
+                    let response = manager
+                        .rpc_service(&connection)
+                        .submit_transactions_from_call(submitter, request)
+                        .await
+                        .map_err(|err| err.to_string())?;
+                    Ok(response)
+                })
+            }),
+        );
+
+        // The reported methods are restricted to those served by the interface
+        interface.method(
+            RpcApiOps::GetServerCapabilities,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: GetServerCapabilitiesRequest| {
+                    Box::pin(async move {
+                        manager.check_access(RpcApiOps::GetServerCapabilities).map_err(|err| err.to_string())?;
+                        let mut response = manager
+                            .rpc_service(&connection)
+                            .get_server_capabilities_call(request)
+                            .await
+                            .map_err(|err| err.to_string())?;
+                        response.methods.retain(|method| manager.is_allowed(method));
+                        Ok(response)
+                    })
+                },
+            ),
+        );
+
         interface.method(
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Scope| {
@@ -80,6 +158,16 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::GetSubscriptions,
+            workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, _request: GetSubscriptionsRequest| {
+                Box::pin(async move {
+                    let response = manager.get_subscriptions(&connection).await.map_err(|err| err.to_string())?;
+                    Ok(response)
+                })
+            }),
+        );
+
         interface.method(
             RpcApiOps::Unsubscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Scope| {
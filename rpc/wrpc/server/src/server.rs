@@ -15,11 +15,17 @@ use kaspa_notify::{
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    GetSubscriptionsRequest, GetSubscriptionsResponse, Notification, RpcResult,
+};
+use kaspa_rpc_service::{
+    access::{op_name, RpcAccessPolicy, RpcInterface},
+    service::RpcCoreService,
 };
-use kaspa_rpc_service::service::RpcCoreService;
 use std::{
     collections::HashMap,
     sync::{
@@ -43,6 +49,8 @@ struct ServerInner {
     pub sockets: Mutex<HashMap<u64, Connection>>,
     pub rpc_core: Option<RpcCore>,
     pub options: Arc<Options>,
+    pub interface: RpcInterface,
+    pub access_policy: Arc<RpcAccessPolicy>,
 }
 
 #[derive(Clone)]
@@ -64,6 +72,17 @@ impl Server {
             "invalid setup: Server must exclusively get either a core service or a gRPC server address"
         );
 
+        let interface = match encoding {
+            Encoding::Borsh => RpcInterface::WrpcBorsh,
+            Encoding::SerdeJson => RpcInterface::WrpcJson,
+        };
+
+        // A proxy relies on the access policy of the gRPC server it connects to
+        let access_policy = match core_service {
+            Some(ref service) => service.access_policy(interface),
+            None => Default::default(),
+        };
+
         let rpc_core = if let Some(service) = core_service {
             // Prepare rpc service objects
             let notification_channel = NotificationChannel::default();
@@ -86,6 +105,7 @@ impl Server {
                 tasks,
                 policies,
             ));
+            service.register_notification_stats(interface.as_str(), wrpc_notifier.stats());
             Some(RpcCore { service, wrpc_notifier })
         } else {
             None
@@ -98,6 +118,8 @@ impl Server {
                 sockets: Mutex::new(HashMap::new()),
                 rpc_core,
                 options,
+                interface,
+                access_policy,
             }),
         }
     }
@@ -168,6 +190,21 @@ impl Server {
         self.inner.rpc_core.as_ref().map(|x| x.wrpc_notifier.clone())
     }
 
+    /// Checks that the access policy of the server allows `op` to be called
+    pub fn check_access(&self, op: RpcApiOps) -> RpcResult<()> {
+        self.inner.access_policy.check(&op_name(&op))
+    }
+
+    /// Tells if the access policy of the server allows the method named `method` to be called
+    pub fn is_allowed(&self, method: &str) -> bool {
+        self.inner.access_policy.is_allowed(method)
+    }
+
+    /// Returns the label attributing the transactions submitted through `connection` to it
+    pub fn submitter(&self, connection: &Connection) -> String {
+        format!("{}/{}", self.inner.interface, connection.id())
+    }
+
     pub fn rpc_service(&self, connection: &Connection) -> DynRpcService {
         if let Some(rpc_core) = &self.inner.rpc_core {
             rpc_core.service.clone()
@@ -212,6 +249,19 @@ impl Server {
         Ok(())
     }
 
+    pub async fn get_subscriptions(&self, connection: &Connection) -> RpcResult<GetSubscriptionsResponse> {
+        if let Some(rpc_core) = &self.inner.rpc_core {
+            let subscriptions = connection
+                .listener_id()
+                .map(|listener_id| rpc_core.wrpc_notifier.listener_subscriptions(listener_id))
+                .unwrap_or_default();
+            Ok(GetSubscriptionsResponse::new(subscriptions.into_iter().map(Into::into).collect()))
+        } else {
+            // The gRPC client of the connection is the listener the proxied node keeps the subscriptions of
+            connection.grpc_client().get_subscriptions_call(GetSubscriptionsRequest {}).await
+        }
+    }
+
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
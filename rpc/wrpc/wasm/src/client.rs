@@ -825,7 +825,7 @@ impl RpcClient {
     pub async fn subscribe_utxos_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
         if let Some(listener_id) = self.listener_id() {
             let addresses: Vec<Address> = addresses.try_into()?;
-            self.inner.client.start_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope { addresses })).await?;
+            self.inner.client.start_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope::new(addresses))).await?;
         } else {
             log_error!("RPC subscribe on a closed connection");
         }
@@ -839,7 +839,7 @@ impl RpcClient {
     pub async fn unsubscribe_utxos_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
         if let Some(listener_id) = self.listener_id() {
             let addresses: Vec<Address> = addresses.try_into()?;
-            self.inner.client.stop_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope { addresses })).await?;
+            self.inner.client.stop_notify(listener_id, Scope::UtxosChanged(UtxosChangedScope::new(addresses))).await?;
         } else {
             log_error!("RPC unsubscribe on a closed connection");
         }
@@ -912,6 +912,23 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a health alert notification event.
+    /// Health alert notification event is produced when the node observes
+    /// an abnormal condition of the Kaspa BlockDAG or when such condition clears.
+    HealthAlert,
+    /// Manage subscription for a mempool transactions evicted notification event.
+    /// Mempool transactions evicted notification event is produced when a re-evaluation
+    /// of the mempool, following a pruning point move or a consensus parameter activation,
+    /// evicts transactions which became invalid.
+    MempoolTransactionsEvicted,
+    /// Manage subscription for a job completed notification event.
+    /// Job completed notification event is produced when a job started
+    /// with `startJob` completes or fails.
+    JobCompleted,
+    /// Manage subscription for an index resync progress notification event.
+    /// Index resync progress notification event is produced periodically while
+    /// an index is being resynced and once the resync is over.
+    IndexResyncProgress,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -979,6 +996,25 @@ build_wrpc_wasm_bindgen_interface!(
         /// Obtains basic information about the synchronization status of the Kaspa node.
         /// Returned information: Syncing status.
         GetSyncStatus,
+        /// Retrieves the abnormal DAG conditions (tip count, red rate, blue work
+        /// stagnation) currently observed by the node.
+        /// Returned information: Degraded status, active health alerts.
+        GetHealth,
+        /// Estimates the feerates a transaction should pay for getting into a block
+        /// soon, within a minute or within an hour given the mempool congestion.
+        /// Returned information: Priority, normal and low feerate buckets.
+        GetFeeEstimate,
+        /// Retrieves the current virtual parents along with the candidate tips
+        /// which were excluded from the virtual parents and the reason of the exclusion.
+        /// Returned information: Selected parent, parents, excluded candidates.
+        GetVirtualParents,
+        /// Retrieves the consensus parameters of the network the node is running on,
+        /// such as GHOSTDAG K, window sizes, mass limits, activation DAA scores and subsidy.
+        GetConsensusParams,
+        /// Returns the notification scopes this client is currently subscribed to, along
+        /// with the size of the server-side address set of a UtxosChanged subscription.
+        /// Returned information: Active subscriptions.
+        GetSubscriptions,
     ],
     [
         // functions with `request` argument
@@ -1044,9 +1080,87 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits a transaction to the Kaspa network.
         /// Returned information: None.
         SubmitTransaction,
+        /// Submits a list of transactions, possibly chained, to the Kaspa network.
+        /// Returned information: A result per transaction.
+        SubmitTransactions,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Kaspa node again.
         /// Returned information: None.
         Unban,
+        /// Computes the mass components of a transaction (size, script public
+        /// key and signature operations mass) along with the applicable
+        /// mass limits.
+        /// Returned information: Transaction mass breakdown and limits.
+        GetTransactionMass,
+        /// Selects UTXOs of the given addresses and builds an unsigned transaction
+        /// paying the requested outputs. Requires the node to run with
+        /// `--utxoindex` and `--enable-transaction-builder-rpc`.
+        BuildUnsignedTransaction,
+        /// Retrieves the current tip count along with the average mergeset size,
+        /// red block rate and average number of parents over a window of recent
+        /// selected chain blocks.
+        GetDagStats,
+        /// Retrieves the block containing a transaction accepted by the selected
+        /// chain along with its acceptance status and confirmations. Requires the
+        /// node to run with `--txindex`.
+        /// Returned information: Containing block, accepting block, confirmations.
+        GetTransactionById,
+        /// Samples random selected chain blocks of a blue score range, each block being
+        /// drawn with a probability proportional to the work it adds to the chain.
+        /// Returned information: The headers of the sampled blocks.
+        SampleChainBlocks,
+        /// Retrieves the current p2p connections along with the bytes and messages exchanged.
+        /// Returned information: Peer id, address, connection age, bytes sent and received and message counts by type.
+        GetConnections,
+        /// Retrieves the lowest selected chain block with a blue score greater than or equal to the requested one.
+        /// Returned information: The block, with the level of detail set by the requested verbosity.
+        GetBlockByBlueScore,
+        /// Retrieves the lowest selected chain block with a DAA score greater than or equal to the requested one.
+        /// Returned information: The block, with the level of detail set by the requested verbosity.
+        GetBlockByDaaScore,
+        /// Retrieves the selected chain block with the timestamp nearest to the requested unix time in milliseconds.
+        /// Returned information: The block, with the level of detail set by the requested verbosity.
+        GetChainBlockByTimestamp,
+        /// Pages through the UTXO set of the pruning point, in outpoint order.
+        /// Returned information: The pruning point, its UTXO commitment, a page of its UTXO set and the cursor of the next page.
+        GetPruningPointUtxos,
+        /// Retrieves the mempool policy in force and the mempool occupancy.
+        /// Returned information: The transaction and orphan counts with the expire intervals and maximum counts in force.
+        GetMempoolInfo,
+        /// Adjusts the mempool policy of the node at runtime, within the bounds set by its configuration.
+        /// Returned information: The mempool policy in force after the update.
+        SetMempoolPolicy,
+        /// Returns whether a block is currently blue or red from the perspective of the virtual,
+        /// along with the selected chain block merging it.
+        GetCurrentBlockColor,
+        /// Returns the IP addresses currently banned by the node.
+        /// Returned information: The ban reason and remaining ban time of each address.
+        GetBans,
+        /// Returns the processing status of a block submitted with `allowNonBlocking`.
+        /// Returned information: The block hash and, once processed, the submission report.
+        GetBlockProcessingStatus,
+        /// Starts a long-running node operation (index resync) as a job and returns its id.
+        /// The job status can be polled with {@link RpcClient.getJobStatus}.
+        /// Requires the node to run with the `--unsaferpc` flag.
+        StartJob,
+        /// Polls the status of a job started with {@link RpcClient.startJob}.
+        GetJobStatus,
+        /// Returns the notification delivery statistics of the node notifiers.
+        /// Returned information: The emitted, delivered and dropped notification counts and the average fan-out, by event type.
+        GetNotificationStats,
+        /// Mines blocks paying to the given address and returns their hashes once processed.
+        /// Only available on simnet, where it replaces the mining of blocks by external miners in tests.
+        GenerateBlocks,
+        /// Returns the RPC methods and the notification types supported by the node,
+        /// letting clients adapt to nodes of another version.
+        GetServerCapabilities,
+        /// Adds or removes transaction ids and script public keys excluded from the block templates built by the node.
+        /// Returned information: The exclusions in force after the update.
+        /// Requires the node to run with the `--unsaferpc` flag.
+        UpdateBlockTemplateExclusions,
+        /// Returns the rolling chain-quality samples computed by the node, oldest first.
+        /// Returned information: Red rate, average propagation delay and miner concentration of every sampling interval.
+        /// Requires the node to run with the `--chain-quality` flag.
+        GetChainQuality,
     ]
 );
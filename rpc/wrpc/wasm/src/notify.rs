@@ -21,6 +21,10 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    HealthAlert = "health-alert",
+    MempoolTransactionsEvicted = "mempool-transactions-evicted",
+    JobCompleted = "job-completed",
+    IndexResyncProgress = "index-resync-progress",
 }
 
 /**
@@ -36,7 +40,11 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IHealthAlert 
+    | IMempoolTransactionsEvicted 
+    | IJobCompleted 
+    | IIndexResyncProgressEvent;
 
 /**
  * RPC notification event data map.
@@ -55,6 +63,10 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "health-alert" : IHealthAlert,
+    "mempool-transactions-evicted" : IMempoolTransactionsEvicted,
+    "job-completed" : IJobCompleted,
+    "index-resync-progress" : IIndexResyncProgressEvent,
 }
 
 /**
@@ -80,6 +92,10 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeHealthAlert},
+ * {@link RpcClient.subscribeMempoolTransactionsEvicted},
+ * {@link RpcClient.subscribeJobCompleted},
+ * {@link RpcClient.subscribeIndexResyncProgress},
  * 
  * @category Node RPC
  */
@@ -237,3 +253,67 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IHealthAlert,
+    r#"
+    /**
+     * Health alert notification event is produced when the node observes
+     * an abnormal condition of the Kaspa BlockDAG (tip count above threshold,
+     * red rate spike, blue work stagnation) or when such condition clears.
+     * 
+     * @category Node RPC
+     */
+    export interface IHealthAlert {
+        [key: string]: any;
+    }
+    "#,
+}
+
+declare! {
+    IMempoolTransactionsEvicted,
+    r#"
+    /**
+     * Mempool transactions evicted notification event is produced when a
+     * re-evaluation of the mempool, following a pruning point move or a
+     * consensus parameter activation, evicts transactions which became invalid.
+     * 
+     * @category Node RPC
+     */
+    export interface IMempoolTransactionsEvicted {
+        transactionIds : HexString[];
+        reason : string;
+    }
+    "#,
+}
+
+declare! {
+    IJobCompleted,
+    r#"
+    /**
+     * Job completed notification event is produced when a job started
+     * with {@link RpcClient.startJob} completes or fails.
+     * 
+     * @category Node RPC
+     */
+    export interface IJobCompleted {
+        job : IJob;
+    }
+    "#,
+}
+
+declare! {
+    IIndexResyncProgressEvent,
+    r#"
+    /**
+     * Index resync progress notification event is produced periodically while
+     * an index is being resynced and once the resync is over.
+     * 
+     * @category Node RPC
+     */
+    export interface IIndexResyncProgressEvent {
+        progress : IIndexResyncProgress;
+        completed : boolean;
+    }
+    "#,
+}
@@ -22,7 +22,7 @@ use kaspa_consensus_core::{
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::{info, task::service::AsyncService, task::tick::TickService, time::unix_now, trace, warn};
 use kaspa_database::prelude::ConnBuilder;
-use kaspa_database::{create_temp_db, load_existing_db};
+use kaspa_database::{create_ephemeral_db, create_temp_db, load_existing_db};
 use kaspa_hashes::Hash;
 use kaspa_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use kaspa_utils::fd_budget;
@@ -59,8 +59,8 @@ struct Args {
     #[arg(short = 'n', long)]
     target_blocks: Option<u64>,
 
-    /// Number of pool-thread threads used by the header and body processors.
-    /// Defaults to the number of logical CPU cores.
+    /// Number of pool-thread threads used by each of the header and body processors.
+    /// Defaults to half the number of logical CPU cores for headers and to all of them for bodies.
     #[arg(short, long)]
     processors_threads: Option<usize>,
 
@@ -117,6 +117,10 @@ struct Args {
     rocksdb_files_limit: Option<i32>,
     #[arg(long)]
     rocksdb_mem_budget: Option<usize>,
+
+    /// Keep the simulation DBs in memory instead of temp directories (ignored for the DB saved to the output directory)
+    #[arg(long, default_value_t = false)]
+    ephemeral: bool,
 }
 
 #[cfg(feature = "heap")]
@@ -190,6 +194,9 @@ fn main_impl(mut args: Args) {
     if !args.test_pruning {
         builder = builder.set_archival();
     }
+    if args.ephemeral {
+        builder = builder.set_ephemeral();
+    }
     let config = Arc::new(builder.build());
     let default_fd = fd_budget::limit() / 2;
     let mut conn_builder = ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd);
@@ -215,6 +222,7 @@ fn main_impl(mut args: Args) {
         let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
         let consensus = Arc::new(Consensus::new(
             db,
+            None,
             config.clone(),
             Default::default(),
             notification_root,
@@ -246,11 +254,16 @@ fn main_impl(mut args: Args) {
     }
 
     // Benchmark the DAG validation time
-    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
+    let conn_builder2 = ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd);
+    let (_lifetime2, db2) = match config.ephemeral {
+        true => create_ephemeral_db!(conn_builder2),
+        false => create_temp_db!(conn_builder2),
+    };
     let (dummy_notification_sender, _) = unbounded();
     let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
     let consensus2 = Arc::new(Consensus::new(
         db2,
+        None,
         config.clone(),
         Default::default(),
         notification_root,
@@ -324,7 +337,8 @@ fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
 
 fn apply_args_to_perf_params(args: &Args, perf_params: &mut PerfParams) {
     if let Some(processors_pool_threads) = args.processors_threads {
-        perf_params.block_processors_num_threads = processors_pool_threads;
+        perf_params.header_processor_num_threads = processors_pool_threads;
+        perf_params.body_processor_num_threads = processors_pool_threads;
     }
     if let Some(virtual_pool_threads) = args.virtual_threads {
         perf_params.virtual_processor_num_threads = virtual_pool_threads;
@@ -13,7 +13,7 @@ use kaspa_consensus_core::tx::{
     MutableTransaction, ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
 };
 use kaspa_consensus_core::utxo::utxo_view::UtxoView;
-use kaspa_core::trace;
+use kaspa_core::{time::VirtualClock, trace};
 use kaspa_utils::sim::{Environment, Process, Resumption, Suspension};
 use rand::rngs::ThreadRng;
 use rand::Rng;
@@ -77,6 +77,9 @@ pub struct Miner {
 
     // Mass calculator
     mass_calculator: MassCalculator,
+
+    // The clock of the consensus instances, following simulation time
+    clock: Arc<VirtualClock>,
 }
 
 impl Miner {
@@ -90,6 +93,7 @@ impl Miner {
         params: &Params,
         target_txs_per_block: u64,
         target_blocks: Option<u64>,
+        clock: Arc<VirtualClock>,
     ) -> Self {
         let (schnorr_public_key, _) = pk.x_only_public_key();
         let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize()).chain(once(0xac)).collect_vec(); // TODO: Use script builder when available to create p2pk properly
@@ -114,6 +118,7 @@ impl Miner {
                 params.mass_per_sig_op,
                 params.storage_mass_parameter,
             ),
+            clock,
         }
     }
 
@@ -254,6 +259,7 @@ impl Miner {
 
 impl Process<Block> for Miner {
     fn resume(&mut self, resumption: Resumption<Block>, env: &mut Environment<Block>) -> Suspension {
+        self.clock.set(env.now());
         match resumption {
             Resumption::Initial => self.sample_mining_interval(),
             Resumption::Scheduled => self.mine(env),
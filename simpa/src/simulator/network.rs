@@ -1,6 +1,6 @@
 use async_channel::unbounded;
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
-use kaspa_core::time::unix_now;
+use kaspa_core::time::{unix_now, VirtualClock};
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
@@ -11,7 +11,7 @@ use kaspa_consensus::consensus::Consensus;
 use kaspa_consensus_core::block::Block;
 use kaspa_database::prelude::ConnBuilder;
 use kaspa_database::utils::DbLifetime;
-use kaspa_database::{create_permanent_db, create_temp_db};
+use kaspa_database::{create_ephemeral_db, create_permanent_db, create_temp_db};
 use kaspa_utils::fd_budget;
 use kaspa_utils::sim::Simulation;
 
@@ -51,6 +51,9 @@ impl KaspaNetworkSimulator {
         rocksdb_files_limit: Option<i32>,
         rocksdb_mem_budget: Option<usize>,
     ) -> &mut Self {
+        // Consensus instances are driven by simulation time, making timestamp validation deterministic
+        let clock = Arc::new(VirtualClock::new(self.config.genesis.timestamp));
+        self.config = Arc::new(self.config.to_builder().set_clock(clock.clone()).build());
         let secp = secp256k1::Secp256k1::new();
         let mut rng = rand::thread_rng();
         for i in 0..num_miners {
@@ -68,6 +71,8 @@ impl KaspaNetworkSimulator {
                 (true, Some(dir), true, None) => create_permanent_db!(dir, builder.enable_stats()),
                 (true, Some(dir), false, _) => create_permanent_db!(dir, builder),
 
+                _ if self.config.ephemeral => create_ephemeral_db!(builder),
+
                 (_, _, true, Some(rocksdb_stats_period_sec)) => {
                     create_temp_db!(builder.enable_stats().with_stats_period(rocksdb_stats_period_sec))
                 }
@@ -79,6 +84,7 @@ impl KaspaNetworkSimulator {
             let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
             let consensus = Arc::new(Consensus::new(
                 db,
+                None,
                 self.config.clone(),
                 Default::default(),
                 notification_root,
@@ -98,6 +104,7 @@ impl KaspaNetworkSimulator {
                 &self.config,
                 target_txs_per_block,
                 self.target_blocks,
+                clock.clone(),
             ));
             self.simulation.register(i, miner_process);
             self.consensuses.push((consensus, handles, lifetime));
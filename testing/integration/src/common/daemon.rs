@@ -31,8 +31,8 @@ pub struct ClientManager {
 impl ClientManager {
     pub fn new(args: Args) -> Self {
         let network = args.network();
-        let context = SubscriptionContext::with_options(None);
-        let rpc_port = args.rpclisten.unwrap().normalize(0).port;
+        let context = SubscriptionContext::with_options(None, None);
+        let rpc_port = args.rpclisten[0].address.normalize(0).port;
         let p2p_port = args.listen.unwrap().normalize(0).port;
         let args = RwLock::new(args);
         Self { args, network, context, rpc_port, p2p_port }
@@ -99,7 +99,9 @@ pub struct Daemon {
 impl Daemon {
     pub fn fill_args_with_random_ports(args: &mut Args) {
         // This should ask the OS to allocate free port for socket 1 to 4.
-        let socket1 = std::net::TcpListener::bind(format!("127.0.0.1:{}", args.rpclisten.map_or(0, |x| x.normalize(0).port))).unwrap();
+        let socket1 =
+            std::net::TcpListener::bind(format!("127.0.0.1:{}", args.rpclisten.first().map_or(0, |x| x.address.normalize(0).port)))
+                .unwrap();
         let rpc_port = socket1.local_addr().unwrap().port();
 
         let socket2 = std::net::TcpListener::bind(format!("127.0.0.1:{}", args.listen.map_or(0, |x| x.normalize(0).port))).unwrap();
@@ -116,7 +118,7 @@ impl Daemon {
         drop(socket3);
         drop(socket4);
 
-        args.rpclisten = Some(format!("0.0.0.0:{rpc_port}").try_into().unwrap());
+        args.rpclisten = vec![format!("0.0.0.0:{rpc_port}").parse().unwrap()];
         args.listen = Some(format!("0.0.0.0:{p2p_port}").try_into().unwrap());
         args.rpclisten_json = Some(format!("0.0.0.0:{rpc_json_port}").parse().unwrap());
         args.rpclisten_borsh = Some(format!("0.0.0.0:{rpc_borsh_port}").parse().unwrap());
@@ -21,12 +21,14 @@ use kaspa_consensus::pipeline::monitor::ConsensusMonitor;
 use kaspa_consensus::pipeline::ProcessingCounters;
 use kaspa_consensus::processes::reachability::tests::{DagBlock, DagBuilder, StoreValidationExtensions};
 use kaspa_consensus::processes::window::{WindowManager, WindowType};
-use kaspa_consensus_core::api::{BlockValidationFutures, ConsensusApi};
+use kaspa_consensus_core::api::{BlockColor, BlockValidationFutures, ConsensusApi};
 use kaspa_consensus_core::block::Block;
 use kaspa_consensus_core::blockhash::new_unique;
 use kaspa_consensus_core::blockstatus::BlockStatus;
 use kaspa_consensus_core::constants::{BLOCK_VERSION, STORAGE_MASS_PARAMETER};
 use kaspa_consensus_core::errors::block::{BlockProcessResult, RuleError};
+use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_consensus_core::errors::difficulty::DifficultyError;
 use kaspa_consensus_core::header::Header;
 use kaspa_consensus_core::network::{NetworkId, NetworkType::Mainnet};
 use kaspa_consensus_core::subnets::SubnetworkId;
@@ -37,7 +39,7 @@ use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensus_notify::service::NotifyService;
 use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::task::tick::TickService;
-use kaspa_core::time::unix_now;
+use kaspa_core::time::{unix_now, VirtualClock};
 use kaspa_database::utils::get_kaspa_tempdir;
 use kaspa_hashes::Hash;
 
@@ -49,7 +51,7 @@ use kaspa_core::signals::Shutdown;
 use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_core::{assert_match, info};
 use kaspa_database::create_temp_db;
-use kaspa_database::prelude::{CachePolicy, ConnBuilder};
+use kaspa_database::prelude::{CachePolicy, CompressionConfig, ConnBuilder, DB};
 use kaspa_index_processor::service::IndexService;
 use kaspa_math::Uint256;
 use kaspa_muhash::MuHash;
@@ -202,6 +204,24 @@ async fn consensus_sanity_test() {
     consensus.shutdown(wait_handles);
 }
 
+#[tokio::test]
+async fn ephemeral_consensus_sanity_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().set_ephemeral().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let mut tip = MAINNET_PARAMS.genesis.hash;
+    for i in 2..10u64 {
+        let hash: Hash = i.into();
+        consensus.add_block_with_parents(hash, vec![tip]).await.unwrap();
+        tip = hash;
+    }
+    assert_eq!(consensus.get_sink(), tip);
+
+    consensus.shutdown(wait_handles);
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct GhostdagTestDag {
     #[serde(rename = "K")]
@@ -459,6 +479,65 @@ async fn header_in_isolation_validation_test() {
     consensus.shutdown(wait_handles);
 }
 
+#[tokio::test]
+async fn virtual_clock_timestamp_validation_test() {
+    init_allocator_with_default_settings();
+    let clock = Arc::new(VirtualClock::new(MAINNET_PARAMS.genesis.timestamp + 60_000));
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().set_clock(clock.clone()).build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+    let max_deviation = config.timestamp_deviation_tolerance(0) * 1000;
+
+    // A block too far into the future of the virtual clock is rejected, whatever the system time
+    let mut block = consensus.build_block_with_parents(2.into(), vec![config.genesis.hash]);
+    let block_ts = clock.unix_now() + max_deviation + 1;
+    block.header.timestamp = block_ts;
+    match consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await {
+        Err(RuleError::TimeTooFarIntoTheFuture(ts, max_block_time)) => {
+            assert_eq!(ts, block_ts);
+            assert_eq!(max_block_time, clock.unix_now() + max_deviation);
+        }
+        res => panic!("Unexpected result: {res:?}"),
+    }
+
+    // Once the clock catches up, a block with the same timestamp is accepted
+    clock.advance(1);
+    let mut block = consensus.build_block_with_parents(3.into(), vec![config.genesis.hash]);
+    block.header.timestamp = clock.unix_now() + max_deviation;
+    consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn current_block_color_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| p.ghostdag_k = 1).build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Three parallel blocks merged by a single block: with k = 1, one of them must be red
+    let parallel_blocks: Vec<Hash> = vec![2.into(), 3.into(), 4.into()];
+    for &hash in parallel_blocks.iter() {
+        consensus.add_block_with_parents(hash, vec![config.genesis.hash]).await.unwrap();
+    }
+    let merging_block: Hash = 5.into();
+    consensus.add_block_with_parents(merging_block, parallel_blocks.clone()).await.unwrap();
+    let sink: Hash = 6.into();
+    consensus.add_block_with_parents(sink, vec![merging_block]).await.unwrap();
+
+    let colors = parallel_blocks.iter().map(|&hash| consensus.get_current_block_color(hash).unwrap().unwrap()).collect::<Vec<_>>();
+    assert!(colors.iter().all(|color| color.merging_block == merging_block));
+    assert_eq!(colors.iter().filter(|color| color.is_blue).count(), 2);
+
+    assert_eq!(consensus.get_current_block_color(merging_block).unwrap(), Some(BlockColor { is_blue: true, merging_block: sink }));
+    // The sink was not merged yet
+    assert_eq!(consensus.get_current_block_color(sink).unwrap(), None);
+    assert_match!(consensus.get_current_block_color(7.into()), Err(ConsensusError::HeaderNotFound(_)));
+
+    consensus.shutdown(wait_handles);
+}
+
 #[tokio::test]
 async fn incest_test() {
     init_allocator_with_default_settings();
@@ -871,6 +950,39 @@ async fn goref_tx_small_concurrent_test() {
     json_test("testdata/dags_for_json_tests/goref-905-tx-265-blocks", true).await
 }
 
+#[test]
+fn compressed_block_bodies_disk_savings_test() {
+    // Measured on these bodies: plain zstd at level 3 stores ~47% of the bincode size, and ~40% once the
+    // dictionary is trained. The bounds below leave some slack over these ratios
+    fn stored_bytes(db: &DB) -> usize {
+        db.iterator(rocksdb::IteratorMode::Start).map(|item| item.unwrap()).map(|(key, value)| key.len() + value.len()).sum()
+    }
+
+    let bodies = gzip_file_lines(Path::new("testdata/dags_for_json_tests/goref-905-tx-265-blocks/blocks.json.gz"))
+        .filter_map(|line| serde_json::from_str::<RPCBlock>(&line).ok())
+        .map(rpc_block_to_block)
+        .collect_vec();
+    let compression = CompressionConfig { training_samples: 64, ..Default::default() };
+
+    let (_plain_db_lifetime, plain_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let (_compressed_db_lifetime, compressed_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let plain_store = DbBlockTransactionsStore::new(plain_db.clone(), CachePolicy::Empty);
+    let compressed_store =
+        DbBlockTransactionsStore::new(compressed_db.clone(), CachePolicy::Empty).with_compression(Some(compression));
+    for block in bodies.iter() {
+        plain_store.insert(block.hash(), block.transactions.clone()).unwrap();
+        compressed_store.insert(block.hash(), block.transactions.clone()).unwrap();
+    }
+
+    let (plain, compressed) = (stored_bytes(&plain_db), stored_bytes(&compressed_db));
+    let ratio = compressed as f64 / plain as f64;
+    info!("Block bodies: {} bytes stored uncompressed, {} bytes compressed (ratio {:.3})", plain, compressed, ratio);
+    assert!(ratio < 0.55, "compressed bodies take {ratio:.3} of the uncompressed size");
+    for block in bodies.iter() {
+        assert_eq!(compressed_store.get(block.hash()).unwrap(), block.transactions);
+    }
+}
+
 #[ignore]
 #[tokio::test]
 async fn goref_tx_big_test() {
@@ -954,6 +1066,7 @@ async fn json_test(file_path: &str, concurrency: bool) {
         &notify_service.notifier(),
         subscription_context.clone(),
         Some(UtxoIndexProxy::new(utxoindex.clone())),
+        None,
     ));
 
     let async_runtime = Arc::new(AsyncRuntime::new(2));
@@ -1328,6 +1441,164 @@ async fn bounded_merge_depth_test() {
     consensus.shutdown(wait_handles);
 }
 
+#[tokio::test]
+async fn sample_chain_blocks_by_work_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // In a linear chain, the blue score of a block is its position in the chain
+    let mut chain = vec![config.genesis.hash];
+    for i in 1..=20u64 {
+        let hash: Hash = (i + 1).into();
+        consensus.add_block_with_parents(hash, vec![*chain.last().unwrap()]).await.unwrap();
+        chain.push(hash);
+    }
+
+    let samples = consensus.sample_chain_blocks_by_work(5, 10, 100).unwrap();
+    assert_eq!(samples.len(), 100);
+    let positions = samples.iter().map(|hash| chain.iter().position(|x| x == hash).unwrap()).collect_vec();
+    assert!(positions.iter().all(|position| (5..=10).contains(position)), "samples should lie in the blue score range");
+    assert!(positions.windows(2).all(|w| w[0] <= w[1]), "samples should be in ascending blue score order");
+
+    // A range above the sink yields no sample
+    assert!(consensus.sample_chain_blocks_by_work(30, 40, 10).unwrap().is_empty());
+
+    assert_match!(consensus.sample_chain_blocks_by_work(10, 5, 1), Err(ConsensusError::InvalidBlueScoreRange(10, 5)));
+    assert_match!(consensus.sample_chain_blocks_by_work(0, 5, 1), Err(ConsensusError::BlueScoreNotAbovePruningPoint(0, 0)));
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn chain_block_by_score_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // In a linear chain, the blue score of a block is its position in the chain and the DAA score is offset by the
+    // DAA score of genesis
+    let mut chain = vec![config.genesis.hash];
+    for i in 1..=20u64 {
+        let hash: Hash = (i + 1).into();
+        consensus.add_block_with_parents(hash, vec![*chain.last().unwrap()]).await.unwrap();
+        chain.push(hash);
+    }
+    let genesis_daa_score = config.genesis.daa_score;
+
+    for position in [0, 1, 7, 20] {
+        assert_eq!(consensus.get_chain_block_by_blue_score(position as u64).unwrap(), chain[position]);
+        assert_eq!(consensus.get_chain_block_by_daa_score(genesis_daa_score + position as u64).unwrap(), chain[position]);
+    }
+
+    assert_match!(consensus.get_chain_block_by_blue_score(21), Err(ConsensusError::ScoreOutOfSelectedChainRange("blue", 21, 0, 20)));
+    assert_match!(
+        consensus.get_chain_block_by_daa_score(genesis_daa_score + 21),
+        Err(ConsensusError::ScoreOutOfSelectedChainRange("DAA", _, _, _))
+    );
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn historical_hashrate_estimation_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Build a linear chain with a block every second
+    let mut chain = vec![config.genesis.hash];
+    for i in 1..=20u64 {
+        let hash: Hash = (i + 1).into();
+        let mut block = consensus.build_block_with_parents(hash, vec![*chain.last().unwrap()]);
+        block.header.timestamp = config.genesis.timestamp + i * 1000;
+        consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await.unwrap();
+        chain.push(hash);
+    }
+
+    // Anchoring at the sink is equivalent to estimating over the virtual window
+    let sink = *chain.last().unwrap();
+    assert_eq!(consensus.get_sink(), sink);
+    let current = consensus.estimate_network_hashes_per_second(None, 10, 2).unwrap();
+    assert!(current > 0);
+    assert_eq!(consensus.estimate_network_hashes_per_second(Some(sink), 10, 2).unwrap(), current);
+
+    // Any past chain block can serve as an anchor
+    assert!(consensus.estimate_network_hashes_per_second(Some(chain[10]), 5, 2).unwrap() > 0);
+
+    // The window of a block close to genesis holds its few chain ancestors and the block itself
+    assert_match!(
+        consensus.estimate_network_hashes_per_second(Some(chain[3]), 10, 5),
+        Err(ConsensusError::DifficultyError(DifficultyError::UnderMinWindowSizeAllowed(3, 5)))
+    );
+    assert_match!(consensus.estimate_network_hashes_per_second(Some(1000.into()), 10, 2), Err(ConsensusError::HeaderNotFound(_)));
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn chain_block_by_timestamp_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Build a chain whose timestamps are not monotonic, every third block lagging behind its selected parent
+    let mut chain = vec![config.genesis.hash];
+    let mut timestamp = config.genesis.timestamp;
+    for i in 1..=30u64 {
+        timestamp += 1000;
+        let mut header = consensus.build_header_with_parents((i + 1).into(), vec![*chain.last().unwrap()]);
+        header.timestamp = if i % 3 == 0 { timestamp - 1500 } else { timestamp };
+        chain.push(header.hash);
+        consensus.validate_and_insert_block(Block::new(header, vec![])).virtual_state_task.await.unwrap();
+    }
+    let timestamps = chain.iter().map(|&hash| consensus.headers_store().get_timestamp(hash).unwrap()).collect_vec();
+
+    for target in (config.genesis.timestamp..=timestamp).step_by(250) {
+        let nearest = consensus.get_chain_block_by_timestamp(target).unwrap();
+        let distance = timestamps[chain.iter().position(|&x| x == nearest).unwrap()].abs_diff(target);
+        assert_eq!(distance, timestamps.iter().map(|x| x.abs_diff(target)).min().unwrap(), "target {target}");
+    }
+    assert_eq!(consensus.get_chain_block_by_timestamp(timestamp + 10_000).unwrap(), *chain.last().unwrap());
+    assert_match!(
+        consensus.get_chain_block_by_timestamp(config.genesis.timestamp - 1),
+        Err(ConsensusError::TimestampBelowPruningPoint(_, _))
+    );
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn chain_headers_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    let mut chain = vec![config.genesis.hash];
+    for i in 1..=10u64 {
+        let hash = i.into();
+        consensus.add_block_with_parents(hash, vec![*chain.last().unwrap()]).await.unwrap();
+        chain.push(hash);
+    }
+    // A block off the selected chain
+    consensus.add_block_with_parents(100.into(), vec![chain[5]]).await.unwrap();
+    let hashes = |headers: Vec<Arc<Header>>| headers.iter().map(|header| header.hash).collect_vec();
+
+    assert_eq!(hashes(consensus.get_chain_headers(chain[3], 4, true).unwrap()), chain[3..7]);
+    assert_eq!(hashes(consensus.get_chain_headers(chain[8], 100, true).unwrap()), chain[8..]);
+    assert_eq!(hashes(consensus.get_chain_headers(chain[6], 3, false).unwrap()), chain[4..7].iter().rev().copied().collect_vec());
+    assert_eq!(hashes(consensus.get_chain_headers(chain[2], 100, false).unwrap()), chain[..3].iter().rev().copied().collect_vec());
+    assert!(consensus.get_chain_headers(chain[0], 0, true).unwrap().is_empty());
+    assert_match!(consensus.get_chain_headers(100.into(), 1, true), Err(ConsensusError::NotSelectedChainBlock(_)));
+
+    consensus.shutdown(wait_handles);
+}
+
 #[tokio::test]
 async fn difficulty_test() {
     init_allocator_with_default_settings();
@@ -1737,6 +2008,8 @@ async fn staging_consensus_test() {
         meta_db,
         &config,
         consensus_db_dir,
+        None,
+        None,
         4,
         notification_root,
         counters,
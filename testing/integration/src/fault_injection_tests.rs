@@ -0,0 +1,35 @@
+//!
+//! Tests exercising the fault injection hooks (requires the `fault-injection` feature).
+//!
+//! `cargo test --release --package kaspa-testing-integration --features fault-injection --lib -- fault_injection_tests`
+//!
+
+use kaspa_database::{
+    create_temp_db,
+    prelude::{CachePolicy, CachedDbAccess, ConnBuilder, DirectDbWriter, StoreError},
+};
+use kaspa_hashes::Hash;
+use kaspa_utils::fault::{self, FaultTrigger};
+
+#[test]
+fn test_store_write_fault() {
+    let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let access = CachedDbAccess::<Hash, u64>::new(db.clone(), CachePolicy::Count(16), vec![1]);
+
+    fault::STORE_WRITE.arm(FaultTrigger::OnceAt(2));
+    let results = (0..4u64).map(|i| access.write(DirectDbWriter::new(&db), i.into(), i)).collect::<Vec<_>>();
+    fault::disarm_all();
+
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(StoreError::InjectedFault(_))));
+    assert!(results[2].is_ok() && results[3].is_ok());
+    assert_eq!(fault::STORE_WRITE.fired(), 1);
+
+    // The failed write must leave neither the cache nor the DB with a value
+    assert!(!access.has(1.into()).unwrap());
+    assert_eq!(access.read(3.into()).unwrap(), 3);
+
+    // A disarmed point never fires
+    access.write(DirectDbWriter::new(&db), 1.into(), 1).unwrap();
+    assert_eq!(access.read(1.into()).unwrap(), 1);
+}
@@ -25,3 +25,7 @@ pub mod subscribe_benchmarks;
 
 #[cfg(test)]
 pub mod rpc_tests;
+
+#[cfg(test)]
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection_tests;
@@ -2,17 +2,23 @@ use std::{str::FromStr, sync::Arc, time::Duration};
 
 use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
 use futures_util::future::try_join_all;
+use itertools::Itertools;
 use kaspa_addresses::{Address, Prefix, Version};
-use kaspa_consensus::params::SIMNET_GENESIS;
-use kaspa_consensus_core::{constants::MAX_SOMPI, subnets::SubnetworkId, tx::Transaction};
+use kaspa_consensus::params::{Params, SIMNET_GENESIS};
+use kaspa_consensus_core::{
+    constants::{MAX_SOMPI, TX_VERSION},
+    subnets::SubnetworkId,
+    tx::Transaction,
+};
 use kaspa_core::info;
 use kaspa_grpc_core::ops::KaspadPayloadOps;
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, FinalityConflictScope, HealthAlertScope, IndexResyncProgressScope, JobCompletedScope,
+        MempoolTransactionsEvictedScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope,
+        UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -49,7 +55,10 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
         unsafe_rpc: true,
+        enable_transaction_builder_rpc: true,
+        chain_quality: true,
         ..Default::default()
     };
 
@@ -90,17 +99,14 @@ async fn sanity_test() {
 
                     // and the virtual chain is the genesis only
                     let response = rpc_client
-                        .get_virtual_chain_from_block_call(GetVirtualChainFromBlockRequest {
-                            start_hash: SIMNET_GENESIS.hash,
-                            include_accepted_transaction_ids: false,
-                        })
+                        .get_virtual_chain_from_block_call(GetVirtualChainFromBlockRequest::new(SIMNET_GENESIS.hash, false))
                         .await
                         .unwrap();
                     assert!(response.added_chain_block_hashes.is_empty());
                     assert!(response.removed_chain_block_hashes.is_empty());
 
                     // Get a block template
-                    let GetBlockTemplateResponse { block, is_synced } = rpc_client
+                    let GetBlockTemplateResponse { block, is_synced, .. } = rpc_client
                         .get_block_template_call(GetBlockTemplateRequest {
                             pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
                             extra_data: Vec::new(),
@@ -140,14 +146,21 @@ async fn sanity_test() {
 
                     // and the virtual chain from genesis contains the added block
                     let response = rpc_client
-                        .get_virtual_chain_from_block_call(GetVirtualChainFromBlockRequest {
-                            start_hash: SIMNET_GENESIS.hash,
-                            include_accepted_transaction_ids: false,
-                        })
+                        .get_virtual_chain_from_block_call(GetVirtualChainFromBlockRequest::new(SIMNET_GENESIS.hash, false))
                         .await
                         .unwrap();
                     assert!(response.added_chain_block_hashes.contains(&block.header.hash));
                     assert!(response.removed_chain_block_hashes.is_empty());
+
+                    // and a batch as large as the chain path reaches the sink
+                    let response = rpc_client
+                        .get_virtual_chain_from_block_call(
+                            GetVirtualChainFromBlockRequest::new(SIMNET_GENESIS.hash, false).with_max_blocks(1),
+                        )
+                        .await
+                        .unwrap();
+                    assert_eq!(response.added_chain_block_hashes, vec![block.header.hash]);
+                    assert!(response.next_start_hash.is_none());
                 })
             }
 
@@ -160,30 +173,52 @@ async fn sanity_test() {
                 tst!(op, {
                     let response = rpc_client.get_current_network_call(GetCurrentNetworkRequest {}).await.unwrap();
                     assert_eq!(response.network, network_id.network_type);
+                    assert_eq!(response.network_id, Some(network_id));
+                    assert!(rpc_client.verify_network_id(network_id).await.is_ok());
                 })
             }
 
             KaspadPayloadOps::GetBlock => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let result = rpc_client.get_block_call(GetBlockRequest { hash: 0.into(), include_transactions: false }).await;
+                    let result = rpc_client.get_block_call(GetBlockRequest::new(0.into(), false)).await;
                     assert!(result.is_err());
 
+                    let response = rpc_client.get_block_call(GetBlockRequest::new(SIMNET_GENESIS.hash, false)).await.unwrap();
+                    assert_eq!(response.block.header.hash, SIMNET_GENESIS.hash);
+                    assert!(response.block.transactions.is_empty());
+                    assert_eq!(response.block.verbose_data.unwrap().transaction_ids.len(), 1);
+
                     let response = rpc_client
-                        .get_block_call(GetBlockRequest { hash: SIMNET_GENESIS.hash, include_transactions: false })
+                        .get_block_call(GetBlockRequest::new(SIMNET_GENESIS.hash, false).with_verbosity(RpcBlockVerbosity::HeaderOnly))
                         .await
                         .unwrap();
                     assert_eq!(response.block.header.hash, SIMNET_GENESIS.hash);
+                    assert!(response.block.verbose_data.unwrap().transaction_ids.is_empty());
+
+                    let response = rpc_client.get_block_call(GetBlockRequest::new(SIMNET_GENESIS.hash, true)).await.unwrap();
+                    assert_eq!(response.block.transactions.len(), 1);
+
+                    // The verbosity supersedes the legacy flag when set
+                    let response = rpc_client
+                        .get_block_call(
+                            GetBlockRequest::new(SIMNET_GENESIS.hash, true).with_verbosity(RpcBlockVerbosity::TransactionIds),
+                        )
+                        .await
+                        .unwrap();
+                    assert!(response.block.transactions.is_empty());
+                    let response = rpc_client
+                        .get_block_call(GetBlockRequest::new(SIMNET_GENESIS.hash, false).with_verbosity(RpcBlockVerbosity::Full))
+                        .await
+                        .unwrap();
+                    assert_eq!(response.block.transactions.len(), 1);
                 })
             }
 
             KaspadPayloadOps::GetBlocks => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let response = rpc_client
-                        .get_blocks_call(GetBlocksRequest { include_blocks: true, include_transactions: false, low_hash: None })
-                        .await
-                        .unwrap();
+                    let response = rpc_client.get_blocks_call(GetBlocksRequest::new(None, true, false)).await.unwrap();
                     assert_eq!(response.blocks.len(), 1, "genesis block should be returned");
                     assert_eq!(response.blocks[0].header.hash, SIMNET_GENESIS.hash);
                     assert_eq!(response.block_hashes[0], SIMNET_GENESIS.hash);
@@ -235,13 +270,7 @@ async fn sanity_test() {
             KaspadPayloadOps::GetMempoolEntries => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let response = rpc_client
-                        .get_mempool_entries_call(GetMempoolEntriesRequest {
-                            include_orphan_pool: true,
-                            filter_transaction_pool: false,
-                        })
-                        .await
-                        .unwrap();
+                    let response = rpc_client.get_mempool_entries_call(GetMempoolEntriesRequest::new(true, false)).await.unwrap();
                     assert!(response.mempool_entries.is_empty());
                 })
             }
@@ -263,7 +292,7 @@ async fn sanity_test() {
                     // Add peer only adds the IP to a connection request. It will only be added to known_addresses if it
                     // actually can be connected to. So in this test we can't expect it to be added unless we set up an
                     // actual peer.
-                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest::default()).await.unwrap();
                     assert!(response.known_addresses.is_empty());
                 })
             }
@@ -275,14 +304,116 @@ async fn sanity_test() {
                     let ip = peer_address.normalize(1).ip;
 
                     let _ = rpc_client.add_peer_call(AddPeerRequest { peer_address, is_permanent: false }).await.unwrap();
-                    let _ = rpc_client.ban_call(BanRequest { ip }).await.unwrap();
+                    let request = BanRequest::new(ip).with_duration(60 * 60 * 1000).with_reason("testing".to_owned());
+                    let _ = rpc_client.ban_call(request).await.unwrap();
 
-                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest::default()).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
-
-                    let _ = rpc_client.unban_call(UnbanRequest { ip }).await.unwrap();
-                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
+                    assert_eq!(response.total_banned_addresses, response.banned_addresses.len() as u64);
+
+                    let request = GetPeerAddressesRequest {
+                        banned_only: true,
+                        network: Some("5.6.0.0/16".to_owned()),
+                        limit: Some(1),
+                        ..Default::default()
+                    };
+                    let response = rpc_client.get_peer_addresses_call(request).await.unwrap();
+                    assert!(response.known_addresses.is_empty());
+                    assert_eq!(response.banned_addresses, vec![ip]);
+                    let request = GetPeerAddressesRequest { ipv6_only: true, ..Default::default() };
+                    let response = rpc_client.get_peer_addresses_call(request).await.unwrap();
                     assert!(!response.banned_addresses.contains(&ip));
+                    let request = GetPeerAddressesRequest { ipv4_only: true, ipv6_only: true, ..Default::default() };
+                    assert!(rpc_client.get_peer_addresses_call(request).await.is_err());
+                    let request = GetPeerAddressesRequest { network: Some("5.6.7.8".to_owned()), ..Default::default() };
+                    assert!(rpc_client.get_peer_addresses_call(request).await.is_err());
+
+                    let bans = rpc_client.get_bans().await.unwrap();
+                    let ban = bans.iter().find(|x| x.ip == ip).unwrap();
+                    assert_eq!(ban.reason.as_deref(), Some("testing"));
+                    assert!(ban.remaining_time <= 60 * 60 * 1000);
+
+                    let _ = rpc_client.unban_call(UnbanRequest::new(ip)).await.unwrap();
+                    let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest::default()).await.unwrap();
+                    assert!(!response.banned_addresses.contains(&ip));
+                    assert!(rpc_client.get_bans().await.unwrap().is_empty());
+
+                    // Ban by network
+                    let network_ip = RpcIpAddress::from_str("9.9.0.0").unwrap();
+                    let request = BanRequest::new(RpcIpAddress::from_str("9.9.9.9").unwrap()).with_prefix_length(16);
+                    let _ = rpc_client.ban_call(request).await.unwrap();
+                    let bans = rpc_client.get_bans().await.unwrap();
+                    assert_eq!(bans.len(), 1);
+                    assert_eq!(bans[0].ip, network_ip);
+                    assert_eq!(bans[0].prefix_length, Some(16));
+                    assert!(rpc_client.ban_call(BanRequest::new(network_ip).with_prefix_length(33)).await.is_err());
+                    assert!(rpc_client.unban_call(UnbanRequest::new(network_ip).with_prefix_length(8)).await.is_err());
+                    let _ = rpc_client.unban_call(UnbanRequest::new(network_ip).with_prefix_length(16)).await.unwrap();
+                    assert!(rpc_client.get_bans().await.unwrap().is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetBans => {
+                tst!(op, "see Ban")
+            }
+
+            KaspadPayloadOps::GetBlockProcessingStatus => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No block was submitted without blocking so the ticket is unknown
+                    let response_result = rpc_client.get_block_processing_status(u64::MAX).await;
+                    assert!(response_result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::StartJob => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let job_id = rpc_client.start_job(RpcJobKind::TxIndexResync).await.unwrap();
+                    let job = loop {
+                        let job = rpc_client.get_job_status(job_id).await.unwrap();
+                        if job.is_finished() {
+                            break job;
+                        }
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                    };
+                    assert_eq!(job.id, job_id);
+                    assert_eq!(job.kind, RpcJobKind::TxIndexResync);
+                    assert_eq!(job.state, RpcJobState::Completed, "{job}");
+                    assert!(job.finished_at.is_some());
+                })
+            }
+
+            KaspadPayloadOps::GetJobStatus => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No job was started with this id
+                    let response_result = rpc_client.get_job_status(u64::MAX).await;
+                    assert!(response_result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GenerateBlocks => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Mining blocks would break the block count expectations of SubmitBlock, so only the validation is covered
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client.generate_blocks(pay_address, 0).await.unwrap().is_empty());
+                    let mainnet_address = Address::new(Prefix::Mainnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client.generate_blocks(mainnet_address, 1).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetNotificationStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let notifiers = rpc_client.get_notification_stats().await.unwrap();
+                    assert!(notifiers.iter().any(|x| x.name == "rpc-core"));
+                    assert!(notifiers.iter().any(|x| x.name == "grpc"));
+                    for stats in notifiers.iter().flat_map(|x| x.events.iter()) {
+                        assert!(stats.emitted > 0);
+                        assert!(stats.average_fan_out >= 0.0);
+                    }
                 })
             }
 
@@ -301,6 +432,19 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::SubmitTransactions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let results = rpc_client.submit_transactions(vec![(&transaction).into()], false).await.unwrap();
+                    // ...that gets rejected by the consensus
+                    assert_eq!(results.len(), 1);
+                    assert_eq!(results[0].transaction_id, transaction.id());
+                    assert!(results[0].error.is_some());
+                })
+            }
+
             KaspadPayloadOps::GetSubnetwork => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -337,7 +481,13 @@ async fn sanity_test() {
                         })
                         .await;
 
-                    // Err because it's currently unimplemented
+                    // Err because the block is unknown
+                    assert!(response_result.is_err());
+
+                    // Err because genesis is on the selected chain, so there is no conflict to resolve
+                    let response_result = rpc_client
+                        .resolve_finality_conflict_call(ResolveFinalityConflictRequest { finality_block_hash: SIMNET_GENESIS.hash })
+                        .await;
                     assert!(response_result.is_err());
                 })
             }
@@ -345,11 +495,18 @@ async fn sanity_test() {
             KaspadPayloadOps::GetHeaders => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let response_result = rpc_client
-                        .get_headers_call(GetHeadersRequest { start_hash: SIMNET_GENESIS.hash, limit: 1, is_ascending: true })
-                        .await;
+                    for is_ascending in [true, false] {
+                        let response = rpc_client
+                            .get_headers_call(GetHeadersRequest { start_hash: SIMNET_GENESIS.hash, limit: 10, is_ascending })
+                            .await
+                            .unwrap();
+                        assert_eq!(response.headers.len(), 1);
+                        assert_eq!(response.headers[0].hash, SIMNET_GENESIS.hash);
+                    }
 
-                    // Err because it's currently unimplemented
+                    // Err because the start hash is not a known block
+                    let response_result =
+                        rpc_client.get_headers_call(GetHeadersRequest { start_hash: 1.into(), limit: 1, is_ascending: true }).await;
                     assert!(response_result.is_err());
                 })
             }
@@ -358,8 +515,16 @@ async fn sanity_test() {
                 let rpc_client = client.clone();
                 tst!(op, {
                     let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32])];
-                    let response = rpc_client.get_utxos_by_addresses_call(GetUtxosByAddressesRequest { addresses }).await.unwrap();
+                    let response =
+                        rpc_client.get_utxos_by_addresses_call(GetUtxosByAddressesRequest::new(addresses.clone())).await.unwrap();
+                    assert!(response.entries.is_empty());
+
+                    let response = rpc_client
+                        .get_utxos_by_addresses_call(GetUtxosByAddressesRequest::new(addresses).with_limit(10))
+                        .await
+                        .unwrap();
                     assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
                 })
             }
 
@@ -407,13 +572,20 @@ async fn sanity_test() {
                 let rpc_client = client.clone();
                 tst!(op, {
                     let response_result = rpc_client
-                        .estimate_network_hashes_per_second_call(EstimateNetworkHashesPerSecondRequest {
-                            window_size: 1000,
-                            start_hash: None,
-                        })
+                        .estimate_network_hashes_per_second_call(EstimateNetworkHashesPerSecondRequest::new(1000, None))
                         .await;
                     // The current DAA window is almost empty so an error is expected
                     assert!(response_result.is_err());
+
+                    // A min window size below the one configured by the node is rejected
+                    let request = EstimateNetworkHashesPerSecondRequest::new(1000, None).with_min_window_size(1);
+                    let response_result = rpc_client.estimate_network_hashes_per_second_call(request).await;
+                    assert!(response_result.is_err());
+
+                    // An unknown anchor block is rejected
+                    let request = EstimateNetworkHashesPerSecondRequest::new(1000, Some(Hash::from_bytes([0x11; 32])));
+                    let response_result = rpc_client.estimate_network_hashes_per_second_call(request).await;
+                    assert!(response_result.is_err());
                 })
             }
 
@@ -441,6 +613,7 @@ async fn sanity_test() {
                 tst!(op, {
                     let response = rpc_client.get_coin_supply_call(GetCoinSupplyRequest {}).await.unwrap();
                     assert_eq!(response.circulating_sompi, 0);
+                    assert_eq!(response.burned_sompi, 0);
                     assert_eq!(response.max_sompi, MAX_SOMPI);
                 })
             }
@@ -461,11 +634,17 @@ async fn sanity_test() {
                             connection_metrics: true,
                             bandwidth_metrics: true,
                             process_metrics: true,
+                            storage_metrics: true,
+                            request_metrics: true,
                         })
                         .await
                         .unwrap();
                     assert!(get_metrics_call_response.process_metrics.is_some());
                     assert!(get_metrics_call_response.consensus_metrics.is_some());
+                    let storage_metrics = get_metrics_call_response.storage_metrics.expect("storage metrics were requested");
+                    assert!(storage_metrics.data_dir_size > 0);
+                    assert!(!storage_metrics.databases.is_empty());
+                    assert!(get_metrics_call_response.request_metrics.is_some());
 
                     let get_metrics_call_response = rpc_client
                         .get_metrics_call(GetMetricsRequest {
@@ -473,11 +652,15 @@ async fn sanity_test() {
                             connection_metrics: true,
                             bandwidth_metrics: true,
                             process_metrics: true,
+                            storage_metrics: false,
+                            request_metrics: false,
                         })
                         .await
                         .unwrap();
                     assert!(get_metrics_call_response.process_metrics.is_some());
                     assert!(get_metrics_call_response.consensus_metrics.is_none());
+                    assert!(get_metrics_call_response.storage_metrics.is_none());
+                    assert!(get_metrics_call_response.request_metrics.is_none());
 
                     let get_metrics_call_response = rpc_client
                         .get_metrics_call(GetMetricsRequest {
@@ -485,6 +668,8 @@ async fn sanity_test() {
                             connection_metrics: true,
                             bandwidth_metrics: false,
                             process_metrics: false,
+                            storage_metrics: false,
+                            request_metrics: false,
                         })
                         .await
                         .unwrap();
@@ -497,6 +682,8 @@ async fn sanity_test() {
                             connection_metrics: true,
                             bandwidth_metrics: false,
                             process_metrics: false,
+                            storage_metrics: false,
+                            request_metrics: false,
                         })
                         .await
                         .unwrap();
@@ -514,10 +701,67 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetServerCapabilities => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_server_capabilities().await.unwrap();
+                    assert_eq!(response.rpc_api_version, kaspa_rpc_core::api::ops::RPC_API_VERSION);
+                    assert!(response.has_method("GetServerCapabilities"));
+                    assert!(response.has_method("getblockdaginfo"));
+                    assert!(response.has_notification("UtxosChanged"));
+                })
+            }
+
+            KaspadPayloadOps::UpdateBlockTemplateExclusions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let transaction_id = RpcTransactionId::from_u64_word(1);
+                    let script_public_key = RpcScriptPublicKey::from_vec(0, vec![0x51]);
+                    let response = rpc_client
+                        .update_block_template_exclusions_call(UpdateBlockTemplateExclusionsRequest {
+                            add_transaction_ids: vec![transaction_id],
+                            add_script_public_keys: vec![script_public_key.clone()],
+                            ..Default::default()
+                        })
+                        .await
+                        .unwrap();
+                    assert_eq!(response.transaction_ids, vec![transaction_id]);
+                    assert_eq!(response.script_public_keys, vec![script_public_key.clone()]);
+
+                    // An empty request only returns the exclusions in force
+                    let response = rpc_client.update_block_template_exclusions_call(Default::default()).await.unwrap();
+                    assert_eq!(response.transaction_ids, vec![transaction_id]);
+
+                    let response = rpc_client
+                        .update_block_template_exclusions_call(UpdateBlockTemplateExclusionsRequest {
+                            remove_transaction_ids: vec![transaction_id],
+                            remove_script_public_keys: vec![script_public_key],
+                            ..Default::default()
+                        })
+                        .await
+                        .unwrap();
+                    assert!(response.transaction_ids.is_empty() && response.script_public_keys.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetChainQuality => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The first sample is taken a sampling interval after the node is synced, so none may be retained yet
+                    let samples = rpc_client.get_chain_quality(0).await.unwrap();
+                    assert!(samples.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+                    assert!(samples.iter().all(|sample| sample.red_block_count <= sample.block_count));
+                    assert!(rpc_client.get_chain_quality(u64::MAX).await.unwrap().is_empty());
+                })
+            }
+
             KaspadPayloadOps::GetSyncStatus => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let _ = rpc_client.get_sync_status_call(GetSyncStatusRequest {}).await.unwrap();
+                    let response = rpc_client.get_sync_status_call(GetSyncStatusRequest {}).await.unwrap();
+                    assert!(response.ibd_peer.is_none());
+                    assert!(response.sink_daa_score <= response.headers_selected_tip_daa_score);
+                    assert!(matches!(response.phase, RpcSyncPhase::Synced | RpcSyncPhase::Idle));
                 })
             }
 
@@ -546,6 +790,202 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetTransactionMass => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let response =
+                        rpc_client.get_transaction_mass_call(GetTransactionMassRequest::new((&transaction).into())).await.unwrap();
+                    assert!(response.size_mass > 0);
+                    assert_eq!(response.sig_op_mass, 0);
+                    assert_eq!(response.compute_mass, response.size_mass + response.script_public_key_mass + response.sig_op_mass);
+                })
+            }
+
+            KaspadPayloadOps::BuildUnsignedTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The addresses own no UTXO so funds are insufficient
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let outputs = vec![RpcPaymentOutput::new(address.clone(), 100_000_000)];
+                    let result = rpc_client.build_unsigned_transaction(vec![address.clone()], outputs, address).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetDagStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_dag_stats(100).await.unwrap();
+                    assert!(response.tip_count > 0);
+                    assert!(response.chain_block_count <= 100);
+                    assert!((0.0..=1.0).contains(&response.red_block_rate));
+                })
+            }
+
+            KaspadPayloadOps::GetHealth => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_health().await.unwrap();
+                    assert_eq!(response.degraded, !response.alerts.is_empty());
+                    assert!(response.index_resyncs.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetFeeEstimate => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let estimate = rpc_client.get_fee_estimate().await.unwrap();
+                    assert!(estimate.priority_bucket.feerate >= estimate.normal_bucket.feerate);
+                    assert!(estimate.normal_bucket.feerate >= estimate.low_bucket.feerate);
+                    assert!(estimate.low_bucket.feerate > 0.0);
+                })
+            }
+
+            KaspadPayloadOps::GetSubscriptions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Subscriptions are concurrently mutated by the other tasks so only the consistency of the result is checked
+                    let subscriptions = rpc_client.get_subscriptions().await.unwrap();
+                    assert!(subscriptions.iter().map(|x| x.event_type).all_unique());
+                    assert!(subscriptions.iter().all(|x| !x.all_addresses || x.address_count == 0));
+                })
+            }
+
+            KaspadPayloadOps::GetVirtualParents => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_virtual_parents().await.unwrap();
+                    assert_eq!(response.parents.first(), Some(&response.selected_parent));
+                    assert!(response.excluded_candidates.iter().all(|x| !response.parents.contains(&x.hash)));
+                })
+            }
+
+            KaspadPayloadOps::GetConsensusParams => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let params = rpc_client.get_consensus_params().await.unwrap();
+                    let expected = Params::from(network_id);
+                    assert_eq!(params.network_id, network_id);
+                    assert_eq!(params.genesis_hash, SIMNET_GENESIS.hash);
+                    assert_eq!(params.ghostdag_k, expected.ghostdag_k as u32);
+                    assert_eq!(params.max_block_mass, expected.max_block_mass);
+                    assert_eq!(params.pruning_depth, expected.pruning_depth);
+                    assert_eq!(params.tx_version_2_activation_daa_score, expected.tx_version_2_activation_daa_score);
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionById => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // No transaction got accepted on top of genesis yet
+                    let result = rpc_client.get_transaction_by_id(0.into()).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::SampleChainBlocks => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An inverted range is rejected
+                    let result = rpc_client.sample_chain_blocks(2, 1, 10).await;
+                    assert!(result.is_err());
+                    // So is a range reaching the pruning point, here genesis
+                    let result = rpc_client.sample_chain_blocks(0, 10, 10).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetConnections => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The tested node runs without any p2p peer
+                    let connections = rpc_client.get_connections().await.unwrap();
+                    assert!(connections.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetBlockByBlueScore => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Genesis is the pruning point, hence the lowest chain block
+                    let block = rpc_client.get_block_by_blue_score(0, RpcBlockVerbosity::HeaderOnly).await.unwrap();
+                    assert_eq!(block.header.hash, SIMNET_GENESIS.hash);
+                    let result = rpc_client.get_block_by_blue_score(u64::MAX, RpcBlockVerbosity::HeaderOnly).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetBlockByDaaScore => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let result = rpc_client.get_block_by_daa_score(u64::MAX, RpcBlockVerbosity::HeaderOnly).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetChainBlockByTimestamp => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A timestamp below the one of the pruning point, here genesis, is rejected
+                    let result = rpc_client.get_chain_block_by_timestamp(0, RpcBlockVerbosity::HeaderOnly).await;
+                    assert!(result.is_err());
+                    // A timestamp in the future resolves to the sink
+                    let result = rpc_client.get_chain_block_by_timestamp(u64::MAX, RpcBlockVerbosity::HeaderOnly).await;
+                    assert!(result.is_ok());
+                })
+            }
+
+            KaspadPayloadOps::GetPruningPointUtxos => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The pruning point is genesis, whose UTXO set fits in a single page
+                    let response = rpc_client.get_pruning_point_utxos(None, None, None).await.unwrap();
+                    assert!(response.next_cursor.is_none());
+                    // Paging through the UTXO set of another block is rejected
+                    let result = rpc_client.get_pruning_point_utxos(Some(RpcHash::from_u64_word(1)), None, None).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetMempoolInfo => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mempool_info().await.unwrap();
+                    assert!(response.policy.maximum_transaction_count > 0);
+                    assert!(response.policy.transaction_expire_interval_seconds > 0);
+                    assert_eq!(response.minimum_standard_transaction_version, TX_VERSION);
+                    assert_eq!(response.maximum_standard_transaction_version, TX_VERSION);
+                })
+            }
+
+            KaspadPayloadOps::SetMempoolPolicy => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let initial = rpc_client.get_mempool_info().await.unwrap().policy;
+                    let policy =
+                        RpcMempoolPolicy { orphan_expire_interval_seconds: initial.orphan_expire_interval_seconds * 2, ..initial };
+                    assert_eq!(rpc_client.set_mempool_policy(policy).await.unwrap(), policy);
+                    assert_eq!(rpc_client.get_mempool_info().await.unwrap().policy, policy);
+
+                    // Err because maximum counts cannot be raised above the ones the node started with
+                    let raised = RpcMempoolPolicy { maximum_transaction_count: initial.maximum_transaction_count + 1, ..initial };
+                    assert!(rpc_client.set_mempool_policy(raised).await.is_err());
+
+                    assert_eq!(rpc_client.set_mempool_policy(initial).await.unwrap(), initial);
+                })
+            }
+
+            KaspadPayloadOps::GetCurrentBlockColor => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Err because the genesis, being the sink, was not merged by any block yet
+                    let sink = rpc_client.get_sink().await.unwrap().sink;
+                    let result = rpc_client.get_current_block_color(sink).await;
+                    assert!(result.is_err());
+                })
+            }
+
             KaspadPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -562,6 +1002,38 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::NotifyHealthAlert => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, HealthAlertScope {}.into()).await.unwrap();
+                })
+            }
+
+            KaspadPayloadOps::NotifyMempoolTransactionsEvicted => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, MempoolTransactionsEvictedScope {}.into()).await.unwrap();
+                })
+            }
+
+            KaspadPayloadOps::NotifyJobCompleted => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, JobCompletedScope {}.into()).await.unwrap();
+                })
+            }
+
+            KaspadPayloadOps::NotifyIndexResyncProgress => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, IndexResyncProgressScope {}.into()).await.unwrap();
+                })
+            }
+
             KaspadPayloadOps::NotifyFinalityConflict => {
                 let rpc_client = client.clone();
                 let id = listener_id;
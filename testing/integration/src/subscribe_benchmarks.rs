@@ -89,7 +89,7 @@ async fn utxos_changed_subscriptions_sanity_check() {
     // Start the daemon
     info!("Launching the daemon...");
     let daemon_args = DaemonArgs::new(
-        args.rpclisten.map(|x| x.normalize(0).port).unwrap(),
+        args.rpclisten[0].address.normalize(0).port,
         args.listen.map(|x| x.normalize(0).port).unwrap(),
         prealloc_sk.display_secret().to_string(),
         Some("ucs-server".to_owned()),
@@ -202,7 +202,7 @@ async fn utxos_changed_subscriptions_client(address_cycle_seconds: u64, address_
     // Start the daemon
     info!("Launching the daemon...");
     let daemon_args = DaemonArgs::new(
-        args.rpclisten.map(|x| x.normalize(0).port).unwrap(),
+        args.rpclisten[0].address.normalize(0).port,
         args.listen.map(|x| x.normalize(0).port).unwrap(),
         prealloc_sk.display_secret().to_string(),
         Some("ucs-server".to_owned()),
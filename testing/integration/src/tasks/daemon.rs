@@ -108,7 +108,7 @@ impl DaemonArgs {
 
     #[cfg(feature = "devnet-prealloc")]
     pub fn apply_to(&self, args: &mut Args) {
-        args.rpclisten = Some(format!("0.0.0.0:{}", self.rpc).try_into().unwrap());
+        args.rpclisten = vec![format!("0.0.0.0:{}", self.rpc).parse().unwrap()];
         args.listen = Some(format!("0.0.0.0:{}", self.p2p).try_into().unwrap());
         args.prealloc_address = Some(self.prealloc_address().to_string());
         args.max_tracked_addresses = self.max_tracked_addresses;
@@ -117,7 +117,7 @@ impl DaemonArgs {
 
     #[cfg(not(feature = "devnet-prealloc"))]
     pub fn apply_to(&self, args: &mut Args) {
-        args.rpclisten = Some(format!("0.0.0.0:{}", self.rpc).try_into().unwrap());
+        args.rpclisten = vec![format!("0.0.0.0:{}", self.rpc).parse().unwrap()];
         args.listen = Some(format!("0.0.0.0:{}", self.p2p).try_into().unwrap());
         args.max_tracked_addresses = self.max_tracked_addresses;
         args.utxoindex = self.utxoindex;
@@ -0,0 +1,170 @@
+//! An in-process simnet node for end-to-end tests.
+//!
+//! [`SimnetNode`] runs a kaspad on simnet, where the proof of work is skipped, with a reduced coinbase maturity.
+//! No block is ever produced unless requested through the `GenerateBlocks` RPC, so a test can fund an address,
+//! send a transaction and confirm it within milliseconds, deterministically and without any external miner.
+
+use kaspa_addresses::{Address, Version};
+use kaspa_consensus_core::network::{NetworkId, NetworkType};
+use kaspa_core::{core::Core, signals::Shutdown, task::runtime::AsyncRuntime};
+use kaspa_database::utils::get_kaspa_tempdir;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_grpc_server::service::GrpcService;
+use kaspa_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode, RpcHash};
+use kaspad_lib::{args::Args, daemon::create_core_with_runtime};
+use std::{net::TcpListener, sync::Arc, thread::JoinHandle};
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod tests;
+
+/// File descriptors budget of a simnet node, enough for a few clients
+const FD_TOTAL_BUDGET: i32 = 10;
+
+#[derive(Clone, Debug)]
+pub struct SimnetConfig {
+    /// Number of blocks after which a coinbase output can be spent
+    pub coinbase_maturity: u64,
+
+    /// Enables the UTXO index, required by the address-based RPC methods wallets rely on
+    pub utxoindex: bool,
+}
+
+impl Default for SimnetConfig {
+    fn default() -> Self {
+        Self { coinbase_maturity: 10, utxoindex: true }
+    }
+}
+
+pub struct SimnetNode {
+    core: Arc<Core>,
+    workers: Option<Vec<JoinHandle<()>>>,
+    rpc_port: u16,
+    wrpc_borsh_port: u16,
+    coinbase_maturity: u64,
+    client: GrpcClient,
+    _appdir_tempdir: TempDir,
+}
+
+impl SimnetNode {
+    pub async fn start() -> Self {
+        Self::with_config(Default::default()).await
+    }
+
+    pub async fn with_config(config: SimnetConfig) -> Self {
+        let appdir_tempdir = get_kaspa_tempdir();
+        let (rpc_port, wrpc_borsh_port, p2p_port) = (free_port(), free_port(), free_port());
+        let args = Args {
+            simnet: true,
+            simnet_coinbase_maturity: Some(config.coinbase_maturity),
+            utxoindex: config.utxoindex,
+            appdir: Some(appdir_tempdir.path().to_str().unwrap().to_owned()),
+            rpclisten: vec![format!("127.0.0.1:{rpc_port}").parse().unwrap()],
+            rpclisten_borsh: Some(format!("127.0.0.1:{wrpc_borsh_port}").parse().unwrap()),
+            listen: Some(format!("127.0.0.1:{p2p_port}").try_into().unwrap()),
+            disable_upnp: true,
+            disable_dns_seeding: true,
+            ..Default::default()
+        };
+        let (core, _) = create_core_with_runtime(&Default::default(), &args, FD_TOTAL_BUDGET);
+        let async_runtime = Arc::downcast::<AsyncRuntime>(core.find(AsyncRuntime::IDENT).unwrap().arc_any()).unwrap();
+        let grpc_service = Arc::downcast::<GrpcService>(async_runtime.find(GrpcService::IDENT).unwrap().arc_any()).unwrap();
+        let grpc_server_started = grpc_service.started();
+        let workers = core.start();
+
+        // Wait for the gRPC server to listen before connecting the default client
+        grpc_server_started.await;
+        let client = connect(rpc_port).await;
+        Self {
+            core,
+            workers: Some(workers),
+            rpc_port,
+            wrpc_borsh_port,
+            coinbase_maturity: config.coinbase_maturity,
+            client,
+            _appdir_tempdir: appdir_tempdir,
+        }
+    }
+
+    pub fn network_id(&self) -> NetworkId {
+        NetworkId::new(NetworkType::Simnet)
+    }
+
+    pub fn coinbase_maturity(&self) -> u64 {
+        self.coinbase_maturity
+    }
+
+    /// The gRPC client connected to the node when it started
+    pub fn client(&self) -> &GrpcClient {
+        &self.client
+    }
+
+    pub async fn new_client(&self) -> GrpcClient {
+        connect(self.rpc_port).await
+    }
+
+    /// The wRPC Borsh url of the node, for wallets and other wRPC clients built by the test itself
+    pub fn wrpc_borsh_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.wrpc_borsh_port)
+    }
+
+    /// An address nobody owns, collecting the rewards of the blocks mined for the sole sake of advancing the DAG
+    pub fn blank_address(&self) -> Address {
+        Address::new(self.network_id().into(), Version::PubKey, &[0; 32])
+    }
+
+    /// Mines `count` blocks paying to `pay_address`, each including the mempool transactions
+    pub async fn generate_to(&self, pay_address: &Address, count: u64) -> Vec<RpcHash> {
+        self.client.generate_blocks(pay_address.clone(), count).await.unwrap()
+    }
+
+    /// Mines `count` blocks paying to the blank address
+    pub async fn generate(&self, count: u64) -> Vec<RpcHash> {
+        self.generate_to(&self.blank_address(), count).await
+    }
+
+    /// Mines one block including the mempool transactions, which then get accepted by the virtual
+    pub async fn confirm(&self) -> RpcHash {
+        self.generate(1).await[0]
+    }
+
+    /// Mines `blocks` blocks paying to `address` and then as many blocks as needed for their rewards to be spendable
+    pub async fn fund(&self, address: &Address, blocks: u64) {
+        self.generate_to(address, blocks).await;
+        // The reward of a block is paid by the coinbase of the chain block merging it
+        self.generate(self.coinbase_maturity + 1).await;
+    }
+
+    pub fn shutdown(&mut self) {
+        if let Some(workers) = self.workers.take() {
+            self.core.shutdown();
+            self.core.join(workers);
+        }
+    }
+}
+
+impl Drop for SimnetNode {
+    fn drop(&mut self) {
+        self.shutdown()
+    }
+}
+
+async fn connect(rpc_port: u16) -> GrpcClient {
+    GrpcClient::connect_with_args(
+        NotificationMode::Direct,
+        format!("grpc://127.0.0.1:{rpc_port}"),
+        None,
+        false,
+        None,
+        false,
+        Some(500_000),
+        Default::default(),
+    )
+    .await
+    .unwrap()
+}
+
+/// Asks the OS for a free port
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
@@ -0,0 +1,52 @@
+use crate::SimnetNode;
+use kaspa_addresses::{Address, Version};
+use kaspa_consensus_core::{
+    constants::TX_VERSION,
+    sign::sign,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{MutableTransaction, Transaction, TransactionInput, TransactionOutput},
+};
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_txscript::pay_to_address_script;
+use rand::thread_rng;
+
+const FEE: u64 = 100_000;
+
+#[tokio::test]
+async fn test_fund_send_confirm() {
+    let node = SimnetNode::start().await;
+    let client = node.client();
+
+    let (sender_sk, sender_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let sender = Address::new(node.network_id().into(), Version::PubKey, &sender_pk.x_only_public_key().0.serialize());
+    let sender_key = secp256k1::Keypair::from_secret_key(secp256k1::SECP256K1, &sender_sk);
+    let (_, recipient_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let recipient = Address::new(node.network_id().into(), Version::PubKey, &recipient_pk.x_only_public_key().0.serialize());
+
+    // Fund
+    node.fund(&sender, 1).await;
+    let utxos = client.get_utxos_by_addresses(vec![sender.clone()]).await.unwrap();
+    assert_eq!(utxos.len(), 1);
+    let utxo = utxos[0].clone();
+    assert!(utxo.utxo_entry.is_coinbase);
+
+    // Send
+    let amount = utxo.utxo_entry.amount / 2;
+    let change = utxo.utxo_entry.amount - amount - FEE;
+    let input = TransactionInput { previous_outpoint: utxo.outpoint, signature_script: vec![], sequence: 0, sig_op_count: 1 };
+    let outputs = vec![
+        TransactionOutput { value: amount, script_public_key: pay_to_address_script(&recipient) },
+        TransactionOutput { value: change, script_public_key: pay_to_address_script(&sender) },
+    ];
+    let unsigned_tx = Transaction::new(TX_VERSION, vec![input], outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+    let tx = sign(MutableTransaction::with_entries(unsigned_tx, vec![utxo.utxo_entry]), sender_key).tx;
+    client.submit_transaction((&tx).into(), false).await.unwrap();
+    assert!(client.get_utxos_by_addresses(vec![recipient.clone()]).await.unwrap().is_empty());
+
+    // Confirm
+    let block_hash = node.confirm().await;
+    assert_eq!(client.get_block_dag_info().await.unwrap().sink, block_hash);
+    let utxos = client.get_utxos_by_addresses(vec![recipient]).await.unwrap();
+    assert_eq!(utxos.len(), 1);
+    assert_eq!(utxos[0].utxo_entry.amount, amount);
+}
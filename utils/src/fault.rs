@@ -0,0 +1,183 @@
+//! Fault injection points for exercising resilience paths in tests.
+//!
+//! Each [`FaultPoint`] is a global switch consulted by instrumented code (store writes,
+//! incoming P2P messages, notification broadcasting). Points are inert until armed with a
+//! [`FaultTrigger`] which decides deterministically which hits fire, so that integration
+//! tests relying on injected faults remain reproducible.
+//!
+//! This module is only available with the `fault-injection` feature.
+
+use parking_lot::{const_mutex, Mutex};
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Policy deciding which hits of an armed [`FaultPoint`] fire
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultTrigger {
+    /// Fire on every hit
+    Always,
+
+    /// Fire on every n-th hit (1-based)
+    EveryNth(u64),
+
+    /// Fire once, on the n-th hit (1-based)
+    OnceAt(u64),
+
+    /// Fire with a probability of `ppm` parts per million, drawn from a PRNG seeded with `seed`
+    Random { ppm: u32, seed: u64 },
+}
+
+struct FaultState {
+    trigger: Option<FaultTrigger>,
+    rng: u64,
+    delay: Duration,
+}
+
+pub struct FaultPoint {
+    name: &'static str,
+    armed: AtomicBool,
+    state: Mutex<FaultState>,
+    hits: AtomicU64,
+    fired: AtomicU64,
+}
+
+impl FaultPoint {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            armed: AtomicBool::new(false),
+            state: const_mutex(FaultState { trigger: None, rng: 0, delay: Duration::ZERO }),
+            hits: AtomicU64::new(0),
+            fired: AtomicU64::new(0),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Arms the point with `trigger`, resetting its counters
+    pub fn arm(&self, trigger: FaultTrigger) {
+        self.arm_with_delay(trigger, Duration::ZERO);
+    }
+
+    /// Arms the point with `trigger` and a `delay` returned by [`FaultPoint::delay`] when firing
+    pub fn arm_with_delay(&self, trigger: FaultTrigger, delay: Duration) {
+        let mut state = self.state.lock();
+        state.rng = match trigger {
+            FaultTrigger::Random { seed, .. } => seed,
+            _ => 0,
+        };
+        state.trigger = Some(trigger);
+        state.delay = delay;
+        self.hits.store(0, Ordering::SeqCst);
+        self.fired.store(0, Ordering::SeqCst);
+        self.armed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn disarm(&self) {
+        let mut state = self.state.lock();
+        self.armed.store(false, Ordering::SeqCst);
+        state.trigger = None;
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.armed.load(Ordering::Relaxed)
+    }
+
+    /// Number of hits since the point was last armed
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    /// Number of fired hits since the point was last armed
+    pub fn fired(&self) -> u64 {
+        self.fired.load(Ordering::SeqCst)
+    }
+
+    /// Registers a hit and returns whether the fault should be injected
+    pub fn should_fire(&self) -> bool {
+        if !self.is_armed() {
+            return false;
+        }
+        let mut state = self.state.lock();
+        let Some(trigger) = state.trigger else {
+            return false;
+        };
+        let hit = self.hits.fetch_add(1, Ordering::SeqCst) + 1;
+        let fire = match trigger {
+            FaultTrigger::Always => true,
+            FaultTrigger::EveryNth(n) => n > 0 && hit % n == 0,
+            FaultTrigger::OnceAt(n) => hit == n,
+            FaultTrigger::Random { ppm, .. } => splitmix64(&mut state.rng) % 1_000_000 < ppm as u64,
+        };
+        if fire {
+            self.fired.fetch_add(1, Ordering::SeqCst);
+        }
+        fire
+    }
+
+    /// Registers a hit and returns the delay to inject if the fault fires
+    pub fn delay(&self) -> Option<Duration> {
+        self.should_fire().then(|| self.state.lock().delay)
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Fails store writes and deletions with an injected store error
+pub static STORE_WRITE: FaultPoint = FaultPoint::new("store-write");
+
+/// Delays the routing of incoming P2P messages to their flows
+pub static P2P_INCOMING_DELAY: FaultPoint = FaultPoint::new("p2p-incoming-delay");
+
+/// Drops notifications about to be sent to a listener connection
+pub static NOTIFICATION_DROP: FaultPoint = FaultPoint::new("notification-drop");
+
+/// Disarms all the fault points
+pub fn disarm_all() {
+    [&STORE_WRITE, &P2P_INCOMING_DELAY, &NOTIFICATION_DROP].into_iter().for_each(|point| point.disarm());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_triggers() {
+        let point = FaultPoint::new("test");
+        assert!(!point.should_fire());
+        assert_eq!(point.hits(), 0);
+
+        point.arm(FaultTrigger::EveryNth(3));
+        let fired = (0..9).filter(|_| point.should_fire()).count();
+        assert_eq!(fired, 3);
+        assert_eq!(point.hits(), 9);
+
+        point.arm(FaultTrigger::OnceAt(2));
+        assert_eq!((0..5).map(|_| point.should_fire()).collect::<Vec<_>>(), vec![false, true, false, false, false]);
+
+        point.arm_with_delay(FaultTrigger::Always, Duration::from_millis(5));
+        assert_eq!(point.delay(), Some(Duration::from_millis(5)));
+
+        // The same seed must yield the same firing sequence
+        let sample = |point: &FaultPoint| {
+            point.arm(FaultTrigger::Random { ppm: 500_000, seed: 42 });
+            (0..64).map(|_| point.should_fire()).collect::<Vec<_>>()
+        };
+        let first = sample(&point);
+        assert_eq!(first, sample(&point));
+        assert!(first.iter().any(|x| *x) && first.iter().any(|x| !*x));
+
+        point.disarm();
+        assert!(!point.should_fire());
+    }
+}
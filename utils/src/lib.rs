@@ -192,3 +192,6 @@ pub mod triggers;
 pub mod vec;
 
 pub mod fd_budget;
+
+#[cfg(feature = "fault-injection")]
+pub mod fault;
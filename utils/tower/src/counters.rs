@@ -1,8 +1,123 @@
-use std::sync::atomic::AtomicUsize;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 #[derive(Default, Clone, Debug)]
 pub struct TowerConnectionCounters {
     pub bytes_tx: Arc<AtomicUsize>,
     pub bytes_rx: Arc<AtomicUsize>,
 }
+
+/// Upper bounds, in milliseconds, of the buckets of the request latency histograms
+pub const LATENCY_BUCKETS_MS: [u64; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 10_000];
+
+/// Request counters of a single RPC method
+#[derive(Default, Debug)]
+pub struct MethodCounters {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    latency_sum_us: AtomicU64,
+    /// Number of requests per latency bucket, the last one counting the requests slower than every bound
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl MethodCounters {
+    /// Records a request answered in `latency`, either successfully or with an error
+    pub fn record(&self, latency: Duration, is_error: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if is_error {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.latency_sum_us.fetch_add(latency.as_micros().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let bucket = LATENCY_BUCKETS_MS.iter().position(|&bound| latency_ms <= bound as f64).unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> MethodCountersSnapshot {
+        let mut cumulative = 0;
+        let latency_buckets = LATENCY_BUCKETS_MS
+            .iter()
+            .zip(self.latency_buckets.iter())
+            .map(|(&bound, count)| {
+                cumulative += count.load(Ordering::Relaxed);
+                (bound, cumulative)
+            })
+            .collect();
+        MethodCountersSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            latency_sum_us: self.latency_sum_us.load(Ordering::Relaxed),
+            latency_buckets,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MethodCountersSnapshot {
+    pub requests: u64,
+    pub errors: u64,
+    /// Sum of the request latencies, in microseconds
+    pub latency_sum_us: u64,
+    /// Cumulative latency histogram as pairs of a bucket upper bound in milliseconds and the number of requests
+    /// answered within it, the requests slower than the last bound being only counted in `requests`
+    pub latency_buckets: Vec<(u64, u64)>,
+}
+
+/// Request counters of the RPC methods served by a server, keyed by method name
+#[derive(Default, Debug)]
+pub struct TowerRequestCounters {
+    methods: RwLock<HashMap<String, Arc<MethodCounters>>>,
+}
+
+impl TowerRequestCounters {
+    /// Returns the counters of `method`, registering them on first use
+    pub fn method(&self, method: &str) -> Arc<MethodCounters> {
+        if let Some(counters) = self.methods.read().unwrap().get(method) {
+            return counters.clone();
+        }
+        self.methods.write().unwrap().entry(method.to_string()).or_default().clone()
+    }
+
+    /// Returns the counters of every method having served some request, sorted by method name
+    pub fn snapshot(&self) -> Vec<(String, MethodCountersSnapshot)> {
+        let mut snapshot = self
+            .methods
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(method, counters)| (method.clone(), counters.snapshot()))
+            .filter(|(_, snapshot)| snapshot.requests > 0)
+            .collect::<Vec<_>>();
+        snapshot.sort_by(|(a, _), (b, _)| a.cmp(b));
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_counters() {
+        let counters = TowerRequestCounters::default();
+        let get_info = counters.method("GetInfo");
+        get_info.record(Duration::from_micros(500), false);
+        get_info.record(Duration::from_millis(30), true);
+        get_info.record(Duration::from_secs(60), false);
+        let _ = counters.method("GetBlock");
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        let (method, get_info) = &snapshot[0];
+        assert_eq!(method, "GetInfo");
+        assert_eq!(get_info.requests, 3);
+        assert_eq!(get_info.errors, 1);
+        assert_eq!(get_info.latency_sum_us, 60_030_500);
+        assert_eq!(get_info.latency_buckets[0], (1, 1));
+        assert_eq!(get_info.latency_buckets[4], (25, 1));
+        assert_eq!(get_info.latency_buckets[5], (50, 2));
+        assert_eq!(*get_info.latency_buckets.last().unwrap(), (10_000, 2));
+    }
+}
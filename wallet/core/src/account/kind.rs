@@ -29,6 +29,15 @@ impl AccountKind {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Derivation scheme of the keys of accounts of this kind, `None` if they are not derived from a master key
+    pub fn derivation_scheme(&self) -> Option<DerivationScheme> {
+        match self.as_str() {
+            LEGACY_ACCOUNT_KIND => Some(DerivationScheme::Legacy),
+            BIP32_ACCOUNT_KIND | MULTISIG_ACCOUNT_KIND => Some(DerivationScheme::Bip44),
+            _ => None,
+        }
+    }
 }
 
 impl AsRef<str> for AccountKind {
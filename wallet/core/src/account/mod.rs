@@ -9,19 +9,17 @@ pub mod variants;
 pub use kind::*;
 pub use variants::*;
 
-use crate::derivation::build_derivate_paths;
 use crate::derivation::AddressDerivationManagerTrait;
 use crate::imports::*;
 use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{CoinSelection, Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
 use crate::utxo::UtxoContextBinding;
-use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey};
+use kaspa_bip32::{AddressType, ExtendedPrivateKey, PrivateKey};
 use kaspa_consensus_client::UtxoEntryReference;
-use kaspa_wallet_keys::derivation::gen0::WalletDerivationManagerV0;
 use workflow_core::abortable::Abortable;
 
 /// Notification callback type used by [`Account::sweep`] and [`Account::send`].
@@ -321,6 +319,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        coin_selection: CoinSelection,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
@@ -329,7 +328,8 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
 
-        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?
+            .with_coin_selection(coin_selection);
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -629,31 +629,14 @@ pub(crate) fn create_private_keys<'l>(
     receive: &[(&'l Address, u32)],
     change: &[(&'l Address, u32)],
 ) -> Result<Vec<(&'l Address, secp256k1::SecretKey)>> {
-    let paths = build_derivate_paths(account_kind, account_index, cosigner_index)?;
+    let scheme = account_kind.derivation_scheme().ok_or(Error::InvalidAccountKind)?;
+    let is_multisig = *account_kind == MULTISIG_ACCOUNT_KIND;
+    let cosigner_index = is_multisig.then_some(cosigner_index);
     let mut private_keys = vec![];
-    if matches!(account_kind.as_ref(), LEGACY_ACCOUNT_KIND) {
-        let (private_key, attrs) = WalletDerivationManagerV0::derive_key_by_path(xkey, paths.0)?;
-        for (address, index) in receive.iter() {
-            let (private_key, _) =
-                WalletDerivationManagerV0::derive_private_key(&private_key, &attrs, ChildNumber::new(*index, true)?)?;
-            private_keys.push((*address, private_key));
-        }
-        let (private_key, attrs) = WalletDerivationManagerV0::derive_key_by_path(xkey, paths.1)?;
-        for (address, index) in change.iter() {
-            let (private_key, _) =
-                WalletDerivationManagerV0::derive_private_key(&private_key, &attrs, ChildNumber::new(*index, true)?)?;
-            private_keys.push((*address, private_key));
-        }
-    } else {
-        let receive_xkey = xkey.clone().derive_path(&paths.0)?;
-        let change_xkey = xkey.clone().derive_path(&paths.1)?;
-
-        for (address, index) in receive.iter() {
-            private_keys.push((*address, *receive_xkey.derive_child(ChildNumber::new(*index, false)?)?.private_key()));
-        }
-        for (address, index) in change.iter() {
-            private_keys.push((*address, *change_xkey.derive_child(ChildNumber::new(*index, false)?)?.private_key()));
-        }
+    for (addresses, address_type) in [(receive, AddressType::Receive), (change, AddressType::Change)] {
+        let indexes = addresses.iter().map(|(_, index)| *index).collect::<Vec<_>>();
+        let keys = scheme.derive_private_keys(xkey, is_multisig, account_index, cosigner_index, address_type, &indexes)?;
+        private_keys.extend(addresses.iter().map(|(address, _)| *address).zip(keys));
     }
 
     Ok(private_keys)
@@ -6,7 +6,7 @@
 //!
 
 use crate::imports::*;
-use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::tx::{CoinSelection, Fees, GeneratorSummary, PaymentDestination};
 use kaspa_addresses::Address;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -451,6 +451,8 @@ pub struct AccountsSendRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    #[serde(default)]
+    pub coin_selection: CoinSelection,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
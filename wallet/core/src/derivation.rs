@@ -511,11 +511,8 @@ pub async fn create_xpub_from_mnemonic(
     let seed = mnemonic.to_seed("");
     let xkey = ExtendedPrivateKey::<secp256k1::SecretKey>::new(seed)?;
 
-    let (secret_key, attrs) = match account_kind.as_ref() {
-        LEGACY_ACCOUNT_KIND => WalletDerivationManagerV0::derive_extended_key_from_master_key(xkey, false, account_index)?,
-        MULTISIG_ACCOUNT_KIND => WalletDerivationManager::derive_extended_key_from_master_key(xkey, true, account_index)?,
-        _ => WalletDerivationManager::derive_extended_key_from_master_key(xkey, false, account_index)?,
-    };
+    let scheme = account_kind.derivation_scheme().unwrap_or(DerivationScheme::Bip44);
+    let (secret_key, attrs) = scheme.derive_account_key(xkey, account_kind == MULTISIG_ACCOUNT_KIND, account_index)?;
 
     let xkey = ExtendedPublicKey { public_key: secret_key.get_public_key(), attrs };
 
@@ -527,12 +524,10 @@ pub async fn create_xpub_from_xprv(
     account_kind: AccountKind,
     account_index: u64,
 ) -> Result<ExtendedPublicKey<secp256k1::PublicKey>> {
-    let (secret_key, attrs) = match account_kind.as_ref() {
-        LEGACY_ACCOUNT_KIND => WalletDerivationManagerV0::derive_extended_key_from_master_key(xprv, false, account_index)?,
-        MULTISIG_ACCOUNT_KIND => WalletDerivationManager::derive_extended_key_from_master_key(xprv, true, account_index)?,
-        BIP32_ACCOUNT_KIND => WalletDerivationManager::derive_extended_key_from_master_key(xprv, false, account_index)?,
-        _ => panic!("create_xpub_from_xprv not supported for account kind: {:?}", account_kind),
+    let Some(scheme) = account_kind.derivation_scheme() else {
+        panic!("create_xpub_from_xprv not supported for account kind: {:?}", account_kind);
     };
+    let (secret_key, attrs) = scheme.derive_account_key(xprv, account_kind == MULTISIG_ACCOUNT_KIND, account_index)?;
 
     let xkey = ExtendedPublicKey { public_key: secret_key.get_public_key(), attrs };
 
@@ -545,16 +540,11 @@ pub fn build_derivate_path(
     cosigner_index: u32,
     address_type: AddressType,
 ) -> Result<DerivationPath> {
-    match account_kind.as_ref() {
-        LEGACY_ACCOUNT_KIND => Ok(WalletDerivationManagerV0::build_derivate_path(account_index, Some(address_type))?),
-        BIP32_ACCOUNT_KIND => Ok(WalletDerivationManager::build_derivate_path(false, account_index, None, Some(address_type))?),
-        MULTISIG_ACCOUNT_KIND => {
-            Ok(WalletDerivationManager::build_derivate_path(true, account_index, Some(cosigner_index), Some(address_type))?)
-        }
-        _ => {
-            panic!("build derivate path not supported for account kind: {:?}", account_kind);
-        }
-    }
+    let Some(scheme) = account_kind.derivation_scheme() else {
+        panic!("build derivate path not supported for account kind: {:?}", account_kind);
+    };
+    let is_multisig = *account_kind == MULTISIG_ACCOUNT_KIND;
+    Ok(scheme.build_derivate_path(is_multisig, account_index, is_multisig.then_some(cosigner_index), Some(address_type))?)
 }
 
 pub fn build_derivate_paths(
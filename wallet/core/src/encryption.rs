@@ -4,20 +4,25 @@
 
 use crate::imports::*;
 use crate::result::Result;
-use argon2::Argon2;
+use argon2::{Algorithm, Argon2, Params, Version};
 use chacha20poly1305::{
-    aead::{AeadCore, AeadInPlace, KeyInit, OsRng},
+    aead::{rand_core::RngCore, AeadCore, AeadInPlace, KeyInit, OsRng},
     Key, XChaCha20Poly1305,
 };
 use sha2::{Digest, Sha256};
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, RangeInclusive};
 use zeroize::Zeroize;
 
 /// Encryption algorithms supported by the Wallet framework.
-#[derive(Default, Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum EncryptionKind {
-    #[default]
+    /// `XChaCha20Poly1305` keyed by an `Argon2id` hash of the secret salted with its own `SHA256` hash,
+    /// so a same secret always yields a same key. Kept for the data encrypted by earlier versions.
     XChaCha20Poly1305,
+    /// `XChaCha20Poly1305` keyed by an `Argon2id` hash of the secret with a random salt, the salt and the
+    /// `Argon2id` parameters being recorded in a versioned header of the encrypted payload.
+    #[default]
+    XChaCha20Poly1305Argon2id,
 }
 
 /// Abstract data container that can contain either plain or encrypted data and
@@ -67,9 +72,8 @@ where
     pub fn encrypt(&self, secret: &Secret, encryption_kind: EncryptionKind) -> Result<Encrypted> {
         match self {
             Self::Plain(v) => Ok(Decrypted::new(v.clone()).encrypt(secret, encryption_kind)?),
-            Self::XChaCha20Poly1305(v) => match encryption_kind {
-                EncryptionKind::XChaCha20Poly1305 => Ok(v.clone()),
-            },
+            Self::XChaCha20Poly1305(v) if v.kind() == encryption_kind => Ok(v.clone()),
+            Self::XChaCha20Poly1305(v) => v.decrypt::<T>(secret)?.encrypt(secret, encryption_kind),
         }
     }
 
@@ -146,10 +150,12 @@ where
     }
 
     pub fn encrypt(&self, secret: &Secret, encryption_kind: EncryptionKind) -> Result<Encrypted> {
-        let bytes = self.0.try_to_vec()?;
+        let mut bytes = self.0.try_to_vec()?;
         let encrypted = match encryption_kind {
             EncryptionKind::XChaCha20Poly1305 => encrypt_xchacha20poly1305(bytes.as_slice(), secret)?,
+            EncryptionKind::XChaCha20Poly1305Argon2id => encrypt_xchacha20poly1305_argon2id(bytes.as_slice(), secret)?,
         };
+        bytes.zeroize();
         Ok(Encrypted::new(encryption_kind, encrypted))
     }
 
@@ -183,6 +189,7 @@ impl Encrypted {
     }
 
     pub fn replace(&mut self, from: Encrypted) {
+        self.encryption_kind = from.encryption_kind;
         self.payload = from.payload;
     }
 
@@ -194,12 +201,11 @@ impl Encrypted {
     where
         T: BorshSerialize + BorshDeserialize,
     {
-        match self.encryption_kind {
-            EncryptionKind::XChaCha20Poly1305 => {
-                let decrypted = decrypt_xchacha20poly1305(&self.payload, secret)?;
-                Ok(Decrypted(T::try_from_slice(decrypted.as_ref())?))
-            }
-        }
+        let decrypted = match self.encryption_kind {
+            EncryptionKind::XChaCha20Poly1305 => decrypt_xchacha20poly1305(&self.payload, secret)?,
+            EncryptionKind::XChaCha20Poly1305Argon2id => decrypt_xchacha20poly1305_argon2id(&self.payload, secret)?,
+        };
+        Ok(Decrypted(T::try_from_slice(decrypted.as_ref())?))
     }
 }
 
@@ -251,6 +257,80 @@ pub fn decrypt_xchacha20poly1305(data: &[u8], secret: &Secret) -> Result<Secret>
     Ok(Secret::new(buffer))
 }
 
+/// Version of the header of the payloads encrypted with [`EncryptionKind::XChaCha20Poly1305Argon2id`]
+const ARGON2ID_HEADER_VERSION: u8 = 1;
+const ARGON2ID_SALT_LENGTH: usize = 16;
+/// Version byte, `m_cost`, `t_cost` and `p_cost` as little-endian `u32` values, and salt
+const ARGON2ID_HEADER_LENGTH: usize = 1 + 3 * 4 + ARGON2ID_SALT_LENGTH;
+/// `Argon2id` memory cost (in KiB), time cost and parallelism accepted when decrypting, so a crafted header
+/// cannot exhaust the memory or stall the wallet
+const ARGON2ID_M_COST_RANGE: RangeInclusive<u32> = Params::MIN_M_COST..=1024 * 1024;
+const ARGON2ID_T_COST_RANGE: RangeInclusive<u32> = Params::MIN_T_COST..=16;
+const ARGON2ID_P_COST_RANGE: RangeInclusive<u32> = Params::MIN_P_COST..=16;
+const XCHACHA20POLY1305_NONCE_LENGTH: usize = 24;
+
+/// Derives a 32 bytes key from `secret` using `Argon2id` with the given salt and parameters.
+pub fn argon2id_hash(secret: &[u8], salt: &[u8], m_cost: u32, t_cost: u32, p_cost: u32) -> Result<Secret> {
+    let params = Params::new(m_cost, t_cost, p_cost, Some(32))?;
+    let mut key = vec![0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(secret, salt, &mut key)?;
+    Ok(key.into())
+}
+
+/// Encrypts the given data using `XChaCha20Poly1305` algorithm keyed by a salted `Argon2id` hash of the secret.
+///
+/// The output starts with a header made of a format version, the `Argon2id` costs and a random salt, followed
+/// by the nonce and the ciphertext. The header is authenticated along with the ciphertext.
+pub fn encrypt_xchacha20poly1305_argon2id(data: &[u8], secret: &Secret) -> Result<Vec<u8>> {
+    let mut salt = [0u8; ARGON2ID_SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let (m_cost, t_cost, p_cost) = (Params::DEFAULT_M_COST, Params::DEFAULT_T_COST, Params::DEFAULT_P_COST);
+    let private_key_bytes = argon2id_hash(secret.as_ref(), &salt, m_cost, t_cost, p_cost)?;
+    let mut header = Vec::with_capacity(ARGON2ID_HEADER_LENGTH + XCHACHA20POLY1305_NONCE_LENGTH + data.len() + 16);
+    header.push(ARGON2ID_HEADER_VERSION);
+    [m_cost, t_cost, p_cost].iter().for_each(|cost| header.extend_from_slice(&cost.to_le_bytes()));
+    header.extend_from_slice(&salt);
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(private_key_bytes.as_ref()));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let mut buffer = data.to_vec();
+    buffer.reserve(16);
+    cipher.encrypt_in_place(&nonce, &header, &mut buffer)?;
+    header.extend_from_slice(&nonce);
+    header.append(&mut buffer);
+    Ok(header)
+}
+
+/// Decrypts the given data using `XChaCha20Poly1305` algorithm keyed by a salted `Argon2id` hash of the secret.
+pub fn decrypt_xchacha20poly1305_argon2id(data: &[u8], secret: &Secret) -> Result<Secret> {
+    if data.len() < ARGON2ID_HEADER_LENGTH + XCHACHA20POLY1305_NONCE_LENGTH {
+        return Err(Error::InvalidEncryptedData("truncated payload".to_string()));
+    }
+    let (header, data) = data.split_at(ARGON2ID_HEADER_LENGTH);
+    if header[0] != ARGON2ID_HEADER_VERSION {
+        return Err(Error::InvalidEncryptedData(format!("unsupported version {}", header[0])));
+    }
+    let cost = |i: usize| u32::from_le_bytes(header[1 + i * 4..5 + i * 4].try_into().unwrap());
+    let (m_cost, t_cost, p_cost) = (cost(0), cost(1), cost(2));
+    for (name, cost, range) in [
+        ("memory", m_cost, ARGON2ID_M_COST_RANGE),
+        ("time", t_cost, ARGON2ID_T_COST_RANGE),
+        ("parallelism", p_cost, ARGON2ID_P_COST_RANGE),
+    ] {
+        if !range.contains(&cost) {
+            return Err(Error::InvalidEncryptedData(format!("argon2 {name} cost {cost} is out of the {range:?} range")));
+        }
+    }
+    let salt = &header[ARGON2ID_HEADER_LENGTH - ARGON2ID_SALT_LENGTH..];
+    let private_key_bytes = argon2id_hash(secret.as_ref(), salt, m_cost, t_cost, p_cost)?;
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(private_key_bytes.as_ref()));
+    let (nonce, data) = data.split_at(XCHACHA20POLY1305_NONCE_LENGTH);
+    let mut buffer = data.to_vec();
+    cipher.decrypt_in_place(nonce.into(), header, &mut buffer)?;
+    Ok(Secret::new(buffer))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,4 +361,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_wallet_encrypt_decrypt_argon2id() -> Result<()> {
+        let password = Secret::new(b"password".to_vec());
+        let original = b"hello world".to_vec();
+        let encrypted = encrypt_xchacha20poly1305_argon2id(&original, &password)?;
+        let decrypted = decrypt_xchacha20poly1305_argon2id(&encrypted, &password)?;
+        assert_eq!(decrypted.as_ref(), original);
+
+        // Every encryption uses a fresh salt, so a same secret yields distinct keys
+        let encrypted_again = encrypt_xchacha20poly1305_argon2id(&original, &password)?;
+        assert_ne!(encrypted[1..ARGON2ID_HEADER_LENGTH], encrypted_again[1..ARGON2ID_HEADER_LENGTH]);
+
+        // A wrong secret, a tampered header and an unknown version are all rejected
+        assert!(decrypt_xchacha20poly1305_argon2id(&encrypted, &Secret::new(b"wrong".to_vec())).is_err());
+        let mut tampered = encrypted.clone();
+        tampered[ARGON2ID_HEADER_LENGTH - 1] ^= 1;
+        assert!(decrypt_xchacha20poly1305_argon2id(&tampered, &password).is_err());
+        let mut tampered = encrypted.clone();
+        tampered[0] = ARGON2ID_HEADER_VERSION + 1;
+        assert!(matches!(decrypt_xchacha20poly1305_argon2id(&tampered, &password), Err(Error::InvalidEncryptedData(_))));
+        assert!(decrypt_xchacha20poly1305_argon2id(&encrypted[..ARGON2ID_HEADER_LENGTH], &password).is_err());
+
+        // Costs out of the accepted ranges are rejected before any key derivation
+        for (i, cost) in [(0, ARGON2ID_M_COST_RANGE.end() + 1), (0, 0), (1, ARGON2ID_T_COST_RANGE.end() + 1), (1, 0), (2, u32::MAX)] {
+            let mut tampered = encrypted.clone();
+            tampered[1 + i * 4..5 + i * 4].copy_from_slice(&cost.to_le_bytes());
+            assert!(matches!(decrypt_xchacha20poly1305_argon2id(&tampered, &password), Err(Error::InvalidEncryptedData(_))));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wallet_encryptable_kind_upgrade() -> Result<()> {
+        let secret = Secret::new(b"password".to_vec());
+        let legacy = Encryptable::Plain(vec![1u8, 2, 3]).into_encrypted(&secret, EncryptionKind::XChaCha20Poly1305)?;
+        let Encryptable::XChaCha20Poly1305(encrypted) = &legacy else { panic!("expected encrypted data") };
+        assert_eq!(encrypted.kind(), EncryptionKind::XChaCha20Poly1305);
+
+        // Re-encrypting with another kind migrates the data
+        let upgraded = legacy.encrypt(&secret, EncryptionKind::XChaCha20Poly1305Argon2id)?;
+        assert_eq!(upgraded.kind(), EncryptionKind::XChaCha20Poly1305Argon2id);
+        assert_eq!(*upgraded.decrypt::<Vec<u8>>(&secret)?, vec![1u8, 2, 3]);
+
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@
 //! Error types used by the wallet framework.
 //!
 
-use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, PrvKeyDataId};
+use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, DerivationScheme, PrvKeyDataId};
 use base64::DecodeError;
 use downcast::DowncastError;
 use kaspa_bip32::Error as BIP32Error;
@@ -144,6 +144,9 @@ pub enum Error {
     #[error("Unable to decrypt this wallet")]
     WalletDecrypt(chacha20poly1305::Error),
 
+    #[error("Invalid encrypted data: {0}")]
+    InvalidEncryptedData(String),
+
     #[error(transparent)]
     FromUtf8Error(#[from] std::string::FromUtf8Error),
 
@@ -204,6 +207,9 @@ pub enum Error {
     #[error("Invalid account type (must be one of: bip32|multisig|legacy")]
     InvalidAccountKind,
 
+    #[error("Derivation scheme {scheme:?} does not match the account type {kind}")]
+    DerivationSchemeMismatch { kind: AccountKind, scheme: Option<DerivationScheme> },
+
     #[error("Insufficient funds")]
     InsufficientFunds { additional_needed: u64, origin: &'static str },
 
@@ -54,6 +54,7 @@ pub(crate) async fn try_load_account(
     meta: Option<Arc<AccountMetadata>>,
 ) -> Result<Arc<dyn Account>> {
     let factory = factories().get(&storage.kind).ok_or_else(|| Error::AccountFactoryNotFound(storage.kind))?;
+    storage.try_derivation_scheme()?;
 
     factory.try_load(wallet, &storage, meta).await
 }
@@ -13,7 +13,7 @@ pub use crate::metrics::{MetricsUpdate, MetricsUpdateKind};
 pub use crate::rpc::{ConnectOptions, ConnectStrategy, DynRpcApi};
 pub use crate::settings::WalletSettings;
 pub use crate::storage::{IdT, Interface, PrvKeyDataId, PrvKeyDataInfo, TransactionId, TransactionRecord, WalletDescriptor};
-pub use crate::tx::{Fees, PaymentDestination, PaymentOutput, PaymentOutputs};
+pub use crate::tx::{CoinSelection, Fees, PaymentDestination, PaymentOutput, PaymentOutputs};
 pub use crate::utxo::balance::{Balance, BalanceStrings};
 pub use crate::wallet::args::*;
 pub use crate::wallet::Wallet;
@@ -46,11 +46,14 @@ pub struct AccountStorage {
     pub prv_key_data_ids: AssocPrvKeyDataIds,
     pub settings: AccountSettings,
     pub serialized: Vec<u8>,
+    /// Derivation scheme of the account keys, `None` for accounts not derived from a master key.
+    /// Accounts stored before the scheme was recorded are tagged with the scheme of their kind.
+    pub derivation_scheme: Option<DerivationScheme>,
 }
 
 impl AccountStorage {
     const STORAGE_MAGIC: u32 = 0x4153414b;
-    const STORAGE_VERSION: u32 = 0;
+    const STORAGE_VERSION: u32 = 1;
 
     pub fn try_new<A>(
         kind: AccountKind,
@@ -63,7 +66,15 @@ impl AccountStorage {
     where
         A: AccountStorable,
     {
-        Ok(Self { id: *id, storage_key: *storage_key, kind, prv_key_data_ids, settings, serialized: serialized.try_to_vec()? })
+        Ok(Self {
+            id: *id,
+            storage_key: *storage_key,
+            kind,
+            prv_key_data_ids,
+            settings,
+            serialized: serialized.try_to_vec()?,
+            derivation_scheme: kind.derivation_scheme(),
+        })
     }
 
     pub fn id(&self) -> &AccountId {
@@ -77,6 +88,15 @@ impl AccountStorage {
     pub fn serialized(&self) -> &[u8] {
         &self.serialized
     }
+
+    /// Ensures the recorded derivation scheme is the one the account kind derives its addresses with,
+    /// since deriving them with another scheme would not find the account funds
+    pub fn try_derivation_scheme(&self) -> Result<Option<DerivationScheme>> {
+        if self.derivation_scheme != self.kind.derivation_scheme() {
+            return Err(Error::DerivationSchemeMismatch { kind: self.kind, scheme: self.derivation_scheme });
+        }
+        Ok(self.derivation_scheme)
+    }
 }
 
 impl std::fmt::Debug for AccountStorage {
@@ -88,6 +108,7 @@ impl std::fmt::Debug for AccountStorage {
             .field("prv_key_data_ids", &self.prv_key_data_ids)
             .field("settings", &self.settings)
             .field("serialized", &self.serialized.to_hex())
+            .field("derivation_scheme", &self.derivation_scheme)
             .finish()
     }
 }
@@ -101,6 +122,7 @@ impl BorshSerialize for AccountStorage {
         BorshSerialize::serialize(&self.prv_key_data_ids, writer)?;
         BorshSerialize::serialize(&self.settings, writer)?;
         BorshSerialize::serialize(&self.serialized, writer)?;
+        BorshSerialize::serialize(&self.derivation_scheme, writer)?;
 
         Ok(())
     }
@@ -108,17 +130,18 @@ impl BorshSerialize for AccountStorage {
 
 impl BorshDeserialize for AccountStorage {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } =
+        let StorageHeader { version, .. } =
             StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
 
-        let kind = BorshDeserialize::deserialize(buf)?;
+        let kind: AccountKind = BorshDeserialize::deserialize(buf)?;
         let id = BorshDeserialize::deserialize(buf)?;
         let storage_key = BorshDeserialize::deserialize(buf)?;
         let prv_key_data_ids = BorshDeserialize::deserialize(buf)?;
         let settings = BorshDeserialize::deserialize(buf)?;
         let serialized = BorshDeserialize::deserialize(buf)?;
+        let derivation_scheme = if version > 0 { BorshDeserialize::deserialize(buf)? } else { kind.derivation_scheme() };
 
-        Ok(Self { kind, id, storage_key, prv_key_data_ids, settings, serialized })
+        Ok(Self { kind, id, storage_key, prv_key_data_ids, settings, serialized, derivation_scheme })
     }
 }
 
@@ -147,6 +170,36 @@ mod tests {
         assert_eq!(storable_in.id, storable_out.id);
         assert_eq!(storable_in.storage_key, storable_out.storage_key);
         assert_eq!(storable_in.serialized, storable_out.serialized);
+        assert_eq!(storable_out.derivation_scheme, Some(DerivationScheme::Bip44));
+        assert!(storable_out.try_derivation_scheme().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_account_storage_derivation_scheme() -> Result<()> {
+        let (id, storage_key) = make_account_hashes(from_data(&LEGACY_ACCOUNT_KIND.into(), &[0x00, 0x01, 0x02, 0x03]));
+        let mut storable_in = AccountStorage::try_new(
+            LEGACY_ACCOUNT_KIND.into(),
+            &id,
+            &storage_key,
+            PrvKeyDataId::new(0xcafe).into(),
+            AccountSettings::default(),
+            legacy::Payload,
+        )?;
+        assert_eq!(storable_in.derivation_scheme, Some(DerivationScheme::Legacy));
+
+        // Accounts stored by the previous version are tagged with the scheme of their kind
+        let mut data = storable_in.try_to_vec()?;
+        data.truncate(data.len() - storable_in.derivation_scheme.try_to_vec()?.len());
+        data[4..8].copy_from_slice(&0u32.to_le_bytes());
+        let storable_out = AccountStorage::try_from_slice(&data)?;
+        assert_eq!(storable_out.derivation_scheme, Some(DerivationScheme::Legacy));
+
+        // An account tagged with a scheme its kind does not derive with is rejected
+        storable_in.derivation_scheme = Some(DerivationScheme::Bip44);
+        let storable_out = AccountStorage::try_from_slice(&storable_in.try_to_vec()?)?;
+        assert!(storable_out.try_derivation_scheme().is_err());
 
         Ok(())
     }
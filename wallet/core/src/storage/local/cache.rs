@@ -79,4 +79,12 @@ impl Cache {
             transactions,
         })
     }
+
+    /// Re-encrypts the private key data with `new_secret` using `encryption_kind`
+    pub fn change_secret(&mut self, old_secret: &Secret, new_secret: &Secret, encryption_kind: EncryptionKind) -> Result<()> {
+        let old_prv_key_data: Decrypted<PrvKeyDataMap> = self.prv_key_data.decrypt(old_secret)?;
+        self.prv_key_data = Decrypted::new(old_prv_key_data.unwrap()).encrypt(new_secret, encryption_kind)?;
+        self.encryption_kind = encryption_kind;
+        Ok(())
+    }
 }
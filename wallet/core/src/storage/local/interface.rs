@@ -151,23 +151,18 @@ impl LocalStoreInner {
         }
     }
 
+    /// Re-encrypts the wallet with `new_secret`, migrating it to the default encryption kind
+    /// so changing the secret also rotates wallets encrypted with a legacy kind.
     async fn change_secret(&self, old_secret: &Secret, new_secret: &Secret) -> Result<()> {
         match &*self.storage() {
             Store::Resident => {
-                let mut cache = self.cache.write().unwrap();
-                let old_prv_key_data: Decrypted<PrvKeyDataMap> = cache.prv_key_data.decrypt(old_secret)?;
-                let new_prv_key_data = Decrypted::new(old_prv_key_data.unwrap()).encrypt(new_secret, cache.encryption_kind)?;
-                cache.prv_key_data.replace(new_prv_key_data);
-
+                self.cache.write().unwrap().change_secret(old_secret, new_secret, EncryptionKind::default())?;
                 Ok(())
             }
             Store::Storage(ref storage) => {
                 let wallet = {
                     let mut cache = self.cache.write().unwrap();
-                    let old_prv_key_data: Decrypted<PrvKeyDataMap> = cache.prv_key_data.decrypt(old_secret)?;
-                    let new_prv_key_data = Decrypted::new(old_prv_key_data.unwrap()).encrypt(new_secret, cache.encryption_kind)?;
-                    cache.prv_key_data.replace(new_prv_key_data);
-
+                    cache.change_secret(old_secret, new_secret, EncryptionKind::default())?;
                     cache.to_wallet(None, new_secret)?
                 };
                 wallet.try_store(storage).await?;
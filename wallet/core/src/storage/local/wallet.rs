@@ -25,7 +25,8 @@ pub struct WalletStorage {
 
 impl WalletStorage {
     pub const STORAGE_MAGIC: u32 = 0x5753414b;
-    pub const STORAGE_VERSION: u32 = 0;
+    /// Version 1 introduced the [`EncryptionKind::XChaCha20Poly1305Argon2id`] encryption kind
+    pub const STORAGE_VERSION: u32 = 1;
 
     pub fn try_new(
         title: Option<String>,
@@ -140,7 +141,7 @@ mod tests {
             Some("title".to_string()),
             Some(Hint::new("hint".to_string())),
             &Secret::from("secret"),
-            EncryptionKind::XChaCha20Poly1305,
+            EncryptionKind::default(),
             Payload::new(vec![], vec![], vec![]),
             vec![],
         )?;
@@ -92,6 +92,7 @@ impl RpcApi for RpcCoreMock {
             is_synced: false,
             has_notify_command: false,
             has_message_id: false,
+            build_info: Default::default(),
         })
     }
 
@@ -151,6 +152,10 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn submit_transactions_call(&self, _request: SubmitTransactionsRequest) -> RpcResult<SubmitTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -245,6 +250,126 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_mass_call(&self, _request: GetTransactionMassRequest) -> RpcResult<GetTransactionMassResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn build_unsigned_transaction_call(
+        &self,
+        _request: BuildUnsignedTransactionRequest,
+    ) -> RpcResult<BuildUnsignedTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_dag_stats_call(&self, _request: GetDagStatsRequest) -> RpcResult<GetDagStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_health_call(&self, _request: GetHealthRequest) -> RpcResult<GetHealthResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_subscriptions_call(&self, _request: GetSubscriptionsRequest) -> RpcResult<GetSubscriptionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_virtual_parents_call(&self, _request: GetVirtualParentsRequest) -> RpcResult<GetVirtualParentsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_consensus_params_call(&self, _request: GetConsensusParamsRequest) -> RpcResult<GetConsensusParamsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_by_id_call(&self, _request: GetTransactionByIdRequest) -> RpcResult<GetTransactionByIdResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn sample_chain_blocks_call(&self, _request: SampleChainBlocksRequest) -> RpcResult<SampleChainBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_connections_call(&self, _request: GetConnectionsRequest) -> RpcResult<GetConnectionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_by_blue_score_call(&self, _request: GetBlockByBlueScoreRequest) -> RpcResult<GetBlockByBlueScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_by_daa_score_call(&self, _request: GetBlockByDaaScoreRequest) -> RpcResult<GetBlockByDaaScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_block_by_timestamp_call(
+        &self,
+        _request: GetChainBlockByTimestampRequest,
+    ) -> RpcResult<GetChainBlockByTimestampResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_pruning_point_utxos_call(&self, _request: GetPruningPointUtxosRequest) -> RpcResult<GetPruningPointUtxosResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_info_call(&self, _request: GetMempoolInfoRequest) -> RpcResult<GetMempoolInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_mempool_policy_call(&self, _request: SetMempoolPolicyRequest) -> RpcResult<SetMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_current_block_color_call(&self, _request: GetCurrentBlockColorRequest) -> RpcResult<GetCurrentBlockColorResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_bans_call(&self, _request: GetBansRequest) -> RpcResult<GetBansResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_processing_status_call(
+        &self,
+        _request: GetBlockProcessingStatusRequest,
+    ) -> RpcResult<GetBlockProcessingStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn start_job_call(&self, _request: StartJobRequest) -> RpcResult<StartJobResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_job_status_call(&self, _request: GetJobStatusRequest) -> RpcResult<GetJobStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_notification_stats_call(&self, _request: GetNotificationStatsRequest) -> RpcResult<GetNotificationStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn generate_blocks_call(&self, _request: GenerateBlocksRequest) -> RpcResult<GenerateBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_server_capabilities_call(&self, _request: GetServerCapabilitiesRequest) -> RpcResult<GetServerCapabilitiesResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn update_block_template_exclusions_call(
+        &self,
+        _request: UpdateBlockTemplateExclusionsRequest,
+    ) -> RpcResult<UpdateBlockTemplateExclusionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_chain_quality_call(&self, _request: GetChainQualityRequest) -> RpcResult<GetChainQualityResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
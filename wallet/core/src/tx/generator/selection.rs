@@ -0,0 +1,321 @@
+//!
+//! Coin selection algorithms ordering the UTXO entries
+//! consumed by the [`Generator`](crate::tx::Generator).
+//!
+
+use crate::imports::*;
+use crate::tx::{Fees, MassCalculator, PaymentDestination, MINIMUM_RELAY_TRANSACTION_FEE};
+use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::{TransactionInput, TransactionOutpoint, TransactionOutput};
+use kaspa_txscript::pay_to_address_script;
+use std::str::FromStr;
+
+/// Maximum number of branches explored by the branch and bound search
+const BNB_MAX_TRIES: usize = 100_000;
+
+/// Strategy choosing the UTXO entries funding a transaction.
+///
+/// The generator consumes the entries in the order produced by the strategy, so when the
+/// preferred entries fall short of the fees actually required, the remaining entries follow
+/// them as a fallback.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CoinSelection {
+    /// Entries are consumed in the UTXO context order, smallest first
+    #[default]
+    Sequential,
+    /// Largest entries first, minimizing the number of inputs
+    LargestFirst,
+    /// Branch and bound search of an input set matching the payment without a change output,
+    /// falling back to largest first
+    BranchAndBound,
+    /// Entries of as few addresses as possible, avoiding to link the addresses of the account
+    Privacy,
+}
+
+impl CoinSelection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoinSelection::Sequential => "sequential",
+            CoinSelection::LargestFirst => "largest-first",
+            CoinSelection::BranchAndBound => "branch-and-bound",
+            CoinSelection::Privacy => "privacy",
+        }
+    }
+}
+
+impl std::fmt::Display for CoinSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CoinSelection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sequential" => Ok(CoinSelection::Sequential),
+            "largest-first" | "largest" => Ok(CoinSelection::LargestFirst),
+            "branch-and-bound" | "bnb" => Ok(CoinSelection::BranchAndBound),
+            "privacy" => Ok(CoinSelection::Privacy),
+            _ => Err(Error::custom(format!(
+                "unknown coin selection '{s}', expected one of sequential, largest-first, branch-and-bound, privacy"
+            ))),
+        }
+    }
+}
+
+/// Fee of `mass` grams at the minimum relay fee rate of the node policy
+fn fee_for_mass(mass: u64) -> u64 {
+    mass * MINIMUM_RELAY_TRANSACTION_FEE / 1000
+}
+
+/// Scores the UTXO entries by their effective value, which is their amount minus the
+/// fee paid for the mass of the input spending them, and orders them by [`CoinSelection`].
+pub struct CoinSelector {
+    mass_calculator: MassCalculator,
+    /// Fee of a signed standard input
+    input_fee: u64,
+    /// Fee of a change output
+    change_output_fee: u64,
+}
+
+impl CoinSelector {
+    pub fn new(network_id: NetworkId, sig_op_count: u8, minimum_signatures: u16, change_address: &Address) -> Self {
+        let mass_calculator = MassCalculator::new(&network_id.into(), &NetworkParams::from(network_id));
+        let input = TransactionInput::new(TransactionOutpoint::default(), vec![], 0, sig_op_count);
+        let input_mass = mass_calculator.calc_mass_for_input(&input) + mass_calculator.calc_signature_mass(minimum_signatures);
+        let change_output = TransactionOutput::new(0, pay_to_address_script(change_address));
+        let change_output_mass = mass_calculator.calc_mass_for_output(&change_output);
+        Self { mass_calculator, input_fee: fee_for_mass(input_mass), change_output_fee: fee_for_mass(change_output_mass) }
+    }
+
+    pub fn input_fee(&self) -> u64 {
+        self.input_fee
+    }
+
+    /// Amount of the entry left once the fee of its input is paid, `None` if spending it is uneconomical
+    pub fn effective_value(&self, entry: &UtxoEntryReference) -> Option<u64> {
+        entry.amount().checked_sub(self.input_fee).filter(|value| *value > 0)
+    }
+
+    /// Cost of creating a change output and spending it later, the excess an exact match is allowed to give away as fees
+    pub fn cost_of_change(&self) -> u64 {
+        self.change_output_fee + self.input_fee
+    }
+
+    /// Effective value the inputs of a payment must cover: the outputs, the priority fee and the fee of the
+    /// transaction body. Returns `None` for sweep transactions, which consume all entries.
+    pub fn target(&self, destination: &PaymentDestination, priority_fee: &Fees, payload_len: usize) -> Option<u64> {
+        let PaymentDestination::PaymentOutputs(outputs) = destination else {
+            return None;
+        };
+        let amount = outputs.amount();
+        if !priority_fee.sender_pays() {
+            return Some(amount);
+        }
+        let outputs = outputs
+            .iter()
+            .map(|output| TransactionOutput::new(output.amount, pay_to_address_script(&output.address)))
+            .collect::<Vec<_>>();
+        let body_mass = self.mass_calculator.blank_transaction_mass()
+            + self.mass_calculator.calc_mass_for_outputs(&outputs)
+            + self.mass_calculator.calc_mass_for_payload(payload_len);
+        Some(amount + priority_fee.additional() + fee_for_mass(body_mass))
+    }
+
+    /// Orders `entries` according to `coin_selection` for a payment requiring `target` effective value
+    pub fn select(
+        &self,
+        coin_selection: CoinSelection,
+        entries: Vec<UtxoEntryReference>,
+        target: Option<u64>,
+    ) -> Vec<UtxoEntryReference> {
+        let Some(target) = target else {
+            return entries;
+        };
+        match coin_selection {
+            CoinSelection::Sequential => entries,
+            CoinSelection::LargestFirst => self.largest_first(entries),
+            CoinSelection::BranchAndBound => self.branch_and_bound(entries, target),
+            CoinSelection::Privacy => self.privacy(entries, target),
+        }
+    }
+
+    /// Economical entries by descending amount
+    fn largest_first(&self, entries: Vec<UtxoEntryReference>) -> Vec<UtxoEntryReference> {
+        let mut entries = entries.into_iter().filter(|entry| self.effective_value(entry).is_some()).collect::<Vec<_>>();
+        entries.sort_by(|a, b| b.amount().cmp(&a.amount()));
+        entries
+    }
+
+    /// The input set with the lowest excess over `target` not exceeding the cost of change, followed by the other entries
+    fn branch_and_bound(&self, entries: Vec<UtxoEntryReference>, target: u64) -> Vec<UtxoEntryReference> {
+        let entries = self.largest_first(entries);
+        let values = entries.iter().map(|entry| self.effective_value(entry).unwrap()).collect::<Vec<_>>();
+        let Some(selection) = branch_and_bound(&values, target, self.cost_of_change()) else {
+            return entries;
+        };
+        let (mut selected, others): (Vec<_>, Vec<_>) =
+            entries.into_iter().enumerate().partition(|(index, _)| selection.binary_search(index).is_ok());
+        selected.extend(others);
+        selected.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Entries grouped by address, the smallest group covering `target` first or else the fewest
+    /// groups covering it, so that the transaction reveals as few addresses of the account as possible
+    fn privacy(&self, entries: Vec<UtxoEntryReference>, target: u64) -> Vec<UtxoEntryReference> {
+        let mut groups: Vec<(Option<Address>, u64, Vec<UtxoEntryReference>)> = vec![];
+        for entry in self.largest_first(entries) {
+            let value = self.effective_value(&entry).unwrap();
+            let address = entry.utxo.address.clone();
+            match groups.iter_mut().find(|(group_address, _, _)| *group_address == address) {
+                Some((_, total, group)) => {
+                    *total += value;
+                    group.push(entry);
+                }
+                None => groups.push((address, value, vec![entry])),
+            }
+        }
+
+        // Spending a group requires a change output unless it happens to match the target
+        let required = target + self.change_output_fee;
+        groups.sort_by(|(_, a, _), (_, b, _)| b.cmp(a));
+        if let Some(position) = groups.iter().rposition(|(_, total, _)| *total >= required) {
+            let group = groups.remove(position);
+            groups.insert(0, group);
+        }
+        groups.into_iter().flat_map(|(_, _, group)| group).collect()
+    }
+}
+
+/// Depth first search over the inclusion or omission of `values`, sorted by descending value, for the
+/// subset summing within `[target, target + cost_of_change]` with the lowest excess. Returns the sorted
+/// indexes of the subset.
+fn branch_and_bound(values: &[u64], target: u64, cost_of_change: u64) -> Option<Vec<usize>> {
+    let upper_bound = target + cost_of_change;
+    let mut remaining: u64 = values.iter().sum();
+    let mut current = 0;
+    let mut selection: Vec<usize> = vec![];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut index = 0;
+
+    for _ in 0..BNB_MAX_TRIES {
+        let backtrack = if current + remaining < target || current > upper_bound {
+            true
+        } else if current >= target {
+            let excess = current - target;
+            if best.as_ref().map_or(true, |(best_excess, _)| excess < *best_excess) {
+                best = Some((excess, selection.clone()));
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            // Switch the last included value to its omission branch
+            let Some(last) = selection.pop() else {
+                break;
+            };
+            remaining += values[last + 1..index].iter().sum::<u64>();
+            current -= values[last];
+            index = last + 1;
+            if best.as_ref().is_some_and(|(excess, _)| *excess == 0) {
+                break;
+            }
+        } else {
+            remaining -= values[index];
+            current += values[index];
+            selection.push(index);
+            index += 1;
+        }
+    }
+
+    best.map(|(_, selection)| selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::PaymentOutputs;
+    use kaspa_addresses::{Prefix, Version};
+    use kaspa_consensus_core::network::NetworkType;
+
+    fn address(byte: u8) -> Address {
+        Address::new(Prefix::Testnet, Version::PubKey, &[byte; 32])
+    }
+
+    fn selector() -> CoinSelector {
+        CoinSelector::new(NetworkId::with_suffix(NetworkType::Testnet, 11), 1, 1, &address(0))
+    }
+
+    fn amounts(entries: &[UtxoEntryReference]) -> Vec<u64> {
+        entries.iter().map(|entry| entry.amount()).collect()
+    }
+
+    #[test]
+    fn test_branch_and_bound_search() {
+        assert_eq!(branch_and_bound(&[10, 7, 5, 3], 8, 0), Some(vec![2, 3]));
+        assert_eq!(branch_and_bound(&[10, 7, 5, 3], 12, 1), Some(vec![1, 2]));
+        assert_eq!(branch_and_bound(&[10, 7, 5, 3], 9, 1), Some(vec![0]));
+        assert_eq!(branch_and_bound(&[10, 7, 5, 3], 26, 100), None);
+        assert_eq!(branch_and_bound(&[10, 7], 4, 1), None);
+    }
+
+    #[test]
+    fn test_coin_selection() {
+        let selector = selector();
+        let fee = selector.input_fee();
+        assert!(fee > 0);
+        let entries = [0, 5_000, 3_000, 2_000, 9_000]
+            .iter()
+            .map(|amount| UtxoEntryReference::simulated(amount * 1000 + fee))
+            .collect::<Vec<_>>();
+
+        // Uneconomical entries are skipped
+        let largest_first = selector.select(CoinSelection::LargestFirst, entries.clone(), Some(1));
+        assert_eq!(amounts(&largest_first), [9_000_000 + fee, 5_000_000 + fee, 3_000_000 + fee, 2_000_000 + fee]);
+
+        // The exact match comes first, the other entries remain available to the generator
+        let bnb = selector.select(CoinSelection::BranchAndBound, entries.clone(), Some(7_000_000));
+        assert_eq!(amounts(&bnb), [5_000_000 + fee, 2_000_000 + fee, 9_000_000 + fee, 3_000_000 + fee]);
+
+        // Sweeps and the sequential selection keep the original order
+        assert_eq!(amounts(&selector.select(CoinSelection::BranchAndBound, entries.clone(), None)), amounts(&entries));
+        assert_eq!(amounts(&selector.select(CoinSelection::Sequential, entries.clone(), Some(1))), amounts(&entries));
+    }
+
+    #[test]
+    fn test_privacy_coin_selection() {
+        let selector = selector();
+        let (a, b, c) = (address(1), address(2), address(3));
+        let entries = [(&a, 4_000_000), (&b, 3_000_000), (&c, 1_000_000), (&b, 3_000_000), (&a, 1_000_000)]
+            .into_iter()
+            .map(|(address, amount)| UtxoEntryReference::simulated_with_address(amount, address))
+            .collect::<Vec<_>>();
+        let addresses = |entries: Vec<UtxoEntryReference>| {
+            entries.into_iter().map(|entry| entry.utxo.address.clone().unwrap()).collect::<Vec<_>>()
+        };
+
+        // The smallest single address covering the target is spent first
+        let selection = selector.select(CoinSelection::Privacy, entries.clone(), Some(4_500_000));
+        assert_eq!(addresses(selection), [a.clone(), a.clone(), b.clone(), b.clone(), c.clone()]);
+
+        // Without any, the addresses holding the most are spent first
+        let selection = selector.select(CoinSelection::Privacy, entries, Some(9_000_000));
+        assert_eq!(addresses(selection), [b.clone(), b, a.clone(), a, c]);
+    }
+
+    #[test]
+    fn test_coin_selection_target() {
+        let selector = selector();
+        let outputs: PaymentDestination = PaymentOutputs::from((address(1), 1_000_000)).into();
+        assert_eq!(selector.target(&PaymentDestination::Change, &Fees::None, 0), None);
+        assert_eq!(selector.target(&outputs, &Fees::ReceiverPays(0), 0), Some(1_000_000));
+        let target = selector.target(&outputs, &Fees::SenderPays(500), 0).unwrap();
+        assert!(target > 1_000_500);
+        assert!(selector.target(&outputs, &Fees::SenderPays(500), 100).unwrap() > target);
+    }
+}
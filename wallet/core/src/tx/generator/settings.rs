@@ -6,7 +6,7 @@
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{Fees, PaymentDestination};
+use crate::tx::{CoinSelection, CoinSelector, Fees, PaymentDestination};
 use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
 use workflow_core::channel::Multiplexer;
@@ -151,4 +151,19 @@ impl GeneratorSettings {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Reorders the UTXO entries of the iterator according to `coin_selection`
+    pub fn with_coin_selection(mut self, coin_selection: CoinSelection) -> Self {
+        if coin_selection != CoinSelection::Sequential {
+            let selector = CoinSelector::new(self.network_id, self.sig_op_count, self.minimum_signatures, &self.change_address);
+            let target = selector.target(
+                &self.final_transaction_destination,
+                &self.final_transaction_priority_fee,
+                self.final_transaction_payload.as_ref().map_or(0, |payload| payload.len()),
+            );
+            let entries = std::mem::replace(&mut self.utxo_iterator, Box::new(std::iter::empty())).collect();
+            self.utxo_iterator = Box::new(selector.select(coin_selection, entries, target).into_iter());
+        }
+        self
+    }
 }
@@ -17,6 +17,12 @@ struct Inner {
     keys: Mutex<AHashMap<Address, [u8; 32]>>,
 }
 
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.keys.lock().unwrap().values_mut().for_each(|key| key.zeroize());
+    }
+}
+
 pub struct Signer {
     inner: Arc<Inner>,
 }
@@ -34,8 +40,9 @@ impl Signer {
             let account = self.inner.account.clone().as_derivation_capable().expect("expecting derivation capable account");
             let (receive, change) = account.derivation().addresses_indexes(&addresses)?;
             let private_keys = account.create_private_keys(&self.inner.keydata, &self.inner.payment_secret, &receive, &change)?;
-            for (address, private_key) in private_keys {
+            for (address, mut private_key) in private_keys {
                 keys.insert(address.clone(), private_key.to_bytes());
+                private_key.non_secure_erase();
             }
         }
 
@@ -62,13 +69,26 @@ struct KeydataSignerInner {
     keys: HashMap<Address, [u8; 32]>,
 }
 
+impl Drop for KeydataSignerInner {
+    fn drop(&mut self) {
+        self.keys.values_mut().for_each(|key| key.zeroize());
+    }
+}
+
 pub struct KeydataSigner {
     inner: Arc<KeydataSignerInner>,
 }
 
 impl KeydataSigner {
     pub fn new(keydata: Vec<(Address, secp256k1::SecretKey)>) -> Self {
-        let keys = keydata.into_iter().map(|(address, key)| (address, key.to_bytes())).collect();
+        let keys = keydata
+            .into_iter()
+            .map(|(address, mut key)| {
+                let bytes = key.to_bytes();
+                key.non_secure_erase();
+                (address, bytes)
+            })
+            .collect();
         Self { inner: Arc::new(KeydataSignerInner { keys }) }
     }
 }
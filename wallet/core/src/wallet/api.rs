@@ -343,13 +343,22 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse> {
-        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, priority_fee_sompi, payload } = request;
+        let AccountsSendRequest {
+            account_id,
+            wallet_secret,
+            payment_secret,
+            destination,
+            priority_fee_sompi,
+            payload,
+            coin_selection,
+        } = request;
 
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
 
         let abortable = Abortable::new();
-        let (generator_summary, transaction_ids) =
-            account.send(destination, priority_fee_sompi, payload, wallet_secret, payment_secret, &abortable, None).await?;
+        let (generator_summary, transaction_ids) = account
+            .send(destination, priority_fee_sompi, payload, coin_selection, wallet_secret, payment_secret, &abortable, None)
+            .await?;
 
         Ok(AccountsSendResponse { generator_summary, transaction_ids })
     }
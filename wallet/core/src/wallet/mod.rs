@@ -1453,8 +1453,7 @@ impl Wallet {
         let mnemonic = Mnemonic::new(bip39_mnemonic, Language::English)?;
 
         // TODO @aspect - this is not efficient, we need to scan without encrypting prv_key_data
-        let prv_key_data =
-            storage::PrvKeyData::try_new_from_mnemonic(mnemonic, bip39_passphrase.as_ref(), EncryptionKind::XChaCha20Poly1305)?;
+        let prv_key_data = storage::PrvKeyData::try_new_from_mnemonic(mnemonic, bip39_passphrase.as_ref(), EncryptionKind::default())?;
 
         let mut last_account_index = 0;
         let mut account_index = 0;
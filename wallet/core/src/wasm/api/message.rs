@@ -1373,6 +1373,10 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Coin selection algorithm choosing the UTXO entries to spend, "sequential" by default.
+         */
+        coinSelection? : "sequential" | "largest-first" | "branch-and-bound" | "privacy";
     }
     "#,
 }
@@ -1388,7 +1392,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload })
+    let coin_selection = args.try_get_string("coinSelection")?.map(|s| s.parse()).transpose()?.unwrap_or_default();
+
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload, coin_selection })
 });
 
 declare! {
@@ -1,3 +1,4 @@
 pub mod gen0;
 pub mod gen1;
+pub mod scheme;
 pub mod traits;
@@ -0,0 +1,185 @@
+//!
+//! Derivation schemes, telling how the keys of an account are derived from the master key.
+//!
+
+use crate::derivation::gen0::WalletDerivationManagerV0;
+use crate::derivation::gen1::WalletDerivationManager;
+use crate::derivation::traits::WalletDerivationManagerTrait;
+use crate::imports::*;
+use kaspa_bip32::{AddressType, DerivationPath, ExtendedKeyAttrs};
+
+/// Derivation scheme of an account, recorded along the account in the keystore.
+///
+/// Addresses derived with a scheme other than the one the wallet was created with
+/// do not hold its funds, so the scheme must be preserved when migrating wallets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DerivationScheme {
+    /// Legacy kaspanet wallets (KDX, kaspanet.io web wallet): `m/44'/972/<account>'/<address type>'/<index>'`.
+    /// The address keys are hardened, so they can only be derived from the private key.
+    Legacy,
+    /// Standard `m/44'/111111'/<account>'/<address type>/<index>` or, for multisig accounts,
+    /// `m/45'/111111'/<account>'/<cosigner>/<address type>/<index>`.
+    Bip44,
+}
+
+impl DerivationScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DerivationScheme::Legacy => "legacy",
+            DerivationScheme::Bip44 => "bip44",
+        }
+    }
+
+    /// Whether the address public keys can be derived from the account extended public key
+    pub fn is_public_derivation_capable(&self) -> bool {
+        matches!(self, DerivationScheme::Bip44)
+    }
+
+    fn check_multisig(&self, is_multisig: bool) -> Result<()> {
+        if is_multisig && *self == DerivationScheme::Legacy {
+            return Err(Error::Custom("the legacy derivation scheme does not support multisig accounts".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Derives the account key, up to `m/<purpose>'/<coin type>'/<account index>'`, from the master key
+    pub fn derive_account_key(
+        &self,
+        xprv: ExtendedPrivateKey<SecretKey>,
+        is_multisig: bool,
+        account_index: u64,
+    ) -> Result<(SecretKey, ExtendedKeyAttrs)> {
+        self.check_multisig(is_multisig)?;
+        match self {
+            DerivationScheme::Legacy => WalletDerivationManagerV0::derive_extended_key_from_master_key(xprv, false, account_index),
+            DerivationScheme::Bip44 => WalletDerivationManager::derive_extended_key_from_master_key(xprv, is_multisig, account_index),
+        }
+    }
+
+    /// Path of the account key or, given an address type, of the parent key of its addresses
+    pub fn build_derivate_path(
+        &self,
+        is_multisig: bool,
+        account_index: u64,
+        cosigner_index: Option<u32>,
+        address_type: Option<AddressType>,
+    ) -> Result<DerivationPath> {
+        self.check_multisig(is_multisig)?;
+        match self {
+            DerivationScheme::Legacy => WalletDerivationManagerV0::build_derivate_path(account_index, address_type),
+            DerivationScheme::Bip44 => {
+                WalletDerivationManager::build_derivate_path(is_multisig, account_index, cosigner_index, address_type)
+            }
+        }
+    }
+
+    /// Derives the private keys of the addresses at `indexes` of the given type
+    pub fn derive_private_keys(
+        &self,
+        xprv: &ExtendedPrivateKey<SecretKey>,
+        is_multisig: bool,
+        account_index: u64,
+        cosigner_index: Option<u32>,
+        address_type: AddressType,
+        indexes: &[u32],
+    ) -> Result<Vec<SecretKey>> {
+        let path = self.build_derivate_path(is_multisig, account_index, cosigner_index, Some(address_type))?;
+        match self {
+            DerivationScheme::Legacy => {
+                let (private_key, attrs) = WalletDerivationManagerV0::derive_key_by_path(xprv, path)?;
+                indexes
+                    .iter()
+                    .map(|index| {
+                        Ok(WalletDerivationManagerV0::derive_private_key(&private_key, &attrs, ChildNumber::new(*index, true)?)?.0)
+                    })
+                    .collect()
+            }
+            DerivationScheme::Bip44 => {
+                let xkey = xprv.clone().derive_path(&path)?;
+                indexes.iter().map(|index| Ok(*xkey.derive_child(ChildNumber::new(*index, false)?)?.private_key())).collect()
+            }
+        }
+    }
+
+    /// Creates the address derivation manager of an account from its extended public key
+    pub fn create_derivation_manager(
+        &self,
+        xpub: ExtendedPublicKey<secp256k1::PublicKey>,
+        cosigner_index: Option<u32>,
+    ) -> Result<Arc<dyn WalletDerivationManagerTrait>> {
+        match self {
+            DerivationScheme::Legacy => {
+                Err(Error::Custom("the legacy derivation scheme cannot derive addresses from a public key".to_string()))
+            }
+            DerivationScheme::Bip44 => Ok(Arc::new(WalletDerivationManager::from_extended_public_key(xpub, cosigner_index)?)),
+        }
+    }
+}
+
+impl std::fmt::Display for DerivationScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DerivationScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "legacy" => Ok(DerivationScheme::Legacy),
+            "bip44" => Ok(DerivationScheme::Bip44),
+            _ => Err(Error::Custom(format!("invalid derivation scheme '{s}' (must be one of: legacy|bip44)"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_bip32::SecretKeyExt;
+
+    const MASTER_XPRV: &str =
+        "kprv5y2qurMHCsXYrNfU3GCihuwG3vMqFji7PZXajMEqyBkNh9UZUJgoHYBLTKu1eM4MvUtomcXPQ3Sw9HZ5ebbM4byoUciHo1zrPJBQfqpLorQ";
+
+    #[test]
+    fn test_derivation_scheme_private_keys() {
+        let xprv = ExtendedPrivateKey::<SecretKey>::from_str(MASTER_XPRV).unwrap();
+        let indexes = (0..5).collect::<Vec<_>>();
+        for scheme in [DerivationScheme::Legacy, DerivationScheme::Bip44] {
+            let manager: Arc<dyn WalletDerivationManagerTrait> = match scheme {
+                DerivationScheme::Legacy => {
+                    Arc::new(WalletDerivationManagerV0::from_master_xprv(MASTER_XPRV, false, 0, None).unwrap())
+                }
+                DerivationScheme::Bip44 => Arc::new(WalletDerivationManager::from_master_xprv(MASTER_XPRV, false, 0, None).unwrap()),
+            };
+            let receive = scheme.derive_private_keys(&xprv, false, 0, None, AddressType::Receive, &indexes).unwrap();
+            let change = scheme.derive_private_keys(&xprv, false, 0, None, AddressType::Change, &indexes).unwrap();
+            for index in indexes.iter() {
+                let receive_key = receive[*index as usize].get_public_key();
+                assert_eq!(manager.derive_receive_pubkey(*index).unwrap(), receive_key, "{scheme} receive key {index}");
+                let change_key = change[*index as usize].get_public_key();
+                assert_eq!(manager.derive_change_pubkey(*index).unwrap(), change_key, "{scheme} change key {index}");
+            }
+        }
+
+        // Both schemes derive distinct keys from a same master key
+        let legacy = DerivationScheme::Legacy.derive_private_keys(&xprv, false, 0, None, AddressType::Receive, &[0]).unwrap();
+        let bip44 = DerivationScheme::Bip44.derive_private_keys(&xprv, false, 0, None, AddressType::Receive, &[0]).unwrap();
+        assert_ne!(legacy, bip44);
+    }
+
+    #[test]
+    fn test_derivation_scheme_paths() {
+        let path = |scheme: DerivationScheme, is_multisig, cosigner_index| {
+            scheme.build_derivate_path(is_multisig, 1, cosigner_index, Some(AddressType::Change)).map(|path| path.to_string())
+        };
+        assert_eq!(path(DerivationScheme::Legacy, false, None).unwrap(), "m/44'/972/1'/1'");
+        assert_eq!(path(DerivationScheme::Bip44, false, None).unwrap(), "m/44'/111111'/1'/1");
+        assert_eq!(path(DerivationScheme::Bip44, true, Some(2)).unwrap(), "m/45'/111111'/1'/2/1");
+        assert!(path(DerivationScheme::Legacy, true, Some(2)).is_err());
+        assert_eq!("bip44".parse::<DerivationScheme>().unwrap(), DerivationScheme::Bip44);
+        assert!("bip32".parse::<DerivationScheme>().is_err());
+    }
+}